@@ -0,0 +1,7 @@
+ALTER TABLE lesson_contents DROP COLUMN IF EXISTS is_private;
+
+DROP TRIGGER IF EXISTS enrollments_set_updated_at ON enrollments;
+DROP FUNCTION IF EXISTS set_enrollments_updated_at();
+DROP INDEX IF EXISTS idx_enrollments_course;
+DROP INDEX IF EXISTS uq_enrollments_user_course;
+DROP TABLE IF EXISTS enrollments;
@@ -0,0 +1,31 @@
+-- Enrollments: which users may access which courses
+CREATE TABLE IF NOT EXISTS enrollments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    course_id UUID NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    status TEXT NOT NULL DEFAULT 'active' CHECK (status IN ('active', 'revoked')),
+    enrolled_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ
+);
+
+CREATE OR REPLACE FUNCTION set_enrollments_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at := NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS enrollments_set_updated_at ON enrollments;
+CREATE TRIGGER enrollments_set_updated_at
+BEFORE UPDATE ON enrollments
+FOR EACH ROW
+EXECUTE FUNCTION set_enrollments_updated_at();
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_enrollments_user_course ON enrollments (user_id, course_id);
+CREATE INDEX IF NOT EXISTS idx_enrollments_course ON enrollments (course_id);
+
+-- Private lesson contents live outside the public /uploads tree; `url` then
+-- holds the storage key and downloads go through signed, expiring URLs.
+ALTER TABLE lesson_contents ADD COLUMN IF NOT EXISTS is_private BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,5 @@
+DROP TRIGGER IF EXISTS lesson_videos_set_updated_at ON lesson_videos;
+DROP FUNCTION IF EXISTS set_lesson_videos_updated_at();
+DROP INDEX IF EXISTS idx_lesson_videos_status;
+DROP INDEX IF EXISTS uq_lesson_videos_provider_video;
+DROP TABLE IF EXISTS lesson_videos;
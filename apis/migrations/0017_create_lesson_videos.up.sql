@@ -0,0 +1,27 @@
+-- Lesson videos: provider-hosted video for a lesson and its processing state
+CREATE TABLE IF NOT EXISTS lesson_videos (
+    lesson_id UUID PRIMARY KEY REFERENCES lessons(id) ON DELETE CASCADE,
+    provider TEXT NOT NULL,
+    provider_video_id TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'uploading' CHECK (status IN ('uploading', 'processing', 'ready', 'failed')),
+    embed_url TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ
+);
+
+CREATE OR REPLACE FUNCTION set_lesson_videos_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at := NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS lesson_videos_set_updated_at ON lesson_videos;
+CREATE TRIGGER lesson_videos_set_updated_at
+BEFORE UPDATE ON lesson_videos
+FOR EACH ROW
+EXECUTE FUNCTION set_lesson_videos_updated_at();
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_lesson_videos_provider_video ON lesson_videos (provider, provider_video_id);
+CREATE INDEX IF NOT EXISTS idx_lesson_videos_status ON lesson_videos (status);
@@ -0,0 +1,5 @@
+DROP TRIGGER IF EXISTS lesson_subtitles_set_updated_at ON lesson_subtitles;
+DROP FUNCTION IF EXISTS set_lesson_subtitles_updated_at();
+DROP INDEX IF EXISTS idx_lesson_subtitles_transcript_tsv;
+DROP INDEX IF EXISTS uq_lesson_subtitles_lesson_language;
+DROP TABLE IF EXISTS lesson_subtitles;
@@ -0,0 +1,31 @@
+-- Lesson subtitles: one caption track per lesson and language, stored as WebVTT
+CREATE TABLE IF NOT EXISTS lesson_subtitles (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    lesson_id UUID NOT NULL REFERENCES lessons(id) ON DELETE CASCADE,
+    language TEXT NOT NULL,
+    label TEXT,
+    vtt TEXT NOT NULL,
+    transcript TEXT NOT NULL DEFAULT '',
+    cue_count INTEGER NOT NULL DEFAULT 0 CHECK (cue_count >= 0),
+    -- Transcript terms for course search
+    transcript_tsv TSVECTOR GENERATED ALWAYS AS (to_tsvector('simple', transcript)) STORED,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ
+);
+
+CREATE OR REPLACE FUNCTION set_lesson_subtitles_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at := NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS lesson_subtitles_set_updated_at ON lesson_subtitles;
+CREATE TRIGGER lesson_subtitles_set_updated_at
+BEFORE UPDATE ON lesson_subtitles
+FOR EACH ROW
+EXECUTE FUNCTION set_lesson_subtitles_updated_at();
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_lesson_subtitles_lesson_language ON lesson_subtitles (lesson_id, language);
+CREATE INDEX IF NOT EXISTS idx_lesson_subtitles_transcript_tsv ON lesson_subtitles USING GIN (transcript_tsv);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_courses_tags;
+DROP INDEX IF EXISTS idx_courses_catalogue_price;
+DROP INDEX IF EXISTS idx_courses_catalogue_newest;
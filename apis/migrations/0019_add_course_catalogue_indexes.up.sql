@@ -0,0 +1,8 @@
+-- Indexes backing the public course catalogue (published courses only)
+CREATE INDEX IF NOT EXISTS idx_courses_catalogue_newest
+    ON courses (published_at DESC NULLS LAST, created_at DESC)
+    WHERE status = 'published';
+CREATE INDEX IF NOT EXISTS idx_courses_catalogue_price
+    ON courses (price)
+    WHERE status = 'published';
+CREATE INDEX IF NOT EXISTS idx_courses_tags ON courses USING GIN (tags);
@@ -0,0 +1,21 @@
+DROP INDEX IF EXISTS idx_blog_posts_title_trgm;
+DROP INDEX IF EXISTS idx_lessons_title_trgm;
+DROP INDEX IF EXISTS idx_courses_title_trgm;
+DROP INDEX IF EXISTS idx_blog_posts_search_tsv;
+DROP INDEX IF EXISTS idx_lessons_search_tsv;
+DROP INDEX IF EXISTS idx_courses_search_tsv;
+
+DROP TRIGGER IF EXISTS blog_posts_set_search_tsv ON blog_posts;
+DROP FUNCTION IF EXISTS set_blog_posts_search_tsv();
+ALTER TABLE blog_posts DROP COLUMN IF EXISTS search_tsv;
+DROP FUNCTION IF EXISTS blog_posts_search_document(TEXT, TEXT, TEXT);
+
+DROP TRIGGER IF EXISTS lessons_set_search_tsv ON lessons;
+DROP FUNCTION IF EXISTS set_lessons_search_tsv();
+ALTER TABLE lessons DROP COLUMN IF EXISTS search_tsv;
+DROP FUNCTION IF EXISTS lessons_search_document(TEXT, TEXT, TEXT);
+
+DROP TRIGGER IF EXISTS courses_set_search_tsv ON courses;
+DROP FUNCTION IF EXISTS set_courses_search_tsv();
+ALTER TABLE courses DROP COLUMN IF EXISTS search_tsv;
+DROP FUNCTION IF EXISTS courses_search_document(TEXT, TEXT, TEXT, TEXT[], TEXT);
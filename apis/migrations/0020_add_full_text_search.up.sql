@@ -0,0 +1,106 @@
+-- Full-text search over courses, lessons and blog posts.
+-- Each table gets a weighted tsvector (A = title, B = summary, C = body) kept
+-- current by a BEFORE trigger, plus trigram indexes on titles for the
+-- typo-tolerant fallback used when the full-text query matches nothing.
+
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- ----- Courses -----
+CREATE OR REPLACE FUNCTION courses_search_document(
+    title TEXT, excerpt TEXT, description TEXT, tags TEXT[], category TEXT
+) RETURNS TSVECTOR AS $$
+    SELECT setweight(to_tsvector('english', coalesce(title, '')), 'A')
+        || setweight(to_tsvector('english', coalesce(excerpt, '') || ' '
+            || coalesce(array_to_string(tags, ' '), '') || ' ' || coalesce(category, '')), 'B')
+        || setweight(to_tsvector('english', coalesce(description, '')), 'C');
+$$ LANGUAGE sql IMMUTABLE;
+
+ALTER TABLE courses ADD COLUMN IF NOT EXISTS search_tsv TSVECTOR;
+
+CREATE OR REPLACE FUNCTION set_courses_search_tsv()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.search_tsv := courses_search_document(
+        NEW.title, NEW.excerpt, NEW.description, NEW.tags, NEW.category
+    );
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS courses_set_search_tsv ON courses;
+CREATE TRIGGER courses_set_search_tsv
+BEFORE INSERT OR UPDATE OF title, excerpt, description, tags, category ON courses
+FOR EACH ROW
+EXECUTE FUNCTION set_courses_search_tsv();
+
+-- ----- Lessons -----
+CREATE OR REPLACE FUNCTION lessons_search_document(
+    title TEXT, description TEXT, content TEXT
+) RETURNS TSVECTOR AS $$
+    SELECT setweight(to_tsvector('english', coalesce(title, '')), 'A')
+        || setweight(to_tsvector('english', coalesce(description, '')), 'B')
+        || setweight(to_tsvector('english', coalesce(content, '')), 'C');
+$$ LANGUAGE sql IMMUTABLE;
+
+ALTER TABLE lessons ADD COLUMN IF NOT EXISTS search_tsv TSVECTOR;
+
+CREATE OR REPLACE FUNCTION set_lessons_search_tsv()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.search_tsv := lessons_search_document(NEW.title, NEW.description, NEW.content);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS lessons_set_search_tsv ON lessons;
+CREATE TRIGGER lessons_set_search_tsv
+BEFORE INSERT OR UPDATE OF title, description, content ON lessons
+FOR EACH ROW
+EXECUTE FUNCTION set_lessons_search_tsv();
+
+-- ----- Blog posts -----
+CREATE OR REPLACE FUNCTION blog_posts_search_document(
+    title TEXT, excerpt TEXT, content TEXT
+) RETURNS TSVECTOR AS $$
+    SELECT setweight(to_tsvector('english', coalesce(title, '')), 'A')
+        || setweight(to_tsvector('english', coalesce(excerpt, '')), 'B')
+        || setweight(to_tsvector('english', coalesce(content, '')), 'C');
+$$ LANGUAGE sql IMMUTABLE;
+
+ALTER TABLE blog_posts ADD COLUMN IF NOT EXISTS search_tsv TSVECTOR;
+
+CREATE OR REPLACE FUNCTION set_blog_posts_search_tsv()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.search_tsv := blog_posts_search_document(NEW.title, NEW.excerpt, NEW.content);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS blog_posts_set_search_tsv ON blog_posts;
+CREATE TRIGGER blog_posts_set_search_tsv
+BEFORE INSERT OR UPDATE OF title, excerpt, content ON blog_posts
+FOR EACH ROW
+EXECUTE FUNCTION set_blog_posts_search_tsv();
+
+-- Backfill existing rows without touching updated_at
+ALTER TABLE courses DISABLE TRIGGER courses_set_updated_at;
+UPDATE courses
+SET search_tsv = courses_search_document(title, excerpt, description, tags, category);
+ALTER TABLE courses ENABLE TRIGGER courses_set_updated_at;
+
+ALTER TABLE lessons DISABLE TRIGGER lessons_set_updated_at;
+UPDATE lessons SET search_tsv = lessons_search_document(title, description, content);
+ALTER TABLE lessons ENABLE TRIGGER lessons_set_updated_at;
+
+ALTER TABLE blog_posts DISABLE TRIGGER blog_posts_set_updated_at;
+UPDATE blog_posts SET search_tsv = blog_posts_search_document(title, excerpt, content);
+ALTER TABLE blog_posts ENABLE TRIGGER blog_posts_set_updated_at;
+
+-- Indexes
+CREATE INDEX IF NOT EXISTS idx_courses_search_tsv ON courses USING GIN (search_tsv);
+CREATE INDEX IF NOT EXISTS idx_lessons_search_tsv ON lessons USING GIN (search_tsv);
+CREATE INDEX IF NOT EXISTS idx_blog_posts_search_tsv ON blog_posts USING GIN (search_tsv);
+CREATE INDEX IF NOT EXISTS idx_courses_title_trgm ON courses USING GIN (title gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_lessons_title_trgm ON lessons USING GIN (title gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_blog_posts_title_trgm ON blog_posts USING GIN (title gin_trgm_ops);
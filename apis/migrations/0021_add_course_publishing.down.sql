@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_courses_scheduled_publish;
+ALTER TABLE courses DROP CONSTRAINT IF EXISTS courses_status_check;
+ALTER TABLE courses DROP COLUMN IF EXISTS scheduled_publish_at;
@@ -0,0 +1,19 @@
+-- Explicit publishing workflow: draft -> published -> archived, plus scheduled publishing
+ALTER TABLE courses
+    ADD COLUMN IF NOT EXISTS scheduled_publish_at TIMESTAMPTZ;
+
+ALTER TABLE courses DROP CONSTRAINT IF EXISTS courses_status_check;
+ALTER TABLE courses
+    ADD CONSTRAINT courses_status_check CHECK (status IN ('draft', 'published', 'archived'));
+
+-- Courses published before the workflow existed never had published_at stamped
+ALTER TABLE courses DISABLE TRIGGER courses_set_updated_at;
+UPDATE courses
+SET published_at = COALESCE(updated_at, created_at)
+WHERE status = 'published' AND published_at IS NULL;
+ALTER TABLE courses ENABLE TRIGGER courses_set_updated_at;
+
+-- Polled by the scheduled publishing task
+CREATE INDEX IF NOT EXISTS idx_courses_scheduled_publish
+    ON courses (scheduled_publish_at)
+    WHERE scheduled_publish_at IS NOT NULL AND status = 'draft';
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS course_revisions;
+ALTER TABLE courses DROP COLUMN IF EXISTS content_version;
@@ -0,0 +1,25 @@
+-- Draft revisions of published courses: edits are staged here and swapped in atomically
+ALTER TABLE courses
+    ADD COLUMN IF NOT EXISTS content_version INTEGER NOT NULL DEFAULT 1;
+
+CREATE TABLE IF NOT EXISTS course_revisions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    course_id UUID NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    status TEXT NOT NULL DEFAULT 'draft' CHECK (status IN ('draft', 'published', 'discarded')),
+    -- courses.content_version the draft was copied from; publishing requires it to be unchanged
+    base_version INTEGER NOT NULL,
+    -- Course fields plus the module/lesson tree, see CourseRevisionContent
+    content JSONB NOT NULL,
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ,
+    published_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_course_revisions_course
+    ON course_revisions (course_id, created_at DESC);
+
+-- At most one open draft per course
+CREATE UNIQUE INDEX IF NOT EXISTS uq_course_revisions_open_draft
+    ON course_revisions (course_id)
+    WHERE status = 'draft';
@@ -0,0 +1,9 @@
+DROP INDEX IF EXISTS idx_courses_catalogue_rating;
+DROP TRIGGER IF EXISTS course_reviews_update_rating ON course_reviews;
+DROP FUNCTION IF EXISTS update_course_rating();
+ALTER TABLE courses
+    DROP COLUMN IF EXISTS rating_average,
+    DROP COLUMN IF EXISTS rating_count;
+DROP TABLE IF EXISTS course_review_votes;
+DROP TABLE IF EXISTS course_reviews;
+DROP FUNCTION IF EXISTS set_course_reviews_updated_at();
@@ -0,0 +1,80 @@
+-- Learner reviews of courses with instructor replies, helpful votes and moderation
+CREATE TABLE IF NOT EXISTS course_reviews (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    course_id UUID NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    rating SMALLINT NOT NULL CHECK (rating BETWEEN 1 AND 5),
+    content TEXT NOT NULL DEFAULT '',
+    -- Only approved reviews are public and count towards the course rating
+    approved BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Set when a moderator approves or rejects; NULL means the review awaits moderation
+    moderated_at TIMESTAMPTZ,
+    instructor_reply TEXT,
+    replied_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    replied_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ,
+    UNIQUE (course_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_course_reviews_course
+    ON course_reviews (course_id, created_at DESC)
+    WHERE approved;
+CREATE INDEX IF NOT EXISTS idx_course_reviews_pending
+    ON course_reviews (created_at)
+    WHERE moderated_at IS NULL;
+
+CREATE TABLE IF NOT EXISTS course_review_votes (
+    review_id UUID NOT NULL REFERENCES course_reviews(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (review_id, user_id)
+);
+
+-- Keep course_reviews.updated_at fresh on any row update
+CREATE OR REPLACE FUNCTION set_course_reviews_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at := NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS course_reviews_set_updated_at ON course_reviews;
+CREATE TRIGGER course_reviews_set_updated_at
+BEFORE UPDATE ON course_reviews
+FOR EACH ROW
+EXECUTE FUNCTION set_course_reviews_updated_at();
+
+-- Denormalised rating of approved reviews, for the catalogue
+ALTER TABLE courses
+    ADD COLUMN IF NOT EXISTS rating_average DOUBLE PRECISION NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS rating_count INTEGER NOT NULL DEFAULT 0;
+
+CREATE OR REPLACE FUNCTION update_course_rating()
+RETURNS TRIGGER AS $$
+DECLARE
+    target_course_id UUID := COALESCE(NEW.course_id, OLD.course_id);
+BEGIN
+    UPDATE courses c
+    SET rating_average = COALESCE(r.average, 0),
+        rating_count = r.total
+    FROM (
+        SELECT ROUND(AVG(rating)::numeric, 2)::float8 AS average, COUNT(*)::int AS total
+        FROM course_reviews
+        WHERE course_id = target_course_id AND approved
+    ) r
+    WHERE c.id = target_course_id;
+    RETURN COALESCE(NEW, OLD);
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS course_reviews_update_rating ON course_reviews;
+CREATE TRIGGER course_reviews_update_rating
+    AFTER INSERT OR DELETE OR UPDATE OF rating, approved ON course_reviews
+    FOR EACH ROW
+    EXECUTE FUNCTION update_course_rating();
+
+CREATE INDEX IF NOT EXISTS idx_courses_catalogue_rating
+    ON courses (rating_average DESC, rating_count DESC)
+    WHERE status = 'published';
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS certificates;
+DROP TABLE IF EXISTS quiz_attempts;
+DROP TABLE IF EXISTS lesson_completions;
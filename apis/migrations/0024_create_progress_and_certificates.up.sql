@@ -0,0 +1,42 @@
+-- Lesson completion, quiz attempts and completion certificates
+CREATE TABLE IF NOT EXISTS lesson_completions (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    lesson_id UUID NOT NULL REFERENCES lessons(id) ON DELETE CASCADE,
+    course_id UUID NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    completed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, lesson_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_lesson_completions_user_course
+    ON lesson_completions (user_id, course_id);
+
+CREATE TABLE IF NOT EXISTS quiz_attempts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    lesson_id UUID NOT NULL REFERENCES lessons(id) ON DELETE CASCADE,
+    course_id UUID NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    correct INTEGER NOT NULL,
+    total INTEGER NOT NULL,
+    -- Percentage of questions answered correctly
+    score DOUBLE PRECISION NOT NULL,
+    -- Whether the score met the pass mark in force when the attempt was graded
+    passed BOOLEAN NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_quiz_attempts_user_lesson
+    ON quiz_attempts (user_id, lesson_id, created_at DESC);
+
+-- Names are copied at issuance so a certificate reads the same forever and
+-- stays verifiable after the course is deleted
+CREATE TABLE IF NOT EXISTS certificates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    course_id UUID REFERENCES courses(id) ON DELETE SET NULL,
+    verification_code TEXT NOT NULL UNIQUE,
+    learner_name TEXT NOT NULL,
+    course_title TEXT NOT NULL,
+    instructor_name TEXT NOT NULL,
+    issued_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, course_id)
+);
@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS payment_attempts;
+DROP TABLE IF EXISTS order_items;
+DROP TRIGGER IF EXISTS orders_set_updated_at ON orders;
+DROP FUNCTION IF EXISTS set_orders_updated_at();
+DROP TABLE IF EXISTS orders;
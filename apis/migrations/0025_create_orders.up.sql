@@ -0,0 +1,58 @@
+-- Orders, their purchased courses and payment attempts with the provider.
+-- Amounts are integer minor units (cents) in the order's currency.
+CREATE TABLE IF NOT EXISTS orders (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    status TEXT NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'paid', 'failed', 'canceled', 'refunded')),
+    currency TEXT NOT NULL,
+    subtotal_cents BIGINT NOT NULL CHECK (subtotal_cents >= 0),
+    total_cents BIGINT NOT NULL CHECK (total_cents >= 0),
+    paid_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ
+);
+
+CREATE OR REPLACE FUNCTION set_orders_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at := NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS orders_set_updated_at ON orders;
+CREATE TRIGGER orders_set_updated_at
+BEFORE UPDATE ON orders
+FOR EACH ROW
+EXECUTE FUNCTION set_orders_updated_at();
+
+CREATE INDEX IF NOT EXISTS idx_orders_user ON orders (user_id, created_at DESC);
+
+-- Course titles and prices are copied so an order reads the same after edits
+CREATE TABLE IF NOT EXISTS order_items (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    order_id UUID NOT NULL REFERENCES orders(id) ON DELETE CASCADE,
+    course_id UUID REFERENCES courses(id) ON DELETE SET NULL,
+    course_title TEXT NOT NULL,
+    unit_price_cents BIGINT NOT NULL CHECK (unit_price_cents >= 0),
+    UNIQUE (order_id, course_id)
+);
+
+CREATE TABLE IF NOT EXISTS payment_attempts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    order_id UUID NOT NULL REFERENCES orders(id) ON DELETE CASCADE,
+    provider TEXT NOT NULL,
+    -- Provider-side payment intent id
+    provider_payment_id TEXT NOT NULL,
+    status TEXT NOT NULL
+        CHECK (status IN ('requires_payment', 'processing', 'succeeded', 'failed', 'canceled')),
+    amount_cents BIGINT NOT NULL,
+    currency TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ,
+    UNIQUE (provider, provider_payment_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_payment_attempts_order
+    ON payment_attempts (order_id, created_at DESC);
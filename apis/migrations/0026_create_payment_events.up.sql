@@ -0,0 +1,17 @@
+-- Provider webhook events, stored verbatim. The (provider, event_id) key makes
+-- redelivered events no-ops once processed_at is set.
+CREATE TABLE IF NOT EXISTS payment_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    provider TEXT NOT NULL,
+    event_id TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    -- Raw request body as signed by the provider
+    payload TEXT NOT NULL,
+    -- Order the event was applied to, if any
+    order_id UUID REFERENCES orders(id) ON DELETE SET NULL,
+    received_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    processed_at TIMESTAMPTZ,
+    UNIQUE (provider, event_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_payment_events_order ON payment_events (order_id);
@@ -0,0 +1,34 @@
+DROP TABLE IF EXISTS course_prices;
+
+UPDATE course_revisions
+   SET content = jsonb_set(
+           jsonb_set(
+               content,
+               '{course,price}',
+               to_jsonb(((content #>> '{course,price,amount}')::numeric / 100)::float8)
+           ),
+           '{course,original_price}',
+           CASE WHEN jsonb_typeof(content #> '{course,original_price}') = 'object'
+                THEN to_jsonb(((content #>> '{course,original_price,amount}')::numeric / 100)::float8)
+                ELSE 'null'::jsonb END
+       )
+ WHERE jsonb_typeof(content #> '{course,price}') = 'object';
+
+ALTER TABLE courses
+    ADD COLUMN IF NOT EXISTS price DOUBLE PRECISION,
+    ADD COLUMN IF NOT EXISTS original_price DOUBLE PRECISION CHECK (original_price >= 0);
+UPDATE courses
+   SET price = price_cents / 100.0,
+       original_price = original_price_cents / 100.0;
+ALTER TABLE courses
+    ALTER COLUMN price SET NOT NULL,
+    ADD CONSTRAINT courses_price_check CHECK (price >= 0);
+
+DROP INDEX IF EXISTS idx_courses_catalogue_price;
+ALTER TABLE courses
+    DROP COLUMN IF EXISTS price_cents,
+    DROP COLUMN IF EXISTS original_price_cents,
+    DROP COLUMN IF EXISTS currency;
+CREATE INDEX IF NOT EXISTS idx_courses_catalogue_price
+    ON courses (price)
+    WHERE status = 'published';
@@ -0,0 +1,56 @@
+-- Course prices as integer minor units with an ISO 4217 currency instead of
+-- floating point, plus optional prices in further currencies per course.
+ALTER TABLE courses
+    ADD COLUMN IF NOT EXISTS currency TEXT NOT NULL DEFAULT 'USD'
+        CHECK (currency ~ '^[A-Z]{3}$'),
+    ADD COLUMN IF NOT EXISTS price_cents BIGINT,
+    ADD COLUMN IF NOT EXISTS original_price_cents BIGINT CHECK (original_price_cents >= 0);
+
+UPDATE courses
+   SET price_cents = ROUND(price::numeric * 100)::bigint,
+       original_price_cents = ROUND(original_price::numeric * 100)::bigint;
+
+ALTER TABLE courses
+    ALTER COLUMN price_cents SET NOT NULL,
+    ADD CONSTRAINT courses_price_cents_check CHECK (price_cents >= 0);
+
+DROP INDEX IF EXISTS idx_courses_catalogue_price;
+ALTER TABLE courses DROP COLUMN IF EXISTS price, DROP COLUMN IF EXISTS original_price;
+CREATE INDEX IF NOT EXISTS idx_courses_catalogue_price
+    ON courses (currency, price_cents)
+    WHERE status = 'published';
+
+-- Prices in currencies other than the course's own; the course columns stay
+-- the price in its base currency
+CREATE TABLE IF NOT EXISTS course_prices (
+    course_id UUID NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    currency TEXT NOT NULL CHECK (currency ~ '^[A-Z]{3}$'),
+    price_cents BIGINT NOT NULL CHECK (price_cents >= 0),
+    original_price_cents BIGINT CHECK (original_price_cents >= 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ,
+    PRIMARY KEY (course_id, currency)
+);
+
+CREATE INDEX IF NOT EXISTS idx_course_prices_currency ON course_prices (currency, price_cents);
+
+-- Draft revisions store the course fields as JSON; convert their prices too
+UPDATE course_revisions
+   SET content = jsonb_set(
+           jsonb_set(
+               content,
+               '{course,price}',
+               jsonb_build_object(
+                   'amount', ROUND((content #>> '{course,price}')::numeric * 100)::bigint,
+                   'currency', 'USD'
+               )
+           ),
+           '{course,original_price}',
+           CASE WHEN jsonb_typeof(content #> '{course,original_price}') = 'number'
+                THEN jsonb_build_object(
+                         'amount', ROUND((content #>> '{course,original_price}')::numeric * 100)::bigint,
+                         'currency', 'USD'
+                     )
+                ELSE 'null'::jsonb END
+       )
+ WHERE jsonb_typeof(content #> '{course,price}') = 'number';
@@ -0,0 +1,7 @@
+ALTER TABLE order_items DROP COLUMN IF EXISTS discount_cents;
+ALTER TABLE orders DROP COLUMN IF EXISTS coupon_code;
+ALTER TABLE orders DROP COLUMN IF EXISTS discount_cents;
+DROP TABLE IF EXISTS coupon_redemptions;
+DROP TRIGGER IF EXISTS coupons_set_updated_at ON coupons;
+DROP FUNCTION IF EXISTS set_coupons_updated_at();
+DROP TABLE IF EXISTS coupons;
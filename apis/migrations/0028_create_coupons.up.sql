@@ -0,0 +1,69 @@
+-- Discount coupons redeemed at checkout. Codes are stored upper-case.
+-- A coupon takes either a percentage or a fixed amount (in minor units of
+-- its currency) off the courses it applies to: one course, the courses of
+-- one category, or every course when neither is set.
+CREATE TABLE IF NOT EXISTS coupons (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    code TEXT NOT NULL UNIQUE CHECK (code ~ '^[A-Z0-9_-]{3,40}$'),
+    description TEXT,
+    discount_type TEXT NOT NULL CHECK (discount_type IN ('percent', 'fixed')),
+    percent_off INTEGER CHECK (percent_off BETWEEN 1 AND 100),
+    amount_off_cents BIGINT CHECK (amount_off_cents > 0),
+    currency TEXT CHECK (currency ~ '^[A-Z]{3}$'),
+    course_id UUID REFERENCES courses(id) ON DELETE CASCADE,
+    category_id INTEGER REFERENCES categories(id) ON DELETE CASCADE,
+    starts_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ,
+    -- NULL means unlimited
+    max_redemptions INTEGER CHECK (max_redemptions > 0),
+    max_redemptions_per_user INTEGER CHECK (max_redemptions_per_user > 0),
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ,
+    CHECK (
+        (discount_type = 'percent' AND percent_off IS NOT NULL
+            AND amount_off_cents IS NULL AND currency IS NULL)
+        OR (discount_type = 'fixed' AND percent_off IS NULL
+            AND amount_off_cents IS NOT NULL AND currency IS NOT NULL)
+    ),
+    CHECK (course_id IS NULL OR category_id IS NULL),
+    CHECK (starts_at IS NULL OR expires_at IS NULL OR expires_at > starts_at)
+);
+
+CREATE OR REPLACE FUNCTION set_coupons_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at := NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS coupons_set_updated_at ON coupons;
+CREATE TRIGGER coupons_set_updated_at
+BEFORE UPDATE ON coupons
+FOR EACH ROW
+EXECUTE FUNCTION set_coupons_updated_at();
+
+-- One row per order placed with a coupon. Usage caps count the redemptions
+-- whose order has not failed or been canceled.
+CREATE TABLE IF NOT EXISTS coupon_redemptions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    coupon_id UUID NOT NULL REFERENCES coupons(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    order_id UUID NOT NULL UNIQUE REFERENCES orders(id) ON DELETE CASCADE,
+    discount_cents BIGINT NOT NULL CHECK (discount_cents >= 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_coupon_redemptions_coupon_user
+    ON coupon_redemptions (coupon_id, user_id);
+
+ALTER TABLE orders
+    ADD COLUMN IF NOT EXISTS discount_cents BIGINT NOT NULL DEFAULT 0
+        CHECK (discount_cents >= 0),
+    ADD COLUMN IF NOT EXISTS coupon_code TEXT;
+
+ALTER TABLE order_items
+    ADD COLUMN IF NOT EXISTS discount_cents BIGINT NOT NULL DEFAULT 0
+        CHECK (discount_cents >= 0 AND discount_cents <= unit_price_cents);
@@ -0,0 +1,6 @@
+ALTER TABLE enrollments DROP COLUMN IF EXISTS revoked_at;
+ALTER TABLE orders DROP COLUMN IF EXISTS refunded_at;
+DROP TABLE IF EXISTS order_events;
+DROP TRIGGER IF EXISTS refund_requests_set_updated_at ON refund_requests;
+DROP FUNCTION IF EXISTS set_refund_requests_updated_at();
+DROP TABLE IF EXISTS refund_requests;
@@ -0,0 +1,60 @@
+-- Learner refund requests and the audit trail of every order.
+CREATE TABLE IF NOT EXISTS refund_requests (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    order_id UUID NOT NULL REFERENCES orders(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- pending: awaiting review; failed: the provider declined, can be retried
+    status TEXT NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'rejected', 'refunded', 'failed')),
+    reason TEXT NOT NULL,
+    amount_cents BIGINT NOT NULL CHECK (amount_cents >= 0),
+    currency TEXT NOT NULL,
+    provider_refund_id TEXT,
+    reviewed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    reviewed_at TIMESTAMPTZ,
+    review_note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ
+);
+
+CREATE OR REPLACE FUNCTION set_refund_requests_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at := NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS refund_requests_set_updated_at ON refund_requests;
+CREATE TRIGGER refund_requests_set_updated_at
+BEFORE UPDATE ON refund_requests
+FOR EACH ROW
+EXECUTE FUNCTION set_refund_requests_updated_at();
+
+-- At most one open request per order
+CREATE UNIQUE INDEX IF NOT EXISTS idx_refund_requests_open_order
+    ON refund_requests (order_id) WHERE status IN ('pending', 'failed');
+CREATE INDEX IF NOT EXISTS idx_refund_requests_status
+    ON refund_requests (status, created_at);
+
+-- Append-only history of what happened to an order and who did it;
+-- actor_id is NULL for the system and the payment provider
+CREATE TABLE IF NOT EXISTS order_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    order_id UUID NOT NULL REFERENCES orders(id) ON DELETE CASCADE,
+    actor_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    event TEXT NOT NULL,
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_order_events_order
+    ON order_events (order_id, created_at);
+
+ALTER TABLE orders ADD COLUMN IF NOT EXISTS refunded_at TIMESTAMPTZ;
+
+-- Set while an enrollment is revoked; the learner's progress is kept as of
+-- this moment
+ALTER TABLE enrollments ADD COLUMN IF NOT EXISTS revoked_at TIMESTAMPTZ;
+UPDATE enrollments SET revoked_at = COALESCE(updated_at, NOW())
+    WHERE status = 'revoked' AND revoked_at IS NULL;
@@ -0,0 +1,9 @@
+ALTER TABLE orders DROP COLUMN IF EXISTS learning_path_id;
+DROP TRIGGER IF EXISTS learning_path_enrollments_set_updated_at ON learning_path_enrollments;
+DROP FUNCTION IF EXISTS set_learning_path_enrollments_updated_at();
+DROP TABLE IF EXISTS learning_path_enrollments;
+DROP TABLE IF EXISTS learning_path_prerequisites;
+DROP TABLE IF EXISTS learning_path_courses;
+DROP TRIGGER IF EXISTS learning_paths_set_updated_at ON learning_paths;
+DROP FUNCTION IF EXISTS set_learning_paths_updated_at();
+DROP TABLE IF EXISTS learning_paths;
@@ -0,0 +1,90 @@
+-- Learning paths: ordered tracks of courses sold together at a bundle price.
+CREATE TABLE IF NOT EXISTS learning_paths (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    slug TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    excerpt TEXT,
+    thumbnail TEXT,
+    status TEXT NOT NULL DEFAULT 'draft'
+        CHECK (status IN ('draft', 'published', 'archived')),
+    -- Price of the whole path, in minor units of currency
+    price_cents BIGINT NOT NULL CHECK (price_cents >= 0),
+    currency TEXT NOT NULL CHECK (currency ~ '^[A-Z]{3}$'),
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    published_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ
+);
+
+CREATE OR REPLACE FUNCTION set_learning_paths_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at := NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS learning_paths_set_updated_at ON learning_paths;
+CREATE TRIGGER learning_paths_set_updated_at
+BEFORE UPDATE ON learning_paths
+FOR EACH ROW
+EXECUTE FUNCTION set_learning_paths_updated_at();
+
+CREATE INDEX IF NOT EXISTS idx_learning_paths_published
+    ON learning_paths (published_at DESC)
+    WHERE status = 'published';
+
+-- The courses of a path in the order they are meant to be taken
+CREATE TABLE IF NOT EXISTS learning_path_courses (
+    path_id UUID NOT NULL REFERENCES learning_paths(id) ON DELETE CASCADE,
+    course_id UUID NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    position INT NOT NULL CHECK (position >= 0),
+    PRIMARY KEY (path_id, course_id),
+    UNIQUE (path_id, position)
+);
+
+CREATE INDEX IF NOT EXISTS idx_learning_path_courses_course
+    ON learning_path_courses (course_id);
+
+-- Courses a learner must have completed before enrolling in the path
+CREATE TABLE IF NOT EXISTS learning_path_prerequisites (
+    path_id UUID NOT NULL REFERENCES learning_paths(id) ON DELETE CASCADE,
+    course_id UUID NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    PRIMARY KEY (path_id, course_id)
+);
+
+CREATE TABLE IF NOT EXISTS learning_path_enrollments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    path_id UUID NOT NULL REFERENCES learning_paths(id) ON DELETE CASCADE,
+    -- Order the path was bought with; NULL when nothing was left to pay for
+    order_id UUID REFERENCES orders(id) ON DELETE SET NULL,
+    status TEXT NOT NULL DEFAULT 'active' CHECK (status IN ('active', 'revoked')),
+    enrolled_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ,
+    UNIQUE (user_id, path_id)
+);
+
+CREATE OR REPLACE FUNCTION set_learning_path_enrollments_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at := NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS learning_path_enrollments_set_updated_at ON learning_path_enrollments;
+CREATE TRIGGER learning_path_enrollments_set_updated_at
+BEFORE UPDATE ON learning_path_enrollments
+FOR EACH ROW
+EXECUTE FUNCTION set_learning_path_enrollments_updated_at();
+
+CREATE INDEX IF NOT EXISTS idx_learning_path_enrollments_path
+    ON learning_path_enrollments (path_id);
+
+-- Orders placed for a whole path enroll the buyer in the path once paid
+ALTER TABLE orders ADD COLUMN IF NOT EXISTS learning_path_id UUID
+    REFERENCES learning_paths(id) ON DELETE SET NULL;
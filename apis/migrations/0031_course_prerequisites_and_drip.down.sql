@@ -0,0 +1,4 @@
+ALTER TABLE course_modules DROP CONSTRAINT IF EXISTS course_modules_drip_check;
+ALTER TABLE course_modules DROP COLUMN IF EXISTS drip_at;
+ALTER TABLE course_modules DROP COLUMN IF EXISTS drip_days;
+DROP TABLE IF EXISTS course_prerequisites;
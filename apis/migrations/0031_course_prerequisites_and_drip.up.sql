@@ -0,0 +1,22 @@
+-- Courses a learner must complete (hold the certificate of) before the
+-- lessons of another course open up
+CREATE TABLE IF NOT EXISTS course_prerequisites (
+    course_id UUID NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    required_course_id UUID NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (course_id, required_course_id),
+    CHECK (course_id <> required_course_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_course_prerequisites_required
+    ON course_prerequisites (required_course_id);
+
+-- Drip release: a module opens a number of days after the learner enrolled,
+-- or on a fixed date; at most one of the two is set
+ALTER TABLE course_modules
+    ADD COLUMN IF NOT EXISTS drip_days INT CHECK (drip_days >= 0),
+    ADD COLUMN IF NOT EXISTS drip_at TIMESTAMPTZ;
+
+ALTER TABLE course_modules
+    ADD CONSTRAINT course_modules_drip_check
+    CHECK (drip_days IS NULL OR drip_at IS NULL);
@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS lesson_daily_stats;
+DROP TABLE IF EXISTS module_daily_stats;
+DROP TABLE IF EXISTS course_daily_revenue;
+DROP TABLE IF EXISTS course_daily_stats;
@@ -0,0 +1,66 @@
+-- Daily analytics rollups, written by the background rollup job so the
+-- instructor dashboard never aggregates raw activity tables per request.
+-- Days are UTC calendar days.
+
+-- Per-course activity on a day; active_enrollments is the count at the end
+-- of the day and view_count a snapshot of courses.view_count (only known for
+-- days rolled up while current)
+CREATE TABLE IF NOT EXISTS course_daily_stats (
+    course_id UUID NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    new_enrollments BIGINT NOT NULL DEFAULT 0,
+    revoked_enrollments BIGINT NOT NULL DEFAULT 0,
+    active_enrollments BIGINT NOT NULL DEFAULT 0,
+    completions BIGINT NOT NULL DEFAULT 0,
+    lessons_completed BIGINT NOT NULL DEFAULT 0,
+    quiz_attempts BIGINT NOT NULL DEFAULT 0,
+    view_count BIGINT,
+    rolled_up_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (course_id, day)
+);
+
+CREATE INDEX IF NOT EXISTS idx_course_daily_stats_day ON course_daily_stats (day);
+
+-- Revenue of a course on a day, per currency; amounts are what buyers paid
+-- for the course after discounts, in minor units
+CREATE TABLE IF NOT EXISTS course_daily_revenue (
+    course_id UUID NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    currency TEXT NOT NULL,
+    orders BIGINT NOT NULL DEFAULT 0,
+    gross_cents BIGINT NOT NULL DEFAULT 0,
+    refunds BIGINT NOT NULL DEFAULT 0,
+    refunded_cents BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (course_id, day, currency)
+);
+
+-- Learners who completed at least one / every published lesson of a module,
+-- as of the end of the day
+CREATE TABLE IF NOT EXISTS module_daily_stats (
+    module_id UUID NOT NULL REFERENCES course_modules(id) ON DELETE CASCADE,
+    course_id UUID NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    learners_started BIGINT NOT NULL DEFAULT 0,
+    learners_completed BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (module_id, day)
+);
+
+CREATE INDEX IF NOT EXISTS idx_module_daily_stats_course
+    ON module_daily_stats (course_id, day);
+
+-- Completions and quiz attempts of a lesson on a day; learners_completed is
+-- the running total at the end of the day
+CREATE TABLE IF NOT EXISTS lesson_daily_stats (
+    lesson_id UUID NOT NULL REFERENCES lessons(id) ON DELETE CASCADE,
+    course_id UUID NOT NULL REFERENCES courses(id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    completions BIGINT NOT NULL DEFAULT 0,
+    learners_completed BIGINT NOT NULL DEFAULT 0,
+    quiz_attempts BIGINT NOT NULL DEFAULT 0,
+    quiz_passes BIGINT NOT NULL DEFAULT 0,
+    quiz_score_sum DOUBLE PRECISION NOT NULL DEFAULT 0,
+    PRIMARY KEY (lesson_id, day)
+);
+
+CREATE INDEX IF NOT EXISTS idx_lesson_daily_stats_course
+    ON lesson_daily_stats (course_id, day);
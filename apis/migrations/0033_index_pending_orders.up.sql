@@ -0,0 +1,4 @@
+-- Unpaid orders expire after a while, giving back their coupon redemption;
+-- the expiry job looks them up by age
+CREATE INDEX IF NOT EXISTS idx_orders_pending_created
+    ON orders (created_at) WHERE status = 'pending';
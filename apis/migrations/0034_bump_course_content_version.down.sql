@@ -0,0 +1,13 @@
+DROP TRIGGER IF EXISTS lesson_assignments_bump_content_version ON lesson_assignments;
+DROP TRIGGER IF EXISTS question_options_bump_content_version ON question_options;
+DROP TRIGGER IF EXISTS lesson_questions_bump_content_version ON lesson_questions;
+DROP TRIGGER IF EXISTS lesson_contents_bump_content_version ON lesson_contents;
+DROP TRIGGER IF EXISTS lessons_bump_content_version_on_update ON lessons;
+DROP TRIGGER IF EXISTS lessons_bump_content_version ON lessons;
+DROP TRIGGER IF EXISTS course_modules_bump_content_version_on_update ON course_modules;
+DROP TRIGGER IF EXISTS course_modules_bump_content_version ON course_modules;
+DROP TRIGGER IF EXISTS courses_bump_content_version ON courses;
+
+DROP FUNCTION IF EXISTS bump_course_content_version();
+DROP FUNCTION IF EXISTS bump_curriculum_content_version();
+DROP FUNCTION IF EXISTS curriculum_course_id(TEXT, JSONB);
@@ -0,0 +1,139 @@
+-- Any write to a course's curriculum makes open draft revisions stale, not just
+-- publishing one: bump courses.content_version whenever the revisioned fields,
+-- modules, lessons or their contents, questions, options or assignment change
+
+-- Course a curriculum row belongs to; `r` is the row as JSON
+CREATE OR REPLACE FUNCTION curriculum_course_id(tbl TEXT, r JSONB)
+RETURNS UUID AS $$
+    SELECT CASE tbl
+        WHEN 'course_modules' THEN (r->>'course_id')::UUID
+        WHEN 'lessons' THEN (
+            SELECT cm.course_id FROM course_modules cm
+            WHERE cm.id = (r->>'module_id')::UUID
+        )
+        WHEN 'question_options' THEN (
+            SELECT cm.course_id FROM lesson_questions q
+            JOIN lessons l ON l.id = q.lesson_id
+            JOIN course_modules cm ON cm.id = l.module_id
+            WHERE q.id = (r->>'question_id')::UUID
+        )
+        ELSE (
+            SELECT cm.course_id FROM lessons l
+            JOIN course_modules cm ON cm.id = l.module_id
+            WHERE l.id = (r->>'lesson_id')::UUID
+        )
+    END
+$$ LANGUAGE sql STABLE;
+
+-- Bump the course of the old and new row; rows whose parent is being deleted
+-- in the same cascade resolve to no course and are skipped
+CREATE OR REPLACE FUNCTION bump_curriculum_content_version()
+RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE courses
+    SET content_version = content_version + 1
+    WHERE id IN (
+        curriculum_course_id(TG_TABLE_NAME, CASE WHEN TG_OP <> 'INSERT' THEN to_jsonb(OLD) END),
+        curriculum_course_id(TG_TABLE_NAME, CASE WHEN TG_OP <> 'DELETE' THEN to_jsonb(NEW) END)
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION bump_course_content_version()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.content_version := OLD.content_version + 1;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Course fields a revision stages; a revision publish bumps the version itself
+DROP TRIGGER IF EXISTS courses_bump_content_version ON courses;
+CREATE TRIGGER courses_bump_content_version
+    BEFORE UPDATE ON courses
+    FOR EACH ROW
+    WHEN (
+        NEW.content_version = OLD.content_version
+        AND (
+            NEW.title IS DISTINCT FROM OLD.title
+            OR NEW.description IS DISTINCT FROM OLD.description
+            OR NEW.excerpt IS DISTINCT FROM OLD.excerpt
+            OR NEW.thumbnail IS DISTINCT FROM OLD.thumbnail
+            OR NEW.currency IS DISTINCT FROM OLD.currency
+            OR NEW.price_cents IS DISTINCT FROM OLD.price_cents
+            OR NEW.original_price_cents IS DISTINCT FROM OLD.original_price_cents
+            OR NEW.duration IS DISTINCT FROM OLD.duration
+            OR NEW.outcomes IS DISTINCT FROM OLD.outcomes
+            OR NEW.category IS DISTINCT FROM OLD.category
+            OR NEW.tags IS DISTINCT FROM OLD.tags
+        )
+    )
+    EXECUTE FUNCTION bump_course_content_version();
+
+-- Modules and lessons also carry fields revisions do not stage (drip schedule,
+-- search vector), so only changes to the staged ones count
+DROP TRIGGER IF EXISTS course_modules_bump_content_version ON course_modules;
+CREATE TRIGGER course_modules_bump_content_version
+    AFTER INSERT OR DELETE ON course_modules
+    FOR EACH ROW
+    EXECUTE FUNCTION bump_curriculum_content_version();
+
+DROP TRIGGER IF EXISTS course_modules_bump_content_version_on_update ON course_modules;
+CREATE TRIGGER course_modules_bump_content_version_on_update
+    AFTER UPDATE ON course_modules
+    FOR EACH ROW
+    WHEN (
+        NEW.course_id IS DISTINCT FROM OLD.course_id
+        OR NEW.title IS DISTINCT FROM OLD.title
+        OR NEW.description IS DISTINCT FROM OLD.description
+        OR NEW.position IS DISTINCT FROM OLD.position
+    )
+    EXECUTE FUNCTION bump_curriculum_content_version();
+
+DROP TRIGGER IF EXISTS lessons_bump_content_version ON lessons;
+CREATE TRIGGER lessons_bump_content_version
+    AFTER INSERT OR DELETE ON lessons
+    FOR EACH ROW
+    EXECUTE FUNCTION bump_curriculum_content_version();
+
+DROP TRIGGER IF EXISTS lessons_bump_content_version_on_update ON lessons;
+CREATE TRIGGER lessons_bump_content_version_on_update
+    AFTER UPDATE ON lessons
+    FOR EACH ROW
+    WHEN (
+        NEW.module_id IS DISTINCT FROM OLD.module_id
+        OR NEW.title IS DISTINCT FROM OLD.title
+        OR NEW.description IS DISTINCT FROM OLD.description
+        OR NEW.content IS DISTINCT FROM OLD.content
+        OR NEW.video_url IS DISTINCT FROM OLD.video_url
+        OR NEW.duration IS DISTINCT FROM OLD.duration
+        OR NEW.position IS DISTINCT FROM OLD.position
+        OR NEW.is_free IS DISTINCT FROM OLD.is_free
+        OR NEW.published IS DISTINCT FROM OLD.published
+    )
+    EXECUTE FUNCTION bump_curriculum_content_version();
+
+DROP TRIGGER IF EXISTS lesson_contents_bump_content_version ON lesson_contents;
+CREATE TRIGGER lesson_contents_bump_content_version
+    AFTER INSERT OR UPDATE OR DELETE ON lesson_contents
+    FOR EACH ROW
+    EXECUTE FUNCTION bump_curriculum_content_version();
+
+DROP TRIGGER IF EXISTS lesson_questions_bump_content_version ON lesson_questions;
+CREATE TRIGGER lesson_questions_bump_content_version
+    AFTER INSERT OR UPDATE OR DELETE ON lesson_questions
+    FOR EACH ROW
+    EXECUTE FUNCTION bump_curriculum_content_version();
+
+DROP TRIGGER IF EXISTS question_options_bump_content_version ON question_options;
+CREATE TRIGGER question_options_bump_content_version
+    AFTER INSERT OR UPDATE OR DELETE ON question_options
+    FOR EACH ROW
+    EXECUTE FUNCTION bump_curriculum_content_version();
+
+DROP TRIGGER IF EXISTS lesson_assignments_bump_content_version ON lesson_assignments;
+CREATE TRIGGER lesson_assignments_bump_content_version
+    AFTER INSERT OR UPDATE OR DELETE ON lesson_assignments
+    FOR EACH ROW
+    EXECUTE FUNCTION bump_curriculum_content_version();
@@ -0,0 +1,360 @@
+//! Instructor analytics
+//!
+//! Reports never aggregate the activity tables per request. A background job
+//! rolls each UTC day up into daily tables (see migration 0032): it re-rolls
+//! today and yesterday every run, so late activity lands in the right day, and
+//! backfills up to [`ROLLUP_BACKFILL_DAYS`] after downtime. Course view counts
+//! are only kept as a total, so the job snapshots them for the current day;
+//! daily views are the differences between snapshots. Reports read the
+//! rollups of a date range, which therefore lag live data by up to one
+//! [`ANALYTICS_ROLLUP_INTERVAL`].
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::NaiveDate;
+
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::logger::{info, warn};
+use crate::pkg::money::Money;
+use crate::repositories::analytics::{
+    AnalyticsRepository, CourseDailyRevenueRecord, CourseDailyStatsRecord,
+};
+use crate::repositories::courses::CoursesRepository;
+use crate::types::analytics_types::{
+    AnalyticsQuery, CourseAnalytics, CourseAnalyticsDay, CourseAnalyticsTotals,
+    CurriculumAnalytics, InstructorAnalytics, InstructorCourseSummary, LessonEngagement,
+    ModuleFunnelStep, RevenueDay, RevenueSummary,
+};
+
+use super::courses::load_managed_course;
+
+/// How often the rollup job refreshes the current day
+pub const ANALYTICS_ROLLUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Oldest day the rollup job fills in after downtime
+pub const ROLLUP_BACKFILL_DAYS: i64 = 30;
+
+/// Days in a report when the query gives no `from`
+const DEFAULT_RANGE_DAYS: i64 = 30;
+
+/// Longest report range, in days
+const MAX_RANGE_DAYS: i64 = 366;
+
+/// Days the rollup job recomputes given the latest rolled-up day, oldest first
+pub fn days_to_roll_up(latest: Option<NaiveDate>, today: NaiveDate) -> Vec<NaiveDate> {
+    let oldest = today - chrono::Duration::days(ROLLUP_BACKFILL_DAYS - 1);
+    let yesterday = today - chrono::Duration::days(1);
+    let first = latest.map_or(oldest, |latest| latest.min(yesterday).max(oldest));
+    first.iter_days().take_while(|day| *day <= today).collect()
+}
+
+/// Roll up every day that is due; returns how many days were rolled up
+pub async fn roll_up_recent_days(repo: &dyn AnalyticsRepository) -> AppResult<usize> {
+    let today = chrono::Utc::now().date_naive();
+    let days = days_to_roll_up(repo.latest_rollup_day().await?, today);
+    for day in &days {
+        repo.roll_up_day(*day, *day == today).await?;
+    }
+    Ok(days.len())
+}
+
+/// Run `roll_up_recent_days` every `interval` until the task is aborted
+pub async fn run_analytics_rollup(repo: Arc<dyn AnalyticsRepository>, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        ticker.tick().await;
+        match roll_up_recent_days(repo.as_ref()).await {
+            Ok(n) => info(format!("Rolled up analytics for {} day(s)", n)),
+            Err(e) => warn(format!("Analytics rollup failed: {}", e)),
+        }
+    }
+}
+
+/// The inclusive day range a query asks for
+pub fn resolve_range(
+    query: &AnalyticsQuery,
+    today: NaiveDate,
+) -> AppResult<(NaiveDate, NaiveDate)> {
+    let to = query.to.unwrap_or(today);
+    let from = query
+        .from
+        .unwrap_or(to - chrono::Duration::days(DEFAULT_RANGE_DAYS - 1));
+    if from > to {
+        return Err(AppError::BadRequest("from must not be after to".into()));
+    }
+    if (to - from).num_days() >= MAX_RANGE_DAYS {
+        return Err(AppError::BadRequest(format!(
+            "The range must not exceed {} days",
+            MAX_RANGE_DAYS
+        )));
+    }
+    Ok((from, to))
+}
+
+/// Views between each day's snapshot and the previous known one; the first
+/// snapshot of the series has nothing to compare with
+pub fn view_deltas(snapshots: &[Option<i64>]) -> Vec<Option<i64>> {
+    let mut previous = None;
+    snapshots
+        .iter()
+        .map(|snapshot| {
+            let current = (*snapshot)?;
+            let delta = previous.map(|p: i64| (current - p).max(0));
+            previous = Some(current);
+            delta
+        })
+        .collect()
+}
+
+/// Share of the previous lesson's learners who did not complete the next one
+pub fn drop_off(previous_learners: i64, learners: i64) -> Option<f64> {
+    (previous_learners > 0)
+        .then(|| (previous_learners - learners).max(0) as f64 / previous_learners as f64)
+}
+
+/// A course's enrollments, completions, views and revenue over a date range
+pub async fn get_course_analytics(
+    courses: &dyn CoursesRepository,
+    analytics: &dyn AnalyticsRepository,
+    user: &AuthUser,
+    course_id: uuid::Uuid,
+    query: AnalyticsQuery,
+) -> AppResult<CourseAnalytics> {
+    load_managed_course(courses, user, course_id).await?;
+    let (from, to) = resolve_range(&query, chrono::Utc::now().date_naive())?;
+    let days = analytics.list_course_days(course_id, from, to).await?;
+    let revenue = analytics.list_course_revenue(course_id, from, to).await?;
+    Ok(CourseAnalytics {
+        course_id,
+        from,
+        to,
+        rolled_up_through: analytics.latest_rollup_day().await?,
+        totals: course_totals(&days),
+        daily: daily_series(days),
+        revenue: summarize_revenue(&revenue),
+        revenue_daily: revenue
+            .into_iter()
+            .map(|r| RevenueDay {
+                day: r.day,
+                orders: r.orders,
+                gross: Money::new(r.gross_cents, r.currency.clone()),
+                refunded: Money::new(r.refunded_cents, r.currency),
+            })
+            .collect(),
+    })
+}
+
+/// Module completion funnel and per-lesson drop-off and quiz results of a course
+pub async fn get_curriculum_analytics(
+    courses: &dyn CoursesRepository,
+    analytics: &dyn AnalyticsRepository,
+    user: &AuthUser,
+    course_id: uuid::Uuid,
+    query: AnalyticsQuery,
+) -> AppResult<CurriculumAnalytics> {
+    load_managed_course(courses, user, course_id).await?;
+    let (from, to) = resolve_range(&query, chrono::Utc::now().date_naive())?;
+    let active_enrollments = analytics
+        .list_course_days(course_id, from, to)
+        .await?
+        .last()
+        .map_or(0, |d| d.active_enrollments);
+    let modules = analytics
+        .list_module_funnel(course_id, to)
+        .await?
+        .into_iter()
+        .map(|m| ModuleFunnelStep {
+            module_id: m.module_id,
+            title: m.title,
+            position: m.position,
+            learners_started: m.learners_started,
+            learners_completed: m.learners_completed,
+            completion_rate: ratio(m.learners_completed, active_enrollments)
+                .map_or(0.0, |r| r.min(1.0)),
+        })
+        .collect();
+    let mut previous: Option<i64> = None;
+    let lessons = analytics
+        .list_lesson_engagement(course_id, from, to)
+        .await?
+        .into_iter()
+        .map(|l| {
+            let drop_off = previous.and_then(|p| drop_off(p, l.learners_completed));
+            previous = Some(l.learners_completed);
+            LessonEngagement {
+                lesson_id: l.lesson_id,
+                module_id: l.module_id,
+                title: l.title,
+                learners_completed: l.learners_completed,
+                completions: l.completions,
+                drop_off,
+                quiz_attempts: l.quiz_attempts,
+                quiz_average_score: (l.quiz_attempts > 0)
+                    .then(|| l.quiz_score_sum / l.quiz_attempts as f64),
+                quiz_pass_rate: ratio(l.quiz_passes, l.quiz_attempts),
+            }
+        })
+        .collect();
+    Ok(CurriculumAnalytics {
+        course_id,
+        from,
+        to,
+        rolled_up_through: analytics.latest_rollup_day().await?,
+        active_enrollments,
+        modules,
+        lessons,
+    })
+}
+
+/// Summaries of every course the signed-in user teaches
+pub async fn get_instructor_analytics(
+    analytics: &dyn AnalyticsRepository,
+    user: &AuthUser,
+    query: AnalyticsQuery,
+) -> AppResult<InstructorAnalytics> {
+    let (from, to) = resolve_range(&query, chrono::Utc::now().date_naive())?;
+    let courses = analytics
+        .summarize_instructor_courses(user.user_id, from, to)
+        .await?
+        .into_iter()
+        .map(|c| InstructorCourseSummary {
+            course_id: c.course_id,
+            title: c.title,
+            new_enrollments: c.new_enrollments,
+            completions: c.completions,
+            active_enrollments: c.active_enrollments,
+            views: c.views,
+        })
+        .collect();
+    let revenue = analytics
+        .list_instructor_revenue(user.user_id, from, to)
+        .await?;
+    Ok(InstructorAnalytics {
+        from,
+        to,
+        rolled_up_through: analytics.latest_rollup_day().await?,
+        courses,
+        revenue: summarize_revenue(&revenue),
+    })
+}
+
+fn ratio(part: i64, whole: i64) -> Option<f64> {
+    (whole > 0).then(|| part as f64 / whole as f64)
+}
+
+fn course_totals(days: &[CourseDailyStatsRecord]) -> CourseAnalyticsTotals {
+    let snapshots: Vec<i64> = days.iter().filter_map(|d| d.view_count).collect();
+    CourseAnalyticsTotals {
+        new_enrollments: days.iter().map(|d| d.new_enrollments).sum(),
+        revoked_enrollments: days.iter().map(|d| d.revoked_enrollments).sum(),
+        active_enrollments: days.last().map_or(0, |d| d.active_enrollments),
+        completions: days.iter().map(|d| d.completions).sum(),
+        lessons_completed: days.iter().map(|d| d.lessons_completed).sum(),
+        quiz_attempts: days.iter().map(|d| d.quiz_attempts).sum(),
+        views: snapshots
+            .first()
+            .zip(snapshots.last())
+            .map(|(first, last)| (last - first).max(0)),
+    }
+}
+
+fn daily_series(days: Vec<CourseDailyStatsRecord>) -> Vec<CourseAnalyticsDay> {
+    let snapshots: Vec<Option<i64>> = days.iter().map(|d| d.view_count).collect();
+    days.into_iter()
+        .zip(view_deltas(&snapshots))
+        .map(|(d, views)| CourseAnalyticsDay {
+            day: d.day,
+            new_enrollments: d.new_enrollments,
+            revoked_enrollments: d.revoked_enrollments,
+            active_enrollments: d.active_enrollments,
+            completions: d.completions,
+            lessons_completed: d.lessons_completed,
+            quiz_attempts: d.quiz_attempts,
+            views,
+            view_count: d.view_count,
+        })
+        .collect()
+}
+
+/// Revenue rows summed per currency
+fn summarize_revenue(rows: &[CourseDailyRevenueRecord]) -> Vec<RevenueSummary> {
+    let mut totals: BTreeMap<&str, (i64, i64, i64, i64)> = BTreeMap::new();
+    for row in rows {
+        let total = totals.entry(row.currency.as_str()).or_default();
+        total.0 += row.orders;
+        total.1 += row.gross_cents;
+        total.2 += row.refunds;
+        total.3 += row.refunded_cents;
+    }
+    totals
+        .into_iter()
+        .map(
+            |(currency, (orders, gross, refunds, refunded))| RevenueSummary {
+                orders,
+                gross: Money::new(gross, currency),
+                refunds,
+                refunded: Money::new(refunded, currency),
+                net: Money::new(gross - refunded, currency),
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+    }
+
+    #[test]
+    fn rollup_redoes_yesterday_and_backfills_a_bounded_window() {
+        assert_eq!(
+            days_to_roll_up(Some(day(20)), day(20)),
+            vec![day(19), day(20)]
+        );
+        assert_eq!(
+            days_to_roll_up(Some(day(17)), day(20)),
+            vec![day(17), day(18), day(19), day(20)]
+        );
+        let fresh = days_to_roll_up(None, day(31));
+        assert_eq!(fresh.len() as i64, ROLLUP_BACKFILL_DAYS);
+        assert_eq!(fresh.first(), Some(&day(2)));
+        assert_eq!(
+            days_to_roll_up(Some(day(1) - chrono::Duration::days(90)), day(31)),
+            fresh
+        );
+    }
+
+    #[test]
+    fn ranges_default_to_the_last_thirty_days_and_are_bounded() {
+        let query = AnalyticsQuery::default();
+        assert_eq!(resolve_range(&query, day(31)).unwrap(), (day(2), day(31)));
+        let reversed = AnalyticsQuery {
+            from: Some(day(10)),
+            to: Some(day(9)),
+        };
+        assert!(resolve_range(&reversed, day(31)).is_err());
+        let too_long = AnalyticsQuery {
+            from: Some(day(1) - chrono::Duration::days(400)),
+            to: Some(day(1)),
+        };
+        assert!(resolve_range(&too_long, day(31)).is_err());
+    }
+
+    #[test]
+    fn views_and_drop_off_are_derived_from_snapshots() {
+        assert_eq!(
+            view_deltas(&[Some(10), None, Some(15), Some(22)]),
+            vec![None, None, Some(5), Some(7)]
+        );
+        assert_eq!(drop_off(40, 30), Some(0.25));
+        assert_eq!(drop_off(0, 0), None);
+        assert_eq!(drop_off(10, 12), Some(0.0));
+    }
+}
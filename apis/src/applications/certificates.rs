@@ -0,0 +1,266 @@
+//! Completion certificates
+//!
+//! A learner earns a certificate once every published lesson of a course is
+//! completed and every lesson quiz has a passing attempt. Learner, course and
+//! instructor names are copied onto the certificate when it is issued, so it
+//! keeps reading the same after renames and stays verifiable by its code
+//! after the course is deleted.
+
+use rand::RngCore;
+
+use crate::configs::certificate_config::CertificateConfig;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::logger::info;
+use crate::pkg::pdf::certificate::{CertificateDocument, render_certificate};
+use crate::repositories::certificates::{
+    CertificateRecord, CertificatesRepository, CreateCertificateRecord,
+};
+use crate::repositories::courses::CoursesRepository;
+use crate::repositories::lesson_progress::CourseProgressRecord;
+use crate::repositories::users::UsersRepository;
+use crate::types::certificate_types::{Certificate, CertificateVerification};
+
+/// Unambiguous code alphabet (no 0/O, 1/I)
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const CODE_GROUPS: usize = 3;
+const CODE_GROUP_LEN: usize = 4;
+
+/// Whether `progress` completes the course
+pub fn is_course_complete(progress: &CourseProgressRecord) -> bool {
+    progress.total_lessons > 0
+        && progress.completed_lesson_ids.len() as i64 >= progress.total_lessons
+        && progress.passed_quiz_lessons >= progress.quiz_lessons
+}
+
+/// What issuing a certificate needs to look up and record
+pub struct CertificateIssuer<'a> {
+    pub courses: &'a dyn CoursesRepository,
+    pub users: &'a dyn UsersRepository,
+    pub certificates: &'a dyn CertificatesRepository,
+    pub config: &'a CertificateConfig,
+}
+
+impl CertificateIssuer<'_> {
+    /// Issue the learner's certificate for a course if `progress` completes it.
+    ///
+    /// Returns the certificate the learner holds, if any; issuing is idempotent.
+    pub async fn issue_if_complete(
+        &self,
+        user_id: uuid::Uuid,
+        course_id: uuid::Uuid,
+        progress: &CourseProgressRecord,
+    ) -> AppResult<Option<Certificate>> {
+        if let Some(existing) = self.held(user_id, course_id).await? {
+            return Ok(Some(existing));
+        }
+        if !is_course_complete(progress) {
+            return Ok(None);
+        }
+
+        let course = self
+            .courses
+            .find_by_id(course_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Course not found".into()))?;
+        let learner = self
+            .users
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".into()))?;
+        let instructor_name = course
+            .instructor
+            .map(|i| display_name(i.full_name, i.username))
+            .unwrap_or_default();
+
+        let record = self
+            .certificates
+            .issue(CreateCertificateRecord {
+                user_id,
+                course_id,
+                verification_code: generate_code(),
+                learner_name: display_name(learner.full_name, learner.username),
+                course_title: course.title,
+                instructor_name,
+            })
+            .await?;
+        info(format!(
+            "Issued certificate {} to user {} for course {}",
+            record.verification_code, user_id, course_id
+        ));
+        Ok(Some(to_certificate(record, self.config)))
+    }
+
+    /// The learner's certificate for a course, without issuing one
+    pub async fn held(
+        &self,
+        user_id: uuid::Uuid,
+        course_id: uuid::Uuid,
+    ) -> AppResult<Option<Certificate>> {
+        Ok(self
+            .certificates
+            .find_by_user_and_course(user_id, course_id)
+            .await?
+            .map(|record| to_certificate(record, self.config)))
+    }
+}
+
+/// Certificates held by the signed-in user
+pub async fn list_my_certificates(
+    certificates: &dyn CertificatesRepository,
+    config: &CertificateConfig,
+    user: &AuthUser,
+) -> AppResult<Vec<Certificate>> {
+    let records = certificates.list_by_user(user.user_id).await?;
+    Ok(records
+        .into_iter()
+        .map(|r| to_certificate(r, config))
+        .collect())
+}
+
+/// Public verification of a certificate code
+pub async fn verify_certificate(
+    certificates: &dyn CertificatesRepository,
+    code: &str,
+) -> AppResult<CertificateVerification> {
+    let record = find_by_code(certificates, code).await?;
+    Ok(CertificateVerification {
+        valid: true,
+        verification_code: record.verification_code,
+        learner_name: record.learner_name,
+        course_title: record.course_title,
+        instructor_name: record.instructor_name,
+        course_id: record.course_id,
+        issued_at: record.issued_at,
+    })
+}
+
+/// Render a certificate as PDF; returns the verification code and the bytes.
+///
+/// Fails with Conflict when its names use characters the PDF fonts lack.
+pub async fn render_certificate_pdf(
+    certificates: &dyn CertificatesRepository,
+    config: &CertificateConfig,
+    code: &str,
+) -> AppResult<(String, Vec<u8>)> {
+    let record = find_by_code(certificates, code).await?;
+    let doc = CertificateDocument {
+        verify_url: verify_url(config, &record.verification_code),
+        learner_name: record.learner_name,
+        course_title: record.course_title,
+        instructor_name: record.instructor_name,
+        issued_on: record.issued_at.date_naive(),
+        verification_code: record.verification_code.clone(),
+    };
+    // Printing names the fonts cannot show as `?` would issue a wrong document
+    let pdf = render_certificate(&doc).map_err(|e| {
+        AppError::Conflict(format!(
+            "This certificate cannot be printed yet: {}. It can still be verified online",
+            e
+        ))
+    })?;
+    Ok((record.verification_code, pdf))
+}
+
+async fn find_by_code(
+    certificates: &dyn CertificatesRepository,
+    code: &str,
+) -> AppResult<CertificateRecord> {
+    certificates
+        .find_by_code(&normalize_code(code))
+        .await?
+        .ok_or_else(|| AppError::NotFound("No certificate with this verification code".into()))
+}
+
+/// Random code such as `7KQ2-MZ4D-XH9P` (60 bits)
+fn generate_code() -> String {
+    let mut bytes = [0u8; CODE_GROUPS * CODE_GROUP_LEN];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    bytes
+        .chunks(CODE_GROUP_LEN)
+        .map(|group| {
+            group
+                .iter()
+                .map(|b| CODE_ALPHABET[(*b as usize) % CODE_ALPHABET.len()] as char)
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Accept codes typed in lowercase or without dashes
+fn normalize_code(code: &str) -> String {
+    let chars: Vec<char> = code
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    if chars.len() != CODE_GROUPS * CODE_GROUP_LEN {
+        return code.trim().to_string();
+    }
+    chars
+        .chunks(CODE_GROUP_LEN)
+        .map(|group| group.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn display_name(full_name: Option<String>, username: String) -> String {
+    full_name
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .unwrap_or(username)
+}
+
+fn verify_url(config: &CertificateConfig, code: &str) -> String {
+    format!("{}/{}", config.verify_base_url, code)
+}
+
+fn to_certificate(record: CertificateRecord, config: &CertificateConfig) -> Certificate {
+    let verify_url = verify_url(config, &record.verification_code);
+    Certificate {
+        id: record.id,
+        course_id: record.course_id,
+        pdf_url: format!("{}/pdf", verify_url),
+        verify_url,
+        verification_code: record.verification_code,
+        learner_name: record.learner_name,
+        course_title: record.course_title,
+        instructor_name: record.instructor_name,
+        issued_at: record.issued_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_grouped_and_normalized() {
+        let code = generate_code();
+        assert_eq!(code.len(), 14);
+        assert!(code.split('-').all(|g| g.len() == 4));
+        assert!(
+            code.chars()
+                .all(|c| c == '-' || CODE_ALPHABET.contains(&(c as u8)))
+        );
+        assert_eq!(normalize_code("7kq2mz4dxh9p"), "7KQ2-MZ4D-XH9P");
+        assert_eq!(normalize_code(" 7KQ2-MZ4D-XH9P "), "7KQ2-MZ4D-XH9P");
+    }
+
+    #[test]
+    fn completion_requires_every_lesson_and_quiz() {
+        let mut progress = CourseProgressRecord {
+            total_lessons: 2,
+            completed_lesson_ids: vec![uuid::Uuid::new_v4(), uuid::Uuid::new_v4()],
+            quiz_lessons: 1,
+            passed_quiz_lessons: 0,
+        };
+        assert!(!is_course_complete(&progress));
+        progress.passed_quiz_lessons = 1;
+        assert!(is_course_complete(&progress));
+        progress.completed_lesson_ids.pop();
+        assert!(!is_course_complete(&progress));
+        assert!(!is_course_complete(&CourseProgressRecord::default()));
+    }
+}
@@ -0,0 +1,481 @@
+//! Discount coupons
+//!
+//! A coupon takes a percentage or a fixed amount off the courses it applies
+//! to: one course, the courses of one category, or every course. Percentages
+//! are rounded per course; fixed amounts are split across the eligible
+//! courses in proportion to their prices and never exceed what they cost.
+//! Usage caps are checked here for a friendly preview and enforced again when
+//! the order is written, under a lock on the coupon.
+
+use crate::applications::orders::{OrderServices, checkout_currency, price_items};
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::money::Money;
+use crate::repositories::categories::CategoriesRepository;
+use crate::repositories::coupons::{
+    CouponRecord, CouponsRepository, CreateCouponRecord, DiscountType, UpdateCouponRecord,
+};
+use crate::repositories::course_categories::CourseCategoriesRepository;
+use crate::repositories::courses::CoursesRepository;
+use crate::repositories::orders::CreateOrderItemRecord;
+use crate::types::coupon_types::{
+    Coupon, CouponPreview, CouponPreviewItem, CreateCouponRequest, UpdateCouponRequest,
+    ValidateCouponRequest,
+};
+
+/// Create a coupon (admin only)
+pub async fn create_coupon(
+    coupons: &dyn CouponsRepository,
+    courses: &dyn CoursesRepository,
+    categories: &dyn CategoriesRepository,
+    user: &AuthUser,
+    req: CreateCouponRequest,
+) -> AppResult<Coupon> {
+    user.require_admin()?;
+    let code = normalize_code(&req.code);
+    if !code
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+    {
+        return Err(AppError::BadRequest(
+            "Coupon codes may only contain letters, digits, '-' and '_'".into(),
+        ));
+    }
+    let discount_type = DiscountType::parse(&req.discount_type)
+        .ok_or_else(|| AppError::BadRequest("discount_type must be 'percent' or 'fixed'".into()))?;
+    let (percent_off, amount_off) = match (discount_type, req.percent_off, req.amount_off) {
+        (DiscountType::Percent, Some(percent), None) => (Some(percent), None),
+        (DiscountType::Fixed, None, Some(amount)) if amount.amount > 0 => (None, Some(amount)),
+        (DiscountType::Percent, ..) => {
+            return Err(AppError::BadRequest(
+                "Percentage coupons take percent_off and no amount_off".into(),
+            ));
+        }
+        (DiscountType::Fixed, ..) => {
+            return Err(AppError::BadRequest(
+                "Fixed coupons take a positive amount_off and no percent_off".into(),
+            ));
+        }
+    };
+    if req.course_id.is_some() && req.category_id.is_some() {
+        return Err(AppError::BadRequest(
+            "A coupon applies to a course or a category, not both".into(),
+        ));
+    }
+    if let Some(course_id) = req.course_id {
+        courses
+            .find_by_id(course_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Course not found".into()))?;
+    }
+    if let Some(category_id) = req.category_id {
+        categories
+            .find_by_id(category_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Category not found".into()))?;
+    }
+    check_window(req.starts_at, req.expires_at)?;
+    if coupons.find_by_code(&code).await?.is_some() {
+        return Err(AppError::Conflict(format!(
+            "Coupon code {} is already taken",
+            code
+        )));
+    }
+
+    let record = coupons
+        .create(CreateCouponRecord {
+            code,
+            description: req.description,
+            discount_type,
+            percent_off,
+            amount_off_cents: amount_off.as_ref().map(|m| m.amount),
+            currency: amount_off.map(|m| m.currency),
+            course_id: req.course_id,
+            category_id: req.category_id,
+            starts_at: req.starts_at,
+            expires_at: req.expires_at,
+            max_redemptions: req.max_redemptions,
+            max_redemptions_per_user: req.max_redemptions_per_user,
+            active: req.active.unwrap_or(true),
+            created_by: user.user_id,
+        })
+        .await?;
+    Ok(record.into())
+}
+
+/// Coupons, newest first (admin only)
+pub async fn list_coupons(
+    coupons: &dyn CouponsRepository,
+    user: &AuthUser,
+    limit: Option<i64>,
+) -> AppResult<Vec<Coupon>> {
+    user.require_admin()?;
+    let limit = limit.unwrap_or(50).clamp(1, 200);
+    Ok(coupons
+        .list(limit)
+        .await?
+        .into_iter()
+        .map(Coupon::from)
+        .collect())
+}
+
+pub async fn get_coupon(
+    coupons: &dyn CouponsRepository,
+    user: &AuthUser,
+    id: uuid::Uuid,
+) -> AppResult<Coupon> {
+    user.require_admin()?;
+    load_coupon(coupons, id).await.map(Coupon::from)
+}
+
+/// Change a coupon's description, validity window, caps or active flag
+/// (admin only)
+pub async fn update_coupon(
+    coupons: &dyn CouponsRepository,
+    user: &AuthUser,
+    id: uuid::Uuid,
+    req: UpdateCouponRequest,
+) -> AppResult<Coupon> {
+    user.require_admin()?;
+    let current = load_coupon(coupons, id).await?;
+    check_window(
+        req.starts_at.or(current.starts_at),
+        req.expires_at.or(current.expires_at),
+    )?;
+    coupons
+        .update_partial(
+            id,
+            UpdateCouponRecord {
+                description: req.description,
+                starts_at: req.starts_at,
+                expires_at: req.expires_at,
+                max_redemptions: req.max_redemptions,
+                max_redemptions_per_user: req.max_redemptions_per_user,
+                active: req.active,
+            },
+        )
+        .await?
+        .map(Coupon::from)
+        .ok_or_else(|| AppError::NotFound("Coupon not found".into()))
+}
+
+/// Price the courses as checkout would, with the coupon applied
+pub async fn validate_coupon(
+    svc: &OrderServices<'_>,
+    user: &AuthUser,
+    req: ValidateCouponRequest,
+) -> AppResult<CouponPreview> {
+    let currency = checkout_currency(req.currency.as_deref(), svc.config)?;
+    let mut items = price_items(svc, user, req.course_ids, &currency).await?;
+    let coupon = apply_coupon(svc, user, &req.code, &currency, &mut items).await?;
+
+    let subtotal: i64 = items.iter().map(|i| i.unit_price_cents).sum();
+    let discount: i64 = items.iter().map(|i| i.discount_cents).sum();
+    Ok(CouponPreview {
+        code: coupon.code,
+        description: coupon.description,
+        subtotal: Money::new(subtotal, currency.clone()),
+        discount: Money::new(discount, currency.clone()),
+        total: Money::new(subtotal - discount, currency.clone()),
+        items: items
+            .into_iter()
+            .map(|i| CouponPreviewItem {
+                course_id: i.course_id,
+                course_title: i.course_title,
+                unit_price: Money::new(i.unit_price_cents, currency.clone()),
+                discount: Money::new(i.discount_cents, currency.clone()),
+            })
+            .collect(),
+    })
+}
+
+/// Look up the coupon `code`, check `user` may redeem it on an order in
+/// `currency`, and set the discount on each of `items`
+pub(crate) async fn apply_coupon(
+    svc: &OrderServices<'_>,
+    user: &AuthUser,
+    code: &str,
+    currency: &str,
+    items: &mut [CreateOrderItemRecord],
+) -> AppResult<CouponRecord> {
+    let coupon = svc
+        .coupons
+        .find_by_code(&normalize_code(code))
+        .await?
+        .ok_or_else(|| AppError::NotFound("Coupon not found".into()))?;
+    check_redeemable(&coupon, chrono::Utc::now())?;
+    let discount = discount_of(&coupon, currency)?;
+
+    let mut prices = Vec::with_capacity(items.len());
+    for item in items.iter() {
+        let eligible = applies_to(svc.course_categories, &coupon, item.course_id).await?;
+        prices.push(eligible.then_some(item.unit_price_cents));
+    }
+    if prices.iter().all(Option::is_none) {
+        return Err(AppError::BadRequest(format!(
+            "Coupon {} does not apply to these courses",
+            coupon.code
+        )));
+    }
+
+    let usage = svc.coupons.usage(coupon.id, user.user_id).await?;
+    if coupon
+        .max_redemptions
+        .is_some_and(|max| usage.total >= max as i64)
+    {
+        return Err(AppError::Conflict(format!(
+            "Coupon {} has been used up",
+            coupon.code
+        )));
+    }
+    if coupon
+        .max_redemptions_per_user
+        .is_some_and(|max| usage.by_user >= max as i64)
+    {
+        return Err(AppError::Conflict(format!(
+            "You have already used coupon {}",
+            coupon.code
+        )));
+    }
+
+    for (item, amount) in items.iter_mut().zip(split_discount(discount, &prices)) {
+        item.discount_cents = amount;
+    }
+    Ok(coupon)
+}
+
+/// Discount a coupon gives, in the order's minor units
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Discount {
+    Percent(i32),
+    Fixed(i64),
+}
+
+fn discount_of(coupon: &CouponRecord, currency: &str) -> AppResult<Discount> {
+    match (
+        DiscountType::parse(&coupon.discount_type),
+        coupon.percent_off,
+        coupon.amount_off_cents,
+    ) {
+        (Some(DiscountType::Percent), Some(percent), _) => Ok(Discount::Percent(percent)),
+        (Some(DiscountType::Fixed), _, Some(amount)) => {
+            if coupon.currency.as_deref() != Some(currency) {
+                return Err(AppError::BadRequest(format!(
+                    "Coupon {} only applies to orders in {}",
+                    coupon.code,
+                    coupon.currency.as_deref().unwrap_or("another currency")
+                )));
+            }
+            Ok(Discount::Fixed(amount))
+        }
+        _ => Err(AppError::Internal(format!(
+            "Coupon {} has an invalid discount",
+            coupon.code
+        ))),
+    }
+}
+
+/// Discount per item; `None` prices are items the coupon does not apply to
+pub(crate) fn split_discount(discount: Discount, prices: &[Option<i64>]) -> Vec<i64> {
+    match discount {
+        Discount::Percent(percent) => prices
+            .iter()
+            .map(|price| {
+                // Round half up
+                price.map_or(0, |p| ((p as i128 * percent as i128 + 50) / 100) as i64)
+            })
+            .collect(),
+        Discount::Fixed(amount) => {
+            let eligible: i64 = prices.iter().flatten().sum();
+            let amount = amount.min(eligible);
+            if amount == 0 {
+                return vec![0; prices.len()];
+            }
+            let mut shares: Vec<i64> = prices
+                .iter()
+                .map(|price| {
+                    price.map_or(0, |p| {
+                        (amount as i128 * p as i128 / eligible as i128) as i64
+                    })
+                })
+                .collect();
+            // Hand the rounding remainder out a unit at a time, in order
+            let mut remainder = amount - shares.iter().sum::<i64>();
+            for (share, price) in shares.iter_mut().zip(prices) {
+                if remainder == 0 {
+                    break;
+                }
+                if let Some(p) = price
+                    && *share < *p
+                {
+                    *share += 1;
+                    remainder -= 1;
+                }
+            }
+            shares
+        }
+    }
+}
+
+fn check_redeemable(coupon: &CouponRecord, now: chrono::DateTime<chrono::Utc>) -> AppResult<()> {
+    if !coupon.active {
+        return Err(AppError::BadRequest(format!(
+            "Coupon {} is no longer active",
+            coupon.code
+        )));
+    }
+    if coupon.starts_at.is_some_and(|at| now < at) {
+        return Err(AppError::BadRequest(format!(
+            "Coupon {} is not valid yet",
+            coupon.code
+        )));
+    }
+    if coupon.expires_at.is_some_and(|at| now >= at) {
+        return Err(AppError::BadRequest(format!(
+            "Coupon {} has expired",
+            coupon.code
+        )));
+    }
+    Ok(())
+}
+
+async fn applies_to(
+    course_categories: &dyn CourseCategoriesRepository,
+    coupon: &CouponRecord,
+    course_id: uuid::Uuid,
+) -> AppResult<bool> {
+    if let Some(only) = coupon.course_id {
+        return Ok(only == course_id);
+    }
+    if let Some(category_id) = coupon.category_id {
+        return Ok(course_categories
+            .list_categories_for_course(course_id)
+            .await?
+            .contains(&category_id));
+    }
+    Ok(true)
+}
+
+fn check_window(
+    starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+) -> AppResult<()> {
+    if let (Some(starts), Some(expires)) = (starts_at, expires_at)
+        && expires <= starts
+    {
+        return Err(AppError::BadRequest(
+            "expires_at must be after starts_at".into(),
+        ));
+    }
+    Ok(())
+}
+
+fn normalize_code(code: &str) -> String {
+    code.trim().to_ascii_uppercase()
+}
+
+async fn load_coupon(coupons: &dyn CouponsRepository, id: uuid::Uuid) -> AppResult<CouponRecord> {
+    coupons
+        .find_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Coupon not found".into()))
+}
+
+impl From<CouponRecord> for Coupon {
+    fn from(record: CouponRecord) -> Self {
+        Coupon {
+            id: record.id,
+            code: record.code,
+            description: record.description,
+            discount_type: record.discount_type,
+            percent_off: record.percent_off,
+            amount_off: record
+                .amount_off_cents
+                .zip(record.currency)
+                .map(|(amount, currency)| Money::new(amount, currency)),
+            course_id: record.course_id,
+            category_id: record.category_id,
+            starts_at: record.starts_at,
+            expires_at: record.expires_at,
+            max_redemptions: record.max_redemptions,
+            max_redemptions_per_user: record.max_redemptions_per_user,
+            active: record.active,
+            times_redeemed: record.times_redeemed,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentages_round_half_up_per_course() {
+        let prices = [Some(1999), None, Some(1000)];
+        assert_eq!(
+            split_discount(Discount::Percent(25), &prices),
+            vec![500, 0, 250]
+        );
+        assert_eq!(
+            split_discount(Discount::Percent(100), &prices),
+            vec![1999, 0, 1000]
+        );
+    }
+
+    #[test]
+    fn fixed_amounts_split_by_price_and_stop_at_the_total() {
+        // 1000 over 1000 and 2000: one third and two thirds, remainder first
+        let prices = [Some(1000), Some(2000), None];
+        assert_eq!(
+            split_discount(Discount::Fixed(1000), &prices),
+            vec![334, 666, 0]
+        );
+        assert_eq!(
+            split_discount(Discount::Fixed(5000), &prices),
+            vec![1000, 2000, 0]
+        );
+        assert_eq!(
+            split_discount(Discount::Fixed(500), &[Some(0), None]),
+            vec![0, 0]
+        );
+    }
+
+    #[test]
+    fn coupons_are_only_redeemable_inside_their_window() {
+        let now = chrono::Utc::now();
+        let mut coupon = CouponRecord {
+            id: uuid::Uuid::new_v4(),
+            code: "LAUNCH".into(),
+            description: None,
+            discount_type: "percent".into(),
+            percent_off: Some(20),
+            amount_off_cents: None,
+            currency: None,
+            course_id: None,
+            category_id: None,
+            starts_at: Some(now - chrono::Duration::days(1)),
+            expires_at: Some(now + chrono::Duration::days(1)),
+            max_redemptions: None,
+            max_redemptions_per_user: None,
+            active: true,
+            times_redeemed: 0,
+            created_by: None,
+            created_at: now,
+            updated_at: None,
+        };
+        assert!(check_redeemable(&coupon, now).is_ok());
+        assert!(check_redeemable(&coupon, now + chrono::Duration::days(2)).is_err());
+        assert!(check_redeemable(&coupon, now - chrono::Duration::days(2)).is_err());
+        coupon.active = false;
+        assert!(check_redeemable(&coupon, now).is_err());
+
+        coupon.discount_type = "fixed".into();
+        coupon.percent_off = None;
+        coupon.amount_off_cents = Some(500);
+        coupon.currency = Some("EUR".into());
+        assert_eq!(discount_of(&coupon, "EUR").unwrap(), Discount::Fixed(500));
+        assert!(discount_of(&coupon, "USD").is_err());
+    }
+}
@@ -0,0 +1,452 @@
+//! Course export and import as portable archives
+//!
+//! See `pkg::course_archive` for the archive format. Exports bundle every
+//! local file the course references; imports store those files through
+//! `Storage`, remap every reference to its new location, and create the
+//! course tree in one transaction as a draft owned by the importer.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::applications::lessons::subtitles::normalize_language;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::course_archive::{
+    self, ARCHIVE_FORMAT, ARCHIVE_VERSION, CourseArchive, CourseManifest, ManifestAssignment,
+    ManifestContent, ManifestCourse, ManifestLesson, ManifestMedia, ManifestModule, ManifestOption,
+    ManifestPrice, ManifestQuestion, ManifestSubtitle,
+};
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::logger::warn;
+use crate::pkg::private_media;
+use crate::pkg::subtitles::{self, SubtitleFormat};
+use crate::pkg::upload::{LocalFsStorage, PUBLIC_UPLOADS_DIR, Storage, split_public_upload_url};
+use crate::repositories::courses::{
+    CourseStatus, CoursesRepository, CreateCourseRecord, ImportCourseRecord, ImportLessonData,
+    ImportModuleData, ImportPriceData, ImportSubtitleData,
+};
+use crate::repositories::lesson_subtitles::LessonSubtitlesRepository;
+use crate::repositories::modules::{
+    CreateLessonAssignmentData, CreateLessonContentData, CreateLessonDeepData,
+    CreateLessonQuestionData, CreateLessonQuestionOptionData, ModulesRepository,
+};
+use crate::types::course_types::Course;
+
+use super::load_managed_course;
+
+/// Export a course the user manages; returns its slug and the ZIP bytes.
+pub async fn export_course(
+    courses: &dyn CoursesRepository,
+    modules: &dyn ModulesRepository,
+    subtitles: &dyn LessonSubtitlesRepository,
+    user: &AuthUser,
+    id: uuid::Uuid,
+    private_root: &Path,
+) -> AppResult<(String, Vec<u8>)> {
+    load_managed_course(courses, user, id).await?;
+    export_course_archive(courses, modules, subtitles, id, private_root).await
+}
+
+/// Export any course without an access check (used by the CLI).
+pub async fn export_course_archive(
+    courses: &dyn CoursesRepository,
+    modules: &dyn ModulesRepository,
+    subtitles: &dyn LessonSubtitlesRepository,
+    id: uuid::Uuid,
+    private_root: &Path,
+) -> AppResult<(String, Vec<u8>)> {
+    let course = courses
+        .find_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Course not found".into()))?;
+    let tree = modules.list_by_course_deep(id).await?;
+    let prices = courses.list_prices(id).await?;
+
+    let mut manifest_modules = Vec::with_capacity(tree.len());
+    for module in tree {
+        let mut lessons = Vec::with_capacity(module.lessons.len());
+        for deep in module.lessons {
+            let tracks = subtitles.list_by_lesson(deep.lesson.id).await?;
+            let lesson = deep.lesson;
+            lessons.push(ManifestLesson {
+                title: lesson.title,
+                description: lesson.description,
+                content: lesson.content,
+                video_url: lesson.video_url,
+                duration: lesson.duration,
+                position: lesson.position,
+                is_free: lesson.is_free,
+                published: lesson.published,
+                contents: deep
+                    .contents
+                    .into_iter()
+                    .map(|c| ManifestContent {
+                        title: c.title,
+                        content_type: c.content_type,
+                        url: c.url,
+                        file_size: c.file_size,
+                        filename: c.filename,
+                        position: c.position,
+                        is_private: c.is_private,
+                    })
+                    .collect(),
+                questions: deep
+                    .questions
+                    .into_iter()
+                    .map(|(q, options)| ManifestQuestion {
+                        question_text: q.question_text,
+                        position: q.position,
+                        options: options
+                            .into_iter()
+                            .map(|o| ManifestOption {
+                                option_text: o.option_text,
+                                is_correct: o.is_correct,
+                                position: o.position,
+                            })
+                            .collect(),
+                    })
+                    .collect(),
+                assignment: deep.assignment.map(|a| ManifestAssignment {
+                    title: a.title,
+                    description: a.description,
+                }),
+                subtitles: tracks
+                    .into_iter()
+                    .map(|t| ManifestSubtitle {
+                        language: t.language,
+                        label: t.label,
+                        vtt: t.vtt,
+                    })
+                    .collect(),
+            });
+        }
+        manifest_modules.push(ManifestModule {
+            title: module.module.title,
+            description: module.module.description,
+            position: module.module.position,
+            lessons,
+        });
+    }
+
+    let mut manifest = CourseManifest {
+        format: ARCHIVE_FORMAT.to_string(),
+        version: ARCHIVE_VERSION,
+        exported_at: chrono::Utc::now(),
+        course: ManifestCourse {
+            slug: course.slug.clone(),
+            title: course.title,
+            description: course.description,
+            excerpt: course.excerpt,
+            thumbnail: course.thumbnail,
+            price: course.price,
+            original_price: course.original_price,
+            duration: course.duration,
+            outcomes: course.outcomes.unwrap_or_default(),
+            category: course.category,
+            tags: course.tags.unwrap_or_default(),
+            prices: prices
+                .into_iter()
+                .map(|p| ManifestPrice {
+                    price: p.price,
+                    original_price: p.original_price,
+                })
+                .collect(),
+        },
+        modules: manifest_modules,
+        media: Vec::new(),
+    };
+    let files = bundle_media(&mut manifest, private_root).await?;
+
+    let bytes =
+        tokio::task::spawn_blocking(move || course_archive::write_archive(&manifest, &files))
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))??;
+    Ok((course.slug, bytes))
+}
+
+/// Read every local file the manifest references into the archive and list
+/// it in `manifest.media`. External URLs and missing public files stay plain
+/// references; a missing private file fails the export, since imports refuse
+/// private contents whose file is not bundled.
+async fn bundle_media(
+    manifest: &mut CourseManifest,
+    private_root: &Path,
+) -> AppResult<Vec<(String, Vec<u8>)>> {
+    let mut references: Vec<(String, bool)> = Vec::new();
+    references.extend(manifest.course.thumbnail.iter().map(|t| (t.clone(), false)));
+    for lesson in manifest.modules.iter().flat_map(|m| m.lessons.iter()) {
+        references.extend(lesson.video_url.iter().map(|v| (v.clone(), false)));
+        references.extend(
+            lesson
+                .contents
+                .iter()
+                .map(|c| (c.url.clone(), c.is_private)),
+        );
+    }
+
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut files: Vec<(String, Vec<u8>)> = Vec::new();
+    for (reference, private) in references {
+        if !seen.insert(reference.clone()) {
+            continue;
+        }
+        let path = if private {
+            private_media::resolve_key(private_root, &reference).ok()
+        } else {
+            split_public_upload_url(&reference).and_then(|(_, relative)| {
+                private_media::resolve_key(Path::new(PUBLIC_UPLOADS_DIR), relative).ok()
+            })
+        };
+        let bytes = match path {
+            Some(path) => match tokio::fs::read(&path).await {
+                Ok(bytes) => Some(bytes),
+                Err(e) => {
+                    warn(format!("Not bundling media {}: {}", path.display(), e));
+                    None
+                }
+            },
+            None => None,
+        };
+        let Some(bytes) = bytes else {
+            if private {
+                return Err(AppError::Conflict(format!(
+                    "Private file {} is missing from storage; upload it again before exporting",
+                    reference
+                )));
+            }
+            continue;
+        };
+        let archive_path = course_archive::media_path(files.len(), &reference);
+        manifest.media.push(ManifestMedia {
+            reference,
+            path: archive_path.clone(),
+            private,
+        });
+        files.push((archive_path, bytes));
+    }
+    Ok(files)
+}
+
+/// Import an archive as a new draft course owned by `instructor_id`.
+///
+/// `slug` overrides the manifest's slug, e.g. when it is taken in this
+/// environment. Archives (and their inflated contents) are limited to `max_bytes`.
+pub async fn import_course(
+    courses: &dyn CoursesRepository,
+    instructor_id: uuid::Uuid,
+    bytes: Vec<u8>,
+    slug: Option<String>,
+    private_root: &Path,
+    max_bytes: usize,
+) -> AppResult<Course> {
+    let archive =
+        tokio::task::spawn_blocking(move || course_archive::read_archive(&bytes, max_bytes as u64))
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))??;
+    let CourseArchive { manifest, files } = archive;
+
+    let slug = slug
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| manifest.course.slug.clone());
+    if courses.find_by_slug(&slug).await?.is_some() {
+        return Err(AppError::Conflict(format!(
+            "Slug '{}' is already taken; import with a different slug",
+            slug
+        )));
+    }
+    // Parse subtitles before any file is stored so bad archives leave nothing behind
+    let modules = import_modules(&manifest.modules)?;
+
+    let mut stored = StoredMedia::default();
+    if let Err(err) = stored.store(&manifest.media, &files, private_root).await {
+        stored.remove().await;
+        return Err(err);
+    }
+
+    let course = manifest.course;
+    let record = ImportCourseRecord {
+        course: CreateCourseRecord {
+            slug,
+            title: course.title,
+            description: course.description,
+            excerpt: course.excerpt,
+            thumbnail: course.thumbnail.map(|t| stored.remap(t)),
+            price: course.price,
+            original_price: course.original_price,
+            duration: course.duration,
+            featured: false,
+            status: Some(CourseStatus::Draft.as_str().to_string()),
+            instructor_id,
+            outcomes: Some(course.outcomes),
+            category: course.category,
+            tags: Some(course.tags),
+        },
+        prices: course
+            .prices
+            .into_iter()
+            .map(|p| ImportPriceData {
+                price: p.price,
+                original_price: p.original_price,
+            })
+            .collect(),
+        modules: modules
+            .into_iter()
+            .map(|mut module| {
+                for entry in &mut module.lessons {
+                    let lesson = &mut entry.lesson;
+                    lesson.video_url = lesson.video_url.take().map(|v| stored.remap(v));
+                    for content in &mut lesson.contents {
+                        content.url = stored.remap(std::mem::take(&mut content.url));
+                    }
+                }
+                module
+            })
+            .collect(),
+    };
+
+    let id = match courses.import_deep(record).await {
+        Ok(id) => id,
+        Err(err) => {
+            stored.remove().await;
+            return Err(err);
+        }
+    };
+    let course = courses
+        .find_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::Internal("Imported course disappeared".into()))?;
+    Ok(course.into())
+}
+
+fn import_modules(modules: &[ManifestModule]) -> AppResult<Vec<ImportModuleData>> {
+    modules
+        .iter()
+        .map(|m| {
+            Ok(ImportModuleData {
+                title: m.title.clone(),
+                description: m.description.clone(),
+                position: m.position,
+                lessons: m
+                    .lessons
+                    .iter()
+                    .map(import_lesson)
+                    .collect::<AppResult<_>>()?,
+            })
+        })
+        .collect()
+}
+
+fn import_lesson(l: &ManifestLesson) -> AppResult<ImportLessonData> {
+    let subtitles = l
+        .subtitles
+        .iter()
+        .map(|s| {
+            let cues = subtitles::parse(&s.vtt, SubtitleFormat::Vtt)?;
+            Ok(ImportSubtitleData {
+                language: normalize_language(&s.language)?,
+                label: s.label.clone(),
+                vtt: subtitles::to_vtt(&cues),
+                transcript: subtitles::transcript(&cues),
+                cue_count: i32::try_from(cues.len()).unwrap_or(i32::MAX),
+            })
+        })
+        .collect::<AppResult<_>>()?;
+    Ok(ImportLessonData {
+        lesson: CreateLessonDeepData {
+            title: l.title.clone(),
+            description: l.description.clone(),
+            content: l.content.clone(),
+            video_url: l.video_url.clone(),
+            duration: l.duration.clone(),
+            position: l.position,
+            is_free: l.is_free,
+            published: l.published,
+            contents: l
+                .contents
+                .iter()
+                .map(|c| CreateLessonContentData {
+                    title: c.title.clone(),
+                    content_type: c.content_type.clone(),
+                    url: c.url.clone(),
+                    file_size: c.file_size,
+                    filename: c.filename.clone(),
+                    position: c.position,
+                    is_private: c.is_private,
+                })
+                .collect(),
+            questions: l
+                .questions
+                .iter()
+                .map(|q| CreateLessonQuestionData {
+                    question_text: q.question_text.clone(),
+                    position: q.position,
+                    options: q
+                        .options
+                        .iter()
+                        .map(|o| CreateLessonQuestionOptionData {
+                            option_text: o.option_text.clone(),
+                            is_correct: o.is_correct,
+                            position: o.position,
+                        })
+                        .collect(),
+                })
+                .collect(),
+            assignment: l.assignment.as_ref().map(|a| CreateLessonAssignmentData {
+                title: a.title.clone(),
+                description: a.description.clone(),
+            }),
+        },
+        subtitles,
+    })
+}
+
+/// Files stored during an import and the references they replace
+#[derive(Default)]
+struct StoredMedia {
+    rewrites: HashMap<String, String>,
+    created: Vec<PathBuf>,
+}
+
+impl StoredMedia {
+    async fn store(
+        &mut self,
+        media: &[ManifestMedia],
+        files: &HashMap<String, Vec<u8>>,
+        private_root: &Path,
+    ) -> AppResult<()> {
+        let public = LocalFsStorage::new(PUBLIC_UPLOADS_DIR);
+        for entry in media {
+            // read_archive guarantees every listed path is present
+            let bytes = files
+                .get(&entry.path)
+                .ok_or_else(|| AppError::Internal(format!("{} missing", entry.path)))?;
+            let new_reference = if entry.private {
+                let key = private_media::save_lesson_bytes(private_root, bytes, Some(&entry.path))
+                    .await?;
+                self.created
+                    .push(private_media::resolve_key(private_root, &key)?);
+                key
+            } else {
+                let url = public.save_bytes(bytes, Some(&entry.path)).await?;
+                if let Some((_, relative)) = split_public_upload_url(&url) {
+                    self.created
+                        .push(Path::new(PUBLIC_UPLOADS_DIR).join(relative));
+                }
+                url
+            };
+            self.rewrites.insert(entry.reference.clone(), new_reference);
+        }
+        Ok(())
+    }
+
+    fn remap(&self, reference: String) -> String {
+        self.rewrites.get(&reference).cloned().unwrap_or(reference)
+    }
+
+    /// Best-effort removal of stored files when the import fails
+    async fn remove(&self) {
+        for path in &self.created {
+            let _ = tokio::fs::remove_file(path).await;
+        }
+    }
+}
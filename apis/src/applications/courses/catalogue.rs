@@ -0,0 +1,117 @@
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::money::normalize_currency;
+use crate::repositories::courses::{CourseCatalogFilters, CourseCatalogSort, CoursesRepository};
+use crate::types::course_types::{Course, CourseCatalogQuery, Page, PageMeta};
+
+/// List published courses for the public catalogue with filters, sorting and pagination.
+///
+/// Price filters and sorting use each course's price in the query currency,
+/// `default_currency` when the query names none.
+pub async fn list_course_catalogue(
+    repo: &dyn CoursesRepository,
+    query: CourseCatalogQuery,
+    default_currency: &str,
+) -> AppResult<Page<Course>> {
+    let page = query.page.unwrap_or(1);
+    let per_page = query.per_page.unwrap_or(12);
+    let safe_page = if page < 1 { 1 } else { page };
+    let safe_per_page = if per_page < 1 { 12 } else { per_page.min(100) };
+    let offset = page_offset(safe_page, safe_per_page)?;
+
+    let sort = match query.sort.as_deref() {
+        Some(s) => CourseCatalogSort::parse(s).ok_or_else(|| {
+            AppError::BadRequest(
+                "sort must be one of newest, popular, price_asc, price_desc, top_rated".into(),
+            )
+        })?,
+        None => CourseCatalogSort::default(),
+    };
+    let currency = match query.currency.as_deref() {
+        Some(c) => normalize_currency(c).ok_or_else(|| {
+            AppError::BadRequest(format!("'{}' is not an ISO 4217 currency code", c))
+        })?,
+        None => default_currency.to_string(),
+    };
+    if query.min_price.is_some_and(|p| p < 0) || query.max_price.is_some_and(|p| p < 0) {
+        return Err(AppError::BadRequest(
+            "Price filters must not be negative".into(),
+        ));
+    }
+    if query.min_rating.is_some_and(|r| !(0.0..=5.0).contains(&r)) {
+        return Err(AppError::BadRequest(
+            "min_rating must be between 0 and 5".into(),
+        ));
+    }
+    if let (Some(min), Some(max)) = (query.min_price, query.max_price)
+        && min > max
+    {
+        return Err(AppError::BadRequest(
+            "min_price must not exceed max_price".into(),
+        ));
+    }
+
+    let filters = CourseCatalogFilters {
+        category_id: query.category_id,
+        tags: parse_tags(query.tags.as_deref()),
+        currency,
+        min_price: query.min_price,
+        max_price: query.max_price,
+        free_only: query.free.unwrap_or(false),
+        featured: query.featured,
+        instructor_id: query.instructor_id,
+        min_rating: query.min_rating,
+    };
+
+    let (records, total) = repo
+        .list_published_paginated(filters, sort, offset, safe_per_page)
+        .await?;
+    let items: Vec<Course> = records.into_iter().map(Into::into).collect();
+    let total_pages = if total == 0 {
+        1
+    } else {
+        (total + safe_per_page - 1) / safe_per_page
+    };
+
+    Ok(Page {
+        items,
+        meta: PageMeta {
+            page: safe_page,
+            per_page: safe_per_page,
+            total,
+            total_pages,
+        },
+    })
+}
+
+/// Rows to skip before `page` (1-based), refusing pages too far to address
+pub(crate) fn page_offset(page: i64, per_page: i64) -> AppResult<i64> {
+    (page - 1)
+        .checked_mul(per_page)
+        .ok_or_else(|| AppError::BadRequest("page is out of range".into()))
+}
+
+/// Split a comma-separated tag list, dropping blanks and duplicates
+fn parse_tags(raw: Option<&str>) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in raw.unwrap_or_default().split(',').map(str::trim) {
+        if !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_offset_refuses_pages_past_i64() {
+        assert_eq!(page_offset(1, 12).unwrap(), 0);
+        assert_eq!(page_offset(3, 100).unwrap(), 200);
+        assert!(matches!(
+            page_offset(i64::MAX, 100),
+            Err(AppError::BadRequest(_))
+        ));
+    }
+}
@@ -1,13 +1,28 @@
-use crate::pkg::error::AppResult;
-use crate::repositories::courses::{CoursesRepository, CreateCourseRecord};
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::courses::{CourseStatus, CoursesRepository, CreateCourseRecord};
 use crate::types::course_types::CreateCourseRequest;
 
+use super::pricing::check_original_price;
+
 /// Create a new course and return its id.
+///
+/// Courses always start as drafts and are published through the publishing
+/// workflow once they pass its checks.
 pub async fn create_course(
     repo: &dyn CoursesRepository,
     instructor_id: uuid::Uuid,
     input: CreateCourseRequest,
 ) -> AppResult<String> {
+    if input
+        .status
+        .as_deref()
+        .is_some_and(|s| s != CourseStatus::Draft.as_str())
+    {
+        return Err(AppError::BadRequest(
+            "New courses are created as drafts; publish them with the publish endpoint".into(),
+        ));
+    }
+    check_original_price(&input.price, input.original_price.as_ref())?;
     let slug = repo
         .create(CreateCourseRecord {
             slug: input.slug,
@@ -19,7 +34,7 @@ pub async fn create_course(
             original_price: input.original_price,
             duration: input.duration,
             featured: input.featured,
-            status: Some(input.status.unwrap_or("draft".to_string())),
+            status: Some(CourseStatus::Draft.as_str().to_string()),
             instructor_id,
             // Ensure NOT NULL constraint: default to empty array when missing
             outcomes: Some(input.outcomes.unwrap_or_default()),
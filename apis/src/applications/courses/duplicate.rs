@@ -0,0 +1,288 @@
+//! Deep course duplication
+//!
+//! Copies a course with its whole module/lesson tree into a new draft, e.g. to
+//! run a new cohort or a translated version. Uploaded files are either shared
+//! with the source (the default) or copied so the two courses can diverge.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::logger::warn;
+use crate::pkg::private_media;
+use crate::pkg::upload::{PUBLIC_UPLOADS_DIR, split_public_upload_url};
+use crate::repositories::courses::{CourseRecord, CoursesRepository, DuplicateCourseRecord};
+use crate::repositories::modules::{ModuleDeepRecord, ModulesRepository};
+use crate::types::course_types::{Course, DuplicateCourseRequest, DuplicateMediaMode};
+
+use super::load_managed_course;
+
+/// How many `-copy-<n>` slugs are tried before falling back to a random suffix
+const MAX_SLUG_ATTEMPTS: usize = 20;
+
+/// Copy a course the user manages into a new draft owned by the same instructor.
+///
+/// `private_root` is the private media directory, needed when files are copied.
+pub async fn duplicate_course(
+    courses: &dyn CoursesRepository,
+    modules: &dyn ModulesRepository,
+    user: &AuthUser,
+    id: uuid::Uuid,
+    input: DuplicateCourseRequest,
+    private_root: &Path,
+) -> AppResult<Course> {
+    let source = load_managed_course(courses, user, id).await?;
+
+    let slug = match input.slug {
+        Some(slug) => {
+            if courses.find_by_slug(&slug).await?.is_some() {
+                return Err(AppError::Conflict(format!(
+                    "Slug '{}' is already taken",
+                    slug
+                )));
+            }
+            slug
+        }
+        None => free_copy_slug(courses, &source.slug).await?,
+    };
+    let title = input
+        .title
+        .unwrap_or_else(|| format!("{} (copy)", source.title));
+
+    let copied = match input.media {
+        DuplicateMediaMode::Reference => CopiedMedia::default(),
+        DuplicateMediaMode::Copy => {
+            let tree = modules.list_by_course_deep(id).await?;
+            copy_media(&source, &tree, private_root).await?
+        }
+    };
+
+    let result = courses
+        .duplicate_deep(
+            id,
+            DuplicateCourseRecord {
+                slug,
+                title,
+                instructor_id: source.instructor_id,
+                media_rewrites: copied.rewrites.clone(),
+            },
+        )
+        .await;
+    let new_id = match result {
+        Ok(Some(new_id)) => new_id,
+        Ok(None) => {
+            copied.remove().await;
+            return Err(AppError::NotFound("Course not found".into()));
+        }
+        Err(err) => {
+            copied.remove().await;
+            return Err(err);
+        }
+    };
+
+    let record = courses
+        .find_by_id(new_id)
+        .await?
+        .ok_or_else(|| AppError::Internal("Duplicated course disappeared".into()))?;
+    Ok(record.into())
+}
+
+/// First unused slug among `<slug>-copy`, `<slug>-copy-2`, ...
+async fn free_copy_slug(courses: &dyn CoursesRepository, slug: &str) -> AppResult<String> {
+    for n in 1..=MAX_SLUG_ATTEMPTS {
+        let candidate = copy_slug(slug, n);
+        if courses.find_by_slug(&candidate).await?.is_none() {
+            return Ok(candidate);
+        }
+    }
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    Ok(format!("{}-copy-{}", slug, &suffix[..8]))
+}
+
+fn copy_slug(slug: &str, n: usize) -> String {
+    if n == 1 {
+        format!("{}-copy", slug)
+    } else {
+        format!("{}-copy-{}", slug, n)
+    }
+}
+
+/// Files copied for a duplicate and the references to rewrite in it
+#[derive(Default)]
+struct CopiedMedia {
+    rewrites: Vec<(String, String)>,
+    created: Vec<PathBuf>,
+}
+
+impl CopiedMedia {
+    /// Best-effort removal of the copies when the duplicate is not stored
+    async fn remove(&self) {
+        for path in &self.created {
+            let _ = tokio::fs::remove_file(path).await;
+        }
+    }
+}
+
+/// Copy every local file the course references: the thumbnail, lesson video
+/// URLs and lesson contents, public or private. External URLs and files that
+/// are missing on disk keep pointing at the source.
+async fn copy_media(
+    course: &CourseRecord,
+    tree: &[ModuleDeepRecord],
+    private_root: &Path,
+) -> AppResult<CopiedMedia> {
+    let mut public: Vec<&str> = Vec::new();
+    let mut private: Vec<&str> = Vec::new();
+    public.extend(course.thumbnail.as_deref());
+    for lesson in tree.iter().flat_map(|m| m.lessons.iter()) {
+        public.extend(lesson.lesson.video_url.as_deref());
+        for content in &lesson.contents {
+            if content.is_private {
+                private.push(&content.url);
+            } else {
+                public.push(&content.url);
+            }
+        }
+    }
+
+    let mut copied = CopiedMedia::default();
+    let mut seen: HashSet<&str> = HashSet::new();
+    let public_root = Path::new(PUBLIC_UPLOADS_DIR);
+    let result: AppResult<()> = async {
+        for url in public {
+            let Some((prefix, relative)) = split_public_upload_url(url) else {
+                continue;
+            };
+            if !seen.insert(url) {
+                continue;
+            }
+            if let Some(new_relative) =
+                copy_file(public_root, relative, &mut copied.created).await?
+            {
+                copied
+                    .rewrites
+                    .push((url.to_string(), format!("{}{}", prefix, new_relative)));
+            }
+        }
+        for key in private {
+            if !seen.insert(key) {
+                continue;
+            }
+            if let Some(new_key) = copy_file(private_root, key, &mut copied.created).await? {
+                copied.rewrites.push((key.to_string(), new_key));
+            }
+        }
+        Ok(())
+    }
+    .await;
+
+    match result {
+        Ok(()) => Ok(copied),
+        Err(err) => {
+            copied.remove().await;
+            Err(err)
+        }
+    }
+}
+
+/// Copy the file at `relative` under `root` to a fresh name in the same
+/// directory and return its new relative path; `None` if there is no such file.
+///
+/// Processed image variants (`<stem>-<label>.webp`) are copied together with
+/// their siblings under one new stem so responsive sizes stay linked.
+async fn copy_file(
+    root: &Path,
+    relative: &str,
+    created: &mut Vec<PathBuf>,
+) -> AppResult<Option<String>> {
+    let Ok(source) = private_media::resolve_key(root, relative) else {
+        return Ok(None);
+    };
+    if !tokio::fs::try_exists(&source).await.unwrap_or(false) {
+        warn(format!(
+            "Not copying missing media file {}",
+            source.display()
+        ));
+        return Ok(None);
+    }
+    let (dir, file_name) = match relative.rsplit_once('/') {
+        Some((dir, file_name)) => (Some(dir), file_name),
+        None => (None, relative),
+    };
+    let parent = source
+        .parent()
+        .ok_or_else(|| AppError::Internal("Media file has no parent directory".into()))?;
+
+    let new_stem = uuid::Uuid::new_v4();
+    let new_name = match variant_stem(file_name) {
+        Some(stem) => {
+            let mut entries = tokio::fs::read_dir(parent)
+                .await
+                .map_err(|e| AppError::Internal(e.to_string()))?;
+            while let Some(entry) = entries
+                .next_entry()
+                .await
+                .map_err(|e| AppError::Internal(e.to_string()))?
+            {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if let Some(rest) = name.strip_prefix(stem).filter(|r| r.starts_with('-')) {
+                    copy_one(
+                        &entry.path(),
+                        &parent.join(format!("{}{}", new_stem, rest)),
+                        created,
+                    )
+                    .await?;
+                }
+            }
+            format!("{}{}", new_stem, &file_name[stem.len()..])
+        }
+        None => {
+            let new_name = match Path::new(file_name).extension().and_then(|e| e.to_str()) {
+                Some(ext) => format!("{}.{}", new_stem, ext),
+                None => new_stem.to_string(),
+            };
+            copy_one(&source, &parent.join(&new_name), created).await?;
+            new_name
+        }
+    };
+
+    Ok(Some(match dir {
+        Some(dir) => format!("{}/{}", dir, new_name),
+        None => new_name,
+    }))
+}
+
+async fn copy_one(from: &Path, to: &Path, created: &mut Vec<PathBuf>) -> AppResult<()> {
+    tokio::fs::copy(from, to)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to copy media file: {}", e)))?;
+    created.push(to.to_path_buf());
+    Ok(())
+}
+
+/// The shared `<uuid>` stem of a processed image variant file name
+fn variant_stem(file_name: &str) -> Option<&str> {
+    let stem = file_name.get(..36)?;
+    uuid::Uuid::parse_str(stem).ok()?;
+    file_name[36..].starts_with('-').then_some(stem)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copy_slugs_count_from_the_second_copy() {
+        assert_eq!(copy_slug("rust-101", 1), "rust-101-copy");
+        assert_eq!(copy_slug("rust-101", 2), "rust-101-copy-2");
+    }
+
+    #[test]
+    fn variant_stems_are_only_found_on_processed_images() {
+        let stem = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+        assert_eq!(variant_stem(&format!("{}-640w.webp", stem)), Some(stem));
+        assert_eq!(variant_stem(&format!("{}.pdf", stem)), None);
+        assert_eq!(variant_stem("notes.pdf"), None);
+    }
+}
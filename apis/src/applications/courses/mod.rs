@@ -1,22 +1,95 @@
 //! Course application services - split by function files
 
+pub mod archive;
+pub mod catalogue;
 pub mod course_list_by_instructor;
 pub mod create;
 pub mod delete_by_id;
+pub mod duplicate;
 pub mod get_by_id;
 pub mod get_by_slug;
+pub mod package_import;
+pub mod prerequisites;
+pub mod pricing;
+pub mod publishing;
+pub mod reviews;
+pub mod revisions;
 pub mod update_by_id;
 
+pub use archive::{export_course, import_course};
+pub use catalogue::list_course_catalogue;
 pub use course_list_by_instructor::list_courses_paginated_by_instructor;
 pub use create::create_course;
 pub use delete_by_id::delete_course_by_id;
+pub use duplicate::duplicate_course;
 pub use get_by_id::get_course_by_id;
 pub use get_by_slug::get_course_by_slug;
+pub use package_import::import_package;
+pub use prerequisites::{list_course_prerequisites, set_course_prerequisites};
+pub use pricing::{delete_course_price, list_course_prices, set_course_price};
+pub use publishing::{
+    archive_course, cancel_scheduled_publish, publish_course, schedule_course_publish,
+    unpublish_course,
+};
+pub use reviews::{
+    create_course_review, delete_course_review, delete_course_review_reply, list_course_reviews,
+    list_pending_course_reviews, moderate_course_review, reply_to_course_review,
+    update_course_review, vote_course_review_helpful,
+};
+pub use revisions::{
+    diff_course_revision, discard_course_revision, get_open_course_revision,
+    publish_course_revision, start_course_revision, update_course_revision,
+};
 pub use update_by_id::update_course_by_id;
 
-use crate::repositories::courses::CourseRecord;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::courses::{CourseRecord, CourseStatus, CoursesRepository};
 use crate::types::course_types::{Course, Instructor};
 
+/// Load a course `user` may manage (its instructor or an admin)
+pub(crate) async fn load_managed_course(
+    repo: &dyn CoursesRepository,
+    user: &AuthUser,
+    id: uuid::Uuid,
+) -> AppResult<CourseRecord> {
+    let course = repo
+        .find_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Course not found".into()))?;
+    if user.role != "admin" && user.user_id != course.instructor_id {
+        return Err(AppError::Forbidden(
+            "Only the course instructor can manage this course".into(),
+        ));
+    }
+    Ok(course)
+}
+
+/// Refuse in-place edits to the content of a course with `status`: a
+/// published course is edited through a draft revision so students never see
+/// half-finished changes
+pub(crate) fn ensure_editable_in_place(status: &str) -> AppResult<()> {
+    if status == CourseStatus::Published.as_str() {
+        return Err(AppError::Conflict(
+            "Published courses are edited through a draft revision; open one with POST /api/courses/:id/revisions".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Load a course whose content may be edited in place
+pub(crate) async fn load_editable_course(
+    repo: &dyn CoursesRepository,
+    id: uuid::Uuid,
+) -> AppResult<CourseRecord> {
+    let course = repo
+        .find_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Course not found".into()))?;
+    ensure_editable_in_place(&course.status)?;
+    Ok(course)
+}
+
 impl From<CourseRecord> for Course {
     fn from(record: CourseRecord) -> Self {
         Course {
@@ -34,6 +107,8 @@ impl From<CourseRecord> for Course {
             students: 0,
             featured: record.featured,
             view_count: record.view_count,
+            rating_average: record.rating_average,
+            rating_count: record.rating_count,
             status: record.status,
             outcomes: record.outcomes,
             category: record.category,
@@ -48,8 +123,24 @@ impl From<CourseRecord> for Course {
                 role: i.role,
             }),
             published_at: record.published_at,
+            scheduled_publish_at: record.scheduled_publish_at,
             created_at: record.created_at,
             updated_at: record.updated_at,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_published_courses_refuse_in_place_edits() {
+        assert!(ensure_editable_in_place(CourseStatus::Draft.as_str()).is_ok());
+        assert!(ensure_editable_in_place(CourseStatus::Archived.as_str()).is_ok());
+        assert!(matches!(
+            ensure_editable_in_place(CourseStatus::Published.as_str()),
+            Err(AppError::Conflict(_))
+        ));
+    }
+}
@@ -0,0 +1,280 @@
+//! SCORM / IMS Common Cartridge import
+//!
+//! See `pkg::content_package` for how a package maps onto modules and
+//! lessons. Launch files and their dependencies are stored through `Storage`
+//! under `uploads/packages/<import id>/`, keeping the package's directory
+//! layout so relative links between its pages keep working.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::pkg::content_package::{self, LessonSource, Package, PackageLesson, PackageNotice};
+use crate::pkg::course_archive::read_zip_entries;
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::logger::info;
+use crate::pkg::money::Money;
+use crate::pkg::upload::{LocalFsStorage, PUBLIC_UPLOADS_DIR, Storage};
+use crate::repositories::courses::{
+    CourseStatus, CoursesRepository, CreateCourseRecord, ImportCourseRecord, ImportLessonData,
+    ImportModuleData,
+};
+use crate::repositories::modules::{
+    CreateLessonContentData, CreateLessonDeepData, CreateLessonQuestionData,
+    CreateLessonQuestionOptionData,
+};
+use crate::types::course_types::{PackageImportNotice, PackageImportResult};
+
+/// Directory under the public uploads tree that package files are stored in
+const PACKAGES_DIR: &str = "packages";
+
+/// Longest slug derived from a package title
+const MAX_SLUG_LEN: usize = 80;
+
+/// Import a SCORM 1.2/2004 or Common Cartridge ZIP as a new draft course owned
+/// by `instructor_id`.
+///
+/// The slug defaults to one derived from the package title. Packages (and
+/// their inflated contents) are limited to `max_bytes`.
+pub async fn import_package(
+    courses: &dyn CoursesRepository,
+    instructor_id: uuid::Uuid,
+    bytes: Vec<u8>,
+    slug: Option<String>,
+    currency: &str,
+    max_bytes: usize,
+) -> AppResult<PackageImportResult> {
+    let (files, package) = tokio::task::spawn_blocking(move || {
+        let files = read_zip_entries(&bytes, max_bytes as u64, |_| true)?;
+        let package = content_package::parse_package(&files)?;
+        Ok::<_, AppError>((files, package))
+    })
+    .await
+    .map_err(|e| AppError::Internal(e.to_string()))??;
+    if package.modules.is_empty() {
+        return Err(AppError::Validation {
+            message: "Package contains nothing that can be imported".into(),
+            details: serde_json::to_value(notices(&package.log)).unwrap_or(serde_json::Value::Null),
+        });
+    }
+
+    let slug = slug
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| slugify(&package.title));
+    if courses.find_by_slug(&slug).await?.is_some() {
+        return Err(AppError::Conflict(format!(
+            "Slug '{}' is already taken; import with a different slug",
+            slug
+        )));
+    }
+
+    let import_dir = Path::new(PUBLIC_UPLOADS_DIR)
+        .join(PACKAGES_DIR)
+        .join(uuid::Uuid::new_v4().to_string());
+    let urls = match store_assets(&package, &files, &import_dir).await {
+        Ok(urls) => urls,
+        Err(err) => {
+            let _ = tokio::fs::remove_dir_all(&import_dir).await;
+            return Err(err);
+        }
+    };
+
+    let record = ImportCourseRecord {
+        course: CreateCourseRecord {
+            slug,
+            title: package.title.clone(),
+            description: package.description.clone().unwrap_or_default(),
+            excerpt: None,
+            thumbnail: None,
+            price: Money::zero(currency),
+            original_price: None,
+            duration: String::new(),
+            featured: false,
+            status: Some(CourseStatus::Draft.as_str().to_string()),
+            instructor_id,
+            outcomes: Some(Vec::new()),
+            category: None,
+            tags: Some(Vec::new()),
+        },
+        prices: Vec::new(),
+        modules: package
+            .modules
+            .iter()
+            .enumerate()
+            .map(|(position, module)| ImportModuleData {
+                title: module.title.clone(),
+                description: None,
+                position: position as i32,
+                lessons: module
+                    .lessons
+                    .iter()
+                    .enumerate()
+                    .map(|(position, lesson)| import_lesson(lesson, position as i32, &urls, &files))
+                    .collect(),
+            })
+            .collect(),
+    };
+
+    let id = match courses.import_deep(record).await {
+        Ok(id) => id,
+        Err(err) => {
+            let _ = tokio::fs::remove_dir_all(&import_dir).await;
+            return Err(err);
+        }
+    };
+    info(format!(
+        "Imported {} package '{}' as course {} ({} notice(s))",
+        package.kind.as_str(),
+        package.title,
+        id,
+        package.log.len()
+    ));
+    let course = courses
+        .find_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::Internal("Imported course disappeared".into()))?;
+    Ok(PackageImportResult {
+        course: course.into(),
+        package_type: package.kind.as_str().to_string(),
+        log: notices(&package.log),
+    })
+}
+
+/// Store every asset under `import_dir`; returns package path -> public URL
+async fn store_assets(
+    package: &Package,
+    files: &HashMap<String, Vec<u8>>,
+    import_dir: &Path,
+) -> AppResult<HashMap<String, String>> {
+    let mut urls = HashMap::with_capacity(package.assets.len());
+    for path in &package.assets {
+        // parse_package only lists files that are present
+        let bytes = files
+            .get(path)
+            .ok_or_else(|| AppError::Internal(format!("{} missing", path)))?;
+        let (dir, file_name) = match path.rsplit_once('/') {
+            Some((dir, file_name)) => (import_dir.join(dir), file_name),
+            None => (PathBuf::from(import_dir), path.as_str()),
+        };
+        let url = LocalFsStorage::new(dir)
+            .save_bytes_as(bytes, file_name)
+            .await?;
+        urls.insert(path.clone(), url);
+    }
+    Ok(urls)
+}
+
+fn import_lesson(
+    lesson: &PackageLesson,
+    position: i32,
+    urls: &HashMap<String, String>,
+    files: &HashMap<String, Vec<u8>>,
+) -> ImportLessonData {
+    let mut contents = Vec::new();
+    let mut questions = Vec::new();
+    match &lesson.source {
+        LessonSource::File { path, query } => {
+            let url = urls.get(path).cloned().unwrap_or_default();
+            contents.push(CreateLessonContentData {
+                title: lesson.title.clone(),
+                content_type: content_package::content_type(path).to_string(),
+                url: match query {
+                    Some(query) => format!("{}?{}", url, query),
+                    None => url,
+                },
+                file_size: files.get(path).map(|b| b.len() as i64),
+                filename: path.rsplit('/').next().map(str::to_string),
+                position: 0,
+                is_private: false,
+            });
+        }
+        LessonSource::Link { url } => contents.push(CreateLessonContentData {
+            title: lesson.title.clone(),
+            content_type: "text/html".to_string(),
+            url: url.clone(),
+            file_size: None,
+            filename: None,
+            position: 0,
+            is_private: false,
+        }),
+        LessonSource::Quiz(items) => {
+            questions = items
+                .iter()
+                .enumerate()
+                .map(|(position, q)| CreateLessonQuestionData {
+                    question_text: q.text.clone(),
+                    position: position as i32,
+                    options: q
+                        .options
+                        .iter()
+                        .enumerate()
+                        .map(|(position, o)| CreateLessonQuestionOptionData {
+                            option_text: o.text.clone(),
+                            is_correct: o.is_correct,
+                            position: position as i32,
+                        })
+                        .collect(),
+                })
+                .collect();
+        }
+    }
+    ImportLessonData {
+        lesson: CreateLessonDeepData {
+            title: lesson.title.clone(),
+            description: None,
+            content: None,
+            video_url: None,
+            duration: String::new(),
+            position,
+            is_free: false,
+            published: true,
+            contents,
+            questions,
+            assignment: None,
+        },
+        subtitles: Vec::new(),
+    }
+}
+
+fn notices(log: &[PackageNotice]) -> Vec<PackageImportNotice> {
+    log.iter()
+        .map(|n| PackageImportNotice {
+            item: n.item.clone(),
+            message: n.message.clone(),
+        })
+        .collect()
+}
+
+/// Lowercase ASCII slug of a title, e.g. "Safety Basics (2024)" -> "safety-basics-2024"
+fn slugify(title: &str) -> String {
+    let mut slug = String::with_capacity(title.len());
+    for ch in title.chars() {
+        if ch.is_ascii_alphanumeric() {
+            slug.push(ch.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.truncate(MAX_SLUG_LEN);
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        format!(
+            "imported-course-{}",
+            &uuid::Uuid::new_v4().simple().to_string()[..8]
+        )
+    } else {
+        slug.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugs_come_from_titles() {
+        assert_eq!(slugify("Safety Basics (2024)"), "safety-basics-2024");
+        assert_eq!(slugify("  Chimie générale "), "chimie-g-n-rale");
+        assert!(slugify("日本語").starts_with("imported-course-"));
+    }
+}
@@ -0,0 +1,109 @@
+//! Course prerequisites
+//!
+//! A course may require other courses to be completed first; its paid
+//! lessons stay locked until the learner holds their certificates. Buying
+//! and enrolling are not restricted, so bundles such as learning paths can
+//! sell a course together with its prerequisites.
+
+use std::collections::HashSet;
+
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::courses::{CoursePrerequisiteRecord, CoursesRepository};
+use crate::types::course_types::{CoursePrerequisite, SetCoursePrerequisitesRequest};
+
+use super::load_managed_course;
+
+/// Courses to complete before the course's lessons open
+pub async fn list_course_prerequisites(
+    repo: &dyn CoursesRepository,
+    course_id: uuid::Uuid,
+) -> AppResult<Vec<CoursePrerequisite>> {
+    repo.find_by_id(course_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Course not found".into()))?;
+    let items = repo.list_prerequisites(course_id).await?;
+    Ok(items.into_iter().map(CoursePrerequisite::from).collect())
+}
+
+/// Replace the course's prerequisites; a course may not end up requiring
+/// itself, directly or through other courses
+pub async fn set_course_prerequisites(
+    repo: &dyn CoursesRepository,
+    user: &AuthUser,
+    course_id: uuid::Uuid,
+    input: SetCoursePrerequisitesRequest,
+) -> AppResult<Vec<CoursePrerequisite>> {
+    load_managed_course(repo, user, course_id).await?;
+    let dependents: HashSet<uuid::Uuid> = repo
+        .list_dependent_ids(course_id)
+        .await?
+        .into_iter()
+        .collect();
+    check_prerequisite_ids(course_id, &input.course_ids, &dependents)?;
+    for id in &input.course_ids {
+        if repo.find_by_id(*id).await?.is_none() {
+            return Err(AppError::NotFound(format!("Course {} not found", id)));
+        }
+    }
+    repo.set_prerequisites(course_id, &input.course_ids).await?;
+    list_course_prerequisites(repo, course_id).await
+}
+
+/// Reject repeated ids and prerequisites that would form a cycle
+fn check_prerequisite_ids(
+    course_id: uuid::Uuid,
+    ids: &[uuid::Uuid],
+    dependents: &HashSet<uuid::Uuid>,
+) -> AppResult<()> {
+    let mut seen = HashSet::with_capacity(ids.len());
+    for id in ids {
+        if !seen.insert(*id) {
+            return Err(AppError::BadRequest(format!(
+                "Course {} is listed more than once",
+                id
+            )));
+        }
+        if *id == course_id {
+            return Err(AppError::BadRequest(
+                "A course cannot require itself".into(),
+            ));
+        }
+        if dependents.contains(id) {
+            return Err(AppError::BadRequest(format!(
+                "Course {} already requires this course",
+                id
+            )));
+        }
+    }
+    Ok(())
+}
+
+impl From<CoursePrerequisiteRecord> for CoursePrerequisite {
+    fn from(record: CoursePrerequisiteRecord) -> Self {
+        CoursePrerequisite {
+            course_id: record.course_id,
+            slug: record.slug,
+            title: record.title,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prerequisites_must_not_repeat_or_form_a_cycle() {
+        let course = uuid::Uuid::new_v4();
+        let basics = uuid::Uuid::new_v4();
+        let advanced = uuid::Uuid::new_v4();
+        let dependents = HashSet::from([advanced]);
+
+        assert!(check_prerequisite_ids(course, &[basics], &dependents).is_ok());
+        assert!(check_prerequisite_ids(course, &[], &dependents).is_ok());
+        assert!(check_prerequisite_ids(course, &[basics, basics], &dependents).is_err());
+        assert!(check_prerequisite_ids(course, &[course], &dependents).is_err());
+        assert!(check_prerequisite_ids(course, &[basics, advanced], &dependents).is_err());
+    }
+}
@@ -0,0 +1,146 @@
+//! Course prices in several currencies
+//!
+//! A course's own price fixes its base currency. Instructors may add a
+//! price list with fixed prices in further currencies; the catalogue and
+//! checkout use the entry for the buyer's currency and never convert
+//! between currencies.
+
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::money::{Money, normalize_currency};
+use crate::repositories::courses::{CoursePriceRecord, CourseRecord, CoursesRepository};
+use crate::types::course_types::{CoursePrice, CoursePriceList, SetCoursePriceRequest};
+
+use super::load_managed_course;
+
+/// Every price of a course, base price first
+pub async fn list_course_prices(
+    repo: &dyn CoursesRepository,
+    course_id: uuid::Uuid,
+) -> AppResult<CoursePriceList> {
+    let course = repo
+        .find_by_id(course_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Course not found".into()))?;
+    let entries = repo.list_prices(course_id).await?;
+    Ok(price_list(&course, entries))
+}
+
+/// Set the course's price in a currency other than its base currency
+pub async fn set_course_price(
+    repo: &dyn CoursesRepository,
+    user: &AuthUser,
+    course_id: uuid::Uuid,
+    currency: &str,
+    input: SetCoursePriceRequest,
+) -> AppResult<CoursePriceList> {
+    let course = load_managed_course(repo, user, course_id).await?;
+    let currency = listed_currency(&course, currency)?;
+    let price = Money::new(input.amount, currency.clone());
+    let original_price = input.original_amount.map(|a| Money::new(a, currency));
+    check_original_price(&price, original_price.as_ref())?;
+
+    repo.upsert_price(course_id, price, original_price).await?;
+    let entries = repo.list_prices(course_id).await?;
+    Ok(price_list(&course, entries))
+}
+
+/// Stop selling the course in a currency of its price list
+pub async fn delete_course_price(
+    repo: &dyn CoursesRepository,
+    user: &AuthUser,
+    course_id: uuid::Uuid,
+    currency: &str,
+) -> AppResult<CoursePriceList> {
+    let course = load_managed_course(repo, user, course_id).await?;
+    let currency = listed_currency(&course, currency)?;
+    if !repo.delete_price(course_id, &currency).await? {
+        return Err(AppError::NotFound(format!(
+            "The course has no {} price",
+            currency
+        )));
+    }
+    let entries = repo.list_prices(course_id).await?;
+    Ok(price_list(&course, entries))
+}
+
+/// The course's price in `currency`, `None` if it is not sold in it
+pub(crate) async fn price_in_currency(
+    repo: &dyn CoursesRepository,
+    course: &CourseRecord,
+    currency: &str,
+) -> AppResult<Option<Money>> {
+    if course.price.currency == currency {
+        return Ok(Some(course.price.clone()));
+    }
+    Ok(repo
+        .find_price(course.id, currency)
+        .await?
+        .map(|entry| entry.price))
+}
+
+/// Reject an original price in another currency or below the price
+pub(crate) fn check_original_price(price: &Money, original: Option<&Money>) -> AppResult<()> {
+    let Some(original) = original else {
+        return Ok(());
+    };
+    if original.currency != price.currency {
+        return Err(AppError::BadRequest(format!(
+            "Original price must be in {} like the price",
+            price.currency
+        )));
+    }
+    if original.amount < price.amount {
+        return Err(AppError::BadRequest(
+            "Original price must not be below the price".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Normalized price-list currency; the base currency is set on the course itself
+fn listed_currency(course: &CourseRecord, currency: &str) -> AppResult<String> {
+    let currency = normalize_currency(currency).ok_or_else(|| {
+        AppError::BadRequest(format!("'{}' is not an ISO 4217 currency code", currency))
+    })?;
+    if currency == course.price.currency {
+        return Err(AppError::BadRequest(format!(
+            "{} is the course's base currency; change it with the course price",
+            currency
+        )));
+    }
+    Ok(currency)
+}
+
+fn price_list(course: &CourseRecord, entries: Vec<CoursePriceRecord>) -> CoursePriceList {
+    let mut prices = vec![CoursePrice {
+        price: course.price.clone(),
+        original_price: course.original_price.clone(),
+        is_base: true,
+    }];
+    prices.extend(entries.into_iter().map(|entry| CoursePrice {
+        price: entry.price,
+        original_price: entry.original_price,
+        is_base: false,
+    }));
+    CoursePriceList {
+        course_id: course.id,
+        base_currency: course.price.currency.clone(),
+        prices,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn original_price_must_match_currency_and_not_undercut() {
+        let price = Money::new(4900, "EUR");
+        assert!(check_original_price(&price, None).is_ok());
+        assert!(check_original_price(&price, Some(&Money::new(9900, "EUR"))).is_ok());
+        assert!(check_original_price(&price, Some(&Money::new(4900, "EUR"))).is_ok());
+        assert!(check_original_price(&price, Some(&Money::new(100, "EUR"))).is_err());
+        assert!(check_original_price(&price, Some(&Money::new(9900, "USD"))).is_err());
+    }
+}
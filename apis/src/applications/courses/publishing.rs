@@ -0,0 +1,326 @@
+//! Course publishing workflow
+//!
+//! Status only moves through explicit transitions:
+//! - publish: `draft`/`archived` → `published`, after the readiness checks
+//! - unpublish: `published` → `draft`
+//! - archive: `draft`/`published` → `archived`
+//!
+//! A draft may also carry a future publish time; the scheduled publisher picks
+//! it up once due and runs the same checks as a manual publish.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::logger::{info, warn};
+use crate::repositories::courses::{
+    CoursePublishReadiness, CourseRecord, CourseStatus, CoursesRepository,
+};
+use crate::types::course_types::Course;
+
+use super::load_managed_course;
+
+/// How often the scheduled publisher looks for due drafts
+pub const SCHEDULED_PUBLISH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Due drafts handled per scheduler tick
+const SCHEDULED_PUBLISH_BATCH: i64 = 100;
+
+/// Statuses a course may move to `target` from
+pub fn allowed_sources(target: CourseStatus) -> &'static [CourseStatus] {
+    match target {
+        CourseStatus::Published => &[CourseStatus::Draft, CourseStatus::Archived],
+        CourseStatus::Draft => &[CourseStatus::Published],
+        CourseStatus::Archived => &[CourseStatus::Draft, CourseStatus::Published],
+    }
+}
+
+/// Reasons the course cannot be published yet, keyed by field; empty when ready
+pub fn publish_problems(
+    course: &CourseRecord,
+    readiness: CoursePublishReadiness,
+) -> serde_json::Map<String, serde_json::Value> {
+    let mut problems = serde_json::Map::new();
+    let mut add = |field: &str, message: &str| {
+        problems.insert(field.to_string(), message.into());
+    };
+
+    if course.title.trim().is_empty() {
+        add("title", "A title is required");
+    }
+    if course.description.trim().is_empty() {
+        add("description", "A description is required");
+    }
+    if course
+        .thumbnail
+        .as_deref()
+        .is_none_or(|t| t.trim().is_empty())
+    {
+        add("thumbnail", "A thumbnail is required");
+    }
+    if course.price.amount < 0 {
+        add("price", "Price must be zero or a positive amount");
+    } else if course
+        .original_price
+        .as_ref()
+        .is_some_and(|original| original.amount < course.price.amount)
+    {
+        add(
+            "original_price",
+            "Original price must not be below the price",
+        );
+    }
+    if readiness.module_count == 0 {
+        add("modules", "At least one module is required");
+    }
+    if readiness.published_lesson_count == 0 {
+        add("lessons", "At least one published lesson is required");
+    }
+    problems
+}
+
+/// Publish a draft or archived course now
+pub async fn publish_course(
+    repo: &dyn CoursesRepository,
+    user: &AuthUser,
+    id: uuid::Uuid,
+) -> AppResult<Course> {
+    let course = load_managed_course(repo, user, id).await?;
+    ensure_transition(&course, CourseStatus::Published, "publish")?;
+    ensure_ready(repo, &course).await?;
+    transition(repo, id, CourseStatus::Published).await
+}
+
+/// Take a published course back to draft
+pub async fn unpublish_course(
+    repo: &dyn CoursesRepository,
+    user: &AuthUser,
+    id: uuid::Uuid,
+) -> AppResult<Course> {
+    let course = load_managed_course(repo, user, id).await?;
+    ensure_transition(&course, CourseStatus::Draft, "unpublish")?;
+    transition(repo, id, CourseStatus::Draft).await
+}
+
+/// Archive a draft or published course
+pub async fn archive_course(
+    repo: &dyn CoursesRepository,
+    user: &AuthUser,
+    id: uuid::Uuid,
+) -> AppResult<Course> {
+    let course = load_managed_course(repo, user, id).await?;
+    ensure_transition(&course, CourseStatus::Archived, "archive")?;
+    transition(repo, id, CourseStatus::Archived).await
+}
+
+/// Schedule a draft to be published at `publish_at`
+///
+/// The readiness checks run now so problems surface early, and again when
+/// the schedule comes due.
+pub async fn schedule_course_publish(
+    repo: &dyn CoursesRepository,
+    user: &AuthUser,
+    id: uuid::Uuid,
+    publish_at: chrono::DateTime<chrono::Utc>,
+) -> AppResult<Course> {
+    if publish_at <= chrono::Utc::now() {
+        return Err(AppError::BadRequest(
+            "publish_at must be in the future".into(),
+        ));
+    }
+    let course = load_managed_course(repo, user, id).await?;
+    ensure_draft(&course, "schedule")?;
+    ensure_ready(repo, &course).await?;
+    set_schedule(repo, id, Some(publish_at)).await
+}
+
+/// Drop a draft's pending publish time
+pub async fn cancel_scheduled_publish(
+    repo: &dyn CoursesRepository,
+    user: &AuthUser,
+    id: uuid::Uuid,
+) -> AppResult<Course> {
+    let course = load_managed_course(repo, user, id).await?;
+    ensure_draft(&course, "unschedule")?;
+    set_schedule(repo, id, None).await
+}
+
+/// Publish every draft whose scheduled time has passed; returns how many were published
+///
+/// Drafts that fail the readiness checks have their schedule cleared so they
+/// are not retried every tick; the instructor has to fix and reschedule them.
+pub async fn publish_due_courses(repo: &dyn CoursesRepository) -> AppResult<usize> {
+    let mut published = 0;
+    for id in repo
+        .list_due_scheduled(chrono::Utc::now(), SCHEDULED_PUBLISH_BATCH)
+        .await?
+    {
+        let Some(course) = repo.find_by_id(id).await? else {
+            continue;
+        };
+        let problems = publish_problems(&course, repo.publish_readiness(id).await?);
+        if !problems.is_empty() {
+            warn(format!(
+                "Scheduled publish of course {} skipped, not ready: {}",
+                id,
+                serde_json::Value::Object(problems)
+            ));
+            repo.set_scheduled_publish(id, None).await?;
+            continue;
+        }
+        // Another instance may have published or the instructor changed it meanwhile
+        if repo
+            .transition_status(id, &[CourseStatus::Draft], CourseStatus::Published)
+            .await?
+            .is_some()
+        {
+            published += 1;
+        }
+    }
+    Ok(published)
+}
+
+/// Run `publish_due_courses` every `interval` until the task is aborted
+pub async fn run_scheduled_publisher(repo: Arc<dyn CoursesRepository>, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        ticker.tick().await;
+        match publish_due_courses(repo.as_ref()).await {
+            Ok(0) => {}
+            Ok(n) => info(format!("Published {} scheduled course(s)", n)),
+            Err(e) => warn(format!("Scheduled course publishing failed: {}", e)),
+        }
+    }
+}
+
+fn ensure_transition(course: &CourseRecord, target: CourseStatus, action: &str) -> AppResult<()> {
+    let current = CourseStatus::parse(&course.status);
+    if current.is_some_and(|s| allowed_sources(target).contains(&s)) {
+        return Ok(());
+    }
+    Err(AppError::Conflict(format!(
+        "Cannot {} a course that is {}",
+        action, course.status
+    )))
+}
+
+fn ensure_draft(course: &CourseRecord, action: &str) -> AppResult<()> {
+    if course.status == CourseStatus::Draft.as_str() {
+        return Ok(());
+    }
+    Err(AppError::Conflict(format!(
+        "Cannot {} a course that is {}; only drafts can be scheduled",
+        action, course.status
+    )))
+}
+
+async fn ensure_ready(repo: &dyn CoursesRepository, course: &CourseRecord) -> AppResult<()> {
+    let problems = publish_problems(course, repo.publish_readiness(course.id).await?);
+    if problems.is_empty() {
+        return Ok(());
+    }
+    Err(AppError::Validation {
+        message: "Course is not ready to be published".to_string(),
+        details: serde_json::Value::Object(problems),
+    })
+}
+
+async fn transition(
+    repo: &dyn CoursesRepository,
+    id: uuid::Uuid,
+    target: CourseStatus,
+) -> AppResult<Course> {
+    repo.transition_status(id, allowed_sources(target), target)
+        .await?
+        .map(Course::from)
+        .ok_or_else(|| AppError::Conflict("Course status changed, please retry".into()))
+}
+
+async fn set_schedule(
+    repo: &dyn CoursesRepository,
+    id: uuid::Uuid,
+    at: Option<chrono::DateTime<chrono::Utc>>,
+) -> AppResult<Course> {
+    repo.set_scheduled_publish(id, at)
+        .await?
+        .map(Course::from)
+        .ok_or_else(|| AppError::Conflict("Course status changed, please retry".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pkg::money::Money;
+
+    fn course() -> CourseRecord {
+        CourseRecord {
+            id: uuid::Uuid::new_v4(),
+            slug: "rust".into(),
+            title: "Rust".into(),
+            description: "Learn Rust".into(),
+            excerpt: None,
+            thumbnail: Some("https://cdn.example.com/rust.webp".into()),
+            price: Money::new(4900, "USD"),
+            original_price: Some(Money::new(9900, "USD")),
+            duration: "4h".into(),
+            lessons: 1,
+            featured: false,
+            view_count: 0,
+            rating_average: 0.0,
+            rating_count: 0,
+            status: "draft".into(),
+            outcomes: None,
+            category: None,
+            tags: None,
+            instructor_id: uuid::Uuid::new_v4(),
+            instructor: None,
+            published_at: None,
+            scheduled_publish_at: None,
+            created_at: chrono::Utc::now(),
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn transitions_follow_the_workflow() {
+        let mut c = course();
+        assert!(ensure_transition(&c, CourseStatus::Published, "publish").is_ok());
+        assert!(ensure_transition(&c, CourseStatus::Archived, "archive").is_ok());
+        assert!(matches!(
+            ensure_transition(&c, CourseStatus::Draft, "unpublish"),
+            Err(AppError::Conflict(_))
+        ));
+
+        c.status = "published".into();
+        assert!(ensure_transition(&c, CourseStatus::Draft, "unpublish").is_ok());
+        assert!(ensure_transition(&c, CourseStatus::Published, "publish").is_err());
+        assert!(ensure_draft(&c, "schedule").is_err());
+
+        c.status = "archived".into();
+        assert!(ensure_transition(&c, CourseStatus::Published, "publish").is_ok());
+        assert!(ensure_transition(&c, CourseStatus::Archived, "archive").is_err());
+        assert!(ensure_transition(&c, CourseStatus::Draft, "unpublish").is_err());
+    }
+
+    #[test]
+    fn readiness_requires_content_thumbnail_and_sane_price() {
+        let ready = CoursePublishReadiness {
+            module_count: 1,
+            published_lesson_count: 1,
+        };
+        assert!(publish_problems(&course(), ready).is_empty());
+
+        let mut c = course();
+        c.thumbnail = Some(" ".into());
+        c.original_price = Some(Money::new(1000, "USD"));
+        let problems = publish_problems(&c, CoursePublishReadiness::default());
+        let mut fields: Vec<_> = problems.keys().map(String::as_str).collect();
+        fields.sort();
+        assert_eq!(
+            fields,
+            ["lessons", "modules", "original_price", "thumbnail"]
+        );
+    }
+}
@@ -0,0 +1,346 @@
+//! Course reviews and ratings
+//!
+//! Enrolled learners leave one review per course. Reviews start out pending
+//! and only count towards the course's rating (kept on `courses` by a database
+//! trigger) once a moderator approves them; editing a review sends it back to
+//! moderation.
+
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::course_reviews::{
+    CourseReviewRecord, CourseReviewSort, CourseReviewsRepository, CreateCourseReviewRecord,
+    UpdateCourseReviewRecord,
+};
+use crate::repositories::courses::{CourseStatus, CoursesRepository};
+use crate::repositories::enrollments::EnrollmentsRepository;
+use crate::types::course_review_types::{
+    CourseRatingSummary, CourseReview, CourseReviewAuthor, CourseReviewPage, CourseReviewReply,
+    CourseReviewReplyRequest, CourseReviewsQuery, CreateCourseReviewRequest, RatingBucket,
+    UpdateCourseReviewRequest,
+};
+use crate::types::course_types::PageMeta;
+
+use super::catalogue::page_offset;
+use super::load_managed_course;
+
+/// Approved reviews of a published course with its rating summary
+pub async fn list_course_reviews(
+    courses: &dyn CoursesRepository,
+    reviews: &dyn CourseReviewsRepository,
+    course_id: uuid::Uuid,
+    query: CourseReviewsQuery,
+) -> AppResult<CourseReviewPage> {
+    let course = courses
+        .find_by_id(course_id)
+        .await?
+        .filter(|c| c.status == CourseStatus::Published.as_str())
+        .ok_or_else(|| AppError::NotFound("Course not found".into()))?;
+
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = match query.per_page.unwrap_or(10) {
+        n if n < 1 => 10,
+        n => n.min(50),
+    };
+    let sort = match query.sort.as_deref() {
+        Some(s) => CourseReviewSort::parse(s).ok_or_else(|| {
+            AppError::BadRequest("sort must be one of newest, helpful, highest, lowest".into())
+        })?,
+        None => CourseReviewSort::default(),
+    };
+
+    let offset = page_offset(page, per_page)?;
+    let (records, total) = reviews
+        .list_approved_by_course(course_id, sort, offset, per_page)
+        .await?;
+    let distribution = reviews.rating_distribution(course_id).await?;
+
+    Ok(CourseReviewPage {
+        summary: CourseRatingSummary {
+            average: course.rating_average,
+            count: course.rating_count,
+            distribution: rating_buckets(&distribution),
+        },
+        items: records.into_iter().map(to_review).collect(),
+        meta: PageMeta {
+            page,
+            per_page,
+            total,
+            total_pages: if total == 0 {
+                1
+            } else {
+                (total + per_page - 1) / per_page
+            },
+        },
+    })
+}
+
+/// Review a course the user is enrolled in; the review awaits moderation
+pub async fn create_course_review(
+    courses: &dyn CoursesRepository,
+    enrollments: &dyn EnrollmentsRepository,
+    reviews: &dyn CourseReviewsRepository,
+    user: &AuthUser,
+    course_id: uuid::Uuid,
+    req: CreateCourseReviewRequest,
+) -> AppResult<CourseReview> {
+    let course = courses
+        .find_by_id(course_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Course not found".into()))?;
+    if course.instructor_id == user.user_id {
+        return Err(AppError::Forbidden(
+            "Instructors cannot review their own course".into(),
+        ));
+    }
+    if !enrollments.is_enrolled(user.user_id, course_id).await? {
+        return Err(AppError::Forbidden(
+            "Only enrolled learners can review this course".into(),
+        ));
+    }
+    if reviews
+        .find_by_user_and_course(user.user_id, course_id)
+        .await?
+        .is_some()
+    {
+        return Err(AppError::Conflict(
+            "You have already reviewed this course; edit your existing review instead".into(),
+        ));
+    }
+
+    let id = reviews
+        .create(CreateCourseReviewRecord {
+            course_id,
+            user_id: user.user_id,
+            rating: req.rating,
+            content: req.content.trim().to_string(),
+        })
+        .await?;
+    load_review(reviews, id).await.map(to_review)
+}
+
+/// Edit one's own review; it goes back to moderation
+pub async fn update_course_review(
+    reviews: &dyn CourseReviewsRepository,
+    user: &AuthUser,
+    id: uuid::Uuid,
+    req: UpdateCourseReviewRequest,
+) -> AppResult<CourseReview> {
+    let review = load_review(reviews, id).await?;
+    if review.user_id != user.user_id {
+        return Err(AppError::Forbidden(
+            "Only the author can edit this review".into(),
+        ));
+    }
+    if req.rating.is_none() && req.content.is_none() {
+        return Err(AppError::BadRequest("Nothing to update".into()));
+    }
+    reviews
+        .update_by_author(
+            id,
+            UpdateCourseReviewRecord {
+                rating: req.rating,
+                content: req.content.map(|c| c.trim().to_string()),
+            },
+        )
+        .await?;
+    load_review(reviews, id).await.map(to_review)
+}
+
+/// Delete a review (its author or an admin)
+pub async fn delete_course_review(
+    reviews: &dyn CourseReviewsRepository,
+    user: &AuthUser,
+    id: uuid::Uuid,
+) -> AppResult<()> {
+    let review = load_review(reviews, id).await?;
+    if review.user_id != user.user_id && user.role != "admin" {
+        return Err(AppError::Forbidden(
+            "Only the author can delete this review".into(),
+        ));
+    }
+    reviews.delete_by_id(id).await
+}
+
+/// Reply to a review as the course instructor (or an admin), replacing any earlier reply
+pub async fn reply_to_course_review(
+    courses: &dyn CoursesRepository,
+    reviews: &dyn CourseReviewsRepository,
+    user: &AuthUser,
+    id: uuid::Uuid,
+    req: CourseReviewReplyRequest,
+) -> AppResult<CourseReview> {
+    let review = load_review(reviews, id).await?;
+    load_managed_course(courses, user, review.course_id).await?;
+    let content = req.content.trim();
+    if content.is_empty() {
+        return Err(AppError::BadRequest("Reply must not be empty".into()));
+    }
+    reviews
+        .set_reply(id, Some(content.to_string()), Some(user.user_id))
+        .await?;
+    load_review(reviews, id).await.map(to_review)
+}
+
+/// Remove the instructor reply from a review
+pub async fn delete_course_review_reply(
+    courses: &dyn CoursesRepository,
+    reviews: &dyn CourseReviewsRepository,
+    user: &AuthUser,
+    id: uuid::Uuid,
+) -> AppResult<CourseReview> {
+    let review = load_review(reviews, id).await?;
+    load_managed_course(courses, user, review.course_id).await?;
+    reviews.set_reply(id, None, None).await?;
+    load_review(reviews, id).await.map(to_review)
+}
+
+/// Mark (`helpful == true`) or unmark an approved review as helpful
+pub async fn vote_course_review_helpful(
+    reviews: &dyn CourseReviewsRepository,
+    user: &AuthUser,
+    id: uuid::Uuid,
+    helpful: bool,
+) -> AppResult<CourseReview> {
+    let review = load_review(reviews, id).await?;
+    if !review.approved {
+        return Err(AppError::NotFound("Review not found".into()));
+    }
+    if review.user_id == user.user_id {
+        return Err(AppError::BadRequest(
+            "You cannot vote on your own review".into(),
+        ));
+    }
+    if helpful {
+        reviews.add_vote(id, user.user_id).await?;
+    } else {
+        reviews.remove_vote(id, user.user_id).await?;
+    }
+    load_review(reviews, id).await.map(to_review)
+}
+
+/// Reviews awaiting moderation (admin only)
+pub async fn list_pending_course_reviews(
+    reviews: &dyn CourseReviewsRepository,
+    user: &AuthUser,
+    limit: Option<i64>,
+) -> AppResult<Vec<CourseReview>> {
+    user.require_admin()?;
+    let limit = limit.unwrap_or(50).clamp(1, 200);
+    let records = reviews.list_pending(limit).await?;
+    Ok(records.into_iter().map(to_review).collect())
+}
+
+/// Approve (`approve == true`) or reject a review (admin only)
+pub async fn moderate_course_review(
+    reviews: &dyn CourseReviewsRepository,
+    user: &AuthUser,
+    id: uuid::Uuid,
+    approve: bool,
+) -> AppResult<CourseReview> {
+    user.require_admin()?;
+    load_review(reviews, id).await?;
+    if approve {
+        reviews.approve(id).await?;
+    } else {
+        reviews.reject(id).await?;
+    }
+    load_review(reviews, id).await.map(to_review)
+}
+
+async fn load_review(
+    reviews: &dyn CourseReviewsRepository,
+    id: uuid::Uuid,
+) -> AppResult<CourseReviewRecord> {
+    reviews
+        .find_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Review not found".into()))
+}
+
+/// Moderation state derived from `approved` and `moderated_at`
+fn review_status(record: &CourseReviewRecord) -> &'static str {
+    match (record.approved, record.moderated_at) {
+        (true, _) => "approved",
+        (false, Some(_)) => "rejected",
+        (false, None) => "pending",
+    }
+}
+
+/// One bucket per star rating, 5 down to 1, filling in missing ratings with 0
+fn rating_buckets(counts: &[(i16, i64)]) -> Vec<RatingBucket> {
+    (1..=5)
+        .rev()
+        .map(|rating| RatingBucket {
+            rating,
+            count: counts
+                .iter()
+                .find(|(r, _)| *r == rating)
+                .map_or(0, |(_, count)| *count),
+        })
+        .collect()
+}
+
+fn to_review(record: CourseReviewRecord) -> CourseReview {
+    let status = review_status(&record).to_string();
+    CourseReview {
+        id: record.id,
+        course_id: record.course_id,
+        rating: record.rating,
+        content: record.content,
+        status,
+        helpful_count: record.helpful_count,
+        author: CourseReviewAuthor {
+            id: record.user_id,
+            username: record.username,
+            full_name: record.full_name,
+            avatar_url: record.avatar_url,
+        },
+        reply: record.instructor_reply.map(|content| CourseReviewReply {
+            content,
+            replied_by: record.replied_by,
+            replied_at: record.replied_at,
+        }),
+        created_at: record.created_at,
+        updated_at: record.updated_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(approved: bool, moderated: bool) -> CourseReviewRecord {
+        CourseReviewRecord {
+            id: uuid::Uuid::new_v4(),
+            course_id: uuid::Uuid::new_v4(),
+            user_id: uuid::Uuid::new_v4(),
+            rating: 4,
+            content: String::new(),
+            approved,
+            moderated_at: moderated.then(chrono::Utc::now),
+            instructor_reply: None,
+            replied_by: None,
+            replied_at: None,
+            helpful_count: 0,
+            created_at: chrono::Utc::now(),
+            updated_at: None,
+            username: "learner".into(),
+            full_name: None,
+            avatar_url: None,
+        }
+    }
+
+    #[test]
+    fn status_follows_moderation() {
+        assert_eq!(review_status(&record(false, false)), "pending");
+        assert_eq!(review_status(&record(true, true)), "approved");
+        assert_eq!(review_status(&record(false, true)), "rejected");
+    }
+
+    #[test]
+    fn distribution_covers_every_rating() {
+        let buckets = rating_buckets(&[(5, 3), (2, 1)]);
+        let counts: Vec<(i16, i64)> = buckets.iter().map(|b| (b.rating, b.count)).collect();
+        assert_eq!(counts, vec![(5, 3), (4, 0), (3, 0), (2, 1), (1, 0)]);
+    }
+}
@@ -0,0 +1,730 @@
+//! Draft revisions of published courses
+//!
+//! Editing a published course in place would show students half-finished
+//! changes. Instead the instructor opens a draft revision: a full copy of the
+//! course fields and module/lesson tree stored as one document. The draft can
+//! be edited freely, compared with the live course, and finally published,
+//! which swaps the live content in a single transaction.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::applications::modules::deep_create::map_lesson_deep_request;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::course_revisions::{
+    CourseRevisionRecord, CourseRevisionsRepository, PublishRevisionCourseData,
+    PublishRevisionData, RevisionContentData, RevisionLessonData, RevisionModuleData,
+};
+use crate::repositories::courses::{CourseRecord, CourseStatus, CoursesRepository};
+use crate::repositories::modules::{CreateLessonContentData, ModuleDeepRecord, ModulesRepository};
+use crate::types::course_revision_types::{
+    CourseRevision, CourseRevisionContent, CourseRevisionDiff, RevisionCourseFields,
+    RevisionFieldChange, RevisionItemChange, RevisionLesson, RevisionLessonContent, RevisionModule,
+};
+use crate::types::course_types::{
+    CreateLessonAssignmentInput, CreateLessonDeepRequest, CreateLessonQuestionInput,
+    CreateQuestionOptionInput,
+};
+
+use super::load_managed_course;
+use super::pricing::check_original_price;
+
+/// Open a draft revision of a published course, or return the one already open.
+///
+/// The boolean is `true` when a new draft was created.
+pub async fn start_course_revision(
+    courses: &dyn CoursesRepository,
+    modules: &dyn ModulesRepository,
+    revisions: &dyn CourseRevisionsRepository,
+    user: &AuthUser,
+    course_id: uuid::Uuid,
+) -> AppResult<(CourseRevision, bool)> {
+    let course = load_managed_course(courses, user, course_id).await?;
+    if let Some(open) = revisions.find_open_draft(course_id).await? {
+        return Ok((to_revision(open)?, false));
+    }
+    if course.status != CourseStatus::Published.as_str() {
+        return Err(AppError::Conflict(format!(
+            "Only published courses use draft revisions; this course is {} and can be edited directly",
+            course.status
+        )));
+    }
+
+    // Read the version before the snapshot so a concurrent publish makes this draft stale
+    let base_version = revisions
+        .content_version(course_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Course not found".into()))?;
+    let live = modules.list_by_course_deep(course_id).await?;
+    let content = encode(&snapshot(&course, live))?;
+    let record = revisions
+        .create_draft(course_id, user.user_id, base_version, content)
+        .await?;
+    Ok((to_revision(record)?, true))
+}
+
+/// The course's open draft revision
+pub async fn get_open_course_revision(
+    courses: &dyn CoursesRepository,
+    revisions: &dyn CourseRevisionsRepository,
+    user: &AuthUser,
+    course_id: uuid::Uuid,
+) -> AppResult<CourseRevision> {
+    load_managed_course(courses, user, course_id).await?;
+    let record = revisions
+        .find_open_draft(course_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("This course has no open draft revision".into()))?;
+    to_revision(record)
+}
+
+/// Replace the content of an open draft
+pub async fn update_course_revision(
+    courses: &dyn CoursesRepository,
+    revisions: &dyn CourseRevisionsRepository,
+    user: &AuthUser,
+    course_id: uuid::Uuid,
+    revision_id: uuid::Uuid,
+    content: CourseRevisionContent,
+) -> AppResult<CourseRevision> {
+    load_managed_course(courses, user, course_id).await?;
+    load_revision(revisions, course_id, revision_id).await?;
+    check_tree(&content)?;
+    let record = revisions
+        .update_draft_content(revision_id, encode(&content)?)
+        .await?
+        .ok_or_else(not_a_draft)?;
+    to_revision(record)
+}
+
+/// Compare a revision with the live course
+pub async fn diff_course_revision(
+    courses: &dyn CoursesRepository,
+    modules: &dyn ModulesRepository,
+    revisions: &dyn CourseRevisionsRepository,
+    user: &AuthUser,
+    course_id: uuid::Uuid,
+    revision_id: uuid::Uuid,
+) -> AppResult<CourseRevisionDiff> {
+    let course = load_managed_course(courses, user, course_id).await?;
+    let record = load_revision(revisions, course_id, revision_id).await?;
+    let live_version = revisions.content_version(course_id).await?;
+    let live = snapshot(&course, modules.list_by_course_deep(course_id).await?);
+    let draft = decode(record.content)?;
+
+    let mut diff = diff_contents(&live, &draft);
+    diff.revision_id = revision_id;
+    diff.stale = record.status == "draft" && live_version != Some(record.base_version);
+    Ok(diff)
+}
+
+/// Make an open draft the live content of its course
+pub async fn publish_course_revision(
+    courses: &dyn CoursesRepository,
+    revisions: &dyn CourseRevisionsRepository,
+    user: &AuthUser,
+    course_id: uuid::Uuid,
+    revision_id: uuid::Uuid,
+) -> AppResult<CourseRevision> {
+    load_managed_course(courses, user, course_id).await?;
+    let record = load_revision(revisions, course_id, revision_id).await?;
+    let content = decode(record.content)?;
+    check_tree(&content)?;
+    let published = revisions
+        .publish(revision_id, publish_data(content))
+        .await?;
+    to_revision(published)
+}
+
+/// Throw away an open draft
+pub async fn discard_course_revision(
+    courses: &dyn CoursesRepository,
+    revisions: &dyn CourseRevisionsRepository,
+    user: &AuthUser,
+    course_id: uuid::Uuid,
+    revision_id: uuid::Uuid,
+) -> AppResult<CourseRevision> {
+    load_managed_course(courses, user, course_id).await?;
+    load_revision(revisions, course_id, revision_id).await?;
+    let record = revisions
+        .discard(revision_id)
+        .await?
+        .ok_or_else(not_a_draft)?;
+    to_revision(record)
+}
+
+async fn load_revision(
+    revisions: &dyn CourseRevisionsRepository,
+    course_id: uuid::Uuid,
+    revision_id: uuid::Uuid,
+) -> AppResult<CourseRevisionRecord> {
+    revisions
+        .find_by_id(revision_id)
+        .await?
+        .filter(|r| r.course_id == course_id)
+        .ok_or_else(|| AppError::NotFound("Revision not found".into()))
+}
+
+fn not_a_draft() -> AppError {
+    AppError::Conflict("Revision is no longer an open draft".into())
+}
+
+fn encode(content: &CourseRevisionContent) -> AppResult<serde_json::Value> {
+    serde_json::to_value(content)
+        .map_err(|e| AppError::Internal(format!("Failed to encode revision: {}", e)))
+}
+
+fn decode(content: serde_json::Value) -> AppResult<CourseRevisionContent> {
+    serde_json::from_value(content)
+        .map_err(|e| AppError::Internal(format!("Stored revision is unreadable: {}", e)))
+}
+
+fn to_revision(record: CourseRevisionRecord) -> AppResult<CourseRevision> {
+    Ok(CourseRevision {
+        id: record.id,
+        course_id: record.course_id,
+        status: record.status,
+        base_version: record.base_version,
+        content: decode(record.content)?,
+        created_by: record.created_by,
+        created_at: record.created_at,
+        updated_at: record.updated_at,
+        published_at: record.published_at,
+    })
+}
+
+/// Live course content in revision form
+fn snapshot(course: &CourseRecord, modules: Vec<ModuleDeepRecord>) -> CourseRevisionContent {
+    CourseRevisionContent {
+        course: RevisionCourseFields {
+            title: course.title.clone(),
+            description: course.description.clone(),
+            excerpt: course.excerpt.clone(),
+            thumbnail: course.thumbnail.clone(),
+            price: course.price.clone(),
+            original_price: course.original_price.clone(),
+            duration: course.duration.clone(),
+            outcomes: course.outcomes.clone().unwrap_or_default(),
+            category: course.category.clone(),
+            tags: course.tags.clone().unwrap_or_default(),
+        },
+        modules: modules
+            .into_iter()
+            .map(|m| RevisionModule {
+                id: Some(m.module.id),
+                title: m.module.title,
+                description: m.module.description,
+                position: m.module.position,
+                lessons: m
+                    .lessons
+                    .into_iter()
+                    .map(|l| RevisionLesson {
+                        id: Some(l.lesson.id),
+                        title: l.lesson.title,
+                        description: l.lesson.description,
+                        content: l.lesson.content,
+                        video_url: l.lesson.video_url,
+                        duration: l.lesson.duration,
+                        position: l.lesson.position,
+                        is_free: l.lesson.is_free,
+                        published: l.lesson.published,
+                        contents: l
+                            .contents
+                            .into_iter()
+                            .map(|c| RevisionLessonContent {
+                                id: Some(c.id),
+                                title: c.title,
+                                content_type: c.content_type,
+                                url: c.url,
+                                file_size: c.file_size,
+                                filename: c.filename,
+                                position: c.position,
+                                is_private: c.is_private,
+                            })
+                            .collect(),
+                        questions: l
+                            .questions
+                            .into_iter()
+                            .map(|(q, options)| CreateLessonQuestionInput {
+                                question_text: q.question_text,
+                                position: q.position,
+                                options: options
+                                    .into_iter()
+                                    .map(|o| CreateQuestionOptionInput {
+                                        option_text: o.option_text,
+                                        is_correct: o.is_correct,
+                                        position: o.position,
+                                    })
+                                    .collect(),
+                            })
+                            .collect(),
+                        assignment: l.assignment.map(|a| CreateLessonAssignmentInput {
+                            title: a.title,
+                            description: a.description,
+                        }),
+                    })
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+/// Reject content the publish swap could not apply: positions must be unique
+/// among siblings, no live module, lesson or content may appear twice, new
+/// contents cannot be private and the original price must be in the price's
+/// currency
+fn check_tree(content: &CourseRevisionContent) -> AppResult<()> {
+    let mut problems = serde_json::Map::new();
+    if let Err(AppError::BadRequest(message)) = check_original_price(
+        &content.course.price,
+        content.course.original_price.as_ref(),
+    ) {
+        problems.insert("course.original_price".into(), message.into());
+    }
+    let mut module_positions = HashSet::new();
+    let mut ids = HashSet::new();
+    for (i, module) in content.modules.iter().enumerate() {
+        if !module_positions.insert(module.position) {
+            problems.insert(
+                format!("modules[{}].position", i),
+                format!("Duplicate module position {}", module.position).into(),
+            );
+        }
+        if let Some(id) = module.id
+            && !ids.insert(id)
+        {
+            problems.insert(format!("modules[{}].id", i), "Duplicate module id".into());
+        }
+        let mut lesson_positions = HashSet::new();
+        for (j, lesson) in module.lessons.iter().enumerate() {
+            if !lesson_positions.insert(lesson.position) {
+                problems.insert(
+                    format!("modules[{}].lessons[{}].position", i, j),
+                    format!("Duplicate lesson position {}", lesson.position).into(),
+                );
+            }
+            if let Some(id) = lesson.id
+                && !ids.insert(id)
+            {
+                problems.insert(
+                    format!("modules[{}].lessons[{}].id", i, j),
+                    "Duplicate lesson id".into(),
+                );
+            }
+            let mut content_positions = HashSet::new();
+            for (k, c) in lesson.contents.iter().enumerate() {
+                let at = format!("modules[{}].lessons[{}].contents[{}]", i, j, k);
+                if !content_positions.insert(c.position) {
+                    problems.insert(
+                        format!("{}.position", at),
+                        format!("Duplicate content position {}", c.position).into(),
+                    );
+                }
+                match c.id {
+                    Some(id) if !ids.insert(id) => {
+                        problems.insert(format!("{}.id", at), "Duplicate content id".into());
+                    }
+                    None if c.is_private => {
+                        problems.insert(
+                            format!("{}.is_private", at),
+                            "Private files are uploaded to the lesson directly, not added in a revision"
+                                .into(),
+                        );
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    if problems.is_empty() {
+        return Ok(());
+    }
+    Err(AppError::Validation {
+        message: "Revision content is inconsistent".to_string(),
+        details: serde_json::Value::Object(problems),
+    })
+}
+
+fn publish_data(content: CourseRevisionContent) -> PublishRevisionData {
+    let course = content.course;
+    PublishRevisionData {
+        course: PublishRevisionCourseData {
+            title: course.title,
+            description: course.description,
+            excerpt: course.excerpt,
+            thumbnail: course.thumbnail,
+            price: course.price,
+            original_price: course.original_price,
+            duration: course.duration,
+            outcomes: course.outcomes,
+            category: course.category,
+            tags: course.tags,
+        },
+        modules: content
+            .modules
+            .into_iter()
+            .map(|m| RevisionModuleData {
+                id: m.id,
+                title: m.title,
+                description: m.description,
+                position: m.position,
+                lessons: m
+                    .lessons
+                    .into_iter()
+                    .map(|l| RevisionLessonData {
+                        id: l.id,
+                        lesson: map_lesson_deep_request(CreateLessonDeepRequest {
+                            title: l.title,
+                            description: l.description,
+                            content: l.content,
+                            video_url: l.video_url,
+                            duration: l.duration,
+                            position: l.position,
+                            is_free: l.is_free,
+                            published: l.published,
+                            contents: Vec::new(),
+                            questions: l.questions,
+                            assignment: l.assignment,
+                        }),
+                        contents: l
+                            .contents
+                            .into_iter()
+                            .map(|c| RevisionContentData {
+                                id: c.id,
+                                content: CreateLessonContentData {
+                                    title: c.title,
+                                    content_type: c.content_type,
+                                    url: c.url,
+                                    file_size: c.file_size,
+                                    filename: c.filename,
+                                    position: c.position,
+                                    is_private: c.is_private,
+                                },
+                            })
+                            .collect(),
+                    })
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+/// Field-level comparison of live and draft content.
+///
+/// Modules and lessons are matched by id; draft items without a live id are
+/// additions. `revision_id` and `stale` are left for the caller to fill in.
+fn diff_contents(
+    live: &CourseRevisionContent,
+    draft: &CourseRevisionContent,
+) -> CourseRevisionDiff {
+    let live_course = to_object(&live.course);
+    let draft_course = to_object(&draft.course);
+    let course = changed_fields(&live_course, &draft_course, &[])
+        .into_iter()
+        .map(|field| RevisionFieldChange {
+            live: live_course.get(&field).cloned().unwrap_or_default(),
+            draft: draft_course.get(&field).cloned().unwrap_or_default(),
+            field,
+        })
+        .collect();
+
+    let mut modules = Vec::new();
+    let live_modules: HashMap<_, _> = live
+        .modules
+        .iter()
+        .filter_map(|m| m.id.map(|id| (id, m)))
+        .collect();
+    for module in &draft.modules {
+        match module.id.and_then(|id| live_modules.get(&id)) {
+            Some(before) => {
+                let fields =
+                    changed_fields(&to_object(*before), &to_object(module), &["id", "lessons"]);
+                if !fields.is_empty() {
+                    modules.push(item_change("changed", module.id, &module.title, fields));
+                }
+            }
+            None => modules.push(item_change("added", None, &module.title, Vec::new())),
+        }
+    }
+    let draft_module_ids: HashSet<_> = draft.modules.iter().filter_map(|m| m.id).collect();
+    for module in &live.modules {
+        if module.id.is_some_and(|id| !draft_module_ids.contains(&id)) {
+            modules.push(item_change("removed", module.id, &module.title, Vec::new()));
+        }
+    }
+
+    // Lessons are compared along with the module they sit in, so moves show up
+    type LessonFields = (
+        Option<uuid::Uuid>,
+        serde_json::Map<String, serde_json::Value>,
+        String,
+    );
+    let lessons_of = |content: &CourseRevisionContent| -> Vec<LessonFields> {
+        content
+            .modules
+            .iter()
+            .flat_map(|m| {
+                m.lessons.iter().map(move |l| {
+                    let mut fields = to_object(l);
+                    fields.insert(
+                        "module_id".into(),
+                        serde_json::to_value(m.id).unwrap_or_default(),
+                    );
+                    (l.id, fields, l.title.clone())
+                })
+            })
+            .collect()
+    };
+    let mut lessons = Vec::new();
+    let live_lessons: HashMap<_, _> = lessons_of(live)
+        .into_iter()
+        .filter_map(|(id, fields, _)| id.map(|id| (id, fields)))
+        .collect();
+    let draft_lessons = lessons_of(draft);
+    for (id, fields, title) in &draft_lessons {
+        match id.and_then(|id| live_lessons.get(&id)) {
+            Some(before) => {
+                let changed = changed_fields(before, fields, &["id"]);
+                if !changed.is_empty() {
+                    lessons.push(item_change("changed", *id, title, changed));
+                }
+            }
+            None => lessons.push(item_change("added", None, title, Vec::new())),
+        }
+    }
+    let draft_lesson_ids: HashSet<_> = draft_lessons.iter().filter_map(|(id, ..)| *id).collect();
+    for (id, _, title) in lessons_of(live) {
+        if id.is_some_and(|id| !draft_lesson_ids.contains(&id)) {
+            lessons.push(item_change("removed", id, &title, Vec::new()));
+        }
+    }
+
+    CourseRevisionDiff {
+        revision_id: uuid::Uuid::nil(),
+        stale: false,
+        course,
+        modules,
+        lessons,
+    }
+}
+
+fn to_object<T: serde::Serialize>(value: &T) -> serde_json::Map<String, serde_json::Value> {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    }
+}
+
+/// Keys whose values differ between the two objects, in a stable order
+fn changed_fields(
+    before: &serde_json::Map<String, serde_json::Value>,
+    after: &serde_json::Map<String, serde_json::Value>,
+    skip: &[&str],
+) -> Vec<String> {
+    let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    keys.into_iter()
+        .filter(|k| !skip.contains(&k.as_str()))
+        .filter(|k| before.get(*k) != after.get(*k))
+        .cloned()
+        .collect()
+}
+
+fn item_change(
+    change: &str,
+    id: Option<uuid::Uuid>,
+    title: &str,
+    fields: Vec<String>,
+) -> RevisionItemChange {
+    RevisionItemChange {
+        change: change.to_string(),
+        id,
+        title: title.to_string(),
+        fields,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pkg::money::Money;
+
+    fn lesson(id: Option<uuid::Uuid>, title: &str, position: i32) -> RevisionLesson {
+        RevisionLesson {
+            id,
+            title: title.into(),
+            description: None,
+            content: None,
+            video_url: None,
+            duration: "10m".into(),
+            position,
+            is_free: false,
+            published: true,
+            contents: Vec::new(),
+            questions: Vec::new(),
+            assignment: None,
+        }
+    }
+
+    fn content(modules: Vec<RevisionModule>) -> CourseRevisionContent {
+        CourseRevisionContent {
+            course: RevisionCourseFields {
+                title: "Rust".into(),
+                description: "Learn Rust".into(),
+                excerpt: None,
+                thumbnail: None,
+                price: Money::new(1000, "USD"),
+                original_price: None,
+                duration: "1h".into(),
+                outcomes: Vec::new(),
+                category: None,
+                tags: Vec::new(),
+            },
+            modules,
+        }
+    }
+
+    fn module(
+        id: Option<uuid::Uuid>,
+        position: i32,
+        lessons: Vec<RevisionLesson>,
+    ) -> RevisionModule {
+        RevisionModule {
+            id,
+            title: format!("Module {}", position),
+            description: None,
+            position,
+            lessons,
+        }
+    }
+
+    #[test]
+    fn diff_reports_course_module_and_lesson_changes() {
+        let (m1, m2) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        let (l1, l2, l3) = (
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+        );
+        let live = content(vec![
+            module(
+                Some(m1),
+                0,
+                vec![lesson(Some(l1), "Intro", 0), lesson(Some(l2), "Setup", 1)],
+            ),
+            module(Some(m2), 1, vec![lesson(Some(l3), "Traits", 0)]),
+        ]);
+        let mut draft = content(vec![
+            module(
+                Some(m1),
+                0,
+                vec![
+                    lesson(Some(l1), "Welcome", 0),
+                    lesson(Some(l3), "Traits", 1),
+                ],
+            ),
+            module(None, 1, vec![lesson(None, "Async", 0)]),
+        ]);
+        draft.course.price = Money::new(2000, "USD");
+
+        let diff = diff_contents(&live, &draft);
+        assert_eq!(diff.course.len(), 1);
+        assert_eq!(diff.course[0].field, "price");
+        assert_eq!(
+            diff.course[0].draft,
+            serde_json::json!({"amount": 2000, "currency": "USD"})
+        );
+
+        let modules: Vec<_> = diff
+            .modules
+            .iter()
+            .map(|c| (c.change.as_str(), c.id))
+            .collect();
+        assert_eq!(modules, [("added", None), ("removed", Some(m2))]);
+
+        let lesson_change = |id| diff.lessons.iter().find(|c| c.id == Some(id)).unwrap();
+        assert_eq!(lesson_change(l1).fields, ["title"]);
+        assert_eq!(lesson_change(l2).change, "removed");
+        assert_eq!(lesson_change(l3).fields, ["module_id", "position"]);
+        assert!(
+            diff.lessons
+                .iter()
+                .any(|c| c.change == "added" && c.title == "Async")
+        );
+    }
+
+    #[test]
+    fn tree_check_rejects_duplicate_positions_and_ids() {
+        let id = uuid::Uuid::new_v4();
+        assert!(
+            check_tree(&content(vec![module(
+                None,
+                0,
+                vec![lesson(Some(id), "A", 0)]
+            )]))
+            .is_ok()
+        );
+
+        let err = check_tree(&content(vec![
+            module(
+                None,
+                0,
+                vec![lesson(Some(id), "A", 0), lesson(None, "B", 0)],
+            ),
+            module(None, 0, vec![lesson(Some(id), "C", 0)]),
+        ]))
+        .unwrap_err();
+        let AppError::Validation { details, .. } = err else {
+            panic!("expected validation error");
+        };
+        let keys: Vec<_> = details.as_object().unwrap().keys().cloned().collect();
+        assert_eq!(
+            keys,
+            [
+                "modules[0].lessons[1].position",
+                "modules[1].lessons[0].id",
+                "modules[1].position"
+            ]
+        );
+    }
+
+    #[test]
+    fn tree_check_rejects_new_private_contents() {
+        let item = |id, position, is_private| RevisionLessonContent {
+            id,
+            title: "Slides".into(),
+            content_type: "file".into(),
+            url: "private/slides.pdf".into(),
+            file_size: None,
+            filename: None,
+            position,
+            is_private,
+        };
+        let live = uuid::Uuid::new_v4();
+        let mut l = lesson(None, "A", 0);
+        l.contents = vec![item(Some(live), 0, true), item(None, 1, false)];
+        assert!(check_tree(&content(vec![module(None, 0, vec![l])])).is_ok());
+
+        let mut l = lesson(None, "A", 0);
+        l.contents = vec![
+            item(Some(live), 0, true),
+            item(Some(live), 1, true),
+            item(None, 1, true),
+        ];
+        let AppError::Validation { details, .. } =
+            check_tree(&content(vec![module(None, 0, vec![l])])).unwrap_err()
+        else {
+            panic!("expected validation error");
+        };
+        let keys: Vec<_> = details.as_object().unwrap().keys().cloned().collect();
+        assert_eq!(
+            keys,
+            [
+                "modules[0].lessons[0].contents[1].id",
+                "modules[0].lessons[0].contents[2].is_private",
+                "modules[0].lessons[0].contents[2].position"
+            ]
+        );
+    }
+}
@@ -2,12 +2,54 @@ use crate::pkg::error::{AppError, AppResult};
 use crate::repositories::courses::{CoursesRepository, UpdateCourseRecord};
 use crate::types::course_types::{Course, UpdateCourseRequest};
 
+use super::ensure_editable_in_place;
+use super::pricing::check_original_price;
+
 /// Update a course partially by id.
+///
+/// Status is not editable here; it changes only through the publishing
+/// workflow. Resending the current status is accepted so full-form clients
+/// keep working. Content of a published course is edited through a draft
+/// revision so students never see half-finished changes; only `featured`
+/// may still be toggled in place.
 pub async fn update_course_by_id(
     repo: &dyn CoursesRepository,
     id: uuid::Uuid,
     input: UpdateCourseRequest,
 ) -> AppResult<Course> {
+    let current = repo
+        .find_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Course not found".into()))?;
+    if input.status.as_deref().is_some_and(|s| s != current.status) {
+        return Err(AppError::BadRequest(
+            "Course status cannot be edited directly; use the publish, unpublish and archive endpoints".into(),
+        ));
+    }
+    let edits_content = input.title.is_some()
+        || input.description.is_some()
+        || input.excerpt.is_some()
+        || input.thumbnail.is_some()
+        || input.price.is_some()
+        || input.original_price.is_some()
+        || input.duration.is_some()
+        || input.lessons.is_some()
+        || input.outcomes.is_some()
+        || input.category.is_some()
+        || input.tags.is_some();
+    if edits_content {
+        ensure_editable_in_place(&current.status)?;
+    }
+
+    if input.original_price.is_some() || input.price.is_some() {
+        let price = input.price.as_ref().unwrap_or(&current.price);
+        let original = input
+            .original_price
+            .as_ref()
+            .or(current.original_price.as_ref());
+        check_original_price(price, original)?;
+    }
+
     let updated = repo
         .update_partial(
             id,
@@ -20,7 +62,7 @@ pub async fn update_course_by_id(
                 original_price: input.original_price,
                 duration: input.duration,
                 lessons: input.lessons,
-                status: input.status,
+                status: None,
                 outcomes: input.outcomes,
                 category: input.category,
                 tags: input.tags,
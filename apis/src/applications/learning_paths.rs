@@ -0,0 +1,595 @@
+//! Learning paths
+//!
+//! A path is an ordered track of courses, such as "Backend with Rust", sold
+//! together at one price. Enrolling buys the courses the learner does not own
+//! yet through a regular order whose items are discounted down to the path's
+//! price, prorated when some courses are already owned; paying for the order
+//! enrolls the learner in each course and in the path. A path may require
+//! courses to be completed first. Progress through a path is derived from
+//! the learner's progress in its courses.
+
+use std::collections::HashSet;
+
+use crate::applications::coupons::{Discount, split_discount};
+use crate::applications::courses::catalogue::page_offset;
+use crate::applications::courses::pricing::price_in_currency;
+use crate::applications::orders::{OrderServices, place_order, price_items};
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::money::Money;
+use crate::repositories::certificates::CertificatesRepository;
+use crate::repositories::courses::{CourseRecord, CourseStatus, CoursesRepository};
+use crate::repositories::enrollments::PathEnrollmentRecord;
+use crate::repositories::learning_paths::{
+    CreateLearningPathRecord, LearningPathRecord, LearningPathStatus, LearningPathsRepository,
+    UpdateLearningPathRecord,
+};
+use crate::repositories::lesson_progress::LessonProgressRepository;
+use crate::repositories::orders::CreateOrderRecord;
+use crate::types::course_types::{Page, PageMeta};
+use crate::types::learning_path_types::{
+    CreateLearningPathRequest, LearningPath, LearningPathDetail, LearningPathsQuery,
+    PathCourseProgress, PathEnrollment, PathEnrollmentResponse, PathPrerequisite, PathProgress,
+    SetPathCoursesRequest, UpdateLearningPathRequest,
+};
+
+/// What enrolling in a path and tracking progress through it need
+pub struct PathServices<'a> {
+    pub paths: &'a dyn LearningPathsRepository,
+    /// Path purchases are regular orders
+    pub orders: OrderServices<'a>,
+    pub certificates: &'a dyn CertificatesRepository,
+    pub progress: &'a dyn LessonProgressRepository,
+}
+
+/// Create a draft path without courses (admin only)
+pub async fn create_learning_path(
+    paths: &dyn LearningPathsRepository,
+    user: &AuthUser,
+    req: CreateLearningPathRequest,
+) -> AppResult<LearningPath> {
+    user.require_admin()?;
+    let path = paths
+        .create(CreateLearningPathRecord {
+            slug: req.slug.trim().to_string(),
+            title: req.title.trim().to_string(),
+            description: req.description,
+            excerpt: req.excerpt,
+            thumbnail: req.thumbnail,
+            price: req.price,
+            created_by: user.user_id,
+        })
+        .await?;
+    Ok(path.into())
+}
+
+/// Published paths, newest first; admins may list other statuses
+pub async fn list_learning_paths(
+    paths: &dyn LearningPathsRepository,
+    user: Option<&AuthUser>,
+    query: LearningPathsQuery,
+) -> AppResult<Page<LearningPath>> {
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = match query.per_page {
+        Some(n) if n >= 1 => n.min(100),
+        _ => 12,
+    };
+    let is_admin = user.is_some_and(|u| u.role == "admin");
+    let status = match query.status.as_deref() {
+        Some("all") if is_admin => None,
+        Some(s) if is_admin => Some(LearningPathStatus::parse(s).ok_or_else(|| {
+            AppError::BadRequest("status must be draft, published, archived or all".into())
+        })?),
+        _ => Some(LearningPathStatus::Published),
+    };
+
+    let offset = page_offset(page, per_page)?;
+    let (records, total) = paths.list_paginated(status, offset, per_page).await?;
+    Ok(Page {
+        items: records.into_iter().map(LearningPath::from).collect(),
+        meta: PageMeta {
+            page,
+            per_page,
+            total,
+            total_pages: if total == 0 {
+                1
+            } else {
+                (total + per_page - 1) / per_page
+            },
+        },
+    })
+}
+
+/// A path with its courses and prerequisites; unpublished paths are only
+/// shown to admins
+pub async fn get_learning_path(
+    paths: &dyn LearningPathsRepository,
+    courses: &dyn CoursesRepository,
+    user: Option<&AuthUser>,
+    id: uuid::Uuid,
+) -> AppResult<LearningPathDetail> {
+    let path = paths.find_by_id(id).await?;
+    path_detail(paths, courses, user, path).await
+}
+
+/// Like [`get_learning_path`], by slug
+pub async fn get_learning_path_by_slug(
+    paths: &dyn LearningPathsRepository,
+    courses: &dyn CoursesRepository,
+    user: Option<&AuthUser>,
+    slug: &str,
+) -> AppResult<LearningPathDetail> {
+    let path = paths.find_by_slug(slug).await?;
+    path_detail(paths, courses, user, path).await
+}
+
+/// Change a path's details, price or status (admin only)
+pub async fn update_learning_path(
+    paths: &dyn LearningPathsRepository,
+    courses: &dyn CoursesRepository,
+    user: &AuthUser,
+    id: uuid::Uuid,
+    req: UpdateLearningPathRequest,
+) -> AppResult<LearningPath> {
+    user.require_admin()?;
+    let path = load_path(paths, id).await?;
+    let status = match req.status.as_deref() {
+        Some(s) => Some(LearningPathStatus::parse(s).ok_or_else(|| {
+            AppError::BadRequest("status must be draft, published or archived".into())
+        })?),
+        None => None,
+    };
+    let published = match status {
+        Some(s) => s == LearningPathStatus::Published,
+        None => LearningPathStatus::parse(&path.status) == Some(LearningPathStatus::Published),
+    };
+    if published {
+        let currency = req.price.as_ref().unwrap_or(&path.price).currency.clone();
+        let course_ids = paths.list_course_ids(id).await?;
+        check_sellable(courses, &course_ids, &currency).await?;
+    }
+
+    let path = paths
+        .update_partial(
+            id,
+            UpdateLearningPathRecord {
+                title: req.title.map(|t| t.trim().to_string()),
+                description: req.description,
+                excerpt: req.excerpt,
+                thumbnail: req.thumbnail,
+                price: req.price,
+                status,
+            },
+        )
+        .await?
+        .ok_or_else(|| AppError::NotFound("Learning path not found".into()))?;
+    Ok(path.into())
+}
+
+pub async fn delete_learning_path(
+    paths: &dyn LearningPathsRepository,
+    user: &AuthUser,
+    id: uuid::Uuid,
+) -> AppResult<()> {
+    user.require_admin()?;
+    if !paths.delete_by_id(id).await? {
+        return Err(AppError::NotFound("Learning path not found".into()));
+    }
+    Ok(())
+}
+
+/// Replace a path's courses; they are taken in the order given (admin only)
+pub async fn set_learning_path_courses(
+    paths: &dyn LearningPathsRepository,
+    courses: &dyn CoursesRepository,
+    user: &AuthUser,
+    id: uuid::Uuid,
+    req: SetPathCoursesRequest,
+) -> AppResult<LearningPathDetail> {
+    user.require_admin()?;
+    let path = load_path(paths, id).await?;
+    check_distinct(&req.course_ids)?;
+    let prerequisites = paths.list_prerequisite_ids(id).await?;
+    if req.course_ids.iter().any(|c| prerequisites.contains(c)) {
+        return Err(AppError::BadRequest(
+            "A prerequisite of the path cannot also be one of its courses".into(),
+        ));
+    }
+    if LearningPathStatus::parse(&path.status) == Some(LearningPathStatus::Published) {
+        check_sellable(courses, &req.course_ids, &path.price.currency).await?;
+    } else {
+        load_courses(courses, &req.course_ids).await?;
+    }
+
+    paths.set_courses(id, &req.course_ids).await?;
+    get_learning_path(paths, courses, Some(user), id).await
+}
+
+/// Replace the courses to complete before enrolling in a path (admin only)
+pub async fn set_learning_path_prerequisites(
+    paths: &dyn LearningPathsRepository,
+    courses: &dyn CoursesRepository,
+    user: &AuthUser,
+    id: uuid::Uuid,
+    req: SetPathCoursesRequest,
+) -> AppResult<LearningPathDetail> {
+    user.require_admin()?;
+    load_path(paths, id).await?;
+    check_distinct(&req.course_ids)?;
+    let path_courses = paths.list_course_ids(id).await?;
+    if req.course_ids.iter().any(|c| path_courses.contains(c)) {
+        return Err(AppError::BadRequest(
+            "A course of the path cannot also be one of its prerequisites".into(),
+        ));
+    }
+    load_courses(courses, &req.course_ids).await?;
+
+    paths.set_prerequisites(id, &req.course_ids).await?;
+    get_learning_path(paths, courses, Some(user), id).await
+}
+
+/// Enroll the signed-in learner in a path.
+///
+/// The courses they do not own yet are bought in one order at the path's
+/// price, prorated by what those courses cost separately. Once it is paid,
+/// or right away when there is nothing left to pay, they are enrolled in the
+/// path and each of its courses.
+pub async fn enroll_in_learning_path(
+    svc: &PathServices<'_>,
+    user: &AuthUser,
+    id: uuid::Uuid,
+) -> AppResult<PathEnrollmentResponse> {
+    let path = load_path(svc.paths, id).await?;
+    if LearningPathStatus::parse(&path.status) != Some(LearningPathStatus::Published) {
+        return Err(AppError::NotFound("Learning path not found".into()));
+    }
+    if svc
+        .orders
+        .enrollments
+        .find_path_enrollment(user.user_id, path.id)
+        .await?
+        .is_some_and(|e| e.status == "active")
+    {
+        return Err(AppError::Conflict(
+            "You are already enrolled in this path".into(),
+        ));
+    }
+    check_prerequisites(svc, user, path.id).await?;
+
+    let course_ids = svc.paths.list_course_ids(path.id).await?;
+    if course_ids.is_empty() {
+        return Err(AppError::Conflict("This path has no courses yet".into()));
+    }
+    let currency = path.price.currency.clone();
+    let mut remaining = Vec::new();
+    let mut owned_cents = 0;
+    for course_id in course_ids {
+        if !svc
+            .orders
+            .enrollments
+            .is_enrolled(user.user_id, course_id)
+            .await?
+        {
+            remaining.push(course_id);
+            continue;
+        }
+        if let Some(course) = svc.orders.courses.find_by_id(course_id).await?
+            && let Some(price) = price_in_currency(svc.orders.courses, &course, &currency).await?
+        {
+            owned_cents += price.amount;
+        }
+    }
+
+    if remaining.is_empty() {
+        let enrollment = svc
+            .orders
+            .enrollments
+            .enroll_in_path(user.user_id, path.id, None)
+            .await?;
+        return Ok(PathEnrollmentResponse {
+            enrollment: Some(enrollment.into()),
+            checkout: None,
+        });
+    }
+
+    let mut items = price_items(&svc.orders, user, remaining, &currency).await?;
+    let remaining_cents: i64 = items.iter().map(|i| i.unit_price_cents).sum();
+    let charge = bundle_charge(
+        path.price.amount,
+        remaining_cents + owned_cents,
+        remaining_cents,
+    );
+    let prices: Vec<Option<i64>> = items.iter().map(|i| Some(i.unit_price_cents)).collect();
+    let discounts = split_discount(Discount::Fixed(remaining_cents - charge), &prices);
+    for (item, discount) in items.iter_mut().zip(discounts) {
+        item.discount_cents = discount;
+    }
+
+    let checkout = place_order(
+        &svc.orders,
+        user,
+        CreateOrderRecord {
+            user_id: user.user_id,
+            currency,
+            items,
+            coupon: None,
+            learning_path_id: Some(path.id),
+        },
+        Some(format!("Learning path {}", path.title)),
+    )
+    .await?;
+    let enrollment = svc
+        .orders
+        .enrollments
+        .find_path_enrollment(user.user_id, path.id)
+        .await?
+        .filter(|e| e.status == "active");
+    Ok(PathEnrollmentResponse {
+        enrollment: enrollment.map(PathEnrollment::from),
+        checkout: Some(checkout),
+    })
+}
+
+/// The signed-in learner's progress through a path's courses
+pub async fn get_learning_path_progress(
+    svc: &PathServices<'_>,
+    user: &AuthUser,
+    id: uuid::Uuid,
+) -> AppResult<PathProgress> {
+    let path = load_path(svc.paths, id).await?;
+    let enrolled = svc
+        .orders
+        .enrollments
+        .find_path_enrollment(user.user_id, path.id)
+        .await?
+        .is_some_and(|e| e.status == "active");
+    if !enrolled
+        && user.role != "admin"
+        && LearningPathStatus::parse(&path.status) != Some(LearningPathStatus::Published)
+    {
+        return Err(AppError::NotFound("Learning path not found".into()));
+    }
+
+    let mut courses = Vec::new();
+    for course_id in svc.paths.list_course_ids(path.id).await? {
+        let Some(course) = svc.orders.courses.find_by_id(course_id).await? else {
+            continue;
+        };
+        let record = svc
+            .progress
+            .course_progress(user.user_id, course_id)
+            .await?;
+        let completed_lessons = record.completed_lesson_ids.len() as i64;
+        courses.push(PathCourseProgress {
+            course_id,
+            title: course.title,
+            enrolled: svc
+                .orders
+                .enrollments
+                .is_enrolled(user.user_id, course_id)
+                .await?,
+            completed: svc
+                .certificates
+                .find_by_user_and_course(user.user_id, course_id)
+                .await?
+                .is_some(),
+            total_lessons: record.total_lessons,
+            completed_lessons,
+            percent: percent(completed_lessons, record.total_lessons),
+        });
+    }
+
+    let total_lessons = courses.iter().map(|c| c.total_lessons).sum();
+    let completed_lessons = courses.iter().map(|c| c.completed_lessons).sum();
+    Ok(PathProgress {
+        path_id: path.id,
+        enrolled,
+        total_courses: courses.len() as i64,
+        completed_courses: courses.iter().filter(|c| c.completed).count() as i64,
+        total_lessons,
+        completed_lessons,
+        percent: percent(completed_lessons, total_lessons),
+        next_course_id: courses.iter().find(|c| !c.completed).map(|c| c.course_id),
+        courses,
+    })
+}
+
+/// What is charged for the courses of a path not owned yet: the path's price
+/// in proportion to their share of what all its courses cost separately,
+/// rounded half up and never more than they cost on their own
+fn bundle_charge(path_price: i64, separate_cents: i64, remaining_cents: i64) -> i64 {
+    if separate_cents == 0 {
+        return 0;
+    }
+    let share = (path_price as i128 * remaining_cents as i128 * 2 + separate_cents as i128)
+        / (separate_cents as i128 * 2);
+    (share as i64).min(remaining_cents)
+}
+
+/// Share of `total` done, in percent with two decimals
+fn percent(done: i64, total: i64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    ((done as f64 / total as f64) * 10000.0).round() / 100.0
+}
+
+async fn check_prerequisites(
+    svc: &PathServices<'_>,
+    user: &AuthUser,
+    path_id: uuid::Uuid,
+) -> AppResult<()> {
+    let mut missing = Vec::new();
+    for course_id in svc.paths.list_prerequisite_ids(path_id).await? {
+        if svc
+            .certificates
+            .find_by_user_and_course(user.user_id, course_id)
+            .await?
+            .is_none()
+            && let Some(course) = svc.orders.courses.find_by_id(course_id).await?
+        {
+            missing.push(course.title);
+        }
+    }
+    if !missing.is_empty() {
+        return Err(AppError::Forbidden(format!(
+            "Complete {} before enrolling in this path",
+            missing.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Every course exists, is published and is sold in `currency`
+async fn check_sellable(
+    courses: &dyn CoursesRepository,
+    course_ids: &[uuid::Uuid],
+    currency: &str,
+) -> AppResult<()> {
+    if course_ids.is_empty() {
+        return Err(AppError::BadRequest(
+            "A published path needs at least one course".into(),
+        ));
+    }
+    for course in load_courses(courses, course_ids).await? {
+        if CourseStatus::parse(&course.status) != Some(CourseStatus::Published) {
+            return Err(AppError::BadRequest(format!(
+                "Course '{}' is not published",
+                course.title
+            )));
+        }
+        if price_in_currency(courses, &course, currency)
+            .await?
+            .is_none()
+        {
+            return Err(AppError::BadRequest(format!(
+                "'{}' is not sold in {}",
+                course.title, currency
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn check_distinct(course_ids: &[uuid::Uuid]) -> AppResult<()> {
+    let mut seen = HashSet::new();
+    if !course_ids.iter().all(|id| seen.insert(*id)) {
+        return Err(AppError::BadRequest("A course is listed twice".into()));
+    }
+    Ok(())
+}
+
+async fn load_courses(
+    courses: &dyn CoursesRepository,
+    course_ids: &[uuid::Uuid],
+) -> AppResult<Vec<CourseRecord>> {
+    let mut records = Vec::with_capacity(course_ids.len());
+    for id in course_ids {
+        let course = courses
+            .find_by_id(*id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Course {} not found", id)))?;
+        records.push(course);
+    }
+    Ok(records)
+}
+
+async fn load_path(
+    paths: &dyn LearningPathsRepository,
+    id: uuid::Uuid,
+) -> AppResult<LearningPathRecord> {
+    paths
+        .find_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Learning path not found".into()))
+}
+
+async fn path_detail(
+    paths: &dyn LearningPathsRepository,
+    courses: &dyn CoursesRepository,
+    user: Option<&AuthUser>,
+    path: Option<LearningPathRecord>,
+) -> AppResult<LearningPathDetail> {
+    let is_admin = user.is_some_and(|u| u.role == "admin");
+    let path = path
+        .filter(|p| {
+            is_admin || LearningPathStatus::parse(&p.status) == Some(LearningPathStatus::Published)
+        })
+        .ok_or_else(|| AppError::NotFound("Learning path not found".into()))?;
+
+    let currency = path.price.currency.clone();
+    let mut separate_cents = Some(0);
+    let mut path_courses = Vec::new();
+    for id in paths.list_course_ids(path.id).await? {
+        let Some(course) = courses.find_by_id(id).await? else {
+            continue;
+        };
+        let price = price_in_currency(courses, &course, &currency).await?;
+        separate_cents = separate_cents.zip(price).map(|(sum, p)| sum + p.amount);
+        path_courses.push(course.into());
+    }
+    let mut prerequisites = Vec::new();
+    for id in paths.list_prerequisite_ids(path.id).await? {
+        if let Some(course) = courses.find_by_id(id).await? {
+            prerequisites.push(PathPrerequisite {
+                course_id: course.id,
+                slug: course.slug,
+                title: course.title,
+            });
+        }
+    }
+
+    Ok(LearningPathDetail {
+        path: path.into(),
+        courses: path_courses,
+        prerequisites,
+        separate_price: separate_cents.map(|amount| Money::new(amount, currency)),
+    })
+}
+
+impl From<LearningPathRecord> for LearningPath {
+    fn from(record: LearningPathRecord) -> Self {
+        LearningPath {
+            id: record.id,
+            slug: record.slug,
+            title: record.title,
+            description: record.description,
+            excerpt: record.excerpt,
+            thumbnail: record.thumbnail,
+            status: record.status,
+            price: record.price,
+            course_count: record.course_count,
+            published_at: record.published_at,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        }
+    }
+}
+
+impl From<PathEnrollmentRecord> for PathEnrollment {
+    fn from(record: PathEnrollmentRecord) -> Self {
+        PathEnrollment {
+            path_id: record.path_id,
+            status: record.status,
+            order_id: record.order_id,
+            enrolled_at: record.enrolled_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_price_is_prorated_over_courses_not_owned_yet() {
+        // Three courses at 50.00 sold together for 100.00
+        assert_eq!(bundle_charge(10000, 15000, 15000), 10000);
+        assert_eq!(bundle_charge(10000, 15000, 10000), 6667);
+        assert_eq!(bundle_charge(10000, 15000, 5000), 3333);
+        // Never more than buying the rest one by one
+        assert_eq!(bundle_charge(20000, 15000, 5000), 5000);
+        assert_eq!(bundle_charge(0, 15000, 15000), 0);
+        assert_eq!(bundle_charge(10000, 0, 0), 0);
+    }
+}
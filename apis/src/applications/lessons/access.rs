@@ -0,0 +1,105 @@
+use crate::applications::courses::ensure_editable_in_place;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::courses::CoursesRepository;
+use crate::repositories::enrollments::EnrollmentsRepository;
+use crate::repositories::lessons::{LessonAccessRecord, LessonsRepository, ModuleAccessRecord};
+
+use super::release::{load_learner, release_lock};
+
+/// Repositories deciding who may see a lesson and whether it is released
+pub struct LessonGate<'a> {
+    pub lessons: &'a dyn LessonsRepository,
+    pub enrollments: &'a dyn EnrollmentsRepository,
+    pub courses: &'a dyn CoursesRepository,
+}
+
+/// Load a lesson's access facts or fail with NotFound
+pub async fn load_lesson_access(
+    lessons: &dyn LessonsRepository,
+    lesson_id: uuid::Uuid,
+) -> AppResult<LessonAccessRecord> {
+    lessons
+        .find_access_context(lesson_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Lesson not found".into()))
+}
+
+/// Whether `user` owns the lesson's course or is an admin
+pub fn can_manage(access: &LessonAccessRecord, user: Option<&AuthUser>) -> bool {
+    is_course_manager(access.instructor_id, user)
+}
+
+/// Whether `user` is the course's instructor or an admin
+pub fn is_course_manager(instructor_id: uuid::Uuid, user: Option<&AuthUser>) -> bool {
+    user.is_some_and(|u| u.role == "admin" || u.user_id == instructor_id)
+}
+
+/// Ensure `user` may see the lesson: its instructor or an admin always can;
+/// anyone can see a published free lesson; otherwise an active enrollment in
+/// the course is required, and the lesson must be released to the learner
+/// (prerequisites completed and the module's drip date reached).
+pub async fn ensure_can_view_lesson(
+    gate: &LessonGate<'_>,
+    lesson_id: uuid::Uuid,
+    user: Option<&AuthUser>,
+) -> AppResult<LessonAccessRecord> {
+    let access = load_lesson_access(gate.lessons, lesson_id).await?;
+    if can_manage(&access, user) {
+        return Ok(access);
+    }
+    if !access.published {
+        return Err(AppError::NotFound("Lesson not found".into()));
+    }
+    if access.is_free {
+        return Ok(access);
+    }
+    let user = user.ok_or_else(|| AppError::Unauthorized("Sign in to access this lesson".into()))?;
+    let learner = load_learner(gate.enrollments, gate.courses, access.course_id, Some(user)).await?;
+    if learner.enrolled_at.is_none() {
+        return Err(AppError::Forbidden("Enroll in this course to access the lesson".into()));
+    }
+    if let Some(lock) = release_lock(&access.drip, &learner, chrono::Utc::now()) {
+        return Err(AppError::Forbidden(lock.message()));
+    }
+    Ok(access)
+}
+
+/// Ensure `user` may change the lesson (instructor of its course or admin)
+pub async fn ensure_can_manage_lesson(
+    lessons: &dyn LessonsRepository,
+    lesson_id: uuid::Uuid,
+    user: &AuthUser,
+) -> AppResult<LessonAccessRecord> {
+    let access = load_lesson_access(lessons, lesson_id).await?;
+    if !can_manage(&access, Some(user)) {
+        return Err(AppError::Forbidden(
+            "Only the course instructor can manage this lesson".into(),
+        ));
+    }
+    Ok(access)
+}
+
+/// Ensure the lesson may be changed in place: content of a published course
+/// is only edited through a draft revision
+pub async fn ensure_lesson_editable(
+    lessons: &dyn LessonsRepository,
+    lesson_id: uuid::Uuid,
+) -> AppResult<LessonAccessRecord> {
+    let access = load_lesson_access(lessons, lesson_id).await?;
+    ensure_editable_in_place(&access.course_status)?;
+    Ok(access)
+}
+
+/// Ensure the module and its lessons may be changed in place
+pub async fn ensure_module_editable(
+    lessons: &dyn LessonsRepository,
+    module_id: uuid::Uuid,
+) -> AppResult<ModuleAccessRecord> {
+    let module = lessons
+        .find_module_access_context(module_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Module not found".into()))?;
+    ensure_editable_in_place(&module.course_status)?;
+    Ok(module)
+}
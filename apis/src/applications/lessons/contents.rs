@@ -1,4 +1,4 @@
-use crate::pkg::error::AppResult;
+use crate::pkg::error::{AppError, AppResult};
 use crate::repositories::lesson_contents::{CreateLessonContentRecord, LessonContentRecord, LessonContentsRepository, UpdateLessonContentRecord};
 use crate::types::course_types::{CreateLessonContentRequest, LessonContent, UpdateLessonContentRequest};
 
@@ -13,6 +13,7 @@ pub async fn create_content(repo: &dyn LessonContentsRepository, input: CreateLe
             file_size: input.file_size,
             filename: input.filename,
             position: input.position,
+            is_private: false,
         })
         .await?;
     Ok(id)
@@ -42,18 +43,41 @@ pub async fn update_content(repo: &dyn LessonContentsRepository, id: uuid::Uuid,
     Ok(updated.map(map_content))
 }
 
-/// Delete a content by id
-pub async fn delete_content(repo: &dyn LessonContentsRepository, id: uuid::Uuid) -> AppResult<()> {
-    repo.delete_by_id(id).await
+/// Delete a content by id, removing its file when it lives in private storage
+pub async fn delete_content(
+    repo: &dyn LessonContentsRepository,
+    private_root: &std::path::Path,
+    id: uuid::Uuid,
+) -> AppResult<()> {
+    let existing = repo.find_by_id(id).await?;
+    repo.delete_by_id(id).await?;
+    if let Some(rec) = existing.filter(|r| r.is_private) {
+        crate::pkg::private_media::remove_key(private_root, &rec.url).await;
+    }
+    Ok(())
+}
+
+/// The lesson a content belongs to
+pub async fn content_lesson_id(repo: &dyn LessonContentsRepository, id: uuid::Uuid) -> AppResult<uuid::Uuid> {
+    let content = repo.find_by_id(id).await?.ok_or_else(|| AppError::NotFound("Lesson content not found".into()))?;
+    Ok(content.lesson_id)
 }
 
-fn map_content(r: LessonContentRecord) -> LessonContent {
+/// Convert a record to its API shape. Private contents never expose their
+/// storage key; `url` points at the endpoint that issues a signed link.
+pub(crate) fn map_content(r: LessonContentRecord) -> LessonContent {
+    let url = if r.is_private {
+        format!("/api/lesson-contents/{}/download-url", r.id)
+    } else {
+        r.url
+    };
     LessonContent {
         id: r.id,
         lesson_id: r.lesson_id,
         title: r.title,
         content_type: r.content_type,
-        url: r.url,
+        url,
+        is_private: r.is_private,
         file_size: r.file_size,
         filename: r.filename,
         position: r.position,
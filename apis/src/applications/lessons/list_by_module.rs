@@ -1,12 +1,34 @@
-use crate::pkg::error::AppResult;
-use crate::repositories::lessons::LessonsRepository;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
 use crate::types::course_types::Lesson;
 
-/// List lessons for a module ordered by position/created_at
+use super::access::LessonGate;
+use super::policy::{gate_lesson, load_viewer};
+
+/// List lessons for a module ordered by position/created_at.
+///
+/// Unpublished lessons are left out for everyone but the course's managers;
+/// paid lessons the viewer may not open yet are listed as locked, without
+/// their content and video.
 pub async fn list_lessons_by_module(
-    repo: &dyn LessonsRepository,
+    gate: &LessonGate<'_>,
     module_id: uuid::Uuid,
+    user: Option<&AuthUser>,
 ) -> AppResult<Vec<Lesson>> {
-    let items = repo.list_by_module(module_id).await?;
-    Ok(items.into_iter().map(Lesson::from).collect())
+    let module = gate
+        .lessons
+        .find_module_access_context(module_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Module not found".into()))?;
+    let viewer = load_viewer(gate, module.course_id, module.instructor_id, user).await?;
+    let now = chrono::Utc::now();
+    let items = gate.lessons.list_by_module(module_id).await?;
+    Ok(items
+        .into_iter()
+        .filter_map(|rec| {
+            let visibility =
+                viewer.lesson_visibility(rec.is_free, rec.published, &module.drip, now);
+            gate_lesson(Lesson::from(rec), visibility)
+        })
+        .collect())
 }
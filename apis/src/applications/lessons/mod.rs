@@ -8,6 +8,13 @@ pub mod contents;
 pub mod questions;
 pub mod assignment;
 pub mod bulk_update_positions;
+pub mod access;
+pub mod private_contents;
+pub mod policy;
+pub mod progress;
+pub mod release;
+pub mod subtitles;
+pub mod video;
 
 pub use create::create_lesson;
 pub use delete_by_id::delete_lesson_by_id as delete_lesson;
@@ -31,6 +38,8 @@ impl From<LessonRecord> for Lesson {
             position: rec.position,
             is_free: rec.is_free,
             published: rec.published,
+            locked: false,
+            unlocks_at: None,
             created_at: rec.created_at,
             updated_at: rec.updated_at,
         }
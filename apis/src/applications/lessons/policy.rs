@@ -0,0 +1,175 @@
+//! Curriculum access policy
+//!
+//! Decides how much of a course's lessons a viewer sees when browsing the
+//! curriculum. The course's instructor and admins see everything. Others
+//! never see unpublished lessons; they get the full lesson only when it is
+//! free, or when they are enrolled and the lesson is released to them (see
+//! [`super::release`]). Every other lesson is reduced to its outline: title,
+//! description, duration and position, without content, video, attachments,
+//! quiz or assignment. Only managers see which quiz options are correct.
+
+use chrono::{DateTime, Utc};
+
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::AppResult;
+use crate::repositories::modules::DripSchedule;
+use crate::types::course_types::Lesson;
+
+use super::access::{LessonGate, is_course_manager};
+use super::release::{Learner, load_learner, release_lock};
+
+/// How much of a lesson a viewer may see
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LessonVisibility {
+    /// Left out entirely
+    Hidden,
+    /// Listed in the curriculum without its content
+    Outline {
+        unlocks_at: Option<DateTime<Utc>>,
+    },
+    Full,
+}
+
+/// A viewer of one course's curriculum
+#[derive(Debug, Clone)]
+pub struct CourseViewer {
+    /// The course's instructor or an admin
+    pub manager: bool,
+    pub learner: Learner,
+}
+
+impl CourseViewer {
+    pub fn lesson_visibility(
+        &self,
+        is_free: bool,
+        published: bool,
+        drip: &DripSchedule,
+        now: DateTime<Utc>,
+    ) -> LessonVisibility {
+        if self.manager {
+            return LessonVisibility::Full;
+        }
+        if !published {
+            return LessonVisibility::Hidden;
+        }
+        if is_free {
+            return LessonVisibility::Full;
+        }
+        if self.learner.enrolled_at.is_none() {
+            return LessonVisibility::Outline { unlocks_at: None };
+        }
+        match release_lock(drip, &self.learner, now) {
+            Some(lock) => LessonVisibility::Outline {
+                unlocks_at: lock.unlocks_at(),
+            },
+            None => LessonVisibility::Full,
+        }
+    }
+}
+
+/// Who `user` is to the course; managers skip the enrollment lookups
+pub async fn load_viewer(
+    gate: &LessonGate<'_>,
+    course_id: uuid::Uuid,
+    instructor_id: uuid::Uuid,
+    user: Option<&AuthUser>,
+) -> AppResult<CourseViewer> {
+    if is_course_manager(instructor_id, user) {
+        return Ok(CourseViewer {
+            manager: true,
+            learner: Learner::default(),
+        });
+    }
+    let learner = load_learner(gate.enrollments, gate.courses, course_id, user).await?;
+    Ok(CourseViewer {
+        manager: false,
+        learner,
+    })
+}
+
+/// Apply `visibility` to a lesson; `None` when it is hidden
+pub(crate) fn gate_lesson(mut lesson: Lesson, visibility: LessonVisibility) -> Option<Lesson> {
+    match visibility {
+        LessonVisibility::Hidden => None,
+        LessonVisibility::Outline { unlocks_at } => {
+            lesson.locked = true;
+            lesson.unlocks_at = unlocks_at;
+            lesson.content = None;
+            lesson.video_url = None;
+            Some(lesson)
+        }
+        LessonVisibility::Full => Some(lesson),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn viewer(manager: bool, enrolled: bool) -> CourseViewer {
+        CourseViewer {
+            manager,
+            learner: Learner {
+                enrolled_at: enrolled.then(Utc::now),
+                unmet_prerequisites: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn outline_for_outsiders_full_for_free_and_enrolled() {
+        let now = Utc::now();
+        let open = DripSchedule::default();
+        let outline = LessonVisibility::Outline { unlocks_at: None };
+
+        let anonymous = viewer(false, false);
+        assert_eq!(
+            anonymous.lesson_visibility(false, true, &open, now),
+            outline
+        );
+        assert_eq!(
+            anonymous.lesson_visibility(true, true, &open, now),
+            LessonVisibility::Full
+        );
+        assert_eq!(
+            anonymous.lesson_visibility(true, false, &open, now),
+            LessonVisibility::Hidden
+        );
+
+        let enrolled = viewer(false, true);
+        assert_eq!(
+            enrolled.lesson_visibility(false, true, &open, now),
+            LessonVisibility::Full
+        );
+        assert_eq!(
+            enrolled.lesson_visibility(false, false, &open, now),
+            LessonVisibility::Hidden
+        );
+
+        let instructor = viewer(true, false);
+        assert_eq!(
+            instructor.lesson_visibility(false, false, &open, now),
+            LessonVisibility::Full
+        );
+    }
+
+    #[test]
+    fn dripped_lessons_stay_outlined_until_released() {
+        let now = Utc::now();
+        let at = now + chrono::Duration::days(2);
+        let drip = DripSchedule {
+            days: None,
+            at: Some(at),
+        };
+        assert_eq!(
+            viewer(false, true).lesson_visibility(false, true, &drip, now),
+            LessonVisibility::Outline {
+                unlocks_at: Some(at)
+            }
+        );
+        assert_eq!(
+            viewer(false, true).lesson_visibility(true, true, &drip, now),
+            LessonVisibility::Full
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use crate::applications::lessons::access::{LessonGate, ensure_can_view_lesson};
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::private_media::UrlSigner;
+use crate::repositories::lesson_contents::{CreateLessonContentRecord, LessonContentsRepository};
+use crate::types::course_types::LessonContentDownload;
+
+/// A file already written to private storage, ready to be attached to a lesson
+#[derive(Debug, Clone)]
+pub struct PrivateContentInput {
+    pub lesson_id: uuid::Uuid,
+    pub title: String,
+    pub content_type: String,
+    /// Storage key returned by `private_media::save_lesson_file`
+    pub key: String,
+    pub file_size: Option<i64>,
+    pub filename: Option<String>,
+    pub position: i32,
+}
+
+/// Record a privately stored file as lesson content
+pub async fn create_private_content(
+    repo: &dyn LessonContentsRepository,
+    input: PrivateContentInput,
+) -> AppResult<uuid::Uuid> {
+    repo.create(CreateLessonContentRecord {
+        lesson_id: input.lesson_id,
+        title: input.title,
+        content_type: input.content_type,
+        url: input.key,
+        file_size: input.file_size,
+        filename: input.filename,
+        position: input.position,
+        is_private: true,
+    })
+    .await
+}
+
+/// Issue a download link for a lesson content.
+///
+/// Public contents are returned as-is. Private contents require the caller to
+/// be allowed to see the lesson and get a signed URL valid for `ttl_seconds`.
+pub async fn issue_download_url(
+    gate: &LessonGate<'_>,
+    contents: &dyn LessonContentsRepository,
+    signer: &UrlSigner,
+    ttl_seconds: i64,
+    content_id: uuid::Uuid,
+    user: Option<&AuthUser>,
+) -> AppResult<LessonContentDownload> {
+    let content = contents
+        .find_by_id(content_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Lesson content not found".into()))?;
+    ensure_can_view_lesson(gate, content.lesson_id, user).await?;
+
+    if !content.is_private {
+        return Ok(LessonContentDownload {
+            url: content.url,
+            expires_at: None,
+        });
+    }
+
+    let now = chrono::Utc::now();
+    let link = signer.sign_download(&content.url, ttl_seconds, now.timestamp());
+    Ok(LessonContentDownload {
+        url: link.url,
+        expires_at: chrono::DateTime::from_timestamp(link.expires_at, 0),
+    })
+}
@@ -0,0 +1,264 @@
+//! Lesson completion and quiz attempts
+//!
+//! Enrolled learners mark lessons complete; a lesson with quiz questions is
+//! only completed by a passing quiz attempt. Each change re-checks the course
+//! and issues the completion certificate once everything is done. When an
+//! enrollment is revoked the learner's progress is frozen: kept and readable,
+//! but no longer updated.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::applications::certificates::CertificateIssuer;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::courses::CoursesRepository;
+use crate::repositories::enrollments::EnrollmentsRepository;
+use crate::repositories::lesson_progress::{
+    CourseProgressRecord, CreateQuizAttemptRecord, LessonProgressRepository,
+};
+use crate::repositories::lesson_questions::LessonQuestionsRepository;
+use crate::repositories::lessons::{LessonAccessRecord, LessonsRepository};
+use crate::types::certificate_types::{
+    Certificate, CourseProgress, QuizAnswerInput, QuizAttemptResult, SubmitQuizAttemptRequest,
+};
+
+use super::access::load_lesson_access;
+use super::release::{load_learner, release_lock};
+
+/// Repositories and settings progress tracking works with
+pub struct ProgressServices<'a> {
+    pub lessons: &'a dyn LessonsRepository,
+    pub enrollments: &'a dyn EnrollmentsRepository,
+    pub courses: &'a dyn CoursesRepository,
+    pub questions: &'a dyn LessonQuestionsRepository,
+    pub progress: &'a dyn LessonProgressRepository,
+    pub issuer: CertificateIssuer<'a>,
+}
+
+/// Mark a lesson complete for the signed-in learner
+pub async fn complete_lesson(
+    svc: &ProgressServices<'_>,
+    user: &AuthUser,
+    lesson_id: uuid::Uuid,
+) -> AppResult<CourseProgress> {
+    let access = load_enrolled_lesson(svc, user, lesson_id).await?;
+    if !svc.questions.list_questions(lesson_id).await?.is_empty()
+        && !svc
+            .progress
+            .has_passed_quiz(user.user_id, lesson_id)
+            .await?
+    {
+        return Err(AppError::BadRequest(
+            "Pass this lesson's quiz to complete it".into(),
+        ));
+    }
+    svc.progress
+        .mark_completed(user.user_id, lesson_id, access.course_id)
+        .await?;
+    course_progress(svc, user.user_id, access.course_id).await
+}
+
+/// Grade a quiz attempt; a passing attempt also completes the lesson
+pub async fn submit_quiz_attempt(
+    svc: &ProgressServices<'_>,
+    user: &AuthUser,
+    lesson_id: uuid::Uuid,
+    req: SubmitQuizAttemptRequest,
+) -> AppResult<QuizAttemptResult> {
+    let access = load_enrolled_lesson(svc, user, lesson_id).await?;
+    let question_rows = svc.questions.list_questions(lesson_id).await?;
+    if question_rows.is_empty() {
+        return Err(AppError::BadRequest("This lesson has no quiz".into()));
+    }
+    let mut answer_key = HashMap::with_capacity(question_rows.len());
+    for question in &question_rows {
+        let correct: HashSet<uuid::Uuid> = svc
+            .questions
+            .list_options(question.id)
+            .await?
+            .into_iter()
+            .filter(|o| o.is_correct)
+            .map(|o| o.id)
+            .collect();
+        answer_key.insert(question.id, correct);
+    }
+
+    let correct = grade(&answer_key, &req.answers);
+    let total = answer_key.len() as i32;
+    let score = ((correct as f64 / total as f64) * 10000.0).round() / 100.0;
+    let pass_mark = svc.issuer.config.quiz_pass_mark;
+    let passed = score >= pass_mark;
+    let attempt = svc
+        .progress
+        .create_quiz_attempt(CreateQuizAttemptRecord {
+            user_id: user.user_id,
+            lesson_id,
+            course_id: access.course_id,
+            correct,
+            total,
+            score,
+            passed,
+        })
+        .await?;
+    if passed {
+        svc.progress
+            .mark_completed(user.user_id, lesson_id, access.course_id)
+            .await?;
+    }
+
+    Ok(QuizAttemptResult {
+        id: attempt.id,
+        lesson_id,
+        correct,
+        total,
+        score,
+        pass_mark,
+        passed,
+        created_at: attempt.created_at,
+        progress: course_progress(svc, user.user_id, access.course_id).await?,
+    })
+}
+
+/// The signed-in learner's progress through a course; frozen as of the
+/// revocation when their enrollment was revoked
+pub async fn get_course_progress(
+    svc: &ProgressServices<'_>,
+    user: &AuthUser,
+    course_id: uuid::Uuid,
+) -> AppResult<CourseProgress> {
+    let enrollment = svc
+        .enrollments
+        .find_by_user_and_course(user.user_id, course_id)
+        .await?
+        .ok_or_else(|| AppError::Forbidden("Enroll in this course to track progress".into()))?;
+    if enrollment.status == "active" {
+        return course_progress(svc, user.user_id, course_id).await;
+    }
+    let record = svc
+        .progress
+        .course_progress(user.user_id, course_id)
+        .await?;
+    let certificate = svc.issuer.held(user.user_id, course_id).await?;
+    let mut progress = to_progress(course_id, record, certificate);
+    progress.frozen_at = enrollment.revoked_at.or(enrollment.updated_at);
+    Ok(progress)
+}
+
+/// Published lesson of a course the learner is enrolled in, released to them
+async fn load_enrolled_lesson(
+    svc: &ProgressServices<'_>,
+    user: &AuthUser,
+    lesson_id: uuid::Uuid,
+) -> AppResult<LessonAccessRecord> {
+    let access = load_lesson_access(svc.lessons, lesson_id).await?;
+    if !access.published {
+        return Err(AppError::NotFound("Lesson not found".into()));
+    }
+    let learner = load_learner(svc.enrollments, svc.courses, access.course_id, Some(user)).await?;
+    if learner.enrolled_at.is_none() {
+        return Err(AppError::Forbidden(
+            "Enroll in this course to track progress".into(),
+        ));
+    }
+    if !access.is_free
+        && let Some(lock) = release_lock(&access.drip, &learner, chrono::Utc::now())
+    {
+        return Err(AppError::Forbidden(lock.message()));
+    }
+    Ok(access)
+}
+
+async fn course_progress(
+    svc: &ProgressServices<'_>,
+    user_id: uuid::Uuid,
+    course_id: uuid::Uuid,
+) -> AppResult<CourseProgress> {
+    let record = svc.progress.course_progress(user_id, course_id).await?;
+    let certificate = svc
+        .issuer
+        .issue_if_complete(user_id, course_id, &record)
+        .await?;
+    Ok(to_progress(course_id, record, certificate))
+}
+
+/// Number of questions answered exactly right: every correct option chosen
+/// and nothing else. Unknown questions and repeated answers are ignored.
+fn grade(
+    answer_key: &HashMap<uuid::Uuid, HashSet<uuid::Uuid>>,
+    answers: &[QuizAnswerInput],
+) -> i32 {
+    let mut seen = HashSet::new();
+    answers
+        .iter()
+        .filter(|a| seen.insert(a.question_id))
+        .filter(|a| {
+            answer_key.get(&a.question_id).is_some_and(|correct| {
+                let chosen: HashSet<uuid::Uuid> = a.option_ids.iter().copied().collect();
+                !correct.is_empty() && chosen == *correct
+            })
+        })
+        .count() as i32
+}
+
+fn to_progress(
+    course_id: uuid::Uuid,
+    record: CourseProgressRecord,
+    certificate: Option<Certificate>,
+) -> CourseProgress {
+    let completed_lessons = record.completed_lesson_ids.len() as i64;
+    let percent = if record.total_lessons == 0 {
+        0.0
+    } else {
+        ((completed_lessons as f64 / record.total_lessons as f64) * 10000.0).round() / 100.0
+    };
+    CourseProgress {
+        course_id,
+        total_lessons: record.total_lessons,
+        completed_lessons,
+        percent,
+        completed: certificate.is_some(),
+        completed_lesson_ids: record.completed_lesson_ids,
+        quiz_lessons: record.quiz_lessons,
+        passed_quiz_lessons: record.passed_quiz_lessons,
+        certificate,
+        frozen_at: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer(question_id: uuid::Uuid, option_ids: &[uuid::Uuid]) -> QuizAnswerInput {
+        QuizAnswerInput {
+            question_id,
+            option_ids: option_ids.to_vec(),
+        }
+    }
+
+    #[test]
+    fn grading_needs_exactly_the_correct_options() {
+        let (q1, q2, q3) = (
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+        );
+        let (a, b, c) = (
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+        );
+        let key = HashMap::from([
+            (q1, HashSet::from([a])),
+            (q2, HashSet::from([b, c])),
+            (q3, HashSet::from([c])),
+        ]);
+
+        assert_eq!(grade(&key, &[answer(q1, &[a]), answer(q2, &[c, b])]), 2);
+        // Partially right or over-selected answers score nothing
+        assert_eq!(grade(&key, &[answer(q2, &[b]), answer(q3, &[a, c])]), 0);
+        // A question answered twice only counts its first answer
+        assert_eq!(grade(&key, &[answer(q1, &[b]), answer(q1, &[a])]), 0);
+        assert_eq!(grade(&key, &[answer(uuid::Uuid::new_v4(), &[a])]), 0);
+    }
+}
@@ -1,4 +1,6 @@
-use crate::pkg::error::AppResult;
+use crate::applications::lessons::access::{LessonGate, can_manage, ensure_can_view_lesson};
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
 use crate::repositories::lesson_questions::{CreateLessonQuestionRecord, CreateQuestionOptionRecord, LessonQuestionRecord, LessonQuestionsRepository, QuestionOptionRecord, UpdateLessonQuestionRecord, UpdateQuestionOptionRecord};
 use crate::types::course_types::{CreateLessonQuestionRequest, CreateQuestionOptionRequest, LessonQuestion, QuestionOption, UpdateLessonQuestionRequest, UpdateQuestionOptionRequest};
 
@@ -47,9 +49,23 @@ pub async fn create_option(repo: &dyn LessonQuestionsRepository, input: CreateQu
         .await
 }
 
-pub async fn list_options(repo: &dyn LessonQuestionsRepository, question_id: uuid::Uuid) -> AppResult<Vec<QuestionOption>> {
+/// List the options of a question on a lesson `user` may view; only the
+/// course's instructor and admins see which are correct
+pub async fn list_options(repo: &dyn LessonQuestionsRepository, gate: &LessonGate<'_>, question_id: uuid::Uuid, user: Option<&AuthUser>) -> AppResult<Vec<QuestionOption>> {
+    let question = repo.find_question(question_id).await?.ok_or_else(|| AppError::NotFound("Question not found".into()))?;
+    let access = ensure_can_view_lesson(gate, question.lesson_id, user).await?;
+    let reveal = can_manage(&access, user);
     let rows = repo.list_options(question_id).await?;
-    Ok(rows.into_iter().map(map_option).collect())
+    Ok(rows
+        .into_iter()
+        .map(map_option)
+        .map(|mut o| {
+            if !reveal {
+                o.is_correct = None;
+            }
+            o
+        })
+        .collect())
 }
 
 pub async fn update_option(repo: &dyn LessonQuestionsRepository, id: uuid::Uuid, input: UpdateQuestionOptionRequest) -> AppResult<Option<QuestionOption>> {
@@ -70,6 +86,18 @@ pub async fn delete_option(repo: &dyn LessonQuestionsRepository, id: uuid::Uuid)
     repo.delete_option(id).await
 }
 
+/// The lesson a question belongs to
+pub async fn question_lesson_id(repo: &dyn LessonQuestionsRepository, question_id: uuid::Uuid) -> AppResult<uuid::Uuid> {
+    let question = repo.find_question(question_id).await?.ok_or_else(|| AppError::NotFound("Question not found".into()))?;
+    Ok(question.lesson_id)
+}
+
+/// The lesson an option's question belongs to
+pub async fn option_lesson_id(repo: &dyn LessonQuestionsRepository, option_id: uuid::Uuid) -> AppResult<uuid::Uuid> {
+    let option = repo.find_option(option_id).await?.ok_or_else(|| AppError::NotFound("Option not found".into()))?;
+    question_lesson_id(repo, option.question_id).await
+}
+
 fn map_question(r: LessonQuestionRecord) -> LessonQuestion {
     LessonQuestion {
         id: r.id,
@@ -86,7 +114,7 @@ fn map_option(r: QuestionOptionRecord) -> QuestionOption {
         id: r.id,
         question_id: r.question_id,
         option_text: r.option_text,
-        is_correct: r.is_correct,
+        is_correct: Some(r.is_correct),
         position: r.position,
         created_at: r.created_at,
         updated_at: r.updated_at,
@@ -0,0 +1,169 @@
+//! Course prerequisites and drip-fed lesson release
+//!
+//! A course's paid lessons open to an enrolled learner once they hold the
+//! certificate of every prerequisite course. A module's lessons may further
+//! wait for the module's drip schedule: a number of days after the learner
+//! enrolled, or a fixed date. Free lessons and the course's managers are never
+//! held back.
+
+use chrono::{DateTime, Utc};
+
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::AppResult;
+use crate::repositories::courses::CoursesRepository;
+use crate::repositories::enrollments::EnrollmentsRepository;
+use crate::repositories::modules::DripSchedule;
+
+/// What decides which lessons of a course are released to a viewer
+#[derive(Debug, Clone, Default)]
+pub struct Learner {
+    /// Start of the viewer's active enrollment
+    pub enrolled_at: Option<DateTime<Utc>>,
+    /// Titles of the prerequisite courses the viewer has not completed
+    pub unmet_prerequisites: Vec<String>,
+}
+
+/// Why a lesson is not released to a viewer yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LessonLock {
+    /// Titles of the prerequisite courses to complete first
+    Prerequisites(Vec<String>),
+    /// The lesson opens at this time
+    Until(DateTime<Utc>),
+    /// The lesson opens this many days after enrolling
+    AfterEnrolling(i32),
+}
+
+impl LessonLock {
+    /// When the lesson opens, if that does not depend on the viewer's actions
+    pub fn unlocks_at(&self) -> Option<DateTime<Utc>> {
+        match self {
+            LessonLock::Until(at) => Some(*at),
+            _ => None,
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            LessonLock::Prerequisites(titles) => {
+                format!("Complete {} to unlock this lesson", titles.join(", "))
+            }
+            LessonLock::Until(at) => format!("This lesson unlocks on {}", at.to_rfc3339()),
+            LessonLock::AfterEnrolling(days) => {
+                format!("This lesson unlocks {} days after enrolling", days)
+            }
+        }
+    }
+}
+
+/// Prerequisites and enrollment of `user` in a course; anonymous viewers
+/// have completed nothing
+pub async fn load_learner(
+    enrollments: &dyn EnrollmentsRepository,
+    courses: &dyn CoursesRepository,
+    course_id: uuid::Uuid,
+    user: Option<&AuthUser>,
+) -> AppResult<Learner> {
+    let Some(user) = user else {
+        let prerequisites = courses.list_prerequisites(course_id).await?;
+        return Ok(Learner {
+            enrolled_at: None,
+            unmet_prerequisites: prerequisites.into_iter().map(|p| p.title).collect(),
+        });
+    };
+    let enrolled_at = enrollments
+        .find_by_user_and_course(user.user_id, course_id)
+        .await?
+        .filter(|e| e.status == "active")
+        .map(|e| e.enrolled_at);
+    let unmet = courses.unmet_prerequisites(course_id, user.user_id).await?;
+    Ok(Learner {
+        enrolled_at,
+        unmet_prerequisites: unmet.into_iter().map(|p| p.title).collect(),
+    })
+}
+
+/// What still holds back a paid lesson of a module with schedule `drip`;
+/// `None` once it is released to the learner
+pub fn release_lock(
+    drip: &DripSchedule,
+    learner: &Learner,
+    now: DateTime<Utc>,
+) -> Option<LessonLock> {
+    if !learner.unmet_prerequisites.is_empty() {
+        return Some(LessonLock::Prerequisites(
+            learner.unmet_prerequisites.clone(),
+        ));
+    }
+    if let Some(at) = drip.at
+        && at > now
+    {
+        return Some(LessonLock::Until(at));
+    }
+    match (drip.days, learner.enrolled_at) {
+        (Some(days), Some(enrolled_at)) => {
+            let at = enrolled_at + chrono::Duration::days(days.into());
+            (at > now).then_some(LessonLock::Until(at))
+        }
+        (Some(days), None) if days > 0 => Some(LessonLock::AfterEnrolling(days)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn learner(enrolled_days_ago: Option<i64>, now: DateTime<Utc>) -> Learner {
+        Learner {
+            enrolled_at: enrolled_days_ago.map(|d| now - chrono::Duration::days(d)),
+            unmet_prerequisites: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn drip_days_count_from_enrollment() {
+        let now = Utc::now();
+        let drip = DripSchedule {
+            days: Some(7),
+            at: None,
+        };
+        assert_eq!(release_lock(&drip, &learner(Some(10), now), now), None);
+        assert_eq!(
+            release_lock(&drip, &learner(Some(3), now), now),
+            Some(LessonLock::Until(now + chrono::Duration::days(4)))
+        );
+        assert_eq!(
+            release_lock(&drip, &learner(None, now), now),
+            Some(LessonLock::AfterEnrolling(7))
+        );
+        assert_eq!(
+            release_lock(&DripSchedule::default(), &learner(None, now), now),
+            None
+        );
+    }
+
+    #[test]
+    fn prerequisites_and_fixed_dates_lock_everyone() {
+        let now = Utc::now();
+        let at = now + chrono::Duration::hours(1);
+        let drip = DripSchedule {
+            days: None,
+            at: Some(at),
+        };
+        assert_eq!(
+            release_lock(&drip, &learner(Some(30), now), now),
+            Some(LessonLock::Until(at))
+        );
+        assert_eq!(release_lock(&drip, &learner(Some(30), now), at), None);
+
+        let blocked = Learner {
+            unmet_prerequisites: vec!["Rust Basics".into()],
+            ..learner(Some(30), now)
+        };
+        assert_eq!(
+            release_lock(&DripSchedule::default(), &blocked, now),
+            Some(LessonLock::Prerequisites(vec!["Rust Basics".into()]))
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::subtitles::{self, SubtitleFormat};
+use crate::repositories::lesson_subtitles::{
+    LessonSubtitleRecord, LessonSubtitlesRepository, UpsertLessonSubtitleRecord,
+};
+use crate::types::course_types::{LessonSubtitle, LessonTranscript};
+
+/// Largest subtitle file accepted on upload (bytes)
+pub const MAX_SUBTITLE_BYTES: usize = 2 * 1024 * 1024;
+
+/// Validate a BCP 47-style language tag and normalise its case (`pt-br` → `pt-BR`)
+pub fn normalize_language(value: &str) -> AppResult<String> {
+    let invalid = || AppError::BadRequest("language must be a tag like 'en' or 'pt-BR'".into());
+    let mut parts = value.trim().split('-');
+    let primary = parts
+        .next()
+        .filter(|p| (2..=3).contains(&p.len()) && p.chars().all(|c| c.is_ascii_alphabetic()))
+        .ok_or_else(invalid)?;
+    let mut out = primary.to_ascii_lowercase();
+    for sub in parts {
+        if !(2..=8).contains(&sub.len()) || !sub.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(invalid());
+        }
+        out.push('-');
+        if sub.len() == 2 {
+            out.push_str(&sub.to_ascii_uppercase());
+        } else {
+            out.push_str(&sub.to_ascii_lowercase());
+        }
+    }
+    Ok(out)
+}
+
+/// Parse an uploaded WebVTT/SRT file and store it as the lesson's track for `language`
+pub async fn upload_subtitles(
+    repo: &dyn LessonSubtitlesRepository,
+    lesson_id: uuid::Uuid,
+    language: &str,
+    label: Option<String>,
+    file_name: Option<&str>,
+    bytes: &[u8],
+) -> AppResult<LessonSubtitle> {
+    let language = normalize_language(language)?;
+    let body = std::str::from_utf8(bytes)
+        .map_err(|_| AppError::BadRequest("Subtitle files must be UTF-8 encoded".into()))?;
+    let format = SubtitleFormat::detect(file_name, body);
+    let cues = subtitles::parse(body, format)?;
+
+    let rec = repo
+        .upsert(UpsertLessonSubtitleRecord {
+            lesson_id,
+            language,
+            label: label.map(|l| l.trim().to_string()).filter(|l| !l.is_empty()),
+            vtt: subtitles::to_vtt(&cues),
+            transcript: subtitles::transcript(&cues),
+            cue_count: i32::try_from(cues.len()).unwrap_or(i32::MAX),
+        })
+        .await?;
+    Ok(rec.into())
+}
+
+/// List caption tracks of a lesson
+pub async fn list_subtitles(
+    repo: &dyn LessonSubtitlesRepository,
+    lesson_id: uuid::Uuid,
+) -> AppResult<Vec<LessonSubtitle>> {
+    let rows = repo.list_by_lesson(lesson_id).await?;
+    Ok(rows.into_iter().map(Into::into).collect())
+}
+
+/// Render a stored track in the requested format
+pub async fn render_subtitles(
+    repo: &dyn LessonSubtitlesRepository,
+    lesson_id: uuid::Uuid,
+    language: &str,
+    format: SubtitleFormat,
+) -> AppResult<String> {
+    let rec = find_track(repo, lesson_id, language).await?;
+    if format == SubtitleFormat::Vtt {
+        return Ok(rec.vtt);
+    }
+    let cues = subtitles::parse(&rec.vtt, SubtitleFormat::Vtt)?;
+    Ok(subtitles::render(&cues, format))
+}
+
+/// Plain-text transcript of a track
+pub async fn get_transcript(
+    repo: &dyn LessonSubtitlesRepository,
+    lesson_id: uuid::Uuid,
+    language: &str,
+) -> AppResult<LessonTranscript> {
+    let rec = find_track(repo, lesson_id, language).await?;
+    Ok(LessonTranscript {
+        lesson_id: rec.lesson_id,
+        language: rec.language,
+        text: rec.transcript,
+    })
+}
+
+/// Remove a lesson's track for `language`
+pub async fn delete_subtitles(
+    repo: &dyn LessonSubtitlesRepository,
+    lesson_id: uuid::Uuid,
+    language: &str,
+) -> AppResult<()> {
+    let language = normalize_language(language)?;
+    if !repo.delete(lesson_id, &language).await? {
+        return Err(AppError::NotFound("Subtitles not found".into()));
+    }
+    Ok(())
+}
+
+async fn find_track(
+    repo: &dyn LessonSubtitlesRepository,
+    lesson_id: uuid::Uuid,
+    language: &str,
+) -> AppResult<LessonSubtitleRecord> {
+    let language = normalize_language(language)?;
+    repo.find(lesson_id, &language)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Subtitles not found".into()))
+}
+
+impl From<LessonSubtitleRecord> for LessonSubtitle {
+    fn from(rec: LessonSubtitleRecord) -> Self {
+        LessonSubtitle {
+            url: format!("/api/lessons/{}/subtitles/{}", rec.lesson_id, rec.language),
+            id: rec.id,
+            lesson_id: rec.lesson_id,
+            language: rec.language,
+            label: rec.label,
+            cue_count: rec.cue_count,
+            created_at: rec.created_at,
+            updated_at: rec.updated_at,
+        }
+    }
+}
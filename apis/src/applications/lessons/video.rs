@@ -0,0 +1,117 @@
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::video::{CreateUploadRequest, VideoProvider, VideoStatus};
+use crate::repositories::lesson_videos::{
+    LessonVideoRecord, LessonVideosRepository, UpsertLessonVideoRecord,
+};
+use crate::repositories::lessons::{LessonsRepository, UpdateLessonRecord};
+use crate::types::course_types::{LessonVideo, LessonVideoUpload, StartLessonVideoUploadRequest};
+
+/// Reserve a video with the provider and attach it to the lesson
+pub async fn start_video_upload(
+    provider: &dyn VideoProvider,
+    lessons: &dyn LessonsRepository,
+    videos: &dyn LessonVideosRepository,
+    lesson_id: uuid::Uuid,
+    input: StartLessonVideoUploadRequest,
+) -> AppResult<LessonVideoUpload> {
+    let lesson = lessons
+        .find_by_id(lesson_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Lesson not found".into()))?;
+    let name = input
+        .name
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .unwrap_or(lesson.title);
+
+    let ticket = provider
+        .create_upload(&CreateUploadRequest {
+            name,
+            size_bytes: input.size_bytes as u64,
+        })
+        .await?;
+    let rec = videos
+        .upsert(UpsertLessonVideoRecord {
+            lesson_id,
+            provider: provider.name().to_string(),
+            provider_video_id: ticket.video_id.clone(),
+            status: VideoStatus::Uploading.as_str().to_string(),
+        })
+        .await?;
+
+    Ok(LessonVideoUpload {
+        provider: rec.provider,
+        video_id: rec.provider_video_id,
+        upload_link: ticket.upload_link,
+        status: rec.status,
+    })
+}
+
+/// Current video state of a lesson, polling the provider while processing.
+///
+/// The first time a video turns ready its embedding is restricted to
+/// `embed_domains` and its player URL becomes the lesson's `video_url`.
+pub async fn refresh_video_status(
+    provider: &dyn VideoProvider,
+    lessons: &dyn LessonsRepository,
+    videos: &dyn LessonVideosRepository,
+    lesson_id: uuid::Uuid,
+    embed_domains: &[String],
+) -> AppResult<LessonVideo> {
+    let rec = videos
+        .find_by_lesson(lesson_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Lesson has no hosted video".into()))?;
+
+    let stored = VideoStatus::parse(&rec.status).unwrap_or(VideoStatus::Uploading);
+    // Videos from a previously configured provider can't be polled any more
+    if stored.is_terminal() || rec.provider != provider.name() {
+        return Ok(rec.into());
+    }
+
+    let status = provider.status(&rec.provider_video_id).await?;
+    if status == stored {
+        return Ok(rec.into());
+    }
+
+    let embed_url = if status == VideoStatus::Ready {
+        provider
+            .restrict_embedding(&rec.provider_video_id, embed_domains)
+            .await?;
+        Some(provider.embed_url(&rec.provider_video_id).await?)
+    } else {
+        None
+    };
+
+    let updated = videos
+        .update_status(lesson_id, status.as_str(), embed_url.clone())
+        .await?
+        .ok_or_else(|| AppError::NotFound("Lesson has no hosted video".into()))?;
+
+    if let Some(url) = embed_url {
+        lessons
+            .update_partial(
+                lesson_id,
+                UpdateLessonRecord {
+                    video_url: Some(url),
+                    ..Default::default()
+                },
+            )
+            .await?;
+    }
+
+    Ok(updated.into())
+}
+
+impl From<LessonVideoRecord> for LessonVideo {
+    fn from(rec: LessonVideoRecord) -> Self {
+        LessonVideo {
+            lesson_id: rec.lesson_id,
+            provider: rec.provider,
+            video_id: rec.provider_video_id,
+            status: rec.status,
+            embed_url: rec.embed_url,
+            updated_at: rec.updated_at,
+        }
+    }
+}
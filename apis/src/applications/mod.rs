@@ -11,3 +11,11 @@ pub mod lessons;
 pub mod users;
 pub mod categories;
 pub mod course_categories;
+pub mod search;
+pub mod certificates;
+pub mod coupons;
+pub mod orders;
+pub mod payment_webhooks;
+pub mod refunds;
+pub mod learning_paths;
+pub mod analytics;
@@ -5,7 +5,7 @@ use crate::repositories::modules::{
     ModulesRepository,
 };
 use crate::types::course_types::{
-    CourseModule, CreateLessonDeepRequest, CreateModuleDeepRequest, Lesson, LessonContent,
+    CourseModule, CreateLessonDeepRequest, CreateModuleDeepRequest, Lesson,
     LessonDeep, LessonQuestion, ModuleDeep, QuestionOption, QuestionWithOptions,
 };
 
@@ -32,7 +32,7 @@ pub async fn create_deep(
     Ok(map_module_deep(record))
 }
 
-fn map_lesson_deep_request(r: CreateLessonDeepRequest) -> CreateLessonDeepData {
+pub(crate) fn map_lesson_deep_request(r: CreateLessonDeepRequest) -> CreateLessonDeepData {
     CreateLessonDeepData {
         title: r.title,
         description: r.description,
@@ -52,6 +52,7 @@ fn map_lesson_deep_request(r: CreateLessonDeepRequest) -> CreateLessonDeepData {
                 file_size: c.file_size,
                 filename: c.filename,
                 position: c.position,
+                is_private: false,
             })
             .collect(),
         questions: r
@@ -86,6 +87,8 @@ fn map_module_deep(r: ModuleDeepRecord) -> ModuleDeep {
             title: r.module.title,
             description: r.module.description,
             position: r.module.position,
+            drip_days: r.module.drip.days,
+            drip_at: r.module.drip.at,
             created_at: r.module.created_at,
             updated_at: r.module.updated_at,
         },
@@ -106,24 +109,15 @@ fn map_lesson_deep(r: LessonDeepRecord) -> LessonDeep {
             position: r.lesson.position,
             is_free: r.lesson.is_free,
             published: r.lesson.published,
+            locked: false,
+            unlocks_at: None,
             created_at: r.lesson.created_at,
             updated_at: r.lesson.updated_at,
         },
         contents: r
             .contents
             .into_iter()
-            .map(|c| LessonContent {
-                id: c.id,
-                lesson_id: c.lesson_id,
-                title: c.title,
-                content_type: c.content_type,
-                url: c.url,
-                file_size: c.file_size,
-                filename: c.filename,
-                position: c.position,
-                created_at: c.created_at,
-                updated_at: c.updated_at,
-            })
+            .map(crate::applications::lessons::contents::map_content)
             .collect(),
         questions: r
             .questions
@@ -143,7 +137,7 @@ fn map_lesson_deep(r: LessonDeepRecord) -> LessonDeep {
                         id: o.id,
                         question_id: o.question_id,
                         option_text: o.option_text,
-                        is_correct: o.is_correct,
+                        is_correct: Some(o.is_correct),
                         position: o.position,
                         created_at: o.created_at,
                         updated_at: o.updated_at,
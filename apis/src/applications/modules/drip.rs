@@ -0,0 +1,36 @@
+use crate::applications::courses::load_managed_course;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::courses::CoursesRepository;
+use crate::repositories::modules::{DripSchedule, ModulesRepository};
+use crate::types::course_types::{CourseModule, SetModuleDripRequest};
+
+/// Replace a module's drip schedule; only the course instructor or an admin
+/// may change it
+pub async fn set_module_drip(
+    modules: &dyn ModulesRepository,
+    courses: &dyn CoursesRepository,
+    user: &AuthUser,
+    id: uuid::Uuid,
+    input: SetModuleDripRequest,
+) -> AppResult<CourseModule> {
+    let module = modules
+        .find_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Module not found".into()))?;
+    load_managed_course(courses, user, module.course_id).await?;
+    if input.drip_days.is_some() && input.drip_at.is_some() {
+        return Err(AppError::BadRequest(
+            "Set either drip_days or drip_at, not both".into(),
+        ));
+    }
+    let drip = DripSchedule {
+        days: input.drip_days,
+        at: input.drip_at,
+    };
+    let updated = modules
+        .set_drip(id, drip)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Module not found".into()))?;
+    Ok(CourseModule::from(updated))
+}
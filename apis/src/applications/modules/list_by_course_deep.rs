@@ -1,17 +1,91 @@
-use crate::pkg::error::AppResult;
-use crate::repositories::modules::{LessonDeepRecord, ModuleDeepRecord, ModulesRepository};
+use crate::applications::lessons::access::LessonGate;
+use crate::applications::lessons::policy::{
+    CourseViewer, LessonVisibility, gate_lesson, load_viewer,
+};
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::modules::{
+    DripSchedule, LessonDeepRecord, ModuleDeepRecord, ModulesRepository,
+};
 use crate::types::course_types::{
-    CourseModule, Lesson, LessonAssignment, LessonContent, LessonDeep, LessonQuestion, ModuleDeep,
+    CourseModule, Lesson, LessonAssignment, LessonDeep, LessonQuestion, ModuleDeep,
     QuestionOption, QuestionWithOptions,
 };
 
-/// List all modules for a course with their nested lessons, contents, questions, and assignments
+/// List all modules for a course with their nested lessons, contents, questions, and assignments.
+///
+/// Non-managers do not see unpublished lessons or the quiz answers, and lessons
+/// they may not open are reduced to an outline without contents, quiz or
+/// assignment.
 pub async fn list_modules_by_course_deep(
     repo: &dyn ModulesRepository,
+    gate: &LessonGate<'_>,
     course_id: uuid::Uuid,
+    user: Option<&AuthUser>,
 ) -> AppResult<Vec<ModuleDeep>> {
+    let course = gate
+        .courses
+        .find_by_id(course_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Course not found".into()))?;
+    let viewer = load_viewer(gate, course_id, course.instructor_id, user).await?;
+    let now = chrono::Utc::now();
     let records = repo.list_by_course_deep(course_id).await?;
-    Ok(records.into_iter().map(map_module_deep).collect())
+    Ok(records
+        .into_iter()
+        .map(|r| gate_module(map_module_deep(r), &viewer, now))
+        .collect())
+}
+
+/// Drop the lessons `viewer` may not see and outline the ones they may not open
+fn gate_module(
+    module: ModuleDeep,
+    viewer: &CourseViewer,
+    now: chrono::DateTime<chrono::Utc>,
+) -> ModuleDeep {
+    let drip = DripSchedule {
+        days: module.module.drip_days,
+        at: module.module.drip_at,
+    };
+    let lessons = module
+        .lessons
+        .into_iter()
+        .filter_map(|deep| {
+            let visibility =
+                viewer.lesson_visibility(deep.lesson.is_free, deep.lesson.published, &drip, now);
+            let lesson = gate_lesson(deep.lesson, visibility)?;
+            if let LessonVisibility::Outline { .. } = visibility {
+                return Some(LessonDeep {
+                    lesson,
+                    contents: Vec::new(),
+                    questions: Vec::new(),
+                    assignment: None,
+                });
+            }
+            let questions = if viewer.manager {
+                deep.questions
+            } else {
+                deep.questions.into_iter().map(hide_answers).collect()
+            };
+            Some(LessonDeep {
+                lesson,
+                questions,
+                ..deep
+            })
+        })
+        .collect();
+    ModuleDeep {
+        module: module.module,
+        lessons,
+    }
+}
+
+/// Strip the answer key from a question shown to a learner
+fn hide_answers(mut question: QuestionWithOptions) -> QuestionWithOptions {
+    for option in &mut question.options {
+        option.is_correct = None;
+    }
+    question
 }
 
 fn map_module_deep(r: ModuleDeepRecord) -> ModuleDeep {
@@ -22,6 +96,8 @@ fn map_module_deep(r: ModuleDeepRecord) -> ModuleDeep {
             title: r.module.title,
             description: r.module.description,
             position: r.module.position,
+            drip_days: r.module.drip.days,
+            drip_at: r.module.drip.at,
             created_at: r.module.created_at,
             updated_at: r.module.updated_at,
         },
@@ -42,24 +118,15 @@ fn map_lesson_deep(r: LessonDeepRecord) -> LessonDeep {
             position: r.lesson.position,
             is_free: r.lesson.is_free,
             published: r.lesson.published,
+            locked: false,
+            unlocks_at: None,
             created_at: r.lesson.created_at,
             updated_at: r.lesson.updated_at,
         },
         contents: r
             .contents
             .into_iter()
-            .map(|c| LessonContent {
-                id: c.id,
-                lesson_id: c.lesson_id,
-                title: c.title,
-                content_type: c.content_type,
-                url: c.url,
-                file_size: c.file_size,
-                filename: c.filename,
-                position: c.position,
-                created_at: c.created_at,
-                updated_at: c.updated_at,
-            })
+            .map(crate::applications::lessons::contents::map_content)
             .collect(),
         questions: r
             .questions
@@ -79,7 +146,7 @@ fn map_lesson_deep(r: LessonDeepRecord) -> LessonDeep {
                         id: o.id,
                         question_id: o.question_id,
                         option_text: o.option_text,
-                        is_correct: o.is_correct,
+                        is_correct: Some(o.is_correct),
                         position: o.position,
                         created_at: o.created_at,
                         updated_at: o.updated_at,
@@ -96,3 +163,91 @@ fn map_lesson_deep(r: LessonDeepRecord) -> LessonDeep {
         }),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::applications::lessons::release::Learner;
+
+    fn quiz_module() -> ModuleDeep {
+        let now = chrono::Utc::now();
+        let id = uuid::Uuid::new_v4();
+        ModuleDeep {
+            module: CourseModule {
+                id,
+                course_id: id,
+                title: "Module".into(),
+                description: None,
+                position: 1,
+                drip_days: None,
+                drip_at: None,
+                created_at: now,
+                updated_at: None,
+            },
+            lessons: vec![LessonDeep {
+                lesson: Lesson {
+                    id,
+                    module_id: id,
+                    title: "Quiz".into(),
+                    description: None,
+                    content: None,
+                    video_url: None,
+                    duration: "5m".into(),
+                    position: 1,
+                    is_free: true,
+                    published: true,
+                    locked: false,
+                    unlocks_at: None,
+                    created_at: now,
+                    updated_at: None,
+                },
+                contents: Vec::new(),
+                questions: vec![QuestionWithOptions {
+                    question: LessonQuestion {
+                        id,
+                        lesson_id: id,
+                        question_text: "2 + 2?".into(),
+                        position: 1,
+                        created_at: now,
+                        updated_at: None,
+                    },
+                    options: vec![QuestionOption {
+                        id,
+                        question_id: id,
+                        option_text: "4".into(),
+                        is_correct: Some(true),
+                        position: 1,
+                        created_at: now,
+                        updated_at: None,
+                    }],
+                }],
+                assignment: None,
+            }],
+        }
+    }
+
+    fn answers(module: &ModuleDeep) -> Vec<Option<bool>> {
+        module.lessons[0].questions[0]
+            .options
+            .iter()
+            .map(|o| o.is_correct)
+            .collect()
+    }
+
+    #[test]
+    fn only_managers_see_the_answer_key() {
+        let now = chrono::Utc::now();
+        let viewer = |manager| CourseViewer {
+            manager,
+            learner: Learner {
+                enrolled_at: Some(now),
+                unmet_prerequisites: Vec::new(),
+            },
+        };
+        let learner = gate_module(quiz_module(), &viewer(false), now);
+        assert_eq!(learner.lessons[0].questions.len(), 1);
+        assert_eq!(answers(&learner), vec![None]);
+        let manager = gate_module(quiz_module(), &viewer(true), now);
+        assert_eq!(answers(&manager), vec![Some(true)]);
+    }
+}
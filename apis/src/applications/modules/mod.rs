@@ -4,6 +4,7 @@ pub mod bulk_update_positions;
 pub mod create;
 pub mod deep_create;
 pub mod delete_by_id;
+pub mod drip;
 pub mod list_by_course;
 pub mod list_by_course_deep;
 pub mod update_by_id;
@@ -12,6 +13,7 @@ pub use bulk_update_positions::bulk_update_module_positions;
 pub use create::create_module;
 pub use deep_create::create_deep;
 pub use delete_by_id::delete_module_by_id as delete_module;
+pub use drip::set_module_drip;
 pub use list_by_course::list_modules_by_course;
 pub use list_by_course_deep::list_modules_by_course_deep;
 pub use update_by_id::update_module_by_id as update_module;
@@ -27,6 +29,8 @@ impl From<ModuleRecord> for CourseModule {
             title: rec.title,
             description: rec.description,
             position: rec.position,
+            drip_days: rec.drip.days,
+            drip_at: rec.drip.at,
             created_at: rec.created_at,
             updated_at: rec.updated_at,
         }
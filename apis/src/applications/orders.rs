@@ -0,0 +1,585 @@
+//! Orders and checkout
+//!
+//! Checkout snapshots the courses' titles and prices in the buyer's currency
+//! onto a pending order and opens a payment intent with the configured
+//! provider. The buyer is enrolled only once the provider reports the payment
+//! as succeeded; free orders, including those a coupon makes free, are
+//! fulfilled on the spot. Every change of an order's status is appended to
+//! its audit trail.
+//!
+//! An unpaid order holds its coupon redemption until the buyer cancels it or
+//! it expires after [`PENDING_ORDER_TTL`]. Either way its payment intent is
+//! canceled with the provider first, so the redemption is only given back
+//! once the order can no longer be paid; an order whose payment is already
+//! processing or has succeeded stays pending.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::applications::coupons::apply_coupon;
+use crate::applications::courses::pricing::price_in_currency;
+use crate::configs::payment_config::PaymentConfig;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::logger::{info, warn};
+use crate::pkg::money::{Money, normalize_currency};
+use crate::pkg::payments::{CreatePaymentIntent, PaymentProvider, PaymentStatus};
+use crate::repositories::coupons::CouponsRepository;
+use crate::repositories::course_categories::CourseCategoriesRepository;
+use crate::repositories::courses::{CourseStatus, CoursesRepository};
+use crate::repositories::enrollments::EnrollmentsRepository;
+use crate::repositories::orders::{
+    CreateOrderEventRecord, CreateOrderItemRecord, CreateOrderRecord, CreatePaymentAttemptRecord,
+    OrderCouponRecord, OrderEventKind, OrderRecord, OrderStatus, OrdersRepository,
+};
+use crate::types::order_types::{
+    CheckoutRequest, CheckoutResponse, Order, OrderEvent, OrderItem, PaymentDetails,
+};
+
+/// How long an unpaid order stays open, holding its coupon redemption
+pub const PENDING_ORDER_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// How often unpaid orders past `PENDING_ORDER_TTL` are expired
+pub const ORDER_EXPIRY_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Orders expired per tick
+const ORDER_EXPIRY_BATCH: i64 = 200;
+
+/// What checkout and payment confirmation need to look up and record
+pub struct OrderServices<'a> {
+    pub courses: &'a dyn CoursesRepository,
+    pub orders: &'a dyn OrdersRepository,
+    pub enrollments: &'a dyn EnrollmentsRepository,
+    pub coupons: &'a dyn CouponsRepository,
+    pub course_categories: &'a dyn CourseCategoriesRepository,
+    pub provider: &'a dyn PaymentProvider,
+    pub config: &'a PaymentConfig,
+}
+
+/// Create an order for the requested courses and start paying for it
+pub async fn checkout(
+    svc: &OrderServices<'_>,
+    user: &AuthUser,
+    req: CheckoutRequest,
+) -> AppResult<CheckoutResponse> {
+    let currency = checkout_currency(req.currency.as_deref(), svc.config)?;
+    let mut items = price_items(svc, user, req.course_ids, &currency).await?;
+    let coupon = match req.coupon_code.as_deref() {
+        Some(code) => {
+            let coupon = apply_coupon(svc, user, code, &currency, &mut items).await?;
+            Some(OrderCouponRecord {
+                coupon_id: coupon.id,
+                code: coupon.code,
+                max_redemptions: coupon.max_redemptions,
+                max_redemptions_per_user: coupon.max_redemptions_per_user,
+            })
+        }
+        None => None,
+    };
+
+    let note = coupon
+        .as_ref()
+        .map(|c| format!("Coupon {} applied", c.code));
+    place_order(
+        svc,
+        user,
+        CreateOrderRecord {
+            user_id: user.user_id,
+            currency,
+            items,
+            coupon,
+            learning_path_id: None,
+        },
+        note,
+    )
+    .await
+}
+
+/// Create the order and open a payment intent for its total; a free order is
+/// fulfilled on the spot. `note` goes on the order's `created` event.
+pub(crate) async fn place_order(
+    svc: &OrderServices<'_>,
+    user: &AuthUser,
+    input: CreateOrderRecord,
+    note: Option<String>,
+) -> AppResult<CheckoutResponse> {
+    let description = input
+        .items
+        .iter()
+        .map(|i| i.course_title.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let order = svc.orders.create(input).await?;
+    record_event(
+        svc.orders,
+        order.id,
+        Some(user.user_id),
+        OrderEventKind::Created,
+        note,
+    )
+    .await?;
+
+    if order.total_cents == 0 {
+        let order = fulfill_order(svc.orders, order.id).await?;
+        return Ok(CheckoutResponse {
+            order: order.into(),
+            payment: None,
+        });
+    }
+
+    let intent = svc
+        .provider
+        .create_intent(&CreatePaymentIntent {
+            order_id: order.id,
+            amount: Money::new(order.total_cents, order.currency.clone()),
+            description,
+        })
+        .await?;
+    svc.orders
+        .create_payment_attempt(CreatePaymentAttemptRecord {
+            order_id: order.id,
+            provider: svc.provider.name().to_string(),
+            provider_payment_id: intent.id.clone(),
+            status: intent.status,
+            amount_cents: order.total_cents,
+            currency: order.currency.clone(),
+        })
+        .await?;
+
+    Ok(CheckoutResponse {
+        order: order.into(),
+        payment: Some(PaymentDetails {
+            provider: svc.provider.name().to_string(),
+            payment_id: intent.id,
+            client_secret: intent.client_secret,
+            status: intent.status.as_str().to_string(),
+        }),
+    })
+}
+
+/// The requested checkout currency, or the store currency
+pub(crate) fn checkout_currency(
+    requested: Option<&str>,
+    config: &PaymentConfig,
+) -> AppResult<String> {
+    match requested {
+        Some(c) => normalize_currency(c).ok_or_else(|| {
+            AppError::BadRequest(format!("'{}' is not an ISO 4217 currency code", c))
+        }),
+        None => Ok(config.currency.clone()),
+    }
+}
+
+/// Check `user` may buy each course and snapshot its title and price in
+/// `currency`, without any discount yet
+pub(crate) async fn price_items(
+    svc: &OrderServices<'_>,
+    user: &AuthUser,
+    course_ids: Vec<uuid::Uuid>,
+    currency: &str,
+) -> AppResult<Vec<CreateOrderItemRecord>> {
+    let mut items = Vec::new();
+    for course_id in unique_ids(course_ids) {
+        let course = svc
+            .courses
+            .find_by_id(course_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Course not found".into()))?;
+        if CourseStatus::parse(&course.status) != Some(CourseStatus::Published) {
+            return Err(AppError::BadRequest(format!(
+                "Course '{}' is not available for purchase",
+                course.title
+            )));
+        }
+        if course.instructor_id == user.user_id {
+            return Err(AppError::BadRequest(format!(
+                "You teach '{}' and cannot buy it",
+                course.title
+            )));
+        }
+        if svc.enrollments.is_enrolled(user.user_id, course_id).await? {
+            return Err(AppError::Conflict(format!(
+                "You are already enrolled in '{}'",
+                course.title
+            )));
+        }
+        let price = price_in_currency(svc.courses, &course, currency)
+            .await?
+            .ok_or_else(|| {
+                AppError::BadRequest(format!(
+                    "'{}' is not sold in {}",
+                    course.title, currency
+                ))
+            })?;
+        items.push(CreateOrderItemRecord {
+            course_id,
+            course_title: course.title,
+            unit_price_cents: price.amount,
+            discount_cents: 0,
+        });
+    }
+    Ok(items)
+}
+
+/// Ask the provider how the order's payment went and fulfil it if it succeeded.
+///
+/// Safe to call repeatedly: a paid order is returned as is.
+pub async fn confirm_order(
+    svc: &OrderServices<'_>,
+    user: &AuthUser,
+    order_id: uuid::Uuid,
+) -> AppResult<Order> {
+    let order = load_own_order(svc.orders, user, order_id).await?;
+    match OrderStatus::parse(&order.status) {
+        Some(OrderStatus::Paid) => return Ok(order.into()),
+        Some(OrderStatus::Pending) => {}
+        _ => {
+            return Err(AppError::Conflict(format!(
+                "Order is {} and can no longer be paid",
+                order.status
+            )));
+        }
+    }
+
+    let attempt = svc
+        .orders
+        .latest_payment_attempt(order.id)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("Order has no payment to confirm".into()))?;
+    let intent = svc
+        .provider
+        .retrieve_intent(&attempt.provider_payment_id)
+        .await?;
+    svc.orders
+        .update_payment_attempt_status(attempt.id, intent.status)
+        .await?;
+
+    let order = match intent.status {
+        PaymentStatus::Succeeded => fulfill_order(svc.orders, order.id).await?,
+        PaymentStatus::Failed | PaymentStatus::Canceled => {
+            let (to, event) = if intent.status == PaymentStatus::Failed {
+                (OrderStatus::Failed, OrderEventKind::Failed)
+            } else {
+                (OrderStatus::Canceled, OrderEventKind::Canceled)
+            };
+            match svc
+                .orders
+                .transition_status(order.id, &[OrderStatus::Pending], to)
+                .await?
+            {
+                Some(order) => {
+                    record_event(svc.orders, order.id, None, event, None).await?;
+                    order
+                }
+                None => order,
+            }
+        }
+        PaymentStatus::RequiresPayment | PaymentStatus::Processing => order,
+    };
+    Ok(order.into())
+}
+
+/// Cancel one of the buyer's unpaid orders, giving back its coupon redemption
+pub async fn cancel_order(
+    orders: &dyn OrdersRepository,
+    provider: &dyn PaymentProvider,
+    user: &AuthUser,
+    order_id: uuid::Uuid,
+) -> AppResult<Order> {
+    let order = load_own_order(orders, user, order_id).await?;
+    if OrderStatus::parse(&order.status) == Some(OrderStatus::Pending)
+        && !cancel_payment(orders, provider, order.id).await?
+    {
+        return Err(AppError::Conflict(
+            "The order's payment is already being processed and cannot be canceled".into(),
+        ));
+    }
+    let order = orders
+        .transition_status(order.id, &[OrderStatus::Pending], OrderStatus::Canceled)
+        .await?
+        .ok_or_else(|| {
+            AppError::Conflict(format!("Order is {} and cannot be canceled", order.status))
+        })?;
+    record_event(
+        orders,
+        order.id,
+        Some(user.user_id),
+        OrderEventKind::Canceled,
+        None,
+    )
+    .await?;
+    Ok(order.into())
+}
+
+/// Cancel every order left unpaid for longer than `PENDING_ORDER_TTL`;
+/// returns how many were canceled
+pub async fn expire_pending_orders(
+    orders: &dyn OrdersRepository,
+    provider: &dyn PaymentProvider,
+) -> AppResult<usize> {
+    let ttl = chrono::Duration::from_std(PENDING_ORDER_TTL)
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+    let mut expired = 0;
+    for id in orders
+        .list_stale_pending(chrono::Utc::now() - ttl, ORDER_EXPIRY_BATCH)
+        .await?
+    {
+        // Left pending while the payment settles; confirmation or the
+        // webhook picks it up
+        match cancel_payment(orders, provider, id).await {
+            Ok(true) => {}
+            Ok(false) => continue,
+            Err(e) => {
+                warn(format!(
+                    "Canceling the payment of order {} failed: {}",
+                    id, e
+                ));
+                continue;
+            }
+        }
+        // Paid or canceled meanwhile
+        if orders
+            .transition_status(id, &[OrderStatus::Pending], OrderStatus::Canceled)
+            .await?
+            .is_some()
+        {
+            record_event(
+                orders,
+                id,
+                None,
+                OrderEventKind::Canceled,
+                Some("Expired unpaid".into()),
+            )
+            .await?;
+            expired += 1;
+        }
+    }
+    Ok(expired)
+}
+
+/// Run `expire_pending_orders` every `interval` until the task is aborted
+pub async fn run_order_expiry(
+    orders: Arc<dyn OrdersRepository>,
+    provider: Arc<dyn PaymentProvider>,
+    interval: Duration,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        ticker.tick().await;
+        match expire_pending_orders(orders.as_ref(), provider.as_ref()).await {
+            Ok(0) => {}
+            Ok(n) => info(format!("Expired {} unpaid order(s)", n)),
+            Err(e) => warn(format!("Expiring unpaid orders failed: {}", e)),
+        }
+    }
+}
+
+/// Cancel the order's latest payment intent so it can no longer settle.
+///
+/// Returns `false` when the payment is already processing or has succeeded.
+async fn cancel_payment(
+    orders: &dyn OrdersRepository,
+    provider: &dyn PaymentProvider,
+    order_id: uuid::Uuid,
+) -> AppResult<bool> {
+    let Some(attempt) = orders.latest_payment_attempt(order_id).await? else {
+        return Ok(true);
+    };
+    if matches!(
+        PaymentStatus::parse(&attempt.status),
+        Some(PaymentStatus::Failed | PaymentStatus::Canceled)
+    ) {
+        return Ok(true);
+    }
+    let intent = provider.cancel_intent(&attempt.provider_payment_id).await?;
+    orders
+        .update_payment_attempt_status(attempt.id, intent.status)
+        .await?;
+    Ok(!matches!(
+        intent.status,
+        PaymentStatus::Processing | PaymentStatus::Succeeded
+    ))
+}
+
+/// Mark an unpaid order paid and enroll the buyer in its courses, and in the
+/// learning path it was placed for.
+///
+/// Orders already marked failed or canceled are fulfilled too: the provider
+/// has taken the money, which only happens when a payment settled while its
+/// intent was being canceled. The status change and the enrollments are one
+/// transaction, so an order is never left paid without them; only the caller
+/// that moves the order to `paid` enrolls, so concurrent confirmations and
+/// webhook deliveries fulfil it once.
+pub(crate) async fn fulfill_order(
+    orders: &dyn OrdersRepository,
+    order_id: uuid::Uuid,
+) -> AppResult<OrderRecord> {
+    let unpaid = [
+        OrderStatus::Pending,
+        OrderStatus::Failed,
+        OrderStatus::Canceled,
+    ];
+    let Some(order) = orders.mark_paid(order_id, &unpaid).await? else {
+        return orders
+            .find_by_id(order_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Order not found".into()));
+    };
+
+    info(format!(
+        "Order {} paid: {}, {} course(s)",
+        order.id,
+        Money::new(order.total_cents, order.currency.clone()),
+        order.items.len()
+    ));
+    Ok(order)
+}
+
+/// Revoke the buyer's enrollments in the courses and learning path of
+/// `order`, freezing their progress, and note it on the order
+pub(crate) async fn revoke_order_enrollments(
+    orders: &dyn OrdersRepository,
+    enrollments: &dyn EnrollmentsRepository,
+    order: &OrderRecord,
+    actor_id: Option<uuid::Uuid>,
+) -> AppResult<()> {
+    let mut revoked = 0;
+    for course_id in order.items.iter().filter_map(|i| i.course_id) {
+        if enrollments.revoke(order.user_id, course_id).await? {
+            revoked += 1;
+        }
+    }
+    if let Some(path_id) = order.learning_path_id {
+        enrollments.revoke_path(order.user_id, path_id).await?;
+    }
+    record_event(
+        orders,
+        order.id,
+        actor_id,
+        OrderEventKind::EnrollmentsRevoked,
+        Some(format!("{} enrollment(s) revoked", revoked)),
+    )
+    .await
+}
+
+/// Append an entry to the order's audit trail
+pub(crate) async fn record_event(
+    orders: &dyn OrdersRepository,
+    order_id: uuid::Uuid,
+    actor_id: Option<uuid::Uuid>,
+    event: OrderEventKind,
+    note: Option<String>,
+) -> AppResult<()> {
+    orders
+        .record_event(CreateOrderEventRecord {
+            order_id,
+            actor_id,
+            event,
+            note,
+        })
+        .await
+}
+
+pub async fn get_order(
+    orders: &dyn OrdersRepository,
+    user: &AuthUser,
+    order_id: uuid::Uuid,
+) -> AppResult<Order> {
+    Ok(load_own_order(orders, user, order_id).await?.into())
+}
+
+/// The order's audit trail, oldest first
+pub async fn get_order_history(
+    orders: &dyn OrdersRepository,
+    user: &AuthUser,
+    order_id: uuid::Uuid,
+) -> AppResult<Vec<OrderEvent>> {
+    let order = load_own_order(orders, user, order_id).await?;
+    Ok(orders
+        .list_events(order.id)
+        .await?
+        .into_iter()
+        .map(|e| OrderEvent {
+            event: e.event,
+            actor_id: e.actor_id,
+            note: e.note,
+            created_at: e.created_at,
+        })
+        .collect())
+}
+
+pub async fn list_my_orders(
+    orders: &dyn OrdersRepository,
+    user: &AuthUser,
+) -> AppResult<Vec<Order>> {
+    Ok(orders
+        .list_by_user(user.user_id)
+        .await?
+        .into_iter()
+        .map(Order::from)
+        .collect())
+}
+
+/// Load an order placed by `user`; admins may see any order
+pub(crate) async fn load_own_order(
+    orders: &dyn OrdersRepository,
+    user: &AuthUser,
+    order_id: uuid::Uuid,
+) -> AppResult<OrderRecord> {
+    let order = orders
+        .find_by_id(order_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Order not found".into()))?;
+    if order.user_id != user.user_id && user.role != "admin" {
+        // Do not reveal other users' orders
+        return Err(AppError::NotFound("Order not found".into()));
+    }
+    Ok(order)
+}
+
+/// Drop repeated ids, keeping the first occurrence
+fn unique_ids(ids: Vec<uuid::Uuid>) -> Vec<uuid::Uuid> {
+    let mut seen = std::collections::HashSet::new();
+    ids.into_iter().filter(|id| seen.insert(*id)).collect()
+}
+
+impl From<OrderRecord> for Order {
+    fn from(record: OrderRecord) -> Self {
+        Order {
+            id: record.id,
+            status: record.status,
+            subtotal: Money::new(record.subtotal_cents, record.currency.clone()),
+            discount: Money::new(record.discount_cents, record.currency.clone()),
+            total: Money::new(record.total_cents, record.currency.clone()),
+            coupon_code: record.coupon_code,
+            learning_path_id: record.learning_path_id,
+            items: record
+                .items
+                .into_iter()
+                .map(|i| OrderItem {
+                    course_id: i.course_id,
+                    course_title: i.course_title,
+                    unit_price: Money::new(i.unit_price_cents, record.currency.clone()),
+                    discount: Money::new(i.discount_cents, record.currency.clone()),
+                })
+                .collect(),
+            paid_at: record.paid_at,
+            refunded_at: record.refunded_at,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_course_ids_are_dropped() {
+        let a = uuid::Uuid::new_v4();
+        let b = uuid::Uuid::new_v4();
+        assert_eq!(unique_ids(vec![a, b, a, b, a]), vec![a, b]);
+    }
+}
@@ -0,0 +1,192 @@
+//! Payment provider webhooks
+//!
+//! Each verified event is stored verbatim before it is applied, keyed by the
+//! provider's event id. Providers deliver at least once, so a redelivery of an
+//! event that was already applied is acknowledged without touching the order
+//! again; one that failed half way is simply applied again, which is safe
+//! because every order transition is conditional on the current status.
+
+use crate::applications::orders::{fulfill_order, record_event, revoke_order_enrollments};
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::logger::{info, warn};
+use crate::pkg::payments::{
+    PaymentEvent, PaymentEventKind, PaymentProvider, PaymentStatus, WebhookVerifier,
+};
+use crate::repositories::enrollments::EnrollmentsRepository;
+use crate::repositories::orders::{OrderEventKind, OrderStatus, OrdersRepository};
+use crate::repositories::payment_events::{CreatePaymentEventRecord, PaymentEventsRepository};
+use crate::types::order_types::WebhookReceipt;
+
+/// What webhook processing needs to verify, record and apply events
+pub struct WebhookServices<'a> {
+    pub orders: &'a dyn OrdersRepository,
+    pub enrollments: &'a dyn EnrollmentsRepository,
+    pub events: &'a dyn PaymentEventsRepository,
+    pub provider: &'a dyn PaymentProvider,
+    /// `None` while no webhook secret is configured
+    pub verifier: Option<WebhookVerifier>,
+}
+
+/// Verify, record and apply one webhook delivery
+pub async fn handle_webhook(
+    svc: &WebhookServices<'_>,
+    signature: Option<&str>,
+    payload: &[u8],
+) -> AppResult<WebhookReceipt> {
+    let verifier = svc.verifier.as_ref().ok_or_else(|| {
+        AppError::ServiceUnavailable("Payment webhooks are not configured".into())
+    })?;
+    let signature =
+        signature.ok_or_else(|| AppError::Unauthorized("Missing webhook signature".into()))?;
+    verifier.verify(payload, signature, chrono::Utc::now().timestamp())?;
+
+    let event = svc.provider.parse_event(payload)?;
+    let stored = svc
+        .events
+        .record(CreatePaymentEventRecord {
+            provider: svc.provider.name().to_string(),
+            event_id: event.id.clone(),
+            event_type: event.event_type.clone(),
+            payload: String::from_utf8_lossy(payload).into_owned(),
+        })
+        .await?;
+
+    let (duplicate, order_id) = if stored.processed_at.is_some() {
+        (true, stored.order_id)
+    } else {
+        let order_id = apply_event(svc, &event).await?;
+        svc.events.mark_processed(stored.id, order_id).await?;
+        (false, order_id)
+    };
+
+    let order_status = match order_id {
+        Some(id) => svc.orders.find_by_id(id).await?.map(|o| o.status),
+        None => None,
+    };
+    Ok(WebhookReceipt {
+        event_id: event.id,
+        duplicate,
+        order_id,
+        order_status,
+    })
+}
+
+/// Move the order behind the event's payment; returns the order it concerned
+async fn apply_event(
+    svc: &WebhookServices<'_>,
+    event: &PaymentEvent,
+) -> AppResult<Option<uuid::Uuid>> {
+    if event.kind == PaymentEventKind::Other {
+        return Ok(None);
+    }
+    let Some(payment_id) = event.payment_id.as_deref() else {
+        return Ok(None);
+    };
+    let Some(attempt) = svc
+        .orders
+        .find_payment_attempt(svc.provider.name(), payment_id)
+        .await?
+    else {
+        // Payments made outside checkout, e.g. from the provider dashboard
+        warn(format!(
+            "Payment event {} ({}) for unknown payment {}",
+            event.id, event.event_type, payment_id
+        ));
+        return Ok(None);
+    };
+
+    match event.kind {
+        PaymentEventKind::Succeeded => {
+            svc.orders
+                .update_payment_attempt_status(attempt.id, PaymentStatus::Succeeded)
+                .await?;
+            fulfill_order(svc.orders, attempt.order_id).await?;
+        }
+        PaymentEventKind::Refunded => {
+            if let Some(order) = svc
+                .orders
+                .transition_status(
+                    attempt.order_id,
+                    &[OrderStatus::Paid],
+                    OrderStatus::Refunded,
+                )
+                .await?
+            {
+                record_event(
+                    svc.orders,
+                    order.id,
+                    None,
+                    OrderEventKind::Refunded,
+                    Some(format!("Refunded by {}", svc.provider.name())),
+                )
+                .await?;
+                revoke_order_enrollments(svc.orders, svc.enrollments, &order, None).await?;
+                info(format!("Order {} refunded by the provider", order.id));
+            }
+        }
+        kind => {
+            if let Some((payment, order)) = unsuccessful_outcome(kind) {
+                // A declined intent can be retried with the same client
+                // secret; cancel it so the order cannot settle after giving
+                // back its coupon redemption
+                if kind == PaymentEventKind::Failed {
+                    let intent = svc.provider.cancel_intent(payment_id).await?;
+                    if matches!(
+                        intent.status,
+                        PaymentStatus::Processing | PaymentStatus::Succeeded
+                    ) {
+                        svc.orders
+                            .update_payment_attempt_status(attempt.id, intent.status)
+                            .await?;
+                        return Ok(Some(attempt.order_id));
+                    }
+                }
+                svc.orders
+                    .update_payment_attempt_status(attempt.id, payment)
+                    .await?;
+                if svc
+                    .orders
+                    .transition_status(attempt.order_id, &[OrderStatus::Pending], order)
+                    .await?
+                    .is_some()
+                {
+                    let event = if order == OrderStatus::Failed {
+                        OrderEventKind::Failed
+                    } else {
+                        OrderEventKind::Canceled
+                    };
+                    record_event(svc.orders, attempt.order_id, None, event, None).await?;
+                }
+            }
+        }
+    }
+    Ok(Some(attempt.order_id))
+}
+
+/// Attempt and order status for a payment that did not go through
+fn unsuccessful_outcome(kind: PaymentEventKind) -> Option<(PaymentStatus, OrderStatus)> {
+    match kind {
+        PaymentEventKind::Failed => Some((PaymentStatus::Failed, OrderStatus::Failed)),
+        PaymentEventKind::Canceled => Some((PaymentStatus::Canceled, OrderStatus::Canceled)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsuccessful_payments_close_pending_orders() {
+        assert_eq!(
+            unsuccessful_outcome(PaymentEventKind::Failed),
+            Some((PaymentStatus::Failed, OrderStatus::Failed))
+        );
+        assert_eq!(
+            unsuccessful_outcome(PaymentEventKind::Canceled),
+            Some((PaymentStatus::Canceled, OrderStatus::Canceled))
+        );
+        assert_eq!(unsuccessful_outcome(PaymentEventKind::Succeeded), None);
+        assert_eq!(unsuccessful_outcome(PaymentEventKind::Refunded), None);
+    }
+}
@@ -0,0 +1,348 @@
+//! Refunds
+//!
+//! Buyers may ask for their money back within the configured number of days
+//! after paying. An admin approves or rejects each request; approval refunds
+//! the order total through the payment provider, marks the order refunded and
+//! revokes the buyer's enrollments, which keeps their progress frozen as it
+//! was. The refund is keyed by the request id, so approving again after a
+//! timeout or a provider error never refunds twice.
+
+use crate::applications::orders::{load_own_order, record_event, revoke_order_enrollments};
+use crate::configs::payment_config::PaymentConfig;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::logger::info;
+use crate::pkg::money::Money;
+use crate::pkg::payments::{CreateRefund, PaymentProvider, PaymentStatus, RefundStatus};
+use crate::repositories::enrollments::EnrollmentsRepository;
+use crate::repositories::orders::{OrderEventKind, OrderRecord, OrderStatus, OrdersRepository};
+use crate::repositories::refund_requests::{
+    CreateRefundRequestRecord, RefundRequestRecord, RefundRequestStatus, RefundRequestsRepository,
+    ReviewRefundRequestRecord,
+};
+use crate::types::order_types::{CreateRefundRequest, RefundRequest, ReviewRefundRequest};
+
+/// What requesting and reviewing refunds need to look up and record
+pub struct RefundServices<'a> {
+    pub orders: &'a dyn OrdersRepository,
+    pub refunds: &'a dyn RefundRequestsRepository,
+    pub enrollments: &'a dyn EnrollmentsRepository,
+    pub provider: &'a dyn PaymentProvider,
+    pub config: &'a PaymentConfig,
+}
+
+/// Ask for a paid order to be refunded
+pub async fn request_refund(
+    svc: &RefundServices<'_>,
+    user: &AuthUser,
+    order_id: uuid::Uuid,
+    req: CreateRefundRequest,
+) -> AppResult<RefundRequest> {
+    let order = load_own_order(svc.orders, user, order_id).await?;
+    if order.user_id != user.user_id {
+        return Err(AppError::Forbidden(
+            "Only the buyer can ask for a refund".into(),
+        ));
+    }
+    if OrderStatus::parse(&order.status) != Some(OrderStatus::Paid) {
+        return Err(AppError::Conflict(format!(
+            "Order is {} and cannot be refunded",
+            order.status
+        )));
+    }
+    check_refund_window(
+        order.paid_at,
+        svc.config.refund_window_days,
+        chrono::Utc::now(),
+    )?;
+    if svc.refunds.find_open_by_order(order.id).await?.is_some() {
+        return Err(AppError::Conflict(
+            "A refund for this order is already requested".into(),
+        ));
+    }
+
+    let request = svc
+        .refunds
+        .create(CreateRefundRequestRecord {
+            order_id: order.id,
+            user_id: user.user_id,
+            reason: req.reason.trim().to_string(),
+            amount_cents: order.total_cents,
+            currency: order.currency.clone(),
+        })
+        .await?;
+    record_event(
+        svc.orders,
+        order.id,
+        Some(user.user_id),
+        OrderEventKind::RefundRequested,
+        Some(request.reason.clone()),
+    )
+    .await?;
+    Ok(request.into())
+}
+
+/// Refund requests, oldest first (admin only)
+pub async fn list_refund_requests(
+    refunds: &dyn RefundRequestsRepository,
+    user: &AuthUser,
+    status: Option<&str>,
+    limit: Option<i64>,
+) -> AppResult<Vec<RefundRequest>> {
+    user.require_admin()?;
+    let status = match status.unwrap_or("pending") {
+        "all" => None,
+        s => Some(RefundRequestStatus::parse(s).ok_or_else(|| {
+            AppError::BadRequest("status must be pending, rejected, refunded, failed or all".into())
+        })?),
+    };
+    let limit = limit.unwrap_or(50).clamp(1, 200);
+    Ok(refunds
+        .list(status, limit)
+        .await?
+        .into_iter()
+        .map(RefundRequest::from)
+        .collect())
+}
+
+/// Refund the order through the provider and revoke access (admin only)
+pub async fn approve_refund(
+    svc: &RefundServices<'_>,
+    user: &AuthUser,
+    id: uuid::Uuid,
+    req: ReviewRefundRequest,
+) -> AppResult<RefundRequest> {
+    user.require_admin()?;
+    let request = load_open_request(svc.refunds, id).await?;
+    let order = svc
+        .orders
+        .find_by_id(request.order_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Order not found".into()))?;
+
+    let provider_refund_id = match OrderStatus::parse(&order.status) {
+        // Already refunded from the provider's dashboard
+        Some(OrderStatus::Refunded) => None,
+        Some(OrderStatus::Paid) => refund_with_provider(svc, user, &request, &order).await?,
+        _ => {
+            return Err(AppError::Conflict(format!(
+                "Order is {} and cannot be refunded",
+                order.status
+            )));
+        }
+    };
+
+    let review = ReviewRefundRequestRecord {
+        reviewed_by: user.user_id,
+        note: req.note,
+        provider_refund_id,
+    };
+    let Some(request) = svc
+        .refunds
+        .transition_status(id, OPEN, RefundRequestStatus::Refunded, review)
+        .await?
+    else {
+        // Approved concurrently
+        return load_request(svc.refunds, id).await.map(RefundRequest::from);
+    };
+
+    if let Some(order) = svc
+        .orders
+        .transition_status(order.id, &[OrderStatus::Paid], OrderStatus::Refunded)
+        .await?
+    {
+        record_event(
+            svc.orders,
+            order.id,
+            Some(user.user_id),
+            OrderEventKind::Refunded,
+            Some(format!(
+                "{} refunded",
+                Money::new(request.amount_cents, request.currency.clone())
+            )),
+        )
+        .await?;
+        revoke_order_enrollments(svc.orders, svc.enrollments, &order, Some(user.user_id)).await?;
+        info(format!(
+            "Order {} refunded on request {}",
+            order.id, request.id
+        ));
+    }
+    Ok(request.into())
+}
+
+/// Turn a refund request down (admin only); the buyer is told why
+pub async fn reject_refund(
+    svc: &RefundServices<'_>,
+    user: &AuthUser,
+    id: uuid::Uuid,
+    req: ReviewRefundRequest,
+) -> AppResult<RefundRequest> {
+    user.require_admin()?;
+    let note = req
+        .note
+        .ok_or_else(|| AppError::BadRequest("Explain why the refund is rejected".into()))?;
+    load_open_request(svc.refunds, id).await?;
+    let request = svc
+        .refunds
+        .transition_status(
+            id,
+            OPEN,
+            RefundRequestStatus::Rejected,
+            ReviewRefundRequestRecord {
+                reviewed_by: user.user_id,
+                note: Some(note.clone()),
+                provider_refund_id: None,
+            },
+        )
+        .await?
+        .ok_or_else(|| AppError::Conflict("Refund request was reviewed meanwhile".into()))?;
+    record_event(
+        svc.orders,
+        request.order_id,
+        Some(user.user_id),
+        OrderEventKind::RefundRejected,
+        Some(note),
+    )
+    .await?;
+    Ok(request.into())
+}
+
+/// Requests an admin can still act on
+const OPEN: &[RefundRequestStatus] = &[RefundRequestStatus::Pending, RefundRequestStatus::Failed];
+
+/// Refund the order total; returns the provider's refund id, if money moved.
+///
+/// A declined refund marks the request failed so it can be approved again.
+async fn refund_with_provider(
+    svc: &RefundServices<'_>,
+    user: &AuthUser,
+    request: &RefundRequestRecord,
+    order: &OrderRecord,
+) -> AppResult<Option<String>> {
+    if request.amount_cents == 0 {
+        return Ok(None);
+    }
+    let attempt = svc
+        .orders
+        .latest_payment_attempt(order.id)
+        .await?
+        .filter(|a| PaymentStatus::parse(&a.status) == Some(PaymentStatus::Succeeded))
+        .ok_or_else(|| AppError::Conflict("Order has no settled payment to refund".into()))?;
+
+    let outcome = svc
+        .provider
+        .refund(&CreateRefund {
+            refund_id: request.id,
+            payment_id: attempt.provider_payment_id,
+            amount: Money::new(request.amount_cents, request.currency.clone()),
+        })
+        .await;
+    let error = match outcome {
+        Ok(refund) if refund.status != RefundStatus::Failed => return Ok(Some(refund.id)),
+        Ok(_) => AppError::BadRequest("The payment provider declined the refund".into()),
+        Err(e) => e,
+    };
+
+    svc.refunds
+        .transition_status(
+            request.id,
+            OPEN,
+            RefundRequestStatus::Failed,
+            ReviewRefundRequestRecord {
+                reviewed_by: user.user_id,
+                note: Some(error.to_string()),
+                provider_refund_id: None,
+            },
+        )
+        .await?;
+    record_event(
+        svc.orders,
+        order.id,
+        Some(user.user_id),
+        OrderEventKind::RefundFailed,
+        Some(error.to_string()),
+    )
+    .await?;
+    Err(error)
+}
+
+/// Refunds can be requested for `window_days` days after payment
+fn check_refund_window(
+    paid_at: Option<chrono::DateTime<chrono::Utc>>,
+    window_days: i64,
+    now: chrono::DateTime<chrono::Utc>,
+) -> AppResult<()> {
+    if window_days == 0 {
+        return Err(AppError::BadRequest(
+            "Refunds cannot be requested online".into(),
+        ));
+    }
+    let paid_at = paid_at.ok_or_else(|| AppError::Conflict("Order has not been paid".into()))?;
+    if now > paid_at + chrono::Duration::days(window_days) {
+        return Err(AppError::BadRequest(format!(
+            "Refunds can only be requested within {} days of payment",
+            window_days
+        )));
+    }
+    Ok(())
+}
+
+async fn load_request(
+    refunds: &dyn RefundRequestsRepository,
+    id: uuid::Uuid,
+) -> AppResult<RefundRequestRecord> {
+    refunds
+        .find_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Refund request not found".into()))
+}
+
+/// A request that is pending or failed
+async fn load_open_request(
+    refunds: &dyn RefundRequestsRepository,
+    id: uuid::Uuid,
+) -> AppResult<RefundRequestRecord> {
+    let request = load_request(refunds, id).await?;
+    match RefundRequestStatus::parse(&request.status) {
+        Some(s) if OPEN.contains(&s) => Ok(request),
+        _ => Err(AppError::Conflict(format!(
+            "Refund request is already {}",
+            request.status
+        ))),
+    }
+}
+
+impl From<RefundRequestRecord> for RefundRequest {
+    fn from(record: RefundRequestRecord) -> Self {
+        RefundRequest {
+            id: record.id,
+            order_id: record.order_id,
+            user_id: record.user_id,
+            status: record.status,
+            reason: record.reason,
+            amount: Money::new(record.amount_cents, record.currency),
+            provider_refund_id: record.provider_refund_id,
+            reviewed_by: record.reviewed_by,
+            reviewed_at: record.reviewed_at,
+            review_note: record.review_note,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refunds_are_only_requested_inside_the_window() {
+        let paid = chrono::Utc::now();
+        let day = chrono::Duration::days(1);
+        assert!(check_refund_window(Some(paid), 14, paid + day * 14).is_ok());
+        assert!(check_refund_window(Some(paid), 14, paid + day * 15).is_err());
+        assert!(check_refund_window(Some(paid), 0, paid).is_err());
+        assert!(check_refund_window(None, 14, paid).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::search::{
+    HIGHLIGHT_START, HIGHLIGHT_STOP, SearchHitRecord, SearchKind, SearchRepository,
+};
+use crate::types::search_types::{SearchQuery, SearchResponse, SearchResult};
+
+const DEFAULT_LIMIT: i64 = 20;
+const MAX_LIMIT: i64 = 50;
+const MAX_QUERY_CHARS: usize = 200;
+
+/// Search published courses, lessons (including subtitle transcripts) and blog
+/// posts. Falls back to trigram title matching when nothing matches exactly.
+pub async fn search(repo: &dyn SearchRepository, query: SearchQuery) -> AppResult<SearchResponse> {
+    let q = query.q.trim().to_string();
+    if q.chars().count() < 2 {
+        return Err(AppError::BadRequest(
+            "Search query must be at least 2 characters".into(),
+        ));
+    }
+    if q.chars().count() > MAX_QUERY_CHARS {
+        return Err(AppError::BadRequest(format!(
+            "Search query must be at most {} characters",
+            MAX_QUERY_CHARS
+        )));
+    }
+    let kinds = parse_kinds(query.types.as_deref())?;
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let mut hits = Vec::new();
+    for kind in &kinds {
+        hits.extend(repo.search(*kind, &q, limit).await?);
+    }
+    let fuzzy = hits.is_empty();
+    if fuzzy {
+        for kind in &kinds {
+            hits.extend(repo.search_fuzzy(*kind, &q, limit).await?);
+        }
+    }
+
+    hits.sort_by(|a, b| b.rank.total_cmp(&a.rank));
+    hits.truncate(limit as usize);
+
+    Ok(SearchResponse {
+        query: q,
+        fuzzy,
+        items: hits.into_iter().map(Into::into).collect(),
+    })
+}
+
+fn parse_kinds(raw: Option<&str>) -> AppResult<Vec<SearchKind>> {
+    let Some(raw) = raw.filter(|r| !r.trim().is_empty()) else {
+        return Ok(SearchKind::ALL.to_vec());
+    };
+    let mut kinds = Vec::new();
+    for part in raw.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let kind = SearchKind::parse(part).ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "Unknown search type '{}'; use course, lesson or post",
+                part
+            ))
+        })?;
+        if !kinds.contains(&kind) {
+            kinds.push(kind);
+        }
+    }
+    Ok(kinds)
+}
+
+/// Escape a snippet for HTML and turn highlight markers into `<mark>` tags
+pub fn highlight_html(snippet: &str) -> String {
+    let mut out = String::with_capacity(snippet.len() + 16);
+    let mut open = false;
+    for c in snippet.chars() {
+        match c {
+            HIGHLIGHT_START if !open => {
+                out.push_str("<mark>");
+                open = true;
+            }
+            HIGHLIGHT_STOP if open => {
+                out.push_str("</mark>");
+                open = false;
+            }
+            HIGHLIGHT_START | HIGHLIGHT_STOP => {}
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    if open {
+        out.push_str("</mark>");
+    }
+    out
+}
+
+impl From<SearchHitRecord> for SearchResult {
+    fn from(hit: SearchHitRecord) -> Self {
+        SearchResult {
+            kind: hit.kind.as_str().to_string(),
+            id: hit.id,
+            title: hit.title,
+            slug: hit.slug,
+            course_id: hit.course_id,
+            course_slug: hit.course_slug,
+            snippet: highlight_html(&hit.snippet),
+            rank: hit.rank,
+            transcript_match: hit.transcript_match,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlights_and_escapes_snippets() {
+        let raw = "use \u{2}<script>\u{3} & \u{2}async\u{3} fn";
+        assert_eq!(
+            highlight_html(raw),
+            "use <mark>&lt;script&gt;</mark> &amp; <mark>async</mark> fn"
+        );
+        assert_eq!(highlight_html("\u{2}open"), "<mark>open</mark>");
+    }
+
+    #[test]
+    fn parses_result_types() {
+        assert_eq!(parse_kinds(None).unwrap(), SearchKind::ALL.to_vec());
+        assert_eq!(
+            parse_kinds(Some("posts, course,post")).unwrap(),
+            vec![SearchKind::Post, SearchKind::Course]
+        );
+        assert!(parse_kinds(Some("videos")).is_err());
+    }
+}
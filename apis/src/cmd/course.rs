@@ -0,0 +1,116 @@
+//! Course archive commands for moving courses between environments.
+//!
+//! - `course export <course_id> <archive.zip>` writes a course archive
+//! - `course import <archive.zip> --instructor <user_id> [--slug <slug>]`
+//!   creates a draft course from one
+//! - `course import-package <package.zip> --instructor <user_id> [--slug <slug>]`
+//!   creates a draft course from a SCORM or Common Cartridge package
+
+use std::path::Path;
+
+use sqlx::postgres::PgPoolOptions;
+
+use crate::applications::courses::{archive, package_import};
+use crate::configs::db_config::DatabaseConfig;
+use crate::configs::media_config::MediaConfig;
+use crate::configs::payment_config::PaymentConfig;
+use crate::repositories::Repositories;
+
+const USAGE: &str = "Usage:
+  execute_academy course export <course_id> <archive.zip>
+  execute_academy course import <archive.zip> --instructor <user_id> [--slug <slug>]
+  execute_academy course import-package <package.zip> --instructor <user_id> [--slug <slug>]";
+
+pub async fn course_command(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    dotenv::dotenv().ok();
+    let db = DatabaseConfig::load_from_env()?;
+    let media = MediaConfig::load_from_env()?;
+    let private_root = Path::new(&media.private_uploads_dir);
+
+    match args.first().map(String::as_str) {
+        Some("export") => {
+            let [_, course_id, out] = args else {
+                return Err(USAGE.into());
+            };
+            let course_id: uuid::Uuid = course_id.parse()?;
+            let repos = connect(&db.database_url).await?;
+            let (slug, bytes) = archive::export_course_archive(
+                repos.courses.as_ref(),
+                repos.modules.as_ref(),
+                repos.lesson_subtitles.as_ref(),
+                course_id,
+                private_root,
+            )
+            .await?;
+            tokio::fs::write(out, &bytes).await?;
+            println!("Exported course '{}' to {} ({} bytes)", slug, out, bytes.len());
+        }
+        Some("import") => {
+            let Some(path) = args.get(1) else {
+                return Err(USAGE.into());
+            };
+            let instructor = flag(args, "--instructor").ok_or(USAGE)?;
+            let instructor_id: uuid::Uuid = instructor.parse()?;
+            let bytes = tokio::fs::read(path).await?;
+            let repos = connect(&db.database_url).await?;
+            // The operator supplies the file, so no upload limit applies here
+            let course = archive::import_course(
+                repos.courses.as_ref(),
+                instructor_id,
+                bytes,
+                flag(args, "--slug").map(str::to_string),
+                private_root,
+                usize::MAX,
+            )
+            .await?;
+            println!("Imported course '{}' as draft {}", course.slug, course.id);
+        }
+        Some("import-package") => {
+            let Some(path) = args.get(1) else {
+                return Err(USAGE.into());
+            };
+            let instructor = flag(args, "--instructor").ok_or(USAGE)?;
+            let instructor_id: uuid::Uuid = instructor.parse()?;
+            let bytes = tokio::fs::read(path).await?;
+            let payments = PaymentConfig::load_from_env()?;
+            let repos = connect(&db.database_url).await?;
+            let result = package_import::import_package(
+                repos.courses.as_ref(),
+                instructor_id,
+                bytes,
+                flag(args, "--slug").map(str::to_string),
+                &payments.currency,
+                usize::MAX,
+            )
+            .await?;
+            println!(
+                "Imported {} package as course '{}' (draft {})",
+                result.package_type, result.course.slug, result.course.id
+            );
+            for notice in &result.log {
+                match &notice.item {
+                    Some(item) => println!("  - [{}] {}", item, notice.message),
+                    None => println!("  - {}", notice.message),
+                }
+            }
+        }
+        _ => return Err(USAGE.into()),
+    }
+    Ok(())
+}
+
+async fn connect(database_url: &str) -> Result<Repositories, Box<dyn std::error::Error>> {
+    let pool = PgPoolOptions::new()
+        .max_connections(5)
+        .connect(database_url)
+        .await?;
+    Ok(Repositories::new(pool))
+}
+
+/// Value following `name` in `args`
+fn flag<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == name)
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
+}
@@ -1,5 +1,6 @@
 pub mod apis;
 pub mod cli;
+pub mod course;
 pub mod graphql;
 pub mod grpc;
 pub mod migrate;
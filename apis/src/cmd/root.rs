@@ -39,6 +39,10 @@ impl RootCommand {
                 let rt = tokio::runtime::Runtime::new()?;
                 rt.block_on(crate::cmd::seed::seed_command())?;
             }
+            "course" => {
+                let rt = tokio::runtime::Runtime::new()?;
+                rt.block_on(crate::cmd::course::course_command(&self.args[2..]))?;
+            }
             "openapi" => {
                 crate::cmd::openapi::openapi_command()?;
             }
@@ -65,6 +69,7 @@ impl RootCommand {
         println!("  grpc    - Run gRPC server");
         println!("  migrate - Run SQLx database migrations (Postgres)");
         println!("  seed    - Seed default admin user (idempotent)");
+        println!("  course  - Export or import course archives, or import SCORM/Common Cartridge packages (course export|import|import-package ...)");
         println!("  openapi - Generate OpenAPI YAML documentation");
         println!();
         println!("Options:");
@@ -5,11 +5,16 @@ use sqlx::postgres::PgPoolOptions;
 use sqlx::{Pool, Postgres};
 
 use crate::configs::auth_config::AuthConfig;
+use crate::configs::certificate_config::CertificateConfig;
 use crate::configs::db_config::DatabaseConfig;
 // Kafka is not used
 use crate::configs::email_config::EmailConfig;
+use crate::configs::media_config::MediaConfig;
+use crate::configs::payment_config::PaymentConfig;
+use crate::configs::rate_limit_config::RateLimitSettings;
 use crate::configs::redis_config::RedisConfig;
 use crate::configs::system_config::SystemConfig;
+use crate::configs::video_config::VideoConfig;
 // Email is sent directly where needed; no producer required
 use crate::pkg::security_services::{
     Argon2PasswordHasher, Hs256JwtService, JwtService, PasswordHasher,
@@ -18,6 +23,8 @@ use crate::repositories::Repositories;
 
 use crate::pkg::redis::RedisManager;
 use crate::pkg::redis::RedisOps;
+use crate::pkg::payments::{FakePaymentProvider, PaymentProvider, StripeProvider};
+use crate::pkg::video::{FakeVideoProvider, VideoProvider, VimeoProvider};
 use tokio::sync::OnceCell;
 
 /// Application context containing runtime configuration and core connections.
@@ -33,6 +40,13 @@ pub struct AppContext {
     pub jwt_service: Arc<dyn JwtService>,
     pub email: EmailConfig,
     pub email_sender: Arc<dyn crate::pkg::email::EmailSender>,
+    pub media: MediaConfig,
+    pub video: VideoConfig,
+    pub video_provider: Arc<dyn VideoProvider>,
+    pub rate_limit: RateLimitSettings,
+    pub certificates: CertificateConfig,
+    pub payments: PaymentConfig,
+    pub payment_provider: Arc<dyn PaymentProvider>,
 }
 
 impl AppContext {
@@ -45,6 +59,11 @@ impl AppContext {
         let redis_cfg = RedisConfig::load_from_env()?;
         let auth = AuthConfig::load_from_env()?;
         let email_cfg = EmailConfig::load_from_env()?;
+        let media = MediaConfig::load_from_env()?;
+        let video = VideoConfig::load_from_env()?;
+        let rate_limit = RateLimitSettings::load_from_env()?;
+        let certificates = CertificateConfig::load_from_env()?;
+        let payments = PaymentConfig::load_from_env()?;
 
         let db_pool = PgPoolOptions::new()
             .max_connections(10)
@@ -71,6 +90,24 @@ impl AppContext {
             crate::pkg::email::smtp_sender::SmtpEmailSender::try_new(&email_cfg)?;
         let email_sender: Arc<dyn crate::pkg::email::EmailSender> = Arc::new(email_sender_impl);
 
+        // Video hosting provider for lesson videos
+        let video_provider: Arc<dyn VideoProvider> = match video.provider.as_str() {
+            "vimeo" => Arc::new(VimeoProvider::new(
+                video.vimeo_access_token.clone(),
+                video.vimeo_api_base.clone(),
+            )),
+            _ => Arc::new(FakeVideoProvider::new()),
+        };
+
+        // Payment provider for checkout
+        let payment_provider: Arc<dyn PaymentProvider> = match payments.provider.as_str() {
+            "stripe" => Arc::new(StripeProvider::new(
+                payments.stripe_secret_key.clone(),
+                payments.stripe_api_base.clone(),
+            )),
+            _ => Arc::new(FakePaymentProvider::new()),
+        };
+
         Ok(Self {
             system,
             db_pool: db_pool.clone(),
@@ -82,6 +119,13 @@ impl AppContext {
             jwt_service,
             email: email_cfg,
             email_sender,
+            media,
+            video,
+            video_provider,
+            rate_limit,
+            certificates,
+            payments,
+            payment_provider,
         })
     }
 
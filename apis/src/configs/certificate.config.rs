@@ -0,0 +1,81 @@
+//! Completion certificate configuration loader.
+
+use std::env;
+
+/// Certificate issuance settings derived from environment variables.
+///
+/// Prefer using `CertificateConfig::load_from_env()` to initialize this struct.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CertificateConfig {
+    /// Minimum quiz score, in percent, that counts as a pass.
+    pub quiz_pass_mark: f64,
+    /// Public verification URL printed on certificates; the code is appended.
+    pub verify_base_url: String,
+}
+
+impl CertificateConfig {
+    /// Load certificate configuration from environment variables.
+    ///
+    /// Supported variables (with defaults):
+    /// - `CERTIFICATE_QUIZ_PASS_MARK` → default: 70
+    /// - `CERTIFICATE_VERIFY_BASE_URL` → default: "http://127.0.0.1:8080/api/certificates"
+    pub fn load_from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        let _ = dotenv::dotenv().ok();
+
+        let quiz_pass_mark = match env::var("CERTIFICATE_QUIZ_PASS_MARK") {
+            Ok(val) => val
+                .parse::<f64>()
+                .ok()
+                .filter(|v| (0.0..=100.0).contains(v))
+                .ok_or("CERTIFICATE_QUIZ_PASS_MARK must be a number between 0 and 100")?,
+            Err(_) => 70.0,
+        };
+        let verify_base_url = env::var("CERTIFICATE_VERIFY_BASE_URL")
+            .unwrap_or_else(|_| "http://127.0.0.1:8080/api/certificates".to_string())
+            .trim_end_matches('/')
+            .to_string();
+
+        Ok(Self {
+            quiz_pass_mark,
+            verify_base_url,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    fn clear_vars() {
+        for k in ["CERTIFICATE_QUIZ_PASS_MARK", "CERTIFICATE_VERIFY_BASE_URL"] {
+            unsafe {
+                env::remove_var(k);
+            }
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn loads_defaults_when_unset() {
+        clear_vars();
+        let cfg = CertificateConfig::load_from_env().expect("should load defaults");
+        assert_eq!(cfg.quiz_pass_mark, 70.0);
+        assert_eq!(
+            cfg.verify_base_url,
+            "http://127.0.0.1:8080/api/certificates"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn rejects_out_of_range_pass_mark() {
+        clear_vars();
+        unsafe {
+            env::set_var("CERTIFICATE_QUIZ_PASS_MARK", "120");
+        }
+        let err = CertificateConfig::load_from_env().unwrap_err();
+        assert!(format!("{}", err).contains("CERTIFICATE_QUIZ_PASS_MARK"));
+        clear_vars();
+    }
+}
@@ -0,0 +1,115 @@
+//! Private media configuration loader.
+//!
+//! Paid lesson files are kept outside the publicly served `./uploads` tree and
+//! handed out through HMAC-signed, expiring URLs. This module loads where those
+//! files live and how the download links are signed.
+
+use std::env;
+
+use super::system_config::{app_env, is_local_env};
+
+/// Private media configuration derived from environment variables.
+///
+/// Prefer using `MediaConfig::load_from_env()` to initialize this struct.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaConfig {
+    /// Root directory for private files. Must not be served statically.
+    pub private_uploads_dir: String,
+    /// Secret used to sign download URLs.
+    pub url_signing_secret: String,
+    /// Lifetime of an issued download URL, in seconds.
+    pub signed_url_ttl_seconds: i64,
+}
+
+impl MediaConfig {
+    /// Load private media configuration from environment variables.
+    ///
+    /// Supported variables (with defaults):
+    /// - `PRIVATE_UPLOADS_DIR` → default: "./private_uploads"
+    /// - `MEDIA_URL_SIGNING_SECRET` → default: "dev_media_secret_change_me"
+    ///   when `APP_ENV` is "development" or "test", required otherwise
+    /// - `SIGNED_URL_TTL_SECONDS` → default: 900
+    pub fn load_from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        let _ = dotenv::dotenv().ok();
+
+        let private_uploads_dir =
+            env::var("PRIVATE_UPLOADS_DIR").unwrap_or_else(|_| "./private_uploads".to_string());
+        let url_signing_secret = match env::var("MEDIA_URL_SIGNING_SECRET") {
+            Ok(v) if !v.trim().is_empty() => v,
+            _ if is_local_env(&app_env()) => "dev_media_secret_change_me".to_string(),
+            _ => return Err("MEDIA_URL_SIGNING_SECRET is required outside development".into()),
+        };
+        let signed_url_ttl_seconds = match env::var("SIGNED_URL_TTL_SECONDS") {
+            Ok(val) => val
+                .parse::<i64>()
+                .ok()
+                .filter(|v| *v > 0)
+                .ok_or("SIGNED_URL_TTL_SECONDS must be a positive integer")?,
+            Err(_) => 900,
+        };
+
+        Ok(Self {
+            private_uploads_dir,
+            url_signing_secret,
+            signed_url_ttl_seconds,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    fn clear_vars() {
+        for k in [
+            "PRIVATE_UPLOADS_DIR",
+            "MEDIA_URL_SIGNING_SECRET",
+            "SIGNED_URL_TTL_SECONDS",
+            "APP_ENV",
+        ] {
+            unsafe {
+                env::remove_var(k);
+            }
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn loads_defaults_when_unset() {
+        clear_vars();
+        let cfg = MediaConfig::load_from_env().expect("should load defaults");
+        assert_eq!(cfg.private_uploads_dir, "./private_uploads");
+        assert_eq!(cfg.signed_url_ttl_seconds, 900);
+    }
+
+    #[test]
+    #[serial]
+    fn rejects_non_positive_ttl() {
+        clear_vars();
+        unsafe {
+            env::set_var("SIGNED_URL_TTL_SECONDS", "0");
+        }
+        let err = MediaConfig::load_from_env().unwrap_err();
+        assert!(format!("{}", err).contains("SIGNED_URL_TTL_SECONDS"));
+        clear_vars();
+    }
+
+    #[test]
+    #[serial]
+    fn signing_secret_is_required_outside_development() {
+        clear_vars();
+        unsafe {
+            env::set_var("APP_ENV", "production");
+        }
+        let err = MediaConfig::load_from_env().unwrap_err();
+        assert!(format!("{}", err).contains("MEDIA_URL_SIGNING_SECRET is required"));
+
+        unsafe {
+            env::set_var("MEDIA_URL_SIGNING_SECRET", "s3cret");
+        }
+        let cfg = MediaConfig::load_from_env().expect("should load with a secret");
+        assert_eq!(cfg.url_signing_secret, "s3cret");
+        clear_vars();
+    }
+}
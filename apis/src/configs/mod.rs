@@ -24,3 +24,23 @@ pub mod auth_config;
 #[path = "email.config.rs"]
 pub mod email_config;
 
+
+// Private media (signed download URLs) config
+#[path = "media.config.rs"]
+pub mod media_config;
+
+// Video hosting provider config
+#[path = "video.config.rs"]
+pub mod video_config;
+
+// HTTP rate limit policy table
+#[path = "rate_limit.config.rs"]
+pub mod rate_limit_config;
+
+// Completion certificates (quiz pass mark, verification URL)
+#[path = "certificate.config.rs"]
+pub mod certificate_config;
+
+// Payment provider selection and credentials
+#[path = "payment.config.rs"]
+pub mod payment_config;
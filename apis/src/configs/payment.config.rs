@@ -0,0 +1,196 @@
+//! Payment configuration loader.
+//!
+//! Selects the `PaymentProvider` used at checkout, holds its credentials and
+//! the currency course prices are charged in.
+
+use std::env;
+
+use super::system_config::{app_env, is_local_env};
+
+/// Payment configuration derived from environment variables.
+///
+/// Prefer using `PaymentConfig::load_from_env()` to initialize this struct.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentConfig {
+    /// Provider name: "stripe" or "fake" (in-memory, approves every payment;
+    /// only allowed in development and test).
+    pub provider: String,
+    /// Stripe secret API key (`sk_...`).
+    pub stripe_secret_key: String,
+    /// Stripe API base URL.
+    pub stripe_api_base: String,
+    /// ISO 4217 code, upper case, that orders are charged in.
+    pub currency: String,
+    /// Secret shared with the provider for signing webhook calls.
+    pub webhook_secret: String,
+    /// Maximum age, in seconds, of a webhook signature timestamp.
+    pub webhook_tolerance_secs: i64,
+    /// Days after payment during which learners may ask for a refund; 0
+    /// turns refund requests off.
+    pub refund_window_days: i64,
+}
+
+impl PaymentConfig {
+    /// Load payment configuration from environment variables.
+    ///
+    /// Supported variables (with defaults):
+    /// - `PAYMENT_PROVIDER` → default: "fake" when `APP_ENV` is "development"
+    ///   or "test", required otherwise; "fake" is refused outside those
+    /// - `STRIPE_SECRET_KEY` → required when the provider is "stripe"
+    /// - `STRIPE_API_BASE` → default: "https://api.stripe.com"
+    /// - `PAYMENT_CURRENCY` → default: "USD"
+    /// - `PAYMENT_WEBHOOK_SECRET` → required when the provider is "stripe";
+    ///   webhooks are refused while it is empty
+    /// - `PAYMENT_WEBHOOK_TOLERANCE_SECS` → default: 300
+    /// - `PAYMENT_REFUND_WINDOW_DAYS` → default: 14
+    pub fn load_from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        let _ = dotenv::dotenv().ok();
+
+        let app_env = app_env();
+        let provider = match env::var("PAYMENT_PROVIDER") {
+            Ok(v) => v.trim().to_ascii_lowercase(),
+            Err(_) if is_local_env(&app_env) => "fake".to_string(),
+            Err(_) => return Err("PAYMENT_PROVIDER is required outside development".into()),
+        };
+        let stripe_secret_key = env::var("STRIPE_SECRET_KEY").unwrap_or_default();
+        let stripe_api_base =
+            env::var("STRIPE_API_BASE").unwrap_or_else(|_| "https://api.stripe.com".to_string());
+        let currency = env::var("PAYMENT_CURRENCY")
+            .map(|v| v.trim().to_ascii_uppercase())
+            .unwrap_or_else(|_| "USD".to_string());
+        let webhook_secret = env::var("PAYMENT_WEBHOOK_SECRET").unwrap_or_default();
+        let webhook_tolerance_secs = match env::var("PAYMENT_WEBHOOK_TOLERANCE_SECS") {
+            Ok(val) => val
+                .parse::<i64>()
+                .ok()
+                .filter(|v| *v > 0)
+                .ok_or("PAYMENT_WEBHOOK_TOLERANCE_SECS must be a positive integer")?,
+            Err(_) => 300,
+        };
+        let refund_window_days = match env::var("PAYMENT_REFUND_WINDOW_DAYS") {
+            Ok(val) => val
+                .parse::<i64>()
+                .ok()
+                .filter(|v| *v >= 0)
+                .ok_or("PAYMENT_REFUND_WINDOW_DAYS must be a non-negative integer")?,
+            Err(_) => 14,
+        };
+
+        if currency.len() != 3 || !currency.bytes().all(|b| b.is_ascii_alphabetic()) {
+            return Err("PAYMENT_CURRENCY must be a three-letter ISO 4217 code".into());
+        }
+        match provider.as_str() {
+            "fake" if !is_local_env(&app_env) => {
+                return Err(format!(
+                    "PAYMENT_PROVIDER=fake approves every payment and is not allowed when APP_ENV={}",
+                    app_env
+                )
+                .into());
+            }
+            "fake" => {}
+            "stripe" if stripe_secret_key.is_empty() => {
+                return Err("STRIPE_SECRET_KEY is required when PAYMENT_PROVIDER=stripe".into());
+            }
+            "stripe" if webhook_secret.is_empty() => {
+                return Err(
+                    "PAYMENT_WEBHOOK_SECRET is required when PAYMENT_PROVIDER=stripe".into(),
+                );
+            }
+            "stripe" => {}
+            other => return Err(format!("Unsupported PAYMENT_PROVIDER: {}", other).into()),
+        }
+
+        Ok(Self {
+            provider,
+            stripe_secret_key,
+            stripe_api_base,
+            currency,
+            webhook_secret,
+            webhook_tolerance_secs,
+            refund_window_days,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    fn clear_vars() {
+        for k in [
+            "APP_ENV",
+            "PAYMENT_PROVIDER",
+            "STRIPE_SECRET_KEY",
+            "STRIPE_API_BASE",
+            "PAYMENT_CURRENCY",
+            "PAYMENT_WEBHOOK_SECRET",
+            "PAYMENT_WEBHOOK_TOLERANCE_SECS",
+            "PAYMENT_REFUND_WINDOW_DAYS",
+        ] {
+            unsafe {
+                env::remove_var(k);
+            }
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn loads_defaults_when_unset() {
+        clear_vars();
+        let cfg = PaymentConfig::load_from_env().expect("should load defaults");
+        assert_eq!(cfg.provider, "fake");
+        assert_eq!(cfg.currency, "USD");
+        assert_eq!(cfg.stripe_api_base, "https://api.stripe.com");
+        assert_eq!(cfg.webhook_tolerance_secs, 300);
+        assert_eq!(cfg.refund_window_days, 14);
+    }
+
+    #[test]
+    #[serial]
+    fn stripe_requires_api_and_webhook_secrets() {
+        clear_vars();
+        unsafe {
+            env::set_var("PAYMENT_PROVIDER", "stripe");
+        }
+        let err = PaymentConfig::load_from_env().unwrap_err();
+        assert!(format!("{}", err).contains("STRIPE_SECRET_KEY"));
+
+        unsafe {
+            env::set_var("STRIPE_SECRET_KEY", "sk_test_123");
+        }
+        let err = PaymentConfig::load_from_env().unwrap_err();
+        assert!(format!("{}", err).contains("PAYMENT_WEBHOOK_SECRET"));
+
+        unsafe {
+            env::set_var("PAYMENT_WEBHOOK_SECRET", "whsec_123");
+            env::set_var("PAYMENT_CURRENCY", "eur");
+        }
+        let cfg = PaymentConfig::load_from_env().unwrap();
+        assert_eq!(cfg.currency, "EUR");
+        clear_vars();
+    }
+
+    #[test]
+    #[serial]
+    fn fake_provider_is_refused_outside_development() {
+        clear_vars();
+        unsafe {
+            env::set_var("APP_ENV", "production");
+        }
+        let err = PaymentConfig::load_from_env().unwrap_err();
+        assert!(format!("{}", err).contains("PAYMENT_PROVIDER is required"));
+
+        unsafe {
+            env::set_var("PAYMENT_PROVIDER", "fake");
+        }
+        let err = PaymentConfig::load_from_env().unwrap_err();
+        assert!(format!("{}", err).contains("not allowed when APP_ENV=production"));
+
+        unsafe {
+            env::set_var("APP_ENV", "test");
+        }
+        assert_eq!(PaymentConfig::load_from_env().unwrap().provider, "fake");
+        clear_vars();
+    }
+}
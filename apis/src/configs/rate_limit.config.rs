@@ -0,0 +1,198 @@
+//! Rate limit configuration loader.
+//!
+//! Builds the HTTP rate limit policy table. The built-in table keeps strict
+//! budgets on login, password reset and OTP endpoints; a YAML/JSON file can
+//! replace it entirely, and a few environment variables cover the common
+//! tweaks without a file.
+
+use std::env;
+
+use serde::Deserialize;
+
+use crate::pkg::client_ip::parse_ip_net;
+use crate::pkg::rate_limit::policy::{RateLimitKey, RateLimitPolicies, RateLimitPolicy};
+
+/// Rate limit configuration derived from environment variables.
+///
+/// Prefer using `RateLimitSettings::load_from_env()` to initialize this struct.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitSettings {
+    /// Policy table for the HTTP API
+    pub policies: RateLimitPolicies,
+}
+
+/// Shape of the optional policy file (YAML or JSON)
+#[derive(Debug, Deserialize)]
+struct PolicyFile {
+    default: Option<LimitSpec>,
+    #[serde(default)]
+    allowlist: Vec<String>,
+    #[serde(default)]
+    policies: Vec<RateLimitPolicy>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LimitSpec {
+    limit: u32,
+    window_secs: u64,
+}
+
+impl RateLimitSettings {
+    /// Load rate limit configuration from environment variables.
+    ///
+    /// Supported variables (with defaults):
+    /// - `RATE_LIMIT_POLICIES_FILE` → optional YAML/JSON file with `default`,
+    ///   `allowlist` and `policies`; its policies replace the built-in table
+    /// - `RATE_LIMIT_DEFAULT` → `<limit>/<window_secs>`, default: "25/60"
+    /// - `RATE_LIMIT_ALLOWLIST` → comma-separated IPs/CIDRs, default: none
+    pub fn load_from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        let _ = dotenv::dotenv().ok();
+
+        let file = match env::var("RATE_LIMIT_POLICIES_FILE") {
+            Ok(path) if !path.trim().is_empty() => {
+                let raw = std::fs::read_to_string(path.trim())
+                    .map_err(|e| format!("RATE_LIMIT_POLICIES_FILE: {}", e))?;
+                Some(
+                    serde_yaml::from_str::<PolicyFile>(&raw)
+                        .map_err(|e| format!("RATE_LIMIT_POLICIES_FILE: {}", e))?,
+                )
+            }
+            _ => None,
+        };
+
+        let (mut limit, mut window_secs) = (25, 60);
+        if let Some(spec) = file.as_ref().and_then(|f| f.default.as_ref()) {
+            (limit, window_secs) = (spec.limit, spec.window_secs);
+        }
+        if let Ok(val) = env::var("RATE_LIMIT_DEFAULT") {
+            (limit, window_secs) = parse_limit(&val)
+                .ok_or("RATE_LIMIT_DEFAULT must look like <limit>/<window_secs>, e.g. 25/60")?;
+        }
+
+        let mut allowlist = Vec::new();
+        let file_allowlist = file
+            .as_ref()
+            .map(|f| f.allowlist.clone())
+            .unwrap_or_default();
+        let env_allowlist = env::var("RATE_LIMIT_ALLOWLIST").unwrap_or_default();
+        for entry in file_allowlist
+            .iter()
+            .map(String::as_str)
+            .chain(env_allowlist.split(','))
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            allowlist.push(
+                parse_ip_net(entry)
+                    .ok_or_else(|| format!("invalid rate limit allowlist entry '{}'", entry))?,
+            );
+        }
+
+        let policies = RateLimitPolicies {
+            default: RateLimitPolicy::new(
+                "default",
+                &[],
+                "/*",
+                limit,
+                window_secs,
+                RateLimitKey::UserOrIp,
+            ),
+            policies: match file {
+                Some(f) => f
+                    .policies
+                    .into_iter()
+                    .map(|mut p| {
+                        p.methods
+                            .iter_mut()
+                            .for_each(|m| *m = m.to_ascii_uppercase());
+                        p
+                    })
+                    .collect(),
+                None => RateLimitPolicies::builtin(),
+            },
+            allowlist,
+        };
+        policies.validate()?;
+
+        Ok(Self { policies })
+    }
+}
+
+fn parse_limit(value: &str) -> Option<(u32, u64)> {
+    let (limit, window) = value.trim().split_once('/')?;
+    let window = window.trim().parse::<u64>().ok().filter(|w| *w > 0)?;
+    Some((limit.trim().parse().ok()?, window))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    fn clear_vars() {
+        for k in [
+            "RATE_LIMIT_POLICIES_FILE",
+            "RATE_LIMIT_DEFAULT",
+            "RATE_LIMIT_ALLOWLIST",
+        ] {
+            unsafe {
+                env::remove_var(k);
+            }
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn loads_builtin_table_with_env_overrides() {
+        clear_vars();
+        unsafe {
+            env::set_var("RATE_LIMIT_DEFAULT", "100/30");
+            env::set_var("RATE_LIMIT_ALLOWLIST", "10.0.0.0/8, 127.0.0.1");
+        }
+        let cfg = RateLimitSettings::load_from_env().expect("should load");
+        assert_eq!(cfg.policies.default.limit, 100);
+        assert_eq!(cfg.policies.default.window_secs, 30);
+        assert_eq!(
+            cfg.policies.resolve("POST", "/api/auth/login").name,
+            "auth-login"
+        );
+        assert!(cfg.policies.is_allowlisted("127.0.0.1".parse().unwrap()));
+        clear_vars();
+    }
+
+    #[test]
+    #[serial]
+    fn policy_file_replaces_builtin_table() {
+        clear_vars();
+        let path = std::env::temp_dir().join(format!("rl-{}.yaml", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            "default: { limit: 50, window_secs: 60 }\n\
+             allowlist: ['192.168.0.0/16']\n\
+             policies:\n\
+             - { name: uploads, methods: [post], path: /api/media/*, limit: 5, window_secs: 60 }\n",
+        )
+        .unwrap();
+        unsafe {
+            env::set_var("RATE_LIMIT_POLICIES_FILE", &path);
+        }
+        let cfg = RateLimitSettings::load_from_env().expect("should load file");
+        assert_eq!(cfg.policies.default.limit, 50);
+        assert_eq!(
+            cfg.policies.resolve("POST", "/api/media/upload").name,
+            "uploads"
+        );
+        assert_eq!(
+            cfg.policies.resolve("POST", "/api/auth/login").name,
+            "default"
+        );
+        assert!(cfg.policies.is_allowlisted("192.168.1.1".parse().unwrap()));
+
+        unsafe {
+            env::set_var("RATE_LIMIT_DEFAULT", "oops");
+        }
+        assert!(RateLimitSettings::load_from_env().is_err());
+        let _ = std::fs::remove_file(path);
+        clear_vars();
+    }
+}
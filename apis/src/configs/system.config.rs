@@ -6,6 +6,10 @@
 
 use std::env;
 
+use ipnet::IpNet;
+
+use crate::pkg::client_ip::parse_ip_net;
+
 /// System-level configuration derived from environment variables.
 ///
 /// Prefer using `SystemConfig::load_from_env()` to initialize this struct.
@@ -30,6 +34,8 @@ pub struct SystemConfig {
     /// Maximum allowed size in bytes for uploaded files (streamed or in-memory).
     /// Defaults to 10 MiB.
     pub max_upload_bytes: usize,
+    /// Reverse proxies whose forwarding headers are trusted for the client IP.
+    pub trusted_proxies: Vec<IpNet>,
 }
 
 impl SystemConfig {
@@ -43,8 +49,9 @@ impl SystemConfig {
     /// - `GRPC_HOST` → default: "127.0.0.1"
     /// - `GRPC_PORT` → default: 50051
     /// - `SHUTDOWN_GRACE_SECONDS` → default: 10
+    /// - `TRUSTED_PROXIES` → comma-separated IPs/CIDRs, default: "127.0.0.1,::1"
     pub fn load_from_env() -> Result<Self, Box<dyn std::error::Error>> {
-        let app_env = env::var("APP_ENV").unwrap_or_else(|_| "development".to_string());
+        let app_env = app_env();
         let log_level = env::var("LOG_LEVEL").unwrap_or_else(|_| "info".to_string());
         let api_host = env::var("API_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
         let api_port = parse_port_with_default("API_PORT", 8080)?;
@@ -53,6 +60,10 @@ impl SystemConfig {
         let shutdown_grace_seconds = parse_u64_with_default("SHUTDOWN_GRACE_SECONDS", 10)?;
         let max_upload_bytes = parse_usize_with_default("MAX_UPLOAD_BYTES", 10 * 1024 * 1024)?;
         let graphql_port = parse_port_with_default("GRAPHQL_PORT", 9099)?;
+        let trusted_proxies = parse_ip_nets(
+            "TRUSTED_PROXIES",
+            &env::var("TRUSTED_PROXIES").unwrap_or_else(|_| "127.0.0.1,::1".to_string()),
+        )?;
 
         Ok(Self {
             app_env,
@@ -64,10 +75,22 @@ impl SystemConfig {
             shutdown_grace_seconds,
             max_upload_bytes,
             graphql_port,
+            trusted_proxies,
         })
     }
 }
 
+/// `APP_ENV`, defaulting to "development" like `SystemConfig`
+pub fn app_env() -> String {
+    env::var("APP_ENV").unwrap_or_else(|_| "development".to_string())
+}
+
+/// Whether `app_env` is a local environment, where insecure development
+/// defaults (fake payments, well-known secrets) are acceptable
+pub fn is_local_env(app_env: &str) -> bool {
+    matches!(app_env, "development" | "test")
+}
+
 /// Parse a `u16` port from an environment variable, applying a default when missing.
 fn parse_port_with_default(
     var: &str,
@@ -116,6 +139,19 @@ fn parse_usize_with_default(
     }
 }
 
+/// Parse a comma-separated list of IPs/CIDRs from the value of `var`.
+fn parse_ip_nets(var: &str, value: &str) -> Result<Vec<IpNet>, Box<dyn std::error::Error>> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            parse_ip_net(s)
+                .ok_or_else(|| format!("{} has an invalid IP or CIDR '{}'", var, s).into())
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -143,6 +179,7 @@ mod tests {
             "SHUTDOWN_GRACE_SECONDS",
             "MAX_UPLOAD_BYTES",
             "GRAPHQL_PORT",
+            "TRUSTED_PROXIES",
         ]);
 
         let cfg = SystemConfig::load_from_env().expect("should load defaults");
@@ -155,6 +192,7 @@ mod tests {
         assert_eq!(cfg.shutdown_grace_seconds, 10);
         assert_eq!(cfg.max_upload_bytes, 10 * 1024 * 1024);
         assert_eq!(cfg.graphql_port, 9099);
+        assert_eq!(cfg.trusted_proxies.len(), 2);
     }
 
     #[test]
@@ -170,6 +208,7 @@ mod tests {
             "SHUTDOWN_GRACE_SECONDS",
             "MAX_UPLOAD_BYTES",
             "GRAPHQL_PORT",
+            "TRUSTED_PROXIES",
         ]);
 
         unsafe {
@@ -182,6 +221,7 @@ mod tests {
             env::set_var("SHUTDOWN_GRACE_SECONDS", "30");
             env::set_var("MAX_UPLOAD_BYTES", "2097152");
             env::set_var("GRAPHQL_PORT", "9099");
+            env::set_var("TRUSTED_PROXIES", "10.0.0.0/8, 172.16.0.10");
         }
 
         let cfg = SystemConfig::load_from_env().expect("should load custom values");
@@ -194,6 +234,14 @@ mod tests {
         assert_eq!(cfg.shutdown_grace_seconds, 30);
         assert_eq!(cfg.max_upload_bytes, 2 * 1024 * 1024);
         assert_eq!(cfg.graphql_port, 9099);
+        assert_eq!(
+            cfg.trusted_proxies,
+            vec![
+                "10.0.0.0/8".parse::<IpNet>().unwrap(),
+                "172.16.0.10/32".parse::<IpNet>().unwrap()
+            ]
+        );
+        clear_vars(&["TRUSTED_PROXIES"]);
     }
 
     #[test]
@@ -0,0 +1,137 @@
+//! Video hosting configuration loader.
+//!
+//! Selects the `VideoProvider` used for lesson videos and holds its
+//! credentials plus the domains allowed to embed our players.
+
+use std::env;
+
+use super::system_config::{app_env, is_local_env};
+
+/// Video hosting configuration derived from environment variables.
+///
+/// Prefer using `VideoConfig::load_from_env()` to initialize this struct.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VideoConfig {
+    /// Provider name: "vimeo" or "fake" (in-memory, never plays anything;
+    /// only allowed in development and test).
+    pub provider: String,
+    /// Vimeo access token with `upload`, `edit` and `private` scopes.
+    pub vimeo_access_token: String,
+    /// Vimeo API base URL.
+    pub vimeo_api_base: String,
+    /// Domains allowed to embed lesson videos.
+    pub embed_domains: Vec<String>,
+}
+
+impl VideoConfig {
+    /// Load video configuration from environment variables.
+    ///
+    /// Supported variables (with defaults):
+    /// - `VIDEO_PROVIDER` → default: "fake" when `APP_ENV` is "development"
+    ///   or "test", required otherwise; "fake" is refused outside those
+    /// - `VIMEO_ACCESS_TOKEN` → required when the provider is "vimeo"
+    /// - `VIMEO_API_BASE` → default: "https://api.vimeo.com"
+    /// - `VIDEO_EMBED_DOMAINS` → comma separated, default: "localhost"
+    pub fn load_from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        let _ = dotenv::dotenv().ok();
+
+        let app_env = app_env();
+        let provider = match env::var("VIDEO_PROVIDER") {
+            Ok(v) => v.trim().to_ascii_lowercase(),
+            Err(_) if is_local_env(&app_env) => "fake".to_string(),
+            Err(_) => return Err("VIDEO_PROVIDER is required outside development".into()),
+        };
+        let vimeo_access_token = env::var("VIMEO_ACCESS_TOKEN").unwrap_or_default();
+        let vimeo_api_base =
+            env::var("VIMEO_API_BASE").unwrap_or_else(|_| "https://api.vimeo.com".to_string());
+        let embed_domains = env::var("VIDEO_EMBED_DOMAINS")
+            .unwrap_or_else(|_| "localhost".to_string())
+            .split(',')
+            .map(|d| d.trim().to_string())
+            .filter(|d| !d.is_empty())
+            .collect();
+
+        match provider.as_str() {
+            "fake" if !is_local_env(&app_env) => {
+                return Err(format!(
+                    "VIDEO_PROVIDER=fake hosts no real videos and is not allowed when APP_ENV={}",
+                    app_env
+                )
+                .into());
+            }
+            "fake" => {}
+            "vimeo" if vimeo_access_token.is_empty() => {
+                return Err("VIMEO_ACCESS_TOKEN is required when VIDEO_PROVIDER=vimeo".into());
+            }
+            "vimeo" => {}
+            other => return Err(format!("Unsupported VIDEO_PROVIDER: {}", other).into()),
+        }
+
+        Ok(Self {
+            provider,
+            vimeo_access_token,
+            vimeo_api_base,
+            embed_domains,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    fn clear_vars() {
+        for k in [
+            "APP_ENV",
+            "VIDEO_PROVIDER",
+            "VIMEO_ACCESS_TOKEN",
+            "VIMEO_API_BASE",
+            "VIDEO_EMBED_DOMAINS",
+        ] {
+            unsafe {
+                env::remove_var(k);
+            }
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn loads_defaults_when_unset() {
+        clear_vars();
+        let cfg = VideoConfig::load_from_env().expect("should load defaults");
+        assert_eq!(cfg.provider, "fake");
+        assert_eq!(cfg.vimeo_api_base, "https://api.vimeo.com");
+        assert_eq!(cfg.embed_domains, ["localhost"]);
+    }
+
+    #[test]
+    #[serial]
+    fn fake_provider_is_refused_outside_development() {
+        clear_vars();
+        unsafe {
+            env::set_var("APP_ENV", "production");
+        }
+        let err = VideoConfig::load_from_env().unwrap_err();
+        assert!(format!("{}", err).contains("VIDEO_PROVIDER is required"));
+
+        unsafe {
+            env::set_var("VIDEO_PROVIDER", "fake");
+        }
+        let err = VideoConfig::load_from_env().unwrap_err();
+        assert!(format!("{}", err).contains("not allowed when APP_ENV=production"));
+
+        unsafe {
+            env::set_var("VIDEO_PROVIDER", "vimeo");
+            env::set_var("VIMEO_ACCESS_TOKEN", "token");
+        }
+        assert_eq!(VideoConfig::load_from_env().unwrap().provider, "vimeo");
+
+        unsafe {
+            env::set_var("APP_ENV", "test");
+            env::set_var("VIDEO_PROVIDER", "fake");
+        }
+        assert_eq!(VideoConfig::load_from_env().unwrap().provider, "fake");
+        clear_vars();
+    }
+}
@@ -2,7 +2,13 @@
 //!
 //! This module contains queries related to course management and course data.
 
-use async_graphql::{Object, Result as GraphQLResult};
+use std::sync::Arc;
+
+use async_graphql::{Context, Object, Result as GraphQLResult};
+
+use crate::applications::courses as service;
+use crate::configs::app_context::AppContext;
+use crate::types::course_types::CoursePriceList;
 
 /// Course queries for GraphQL
 pub struct CourseQueries;
@@ -14,4 +20,14 @@ impl CourseQueries {
     async fn placeholder(&self) -> GraphQLResult<String> {
         Ok("Course queries placeholder".to_string())
     }
+
+    /// Base price and price list of a course
+    async fn prices(
+        &self,
+        ctx: &Context<'_>,
+        course_id: uuid::Uuid,
+    ) -> GraphQLResult<CoursePriceList> {
+        let app_ctx = ctx.data::<Arc<AppContext>>()?;
+        Ok(service::list_course_prices(app_ctx.repos.courses.as_ref(), course_id).await?)
+    }
 }
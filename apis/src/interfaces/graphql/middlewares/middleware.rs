@@ -11,21 +11,31 @@ use tower_http::{compression::CompressionLayer, cors::CorsLayer, trace::TraceLay
 use crate::configs::app_context::AppContext;
 use crate::interfaces::graphql::handlers::{graphql_handler, graphql_playground};
 use crate::interfaces::graphql::schema::create_schema;
+use crate::interfaces::middlewares::axum_client_ip::client_ip as client_ip_mw;
 use crate::interfaces::middlewares::axum_error_handler::error_handler as error_handler_mw;
 use crate::interfaces::middlewares::axum_rate_limit::{
     RateLimitState, rate_limit as rate_limit_mw,
 };
 use crate::interfaces::middlewares::axum_request_logger::request_logger as request_logger_mw;
+use crate::pkg::client_ip::TrustedProxies;
+use crate::pkg::rate_limit::RateLimitPolicies;
 
 /// Configure and apply all middleware layers to the GraphQL router
 ///
-/// Sets up CORS, compression, tracing, rate limiting, request logging, and error handling
+/// Sets up client IP resolution, CORS, compression, tracing, rate limiting, request logging,
+/// and error handling
 /// middleware layers in the correct order for optimal performance and security.
-pub fn configure_middleware(app: Router, _ctx: Arc<AppContext>) -> Router {
+pub fn configure_middleware(app: Router, ctx: Arc<AppContext>) -> Router {
     let cors = CorsLayer::permissive();
     let trace = TraceLayer::new_for_http();
     let compression = CompressionLayer::new();
-    let _rate_limit_state = RateLimitState::new(100, 60);
+    let _rate_limit_state = RateLimitState::redis(
+        ctx.redis.clone(),
+        "rl:graphql",
+        RateLimitPolicies::single(100, 60),
+    )
+    .with_auth(ctx.jwt_service.clone());
+    let trusted_proxies = Arc::new(TrustedProxies::new(ctx.system.trusted_proxies.clone()));
 
     app.layer(
         ServiceBuilder::new()
@@ -39,6 +49,10 @@ pub fn configure_middleware(app: Router, _ctx: Arc<AppContext>) -> Router {
     )
     .layer(axum::middleware::from_fn(request_logger_mw))
     .layer(axum::middleware::from_fn(error_handler_mw))
+    .layer(axum::middleware::from_fn_with_state(
+        trusted_proxies,
+        client_ip_mw,
+    ))
 }
 
 /// Build the complete GraphQL application router with all routes and middleware
@@ -32,9 +32,13 @@ pub async fn start_server(
     ));
 
     // Start the server with graceful shutdown
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
-        .await?;
+    // Peer addresses feed the client IP middleware
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal())
+    .await?;
 
     info("GraphQL server shutdown complete".to_string());
     Ok(())
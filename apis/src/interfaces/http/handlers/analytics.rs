@@ -0,0 +1,123 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, Query},
+    http::StatusCode,
+};
+
+use crate::applications::analytics as service;
+use crate::configs::app_context::AppContext;
+use crate::pkg::Response;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::AppResult;
+use crate::types::analytics_types::{
+    AnalyticsQuery, CourseAnalytics, CurriculumAnalytics, InstructorAnalytics,
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/courses/:id/analytics",
+    params(
+        ("id" = uuid::Uuid, Path, description = "Course id"),
+        ("from" = Option<chrono::NaiveDate>, Query, description = "first day, UTC (default 29 days before to)"),
+        ("to" = Option<chrono::NaiveDate>, Query, description = "last day, UTC (default today)")
+    ),
+    responses(
+        (status = 200, description = "Enrollments, completions, views and revenue per day", body = CourseAnalytics),
+        (status = 400, description = "Invalid date range"),
+        (status = 403, description = "Only the course instructor can view its analytics"),
+        (status = 404, description = "Course not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Analytics"
+)]
+pub async fn get_course_analytics(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+    Query(q): Query<AnalyticsQuery>,
+) -> AppResult<Json<Response<CourseAnalytics>>> {
+    let report = service::get_course_analytics(
+        ctx.repos.courses.as_ref(),
+        ctx.repos.analytics.as_ref(),
+        &auth,
+        id,
+        q,
+    )
+    .await?;
+    Ok(Json(Response::with_data(
+        "Course analytics",
+        report,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/courses/:id/analytics/curriculum",
+    params(
+        ("id" = uuid::Uuid, Path, description = "Course id"),
+        ("from" = Option<chrono::NaiveDate>, Query, description = "first day, UTC (default 29 days before to)"),
+        ("to" = Option<chrono::NaiveDate>, Query, description = "last day, UTC (default today)")
+    ),
+    responses(
+        (status = 200, description = "Module completion funnel and lesson drop-off and quiz scores", body = CurriculumAnalytics),
+        (status = 400, description = "Invalid date range"),
+        (status = 403, description = "Only the course instructor can view its analytics"),
+        (status = 404, description = "Course not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Analytics"
+)]
+pub async fn get_curriculum_analytics(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+    Query(q): Query<AnalyticsQuery>,
+) -> AppResult<Json<Response<CurriculumAnalytics>>> {
+    let report = service::get_curriculum_analytics(
+        ctx.repos.courses.as_ref(),
+        ctx.repos.analytics.as_ref(),
+        &auth,
+        id,
+        q,
+    )
+    .await?;
+    Ok(Json(Response::with_data(
+        "Curriculum analytics",
+        report,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/instructors/me/analytics",
+    params(
+        ("from" = Option<chrono::NaiveDate>, Query, description = "first day, UTC (default 29 days before to)"),
+        ("to" = Option<chrono::NaiveDate>, Query, description = "last day, UTC (default today)")
+    ),
+    responses(
+        (status = 200, description = "Summaries of the courses the user teaches and their revenue", body = InstructorAnalytics),
+        (status = 400, description = "Invalid date range")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Analytics"
+)]
+pub async fn get_instructor_analytics(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Query(q): Query<AnalyticsQuery>,
+) -> AppResult<Json<Response<InstructorAnalytics>>> {
+    let report = service::get_instructor_analytics(ctx.repos.analytics.as_ref(), &auth, q).await?;
+    Ok(Json(Response::with_data(
+        "Instructor analytics",
+        report,
+        StatusCode::OK.as_u16(),
+    )))
+}
@@ -0,0 +1,84 @@
+use axum::{
+    Extension, Json,
+    extract::Path,
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
+
+use crate::applications::certificates as service;
+use crate::configs::app_context::AppContext;
+use crate::pkg::Response;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::AppResult;
+use crate::types::certificate_types::{Certificate, CertificateVerification};
+
+#[utoipa::path(
+    get,
+    path = "/api/certificates",
+    responses((status = 200, description = "Certificates held by the signed-in user", body = [Certificate])),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Certificates"
+)]
+pub async fn list_my_certificates(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+) -> AppResult<Json<Response<Vec<Certificate>>>> {
+    let certificates =
+        service::list_my_certificates(ctx.repos.certificates.as_ref(), &ctx.certificates, &auth)
+            .await?;
+    Ok(Json(Response::with_data(
+        "Certificates",
+        certificates,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/certificates/:code",
+    responses(
+        (status = 200, description = "The certificate is genuine", body = CertificateVerification),
+        (status = 404, description = "No certificate has this code")
+    ),
+    tag = "Certificates"
+)]
+pub async fn verify_certificate(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    Path(code): Path<String>,
+) -> AppResult<Json<Response<CertificateVerification>>> {
+    let verification = service::verify_certificate(ctx.repos.certificates.as_ref(), &code).await?;
+    Ok(Json(Response::with_data(
+        "Certificate is valid",
+        verification,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/certificates/:code/pdf",
+    responses(
+        (status = 200, description = "Printable certificate", content_type = "application/pdf"),
+        (status = 404, description = "No certificate has this code"),
+        (status = 409, description = "A name on the certificate uses characters the PDF fonts cannot print")
+    ),
+    tag = "Certificates"
+)]
+pub async fn download_certificate_pdf(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    Path(code): Path<String>,
+) -> AppResult<impl IntoResponse> {
+    let (code, bytes) =
+        service::render_certificate_pdf(ctx.repos.certificates.as_ref(), &ctx.certificates, &code)
+            .await?;
+    let disposition = format!("inline; filename=\"certificate-{}.pdf\"", code);
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        bytes,
+    ))
+}
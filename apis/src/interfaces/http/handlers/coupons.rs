@@ -0,0 +1,164 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, Query},
+    http::StatusCode,
+};
+
+use crate::applications::coupons as service;
+use crate::configs::app_context::AppContext;
+use crate::interfaces::http::handlers::orders::order_services;
+use crate::pkg::Response;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::AppResult;
+use crate::pkg::validators::ValidatedJson;
+use crate::types::coupon_types::{
+    Coupon, CouponPreview, CouponsQuery, CreateCouponRequest, UpdateCouponRequest,
+    ValidateCouponRequest,
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/coupons/validate",
+    request_body = ValidateCouponRequest,
+    responses(
+        (status = 200, description = "What checkout would charge with the coupon", body = CouponPreview),
+        (status = 400, description = "The coupon is inactive, outside its validity window or does not apply"),
+        (status = 404, description = "Coupon or course not found"),
+        (status = 409, description = "The coupon is used up")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Coupons"
+)]
+pub async fn validate_coupon(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    ValidatedJson(input): ValidatedJson<ValidateCouponRequest>,
+) -> AppResult<Json<Response<CouponPreview>>> {
+    let preview = service::validate_coupon(&order_services(&ctx), &auth, input).await?;
+    Ok(Json(Response::with_data(
+        "Coupon applies",
+        preview,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/coupons",
+    request_body = CreateCouponRequest,
+    responses(
+        (status = 201, description = "Coupon created", body = Coupon),
+        (status = 403, description = "Admin access required"),
+        (status = 409, description = "The code is taken")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Coupons"
+)]
+pub async fn create_coupon(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    ValidatedJson(input): ValidatedJson<CreateCouponRequest>,
+) -> AppResult<(StatusCode, Json<Response<Coupon>>)> {
+    let coupon = service::create_coupon(
+        ctx.repos.coupons.as_ref(),
+        ctx.repos.courses.as_ref(),
+        ctx.repos.categories.as_ref(),
+        &auth,
+        input,
+    )
+    .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(Response::with_data(
+            "Coupon created",
+            coupon,
+            StatusCode::CREATED.as_u16(),
+        )),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/coupons",
+    params(
+        ("limit" = Option<i64>, Query, description = "maximum number of coupons (default 50, max 200)")
+    ),
+    responses(
+        (status = 200, description = "Coupons, newest first", body = [Coupon]),
+        (status = 403, description = "Admin access required")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Coupons"
+)]
+pub async fn list_coupons(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Query(q): Query<CouponsQuery>,
+) -> AppResult<Json<Response<Vec<Coupon>>>> {
+    let coupons = service::list_coupons(ctx.repos.coupons.as_ref(), &auth, q.limit).await?;
+    Ok(Json(Response::with_data(
+        "Coupons",
+        coupons,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/coupons/:id",
+    responses(
+        (status = 200, description = "Coupon", body = Coupon),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Coupon not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Coupons"
+)]
+pub async fn get_coupon(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<Coupon>>> {
+    let coupon = service::get_coupon(ctx.repos.coupons.as_ref(), &auth, id).await?;
+    Ok(Json(Response::with_data(
+        "Coupon",
+        coupon,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/coupons/:id",
+    request_body = UpdateCouponRequest,
+    responses(
+        (status = 200, description = "Coupon updated", body = Coupon),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Coupon not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Coupons"
+)]
+pub async fn update_coupon(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+    ValidatedJson(input): ValidatedJson<UpdateCouponRequest>,
+) -> AppResult<Json<Response<Coupon>>> {
+    let coupon = service::update_coupon(ctx.repos.coupons.as_ref(), &auth, id, input).await?;
+    Ok(Json(Response::with_data(
+        "Coupon updated",
+        coupon,
+        StatusCode::OK.as_u16(),
+    )))
+}
@@ -0,0 +1,87 @@
+use axum::{
+    Extension, Json,
+    extract::Path,
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
+use axum_extra::extract::Multipart;
+
+use crate::applications::courses as service;
+use crate::configs::app_context::AppContext;
+use crate::pkg::Response;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::utils::multipart::MultipartForm;
+use crate::types::course_types::Course;
+
+#[utoipa::path(
+    get,
+    path = "/api/courses/:id/export",
+    responses(
+        (status = 200, description = "Course archive (manifest.json plus bundled media)", content_type = "application/zip"),
+        (status = 403, description = "Only the course instructor can export it")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn export_course(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> AppResult<impl IntoResponse> {
+    let (slug, bytes) = service::export_course(
+        ctx.repos.courses.as_ref(),
+        ctx.repos.modules.as_ref(),
+        ctx.repos.lesson_subtitles.as_ref(),
+        &auth,
+        id,
+        std::path::Path::new(&ctx.media.private_uploads_dir),
+    )
+    .await?;
+    let disposition = format!("attachment; filename=\"{}.zip\"", slug);
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        bytes,
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/courses/import",
+    request_body(content = String, description = "Multipart form: `file` (course archive) and optional `slug` override", content_type = "multipart/form-data"),
+    responses(
+        (status = 201, description = "Imported draft course", body = Course),
+        (status = 400, description = "Invalid archive or manifest"),
+        (status = 409, description = "Slug is already taken")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn import_course(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    multipart: Multipart,
+) -> AppResult<(StatusCode, Json<Response<Course>>)> {
+    let form = MultipartForm::parse_with_limit(multipart, ctx.system.max_upload_bytes).await?;
+    let file = form
+        .file("file")
+        .ok_or_else(|| AppError::BadRequest("file is required".into()))?;
+    let course = service::import_course(
+        ctx.repos.courses.as_ref(),
+        auth.user_id,
+        file.data.to_vec(),
+        form.text("slug").map(str::to_string),
+        std::path::Path::new(&ctx.media.private_uploads_dir),
+        ctx.system.max_upload_bytes,
+    )
+    .await?;
+    let body = Response::with_data("Course imported", course, StatusCode::CREATED.as_u16());
+    Ok((StatusCode::CREATED, Json(body)))
+}
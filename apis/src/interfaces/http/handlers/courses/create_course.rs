@@ -6,20 +6,22 @@ use crate::configs::app_context::AppContext;
 use crate::pkg::Response;
 use crate::pkg::auth::AuthUser;
 use crate::pkg::error::{AppError, AppResult};
-use crate::pkg::upload::{LocalFsStorage, Storage};
+use crate::pkg::money::Money;
 use crate::pkg::utils::multipart::MultipartForm;
 use crate::types::course_types::CreateCourseRequest;
 use tokio::fs;
 use validator::Validate;
 
+use super::save_thumbnail;
+
 /// Parsed fields for CreateCourse assembled from multipart form.
 struct ParsedCreateCourseFields {
     slug: String,
     title: String,
     description: String,
     excerpt: Option<String>,
-    price: f64,
-    original_price: Option<f64>,
+    price: Money,
+    original_price: Option<Money>,
     duration: String,
     featured: bool,
     status: Option<String>,
@@ -29,16 +31,22 @@ struct ParsedCreateCourseFields {
 }
 
 impl ParsedCreateCourseFields {
-    fn from_form(form: &MultipartForm) -> crate::pkg::error::AppResult<Self> {
+    /// Prices are decimal strings in the `currency` field's currency,
+    /// `default_currency` when it is absent
+    fn from_form(
+        form: &MultipartForm,
+        default_currency: &str,
+    ) -> crate::pkg::error::AppResult<Self> {
+        let currency = form.text("currency").unwrap_or(default_currency);
         Ok(Self {
             slug: form.required_text("slug")?.to_string(),
             title: form.required_text("title")?.to_string(),
             description: form.required_text("description")?.to_string(),
             excerpt: form.text("excerpt").map(|s| s.to_string()),
-            price: form.f64("price").ok_or_else(|| {
+            price: form.money("price", currency)?.ok_or_else(|| {
                 crate::pkg::error::AppError::BadRequest("price is required".into())
             })?,
-            original_price: form.f64("original_price"),
+            original_price: form.money("original_price", currency)?,
             duration: form.required_text("duration")?.to_string(),
             featured: form.bool("featured").unwrap_or(false),
             status: form.text("status").map(|s| s.to_string()),
@@ -66,22 +74,13 @@ pub async fn create_course(
 ) -> AppResult<(StatusCode, Json<Response<String>>)> {
     // Use universal multipart parser with configured upload limit
     let form = MultipartForm::parse_with_limit(multipart, ctx.system.max_upload_bytes).await?;
-    let parsed = ParsedCreateCourseFields::from_form(&form)?;
+    let parsed = ParsedCreateCourseFields::from_form(&form, &ctx.payments.currency)?;
 
     // File handling: support keys "thumbnail" or "file"
     let mut thumbnail_url: Option<String> = None;
-    let mut saved_disk_path: Option<std::path::PathBuf> = None; // Track for cleanup on failure
+    let mut saved_disk_paths: Vec<std::path::PathBuf> = Vec::new(); // Track for cleanup on failure
     if let Some(file) = form.file("thumbnail").or_else(|| form.file("file")) {
-        let storage = LocalFsStorage::new("./uploads/courses");
-        let relative_path = storage
-            .save_bytes(&file.data, file.file_name.as_deref())
-            .await?;
-
-        // Derive on-disk path from returned relative path and known storage root
-        if let Some(filename) = relative_path.rsplit('/').next() {
-            saved_disk_path = Some(std::path::Path::new("./uploads/courses").join(filename));
-        }
-
+        let relative_path = save_thumbnail(file, &mut saved_disk_paths).await?;
         let base_url = format!("http://{}:{}", ctx.system.api_host, ctx.system.api_port);
         thumbnail_url = Some(format!("{}{}", base_url, relative_path));
     }
@@ -104,7 +103,7 @@ pub async fn create_course(
 
     // Validate according to struct annotations; cleanup uploaded file on failure
     if let Err(e) = input.validate() {
-        if let Some(path) = saved_disk_path.as_ref() {
+        for path in &saved_disk_paths {
             let _ = fs::remove_file(path).await; // best-effort cleanup
         }
         return Err(AppError::BadRequest(e.to_string()));
@@ -117,7 +116,7 @@ pub async fn create_course(
             Ok((StatusCode::CREATED, Json(body)))
         }
         Err(err) => {
-            if let Some(path) = saved_disk_path.as_ref() {
+            for path in &saved_disk_paths {
                 let _ = fs::remove_file(path).await; // best-effort cleanup
             }
             Err(err)
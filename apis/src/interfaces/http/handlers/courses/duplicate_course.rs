@@ -0,0 +1,42 @@
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+
+use crate::applications::courses as service;
+use crate::configs::app_context::AppContext;
+use crate::pkg::Response;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::AppResult;
+use crate::pkg::validators::ValidatedJson;
+use crate::types::course_types::{Course, DuplicateCourseRequest};
+
+#[utoipa::path(
+    post,
+    path = "/api/courses/:id/duplicate",
+    request_body = DuplicateCourseRequest,
+    responses(
+        (status = 201, description = "Draft copy of the course", body = Course),
+        (status = 403, description = "Only the course instructor can duplicate it"),
+        (status = 409, description = "Requested slug is already taken")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn duplicate_course(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+    ValidatedJson(input): ValidatedJson<DuplicateCourseRequest>,
+) -> AppResult<(StatusCode, Json<Response<Course>>)> {
+    let course = service::duplicate_course(
+        ctx.repos.courses.as_ref(),
+        ctx.repos.modules.as_ref(),
+        &auth,
+        id,
+        input,
+        std::path::Path::new(&ctx.media.private_uploads_dir),
+    )
+    .await?;
+    let body = Response::with_data("Course duplicated", course, StatusCode::CREATED.as_u16());
+    Ok((StatusCode::CREATED, Json(body)))
+}
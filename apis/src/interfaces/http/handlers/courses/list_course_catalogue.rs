@@ -0,0 +1,38 @@
+use axum::{Extension, Json, extract::Query, http::StatusCode};
+
+use crate::applications::courses as service;
+use crate::configs::app_context::AppContext;
+use crate::pkg::Response;
+use crate::pkg::error::AppResult;
+use crate::types::course_types::{Course, CourseCatalogQuery, Page};
+
+#[utoipa::path(
+    get,
+    path = "/api/courses",
+    params(
+        ("page" = Option<i64>, Query, description = "1-based page"),
+        ("per_page" = Option<i64>, Query, description = "items per page (max 100)"),
+        ("category_id" = Option<i32>, Query, description = "category id"),
+        ("tags" = Option<String>, Query, description = "comma-separated tags, any match"),
+        ("currency" = Option<String>, Query, description = "ISO 4217 currency the price filters and sort use (default: store currency)"),
+        ("min_price" = Option<i64>, Query, description = "minimum price in minor units of the currency"),
+        ("max_price" = Option<i64>, Query, description = "maximum price in minor units of the currency"),
+        ("free" = Option<bool>, Query, description = "only free courses"),
+        ("featured" = Option<bool>, Query, description = "featured flag"),
+        ("instructor_id" = Option<uuid::Uuid>, Query, description = "instructor id"),
+        ("min_rating" = Option<f64>, Query, description = "minimum average rating (0-5)"),
+        ("sort" = Option<String>, Query, description = "newest (default), popular, price_asc, price_desc, top_rated")
+    ),
+    responses((status = 200, description = "Published courses", body = Page<Course>)),
+    tag = "Courses"
+)]
+pub async fn list_course_catalogue(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    Query(q): Query<CourseCatalogQuery>,
+) -> AppResult<(StatusCode, Json<Response<Page<Course>>>)> {
+    let data =
+        service::list_course_catalogue(ctx.repos.courses.as_ref(), q, &ctx.payments.currency)
+            .await?;
+    let body = Response::with_data("Courses", data, StatusCode::OK.as_u16());
+    Ok((StatusCode::OK, Json(body)))
+}
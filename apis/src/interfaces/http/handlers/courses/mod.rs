@@ -1,13 +1,88 @@
+pub mod archive;
 pub mod create_course;
 pub mod delete_course;
+pub mod duplicate_course;
 pub mod get_course_by_id;
 pub mod get_course_by_slug;
+pub mod list_course_catalogue;
 pub mod list_courses_by_instructor_paginated;
+pub mod package_import;
+pub mod prerequisites;
+pub mod pricing;
+pub mod publishing;
+pub mod reviews;
+pub mod revisions;
 pub mod update_course;
 
+pub use archive::{export_course, import_course};
 pub use create_course::create_course;
 pub use delete_course::delete_course;
+pub use duplicate_course::duplicate_course;
 pub use get_course_by_id::get_course_by_id;
 pub use get_course_by_slug::get_course_by_slug;
+pub use list_course_catalogue::list_course_catalogue;
 pub use list_courses_by_instructor_paginated::list_courses_by_instructor_paginated;
+pub use package_import::import_course_package;
+pub use prerequisites::{list_course_prerequisites, set_course_prerequisites};
+pub use pricing::{delete_course_price, list_course_prices, set_course_price};
+pub use publishing::{
+    archive_course, cancel_scheduled_publish, publish_course, schedule_course_publish,
+    unpublish_course,
+};
+pub use reviews::{
+    approve_course_review, create_course_review, delete_course_review, delete_course_review_reply,
+    list_course_reviews, list_pending_course_reviews, mark_course_review_helpful,
+    reject_course_review, reply_to_course_review, unmark_course_review_helpful,
+    update_course_review,
+};
+pub use revisions::{
+    diff_course_revision, discard_course_revision, get_open_course_revision,
+    publish_course_revision, start_course_revision, update_course_revision,
+};
 pub use update_course::update_course;
+
+use crate::pkg::error::AppResult;
+use crate::pkg::image_processing::{self, ImageKind};
+use crate::pkg::upload::{LocalFsStorage, Storage};
+use crate::pkg::utils::multipart::MultipartFile;
+
+/// On-disk root for course thumbnails (served under `/uploads/courses`).
+const COURSE_UPLOADS_DIR: &str = "./uploads/courses";
+
+/// Store an uploaded course thumbnail and return the relative path to use.
+///
+/// Raster images are resized into WebP thumbnail variants with metadata
+/// stripped, and the largest variant is returned; other formats (e.g. SVG)
+/// are stored as-is. Every file written is pushed onto `saved` so callers can
+/// clean up if the course write fails.
+pub(crate) async fn save_thumbnail(
+    file: &MultipartFile,
+    saved: &mut Vec<std::path::PathBuf>,
+) -> AppResult<String> {
+    let storage = LocalFsStorage::new(COURSE_UPLOADS_DIR);
+    let root = std::path::Path::new(COURSE_UPLOADS_DIR);
+
+    let processable = file
+        .content_type
+        .as_deref()
+        .is_some_and(image_processing::is_processable);
+    if !processable {
+        let relative_path = storage
+            .save_bytes(&file.data, file.file_name.as_deref())
+            .await?;
+        // Derive on-disk path from returned relative path and known storage root
+        if let Some(filename) = relative_path.rsplit('/').next() {
+            saved.push(root.join(filename));
+        }
+        return Ok(relative_path);
+    }
+
+    let variants =
+        image_processing::process_image_blocking(file.data.clone(), ImageKind::Thumbnail).await?;
+    let stored = image_processing::store_variants(&storage, &variants).await?;
+    saved.extend(stored.iter().map(|v| root.join(&v.file_name)));
+    stored
+        .last()
+        .map(|v| v.path.clone())
+        .ok_or_else(|| crate::pkg::error::AppError::Internal("No thumbnail variants".into()))
+}
@@ -0,0 +1,56 @@
+use axum::{Extension, Json, http::StatusCode};
+use axum_extra::extract::Multipart;
+
+use crate::applications::courses as service;
+use crate::configs::app_context::AppContext;
+use crate::pkg::Response;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::utils::multipart::MultipartForm;
+use crate::types::course_types::PackageImportResult;
+
+#[utoipa::path(
+    post,
+    path = "/api/courses/import/package",
+    request_body(content = String, description = "Multipart form: `file` (SCORM 1.2/2004 or Common Cartridge ZIP), optional `slug` and optional `instructor_id` (defaults to the caller)", content_type = "multipart/form-data"),
+    responses(
+        (status = 201, description = "Imported draft course and the import log of skipped items", body = PackageImportResult),
+        (status = 400, description = "Not a valid package"),
+        (status = 403, description = "Admin access required"),
+        (status = 409, description = "Slug is already taken"),
+        (status = 422, description = "Nothing in the package could be imported")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn import_course_package(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    multipart: Multipart,
+) -> AppResult<(StatusCode, Json<Response<PackageImportResult>>)> {
+    // Package pages are served from our origin, so only staff may import them
+    auth.require_admin()?;
+    let form = MultipartForm::parse_with_limit(multipart, ctx.system.max_upload_bytes).await?;
+    let file = form
+        .file("file")
+        .ok_or_else(|| AppError::BadRequest("file is required".into()))?;
+    let instructor_id = match form.text("instructor_id") {
+        Some(id) => id
+            .parse::<uuid::Uuid>()
+            .map_err(|_| AppError::BadRequest("instructor_id must be a UUID".into()))?,
+        None => auth.user_id,
+    };
+    let result = service::import_package(
+        ctx.repos.courses.as_ref(),
+        instructor_id,
+        file.data.to_vec(),
+        form.text("slug").map(str::to_string),
+        &ctx.payments.currency,
+        ctx.system.max_upload_bytes,
+    )
+    .await?;
+    let body = Response::with_data("Package imported", result, StatusCode::CREATED.as_u16());
+    Ok((StatusCode::CREATED, Json(body)))
+}
@@ -0,0 +1,60 @@
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+
+use crate::applications::courses as service;
+use crate::configs::app_context::AppContext;
+use crate::pkg::Response;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::AppResult;
+use crate::pkg::validators::ValidatedJson;
+use crate::types::course_types::{CoursePrerequisite, SetCoursePrerequisitesRequest};
+
+#[utoipa::path(
+    get,
+    path = "/api/courses/:id/prerequisites",
+    responses(
+        (status = 200, description = "Courses to complete before this course's lessons open", body = [CoursePrerequisite]),
+        (status = 404, description = "Course not found")
+    ),
+    tag = "Courses"
+)]
+pub async fn list_course_prerequisites(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    Path(id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<Vec<CoursePrerequisite>>>> {
+    let items = service::list_course_prerequisites(ctx.repos.courses.as_ref(), id).await?;
+    Ok(Json(Response::with_data(
+        "Course prerequisites",
+        items,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/courses/:id/prerequisites",
+    request_body = SetCoursePrerequisitesRequest,
+    responses(
+        (status = 200, description = "Prerequisites replaced", body = [CoursePrerequisite]),
+        (status = 400, description = "A course is listed twice or would end up requiring itself"),
+        (status = 403, description = "Not the course instructor"),
+        (status = 404, description = "Course not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn set_course_prerequisites(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+    ValidatedJson(input): ValidatedJson<SetCoursePrerequisitesRequest>,
+) -> AppResult<Json<Response<Vec<CoursePrerequisite>>>> {
+    let items =
+        service::set_course_prerequisites(ctx.repos.courses.as_ref(), &auth, id, input).await?;
+    Ok(Json(Response::with_data(
+        "Course prerequisites updated",
+        items,
+        StatusCode::OK.as_u16(),
+    )))
+}
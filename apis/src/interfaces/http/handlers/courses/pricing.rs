@@ -0,0 +1,86 @@
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+
+use crate::applications::courses as service;
+use crate::configs::app_context::AppContext;
+use crate::pkg::Response;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::AppResult;
+use crate::pkg::validators::ValidatedJson;
+use crate::types::course_types::{CoursePriceList, SetCoursePriceRequest};
+
+#[utoipa::path(
+    get,
+    path = "/api/courses/:id/prices",
+    responses(
+        (status = 200, description = "Base price and price list", body = CoursePriceList),
+        (status = 404, description = "Course not found")
+    ),
+    tag = "Courses"
+)]
+pub async fn list_course_prices(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    Path(id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<CoursePriceList>>> {
+    let prices = service::list_course_prices(ctx.repos.courses.as_ref(), id).await?;
+    Ok(Json(Response::with_data(
+        "Course prices",
+        prices,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/courses/:id/prices/:currency",
+    request_body = SetCoursePriceRequest,
+    responses(
+        (status = 200, description = "Price set; returns the updated price list", body = CoursePriceList),
+        (status = 400, description = "Unknown currency, the base currency, or an original price below the price"),
+        (status = 403, description = "Not the course instructor")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn set_course_price(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path((id, currency)): Path<(uuid::Uuid, String)>,
+    ValidatedJson(input): ValidatedJson<SetCoursePriceRequest>,
+) -> AppResult<Json<Response<CoursePriceList>>> {
+    let prices =
+        service::set_course_price(ctx.repos.courses.as_ref(), &auth, id, &currency, input).await?;
+    Ok(Json(Response::with_data(
+        "Course price set",
+        prices,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/courses/:id/prices/:currency",
+    responses(
+        (status = 200, description = "Price removed; returns the updated price list", body = CoursePriceList),
+        (status = 403, description = "Not the course instructor"),
+        (status = 404, description = "The course has no price in this currency")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn delete_course_price(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path((id, currency)): Path<(uuid::Uuid, String)>,
+) -> AppResult<Json<Response<CoursePriceList>>> {
+    let prices =
+        service::delete_course_price(ctx.repos.courses.as_ref(), &auth, id, &currency).await?;
+    Ok(Json(Response::with_data(
+        "Course price removed",
+        prices,
+        StatusCode::OK.as_u16(),
+    )))
+}
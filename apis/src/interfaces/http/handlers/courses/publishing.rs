@@ -0,0 +1,140 @@
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+
+use crate::applications::courses as service;
+use crate::configs::app_context::AppContext;
+use crate::pkg::Response;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::AppResult;
+use crate::pkg::validators::ValidatedJson;
+use crate::types::course_types::{Course, ScheduleCoursePublishRequest};
+
+#[utoipa::path(
+    post,
+    path = "/api/courses/:id/publish",
+    responses(
+        (status = 200, description = "Published course", body = Course),
+        (status = 400, description = "Course is not ready to be published"),
+        (status = 409, description = "Course cannot be published from its current status")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn publish_course(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<Course>>> {
+    let course = service::publish_course(ctx.repos.courses.as_ref(), &auth, id).await?;
+    Ok(Json(Response::with_data(
+        "Course published",
+        course,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/courses/:id/unpublish",
+    responses(
+        (status = 200, description = "Course moved back to draft", body = Course),
+        (status = 409, description = "Course is not published")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn unpublish_course(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<Course>>> {
+    let course = service::unpublish_course(ctx.repos.courses.as_ref(), &auth, id).await?;
+    Ok(Json(Response::with_data(
+        "Course unpublished",
+        course,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/courses/:id/archive",
+    responses(
+        (status = 200, description = "Archived course", body = Course),
+        (status = 409, description = "Course is already archived")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn archive_course(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<Course>>> {
+    let course = service::archive_course(ctx.repos.courses.as_ref(), &auth, id).await?;
+    Ok(Json(Response::with_data(
+        "Course archived",
+        course,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/courses/:id/schedule",
+    request_body = ScheduleCoursePublishRequest,
+    responses(
+        (status = 200, description = "Draft scheduled for publishing", body = Course),
+        (status = 400, description = "Time is not in the future or course is not ready"),
+        (status = 409, description = "Only drafts can be scheduled")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn schedule_course_publish(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+    ValidatedJson(input): ValidatedJson<ScheduleCoursePublishRequest>,
+) -> AppResult<Json<Response<Course>>> {
+    let course =
+        service::schedule_course_publish(ctx.repos.courses.as_ref(), &auth, id, input.publish_at)
+            .await?;
+    Ok(Json(Response::with_data(
+        "Course publish scheduled",
+        course,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/courses/:id/schedule",
+    responses(
+        (status = 200, description = "Pending publish cancelled", body = Course),
+        (status = 409, description = "Course is not a draft")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn cancel_scheduled_publish(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<Course>>> {
+    let course = service::cancel_scheduled_publish(ctx.repos.courses.as_ref(), &auth, id).await?;
+    Ok(Json(Response::with_data(
+        "Scheduled publish cancelled",
+        course,
+        StatusCode::OK.as_u16(),
+    )))
+}
@@ -0,0 +1,350 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, Query},
+    http::StatusCode,
+};
+
+use crate::applications::courses as service;
+use crate::configs::app_context::AppContext;
+use crate::pkg::Response;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::AppResult;
+use crate::pkg::validators::ValidatedJson;
+use crate::types::course_review_types::{
+    CourseReview, CourseReviewPage, CourseReviewReplyRequest, CourseReviewsQuery,
+    CreateCourseReviewRequest, PendingCourseReviewsQuery, UpdateCourseReviewRequest,
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/courses/:id/reviews",
+    params(
+        ("page" = Option<i64>, Query, description = "1-based page"),
+        ("per_page" = Option<i64>, Query, description = "items per page (max 50)"),
+        ("sort" = Option<String>, Query, description = "newest (default), helpful, highest, lowest")
+    ),
+    responses(
+        (status = 200, description = "Approved reviews and rating summary", body = CourseReviewPage),
+        (status = 404, description = "Course not found or not published")
+    ),
+    tag = "Courses"
+)]
+pub async fn list_course_reviews(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    Path(id): Path<uuid::Uuid>,
+    Query(q): Query<CourseReviewsQuery>,
+) -> AppResult<Json<Response<CourseReviewPage>>> {
+    let page = service::list_course_reviews(
+        ctx.repos.courses.as_ref(),
+        ctx.repos.course_reviews.as_ref(),
+        id,
+        q,
+    )
+    .await?;
+    Ok(Json(Response::with_data(
+        "Course reviews",
+        page,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/courses/:id/reviews",
+    request_body = CreateCourseReviewRequest,
+    responses(
+        (status = 201, description = "Review submitted for moderation", body = CourseReview),
+        (status = 403, description = "Not enrolled in the course"),
+        (status = 409, description = "Already reviewed this course")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn create_course_review(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+    ValidatedJson(input): ValidatedJson<CreateCourseReviewRequest>,
+) -> AppResult<(StatusCode, Json<Response<CourseReview>>)> {
+    let review = service::create_course_review(
+        ctx.repos.courses.as_ref(),
+        ctx.repos.enrollments.as_ref(),
+        ctx.repos.course_reviews.as_ref(),
+        &auth,
+        id,
+        input,
+    )
+    .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(Response::with_data(
+            "Review submitted for moderation",
+            review,
+            StatusCode::CREATED.as_u16(),
+        )),
+    ))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/courses/reviews/:review_id",
+    request_body = UpdateCourseReviewRequest,
+    responses(
+        (status = 200, description = "Review updated and sent back to moderation", body = CourseReview),
+        (status = 403, description = "Not the author of the review")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn update_course_review(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(review_id): Path<uuid::Uuid>,
+    ValidatedJson(input): ValidatedJson<UpdateCourseReviewRequest>,
+) -> AppResult<Json<Response<CourseReview>>> {
+    let review =
+        service::update_course_review(ctx.repos.course_reviews.as_ref(), &auth, review_id, input)
+            .await?;
+    Ok(Json(Response::with_data(
+        "Review updated",
+        review,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/courses/reviews/:review_id",
+    responses(
+        (status = 200, description = "Review deleted"),
+        (status = 403, description = "Not the author of the review")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn delete_course_review(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(review_id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<()>>> {
+    service::delete_course_review(ctx.repos.course_reviews.as_ref(), &auth, review_id).await?;
+    Ok(Json(Response::with_data(
+        "Review deleted",
+        (),
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/courses/reviews/:review_id/reply",
+    request_body = CourseReviewReplyRequest,
+    responses(
+        (status = 200, description = "Reply saved", body = CourseReview),
+        (status = 403, description = "Not the course instructor")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn reply_to_course_review(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(review_id): Path<uuid::Uuid>,
+    ValidatedJson(input): ValidatedJson<CourseReviewReplyRequest>,
+) -> AppResult<Json<Response<CourseReview>>> {
+    let review = service::reply_to_course_review(
+        ctx.repos.courses.as_ref(),
+        ctx.repos.course_reviews.as_ref(),
+        &auth,
+        review_id,
+        input,
+    )
+    .await?;
+    Ok(Json(Response::with_data(
+        "Reply saved",
+        review,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/courses/reviews/:review_id/reply",
+    responses(
+        (status = 200, description = "Reply removed", body = CourseReview),
+        (status = 403, description = "Not the course instructor")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn delete_course_review_reply(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(review_id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<CourseReview>>> {
+    let review = service::delete_course_review_reply(
+        ctx.repos.courses.as_ref(),
+        ctx.repos.course_reviews.as_ref(),
+        &auth,
+        review_id,
+    )
+    .await?;
+    Ok(Json(Response::with_data(
+        "Reply removed",
+        review,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/courses/reviews/:review_id/helpful",
+    responses(
+        (status = 200, description = "Review marked helpful", body = CourseReview),
+        (status = 400, description = "Cannot vote on your own review")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn mark_course_review_helpful(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(review_id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<CourseReview>>> {
+    let review = service::vote_course_review_helpful(
+        ctx.repos.course_reviews.as_ref(),
+        &auth,
+        review_id,
+        true,
+    )
+    .await?;
+    Ok(Json(Response::with_data(
+        "Review marked helpful",
+        review,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/courses/reviews/:review_id/helpful",
+    responses((status = 200, description = "Helpful vote removed", body = CourseReview)),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn unmark_course_review_helpful(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(review_id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<CourseReview>>> {
+    let review = service::vote_course_review_helpful(
+        ctx.repos.course_reviews.as_ref(),
+        &auth,
+        review_id,
+        false,
+    )
+    .await?;
+    Ok(Json(Response::with_data(
+        "Helpful vote removed",
+        review,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/courses/reviews/pending",
+    params(
+        ("limit" = Option<i64>, Query, description = "maximum number of reviews (default 50, max 200)")
+    ),
+    responses(
+        (status = 200, description = "Reviews awaiting moderation, oldest first", body = [CourseReview]),
+        (status = 403, description = "Admin access required")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn list_pending_course_reviews(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Query(q): Query<PendingCourseReviewsQuery>,
+) -> AppResult<Json<Response<Vec<CourseReview>>>> {
+    let reviews =
+        service::list_pending_course_reviews(ctx.repos.course_reviews.as_ref(), &auth, q.limit)
+            .await?;
+    Ok(Json(Response::with_data(
+        "Pending reviews",
+        reviews,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/courses/reviews/:review_id/approve",
+    responses(
+        (status = 200, description = "Review approved", body = CourseReview),
+        (status = 403, description = "Admin access required")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn approve_course_review(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(review_id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<CourseReview>>> {
+    let review =
+        service::moderate_course_review(ctx.repos.course_reviews.as_ref(), &auth, review_id, true)
+            .await?;
+    Ok(Json(Response::with_data(
+        "Review approved",
+        review,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/courses/reviews/:review_id/reject",
+    responses(
+        (status = 200, description = "Review rejected", body = CourseReview),
+        (status = 403, description = "Admin access required")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn reject_course_review(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(review_id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<CourseReview>>> {
+    let review =
+        service::moderate_course_review(ctx.repos.course_reviews.as_ref(), &auth, review_id, false)
+            .await?;
+    Ok(Json(Response::with_data(
+        "Review rejected",
+        review,
+        StatusCode::OK.as_u16(),
+    )))
+}
@@ -0,0 +1,206 @@
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+
+use crate::applications::courses as service;
+use crate::configs::app_context::AppContext;
+use crate::pkg::Response;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::AppResult;
+use crate::pkg::validators::ValidatedJson;
+use crate::types::course_revision_types::{
+    CourseRevision, CourseRevisionContent, CourseRevisionDiff,
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/courses/:id/revisions",
+    responses(
+        (status = 201, description = "Draft revision created from the live course", body = CourseRevision),
+        (status = 200, description = "The course's already open draft revision", body = CourseRevision),
+        (status = 409, description = "Course is not published")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn start_course_revision(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> AppResult<(StatusCode, Json<Response<CourseRevision>>)> {
+    let (revision, created) = service::start_course_revision(
+        ctx.repos.courses.as_ref(),
+        ctx.repos.modules.as_ref(),
+        ctx.repos.course_revisions.as_ref(),
+        &auth,
+        id,
+    )
+    .await?;
+    let (status, message) = if created {
+        (StatusCode::CREATED, "Draft revision created")
+    } else {
+        (StatusCode::OK, "Draft revision already open")
+    };
+    let body = Response::with_data(message, revision, status.as_u16());
+    Ok((status, Json(body)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/courses/:id/revisions/draft",
+    responses(
+        (status = 200, description = "Open draft revision", body = CourseRevision),
+        (status = 404, description = "No open draft")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn get_open_course_revision(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<CourseRevision>>> {
+    let revision = service::get_open_course_revision(
+        ctx.repos.courses.as_ref(),
+        ctx.repos.course_revisions.as_ref(),
+        &auth,
+        id,
+    )
+    .await?;
+    Ok(Json(Response::with_data(
+        "Draft revision",
+        revision,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/courses/:id/revisions/:revision_id",
+    request_body = CourseRevisionContent,
+    responses(
+        (status = 200, description = "Draft content replaced", body = CourseRevision),
+        (status = 409, description = "Revision is no longer a draft")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn update_course_revision(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path((id, revision_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    ValidatedJson(input): ValidatedJson<CourseRevisionContent>,
+) -> AppResult<Json<Response<CourseRevision>>> {
+    let revision = service::update_course_revision(
+        ctx.repos.courses.as_ref(),
+        ctx.repos.course_revisions.as_ref(),
+        &auth,
+        id,
+        revision_id,
+        input,
+    )
+    .await?;
+    Ok(Json(Response::with_data(
+        "Draft revision updated",
+        revision,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/courses/:id/revisions/:revision_id/diff",
+    responses((status = 200, description = "Changes the revision makes to the live course", body = CourseRevisionDiff)),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn diff_course_revision(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path((id, revision_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+) -> AppResult<Json<Response<CourseRevisionDiff>>> {
+    let diff = service::diff_course_revision(
+        ctx.repos.courses.as_ref(),
+        ctx.repos.modules.as_ref(),
+        ctx.repos.course_revisions.as_ref(),
+        &auth,
+        id,
+        revision_id,
+    )
+    .await?;
+    Ok(Json(Response::with_data(
+        "Revision diff",
+        diff,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/courses/:id/revisions/:revision_id/publish",
+    responses(
+        (status = 200, description = "Revision is now the live course content", body = CourseRevision),
+        (status = 409, description = "Revision is not a draft or the live course changed since it was taken")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn publish_course_revision(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path((id, revision_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+) -> AppResult<Json<Response<CourseRevision>>> {
+    let revision = service::publish_course_revision(
+        ctx.repos.courses.as_ref(),
+        ctx.repos.course_revisions.as_ref(),
+        &auth,
+        id,
+        revision_id,
+    )
+    .await?;
+    Ok(Json(Response::with_data(
+        "Revision published",
+        revision,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/courses/:id/revisions/:revision_id",
+    responses(
+        (status = 200, description = "Draft revision discarded", body = CourseRevision),
+        (status = 409, description = "Revision is no longer a draft")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn discard_course_revision(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path((id, revision_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+) -> AppResult<Json<Response<CourseRevision>>> {
+    let revision = service::discard_course_revision(
+        ctx.repos.courses.as_ref(),
+        ctx.repos.course_revisions.as_ref(),
+        &auth,
+        id,
+        revision_id,
+    )
+    .await?;
+    Ok(Json(Response::with_data(
+        "Draft revision discarded",
+        revision,
+        StatusCode::OK.as_u16(),
+    )))
+}
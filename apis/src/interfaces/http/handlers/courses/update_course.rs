@@ -5,12 +5,13 @@ use crate::applications::courses as service;
 use crate::configs::app_context::AppContext;
 use crate::pkg::Response;
 use crate::pkg::error::{AppError, AppResult};
-use crate::pkg::upload::{LocalFsStorage, Storage};
 use crate::pkg::utils::multipart::MultipartForm;
 use crate::types::course_types::{Course, UpdateCourseRequest};
 use tokio::fs;
 use validator::Validate;
 
+use super::save_thumbnail;
+
 #[utoipa::path(
     patch,
     path = "/api/courses/:id",
@@ -31,8 +32,22 @@ pub async fn update_course(
     let title = form.text("title").map(|s| s.to_string());
     let description = form.text("description").map(|s| s.to_string());
     let excerpt = form.text("excerpt").map(|s| s.to_string());
-    let price = form.f64("price");
-    let original_price = form.f64("original_price");
+    // Prices are decimal strings in the `currency` field's currency, else the course's own
+    let currency = match form.text("currency") {
+        Some(currency) => currency.to_string(),
+        None if form.text("price").is_some() || form.text("original_price").is_some() => {
+            ctx.repos
+                .courses
+                .find_by_id(id)
+                .await?
+                .ok_or_else(|| AppError::NotFound("Course not found".into()))?
+                .price
+                .currency
+        }
+        None => String::new(),
+    };
+    let price = form.money("price", &currency)?;
+    let original_price = form.money("original_price", &currency)?;
     let duration = form.text("duration").map(|s| s.to_string());
     let lessons = form.text("lessons").and_then(|v| v.parse::<i32>().ok());
     let featured = form.bool("featured");
@@ -43,19 +58,10 @@ pub async fn update_course(
 
     // Thumbnail can be provided as a file or as a direct URL via "thumbnail_url"
     let mut thumbnail: Option<String> = form.text("thumbnail_url").map(|s| s.to_string());
-    let mut saved_disk_path: Option<std::path::PathBuf> = None; // Track for cleanup on failure
+    let mut saved_disk_paths: Vec<std::path::PathBuf> = Vec::new(); // Track for cleanup on failure
     if thumbnail.is_none() {
         if let Some(file) = form.file("thumbnail").or_else(|| form.file("file")) {
-            let storage = LocalFsStorage::new("./uploads/courses");
-            let relative_path = storage
-                .save_bytes(&file.data, file.file_name.as_deref())
-                .await?;
-
-            // Compute on-disk path for cleanup if the update fails
-            if let Some(filename) = relative_path.rsplit('/').next() {
-                saved_disk_path = Some(std::path::Path::new("./uploads/courses").join(filename));
-            }
-
+            let relative_path = save_thumbnail(file, &mut saved_disk_paths).await?;
             let base_url = format!("http://{}:{}", ctx.system.api_host, ctx.system.api_port);
             thumbnail = Some(format!("{}{}", base_url, relative_path));
         }
@@ -80,7 +86,7 @@ pub async fn update_course(
 
     // Validate; delete uploaded file if validation fails
     if let Err(e) = input.validate() {
-        if let Some(path) = saved_disk_path.as_ref() {
+        for path in &saved_disk_paths {
             let _ = fs::remove_file(path).await;
         }
         return Err(AppError::BadRequest(e.to_string()));
@@ -93,7 +99,7 @@ pub async fn update_course(
             Ok((StatusCode::OK, Json(body)))
         }
         Err(err) => {
-            if let Some(path) = saved_disk_path.as_ref() {
+            for path in &saved_disk_paths {
                 let _ = fs::remove_file(path).await;
             }
             Err(err)
@@ -0,0 +1,334 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, Query},
+    http::StatusCode,
+};
+
+use crate::applications::learning_paths::{self as service, PathServices};
+use crate::configs::app_context::AppContext;
+use crate::interfaces::http::handlers::orders::order_services;
+use crate::pkg::Response;
+use crate::pkg::auth::{AuthUser, OptionalAuthUser};
+use crate::pkg::error::AppResult;
+use crate::pkg::validators::ValidatedJson;
+use crate::types::course_types::Page;
+use crate::types::learning_path_types::{
+    CreateLearningPathRequest, LearningPath, LearningPathDetail, LearningPathsQuery,
+    PathEnrollmentResponse, PathProgress, SetPathCoursesRequest, UpdateLearningPathRequest,
+};
+
+/// Path services backed by the app's repositories and payment provider
+fn path_services(ctx: &AppContext) -> PathServices<'_> {
+    PathServices {
+        paths: ctx.repos.learning_paths.as_ref(),
+        orders: order_services(ctx),
+        certificates: ctx.repos.certificates.as_ref(),
+        progress: ctx.repos.lesson_progress.as_ref(),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/paths",
+    params(
+        ("page" = Option<i64>, Query, description = "1-based page"),
+        ("per_page" = Option<i64>, Query, description = "items per page (max 100)"),
+        ("status" = Option<String>, Query, description = "admins only: draft, published (default), archived or all")
+    ),
+    responses((status = 200, description = "Learning paths, newest first", body = Page<LearningPath>)),
+    tag = "Learning Paths"
+)]
+pub async fn list_learning_paths(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    OptionalAuthUser(auth): OptionalAuthUser,
+    Query(q): Query<LearningPathsQuery>,
+) -> AppResult<Json<Response<Page<LearningPath>>>> {
+    let page =
+        service::list_learning_paths(ctx.repos.learning_paths.as_ref(), auth.as_ref(), q).await?;
+    Ok(Json(Response::with_data(
+        "OK",
+        page,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/paths",
+    request_body = CreateLearningPathRequest,
+    responses(
+        (status = 201, description = "Draft path created", body = LearningPath),
+        (status = 403, description = "Admin access required"),
+        (status = 409, description = "The slug is taken")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Learning Paths"
+)]
+pub async fn create_learning_path(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    ValidatedJson(input): ValidatedJson<CreateLearningPathRequest>,
+) -> AppResult<(StatusCode, Json<Response<LearningPath>>)> {
+    let path =
+        service::create_learning_path(ctx.repos.learning_paths.as_ref(), &auth, input).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(Response::with_data(
+            "Learning path created",
+            path,
+            StatusCode::CREATED.as_u16(),
+        )),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/paths/:id",
+    responses(
+        (status = 200, description = "Path with its courses in order", body = LearningPathDetail),
+        (status = 404, description = "Learning path not found")
+    ),
+    tag = "Learning Paths"
+)]
+pub async fn get_learning_path(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    OptionalAuthUser(auth): OptionalAuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<LearningPathDetail>>> {
+    let detail = service::get_learning_path(
+        ctx.repos.learning_paths.as_ref(),
+        ctx.repos.courses.as_ref(),
+        auth.as_ref(),
+        id,
+    )
+    .await?;
+    Ok(Json(Response::with_data(
+        "OK",
+        detail,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/path/:slug",
+    responses(
+        (status = 200, description = "Path with its courses in order", body = LearningPathDetail),
+        (status = 404, description = "Learning path not found")
+    ),
+    tag = "Learning Paths"
+)]
+pub async fn get_learning_path_by_slug(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    OptionalAuthUser(auth): OptionalAuthUser,
+    Path(slug): Path<String>,
+) -> AppResult<Json<Response<LearningPathDetail>>> {
+    let detail = service::get_learning_path_by_slug(
+        ctx.repos.learning_paths.as_ref(),
+        ctx.repos.courses.as_ref(),
+        auth.as_ref(),
+        &slug,
+    )
+    .await?;
+    Ok(Json(Response::with_data(
+        "OK",
+        detail,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/paths/:id",
+    request_body = UpdateLearningPathRequest,
+    responses(
+        (status = 200, description = "Path updated", body = LearningPath),
+        (status = 400, description = "A published path needs published courses sold in its currency"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Learning path not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Learning Paths"
+)]
+pub async fn update_learning_path(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+    ValidatedJson(input): ValidatedJson<UpdateLearningPathRequest>,
+) -> AppResult<Json<Response<LearningPath>>> {
+    let path = service::update_learning_path(
+        ctx.repos.learning_paths.as_ref(),
+        ctx.repos.courses.as_ref(),
+        &auth,
+        id,
+        input,
+    )
+    .await?;
+    Ok(Json(Response::with_data(
+        "Learning path updated",
+        path,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/paths/:id",
+    responses(
+        (status = 200, description = "Deleted"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Learning path not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Learning Paths"
+)]
+pub async fn delete_learning_path(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<()>>> {
+    service::delete_learning_path(ctx.repos.learning_paths.as_ref(), &auth, id).await?;
+    Ok(Json(Response::with_message(
+        "Deleted",
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/paths/:id/courses",
+    request_body = SetPathCoursesRequest,
+    responses(
+        (status = 200, description = "Courses replaced, in the order given", body = LearningPathDetail),
+        (status = 400, description = "A course is listed twice, is a prerequisite, or cannot be sold in a published path"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Learning path or course not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Learning Paths"
+)]
+pub async fn set_learning_path_courses(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+    ValidatedJson(input): ValidatedJson<SetPathCoursesRequest>,
+) -> AppResult<Json<Response<LearningPathDetail>>> {
+    let detail = service::set_learning_path_courses(
+        ctx.repos.learning_paths.as_ref(),
+        ctx.repos.courses.as_ref(),
+        &auth,
+        id,
+        input,
+    )
+    .await?;
+    Ok(Json(Response::with_data(
+        "Courses updated",
+        detail,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/paths/:id/prerequisites",
+    request_body = SetPathCoursesRequest,
+    responses(
+        (status = 200, description = "Prerequisites replaced", body = LearningPathDetail),
+        (status = 400, description = "A course is listed twice or belongs to the path"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Learning path or course not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Learning Paths"
+)]
+pub async fn set_learning_path_prerequisites(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+    ValidatedJson(input): ValidatedJson<SetPathCoursesRequest>,
+) -> AppResult<Json<Response<LearningPathDetail>>> {
+    let detail = service::set_learning_path_prerequisites(
+        ctx.repos.learning_paths.as_ref(),
+        ctx.repos.courses.as_ref(),
+        &auth,
+        id,
+        input,
+    )
+    .await?;
+    Ok(Json(Response::with_data(
+        "Prerequisites updated",
+        detail,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/paths/:id/enroll",
+    responses(
+        (status = 201, description = "Enrolled, or an order to pay for the path", body = PathEnrollmentResponse),
+        (status = 400, description = "A course of the path cannot be bought"),
+        (status = 403, description = "Prerequisites are not completed"),
+        (status = 404, description = "Learning path not found"),
+        (status = 409, description = "Already enrolled in the path")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Learning Paths"
+)]
+pub async fn enroll_in_learning_path(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> AppResult<(StatusCode, Json<Response<PathEnrollmentResponse>>)> {
+    let result = service::enroll_in_learning_path(&path_services(&ctx), &auth, id).await?;
+    let message = if result.enrollment.is_some() {
+        "Enrolled in learning path"
+    } else {
+        "Order created"
+    };
+    Ok((
+        StatusCode::CREATED,
+        Json(Response::with_data(
+            message,
+            result,
+            StatusCode::CREATED.as_u16(),
+        )),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/paths/:id/progress",
+    responses(
+        (status = 200, description = "Progress through the path's courses", body = PathProgress),
+        (status = 404, description = "Learning path not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Learning Paths"
+)]
+pub async fn get_learning_path_progress(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<PathProgress>>> {
+    let progress = service::get_learning_path_progress(&path_services(&ctx), &auth, id).await?;
+    Ok(Json(Response::with_data(
+        "OK",
+        progress,
+        StatusCode::OK.as_u16(),
+    )))
+}
@@ -1,5 +1,6 @@
 use axum::{Extension, Json, extract::Path, http::StatusCode};
 
+use crate::applications::lessons::access::ensure_lesson_editable;
 use crate::applications::lessons::assignment as service;
 use crate::configs::app_context::AppContext;
 use crate::pkg::Response;
@@ -11,7 +12,10 @@ use crate::types::course_types::{LessonAssignment, UpsertLessonAssignmentRequest
     put,
     path = "/api/lessons/:lesson_id/assignment",
     request_body = UpsertLessonAssignmentRequest,
-    responses((status = 200, description = "Upsert assignment", body = uuid::Uuid)),
+    responses(
+        (status = 200, description = "Upsert assignment", body = uuid::Uuid),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
     tag = "Lessons"
 )]
 pub async fn upsert_assignment(
@@ -19,6 +23,7 @@ pub async fn upsert_assignment(
     Path(_lesson_id): Path<uuid::Uuid>,
     ValidatedJson(input): ValidatedJson<UpsertLessonAssignmentRequest>,
 ) -> AppResult<Json<Response<uuid::Uuid>>> {
+    ensure_lesson_editable(ctx.repos.lessons.as_ref(), input.lesson_id).await?;
     let id = service::upsert(ctx.repos.lesson_assignments.as_ref(), input).await?;
     let body = Response::with_data("OK", id, StatusCode::OK.as_u16());
     Ok(Json(body))
@@ -42,13 +47,17 @@ pub async fn get_assignment(
 #[utoipa::path(
     delete,
     path = "/api/lessons/:lesson_id/assignment",
-    responses((status = 200, description = "Delete assignment")),
+    responses(
+        (status = 200, description = "Delete assignment"),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
     tag = "Lessons"
 )]
 pub async fn delete_assignment(
     Extension(ctx): Extension<std::sync::Arc<AppContext>>,
     Path(lesson_id): Path<uuid::Uuid>,
 ) -> AppResult<Json<Response<()>>> {
+    ensure_lesson_editable(ctx.repos.lessons.as_ref(), lesson_id).await?;
     service::delete(ctx.repos.lesson_assignments.as_ref(), lesson_id).await?;
     let body = Response::with_message("Deleted", StatusCode::OK.as_u16());
     Ok(Json(body))
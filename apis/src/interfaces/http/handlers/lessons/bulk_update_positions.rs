@@ -1,6 +1,7 @@
 use axum::{Extension, Json, http::StatusCode};
 
 use crate::applications::lessons as service;
+use crate::applications::lessons::access::ensure_module_editable;
 use crate::configs::app_context::AppContext;
 use crate::pkg::Response;
 use crate::pkg::error::AppResult;
@@ -11,7 +12,10 @@ use crate::types::course_types::{BulkUpdateLessonPositionsRequest, Lesson};
     patch,
     path = "/api/modules/{module_id}/lessons/positions",
     request_body = BulkUpdateLessonPositionsRequest,
-    responses((status = 200, description = "Updated lesson positions", body = Vec<Lesson>)),
+    responses(
+        (status = 200, description = "Updated lesson positions", body = Vec<Lesson>),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
     security(
         ("bearerAuth" = [])
     ),
@@ -21,6 +25,7 @@ pub async fn bulk_update_lesson_positions(
     Extension(ctx): Extension<std::sync::Arc<AppContext>>,
     ValidatedJson(input): ValidatedJson<BulkUpdateLessonPositionsRequest>,
 ) -> AppResult<(StatusCode, Json<Response<Vec<Lesson>>>)> {
+    ensure_module_editable(ctx.repos.lessons.as_ref(), input.module_id).await?;
     let lessons = service::bulk_update_lesson_positions(ctx.repos.lessons.as_ref(), input).await?;
 
     let body = Response::with_data("Updated lesson positions", lessons, StatusCode::OK.as_u16());
@@ -1,8 +1,11 @@
 use axum::{Extension, Json, extract::Path, http::StatusCode};
 
+use crate::applications::lessons::access::{ensure_can_view_lesson, ensure_lesson_editable};
 use crate::applications::lessons::contents as service;
 use crate::configs::app_context::AppContext;
+use crate::interfaces::http::handlers::lessons::lesson_gate;
 use crate::pkg::Response;
+use crate::pkg::auth::OptionalAuthUser;
 use crate::pkg::error::AppResult;
 use crate::pkg::validators::ValidatedJson;
 use crate::types::course_types::{
@@ -13,7 +16,10 @@ use crate::types::course_types::{
     post,
     path = "/api/lessons/:lesson_id/contents",
     request_body = CreateLessonContentRequest,
-    responses((status = 201, description = "Created lesson content", body = uuid::Uuid)),
+    responses(
+        (status = 201, description = "Created lesson content", body = uuid::Uuid),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
     security(
         ("bearerAuth" = [])
     ),
@@ -24,6 +30,7 @@ pub async fn create_lesson_content(
     Path(_lesson_id): Path<uuid::Uuid>,
     ValidatedJson(input): ValidatedJson<CreateLessonContentRequest>,
 ) -> AppResult<(StatusCode, Json<Response<uuid::Uuid>>)> {
+    ensure_lesson_editable(ctx.repos.lessons.as_ref(), input.lesson_id).await?;
     let id = service::create_content(ctx.repos.lesson_contents.as_ref(), input).await?;
     let body = Response::with_data("Lesson content created", id, StatusCode::CREATED.as_u16());
     Ok((StatusCode::CREATED, Json(body)))
@@ -32,13 +39,20 @@ pub async fn create_lesson_content(
 #[utoipa::path(
     get,
     path = "/api/lessons/:lesson_id/contents",
-    responses((status = 200, description = "List lesson contents", body = [LessonContent])),
+    responses(
+        (status = 200, description = "List lesson contents", body = [LessonContent]),
+        (status = 401, description = "Sign in to access a paid lesson"),
+        (status = 403, description = "Not enrolled, or the lesson is not released yet"),
+        (status = 404, description = "Lesson not found or not published")
+    ),
     tag = "Lessons"
 )]
 pub async fn list_lesson_contents(
     Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    OptionalAuthUser(auth): OptionalAuthUser,
     Path(lesson_id): Path<uuid::Uuid>,
 ) -> AppResult<Json<Response<Vec<LessonContent>>>> {
+    ensure_can_view_lesson(&lesson_gate(&ctx), lesson_id, auth.as_ref()).await?;
     let items = service::list_contents(ctx.repos.lesson_contents.as_ref(), lesson_id).await?;
     let body = Response::with_data("OK", items, StatusCode::OK.as_u16());
     Ok(Json(body))
@@ -48,7 +62,10 @@ pub async fn list_lesson_contents(
     patch,
     path = "/api/lesson-contents/:id",
     request_body = UpdateLessonContentRequest,
-    responses((status = 200, description = "Updated lesson content", body = LessonContent)),
+    responses(
+        (status = 200, description = "Updated lesson content", body = LessonContent),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
     tag = "Lessons"
 )]
 pub async fn update_lesson_content(
@@ -56,6 +73,8 @@ pub async fn update_lesson_content(
     Path(id): Path<uuid::Uuid>,
     ValidatedJson(input): ValidatedJson<UpdateLessonContentRequest>,
 ) -> AppResult<Json<Response<Option<LessonContent>>>> {
+    let lesson_id = service::content_lesson_id(ctx.repos.lesson_contents.as_ref(), id).await?;
+    ensure_lesson_editable(ctx.repos.lessons.as_ref(), lesson_id).await?;
     let updated = service::update_content(ctx.repos.lesson_contents.as_ref(), id, input).await?;
     let body = Response::with_data("OK", updated, StatusCode::OK.as_u16());
     Ok(Json(body))
@@ -64,14 +83,24 @@ pub async fn update_lesson_content(
 #[utoipa::path(
     delete,
     path = "/api/lesson-contents/:id",
-    responses((status = 200, description = "Deleted")),
+    responses(
+        (status = 200, description = "Deleted"),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
     tag = "Lessons"
 )]
 pub async fn delete_lesson_content(
     Extension(ctx): Extension<std::sync::Arc<AppContext>>,
     Path(id): Path<uuid::Uuid>,
 ) -> AppResult<Json<Response<()>>> {
-    service::delete_content(ctx.repos.lesson_contents.as_ref(), id).await?;
+    let lesson_id = service::content_lesson_id(ctx.repos.lesson_contents.as_ref(), id).await?;
+    ensure_lesson_editable(ctx.repos.lessons.as_ref(), lesson_id).await?;
+    service::delete_content(
+        ctx.repos.lesson_contents.as_ref(),
+        std::path::Path::new(&ctx.media.private_uploads_dir),
+        id,
+    )
+    .await?;
     let body = Response::with_message("Deleted", StatusCode::OK.as_u16());
     Ok(Json(body))
 }
@@ -1,6 +1,7 @@
 use axum::{Extension, Json, extract::Path, http::StatusCode};
 
 use crate::applications::lessons as service;
+use crate::applications::lessons::access::ensure_module_editable;
 use crate::configs::app_context::AppContext;
 use crate::pkg::Response;
 use crate::pkg::error::AppResult;
@@ -11,7 +12,10 @@ use crate::types::course_types::CreateLessonRequest;
     post,
     path = "/api/modules/:module_id/lessons",
     request_body = CreateLessonRequest,
-    responses((status = 201, description = "Created lesson", body = uuid::Uuid)),
+    responses(
+        (status = 201, description = "Created lesson", body = uuid::Uuid),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
     security(
         ("bearerAuth" = [])
     ),
@@ -22,6 +26,7 @@ pub async fn create_lesson(
     Path(_module_id): Path<uuid::Uuid>,
     ValidatedJson(input): ValidatedJson<CreateLessonRequest>,
 ) -> AppResult<(StatusCode, Json<Response<uuid::Uuid>>)> {
+    ensure_module_editable(ctx.repos.lessons.as_ref(), input.module_id).await?;
     let id = service::create_lesson(ctx.repos.lessons.as_ref(), input).await?;
     let body = Response::with_data("Lesson created", id, StatusCode::CREATED.as_u16());
     Ok((StatusCode::CREATED, Json(body)))
@@ -1,6 +1,7 @@
 use axum::{Extension, Json, extract::Path, http::StatusCode};
 
 use crate::applications::lessons as service;
+use crate::applications::lessons::access::ensure_lesson_editable;
 use crate::configs::app_context::AppContext;
 use crate::pkg::Response;
 use crate::pkg::error::AppResult;
@@ -8,7 +9,10 @@ use crate::pkg::error::AppResult;
 #[utoipa::path(
     delete,
     path = "/api/lessons/:id",
-    responses((status = 200, description = "Deleted")),
+    responses(
+        (status = 200, description = "Deleted"),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
     security(
         ("bearerAuth" = [])
     ),
@@ -18,6 +22,7 @@ pub async fn delete_lesson(
     Extension(ctx): Extension<std::sync::Arc<AppContext>>,
     Path(id): Path<uuid::Uuid>,
 ) -> AppResult<(StatusCode, Json<Response<serde_json::Value>>)> {
+    ensure_lesson_editable(ctx.repos.lessons.as_ref(), id).await?;
     service::delete_lesson(ctx.repos.lessons.as_ref(), id).await?;
     let body = Response::with_data(
         "Deleted",
@@ -2,14 +2,19 @@ use axum::{Extension, Json, extract::Path, http::StatusCode};
 
 use crate::applications::lessons as service;
 use crate::configs::app_context::AppContext;
+use crate::interfaces::http::handlers::lessons::lesson_gate;
 use crate::pkg::Response;
+use crate::pkg::auth::OptionalAuthUser;
 use crate::pkg::error::AppResult;
 use crate::types::course_types::Lesson;
 
 #[utoipa::path(
     get,
     path = "/api/modules/:module_id/lessons",
-    responses((status = 200, description = "Lessons", body = [Lesson])),
+    responses(
+        (status = 200, description = "Lessons; those not released to the caller are locked and without content", body = [Lesson]),
+        (status = 404, description = "Module not found")
+    ),
     security(
         ("bearerAuth" = [])
     ),
@@ -17,9 +22,11 @@ use crate::types::course_types::Lesson;
 )]
 pub async fn list_lessons(
     Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    OptionalAuthUser(auth): OptionalAuthUser,
     Path(module_id): Path<uuid::Uuid>,
 ) -> AppResult<(StatusCode, Json<Response<Vec<Lesson>>>)> {
-    let items = service::list_lessons_by_module(ctx.repos.lessons.as_ref(), module_id).await?;
+    let items =
+        service::list_lessons_by_module(&lesson_gate(&ctx), module_id, auth.as_ref()).await?;
     let body = Response::with_data("Lessons", items, StatusCode::OK.as_u16());
     Ok((StatusCode::OK, Json(body)))
 }
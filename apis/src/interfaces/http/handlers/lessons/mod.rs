@@ -4,8 +4,12 @@ pub mod contents;
 pub mod create_lesson;
 pub mod delete_lesson;
 pub mod list_lessons;
+pub mod private_contents;
+pub mod progress;
 pub mod questions;
+pub mod subtitles;
 pub mod update_lesson;
+pub mod video;
 
 pub use assignment::*;
 pub use bulk_update_positions::bulk_update_lesson_positions;
@@ -13,5 +17,21 @@ pub use contents::*;
 pub use create_lesson::create_lesson;
 pub use delete_lesson::delete_lesson;
 pub use list_lessons::list_lessons;
+pub use private_contents::*;
+pub use progress::{complete_lesson, get_course_progress, submit_quiz_attempt};
 pub use questions::*;
+pub use subtitles::*;
 pub use update_lesson::update_lesson;
+pub use video::*;
+
+use crate::applications::lessons::access::LessonGate;
+use crate::configs::app_context::AppContext;
+
+/// Lesson access checks backed by the app's repositories
+pub(crate) fn lesson_gate(ctx: &AppContext) -> LessonGate<'_> {
+    LessonGate {
+        lessons: ctx.repos.lessons.as_ref(),
+        enrollments: ctx.repos.enrollments.as_ref(),
+        courses: ctx.repos.courses.as_ref(),
+    }
+}
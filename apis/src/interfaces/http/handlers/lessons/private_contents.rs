@@ -0,0 +1,174 @@
+use std::path::Path as FsPath;
+
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+use axum_extra::extract::Multipart;
+
+use crate::applications::lessons::access::{ensure_can_manage_lesson, ensure_lesson_editable};
+use crate::applications::lessons::private_contents::{self as service, PrivateContentInput};
+use crate::configs::app_context::AppContext;
+use crate::interfaces::http::handlers::lessons::lesson_gate;
+use crate::interfaces::http::handlers::media::upload::is_allowed_content_type;
+use crate::pkg::Response;
+use crate::pkg::auth::{AuthUser, OptionalAuthUser};
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::private_media::{self, UrlSigner};
+use crate::types::course_types::LessonContentDownload;
+
+#[utoipa::path(
+    post,
+    path = "/api/lessons/:lesson_id/contents/private",
+    responses(
+        (status = 201, description = "Uploaded private lesson content", body = uuid::Uuid),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Lessons"
+)]
+pub async fn upload_private_lesson_content(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(lesson_id): Path<uuid::Uuid>,
+    mut multipart: Multipart,
+) -> AppResult<(StatusCode, Json<Response<uuid::Uuid>>)> {
+    ensure_can_manage_lesson(ctx.repos.lessons.as_ref(), lesson_id, &auth).await?;
+    ensure_lesson_editable(ctx.repos.lessons.as_ref(), lesson_id).await?;
+
+    let root = FsPath::new(&ctx.media.private_uploads_dir);
+    let mut key: Option<String> = None;
+    let mut file_name: Option<String> = None;
+    let mut content_type: Option<String> = None;
+    let mut title: Option<String> = None;
+    let mut position: Option<String> = None;
+
+    let parsed: AppResult<()> = async {
+        while let Some(field) = multipart
+            .next_field()
+            .await
+            .map_err(|e| AppError::BadRequest(e.to_string()))?
+        {
+            match field.name() {
+                Some("file") if key.is_none() => {
+                    let ct = field
+                        .content_type()
+                        .map(|s| s.to_string())
+                        .unwrap_or_else(|| "application/octet-stream".to_string());
+                    if !is_allowed_content_type(&ct) {
+                        return Err(AppError::BadRequest("File type not allowed".into()));
+                    }
+                    file_name = field.file_name().map(|s| s.to_string());
+                    content_type = Some(ct);
+                    let saved = private_media::save_lesson_file(
+                        root,
+                        field,
+                        file_name.as_deref(),
+                        ctx.system.max_upload_bytes,
+                    )
+                    .await?;
+                    key = Some(saved);
+                }
+                Some("title") => {
+                    title = Some(field.text().await.map_err(|e| AppError::BadRequest(e.to_string()))?)
+                }
+                Some("position") => {
+                    position =
+                        Some(field.text().await.map_err(|e| AppError::BadRequest(e.to_string()))?)
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+    .await;
+
+    let result = async {
+        parsed?;
+        let key = key
+            .clone()
+            .ok_or_else(|| AppError::BadRequest("Missing file field".into()))?;
+        let title = title
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| AppError::BadRequest("title is required".into()))?;
+        let position = match position.as_deref().map(str::trim) {
+            Some(p) => p
+                .parse::<i32>()
+                .ok()
+                .filter(|p| *p >= 0)
+                .ok_or_else(|| AppError::BadRequest("position must be a non-negative integer".into()))?,
+            None => 0,
+        };
+        let file_size = tokio::fs::metadata(private_media::resolve_key(root, &key)?)
+            .await
+            .ok()
+            .map(|m| m.len() as i64);
+
+        service::create_private_content(
+            ctx.repos.lesson_contents.as_ref(),
+            PrivateContentInput {
+                lesson_id,
+                title,
+                content_type: content_type.clone().unwrap_or_default(),
+                key,
+                file_size,
+                filename: file_name.clone(),
+                position,
+            },
+        )
+        .await
+    }
+    .await;
+
+    match result {
+        Ok(id) => {
+            let body = Response::with_data(
+                "Private lesson content uploaded",
+                id,
+                StatusCode::CREATED.as_u16(),
+            );
+            Ok((StatusCode::CREATED, Json(body)))
+        }
+        Err(e) => {
+            // Never leave orphaned files behind when the row was not created
+            if let Some(k) = key.as_deref() {
+                private_media::remove_key(root, k).await;
+            }
+            Err(e)
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/lesson-contents/:id/download-url",
+    responses(
+        (status = 200, description = "Short-lived download link", body = LessonContentDownload),
+        (status = 401, description = "Sign in required"),
+        (status = 403, description = "Not enrolled"),
+        (status = 404, description = "Not found")
+    ),
+    security(
+        (),
+        ("bearerAuth" = [])
+    ),
+    tag = "Lessons"
+)]
+pub async fn get_lesson_content_download_url(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    OptionalAuthUser(auth): OptionalAuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<LessonContentDownload>>> {
+    let signer = UrlSigner::new(&ctx.media.url_signing_secret);
+    let link = service::issue_download_url(
+        &lesson_gate(&ctx),
+        ctx.repos.lesson_contents.as_ref(),
+        &signer,
+        ctx.media.signed_url_ttl_seconds,
+        id,
+        auth.as_ref(),
+    )
+    .await?;
+    let body = Response::with_data("OK", link, StatusCode::OK.as_u16());
+    Ok(Json(body))
+}
@@ -0,0 +1,117 @@
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+
+use crate::applications::certificates::CertificateIssuer;
+use crate::applications::lessons::progress::{self as service, ProgressServices};
+use crate::configs::app_context::AppContext;
+use crate::pkg::Response;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::AppResult;
+use crate::pkg::validators::ValidatedJson;
+use crate::types::certificate_types::{
+    CourseProgress, QuizAttemptResult, SubmitQuizAttemptRequest,
+};
+
+/// Progress tracking services backed by the app's repositories
+pub(crate) fn progress_services(ctx: &AppContext) -> ProgressServices<'_> {
+    ProgressServices {
+        lessons: ctx.repos.lessons.as_ref(),
+        enrollments: ctx.repos.enrollments.as_ref(),
+        courses: ctx.repos.courses.as_ref(),
+        questions: ctx.repos.lesson_questions.as_ref(),
+        progress: ctx.repos.lesson_progress.as_ref(),
+        issuer: CertificateIssuer {
+            courses: ctx.repos.courses.as_ref(),
+            users: ctx.repos.users.as_ref(),
+            certificates: ctx.repos.certificates.as_ref(),
+            config: &ctx.certificates,
+        },
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/lessons/:lesson_id/complete",
+    responses(
+        (status = 200, description = "Lesson completed; includes the certificate once the course is done", body = CourseProgress),
+        (status = 400, description = "The lesson's quiz has not been passed"),
+        (status = 403, description = "Not enrolled in the course")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Lessons"
+)]
+pub async fn complete_lesson(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(lesson_id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<CourseProgress>>> {
+    let progress = service::complete_lesson(&progress_services(&ctx), &auth, lesson_id).await?;
+    Ok(Json(Response::with_data(
+        "Lesson completed",
+        progress,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/lessons/:lesson_id/quiz/attempts",
+    request_body = SubmitQuizAttemptRequest,
+    responses(
+        (status = 201, description = "Graded attempt; a pass completes the lesson", body = QuizAttemptResult),
+        (status = 400, description = "The lesson has no quiz"),
+        (status = 403, description = "Not enrolled in the course")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Lessons"
+)]
+pub async fn submit_quiz_attempt(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(lesson_id): Path<uuid::Uuid>,
+    ValidatedJson(input): ValidatedJson<SubmitQuizAttemptRequest>,
+) -> AppResult<(StatusCode, Json<Response<QuizAttemptResult>>)> {
+    let result =
+        service::submit_quiz_attempt(&progress_services(&ctx), &auth, lesson_id, input).await?;
+    let message = if result.passed {
+        "Quiz passed"
+    } else {
+        "Quiz not passed"
+    };
+    Ok((
+        StatusCode::CREATED,
+        Json(Response::with_data(
+            message,
+            result,
+            StatusCode::CREATED.as_u16(),
+        )),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/courses/:id/progress",
+    responses(
+        (status = 200, description = "Signed-in learner's progress and certificate", body = CourseProgress),
+        (status = 403, description = "Not enrolled in the course")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn get_course_progress(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<CourseProgress>>> {
+    let progress = service::get_course_progress(&progress_services(&ctx), &auth, id).await?;
+    Ok(Json(Response::with_data(
+        "Course progress",
+        progress,
+        StatusCode::OK.as_u16(),
+    )))
+}
@@ -1,8 +1,11 @@
 use axum::{Extension, Json, extract::Path, http::StatusCode};
 
+use crate::applications::lessons::access::{ensure_can_view_lesson, ensure_lesson_editable};
 use crate::applications::lessons::questions as service;
 use crate::configs::app_context::AppContext;
+use crate::interfaces::http::handlers::lessons::lesson_gate;
 use crate::pkg::Response;
+use crate::pkg::auth::OptionalAuthUser;
 use crate::pkg::error::AppResult;
 use crate::pkg::validators::ValidatedJson;
 use crate::types::course_types::{CreateLessonQuestionRequest, CreateQuestionOptionRequest, LessonQuestion, QuestionOption, UpdateLessonQuestionRequest, UpdateQuestionOptionRequest};
@@ -12,7 +15,10 @@ use crate::types::course_types::{CreateLessonQuestionRequest, CreateQuestionOpti
     post,
     path = "/api/lessons/:lesson_id/questions",
     request_body = CreateLessonQuestionRequest,
-    responses((status = 201, description = "Created question", body = uuid::Uuid)),
+    responses(
+        (status = 201, description = "Created question", body = uuid::Uuid),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
     tag = "Lessons"
 )]
 pub async fn create_question(
@@ -20,6 +26,7 @@ pub async fn create_question(
     Path(_lesson_id): Path<uuid::Uuid>,
     ValidatedJson(input): ValidatedJson<CreateLessonQuestionRequest>,
 ) -> AppResult<(StatusCode, Json<Response<uuid::Uuid>>)> {
+    ensure_lesson_editable(ctx.repos.lessons.as_ref(), input.lesson_id).await?;
     let id = service::create_question(ctx.repos.lesson_questions.as_ref(), input).await?;
     let body = Response::with_data("Created", id, StatusCode::CREATED.as_u16());
     Ok((StatusCode::CREATED, Json(body)))
@@ -28,13 +35,20 @@ pub async fn create_question(
 #[utoipa::path(
     get,
     path = "/api/lessons/:lesson_id/questions",
-    responses((status = 200, description = "List questions", body = [LessonQuestion])),
+    responses(
+        (status = 200, description = "List questions", body = [LessonQuestion]),
+        (status = 401, description = "Sign in to access a paid lesson"),
+        (status = 403, description = "Not enrolled, or the lesson is not released yet"),
+        (status = 404, description = "Lesson not found or not published")
+    ),
     tag = "Lessons"
 )]
 pub async fn list_questions(
     Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    OptionalAuthUser(auth): OptionalAuthUser,
     Path(lesson_id): Path<uuid::Uuid>,
 ) -> AppResult<Json<Response<Vec<LessonQuestion>>>> {
+    ensure_can_view_lesson(&lesson_gate(&ctx), lesson_id, auth.as_ref()).await?;
     let items = service::list_questions(ctx.repos.lesson_questions.as_ref(), lesson_id).await?;
     let body = Response::with_data("OK", items, StatusCode::OK.as_u16());
     Ok(Json(body))
@@ -44,7 +58,10 @@ pub async fn list_questions(
     patch,
     path = "/api/lesson-questions/:id",
     request_body = UpdateLessonQuestionRequest,
-    responses((status = 200, description = "Updated question", body = LessonQuestion)),
+    responses(
+        (status = 200, description = "Updated question", body = LessonQuestion),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
     tag = "Lessons"
 )]
 pub async fn update_question(
@@ -52,6 +69,8 @@ pub async fn update_question(
     Path(id): Path<uuid::Uuid>,
     ValidatedJson(input): ValidatedJson<UpdateLessonQuestionRequest>,
 ) -> AppResult<Json<Response<Option<LessonQuestion>>>> {
+    let lesson_id = service::question_lesson_id(ctx.repos.lesson_questions.as_ref(), id).await?;
+    ensure_lesson_editable(ctx.repos.lessons.as_ref(), lesson_id).await?;
     let updated = service::update_question(ctx.repos.lesson_questions.as_ref(), id, input).await?;
     let body = Response::with_data("OK", updated, StatusCode::OK.as_u16());
     Ok(Json(body))
@@ -60,13 +79,18 @@ pub async fn update_question(
 #[utoipa::path(
     delete,
     path = "/api/lesson-questions/:id",
-    responses((status = 200, description = "Deleted question")),
+    responses(
+        (status = 200, description = "Deleted question"),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
     tag = "Lessons"
 )]
 pub async fn delete_question(
     Extension(ctx): Extension<std::sync::Arc<AppContext>>,
     Path(id): Path<uuid::Uuid>,
 ) -> AppResult<Json<Response<()>>> {
+    let lesson_id = service::question_lesson_id(ctx.repos.lesson_questions.as_ref(), id).await?;
+    ensure_lesson_editable(ctx.repos.lessons.as_ref(), lesson_id).await?;
     service::delete_question(ctx.repos.lesson_questions.as_ref(), id).await?;
     let body = Response::with_message("Deleted", StatusCode::OK.as_u16());
     Ok(Json(body))
@@ -77,7 +101,10 @@ pub async fn delete_question(
     post,
     path = "/api/lesson-questions/:question_id/options",
     request_body = CreateQuestionOptionRequest,
-    responses((status = 201, description = "Created option", body = uuid::Uuid)),
+    responses(
+        (status = 201, description = "Created option", body = uuid::Uuid),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
     tag = "Lessons"
 )]
 pub async fn create_option(
@@ -85,6 +112,9 @@ pub async fn create_option(
     Path(_question_id): Path<uuid::Uuid>,
     ValidatedJson(input): ValidatedJson<CreateQuestionOptionRequest>,
 ) -> AppResult<(StatusCode, Json<Response<uuid::Uuid>>)> {
+    let lesson_id =
+        service::question_lesson_id(ctx.repos.lesson_questions.as_ref(), input.question_id).await?;
+    ensure_lesson_editable(ctx.repos.lessons.as_ref(), lesson_id).await?;
     let id = service::create_option(ctx.repos.lesson_questions.as_ref(), input).await?;
     let body = Response::with_data("Created", id, StatusCode::CREATED.as_u16());
     Ok((StatusCode::CREATED, Json(body)))
@@ -93,14 +123,26 @@ pub async fn create_option(
 #[utoipa::path(
     get,
     path = "/api/lesson-questions/:question_id/options",
-    responses((status = 200, description = "List options", body = [QuestionOption])),
+    responses(
+        (status = 200, description = "List options; `is_correct` is only included for the course's instructor and admins", body = [QuestionOption]),
+        (status = 401, description = "Sign in to access a paid lesson"),
+        (status = 403, description = "Not enrolled, or the lesson is not released yet"),
+        (status = 404, description = "Question not found, or its lesson is not published")
+    ),
     tag = "Lessons"
 )]
 pub async fn list_options(
     Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    OptionalAuthUser(auth): OptionalAuthUser,
     Path(question_id): Path<uuid::Uuid>,
 ) -> AppResult<Json<Response<Vec<QuestionOption>>>> {
-    let items = service::list_options(ctx.repos.lesson_questions.as_ref(), question_id).await?;
+    let items = service::list_options(
+        ctx.repos.lesson_questions.as_ref(),
+        &lesson_gate(&ctx),
+        question_id,
+        auth.as_ref(),
+    )
+    .await?;
     let body = Response::with_data("OK", items, StatusCode::OK.as_u16());
     Ok(Json(body))
 }
@@ -109,7 +151,10 @@ pub async fn list_options(
     patch,
     path = "/api/question-options/:id",
     request_body = UpdateQuestionOptionRequest,
-    responses((status = 200, description = "Updated option", body = QuestionOption)),
+    responses(
+        (status = 200, description = "Updated option", body = QuestionOption),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
     tag = "Lessons"
 )]
 pub async fn update_option(
@@ -117,6 +162,8 @@ pub async fn update_option(
     Path(id): Path<uuid::Uuid>,
     ValidatedJson(input): ValidatedJson<UpdateQuestionOptionRequest>,
 ) -> AppResult<Json<Response<Option<QuestionOption>>>> {
+    let lesson_id = service::option_lesson_id(ctx.repos.lesson_questions.as_ref(), id).await?;
+    ensure_lesson_editable(ctx.repos.lessons.as_ref(), lesson_id).await?;
     let updated = service::update_option(ctx.repos.lesson_questions.as_ref(), id, input).await?;
     let body = Response::with_data("OK", updated, StatusCode::OK.as_u16());
     Ok(Json(body))
@@ -125,13 +172,18 @@ pub async fn update_option(
 #[utoipa::path(
     delete,
     path = "/api/question-options/:id",
-    responses((status = 200, description = "Deleted option")),
+    responses(
+        (status = 200, description = "Deleted option"),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
     tag = "Lessons"
 )]
 pub async fn delete_option(
     Extension(ctx): Extension<std::sync::Arc<AppContext>>,
     Path(id): Path<uuid::Uuid>,
 ) -> AppResult<Json<Response<()>>> {
+    let lesson_id = service::option_lesson_id(ctx.repos.lesson_questions.as_ref(), id).await?;
+    ensure_lesson_editable(ctx.repos.lessons.as_ref(), lesson_id).await?;
     service::delete_option(ctx.repos.lesson_questions.as_ref(), id).await?;
     let body = Response::with_message("Deleted", StatusCode::OK.as_u16());
     Ok(Json(body))
@@ -0,0 +1,163 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, Query},
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
+use axum_extra::extract::Multipart;
+
+use crate::applications::lessons::access::{ensure_can_manage_lesson, ensure_can_view_lesson};
+use crate::applications::lessons::subtitles as service;
+use crate::configs::app_context::AppContext;
+use crate::interfaces::http::handlers::lessons::lesson_gate;
+use crate::pkg::Response;
+use crate::pkg::auth::{AuthUser, OptionalAuthUser};
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::subtitles::SubtitleFormat;
+use crate::pkg::utils::multipart::MultipartForm;
+use crate::types::course_types::{LessonSubtitle, LessonTranscript, SubtitleFormatQuery};
+
+#[utoipa::path(
+    put,
+    path = "/api/lessons/:lesson_id/subtitles/:language",
+    responses((status = 200, description = "Stored subtitles (WebVTT or SRT upload)", body = LessonSubtitle)),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Lessons"
+)]
+pub async fn upload_lesson_subtitles(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path((lesson_id, language)): Path<(uuid::Uuid, String)>,
+    multipart: Multipart,
+) -> AppResult<Json<Response<LessonSubtitle>>> {
+    ensure_can_manage_lesson(ctx.repos.lessons.as_ref(), lesson_id, &auth).await?;
+    let form = MultipartForm::parse_with_limit(multipart, service::MAX_SUBTITLE_BYTES).await?;
+    let file = form
+        .file("file")
+        .ok_or_else(|| AppError::BadRequest("Missing file field".into()))?;
+
+    let track = service::upload_subtitles(
+        ctx.repos.lesson_subtitles.as_ref(),
+        lesson_id,
+        &language,
+        form.text("label").map(|s| s.to_string()),
+        file.file_name.as_deref(),
+        &file.data,
+    )
+    .await?;
+    let body = Response::with_data("Subtitles saved", track, StatusCode::OK.as_u16());
+    Ok(Json(body))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/lessons/:lesson_id/subtitles",
+    responses(
+        (status = 200, description = "Caption tracks of a lesson", body = [LessonSubtitle]),
+        (status = 401, description = "Sign in to access a paid lesson"),
+        (status = 403, description = "Not enrolled, or the lesson is not released yet"),
+        (status = 404, description = "Lesson not found or not published")
+    ),
+    tag = "Lessons"
+)]
+pub async fn list_lesson_subtitles(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    OptionalAuthUser(auth): OptionalAuthUser,
+    Path(lesson_id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<Vec<LessonSubtitle>>>> {
+    ensure_can_view_lesson(&lesson_gate(&ctx), lesson_id, auth.as_ref()).await?;
+    let items = service::list_subtitles(ctx.repos.lesson_subtitles.as_ref(), lesson_id).await?;
+    let body = Response::with_data("OK", items, StatusCode::OK.as_u16());
+    Ok(Json(body))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/lessons/:lesson_id/subtitles/:language",
+    params(("format" = Option<String>, Query, description = "vtt (default) or srt")),
+    responses(
+        (status = 200, description = "Subtitle file", content_type = "text/vtt"),
+        (status = 401, description = "Sign in to access a paid lesson"),
+        (status = 403, description = "Not enrolled, or the lesson is not released yet"),
+        (status = 404, description = "Lesson not found or not published")
+    ),
+    tag = "Lessons"
+)]
+pub async fn get_lesson_subtitles(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    OptionalAuthUser(auth): OptionalAuthUser,
+    Path((lesson_id, language)): Path<(uuid::Uuid, String)>,
+    Query(query): Query<SubtitleFormatQuery>,
+) -> AppResult<impl IntoResponse> {
+    let format = match query.format.as_deref() {
+        Some(f) => SubtitleFormat::parse(f)
+            .ok_or_else(|| AppError::BadRequest("format must be 'vtt' or 'srt'".into()))?,
+        None => SubtitleFormat::Vtt,
+    };
+    ensure_can_view_lesson(&lesson_gate(&ctx), lesson_id, auth.as_ref()).await?;
+    let document = service::render_subtitles(
+        ctx.repos.lesson_subtitles.as_ref(),
+        lesson_id,
+        &language,
+        format,
+    )
+    .await?;
+    let disposition = format!(
+        "inline; filename=\"{}-{}.{}\"",
+        lesson_id,
+        language,
+        format.extension()
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        document,
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/lessons/:lesson_id/subtitles/:language/transcript",
+    responses(
+        (status = 200, description = "Plain-text transcript derived from the cues", body = LessonTranscript),
+        (status = 401, description = "Sign in to access a paid lesson"),
+        (status = 403, description = "Not enrolled, or the lesson is not released yet"),
+        (status = 404, description = "Lesson not found or not published")
+    ),
+    tag = "Lessons"
+)]
+pub async fn get_lesson_transcript(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    OptionalAuthUser(auth): OptionalAuthUser,
+    Path((lesson_id, language)): Path<(uuid::Uuid, String)>,
+) -> AppResult<Json<Response<LessonTranscript>>> {
+    ensure_can_view_lesson(&lesson_gate(&ctx), lesson_id, auth.as_ref()).await?;
+    let transcript =
+        service::get_transcript(ctx.repos.lesson_subtitles.as_ref(), lesson_id, &language).await?;
+    let body = Response::with_data("OK", transcript, StatusCode::OK.as_u16());
+    Ok(Json(body))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/lessons/:lesson_id/subtitles/:language",
+    responses((status = 200, description = "Deleted")),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Lessons"
+)]
+pub async fn delete_lesson_subtitles(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path((lesson_id, language)): Path<(uuid::Uuid, String)>,
+) -> AppResult<Json<Response<()>>> {
+    ensure_can_manage_lesson(ctx.repos.lessons.as_ref(), lesson_id, &auth).await?;
+    service::delete_subtitles(ctx.repos.lesson_subtitles.as_ref(), lesson_id, &language).await?;
+    let body = Response::with_message("Deleted", StatusCode::OK.as_u16());
+    Ok(Json(body))
+}
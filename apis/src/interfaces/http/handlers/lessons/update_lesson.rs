@@ -1,6 +1,7 @@
 use axum::{Extension, Json, extract::Path, http::StatusCode};
 
 use crate::applications::lessons as service;
+use crate::applications::lessons::access::ensure_lesson_editable;
 use crate::configs::app_context::AppContext;
 use crate::pkg::Response;
 use crate::pkg::error::AppResult;
@@ -11,7 +12,10 @@ use crate::types::course_types::{Lesson, UpdateLessonRequest};
     patch,
     path = "/api/lessons/:id",
     request_body = UpdateLessonRequest,
-    responses((status = 200, description = "Updated lesson", body = Lesson)),
+    responses(
+        (status = 200, description = "Updated lesson", body = Lesson),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
     security(
         ("bearerAuth" = [])
     ),
@@ -22,6 +26,7 @@ pub async fn update_lesson(
     Path(id): Path<uuid::Uuid>,
     ValidatedJson(input): ValidatedJson<UpdateLessonRequest>,
 ) -> AppResult<(StatusCode, Json<Response<Lesson>>)> {
+    ensure_lesson_editable(ctx.repos.lessons.as_ref(), id).await?;
     let lesson = service::update_lesson(ctx.repos.lessons.as_ref(), id, input).await?;
     let body = Response::with_data("Updated lesson", lesson, StatusCode::OK.as_u16());
     Ok((StatusCode::OK, Json(body)))
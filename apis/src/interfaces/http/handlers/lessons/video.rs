@@ -0,0 +1,75 @@
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+
+use crate::applications::lessons::access::{
+    ensure_can_manage_lesson, ensure_can_view_lesson, ensure_lesson_editable,
+};
+use crate::applications::lessons::video as service;
+use crate::configs::app_context::AppContext;
+use crate::interfaces::http::handlers::lessons::lesson_gate;
+use crate::pkg::Response;
+use crate::pkg::auth::{AuthUser, OptionalAuthUser};
+use crate::pkg::error::AppResult;
+use crate::pkg::validators::ValidatedJson;
+use crate::types::course_types::{LessonVideo, LessonVideoUpload, StartLessonVideoUploadRequest};
+
+#[utoipa::path(
+    post,
+    path = "/api/lessons/:lesson_id/video",
+    request_body = StartLessonVideoUploadRequest,
+    responses(
+        (status = 201, description = "Upload ticket issued by the video provider", body = LessonVideoUpload),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Lessons"
+)]
+pub async fn start_lesson_video_upload(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(lesson_id): Path<uuid::Uuid>,
+    ValidatedJson(input): ValidatedJson<StartLessonVideoUploadRequest>,
+) -> AppResult<(StatusCode, Json<Response<LessonVideoUpload>>)> {
+    ensure_can_manage_lesson(ctx.repos.lessons.as_ref(), lesson_id, &auth).await?;
+    ensure_lesson_editable(ctx.repos.lessons.as_ref(), lesson_id).await?;
+    let ticket = service::start_video_upload(
+        ctx.video_provider.as_ref(),
+        ctx.repos.lessons.as_ref(),
+        ctx.repos.lesson_videos.as_ref(),
+        lesson_id,
+        input,
+    )
+    .await?;
+    let body = Response::with_data("Video upload started", ticket, StatusCode::CREATED.as_u16());
+    Ok((StatusCode::CREATED, Json(body)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/lessons/:lesson_id/video",
+    responses(
+        (status = 200, description = "Hosted video processing status", body = LessonVideo),
+        (status = 401, description = "Sign in to access a paid lesson"),
+        (status = 403, description = "Not enrolled, or the lesson is not released yet"),
+        (status = 404, description = "Lesson not found or not published")
+    ),
+    tag = "Lessons"
+)]
+pub async fn get_lesson_video(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    OptionalAuthUser(auth): OptionalAuthUser,
+    Path(lesson_id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<LessonVideo>>> {
+    ensure_can_view_lesson(&lesson_gate(&ctx), lesson_id, auth.as_ref()).await?;
+    let video = service::refresh_video_status(
+        ctx.video_provider.as_ref(),
+        ctx.repos.lessons.as_ref(),
+        ctx.repos.lesson_videos.as_ref(),
+        lesson_id,
+        &ctx.video.embed_domains,
+    )
+    .await?;
+    let body = Response::with_data("OK", video, StatusCode::OK.as_u16());
+    Ok(Json(body))
+}
@@ -1,3 +1,5 @@
+pub mod private_download;
 pub mod upload;
 
+pub use private_download::download_private_media;
 pub use upload::{upload_media, UploadResponse};
@@ -0,0 +1,74 @@
+use std::path::Path as FsPath;
+
+use axum::{
+    Extension,
+    extract::{Path, Query, Request},
+    http::{HeaderValue, header},
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use tower::ServiceExt;
+use tower_http::services::ServeFile;
+
+use crate::configs::app_context::AppContext;
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::private_media::{self, UrlSigner};
+
+/// Query string carried by every signed download URL
+#[derive(Debug, Deserialize)]
+pub struct SignedDownloadQuery {
+    pub expires: i64,
+    pub signature: String,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/media/private/{key}",
+    params(
+        ("key" = String, Path, description = "Private storage key"),
+        ("expires" = i64, Query, description = "Unix expiry timestamp"),
+        ("signature" = String, Query, description = "HMAC signature")
+    ),
+    responses(
+        (status = 200, description = "File contents"),
+        (status = 206, description = "Requested byte range"),
+        (status = 403, description = "Invalid or expired signature"),
+        (status = 416, description = "Range not satisfiable")
+    ),
+    tag = "Media"
+)]
+pub async fn download_private_media(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    Path(key): Path<String>,
+    Query(query): Query<SignedDownloadQuery>,
+    request: Request,
+) -> AppResult<Response> {
+    let signer = UrlSigner::new(&ctx.media.url_signing_secret);
+    signer.verify(
+        &key,
+        query.expires,
+        &query.signature,
+        chrono::Utc::now().timestamp(),
+    )?;
+
+    let path = private_media::resolve_key(FsPath::new(&ctx.media.private_uploads_dir), &key)?;
+    if !tokio::fs::metadata(&path)
+        .await
+        .map(|m| m.is_file())
+        .unwrap_or(false)
+    {
+        return Err(AppError::NotFound("File not found".into()));
+    }
+
+    // ServeFile handles Range/If-Range and conditional requests for seeking
+    let mut response = ServeFile::new(&path)
+        .oneshot(request)
+        .await
+        .map_err(|e| AppError::Internal(e.to_string()))?
+        .into_response();
+    response.headers_mut().insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("private, no-transform"),
+    );
+    Ok(response)
+}
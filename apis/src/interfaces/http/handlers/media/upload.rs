@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use axum::{Extension, Json, http::StatusCode};
 use axum_extra::extract::Multipart;
 use serde::{Deserialize, Serialize};
@@ -5,14 +7,29 @@ use serde::{Deserialize, Serialize};
 use crate::configs::app_context::AppContext;
 use crate::pkg::Response;
 use crate::pkg::error::AppResult;
-use crate::pkg::upload;
+use crate::pkg::image_processing::{self, ImageKind};
+use crate::pkg::upload::{self, LocalFsStorage};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UploadResponse {
+    /// URL of the stored file; for processed images this is the largest variant
     pub url: String,
     pub filename: String,
     pub size: usize,
     pub content_type: String,
+    /// Responsive WebP variants keyed by width bucket (e.g. "320w", "640w");
+    /// present only when an image `kind` was requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variants: Option<BTreeMap<String, UploadVariant>>,
+}
+
+/// A single resized rendition of an uploaded image
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UploadVariant {
+    pub url: String,
+    pub width: u32,
+    pub height: u32,
+    pub size: usize,
 }
 
 #[utoipa::path(
@@ -28,6 +45,7 @@ pub async fn upload_media(
     let mut file_bytes: Option<Vec<u8>> = None;
     let mut file_name: Option<String> = None;
     let mut content_type: Option<String> = None;
+    let mut kind: Option<String> = None;
 
     while let Some(field) = multipart
         .next_field()
@@ -52,6 +70,13 @@ pub async fn upload_media(
                     content_type = Some(ct_);
                 }
             }
+            Some("kind") => {
+                let value = field
+                    .text()
+                    .await
+                    .map_err(|e| crate::pkg::error::AppError::BadRequest(e.to_string()))?;
+                kind = Some(value);
+            }
             _ => {}
         }
     }
@@ -70,11 +95,59 @@ pub async fn upload_media(
         return Err(crate::pkg::error::AppError::BadRequest("File type not allowed".into()));
     }
 
-    // Save the file to uploads directory
-    let file_path = upload::save_bytes(&bytes, file_name.as_deref()).await?;
-    
     // Construct the full URL using the API host and port
     let base_url = format!("http://{}:{}", ctx.system.api_host, ctx.system.api_port);
+
+    // Images tagged with a `kind` are resized into WebP variants (metadata stripped)
+    let image_kind = match kind.as_deref() {
+        Some(k) => Some(ImageKind::parse(k).ok_or_else(|| {
+            crate::pkg::error::AppError::BadRequest(
+                "kind must be one of: thumbnail, avatar, cover".into(),
+            )
+        })?),
+        None => None,
+    };
+    if let Some(image_kind) = image_kind {
+        if !image_processing::is_processable(&content_type) {
+            return Err(crate::pkg::error::AppError::BadRequest(
+                "Only JPEG, PNG or WebP images can be processed".into(),
+            ));
+        }
+        let rendered = image_processing::process_image_blocking(bytes.into(), image_kind).await?;
+        let storage = LocalFsStorage::new("./uploads");
+        let stored = image_processing::store_variants(&storage, &rendered).await?;
+        let largest = stored
+            .last()
+            .ok_or_else(|| crate::pkg::error::AppError::Internal("No image variants".into()))?;
+
+        let response = UploadResponse {
+            url: format!("{}{}", base_url, largest.path),
+            filename: file_name.unwrap_or_else(|| "uploaded_file".to_string()),
+            size: largest.size,
+            content_type: image_processing::VARIANT_CONTENT_TYPE.to_string(),
+            variants: Some(
+                stored
+                    .iter()
+                    .map(|v| {
+                        (
+                            v.label.clone(),
+                            UploadVariant {
+                                url: format!("{}{}", base_url, v.path),
+                                width: v.width,
+                                height: v.height,
+                                size: v.size,
+                            },
+                        )
+                    })
+                    .collect(),
+            ),
+        };
+        let body = Response::with_data("File uploaded successfully", response, StatusCode::OK.as_u16());
+        return Ok((StatusCode::OK, Json(body)));
+    }
+
+    // Save the file to uploads directory
+    let file_path = upload::save_bytes(&bytes, file_name.as_deref()).await?;
     let full_url = format!("{}{}", base_url, file_path);
 
     let response = UploadResponse {
@@ -82,6 +155,7 @@ pub async fn upload_media(
         filename: file_name.unwrap_or_else(|| "uploaded_file".to_string()),
         size: bytes.len(),
         content_type,
+        variants: None,
     };
 
     let body = Response::with_data("File uploaded successfully", response, StatusCode::OK.as_u16());
@@ -89,7 +163,7 @@ pub async fn upload_media(
 }
 
 /// Check if the content type is allowed
-fn is_allowed_content_type(content_type: &str) -> bool {
+pub(crate) fn is_allowed_content_type(content_type: &str) -> bool {
     let allowed_types = [
         // Images
         "image/jpeg", "image/jpg", "image/png", "image/gif", "image/webp", "image/svg+xml",
@@ -10,3 +10,11 @@ pub mod lessons;
 pub mod media;
 pub mod categories;
 pub mod course_categories;
+pub mod search;
+pub mod certificates;
+pub mod orders;
+pub mod coupons;
+pub mod refunds;
+pub mod payments;
+pub mod learning_paths;
+pub mod analytics;
@@ -1,5 +1,6 @@
 use axum::{Extension, Json, http::StatusCode};
 
+use crate::applications::courses::load_editable_course;
 use crate::applications::modules as service;
 use crate::configs::app_context::AppContext;
 use crate::pkg::Response;
@@ -11,13 +12,17 @@ use crate::types::course_types::{BulkUpdateModulePositionsRequest, CourseModule}
     patch,
     path = "/api/courses/{course_id}/modules/positions",
     request_body = BulkUpdateModulePositionsRequest,
-    responses((status = 200, description = "Updated module positions", body = Vec<CourseModule>)),
+    responses(
+        (status = 200, description = "Updated module positions", body = Vec<CourseModule>),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
     tag = "Courses"
 )]
 pub async fn bulk_update_module_positions(
     Extension(ctx): Extension<std::sync::Arc<AppContext>>,
     ValidatedJson(input): ValidatedJson<BulkUpdateModulePositionsRequest>,
 ) -> AppResult<(StatusCode, Json<Response<Vec<CourseModule>>>)> {
+    load_editable_course(ctx.repos.courses.as_ref(), input.course_id).await?;
     let modules = service::bulk_update_module_positions(ctx.repos.modules.as_ref(), input).await?;
     
     let body = Response::with_data("Updated module positions", modules, StatusCode::OK.as_u16());
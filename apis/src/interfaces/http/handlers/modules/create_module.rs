@@ -1,5 +1,6 @@
 use axum::{Extension, Json, extract::Path, http::StatusCode};
 
+use crate::applications::courses::load_editable_course;
 use crate::applications::modules as service;
 use crate::configs::app_context::AppContext;
 use crate::pkg::Response;
@@ -11,7 +12,10 @@ use crate::types::course_types::CreateModuleRequest;
     post,
     path = "/api/courses/:course_id/modules",
     request_body = CreateModuleRequest,
-    responses((status = 201, description = "Created module", body = uuid::Uuid)),
+    responses(
+        (status = 201, description = "Created module", body = uuid::Uuid),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
     tag = "Courses"
 )]
 pub async fn create_module(
@@ -19,6 +23,7 @@ pub async fn create_module(
     Path(_course_id): Path<uuid::Uuid>,
     ValidatedJson(input): ValidatedJson<CreateModuleRequest>,
 ) -> AppResult<(StatusCode, Json<Response<uuid::Uuid>>)> {
+    load_editable_course(ctx.repos.courses.as_ref(), input.course_id).await?;
     let id = service::create_module(ctx.repos.modules.as_ref(), input).await?;
     let body = Response::with_data("Module created", id, StatusCode::CREATED.as_u16());
     Ok((StatusCode::CREATED, Json(body)))
@@ -1,5 +1,6 @@
 use axum::{Extension, Json, extract::Path, http::StatusCode};
 
+use crate::applications::courses::load_editable_course;
 use crate::applications::modules::deep_create as service;
 use crate::configs::app_context::AppContext;
 use crate::pkg::Response;
@@ -13,7 +14,10 @@ use crate::types::course_types::{CreateModuleDeepRequest, ModuleDeep};
     path = "/api/courses/:course_id/modules/deep",
     security(("bearerAuth" = [])),
     request_body = CreateModuleDeepRequest,
-    responses((status = 201, description = "Created or updated module with nested lessons", body = ModuleDeep)),
+    responses(
+        (status = 201, description = "Created or updated module with nested lessons", body = ModuleDeep),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
     tag = "Courses"
 )]
 pub async fn create_module_deep(
@@ -24,6 +28,7 @@ pub async fn create_module_deep(
 ) -> AppResult<(StatusCode, Json<Response<ModuleDeep>>)> {
     // Ensure only admin users can access this route
     auth_user.require_admin()?;
+    load_editable_course(ctx.repos.courses.as_ref(), course_id).await?;
 
     let result = service::create_deep(ctx.repos.modules.as_ref(), course_id, input).await?;
     let body = Response::with_data(
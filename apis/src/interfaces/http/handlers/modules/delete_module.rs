@@ -1,5 +1,6 @@
 use axum::{Extension, Json, extract::Path, http::StatusCode};
 
+use crate::applications::lessons::access::ensure_module_editable;
 use crate::applications::modules as service;
 use crate::configs::app_context::AppContext;
 use crate::pkg::Response;
@@ -8,13 +9,17 @@ use crate::pkg::error::AppResult;
 #[utoipa::path(
     delete,
     path = "/api/modules/:id",
-    responses((status = 200, description = "Deleted")),
+    responses(
+        (status = 200, description = "Deleted"),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
     tag = "Courses"
 )]
 pub async fn delete_module(
     Extension(ctx): Extension<std::sync::Arc<AppContext>>,
     Path(id): Path<uuid::Uuid>,
 ) -> AppResult<(StatusCode, Json<Response<serde_json::Value>>)> {
+    ensure_module_editable(ctx.repos.lessons.as_ref(), id).await?;
     service::delete_module(ctx.repos.modules.as_ref(), id).await?;
     let body = Response::with_data(
         "Deleted",
@@ -3,6 +3,7 @@ pub mod create_module_deep;
 pub mod delete_module;
 pub mod list_modules;
 pub mod list_modules_deep;
+pub mod set_module_drip;
 pub mod update_module;
 pub mod bulk_update_positions;
 
@@ -11,5 +12,6 @@ pub use create_module_deep::create_module_deep;
 pub use delete_module::delete_module;
 pub use list_modules::list_modules;
 pub use list_modules_deep::list_modules_deep;
+pub use set_module_drip::set_module_drip;
 pub use update_module::update_module;
 pub use bulk_update_positions::bulk_update_module_positions;
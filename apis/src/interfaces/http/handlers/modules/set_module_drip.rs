@@ -0,0 +1,42 @@
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+
+use crate::applications::modules as service;
+use crate::configs::app_context::AppContext;
+use crate::pkg::Response;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::AppResult;
+use crate::pkg::validators::ValidatedJson;
+use crate::types::course_types::{CourseModule, SetModuleDripRequest};
+
+#[utoipa::path(
+    put,
+    path = "/api/modules/:id/drip",
+    request_body = SetModuleDripRequest,
+    responses(
+        (status = 200, description = "Drip schedule replaced", body = CourseModule),
+        (status = 400, description = "Both drip_days and drip_at are set"),
+        (status = 403, description = "Not the course instructor"),
+        (status = 404, description = "Module not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Courses"
+)]
+pub async fn set_module_drip(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+    ValidatedJson(input): ValidatedJson<SetModuleDripRequest>,
+) -> AppResult<(StatusCode, Json<Response<CourseModule>>)> {
+    let module = service::set_module_drip(
+        ctx.repos.modules.as_ref(),
+        ctx.repos.courses.as_ref(),
+        &auth,
+        id,
+        input,
+    )
+    .await?;
+    let body = Response::with_data("Drip schedule updated", module, StatusCode::OK.as_u16());
+    Ok((StatusCode::OK, Json(body)))
+}
@@ -1,5 +1,6 @@
 use axum::{Extension, Json, extract::Path, http::StatusCode};
 
+use crate::applications::lessons::access::ensure_module_editable;
 use crate::applications::modules as service;
 use crate::configs::app_context::AppContext;
 use crate::pkg::Response;
@@ -11,7 +12,10 @@ use crate::types::course_types::{CourseModule, UpdateModuleRequest};
     patch,
     path = "/api/modules/:id",
     request_body = UpdateModuleRequest,
-    responses((status = 200, description = "Updated module", body = CourseModule)),
+    responses(
+        (status = 200, description = "Updated module", body = CourseModule),
+        (status = 409, description = "The course is published; edit it through a draft revision")
+    ),
     security(
         ("bearerAuth" = [])
     ),
@@ -22,6 +26,7 @@ pub async fn update_module(
     Path(id): Path<uuid::Uuid>,
     ValidatedJson(input): ValidatedJson<UpdateModuleRequest>,
 ) -> AppResult<(StatusCode, Json<Response<CourseModule>>)> {
+    ensure_module_editable(ctx.repos.lessons.as_ref(), id).await?;
     let module = service::update_module(ctx.repos.modules.as_ref(), id, input).await?;
     let body = Response::with_data("Updated module", module, StatusCode::OK.as_u16());
     Ok((StatusCode::OK, Json(body)))
@@ -0,0 +1,182 @@
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+
+use crate::applications::orders::{self as service, OrderServices};
+use crate::configs::app_context::AppContext;
+use crate::pkg::Response;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::AppResult;
+use crate::pkg::validators::ValidatedJson;
+use crate::types::order_types::{CheckoutRequest, CheckoutResponse, Order, OrderEvent};
+
+/// Checkout services backed by the app's repositories and payment provider
+pub(crate) fn order_services(ctx: &AppContext) -> OrderServices<'_> {
+    OrderServices {
+        courses: ctx.repos.courses.as_ref(),
+        orders: ctx.repos.orders.as_ref(),
+        enrollments: ctx.repos.enrollments.as_ref(),
+        coupons: ctx.repos.coupons.as_ref(),
+        course_categories: ctx.repos.course_categories.as_ref(),
+        provider: ctx.payment_provider.as_ref(),
+        config: &ctx.payments,
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/checkout",
+    request_body = CheckoutRequest,
+    responses(
+        (status = 201, description = "Order created; pay with the returned client secret, then confirm", body = CheckoutResponse),
+        (status = 400, description = "A course is not for sale, or the coupon does not apply"),
+        (status = 404, description = "Course or coupon not found"),
+        (status = 409, description = "Already enrolled in a course, or the coupon is used up")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Orders"
+)]
+pub async fn checkout(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    ValidatedJson(input): ValidatedJson<CheckoutRequest>,
+) -> AppResult<(StatusCode, Json<Response<CheckoutResponse>>)> {
+    let result = service::checkout(&order_services(&ctx), &auth, input).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(Response::with_data(
+            "Order created",
+            result,
+            StatusCode::CREATED.as_u16(),
+        )),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/orders/:id/confirm",
+    responses(
+        (status = 200, description = "Order with its payment outcome; paid orders are enrolled", body = Order),
+        (status = 404, description = "Order not found"),
+        (status = 409, description = "Order can no longer be paid")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Orders"
+)]
+pub async fn confirm_order(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<Order>>> {
+    let order = service::confirm_order(&order_services(&ctx), &auth, id).await?;
+    Ok(Json(Response::with_data(
+        "Order payment checked",
+        order,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/orders/:id/cancel",
+    responses(
+        (status = 200, description = "Unpaid order canceled; its coupon can be used again", body = Order),
+        (status = 404, description = "Order not found"),
+        (status = 409, description = "Order is no longer pending, or its payment is already processing")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Orders"
+)]
+pub async fn cancel_order(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<Order>>> {
+    let order = service::cancel_order(
+        ctx.repos.orders.as_ref(),
+        ctx.payment_provider.as_ref(),
+        &auth,
+        id,
+    )
+    .await?;
+    Ok(Json(Response::with_data(
+        "Order canceled",
+        order,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/orders",
+    responses((status = 200, description = "Orders placed by the signed-in user", body = [Order])),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Orders"
+)]
+pub async fn list_my_orders(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+) -> AppResult<Json<Response<Vec<Order>>>> {
+    let orders = service::list_my_orders(ctx.repos.orders.as_ref(), &auth).await?;
+    Ok(Json(Response::with_data(
+        "Orders",
+        orders,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/orders/:id",
+    responses(
+        (status = 200, description = "Order", body = Order),
+        (status = 404, description = "Order not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Orders"
+)]
+pub async fn get_order(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<Order>>> {
+    let order = service::get_order(ctx.repos.orders.as_ref(), &auth, id).await?;
+    Ok(Json(Response::with_data(
+        "Order",
+        order,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/orders/:id/history",
+    responses(
+        (status = 200, description = "Audit trail of the order, oldest first", body = [OrderEvent]),
+        (status = 404, description = "Order not found")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Orders"
+)]
+pub async fn get_order_history(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+) -> AppResult<Json<Response<Vec<OrderEvent>>>> {
+    let events = service::get_order_history(ctx.repos.orders.as_ref(), &auth, id).await?;
+    Ok(Json(Response::with_data(
+        "Order history",
+        events,
+        StatusCode::OK.as_u16(),
+    )))
+}
@@ -0,0 +1,53 @@
+use axum::{
+    Extension, Json,
+    body::Bytes,
+    http::{HeaderMap, StatusCode},
+};
+
+use crate::applications::payment_webhooks::{self as service, WebhookServices};
+use crate::configs::app_context::AppContext;
+use crate::pkg::Response;
+use crate::pkg::error::AppResult;
+use crate::pkg::payments::WebhookVerifier;
+use crate::types::order_types::WebhookReceipt;
+
+#[utoipa::path(
+    post,
+    path = "/api/payments/webhook",
+    request_body(content = String, description = "Provider event, signed in the provider's signature header", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Event accepted; redeliveries are acknowledged as duplicates", body = WebhookReceipt),
+        (status = 400, description = "Malformed event"),
+        (status = 401, description = "Missing, invalid or expired signature"),
+        (status = 503, description = "Webhooks are not configured")
+    ),
+    tag = "Orders"
+)]
+pub async fn payment_webhook(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> AppResult<Json<Response<WebhookReceipt>>> {
+    let provider = ctx.payment_provider.as_ref();
+    let signature = headers
+        .get(provider.signature_header())
+        .and_then(|v| v.to_str().ok());
+    let svc = WebhookServices {
+        orders: ctx.repos.orders.as_ref(),
+        enrollments: ctx.repos.enrollments.as_ref(),
+        events: ctx.repos.payment_events.as_ref(),
+        provider,
+        verifier: (!ctx.payments.webhook_secret.is_empty()).then(|| {
+            WebhookVerifier::new(
+                &ctx.payments.webhook_secret,
+                ctx.payments.webhook_tolerance_secs,
+            )
+        }),
+    };
+    let receipt = service::handle_webhook(&svc, signature, &body).await?;
+    Ok(Json(Response::with_data(
+        "Event received",
+        receipt,
+        StatusCode::OK.as_u16(),
+    )))
+}
@@ -0,0 +1,151 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, Query},
+    http::StatusCode,
+};
+
+use crate::applications::refunds::{self as service, RefundServices};
+use crate::configs::app_context::AppContext;
+use crate::pkg::Response;
+use crate::pkg::auth::AuthUser;
+use crate::pkg::error::AppResult;
+use crate::pkg::validators::ValidatedJson;
+use crate::types::order_types::{
+    CreateRefundRequest, RefundRequest, RefundRequestsQuery, ReviewRefundRequest,
+};
+
+/// Refund services backed by the app's repositories and payment provider
+fn refund_services(ctx: &AppContext) -> RefundServices<'_> {
+    RefundServices {
+        orders: ctx.repos.orders.as_ref(),
+        refunds: ctx.repos.refund_requests.as_ref(),
+        enrollments: ctx.repos.enrollments.as_ref(),
+        provider: ctx.payment_provider.as_ref(),
+        config: &ctx.payments,
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/orders/:id/refund",
+    request_body = CreateRefundRequest,
+    responses(
+        (status = 201, description = "Refund requested; an admin reviews it", body = RefundRequest),
+        (status = 400, description = "The refund window has closed"),
+        (status = 404, description = "Order not found"),
+        (status = 409, description = "Order is not paid, or a refund is already requested")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Orders"
+)]
+pub async fn request_refund(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+    ValidatedJson(input): ValidatedJson<CreateRefundRequest>,
+) -> AppResult<(StatusCode, Json<Response<RefundRequest>>)> {
+    let request = service::request_refund(&refund_services(&ctx), &auth, id, input).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(Response::with_data(
+            "Refund requested",
+            request,
+            StatusCode::CREATED.as_u16(),
+        )),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/refunds",
+    params(
+        ("status" = Option<String>, Query, description = "pending (default), rejected, refunded, failed or all"),
+        ("limit" = Option<i64>, Query, description = "maximum number of requests (default 50, max 200)")
+    ),
+    responses(
+        (status = 200, description = "Refund requests, oldest first", body = [RefundRequest]),
+        (status = 403, description = "Admin access required")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Orders"
+)]
+pub async fn list_refund_requests(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Query(q): Query<RefundRequestsQuery>,
+) -> AppResult<Json<Response<Vec<RefundRequest>>>> {
+    let requests = service::list_refund_requests(
+        ctx.repos.refund_requests.as_ref(),
+        &auth,
+        q.status.as_deref(),
+        q.limit,
+    )
+    .await?;
+    Ok(Json(Response::with_data(
+        "Refund requests",
+        requests,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/refunds/:id/approve",
+    request_body = ReviewRefundRequest,
+    responses(
+        (status = 200, description = "Order refunded and enrollments revoked", body = RefundRequest),
+        (status = 400, description = "The payment provider declined the refund; it can be approved again"),
+        (status = 403, description = "Admin access required"),
+        (status = 409, description = "The request was already reviewed")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Orders"
+)]
+pub async fn approve_refund(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+    ValidatedJson(input): ValidatedJson<ReviewRefundRequest>,
+) -> AppResult<Json<Response<RefundRequest>>> {
+    let request = service::approve_refund(&refund_services(&ctx), &auth, id, input).await?;
+    Ok(Json(Response::with_data(
+        "Refund approved",
+        request,
+        StatusCode::OK.as_u16(),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/refunds/:id/reject",
+    request_body = ReviewRefundRequest,
+    responses(
+        (status = 200, description = "Refund rejected", body = RefundRequest),
+        (status = 400, description = "A note is required"),
+        (status = 403, description = "Admin access required"),
+        (status = 409, description = "The request was already reviewed")
+    ),
+    security(
+        ("bearerAuth" = [])
+    ),
+    tag = "Orders"
+)]
+pub async fn reject_refund(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    auth: AuthUser,
+    Path(id): Path<uuid::Uuid>,
+    ValidatedJson(input): ValidatedJson<ReviewRefundRequest>,
+) -> AppResult<Json<Response<RefundRequest>>> {
+    let request = service::reject_refund(&refund_services(&ctx), &auth, id, input).await?;
+    Ok(Json(Response::with_data(
+        "Refund rejected",
+        request,
+        StatusCode::OK.as_u16(),
+    )))
+}
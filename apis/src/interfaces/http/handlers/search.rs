@@ -0,0 +1,27 @@
+use axum::{Extension, Json, extract::Query, http::StatusCode};
+
+use crate::applications::search as service;
+use crate::configs::app_context::AppContext;
+use crate::pkg::Response;
+use crate::pkg::error::AppResult;
+use crate::types::search_types::{SearchQuery, SearchResponse};
+
+#[utoipa::path(
+    get,
+    path = "/api/search",
+    params(
+        ("q" = String, Query, description = "search text"),
+        ("types" = Option<String>, Query, description = "comma-separated: course, lesson, post"),
+        ("limit" = Option<i64>, Query, description = "max results (default 20, max 50)")
+    ),
+    responses((status = 200, description = "Ranked results across courses, lessons and blog posts", body = SearchResponse)),
+    tag = "Search"
+)]
+pub async fn search(
+    Extension(ctx): Extension<std::sync::Arc<AppContext>>,
+    Query(q): Query<SearchQuery>,
+) -> AppResult<Json<Response<SearchResponse>>> {
+    let data = service::search(ctx.repos.search.as_ref(), q).await?;
+    let body = Response::with_data("OK", data, StatusCode::OK.as_u16());
+    Ok(Json(body))
+}
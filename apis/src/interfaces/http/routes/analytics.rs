@@ -0,0 +1,17 @@
+use axum::{Router, routing::get};
+
+use crate::interfaces::http::handlers::analytics as h;
+
+pub fn router() -> Router {
+    Router::new()
+        // Course managers only; read from the daily rollups
+        .route("/api/courses/:id/analytics", get(h::get_course_analytics))
+        .route(
+            "/api/courses/:id/analytics/curriculum",
+            get(h::get_curriculum_analytics),
+        )
+        .route(
+            "/api/instructors/me/analytics",
+            get(h::get_instructor_analytics),
+        )
+}
@@ -0,0 +1,14 @@
+use axum::{Router, routing::get};
+
+use crate::interfaces::http::handlers::certificates as h;
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/api/certificates", get(h::list_my_certificates))
+        // Public verification
+        .route("/api/certificates/:code", get(h::verify_certificate))
+        .route(
+            "/api/certificates/:code/pdf",
+            get(h::download_certificate_pdf),
+        )
+}
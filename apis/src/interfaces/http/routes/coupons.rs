@@ -0,0 +1,17 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::interfaces::http::handlers::coupons as h;
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/api/coupons", post(h::create_coupon).get(h::list_coupons))
+        .route(
+            "/api/coupons/:id",
+            get(h::get_coupon).patch(h::update_coupon),
+        )
+        // Preview for learners before checkout
+        .route("/api/coupons/validate", post(h::validate_coupon))
+}
@@ -1,6 +1,6 @@
 use axum::{
     Router,
-    routing::{delete, get, patch, post},
+    routing::{delete, get, patch, post, put},
 };
 
 use crate::interfaces::http::handlers::courses as h;
@@ -8,9 +8,102 @@ use crate::interfaces::http::handlers::courses as h;
 pub fn router() -> Router {
     Router::new()
         .route("/api/courses", post(h::create_course))
+        // Public catalogue of published courses
+        .route("/api/courses", get(h::list_course_catalogue))
         .route("/api/courses/:id", get(h::get_course_by_id))
         .route("/api/courses/:id", patch(h::update_course))
         .route("/api/courses/:id", delete(h::delete_course))
+        .route("/api/courses/:id/duplicate", post(h::duplicate_course))
+        // Portable archives
+        .route("/api/courses/:id/export", get(h::export_course))
+        .route("/api/courses/import", post(h::import_course))
+        // SCORM / Common Cartridge packages
+        .route("/api/courses/import/package", post(h::import_course_package))
+        // Publishing workflow
+        .route("/api/courses/:id/publish", post(h::publish_course))
+        .route("/api/courses/:id/unpublish", post(h::unpublish_course))
+        .route("/api/courses/:id/archive", post(h::archive_course))
+        .route("/api/courses/:id/schedule", post(h::schedule_course_publish))
+        .route(
+            "/api/courses/:id/schedule",
+            delete(h::cancel_scheduled_publish),
+        )
+        // Draft revisions of published courses
+        .route("/api/courses/:id/revisions", post(h::start_course_revision))
+        .route(
+            "/api/courses/:id/revisions/draft",
+            get(h::get_open_course_revision),
+        )
+        .route(
+            "/api/courses/:id/revisions/:revision_id",
+            put(h::update_course_revision),
+        )
+        .route(
+            "/api/courses/:id/revisions/:revision_id",
+            delete(h::discard_course_revision),
+        )
+        .route(
+            "/api/courses/:id/revisions/:revision_id/diff",
+            get(h::diff_course_revision),
+        )
+        .route(
+            "/api/courses/:id/revisions/:revision_id/publish",
+            post(h::publish_course_revision),
+        )
+        // Courses to complete before this one's lessons open
+        .route(
+            "/api/courses/:id/prerequisites",
+            get(h::list_course_prerequisites).put(h::set_course_prerequisites),
+        )
+        // Prices in further currencies
+        .route("/api/courses/:id/prices", get(h::list_course_prices))
+        .route(
+            "/api/courses/:id/prices/:currency",
+            put(h::set_course_price),
+        )
+        .route(
+            "/api/courses/:id/prices/:currency",
+            delete(h::delete_course_price),
+        )
+        // Reviews and ratings
+        .route("/api/courses/:id/reviews", get(h::list_course_reviews))
+        .route("/api/courses/:id/reviews", post(h::create_course_review))
+        .route(
+            "/api/courses/reviews/pending",
+            get(h::list_pending_course_reviews),
+        )
+        .route(
+            "/api/courses/reviews/:review_id",
+            patch(h::update_course_review),
+        )
+        .route(
+            "/api/courses/reviews/:review_id",
+            delete(h::delete_course_review),
+        )
+        .route(
+            "/api/courses/reviews/:review_id/reply",
+            put(h::reply_to_course_review),
+        )
+        .route(
+            "/api/courses/reviews/:review_id/reply",
+            delete(h::delete_course_review_reply),
+        )
+        .route(
+            "/api/courses/reviews/:review_id/helpful",
+            post(h::mark_course_review_helpful),
+        )
+        .route(
+            "/api/courses/reviews/:review_id/helpful",
+            delete(h::unmark_course_review_helpful),
+        )
+        .route(
+            "/api/courses/reviews/:review_id/approve",
+            post(h::approve_course_review),
+        )
+        .route(
+            "/api/courses/reviews/:review_id/reject",
+            post(h::reject_course_review),
+        )
         // Instructor specific
         .route(
             "/api/instructors/courses/list",
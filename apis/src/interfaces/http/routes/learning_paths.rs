@@ -0,0 +1,28 @@
+use axum::{
+    Router,
+    routing::{delete, get, patch, post, put},
+};
+
+use crate::interfaces::http::handlers::learning_paths as h;
+
+pub fn router() -> Router {
+    Router::new()
+        // Public catalogue of published paths
+        .route("/api/paths", get(h::list_learning_paths))
+        .route("/api/paths", post(h::create_learning_path))
+        .route("/api/paths/:id", get(h::get_learning_path))
+        .route("/api/paths/:id", patch(h::update_learning_path))
+        .route("/api/paths/:id", delete(h::delete_learning_path))
+        .route("/api/paths/:id/courses", put(h::set_learning_path_courses))
+        .route(
+            "/api/paths/:id/prerequisites",
+            put(h::set_learning_path_prerequisites),
+        )
+        .route("/api/paths/:id/enroll", post(h::enroll_in_learning_path))
+        .route(
+            "/api/paths/:id/progress",
+            get(h::get_learning_path_progress),
+        )
+        // Public fetch by slug
+        .route("/api/path/:slug", get(h::get_learning_path_by_slug))
+}
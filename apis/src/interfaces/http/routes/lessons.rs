@@ -14,6 +14,27 @@ pub fn router() -> Router {
             patch(h::bulk_update_lesson_positions),
         )
         .route("/api/lessons/:id", delete(h::delete_lesson))
+        // hosted video
+        .route(
+            "/api/lessons/:lesson_id/video",
+            post(h::start_lesson_video_upload),
+        )
+        .route("/api/lessons/:lesson_id/video", get(h::get_lesson_video))
+        // subtitles
+        .route(
+            "/api/lessons/:lesson_id/subtitles",
+            get(h::list_lesson_subtitles),
+        )
+        .route(
+            "/api/lessons/:lesson_id/subtitles/:language",
+            get(h::get_lesson_subtitles)
+                .put(h::upload_lesson_subtitles)
+                .delete(h::delete_lesson_subtitles),
+        )
+        .route(
+            "/api/lessons/:lesson_id/subtitles/:language/transcript",
+            get(h::get_lesson_transcript),
+        )
         // contents
         .route(
             "/api/lessons/:lesson_id/contents",
@@ -23,6 +44,14 @@ pub fn router() -> Router {
             "/api/lessons/:lesson_id/contents",
             post(h::create_lesson_content),
         )
+        .route(
+            "/api/lessons/:lesson_id/contents/private",
+            post(h::upload_private_lesson_content),
+        )
+        .route(
+            "/api/lesson-contents/:id/download-url",
+            get(h::get_lesson_content_download_url),
+        )
         .route("/api/lesson-contents/:id", patch(h::update_lesson_content))
         .route("/api/lesson-contents/:id", delete(h::delete_lesson_content))
         // questions
@@ -44,6 +73,13 @@ pub fn router() -> Router {
         )
         .route("/api/question-options/:id", patch(h::update_option))
         .route("/api/question-options/:id", delete(h::delete_option))
+        // progress
+        .route("/api/lessons/:lesson_id/complete", post(h::complete_lesson))
+        .route(
+            "/api/lessons/:lesson_id/quiz/attempts",
+            post(h::submit_quiz_attempt),
+        )
+        .route("/api/courses/:id/progress", get(h::get_course_progress))
         // assignment
         .route("/api/lessons/:lesson_id/assignment", get(h::get_assignment))
         .route(
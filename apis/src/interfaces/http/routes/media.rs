@@ -1,6 +1,6 @@
 use axum::{
     Router,
-    routing::post,
+    routing::{get, post},
 };
 
 use crate::interfaces::http::handlers::media as h;
@@ -8,4 +8,5 @@ use crate::interfaces::http::handlers::media as h;
 pub fn router() -> Router {
     Router::new()
         .route("/api/media/upload", post(h::upload_media))
+        .route("/api/media/private/*key", get(h::download_private_media))
 }
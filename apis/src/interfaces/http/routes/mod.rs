@@ -5,6 +5,12 @@ pub mod media;
 pub mod modules;
 pub mod users;
 pub mod categories;
+pub mod search;
+pub mod certificates;
+pub mod orders;
+pub mod coupons;
+pub mod learning_paths;
+pub mod analytics;
 pub use crate::interfaces::http::handlers::root::handler;
 
 use crate::interfaces::http::handlers::{graphql, health};
@@ -23,4 +29,10 @@ pub fn router() -> Router {
         .merge(lessons::router())
         .merge(media::router())
         .merge(categories::router())
+        .merge(search::router())
+        .merge(certificates::router())
+        .merge(orders::router())
+        .merge(coupons::router())
+        .merge(learning_paths::router())
+        .merge(analytics::router())
 }
@@ -1,6 +1,6 @@
 use axum::{
     Router,
-    routing::{delete, get, patch, post},
+    routing::{delete, get, patch, post, put},
 };
 
 use crate::interfaces::http::handlers::modules as h;
@@ -19,4 +19,5 @@ pub fn router() -> Router {
         .route("/api/courses/:course_id/modules", post(h::create_module))
         .route("/api/modules/:id", patch(h::update_module))
         .route("/api/modules/:id", delete(h::delete_module))
+        .route("/api/modules/:id/drip", put(h::set_module_drip))
 }
@@ -0,0 +1,23 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::interfaces::http::handlers::{orders as h, payments, refunds};
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/api/checkout", post(h::checkout))
+        .route("/api/orders", get(h::list_my_orders))
+        .route("/api/orders/:id", get(h::get_order))
+        .route("/api/orders/:id/confirm", post(h::confirm_order))
+        .route("/api/orders/:id/cancel", post(h::cancel_order))
+        .route("/api/orders/:id/history", get(h::get_order_history))
+        // Refunds: requested by the buyer, reviewed by an admin
+        .route("/api/orders/:id/refund", post(refunds::request_refund))
+        .route("/api/refunds", get(refunds::list_refund_requests))
+        .route("/api/refunds/:id/approve", post(refunds::approve_refund))
+        .route("/api/refunds/:id/reject", post(refunds::reject_refund))
+        // Provider callbacks; authenticated by signature, not by user
+        .route("/api/payments/webhook", post(payments::payment_webhook))
+}
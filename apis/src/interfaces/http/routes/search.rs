@@ -0,0 +1,7 @@
+use axum::{Router, routing::get};
+
+use crate::interfaces::http::handlers::search as h;
+
+pub fn router() -> Router {
+    Router::new().route("/api/search", get(h::search))
+}
@@ -8,12 +8,19 @@ use tower_http::{
     compression::CompressionLayer, cors::CorsLayer, services::ServeDir, trace::TraceLayer,
 };
 
+use crate::applications::analytics::{ANALYTICS_ROLLUP_INTERVAL, run_analytics_rollup};
+use crate::applications::courses::publishing::{
+    SCHEDULED_PUBLISH_INTERVAL, run_scheduled_publisher,
+};
+use crate::applications::orders::{ORDER_EXPIRY_INTERVAL, run_order_expiry};
 use crate::configs::app_context::AppContext;
+use crate::interfaces::middlewares::axum_client_ip::client_ip as client_ip_mw;
 use crate::interfaces::middlewares::axum_error_handler::error_handler as error_handler_mw;
 use crate::interfaces::middlewares::axum_rate_limit::{
     RateLimitState, rate_limit as rate_limit_mw,
 };
 use crate::interfaces::middlewares::axum_request_logger::request_logger as request_logger_mw;
+use crate::pkg::client_ip::TrustedProxies;
 use crate::pkg::logger::info;
 
 use super::routes;
@@ -27,7 +34,13 @@ fn build_app(ctx: Arc<AppContext>) -> Router {
     let compression = CompressionLayer::new();
 
     let api = routes::router();
-    let rate_limit_state = RateLimitState::new(25, 60);
+    let rate_limit_state = RateLimitState::redis(
+        ctx.redis.clone(),
+        "rl:http",
+        ctx.rate_limit.policies.clone(),
+    )
+    .with_auth(ctx.jwt_service.clone());
+    let trusted_proxies = Arc::new(TrustedProxies::new(ctx.system.trusted_proxies.clone()));
 
     let app = Router::new()
         .merge(api)
@@ -45,6 +58,10 @@ fn build_app(ctx: Arc<AppContext>) -> Router {
         .route_layer(middleware::from_fn_with_state(
             rate_limit_state.clone(),
             rate_limit_mw,
+        ))
+        .layer(middleware::from_fn_with_state(
+            trusted_proxies,
+            client_ip_mw,
         ));
 
     // Build OpenAPI doc and mount Swagger UI at /docs
@@ -58,15 +75,38 @@ pub async fn start_server(
     config: &AppContext,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let ctx = Arc::new(config.clone());
+    // Publishes drafts whose scheduled time has come; stopped with the server
+    let scheduled_publisher = tokio::spawn(run_scheduled_publisher(
+        ctx.repos.courses.clone(),
+        SCHEDULED_PUBLISH_INTERVAL,
+    ));
+    // Keeps the analytics rollup tables current; stopped with the server
+    let analytics_rollup = tokio::spawn(run_analytics_rollup(
+        ctx.repos.analytics.clone(),
+        ANALYTICS_ROLLUP_INTERVAL,
+    ));
+    // Cancels orders left unpaid, giving back their coupon redemptions
+    let order_expiry = tokio::spawn(run_order_expiry(
+        ctx.repos.orders.clone(),
+        ctx.payment_provider.clone(),
+        ORDER_EXPIRY_INTERVAL,
+    ));
     let app = build_app(ctx);
 
     let addr: SocketAddr = format!("{}:{}", host, config.system.api_port).parse()?;
     let listener = tokio::net::TcpListener::bind(addr).await?;
 
     // Start the server with graceful shutdown
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
-        .await?;
+    // Peer addresses feed the client IP middleware
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal())
+    .await?;
+    scheduled_publisher.abort();
+    analytics_rollup.abort();
+    order_expiry.abort();
 
     info("Server shutdown complete".to_string());
     Ok(())
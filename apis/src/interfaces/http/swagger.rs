@@ -30,19 +30,105 @@ use utoipa::{Modify, OpenApi};
         crate::interfaces::http::handlers::courses::create_course::create_course,
         crate::interfaces::http::handlers::courses::get_course_by_id::get_course_by_id,
         crate::interfaces::http::handlers::courses::get_course_by_slug::get_course_by_slug,
+        crate::interfaces::http::handlers::courses::list_course_catalogue::list_course_catalogue,
         crate::interfaces::http::handlers::courses::update_course::update_course,
         crate::interfaces::http::handlers::courses::delete_course::delete_course,
+        crate::interfaces::http::handlers::courses::duplicate_course::duplicate_course,
+        crate::interfaces::http::handlers::courses::archive::export_course,
+        crate::interfaces::http::handlers::courses::archive::import_course,
+        crate::interfaces::http::handlers::courses::package_import::import_course_package,
+        crate::interfaces::http::handlers::courses::publishing::publish_course,
+        crate::interfaces::http::handlers::courses::publishing::unpublish_course,
+        crate::interfaces::http::handlers::courses::publishing::archive_course,
+        crate::interfaces::http::handlers::courses::publishing::schedule_course_publish,
+        crate::interfaces::http::handlers::courses::publishing::cancel_scheduled_publish,
+        crate::interfaces::http::handlers::courses::revisions::start_course_revision,
+        crate::interfaces::http::handlers::courses::revisions::get_open_course_revision,
+        crate::interfaces::http::handlers::courses::revisions::update_course_revision,
+        crate::interfaces::http::handlers::courses::revisions::diff_course_revision,
+        crate::interfaces::http::handlers::courses::revisions::publish_course_revision,
+        crate::interfaces::http::handlers::courses::revisions::discard_course_revision,
+        crate::interfaces::http::handlers::courses::pricing::list_course_prices,
+        crate::interfaces::http::handlers::courses::pricing::set_course_price,
+        crate::interfaces::http::handlers::courses::pricing::delete_course_price,
+        crate::interfaces::http::handlers::courses::prerequisites::list_course_prerequisites,
+        crate::interfaces::http::handlers::courses::prerequisites::set_course_prerequisites,
+        crate::interfaces::http::handlers::courses::reviews::list_course_reviews,
+        crate::interfaces::http::handlers::courses::reviews::create_course_review,
+        crate::interfaces::http::handlers::courses::reviews::update_course_review,
+        crate::interfaces::http::handlers::courses::reviews::delete_course_review,
+        crate::interfaces::http::handlers::courses::reviews::reply_to_course_review,
+        crate::interfaces::http::handlers::courses::reviews::delete_course_review_reply,
+        crate::interfaces::http::handlers::courses::reviews::mark_course_review_helpful,
+        crate::interfaces::http::handlers::courses::reviews::unmark_course_review_helpful,
+        crate::interfaces::http::handlers::courses::reviews::list_pending_course_reviews,
+        crate::interfaces::http::handlers::courses::reviews::approve_course_review,
+        crate::interfaces::http::handlers::courses::reviews::reject_course_review,
         // Modules
         crate::interfaces::http::handlers::modules::list_modules::list_modules,
         crate::interfaces::http::handlers::modules::list_modules_deep::list_modules_deep,
         crate::interfaces::http::handlers::modules::create_module::create_module,
         crate::interfaces::http::handlers::modules::update_module::update_module,
         crate::interfaces::http::handlers::modules::delete_module::delete_module,
+        crate::interfaces::http::handlers::modules::set_module_drip::set_module_drip,
         // Lessons
         crate::interfaces::http::handlers::lessons::list_lessons::list_lessons,
         crate::interfaces::http::handlers::lessons::create_lesson::create_lesson,
         crate::interfaces::http::handlers::lessons::update_lesson::update_lesson,
         crate::interfaces::http::handlers::lessons::delete_lesson::delete_lesson,
+        crate::interfaces::http::handlers::lessons::private_contents::upload_private_lesson_content,
+        crate::interfaces::http::handlers::lessons::private_contents::get_lesson_content_download_url,
+        crate::interfaces::http::handlers::lessons::video::start_lesson_video_upload,
+        crate::interfaces::http::handlers::lessons::video::get_lesson_video,
+        crate::interfaces::http::handlers::lessons::subtitles::upload_lesson_subtitles,
+        crate::interfaces::http::handlers::lessons::subtitles::list_lesson_subtitles,
+        crate::interfaces::http::handlers::lessons::subtitles::get_lesson_subtitles,
+        crate::interfaces::http::handlers::lessons::subtitles::get_lesson_transcript,
+        crate::interfaces::http::handlers::lessons::subtitles::delete_lesson_subtitles,
+        crate::interfaces::http::handlers::lessons::progress::complete_lesson,
+        crate::interfaces::http::handlers::lessons::progress::submit_quiz_attempt,
+        crate::interfaces::http::handlers::lessons::progress::get_course_progress,
+        // Certificates
+        crate::interfaces::http::handlers::certificates::list_my_certificates,
+        crate::interfaces::http::handlers::certificates::verify_certificate,
+        crate::interfaces::http::handlers::certificates::download_certificate_pdf,
+        // Orders
+        crate::interfaces::http::handlers::orders::checkout,
+        crate::interfaces::http::handlers::orders::confirm_order,
+        crate::interfaces::http::handlers::orders::cancel_order,
+        crate::interfaces::http::handlers::orders::list_my_orders,
+        crate::interfaces::http::handlers::orders::get_order,
+        crate::interfaces::http::handlers::orders::get_order_history,
+        crate::interfaces::http::handlers::refunds::request_refund,
+        crate::interfaces::http::handlers::refunds::list_refund_requests,
+        crate::interfaces::http::handlers::refunds::approve_refund,
+        crate::interfaces::http::handlers::refunds::reject_refund,
+        crate::interfaces::http::handlers::payments::payment_webhook,
+        // Coupons
+        crate::interfaces::http::handlers::coupons::validate_coupon,
+        crate::interfaces::http::handlers::coupons::create_coupon,
+        crate::interfaces::http::handlers::coupons::list_coupons,
+        crate::interfaces::http::handlers::coupons::get_coupon,
+        crate::interfaces::http::handlers::coupons::update_coupon,
+        // Learning paths
+        crate::interfaces::http::handlers::learning_paths::list_learning_paths,
+        crate::interfaces::http::handlers::learning_paths::create_learning_path,
+        crate::interfaces::http::handlers::learning_paths::get_learning_path,
+        crate::interfaces::http::handlers::learning_paths::get_learning_path_by_slug,
+        crate::interfaces::http::handlers::learning_paths::update_learning_path,
+        crate::interfaces::http::handlers::learning_paths::delete_learning_path,
+        crate::interfaces::http::handlers::learning_paths::set_learning_path_courses,
+        crate::interfaces::http::handlers::learning_paths::set_learning_path_prerequisites,
+        crate::interfaces::http::handlers::learning_paths::enroll_in_learning_path,
+        crate::interfaces::http::handlers::learning_paths::get_learning_path_progress,
+        // Analytics
+        crate::interfaces::http::handlers::analytics::get_course_analytics,
+        crate::interfaces::http::handlers::analytics::get_curriculum_analytics,
+        crate::interfaces::http::handlers::analytics::get_instructor_analytics,
+        // Media
+        crate::interfaces::http::handlers::media::private_download::download_private_media,
+        // Search
+        crate::interfaces::http::handlers::search::search,
     ),
     components(schemas(
             // Shared
@@ -61,18 +147,112 @@ use utoipa::{Modify, OpenApi};
             crate::types::users::request_type::UpdateUserRequest,
             crate::types::users::request_type::ResendOtpRequest,
             // Courses & content
+            crate::pkg::money::Money,
             crate::types::course_types::Course,
             crate::types::course_types::Instructor,
             crate::types::course_types::PaginationQuery,
+            crate::types::course_types::CourseCatalogQuery,
             crate::types::course_types::PageMeta,
             crate::types::course_types::CreateCourseRequest,
             crate::types::course_types::UpdateCourseRequest,
+            crate::types::course_types::ScheduleCoursePublishRequest,
+            crate::types::course_types::DuplicateCourseRequest,
+            crate::types::course_types::DuplicateMediaMode,
+            crate::types::course_types::PackageImportResult,
+            crate::types::course_types::PackageImportNotice,
+            crate::types::course_types::CoursePrice,
+            crate::types::course_types::CoursePriceList,
+            crate::types::course_types::SetCoursePriceRequest,
+            crate::types::course_types::CoursePrerequisite,
+            crate::types::course_types::SetCoursePrerequisitesRequest,
+            crate::types::course_types::CreateLessonContentInput,
+            crate::types::course_types::CreateQuestionOptionInput,
+            crate::types::course_types::CreateLessonQuestionInput,
+            crate::types::course_types::CreateLessonAssignmentInput,
+            crate::types::course_revision_types::RevisionCourseFields,
+            crate::types::course_revision_types::RevisionLesson,
+            crate::types::course_revision_types::RevisionLessonContent,
+            crate::types::course_revision_types::RevisionModule,
+            crate::types::course_revision_types::CourseRevisionContent,
+            crate::types::course_revision_types::CourseRevision,
+            crate::types::course_revision_types::RevisionFieldChange,
+            crate::types::course_revision_types::RevisionItemChange,
+            crate::types::course_revision_types::CourseRevisionDiff,
+            crate::types::course_review_types::CourseReviewAuthor,
+            crate::types::course_review_types::CourseReviewReply,
+            crate::types::course_review_types::CourseReview,
+            crate::types::course_review_types::CreateCourseReviewRequest,
+            crate::types::course_review_types::UpdateCourseReviewRequest,
+            crate::types::course_review_types::CourseReviewReplyRequest,
+            crate::types::course_review_types::RatingBucket,
+            crate::types::course_review_types::CourseRatingSummary,
+            crate::types::course_review_types::CourseReviewPage,
             crate::types::course_types::CourseModule,
+            crate::types::course_types::SetModuleDripRequest,
             crate::types::course_types::CreateModuleRequest,
             crate::types::course_types::UpdateModuleRequest,
             crate::types::course_types::Lesson,
             crate::types::course_types::CreateLessonRequest,
-            crate::types::course_types::UpdateLessonRequest
+            crate::types::course_types::UpdateLessonRequest,
+            crate::types::course_types::LessonContentDownload,
+            crate::types::course_types::StartLessonVideoUploadRequest,
+            crate::types::course_types::LessonVideoUpload,
+            crate::types::course_types::LessonVideo,
+            crate::types::course_types::LessonSubtitle,
+            crate::types::course_types::LessonTranscript,
+            // Progress and certificates
+            crate::types::certificate_types::Certificate,
+            crate::types::certificate_types::CertificateVerification,
+            crate::types::certificate_types::CourseProgress,
+            crate::types::certificate_types::QuizAnswerInput,
+            crate::types::certificate_types::SubmitQuizAttemptRequest,
+            crate::types::certificate_types::QuizAttemptResult,
+            // Orders
+            crate::types::order_types::CheckoutRequest,
+            crate::types::order_types::CheckoutResponse,
+            crate::types::order_types::Order,
+            crate::types::order_types::OrderItem,
+            crate::types::order_types::PaymentDetails,
+            crate::types::order_types::WebhookReceipt,
+            crate::types::order_types::OrderEvent,
+            crate::types::order_types::CreateRefundRequest,
+            crate::types::order_types::ReviewRefundRequest,
+            crate::types::order_types::RefundRequest,
+            // Coupons
+            crate::types::coupon_types::Coupon,
+            crate::types::coupon_types::CreateCouponRequest,
+            crate::types::coupon_types::UpdateCouponRequest,
+            crate::types::coupon_types::ValidateCouponRequest,
+            crate::types::coupon_types::CouponPreview,
+            crate::types::coupon_types::CouponPreviewItem,
+            // Learning paths
+            crate::types::learning_path_types::LearningPath,
+            crate::types::learning_path_types::PathPrerequisite,
+            crate::types::learning_path_types::LearningPathDetail,
+            crate::types::learning_path_types::CreateLearningPathRequest,
+            crate::types::learning_path_types::UpdateLearningPathRequest,
+            crate::types::learning_path_types::SetPathCoursesRequest,
+            crate::types::learning_path_types::LearningPathsQuery,
+            crate::types::learning_path_types::PathEnrollment,
+            crate::types::learning_path_types::PathEnrollmentResponse,
+            crate::types::learning_path_types::PathCourseProgress,
+            crate::types::learning_path_types::PathProgress,
+            // Analytics
+            crate::types::analytics_types::AnalyticsQuery,
+            crate::types::analytics_types::CourseAnalyticsTotals,
+            crate::types::analytics_types::CourseAnalyticsDay,
+            crate::types::analytics_types::RevenueSummary,
+            crate::types::analytics_types::RevenueDay,
+            crate::types::analytics_types::CourseAnalytics,
+            crate::types::analytics_types::ModuleFunnelStep,
+            crate::types::analytics_types::LessonEngagement,
+            crate::types::analytics_types::CurriculumAnalytics,
+            crate::types::analytics_types::InstructorCourseSummary,
+            crate::types::analytics_types::InstructorAnalytics,
+            // Search
+            crate::types::search_types::SearchQuery,
+            crate::types::search_types::SearchResult,
+            crate::types::search_types::SearchResponse
     )),
     modifiers(&ApiSecurity)
 )]
@@ -0,0 +1,42 @@
+//! Axum middleware resolving the client IP once per request
+//!
+//! Reads the socket address from `ConnectInfo` (the server must be started with
+//! `into_make_service_with_connect_info`) and stores the resolved [`ClientIp`]
+//! in request extensions for the rate limiter, request logger and handlers.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, State},
+    http::Request,
+    middleware::Next,
+    response::Response,
+};
+
+use crate::pkg::client_ip::{ClientIp, TrustedProxies};
+
+/// Middleware that resolves the client address; install it outside every
+/// layer that reads [`ClientIp`].
+pub async fn client_ip(
+    State(proxies): State<Arc<TrustedProxies>>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Response {
+    let peer = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let ip = proxies.resolve(peer, req.headers());
+    req.extensions_mut().insert(ClientIp(ip));
+    next.run(req).await
+}
+
+/// Client IP recorded by [`client_ip`], `unknown` when the middleware did not run
+pub fn request_client_ip<B>(req: &Request<B>) -> ClientIp {
+    req.extensions()
+        .get::<ClientIp>()
+        .copied()
+        .unwrap_or(ClientIp(None))
+}
@@ -1,33 +1,84 @@
 //! Axum rate limit middleware built on `pkg::rate_limit`
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use axum::{body::Body, extract::State, http::Request, middleware::Next, response::Response};
 use http::HeaderValue;
 
+use super::axum_client_ip::request_client_ip;
+use crate::pkg::auth::request_token;
 use crate::pkg::logger::warn;
-use crate::pkg::rate_limit::{InMemorySlidingWindowRateLimiter, RateLimitConfig};
+use crate::pkg::rate_limit::{
+    InMemorySlidingWindowRateLimiter, RateLimitKey, RateLimitPolicies, RateLimitPolicy,
+    RateLimiter, RedisSlidingWindowRateLimiter,
+};
+use crate::pkg::redis::RedisManager;
 use crate::pkg::response::ApiErrorResponse;
+use crate::pkg::security::security_services::JwtService;
 
 /// Shared limiter state for the middleware
 #[derive(Clone)]
 pub struct RateLimitState {
-    limiter: Arc<InMemorySlidingWindowRateLimiter>,
+    policies: Arc<RateLimitPolicies>,
+    /// One limiter per policy name
+    limiters: Arc<HashMap<String, Arc<dyn RateLimiter>>>,
+    /// Verifies access tokens so authenticated callers get their own quota
+    jwt: Option<Arc<dyn JwtService>>,
 }
 
 impl RateLimitState {
+    /// Process-local limiter; each replica enforces its own quota.
     pub fn new(limit: u32, window_secs: u64) -> Self {
-        let limiter = InMemorySlidingWindowRateLimiter::new(RateLimitConfig::new(
-            limit,
-            std::time::Duration::from_secs(window_secs),
-        ));
+        Self::with_policies(RateLimitPolicies::single(limit, window_secs), |p| {
+            Arc::new(InMemorySlidingWindowRateLimiter::new(p.config()))
+        })
+    }
+
+    /// Limiters shared by all replicas through Redis, keyed under `prefix:<policy>`.
+    pub fn redis(redis: Arc<RedisManager>, prefix: &str, policies: RateLimitPolicies) -> Self {
+        Self::with_policies(policies, |p| {
+            Arc::new(RedisSlidingWindowRateLimiter::new(
+                redis.clone(),
+                p.config(),
+                format!("{}:{}", prefix, p.name),
+            ))
+        })
+    }
+
+    /// Build one limiter per policy with `make_limiter`.
+    pub fn with_policies(
+        policies: RateLimitPolicies,
+        make_limiter: impl Fn(&RateLimitPolicy) -> Arc<dyn RateLimiter>,
+    ) -> Self {
+        let limiters = policies
+            .all()
+            .filter(|p| !p.is_unlimited())
+            .map(|p| (p.name.clone(), make_limiter(p)))
+            .collect();
         Self {
-            limiter: Arc::new(limiter),
+            policies: Arc::new(policies),
+            limiters: Arc::new(limiters),
+            jwt: None,
         }
     }
+
+    /// Key `user_or_ip` policies by the token's user id when one is presented.
+    pub fn with_auth(mut self, jwt: Arc<dyn JwtService>) -> Self {
+        self.jwt = Some(jwt);
+        self
+    }
+
+    fn user_id(&self, headers: &http::HeaderMap) -> Option<String> {
+        let token = request_token(headers)?;
+        let claims = self.jwt.as_ref()?.verify(&token).ok()?;
+        uuid::Uuid::parse_str(&claims.sub)
+            .ok()
+            .map(|id| id.to_string())
+    }
 }
 
-/// Middleware function that enforces sliding-window rate limit per ip+path.
+/// Middleware function that enforces the sliding-window policy matching the route.
 pub async fn rate_limit(
     State(state): State<RateLimitState>,
     req: Request<Body>,
@@ -41,24 +92,42 @@ pub async fn rate_limit(
     if method == http::Method::OPTIONS || method == http::Method::HEAD {
         return next.run(req).await;
     }
-    // Prefer X-Forwarded-For, then X-Real-IP, else unknown
+    let client_ip = request_client_ip(&req);
+    if client_ip
+        .0
+        .is_some_and(|addr| state.policies.is_allowlisted(addr))
+    {
+        return next.run(req).await;
+    }
+    let ip = client_ip.to_key();
     let headers = req.headers();
-    let ip = headers
-        .get("x-forwarded-for")
-        .and_then(|h| h.to_str().ok())
-        .or_else(|| headers.get("x-real-ip").and_then(|h| h.to_str().ok()))
-        .unwrap_or("unknown");
     let path = req.uri().path();
-    let key = InMemorySlidingWindowRateLimiter::compose_key(&["ip", ip, "path", path]);
-    let decision = state.limiter.check_and_consume(&key);
+    let policy = state.policies.resolve(method.as_str(), path);
+    let Some(limiter) = state.limiters.get(&policy.name) else {
+        return next.run(req).await;
+    };
+    let user_id = match policy.key {
+        RateLimitKey::UserOrIp => state.user_id(headers),
+        RateLimitKey::Ip => None,
+    };
+    let caller = match user_id.as_deref() {
+        Some(id) => ("user", id),
+        None => ("ip", ip.as_str()),
+    };
+    let key = state.policies.counter_key(policy, caller, path);
+    let decision = limiter.check_and_consume(&key).await;
 
     // We attach rate-limit headers on the response path below
 
     if !decision.allowed {
         let retry_secs = decision.retry_after.map(|d| d.as_secs()).unwrap_or(1);
         warn(format!(
-            "Rate limited ip={} path={} retry_after_secs={}",
-            ip, path, retry_secs
+            "Rate limited policy={} ip={} user={} path={} retry_after_secs={}",
+            policy.name,
+            ip,
+            user_id.as_deref().unwrap_or("-"),
+            path,
+            retry_secs
         ));
 
         let response = axum::http::Response::builder()
@@ -13,6 +13,7 @@ use axum::{
     response::Response,
 };
 
+use super::axum_client_ip::request_client_ip;
 use crate::pkg::logger::{LogLevel, get_logger};
 
 /// Middleware that logs each HTTP request/response with basic metadata.
@@ -29,13 +30,8 @@ pub async fn request_logger(req: Request<Body>, next: Next) -> Response {
     // Determine request type based on path and content type
     let request_type = determine_request_type(&path, headers);
 
-    // Prefer X-Forwarded-For, then X-Real-IP, otherwise unknown.
-    let ip = headers
-        .get("x-forwarded-for")
-        .and_then(|h| h.to_str().ok())
-        .or_else(|| headers.get("x-real-ip").and_then(|h| h.to_str().ok()))
-        .unwrap_or("unknown")
-        .to_string();
+    // Resolved by the client IP middleware from trusted proxy headers
+    let ip = request_client_ip(&req).to_key();
 
     let user_agent = headers
         .get(axum::http::header::USER_AGENT)
@@ -26,32 +26,7 @@ where
             .cloned()
             .ok_or_else(|| AppError::Internal("Missing application context".into()))?;
 
-        // First try to get token from cookies (access_token)
-        let token = parts
-            .headers
-            .get(axum::http::header::COOKIE)
-            .and_then(|cookie_header| {
-                cookie_header.to_str().ok().and_then(|cookies| {
-                    cookies.split(';').find_map(|cookie| {
-                        let cookie = cookie.trim();
-                        if cookie.starts_with("access_token=") {
-                            Some(cookie[13..].to_string())
-                        } else {
-                            None
-                        }
-                    })
-                })
-            })
-            // If no token in cookies, try Authorization header
-            .or_else(|| {
-                parts
-                    .headers
-                    .get(axum::http::header::AUTHORIZATION)
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|auth_header| {
-                        auth_header.strip_prefix("Bearer ").map(|s| s.to_string())
-                    })
-            })
+        let token = request_token(&parts.headers)
             .ok_or_else(|| AppError::Unauthorized("Missing authentication token".into()))?;
 
         let claims: crate::pkg::security::Claims = ctx
@@ -67,6 +42,28 @@ where
     }
 }
 
+/// Access token from the `access_token` cookie, else the `Authorization: Bearer` header
+pub(crate) fn request_token(headers: &axum::http::HeaderMap) -> Option<String> {
+    headers
+        .get(axum::http::header::COOKIE)
+        .and_then(|cookie_header| {
+            cookie_header.to_str().ok().and_then(|cookies| {
+                cookies.split(';').find_map(|cookie| {
+                    cookie
+                        .trim()
+                        .strip_prefix("access_token=")
+                        .map(|s| s.to_string())
+                })
+            })
+        })
+        .or_else(|| {
+            headers
+                .get(axum::http::header::AUTHORIZATION)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|auth_header| auth_header.strip_prefix("Bearer ").map(|s| s.to_string()))
+        })
+}
+
 impl AuthUser {
     pub fn require_admin(&self) -> Result<(), AppError> {
         if self.role != "admin" {
@@ -75,3 +72,28 @@ impl AuthUser {
         Ok(())
     }
 }
+
+/// Like `AuthUser`, but for endpoints that also serve anonymous visitors.
+///
+/// Missing or invalid tokens yield `None` instead of rejecting the request,
+/// so a stale cookie never locks someone out of public content.
+#[derive(Debug, Clone)]
+pub struct OptionalAuthUser(pub Option<AuthUser>);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for OptionalAuthUser
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match AuthUser::from_request_parts(parts, state).await {
+            Ok(user) => Ok(OptionalAuthUser(Some(user))),
+            Err(AppError::Unauthorized(_)) | Err(AppError::BadRequest(_)) => {
+                Ok(OptionalAuthUser(None))
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! Client IP resolution behind reverse proxies
+//!
+//! Forwarding headers are only believed when the socket peer is one of the
+//! configured trusted proxies. The proxy chain (RFC 7239 `Forwarded`, else
+//! `X-Forwarded-For`, else `X-Real-IP`) is then walked from the right, skipping
+//! trusted hops; the first untrusted hop is the client. Entries to the left of
+//! it were written by the client itself and are ignored, so spoofed headers
+//! cannot pick the address used for rate limiting or logging.
+
+use std::net::{IpAddr, SocketAddr};
+
+use http::HeaderMap;
+use ipnet::IpNet;
+
+/// Resolved client address, stored in request extensions by the client IP middleware
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIp(pub Option<IpAddr>);
+
+impl ClientIp {
+    /// Address as text, or `unknown` when it could not be determined
+    pub fn to_key(&self) -> String {
+        self.0
+            .map(|ip| ip.to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+}
+
+/// Networks whose forwarding headers are believed (e.g. our nginx)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustedProxies {
+    nets: Vec<IpNet>,
+}
+
+impl TrustedProxies {
+    pub fn new(nets: Vec<IpNet>) -> Self {
+        Self { nets }
+    }
+
+    pub fn is_trusted(&self, ip: IpAddr) -> bool {
+        self.nets.iter().any(|net| net.contains(&ip))
+    }
+
+    /// Client address for a connection from `peer` carrying `headers`.
+    ///
+    /// Without a peer address (no `ConnectInfo`) nothing can be trusted and
+    /// the result is `None`.
+    pub fn resolve(&self, peer: Option<IpAddr>, headers: &HeaderMap) -> Option<IpAddr> {
+        let peer = peer?;
+        if !self.is_trusted(peer) {
+            return Some(peer);
+        }
+
+        let chain = forwarded_chain(headers);
+        let mut client = peer;
+        for hop in chain.iter().rev() {
+            match hop {
+                // Unparseable or obfuscated hop: the last trusted hop is all we know
+                None => return Some(client),
+                Some(ip) => {
+                    client = *ip;
+                    if !self.is_trusted(*ip) {
+                        return Some(client);
+                    }
+                }
+            }
+        }
+        Some(client)
+    }
+}
+
+/// Proxy chain in left-to-right order; `None` marks hops that are not IPs
+fn forwarded_chain(headers: &HeaderMap) -> Vec<Option<IpAddr>> {
+    let values = |name: &str| {
+        headers
+            .get_all(name)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    let forwarded = values("forwarded");
+    if !forwarded.is_empty() {
+        return forwarded
+            .iter()
+            .map(|element| {
+                element
+                    .split(';')
+                    .filter_map(|pair| pair.split_once('='))
+                    .find(|(k, _)| k.trim().eq_ignore_ascii_case("for"))
+                    .and_then(|(_, v)| parse_node(v.trim().trim_matches('"')))
+            })
+            .collect();
+    }
+
+    let xff = values("x-forwarded-for");
+    if !xff.is_empty() {
+        return xff.iter().map(|v| parse_node(v)).collect();
+    }
+
+    values("x-real-ip")
+        .first()
+        .map(|v| vec![parse_node(v)])
+        .unwrap_or_default()
+}
+
+/// Parse `1.2.3.4`, `1.2.3.4:80`, `::1` or `[::1]:80`
+fn parse_node(value: &str) -> Option<IpAddr> {
+    if let Ok(ip) = value.parse::<IpAddr>() {
+        return Some(ip);
+    }
+    if let Ok(addr) = value.parse::<SocketAddr>() {
+        return Some(addr.ip());
+    }
+    value
+        .strip_prefix('[')
+        .and_then(|v| v.split(']').next())
+        .and_then(|v| v.parse().ok())
+}
+
+/// Parse a CIDR, accepting bare addresses as single-host networks
+pub fn parse_ip_net(value: &str) -> Option<IpNet> {
+    value
+        .parse::<IpNet>()
+        .ok()
+        .or_else(|| value.parse::<IpAddr>().ok().map(IpNet::from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    fn proxies() -> TrustedProxies {
+        TrustedProxies::new(vec![
+            parse_ip_net("10.0.0.0/8").unwrap(),
+            parse_ip_net("::1").unwrap(),
+        ])
+    }
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut h = HeaderMap::new();
+        for (k, v) in pairs {
+            h.append(*k, HeaderValue::from_static(v));
+        }
+        h
+    }
+
+    fn ip(s: &str) -> Option<IpAddr> {
+        Some(s.parse().unwrap())
+    }
+
+    #[test]
+    fn ignores_headers_from_untrusted_peers() {
+        let h = headers(&[("x-forwarded-for", "1.1.1.1")]);
+        assert_eq!(proxies().resolve(ip("203.0.113.9"), &h), ip("203.0.113.9"));
+        assert_eq!(proxies().resolve(None, &h), None);
+    }
+
+    #[test]
+    fn takes_right_most_untrusted_forwarded_for_hop() {
+        // Client spoofed 6.6.6.6; our proxies appended the real address then 10.0.0.2
+        let h = headers(&[
+            ("x-forwarded-for", "6.6.6.6, 198.51.100.7"),
+            ("x-forwarded-for", "10.0.0.2"),
+        ]);
+        assert_eq!(proxies().resolve(ip("10.0.0.1"), &h), ip("198.51.100.7"));
+
+        let h = headers(&[("x-forwarded-for", "garbage, 10.0.0.3")]);
+        assert_eq!(proxies().resolve(ip("10.0.0.1"), &h), ip("10.0.0.3"));
+
+        let h = headers(&[("x-real-ip", "198.51.100.8")]);
+        assert_eq!(proxies().resolve(ip("::1"), &h), ip("198.51.100.8"));
+    }
+
+    #[test]
+    fn parses_rfc7239_forwarded() {
+        let h = headers(&[
+            (
+                "forwarded",
+                "for=6.6.6.6, for=\"[2001:db8:cafe::17]:4711\";proto=https",
+            ),
+            ("forwarded", "for=10.0.0.9:80;by=10.0.0.1"),
+            ("x-forwarded-for", "198.51.100.1"),
+        ]);
+        assert_eq!(
+            proxies().resolve(ip("10.0.0.1"), &h),
+            ip("2001:db8:cafe::17")
+        );
+
+        let h = headers(&[("forwarded", "for=_hidden")]);
+        assert_eq!(proxies().resolve(ip("10.0.0.1"), &h), ip("10.0.0.1"));
+    }
+}
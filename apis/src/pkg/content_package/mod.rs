@@ -0,0 +1,760 @@
+//! SCORM and IMS Common Cartridge packages
+//!
+//! Both formats are IMS content packages: a ZIP with an `imsmanifest.xml` at
+//! its root listing organizations (tables of contents, as trees of items)
+//! and resources (the files an item launches). `parse_package` maps the
+//! default organization onto our course shape: top-level items become
+//! modules and the leaf items below them lessons. Anything that cannot be
+//! represented is reported in `Package::log` rather than failing the import.
+//!
+//! SCORM runtime tracking is not supported; a SCO is imported as a lesson
+//! content linking to its launch page, with every file it depends on.
+
+pub mod qti;
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use roxmltree::{Document, Node, ParsingOptions};
+
+use crate::pkg::error::{AppError, AppResult};
+
+/// Location of the manifest inside a package
+pub const MANIFEST_PATH: &str = "imsmanifest.xml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageKind {
+    Scorm12,
+    Scorm2004,
+    CommonCartridge,
+    /// A plain IMS content package without SCORM or CC metadata
+    ContentPackage,
+}
+
+impl PackageKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Scorm12 => "scorm_1.2",
+            Self::Scorm2004 => "scorm_2004",
+            Self::CommonCartridge => "common_cartridge",
+            Self::ContentPackage => "ims_content_package",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Package {
+    pub kind: PackageKind,
+    pub title: String,
+    pub description: Option<String>,
+    pub modules: Vec<PackageModule>,
+    /// Package paths the lessons need: launch files and everything they depend on
+    pub assets: BTreeSet<String>,
+    pub log: Vec<PackageNotice>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PackageModule {
+    pub title: String,
+    pub lessons: Vec<PackageLesson>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PackageLesson {
+    pub title: String,
+    pub source: LessonSource,
+}
+
+#[derive(Debug, Clone)]
+pub enum LessonSource {
+    /// A file in the package (e.g. a SCO's launch page) and the query string to open it with
+    File { path: String, query: Option<String> },
+    /// An external web link
+    Link { url: String },
+    /// Choice questions converted from QTI
+    Quiz(Vec<qti::ChoiceQuestion>),
+}
+
+/// An item or file that was skipped during an import
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageNotice {
+    /// Identifier of the manifest item or resource concerned
+    pub item: Option<String>,
+    pub message: String,
+}
+
+/// Parse the manifest of an inflated package (`files` keyed by ZIP path).
+pub fn parse_package(files: &HashMap<String, Vec<u8>>) -> AppResult<Package> {
+    let manifest = files.get(MANIFEST_PATH).ok_or_else(|| {
+        AppError::BadRequest(format!("Package has no {} at its root", MANIFEST_PATH))
+    })?;
+    let text = std::str::from_utf8(manifest)
+        .map_err(|_| AppError::BadRequest(format!("{} is not valid UTF-8", MANIFEST_PATH)))?;
+    let doc = parse_xml(text)
+        .map_err(|e| AppError::BadRequest(format!("Invalid {}: {}", MANIFEST_PATH, e)))?;
+    let root = doc.root_element();
+    if root.tag_name().name() != "manifest" {
+        return Err(AppError::BadRequest(format!(
+            "{} has no <manifest> root element",
+            MANIFEST_PATH
+        )));
+    }
+
+    let mut parser = ManifestParser {
+        files,
+        resources: HashMap::new(),
+        assets: BTreeSet::new(),
+        visited: HashSet::new(),
+        log: Vec::new(),
+    };
+    parser.read_resources(root);
+
+    let organizations = child(root, "organizations");
+    let all: Vec<Node> = organizations
+        .map(|o| elements(o, "organization").collect())
+        .unwrap_or_default();
+    let default_id = organizations.and_then(|o| o.attribute("default"));
+    let organization = all
+        .iter()
+        .find(|o| default_id.is_some() && o.attribute("identifier") == default_id)
+        .or(all.first())
+        .copied()
+        .ok_or_else(|| AppError::BadRequest("Package has no organization to import".into()))?;
+    if all.len() > 1 {
+        parser.notice(
+            None,
+            format!(
+                "Only the default organization was imported; {} other(s) skipped",
+                all.len() - 1
+            ),
+        );
+    }
+
+    let lom = child(root, "metadata").and_then(|m| {
+        m.descendants()
+            .find(|n| n.is_element() && n.tag_name().name() == "general")
+    });
+    let title = child_text(organization, "title")
+        .or_else(|| lom.and_then(|g| lom_text(g, "title")))
+        .unwrap_or_else(|| "Imported course".to_string());
+    let description = lom.and_then(|g| lom_text(g, "description"));
+
+    // Cartridges wrap the whole outline in one container item (`LearningModules`)
+    let mut top: Vec<Node> = parser.items(organization);
+    while let [wrapper] = top.as_slice() {
+        let has_containers = elements(*wrapper, "item").any(has_items);
+        if wrapper.attribute("identifierref").is_some() || !has_containers {
+            break;
+        }
+        top = parser.items(*wrapper);
+    }
+
+    let mut modules: Vec<PackageModule> = Vec::new();
+    let mut loose: Vec<PackageLesson> = Vec::new();
+    for item in top {
+        if has_items(item) {
+            if !loose.is_empty() {
+                modules.push(PackageModule {
+                    title: title.clone(),
+                    lessons: std::mem::take(&mut loose),
+                });
+            }
+            let mut lessons = Vec::new();
+            parser.collect_lessons(item, &mut lessons);
+            if lessons.is_empty() {
+                parser.notice(
+                    item.attribute("identifier"),
+                    format!(
+                        "Module '{}' skipped: nothing in it could be imported",
+                        item_title(item)
+                    ),
+                );
+                continue;
+            }
+            modules.push(PackageModule {
+                title: item_title(item),
+                lessons,
+            });
+        } else if let Some(lesson) = parser.lesson(item) {
+            loose.push(lesson);
+        }
+    }
+    if !loose.is_empty() {
+        modules.push(PackageModule {
+            title: title.clone(),
+            lessons: loose,
+        });
+    }
+
+    Ok(Package {
+        kind: detect_kind(root),
+        title,
+        description,
+        modules,
+        assets: parser.assets,
+        log: parser.log,
+    })
+}
+
+/// Rough content type of a package file from its extension
+pub fn content_type(path: &str) -> &'static str {
+    let ext = path
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "html" | "htm" => "text/html",
+        "txt" => "text/plain",
+        "pdf" => "application/pdf",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "ppt" => "application/vnd.ms-powerpoint",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "xls" => "application/vnd.ms-excel",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "mp3" => "audio/mpeg",
+        _ => "application/octet-stream",
+    }
+}
+
+struct Resource {
+    /// `type` attribute, lowercased
+    kind: String,
+    /// `adlcp:scormtype` (SCORM 1.2) / `adlcp:scormType` (SCORM 2004)
+    scorm_type: Option<String>,
+    base: String,
+    href: Option<String>,
+    files: Vec<String>,
+    dependencies: Vec<String>,
+}
+
+struct ManifestParser<'a> {
+    files: &'a HashMap<String, Vec<u8>>,
+    resources: HashMap<String, Resource>,
+    assets: BTreeSet<String>,
+    /// Resources whose files were already added to `assets`
+    visited: HashSet<String>,
+    log: Vec<PackageNotice>,
+}
+
+impl ManifestParser<'_> {
+    fn notice(&mut self, item: Option<&str>, message: String) {
+        self.log.push(PackageNotice {
+            item: item.map(str::to_string),
+            message,
+        });
+    }
+
+    fn read_resources(&mut self, root: Node) {
+        let Some(resources) = child(root, "resources") else {
+            return;
+        };
+        let outer = [xml_base(root), xml_base(resources)].concat();
+        for resource in elements(resources, "resource") {
+            let Some(id) = resource.attribute("identifier") else {
+                continue;
+            };
+            let base = [outer.clone(), xml_base(resource)].concat();
+            self.resources.insert(
+                id.to_string(),
+                Resource {
+                    kind: resource
+                        .attribute("type")
+                        .unwrap_or_default()
+                        .to_ascii_lowercase(),
+                    scorm_type: resource
+                        .attributes()
+                        .find(|a| a.name().eq_ignore_ascii_case("scormtype"))
+                        .map(|a| a.value().to_ascii_lowercase()),
+                    href: resource.attribute("href").map(str::to_string),
+                    files: elements(resource, "file")
+                        .filter_map(|f| f.attribute("href"))
+                        .filter_map(|href| resolve_href(&base, href))
+                        .collect(),
+                    dependencies: elements(resource, "dependency")
+                        .filter_map(|d| d.attribute("identifierref"))
+                        .map(str::to_string)
+                        .collect(),
+                    base,
+                },
+            );
+        }
+    }
+
+    /// Visible child items; hidden ones are reported and left out
+    fn items<'n, 'i>(&mut self, parent: Node<'n, 'i>) -> Vec<Node<'n, 'i>> {
+        let mut visible = Vec::new();
+        for item in elements(parent, "item") {
+            if item.attribute("isvisible") == Some("false") {
+                self.notice(
+                    item.attribute("identifier"),
+                    format!("Hidden item '{}' skipped", item_title(item)),
+                );
+            } else {
+                visible.push(item);
+            }
+        }
+        visible
+    }
+
+    /// Lessons for `item` and every item below it, depth first
+    fn collect_lessons(&mut self, item: Node, lessons: &mut Vec<PackageLesson>) {
+        if item.attribute("identifierref").is_some() {
+            lessons.extend(self.lesson(item));
+        }
+        for child in self.items(item) {
+            self.collect_lessons(child, lessons);
+        }
+    }
+
+    fn lesson(&mut self, item: Node) -> Option<PackageLesson> {
+        let id = item.attribute("identifier");
+        let title = item_title(item);
+        let Some(reference) = item.attribute("identifierref") else {
+            self.notice(id, format!("Item '{}' skipped: it has no content", title));
+            return None;
+        };
+        let Some(resource) = self.resources.get(reference) else {
+            self.notice(
+                id,
+                format!(
+                    "Item '{}' skipped: resource '{}' is not in the manifest",
+                    title, reference
+                ),
+            );
+            return None;
+        };
+
+        let kind = resource.kind.clone();
+        let source = if kind.starts_with("imswl_") {
+            self.web_link(reference, &title, id)?
+        } else if kind.contains("qti") || kind.ends_with("/assessment") {
+            self.quiz(reference, &title, id)?
+        } else if kind == "webcontent" || resource.scorm_type.is_some() {
+            self.launch_file(reference, item, &title, id)?
+        } else {
+            self.notice(
+                id,
+                format!(
+                    "Item '{}' skipped: resource type '{}' is not supported",
+                    title, resource.kind
+                ),
+            );
+            return None;
+        };
+        Some(PackageLesson { title, source })
+    }
+
+    fn launch_file(
+        &mut self,
+        reference: &str,
+        item: Node,
+        title: &str,
+        id: Option<&str>,
+    ) -> Option<LessonSource> {
+        let resource = &self.resources[reference];
+        let (path, query) = match resource.href.as_deref() {
+            Some(href) => {
+                let (file, query) = split_query(href);
+                (resolve_href(&resource.base, file), query)
+            }
+            None => (resource.files.first().cloned(), None),
+        };
+        let Some(path) = path.filter(|p| self.files.contains_key(p)) else {
+            self.notice(
+                id,
+                format!("Item '{}' skipped: its launch file is missing", title),
+            );
+            return None;
+        };
+
+        // SCORM items may pass launch parameters to the SCO
+        let query = match (query, item.attribute("parameters")) {
+            (query, None) => query,
+            (None, Some(params)) => Some(params.trim_start_matches(['?', '&']).to_string()),
+            (Some(query), Some(params)) => Some(format!(
+                "{}&{}",
+                query,
+                params.trim_start_matches(['?', '&'])
+            )),
+        }
+        .filter(|q| !q.is_empty());
+
+        self.assets.insert(path.clone());
+        self.add_assets(reference);
+        Some(LessonSource::File { path, query })
+    }
+
+    /// Add a resource's files, and those of its dependencies, to `assets`
+    fn add_assets(&mut self, reference: &str) {
+        if !self.visited.insert(reference.to_string()) {
+            return;
+        }
+        let Some(resource) = self.resources.get(reference) else {
+            self.notice(
+                Some(reference),
+                format!("Dependency '{}' is not in the manifest", reference),
+            );
+            return;
+        };
+        let (files, dependencies) = (resource.files.clone(), resource.dependencies.clone());
+        for file in files {
+            if self.files.contains_key(&file) {
+                self.assets.insert(file);
+            } else {
+                self.notice(
+                    Some(reference),
+                    format!("File '{}' is listed but missing from the package", file),
+                );
+            }
+        }
+        for dependency in dependencies {
+            self.add_assets(&dependency);
+        }
+    }
+
+    /// Text of the resource's first file
+    fn resource_xml(&mut self, reference: &str, title: &str, id: Option<&str>) -> Option<String> {
+        let resource = &self.resources[reference];
+        let path = resource
+            .files
+            .first()
+            .cloned()
+            .or_else(|| resolve_href(&resource.base, resource.href.as_deref()?));
+        let text = path
+            .and_then(|p| self.files.get(&p))
+            .and_then(|bytes| String::from_utf8(bytes.clone()).ok());
+        if text.is_none() {
+            self.notice(
+                id,
+                format!("Item '{}' skipped: its resource file is missing", title),
+            );
+        }
+        text
+    }
+
+    fn web_link(&mut self, reference: &str, title: &str, id: Option<&str>) -> Option<LessonSource> {
+        let xml = self.resource_xml(reference, title, id)?;
+        let url = parse_xml(&xml).ok().and_then(|doc| {
+            doc.descendants()
+                .find(|n| n.is_element() && n.tag_name().name() == "url")
+                .and_then(|n| n.attribute("href"))
+                .map(str::to_string)
+        });
+        match url.filter(|u| u.starts_with("http://") || u.starts_with("https://")) {
+            Some(url) => Some(LessonSource::Link { url }),
+            None => {
+                self.notice(
+                    id,
+                    format!("Item '{}' skipped: web link has no http(s) URL", title),
+                );
+                None
+            }
+        }
+    }
+
+    fn quiz(&mut self, reference: &str, title: &str, id: Option<&str>) -> Option<LessonSource> {
+        let xml = self.resource_xml(reference, title, id)?;
+        let items = match qti::parse(&xml) {
+            Ok(items) => items,
+            Err(reason) => {
+                self.notice(id, format!("Item '{}' skipped: {}", title, reason));
+                return None;
+            }
+        };
+        for message in items.unsupported {
+            self.notice(id, message);
+        }
+        if items.questions.is_empty() {
+            self.notice(
+                id,
+                format!("Item '{}' skipped: it has no supported questions", title),
+            );
+            return None;
+        }
+        Some(LessonSource::Quiz(items.questions))
+    }
+}
+
+fn detect_kind(root: Node) -> PackageKind {
+    let metadata = child(root, "metadata");
+    let schema = metadata
+        .and_then(|m| child_text(m, "schema"))
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let version = metadata
+        .and_then(|m| child_text(m, "schemaversion"))
+        .unwrap_or_default();
+    let namespaces: Vec<&str> = root.namespaces().map(|ns| ns.uri()).collect();
+
+    if schema.contains("common cartridge") || namespaces.iter().any(|ns| ns.contains("imscc")) {
+        PackageKind::CommonCartridge
+    } else if schema.contains("scorm") || namespaces.iter().any(|ns| ns.contains("adlcp")) {
+        if version == "1.2" || namespaces.iter().any(|ns| ns.contains("adlcp_rootv1p2")) {
+            PackageKind::Scorm12
+        } else {
+            PackageKind::Scorm2004
+        }
+    } else {
+        PackageKind::ContentPackage
+    }
+}
+
+/// Join a manifest href onto `base` and normalise it into a package path.
+/// Returns `None` for external URLs and paths escaping the package.
+fn resolve_href(base: &str, href: &str) -> Option<String> {
+    if href.contains("://") || href.starts_with('/') || href.contains('\\') {
+        return None;
+    }
+    let decoded = urlencoding::decode(href).ok()?;
+    let mut parts: Vec<&str> = Vec::new();
+    for segment in base.split('/').chain(decoded.split('/')) {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            segment => parts.push(segment),
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+fn split_query(href: &str) -> (&str, Option<String>) {
+    let file_end = href.find(['?', '#']).unwrap_or(href.len());
+    let query = href[file_end..]
+        .strip_prefix('?')
+        .map(|q| q.split('#').next().unwrap_or_default().to_string());
+    (&href[..file_end], query)
+}
+
+/// `xml:base` of a node as a path prefix
+fn xml_base(node: Node) -> String {
+    match node.attribute((roxmltree::NS_XML_URI, "base")) {
+        Some(base) if !base.ends_with('/') => format!("{}/", base),
+        Some(base) => base.to_string(),
+        None => String::new(),
+    }
+}
+
+fn has_items(node: Node) -> bool {
+    elements(node, "item").next().is_some()
+}
+
+fn item_title(item: Node) -> String {
+    child_text(item, "title").unwrap_or_else(|| "Untitled".to_string())
+}
+
+/// LOM strings are wrapped in `<string>` (one per language) or `<langstring>`;
+/// the first one is used
+fn lom_text(general: Node, name: &str) -> Option<String> {
+    let node = child(general, name)?;
+    let text = node
+        .descendants()
+        .find(|n| matches!(n.tag_name().name(), "string" | "langstring"))
+        .unwrap_or(node)
+        .text()
+        .map(plain_text)?;
+    (!text.is_empty()).then_some(text)
+}
+
+pub(crate) fn parse_xml(text: &str) -> Result<Document<'_>, roxmltree::Error> {
+    let options = ParsingOptions {
+        allow_dtd: true,
+        ..ParsingOptions::default()
+    };
+    Document::parse_with_options(text.trim_start_matches('\u{feff}'), options)
+}
+
+fn elements<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    node.children()
+        .filter(move |n| n.is_element() && n.tag_name().name() == name)
+}
+
+pub(crate) fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|n| n.is_element() && n.tag_name().name() == name)
+}
+
+pub(crate) fn child_text(node: Node, name: &str) -> Option<String> {
+    let text = plain_text(child(node, name)?.text()?);
+    (!text.is_empty()).then_some(text)
+}
+
+/// Text with markup removed, common entities decoded and whitespace collapsed
+pub(crate) fn plain_text(text: &str) -> String {
+    const BLOCK_TAGS: &[&str] = &["p", "br", "div", "li", "tr", "td", "h1", "h2", "h3", "h4"];
+    let mut out = String::with_capacity(text.len());
+    let mut tag: Option<String> = None;
+    for ch in text.chars() {
+        match (&mut tag, ch) {
+            (None, '<') => tag = Some(String::new()),
+            (Some(name), '>') => {
+                let name = name.trim_start_matches('/').to_ascii_lowercase();
+                let name = name.split([' ', '/']).next().unwrap_or_default();
+                if BLOCK_TAGS.contains(&name) {
+                    out.push(' ');
+                }
+                tag = None;
+            }
+            (Some(name), c) => name.push(c),
+            (None, c) => out.push(c),
+        }
+    }
+    let decoded = out
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    decoded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(entries: &[(&str, &str)]) -> HashMap<String, Vec<u8>> {
+        entries
+            .iter()
+            .map(|(name, body)| (name.to_string(), body.as_bytes().to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn maps_a_common_cartridge_outline() {
+        let manifest = r#"<?xml version="1.0" encoding="UTF-8"?>
+<manifest identifier="cc" xmlns="http://www.imsglobal.org/xsd/imsccv1p1/imscp_v1p1">
+  <metadata><schema>IMS Common Cartridge</schema><schemaversion>1.1.0</schemaversion>
+    <lomimscc:lom xmlns:lomimscc="http://ltsc.ieee.org/xsd/imsccv1p1/LOM/manifest"><lomimscc:general>
+      <lomimscc:title><lomimscc:string>Chemistry</lomimscc:string></lomimscc:title>
+    </lomimscc:general></lomimscc:lom></metadata>
+  <organizations><organization identifier="org" structure="rooted-hierarchy">
+    <item identifier="LearningModules">
+      <item identifier="m1"><title>Week 1</title>
+        <item identifier="i1" identifierref="r1"><title>Reading</title></item>
+        <item identifier="i2" identifierref="r2"><title>Video</title></item>
+        <item identifier="i3" identifierref="r3"><title>Quiz</title></item>
+        <item identifier="i4" identifierref="r4"><title>Discuss</title></item>
+      </item>
+    </item>
+  </organization></organizations>
+  <resources>
+    <resource identifier="r1" type="webcontent" href="week1/read%20me.html">
+      <file href="week1/read%20me.html"/><dependency identifierref="shared"/></resource>
+    <resource identifier="shared" type="webcontent"><file href="css/site.css"/></resource>
+    <resource identifier="r2" type="imswl_xmlv1p1"><file href="wl.xml"/></resource>
+    <resource identifier="r3" type="imsqti_xmlv1p2/imscc_xmlv1p1/assessment"><file href="q/quiz.xml"/></resource>
+    <resource identifier="r4" type="imsdt_xmlv1p1"><file href="dt.xml"/></resource>
+  </resources>
+</manifest>"#;
+        let quiz = r#"<questestinterop><assessment ident="a"><section ident="s">
+  <item ident="q" title="Water"><presentation><material><mattext>H2O is?</mattext></material>
+    <response_lid ident="r"><render_choice>
+      <response_label ident="a"><material><mattext>Water</mattext></material></response_label>
+      <response_label ident="b"><material><mattext>Salt</mattext></material></response_label>
+    </render_choice></response_lid></presentation>
+    <resprocessing><respcondition><conditionvar><varequal respident="r">a</varequal></conditionvar>
+      <setvar action="Set">100</setvar></respcondition></resprocessing></item>
+</section></assessment></questestinterop>"#;
+        let files = package(&[
+            (MANIFEST_PATH, manifest),
+            ("week1/read me.html", "<p>hi</p>"),
+            ("css/site.css", "p{}"),
+            (
+                "wl.xml",
+                r#"<webLink><title>Clip</title><url href="https://video.example/1"/></webLink>"#,
+            ),
+            ("q/quiz.xml", quiz),
+            ("dt.xml", "<topic/>"),
+        ]);
+
+        let package = parse_package(&files).unwrap();
+        assert_eq!(package.kind, PackageKind::CommonCartridge);
+        assert_eq!(package.title, "Chemistry");
+        assert_eq!(package.modules.len(), 1);
+        let module = &package.modules[0];
+        assert_eq!(module.title, "Week 1");
+        assert_eq!(module.lessons.len(), 3);
+        assert!(matches!(
+            &module.lessons[0].source,
+            LessonSource::File { path, query: None } if path == "week1/read me.html"
+        ));
+        assert!(
+            matches!(&module.lessons[1].source, LessonSource::Link { url } if url == "https://video.example/1")
+        );
+        assert!(matches!(&module.lessons[2].source, LessonSource::Quiz(q) if q.len() == 1));
+        assert_eq!(
+            package
+                .assets
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>(),
+            ["css/site.css", "week1/read me.html"]
+        );
+        assert_eq!(package.log.len(), 1);
+        assert_eq!(package.log[0].item.as_deref(), Some("i4"));
+        assert!(package.log[0].message.contains("imsdt_xmlv1p1"));
+    }
+
+    #[test]
+    fn maps_a_scorm_package_with_loose_items() {
+        let manifest = r#"<manifest identifier="s" xmlns="http://www.imsproject.org/xsd/imscp_rootv1p1p2"
+    xmlns:adlcp="http://www.adlnet.org/xsd/adlcp_rootv1p2">
+  <metadata><schema>ADL SCORM</schema><schemaversion>1.2</schemaversion></metadata>
+  <organizations default="main">
+    <organization identifier="alt"><title>Alternative</title></organization>
+    <organization identifier="main"><title>Safety basics</title>
+      <item identifier="i1" identifierref="sco1" parameters="?page=2"><title>Intro</title></item>
+      <item identifier="i2" identifierref="sco2"><title>Missing</title></item>
+    </organization>
+  </organizations>
+  <resources xml:base="content/">
+    <resource identifier="sco1" type="webcontent" adlcp:scormtype="sco" href="intro/index.html?lang=en">
+      <file href="intro/index.html"/><file href="intro/gone.png"/></resource>
+    <resource identifier="sco2" type="webcontent" adlcp:scormtype="sco" href="nowhere.html"/>
+  </resources>
+</manifest>"#;
+        let files = package(&[
+            (MANIFEST_PATH, manifest),
+            ("content/intro/index.html", "<html/>"),
+        ]);
+
+        let package = parse_package(&files).unwrap();
+        assert_eq!(package.kind, PackageKind::Scorm12);
+        assert_eq!(package.modules.len(), 1);
+        assert_eq!(package.modules[0].title, "Safety basics");
+        assert_eq!(package.modules[0].lessons.len(), 1);
+        assert!(matches!(
+            &package.modules[0].lessons[0].source,
+            LessonSource::File { path, query: Some(q) }
+                if path == "content/intro/index.html" && q == "lang=en&page=2"
+        ));
+        let messages: Vec<&str> = package.log.iter().map(|n| n.message.as_str()).collect();
+        assert_eq!(messages.len(), 3, "{:?}", messages);
+        assert!(messages[0].contains("other(s) skipped"));
+        assert!(messages.iter().any(|m| m.contains("gone.png")));
+        assert!(messages.iter().any(|m| m.contains("'Missing' skipped")));
+    }
+
+    #[test]
+    fn hrefs_cannot_leave_the_package() {
+        assert_eq!(
+            resolve_href("a/", "../b/c.html").as_deref(),
+            Some("b/c.html")
+        );
+        assert_eq!(resolve_href("", "x%20y.pdf").as_deref(), Some("x y.pdf"));
+        assert_eq!(resolve_href("a/", "../../etc/passwd"), None);
+        assert_eq!(resolve_href("", "/etc/passwd"), None);
+        assert_eq!(resolve_href("", "https://example.com/x"), None);
+    }
+}
@@ -0,0 +1,364 @@
+//! Multiple-choice questions from IMS QTI
+//!
+//! Common Cartridge assessments use the QTI 1.2 `questestinterop` profile;
+//! standalone QTI 2.x items (`assessmentItem`) show up in some SCORM
+//! packages. Single and multiple response choice items, including
+//! true/false, become questions with options; every other item type is
+//! reported back as unsupported.
+
+use roxmltree::Node;
+
+use super::{child, child_text, parse_xml, plain_text};
+
+/// Item profiles (CC `cc_profile`, legacy `qmd_itemtype`) that are choice questions
+const CHOICE_PROFILES: &[&str] = &[
+    "multiple_choice",
+    "multiple_response",
+    "true_false",
+    "multiple choice",
+    "multiple response",
+    "true/false",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChoiceQuestion {
+    pub text: String,
+    pub options: Vec<ChoiceOption>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChoiceOption {
+    pub text: String,
+    pub is_correct: bool,
+}
+
+/// Questions converted from a QTI document and why the other items were skipped
+#[derive(Debug, Clone, Default)]
+pub struct QtiItems {
+    pub questions: Vec<ChoiceQuestion>,
+    pub unsupported: Vec<String>,
+}
+
+/// Convert the choice items of a QTI 1.2 or 2.x document
+pub fn parse(xml: &str) -> Result<QtiItems, String> {
+    let doc = parse_xml(xml).map_err(|e| format!("invalid QTI XML: {}", e))?;
+    let root = doc.root_element();
+    let mut items = QtiItems::default();
+    match root.tag_name().name() {
+        "questestinterop" => {
+            for item in root
+                .descendants()
+                .filter(|n| n.is_element() && n.tag_name().name() == "item")
+            {
+                let title = item.attribute("title").unwrap_or("untitled");
+                match parse_v1_item(item) {
+                    Ok(question) => items.questions.push(question),
+                    Err(reason) => items
+                        .unsupported
+                        .push(format!("Question '{}' skipped: {}", title, reason)),
+                }
+            }
+        }
+        "assessmentItem" => {
+            let title = root.attribute("title").unwrap_or("untitled");
+            match parse_v2_item(root) {
+                Ok(questions) => items.questions.extend(questions),
+                Err(reason) => items
+                    .unsupported
+                    .push(format!("Question '{}' skipped: {}", title, reason)),
+            }
+        }
+        other => return Err(format!("'{}' is not a QTI item or assessment", other)),
+    }
+    Ok(items)
+}
+
+/// QTI 1.2 `item` with one `response_lid`/`render_choice` interaction
+fn parse_v1_item(item: Node) -> Result<ChoiceQuestion, String> {
+    let profile = item
+        .descendants()
+        .filter(|n| n.is_element() && n.tag_name().name() == "qtimetadatafield")
+        .find_map(|field| {
+            let label = child_text(field, "fieldlabel")?;
+            matches!(
+                label.as_str(),
+                "cc_profile" | "qmd_itemtype" | "question_type"
+            )
+            .then(|| child_text(field, "fieldentry"))
+            .flatten()
+        });
+    if let Some(profile) = profile {
+        let lower = profile.to_ascii_lowercase();
+        if !CHOICE_PROFILES.iter().any(|p| lower.contains(p)) {
+            return Err(format!("question type '{}' is not supported", profile));
+        }
+    }
+
+    let presentation = child(item, "presentation").ok_or("it has no presentation")?;
+    let interactions: Vec<Node> = presentation
+        .descendants()
+        .filter(|n| {
+            let name = n.tag_name().name();
+            n.is_element() && name.starts_with("response_") && name != "response_label"
+        })
+        .collect();
+    let [lid] = interactions.as_slice() else {
+        return Err("only items with exactly one choice interaction are supported".into());
+    };
+    if lid.tag_name().name() != "response_lid" {
+        return Err(format!(
+            "'{}' responses are not supported",
+            lid.tag_name().name()
+        ));
+    }
+
+    let text = presentation
+        .descendants()
+        .filter(|n| n.is_element() && n.tag_name().name() == "mattext")
+        .filter(|n| !n.ancestors().any(|a| a == *lid))
+        .map(node_text)
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let correct: Vec<String> = item
+        .descendants()
+        .filter(|n| n.is_element() && n.tag_name().name() == "respcondition")
+        .filter(|cond| {
+            cond.children()
+                .filter(|n| n.is_element() && n.tag_name().name() == "setvar")
+                .any(|v| {
+                    v.text()
+                        .and_then(|t| t.trim().parse::<f64>().ok())
+                        .unwrap_or(0.0)
+                        > 0.0
+                })
+        })
+        .flat_map(|cond| {
+            cond.descendants()
+                .filter(|n| n.is_element() && n.tag_name().name() == "varequal")
+                // `<not><varequal>` marks answers that must not be chosen
+                .filter(move |v| {
+                    !v.ancestors()
+                        .take_while(|a| *a != cond)
+                        .any(|a| a.tag_name().name() == "not")
+                })
+                .filter_map(|v| v.text().map(|t| t.trim().to_string()))
+        })
+        .collect();
+
+    let options = lid
+        .descendants()
+        .filter(|n| n.is_element() && n.tag_name().name() == "response_label")
+        .map(|label| {
+            let ident = label.attribute("ident").unwrap_or_default();
+            ChoiceOption {
+                text: node_text(label),
+                is_correct: correct.iter().any(|c| c == ident),
+            }
+        })
+        .collect();
+    choice_question(text, item.attribute("title"), options)
+}
+
+/// `choiceInteraction`s of a QTI 2.x `assessmentItem`, one question each
+fn parse_v2_item(item: Node) -> Result<Vec<ChoiceQuestion>, String> {
+    let body = child(item, "itemBody").ok_or("it has no itemBody")?;
+    let interactions: Vec<Node> = body
+        .descendants()
+        .filter(|n| n.is_element() && n.tag_name().name().ends_with("Interaction"))
+        .collect();
+    if let Some(other) = interactions
+        .iter()
+        .find(|n| n.tag_name().name() != "choiceInteraction")
+    {
+        return Err(format!("'{}' is not supported", other.tag_name().name()));
+    }
+    if interactions.is_empty() {
+        return Err("it has no choice interaction".into());
+    }
+
+    // Item text outside the interactions, used when an interaction has no prompt
+    let stem = body
+        .descendants()
+        .filter(|n| n.is_text())
+        .filter(|n| !n.ancestors().any(|a| interactions.contains(&a)))
+        .filter_map(|n| n.text())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    interactions
+        .iter()
+        .map(|interaction| {
+            let response = interaction
+                .attribute("responseIdentifier")
+                .unwrap_or_default();
+            let correct: Vec<String> = item
+                .children()
+                .filter(|n| {
+                    n.tag_name().name() == "responseDeclaration"
+                        && n.attribute("identifier") == Some(response)
+                })
+                .filter_map(|d| child(d, "correctResponse"))
+                .flat_map(|c| c.children().filter(|n| n.tag_name().name() == "value"))
+                .filter_map(|v| v.text().map(|t| t.trim().to_string()))
+                .collect();
+            let text = child(*interaction, "prompt")
+                .map(node_text)
+                .filter(|t| !t.is_empty())
+                .unwrap_or_else(|| plain_text(&stem));
+            let options = interaction
+                .children()
+                .filter(|n| n.tag_name().name() == "simpleChoice")
+                .map(|choice| ChoiceOption {
+                    text: node_text(choice),
+                    is_correct: choice
+                        .attribute("identifier")
+                        .is_some_and(|id| correct.iter().any(|c| c == id)),
+                })
+                .collect();
+            choice_question(text, item.attribute("title"), options)
+        })
+        .collect()
+}
+
+fn choice_question(
+    text: String,
+    title: Option<&str>,
+    options: Vec<ChoiceOption>,
+) -> Result<ChoiceQuestion, String> {
+    let text = if text.is_empty() {
+        title.map(plain_text).unwrap_or_default()
+    } else {
+        text
+    };
+    if text.is_empty() {
+        return Err("it has no question text".into());
+    }
+    if options.len() < 2 {
+        return Err("it has fewer than two choices".into());
+    }
+    if options.iter().any(|o| o.text.is_empty()) {
+        return Err("a choice has no text".into());
+    }
+    if !options.iter().any(|o| o.is_correct) {
+        return Err("no choice is marked correct".into());
+    }
+    Ok(ChoiceQuestion { text, options })
+}
+
+/// Plain text of an element; QTI `mattext` often carries escaped HTML
+fn node_text(node: Node) -> String {
+    let raw = node
+        .descendants()
+        .filter(|n| n.is_text())
+        .filter_map(|n| n.text())
+        .collect::<String>();
+    plain_text(&raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CC_QUIZ: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<questestinterop xmlns="http://www.imsglobal.org/xsd/ims_qtiasiv1p2">
+  <assessment ident="quiz" title="Quiz">
+    <section ident="root">
+      <item ident="q1" title="Capital">
+        <itemmetadata><qtimetadata><qtimetadatafield>
+          <fieldlabel>cc_profile</fieldlabel><fieldentry>cc.multiple_choice.v0p1</fieldentry>
+        </qtimetadatafield></qtimetadata></itemmetadata>
+        <presentation>
+          <material><mattext texttype="text/html">&lt;p&gt;Capital of &lt;b&gt;France&lt;/b&gt;?&lt;/p&gt;</mattext></material>
+          <response_lid ident="r1" rcardinality="Single">
+            <render_choice>
+              <response_label ident="a"><material><mattext>Paris</mattext></material></response_label>
+              <response_label ident="b"><material><mattext>Lyon</mattext></material></response_label>
+            </render_choice>
+          </response_lid>
+        </presentation>
+        <resprocessing>
+          <respcondition><conditionvar><varequal respident="r1">a</varequal></conditionvar>
+            <setvar action="Set" varname="SCORE">100</setvar></respcondition>
+        </resprocessing>
+      </item>
+      <item ident="q2" title="Pick primes">
+        <itemmetadata><qtimetadata><qtimetadatafield>
+          <fieldlabel>cc_profile</fieldlabel><fieldentry>cc.multiple_response.v0p1</fieldentry>
+        </qtimetadatafield></qtimetadata></itemmetadata>
+        <presentation>
+          <material><mattext>Which are prime?</mattext></material>
+          <response_lid ident="r2" rcardinality="Multiple">
+            <render_choice>
+              <response_label ident="x"><material><mattext>2</mattext></material></response_label>
+              <response_label ident="y"><material><mattext>3</mattext></material></response_label>
+              <response_label ident="z"><material><mattext>4</mattext></material></response_label>
+            </render_choice>
+          </response_lid>
+        </presentation>
+        <resprocessing>
+          <respcondition><conditionvar><and>
+            <varequal respident="r2">x</varequal><varequal respident="r2">y</varequal>
+            <not><varequal respident="r2">z</varequal></not>
+          </and></conditionvar><setvar action="Set" varname="SCORE">100</setvar></respcondition>
+        </resprocessing>
+      </item>
+      <item ident="q3" title="Explain">
+        <itemmetadata><qtimetadata><qtimetadatafield>
+          <fieldlabel>cc_profile</fieldlabel><fieldentry>cc.essay.v0p1</fieldentry>
+        </qtimetadatafield></qtimetadata></itemmetadata>
+        <presentation><material><mattext>Why?</mattext></material>
+          <response_str ident="r3"><render_fib/></response_str></presentation>
+      </item>
+    </section>
+  </assessment>
+</questestinterop>"#;
+
+    #[test]
+    fn converts_cc_choice_items_and_reports_the_rest() {
+        let items = parse(CC_QUIZ).unwrap();
+        assert_eq!(items.questions.len(), 2);
+        assert_eq!(items.questions[0].text, "Capital of France?");
+        let correct =
+            |q: &ChoiceQuestion| -> Vec<bool> { q.options.iter().map(|o| o.is_correct).collect() };
+        assert_eq!(correct(&items.questions[0]), [true, false]);
+        assert_eq!(correct(&items.questions[1]), [true, true, false]);
+        assert_eq!(items.unsupported.len(), 1);
+        assert!(items.unsupported[0].contains("cc.essay.v0p1"));
+    }
+
+    #[test]
+    fn converts_qti2_choice_interactions() {
+        let xml = r#"<assessmentItem xmlns="http://www.imsglobal.org/xsd/imsqti_v2p1" identifier="i1" title="Sky">
+  <responseDeclaration identifier="RESPONSE" cardinality="single">
+    <correctResponse><value>blue</value></correctResponse>
+  </responseDeclaration>
+  <itemBody>
+    <choiceInteraction responseIdentifier="RESPONSE" maxChoices="1">
+      <prompt>What colour is the sky?</prompt>
+      <simpleChoice identifier="blue">Blue</simpleChoice>
+      <simpleChoice identifier="green">Green</simpleChoice>
+    </choiceInteraction>
+  </itemBody>
+</assessmentItem>"#;
+        let items = parse(xml).unwrap();
+        assert_eq!(
+            items.questions,
+            [ChoiceQuestion {
+                text: "What colour is the sky?".into(),
+                options: vec![
+                    ChoiceOption {
+                        text: "Blue".into(),
+                        is_correct: true
+                    },
+                    ChoiceOption {
+                        text: "Green".into(),
+                        is_correct: false
+                    },
+                ],
+            }]
+        );
+        assert!(parse("<html/>").is_err());
+    }
+}
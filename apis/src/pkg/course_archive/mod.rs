@@ -0,0 +1,633 @@
+//! Portable course archives
+//!
+//! A course archive is a ZIP file holding a versioned `manifest.json` that
+//! describes the course and its module/lesson tree, plus the uploaded files
+//! it references under `media/`. Ids are not part of the format; everything
+//! gets fresh ids when an archive is imported, so archives can move courses
+//! between environments or serve as backups.
+//!
+//! Media references inside the tree (thumbnail, lesson video URLs, lesson
+//! content URLs or private keys) keep their original values. The manifest's
+//! `media` list maps each bundled reference to its file in the archive, and
+//! an importer replaces those references with wherever it stored the files.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{Cursor, Read, Write};
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::money::Money;
+
+/// `format` value identifying a course archive manifest
+pub const ARCHIVE_FORMAT: &str = "execute-academy/course";
+
+/// Manifest version written by this build. Version 1 manifests, whose prices
+/// were plain USD amounts, are upgraded on read; anything else is rejected.
+pub const ARCHIVE_VERSION: u32 = 2;
+
+/// Location of the manifest inside the archive
+pub const MANIFEST_PATH: &str = "manifest.json";
+
+/// Directory bundled media files live under inside the archive
+pub const MEDIA_DIR: &str = "media";
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CourseManifest {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: chrono::DateTime<chrono::Utc>,
+    #[validate(nested)]
+    pub course: ManifestCourse,
+    #[serde(default)]
+    #[validate(nested)]
+    pub modules: Vec<ManifestModule>,
+    /// Bundled files and the references they stand for
+    #[serde(default)]
+    pub media: Vec<ManifestMedia>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct ManifestCourse {
+    #[validate(length(min = 1))]
+    pub slug: String,
+    #[validate(length(min = 1))]
+    pub title: String,
+    #[validate(length(min = 1))]
+    pub description: String,
+    pub excerpt: Option<String>,
+    pub thumbnail: Option<String>,
+    #[validate(nested)]
+    pub price: Money,
+    #[validate(nested)]
+    pub original_price: Option<Money>,
+    #[validate(length(min = 1))]
+    pub duration: String,
+    #[serde(default)]
+    pub outcomes: Vec<String>,
+    pub category: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Prices in currencies other than `price`'s
+    #[serde(default)]
+    #[validate(nested)]
+    pub prices: Vec<ManifestPrice>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct ManifestPrice {
+    #[validate(nested)]
+    pub price: Money,
+    #[validate(nested)]
+    pub original_price: Option<Money>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct ManifestModule {
+    #[validate(length(min = 1))]
+    pub title: String,
+    pub description: Option<String>,
+    #[validate(range(min = 0))]
+    pub position: i32,
+    #[serde(default)]
+    #[validate(nested)]
+    pub lessons: Vec<ManifestLesson>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct ManifestLesson {
+    #[validate(length(min = 1))]
+    pub title: String,
+    pub description: Option<String>,
+    pub content: Option<String>,
+    pub video_url: Option<String>,
+    #[validate(length(min = 1))]
+    pub duration: String,
+    #[validate(range(min = 0))]
+    pub position: i32,
+    pub is_free: bool,
+    pub published: bool,
+    #[serde(default)]
+    #[validate(nested)]
+    pub contents: Vec<ManifestContent>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub questions: Vec<ManifestQuestion>,
+    #[validate(nested)]
+    pub assignment: Option<ManifestAssignment>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub subtitles: Vec<ManifestSubtitle>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct ManifestContent {
+    #[validate(length(min = 1))]
+    pub title: String,
+    #[validate(length(min = 1))]
+    pub content_type: String,
+    /// Public URL, or a private storage key when `is_private` is set
+    #[validate(length(min = 1))]
+    pub url: String,
+    pub file_size: Option<i64>,
+    pub filename: Option<String>,
+    #[validate(range(min = 0))]
+    pub position: i32,
+    #[serde(default)]
+    pub is_private: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct ManifestQuestion {
+    #[validate(length(min = 1))]
+    pub question_text: String,
+    #[validate(range(min = 0))]
+    pub position: i32,
+    #[serde(default)]
+    #[validate(nested)]
+    pub options: Vec<ManifestOption>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct ManifestOption {
+    #[validate(length(min = 1))]
+    pub option_text: String,
+    pub is_correct: bool,
+    #[validate(range(min = 0))]
+    pub position: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct ManifestAssignment {
+    #[validate(length(min = 1))]
+    pub title: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct ManifestSubtitle {
+    #[validate(length(min = 1))]
+    pub language: String,
+    pub label: Option<String>,
+    /// WebVTT document
+    #[validate(length(min = 1))]
+    pub vtt: String,
+}
+
+/// A bundled file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestMedia {
+    /// Reference used in the tree (URL or private key) that this file replaces
+    pub reference: String,
+    /// Path of the file inside the archive, under `media/`
+    pub path: String,
+    /// Whether the file belongs in private storage
+    #[serde(default)]
+    pub private: bool,
+}
+
+/// An archive read into memory
+#[derive(Debug)]
+pub struct CourseArchive {
+    pub manifest: CourseManifest,
+    /// Bundled files keyed by their path in the archive
+    pub files: HashMap<String, Vec<u8>>,
+}
+
+/// Build the ZIP for `manifest` and its bundled `files` (archive path, bytes).
+pub fn write_archive(manifest: &CourseManifest, files: &[(String, Vec<u8>)]) -> AppResult<Vec<u8>> {
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+
+    let manifest_json = serde_json::to_vec_pretty(manifest)
+        .map_err(|e| AppError::Internal(format!("Failed to encode manifest: {}", e)))?;
+    writer
+        .start_file(MANIFEST_PATH, options)
+        .map_err(zip_error)?;
+    writer.write_all(&manifest_json).map_err(io_error)?;
+    for (path, bytes) in files {
+        writer
+            .start_file(path.as_str(), options)
+            .map_err(zip_error)?;
+        writer.write_all(bytes).map_err(io_error)?;
+    }
+    Ok(writer.finish().map_err(zip_error)?.into_inner())
+}
+
+/// Read and validate an archive, refusing to inflate more than `max_bytes`.
+///
+/// Checks the format and version, field constraints, unique positions at
+/// every level of the tree, and that every bundled media entry points at a
+/// file under `media/` that is present in the archive.
+pub fn read_archive(bytes: &[u8], max_bytes: u64) -> AppResult<CourseArchive> {
+    let mut files =
+        read_zip_entries(bytes, max_bytes, |name| name == MANIFEST_PATH || is_media_path(name))?;
+    let manifest_bytes = files
+        .remove(MANIFEST_PATH)
+        .ok_or_else(|| AppError::BadRequest(format!("Archive has no {}", MANIFEST_PATH)))?;
+    let mut raw: serde_json::Value = serde_json::from_slice(&manifest_bytes)
+        .map_err(|e| AppError::BadRequest(format!("Invalid manifest: {}", e)))?;
+    upgrade_manifest(&mut raw);
+    let manifest: CourseManifest = serde_json::from_value(raw)
+        .map_err(|e| AppError::BadRequest(format!("Invalid manifest: {}", e)))?;
+    check_manifest(&manifest, &files)?;
+    Ok(CourseArchive { manifest, files })
+}
+
+/// Rewrite an older manifest in the current version's shape.
+///
+/// Version 1 stored prices as floating-point major units without a currency;
+/// every course was priced in USD then.
+fn upgrade_manifest(raw: &mut serde_json::Value) {
+    if raw.get("version").and_then(|v| v.as_u64()) != Some(1) {
+        return;
+    }
+    if let Some(course) = raw.get_mut("course").and_then(|c| c.as_object_mut()) {
+        for field in ["price", "original_price"] {
+            if let Some(amount) = course.get(field).and_then(|v| v.as_f64()) {
+                let money = Money::new((amount * 100.0).round() as i64, "USD");
+                course.insert(field.into(), serde_json::json!(money));
+            }
+        }
+    }
+    raw["version"] = ARCHIVE_VERSION.into();
+}
+
+/// Inflate the ZIP entries whose names pass `keep`, refusing to inflate more
+/// than `max_bytes` in total. Directories are skipped.
+pub fn read_zip_entries(
+    bytes: &[u8],
+    max_bytes: u64,
+    keep: impl Fn(&str) -> bool,
+) -> AppResult<HashMap<String, Vec<u8>>> {
+    let mut zip = zip::ZipArchive::new(Cursor::new(bytes))
+        .map_err(|e| AppError::BadRequest(format!("Not a valid ZIP archive: {}", e)))?;
+
+    let mut inflated: u64 = 0;
+    let mut files: HashMap<String, Vec<u8>> = HashMap::new();
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i).map_err(bad_archive)?;
+        if entry.is_dir() {
+            continue;
+        }
+        let name = entry.name().to_string();
+        if !keep(&name) {
+            continue;
+        }
+        // Declared sizes can lie, so the actual read is capped as well
+        let remaining = max_bytes - inflated;
+        if entry.size() > remaining {
+            return Err(too_large());
+        }
+        let mut data = Vec::new();
+        (&mut entry)
+            .take(remaining.saturating_add(1))
+            .read_to_end(&mut data)
+            .map_err(bad_archive)?;
+        inflated += data.len() as u64;
+        if inflated > max_bytes {
+            return Err(too_large());
+        }
+        files.insert(name, data);
+    }
+    Ok(files)
+}
+
+/// Archive path for the `n`th bundled file, keeping the source extension
+pub fn media_path(n: usize, source_name: &str) -> String {
+    match std::path::Path::new(source_name)
+        .extension()
+        .and_then(|e| e.to_str())
+    {
+        Some(ext) => format!("{}/{:04}.{}", MEDIA_DIR, n, ext),
+        None => format!("{}/{:04}", MEDIA_DIR, n),
+    }
+}
+
+fn is_media_path(name: &str) -> bool {
+    name.strip_prefix(MEDIA_DIR)
+        .and_then(|rest| rest.strip_prefix('/'))
+        .is_some_and(|file| !file.is_empty() && !file.contains(['/', '\\']) && file != "..")
+}
+
+fn check_manifest(manifest: &CourseManifest, files: &HashMap<String, Vec<u8>>) -> AppResult<()> {
+    if manifest.format != ARCHIVE_FORMAT {
+        return Err(AppError::BadRequest(format!(
+            "Unsupported archive format '{}'",
+            manifest.format
+        )));
+    }
+    if manifest.version != ARCHIVE_VERSION {
+        return Err(AppError::BadRequest(format!(
+            "Unsupported archive version {} (expected {})",
+            manifest.version, ARCHIVE_VERSION
+        )));
+    }
+    manifest.validate().map_err(|e| AppError::Validation {
+        message: "Invalid manifest".into(),
+        details: serde_json::to_value(&e).unwrap_or_default(),
+    })?;
+
+    let mut problems = serde_json::Map::new();
+    let course = &manifest.course;
+    if mismatched_original(&course.price, course.original_price.as_ref()) {
+        problems.insert(
+            "course.original_price".into(),
+            "Original price must be in the price's currency".into(),
+        );
+    }
+    let mut currencies = HashSet::from([course.price.currency.as_str()]);
+    for (i, entry) in course.prices.iter().enumerate() {
+        let at = format!("course.prices[{}]", i);
+        if !currencies.insert(entry.price.currency.as_str()) {
+            problems.insert(
+                format!("{}.price", at),
+                format!("Duplicate price in {}", entry.price.currency).into(),
+            );
+        }
+        if mismatched_original(&entry.price, entry.original_price.as_ref()) {
+            problems.insert(
+                format!("{}.original_price", at),
+                "Original price must be in the price's currency".into(),
+            );
+        }
+    }
+    // A private key that is not bundled would point the new course at a file
+    // already stored here, possibly another instructor's
+    let bundled_private: HashSet<&str> = manifest
+        .media
+        .iter()
+        .filter(|m| m.private)
+        .map(|m| m.reference.as_str())
+        .collect();
+    let mut module_positions = HashSet::new();
+    for (i, module) in manifest.modules.iter().enumerate() {
+        let at = format!("modules[{}]", i);
+        unique_position(&mut problems, &mut module_positions, &at, module.position);
+        let mut lesson_positions = HashSet::new();
+        for (j, lesson) in module.lessons.iter().enumerate() {
+            let at = format!("{}.lessons[{}]", at, j);
+            unique_position(&mut problems, &mut lesson_positions, &at, lesson.position);
+            let mut content_positions = HashSet::new();
+            for (k, content) in lesson.contents.iter().enumerate() {
+                let at = format!("{}.contents[{}]", at, k);
+                unique_position(&mut problems, &mut content_positions, &at, content.position);
+                if content.is_private && !bundled_private.contains(content.url.as_str()) {
+                    problems.insert(
+                        format!("{}.url", at),
+                        "Private content must reference a private file bundled in the archive"
+                            .into(),
+                    );
+                }
+            }
+            let mut question_positions = HashSet::new();
+            for (k, question) in lesson.questions.iter().enumerate() {
+                let at = format!("{}.questions[{}]", at, k);
+                unique_position(
+                    &mut problems,
+                    &mut question_positions,
+                    &at,
+                    question.position,
+                );
+                let mut option_positions = HashSet::new();
+                for (o, option) in question.options.iter().enumerate() {
+                    let at = format!("{}.options[{}]", at, o);
+                    unique_position(&mut problems, &mut option_positions, &at, option.position);
+                }
+            }
+            let mut languages = HashSet::new();
+            for (k, subtitle) in lesson.subtitles.iter().enumerate() {
+                if !languages.insert(subtitle.language.as_str()) {
+                    problems.insert(
+                        format!("{}.subtitles[{}].language", at, k),
+                        format!("Duplicate subtitle language {}", subtitle.language).into(),
+                    );
+                }
+            }
+        }
+    }
+
+    let mut references = HashSet::new();
+    for (i, media) in manifest.media.iter().enumerate() {
+        if !is_media_path(&media.path) || !files.contains_key(&media.path) {
+            problems.insert(
+                format!("media[{}].path", i),
+                format!("{} is not a file in the archive", media.path).into(),
+            );
+        }
+        if !references.insert(media.reference.as_str()) {
+            problems.insert(
+                format!("media[{}].reference", i),
+                "Reference is bundled twice".into(),
+            );
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Validation {
+            message: "Invalid manifest".into(),
+            details: serde_json::Value::Object(problems),
+        })
+    }
+}
+
+/// Record a problem at `<at>.position` if `position` was already used among its siblings
+fn unique_position(
+    problems: &mut serde_json::Map<String, serde_json::Value>,
+    seen: &mut HashSet<i32>,
+    at: &str,
+    position: i32,
+) {
+    if !seen.insert(position) {
+        problems.insert(
+            format!("{}.position", at),
+            format!("Duplicate position {}", position).into(),
+        );
+    }
+}
+
+fn mismatched_original(price: &Money, original: Option<&Money>) -> bool {
+    original.is_some_and(|o| o.currency != price.currency)
+}
+
+fn too_large() -> AppError {
+    AppError::BadRequest("Archive contents exceed the size limit".into())
+}
+
+fn zip_error(e: zip::result::ZipError) -> AppError {
+    AppError::Internal(format!("Failed to write archive: {}", e))
+}
+
+fn io_error(e: std::io::Error) -> AppError {
+    AppError::Internal(format!("Failed to write archive: {}", e))
+}
+
+fn bad_archive(e: impl std::fmt::Display) -> AppError {
+    AppError::BadRequest(format!("Corrupt archive: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> CourseManifest {
+        CourseManifest {
+            format: ARCHIVE_FORMAT.into(),
+            version: ARCHIVE_VERSION,
+            exported_at: chrono::Utc::now(),
+            course: ManifestCourse {
+                slug: "rust-101".into(),
+                title: "Rust 101".into(),
+                description: "Basics".into(),
+                excerpt: None,
+                thumbnail: Some("/uploads/courses/a.webp".into()),
+                price: Money::new(1000, "USD"),
+                original_price: None,
+                duration: "2h".into(),
+                outcomes: vec![],
+                category: None,
+                tags: vec![],
+                prices: vec![],
+            },
+            modules: vec![ManifestModule {
+                title: "Intro".into(),
+                description: None,
+                position: 0,
+                lessons: vec![],
+            }],
+            media: vec![ManifestMedia {
+                reference: "/uploads/courses/a.webp".into(),
+                path: media_path(0, "a.webp"),
+                private: false,
+            }],
+        }
+    }
+
+    #[test]
+    fn archives_round_trip() {
+        let files = vec![(media_path(0, "a.webp"), b"image".to_vec())];
+        let bytes = write_archive(&manifest(), &files).unwrap();
+        let archive = read_archive(&bytes, 1024 * 1024).unwrap();
+        assert_eq!(archive.manifest.course.slug, "rust-101");
+        assert_eq!(archive.files["media/0000.webp"], b"image");
+    }
+
+    #[test]
+    fn rejects_missing_media_and_duplicate_positions() {
+        let mut m = manifest();
+        m.modules.push(m.modules[0].clone());
+        let bytes = write_archive(&m, &[]).unwrap();
+        let Err(AppError::Validation { details, .. }) = read_archive(&bytes, 1024 * 1024) else {
+            panic!("expected a validation error");
+        };
+        assert!(details.get("modules[1].position").is_some());
+        assert!(details.get("media[0].path").is_some());
+    }
+
+    #[test]
+    fn rejects_private_contents_that_are_not_bundled() {
+        let mut m = manifest();
+        m.modules[0].lessons.push(ManifestLesson {
+            title: "Paid".into(),
+            description: None,
+            content: None,
+            video_url: None,
+            duration: "5m".into(),
+            position: 0,
+            is_free: false,
+            published: true,
+            contents: vec![ManifestContent {
+                title: "Workbook".into(),
+                content_type: "application/pdf".into(),
+                url: "lessons/someone-elses.pdf".into(),
+                file_size: None,
+                filename: None,
+                position: 0,
+                is_private: true,
+            }],
+            questions: vec![],
+            assignment: None,
+            subtitles: vec![],
+        });
+        let files = vec![(media_path(0, "a.webp"), b"image".to_vec())];
+        let at = "modules[0].lessons[0].contents[0].url";
+
+        // Neither a loose key nor a key bundled as a public file is accepted
+        for private in [None, Some(false)] {
+            let mut m = m.clone();
+            if let Some(private) = private {
+                m.media.push(ManifestMedia {
+                    reference: "lessons/someone-elses.pdf".into(),
+                    path: media_path(1, "w.pdf"),
+                    private,
+                });
+            }
+            let mut files = files.clone();
+            files.push((media_path(1, "w.pdf"), b"pdf".to_vec()));
+            let bytes = write_archive(&m, &files).unwrap();
+            let Err(AppError::Validation { details, .. }) = read_archive(&bytes, 1024 * 1024)
+            else {
+                panic!("expected a validation error");
+            };
+            assert!(details.get(at).is_some());
+        }
+
+        m.media.push(ManifestMedia {
+            reference: "lessons/someone-elses.pdf".into(),
+            path: media_path(1, "w.pdf"),
+            private: true,
+        });
+        let mut files = files;
+        files.push((media_path(1, "w.pdf"), b"pdf".to_vec()));
+        let bytes = write_archive(&m, &files).unwrap();
+        assert!(read_archive(&bytes, 1024 * 1024).is_ok());
+    }
+
+    #[test]
+    fn version_one_prices_are_upgraded() {
+        let mut raw = serde_json::to_value(manifest()).unwrap();
+        raw["version"] = 1.into();
+        raw["course"]["price"] = serde_json::json!(19.99);
+        raw["course"]["original_price"] = serde_json::json!(null);
+        let bytes = write_raw_manifest(&raw);
+        let archive = read_archive(&bytes, 1024 * 1024).unwrap();
+        assert_eq!(archive.manifest.version, ARCHIVE_VERSION);
+        assert_eq!(archive.manifest.course.price, Money::new(1999, "USD"));
+        assert_eq!(archive.manifest.course.original_price, None);
+    }
+
+    fn write_raw_manifest(raw: &serde_json::Value) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .start_file(MANIFEST_PATH, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(raw.to_string().as_bytes()).unwrap();
+        writer
+            .start_file(media_path(0, "a.webp"), zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"image").unwrap();
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn rejects_other_versions_and_oversized_contents() {
+        let mut m = manifest();
+        m.media.clear();
+        let bytes = write_archive(&m, &[]).unwrap();
+        assert!(matches!(
+            read_archive(&bytes, 10),
+            Err(AppError::BadRequest(_))
+        ));
+
+        m.version = ARCHIVE_VERSION + 1;
+        let bytes = write_archive(&m, &[]).unwrap();
+        assert!(matches!(
+            read_archive(&bytes, 1024 * 1024),
+            Err(AppError::BadRequest(_))
+        ));
+    }
+}
@@ -0,0 +1,281 @@
+//! On-upload image processing
+//!
+//! Turns an uploaded raster image into a set of responsive WebP variants.
+//! Every variant is decoded and re-encoded from pixels only, so EXIF/GPS and
+//! any other embedded metadata never reaches disk. EXIF orientation is applied
+//! before it is discarded so portrait photos from phones stay upright.
+//!
+//! Typical usage:
+//! ```rust,no_run
+//! use execute_academy::pkg::image_processing::{ImageKind, process_image};
+//!
+//! # fn run(bytes: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+//! let variants = process_image(bytes, ImageKind::Thumbnail.widths())?;
+//! for v in &variants {
+//!     println!("{}x{} -> {} bytes", v.width, v.height, v.bytes.len());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io::Cursor;
+
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits};
+
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::upload::Storage;
+
+/// Hard cap on decoded image dimensions to protect against decompression bombs.
+const MAX_SOURCE_DIMENSION: u32 = 12_000;
+
+/// Hard cap on decoder allocations (bytes).
+const MAX_DECODE_ALLOC: u64 = 256 * 1024 * 1024;
+
+/// Content type of every generated variant.
+pub const VARIANT_CONTENT_TYPE: &str = "image/webp";
+
+/// File extension of every generated variant.
+pub const VARIANT_EXTENSION: &str = "webp";
+
+/// What an uploaded image is going to be used for; drives the variant widths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageKind {
+    /// Course `thumbnail`
+    Thumbnail,
+    /// User `avatar_url`
+    Avatar,
+    /// Blog post `cover_image`
+    Cover,
+}
+
+impl ImageKind {
+    /// Target widths in pixels, smallest first.
+    pub fn widths(&self) -> &'static [u32] {
+        match self {
+            ImageKind::Thumbnail => &[320, 640, 1280],
+            ImageKind::Avatar => &[64, 128, 256],
+            ImageKind::Cover => &[640, 1280, 1920],
+        }
+    }
+
+    /// Parse the `kind` form field used by the media upload endpoint.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "thumbnail" | "course_thumbnail" => Some(ImageKind::Thumbnail),
+            "avatar" => Some(ImageKind::Avatar),
+            "cover" | "cover_image" => Some(ImageKind::Cover),
+            _ => None,
+        }
+    }
+}
+
+/// One encoded output of the pipeline.
+#[derive(Debug, Clone)]
+pub struct ImageVariant {
+    /// Requested width bucket (e.g. 640). The actual `width` may be smaller
+    /// when the source image is narrower, since images are never upscaled.
+    pub target_width: u32,
+    pub width: u32,
+    pub height: u32,
+    /// WebP-encoded bytes
+    pub bytes: Vec<u8>,
+}
+
+impl ImageVariant {
+    /// Stable label used as the key in variant maps, e.g. `"640w"`.
+    pub fn label(&self) -> String {
+        format!("{}w", self.target_width)
+    }
+}
+
+/// A variant persisted through a `Storage` backend.
+#[derive(Debug, Clone)]
+pub struct StoredImageVariant {
+    /// Variant map key, e.g. `"640w"`
+    pub label: String,
+    /// Relative path returned by storage, e.g. `/uploads/<stem>-640w.webp`
+    pub path: String,
+    /// Bare file name under the storage root, for cleanup on failure
+    pub file_name: String,
+    pub width: u32,
+    pub height: u32,
+    pub size: usize,
+}
+
+/// Whether `content_type` is a raster format this pipeline can decode.
+///
+/// SVGs are vector and pass through untouched; GIFs are excluded because
+/// re-encoding would drop animation frames.
+pub fn is_processable(content_type: &str) -> bool {
+    matches!(
+        content_type,
+        "image/jpeg" | "image/jpg" | "image/png" | "image/webp"
+    )
+}
+
+/// Decode `bytes`, apply EXIF orientation, and produce one WebP variant per
+/// width in `widths`.
+///
+/// Widths larger than the source are collapsed into a single variant at the
+/// source width so images are never upscaled. The returned list is ordered
+/// by width ascending and is never empty for a valid image.
+pub fn process_image(bytes: &[u8], widths: &[u32]) -> AppResult<Vec<ImageVariant>> {
+    let source = decode_oriented(bytes)?;
+    let (src_w, src_h) = (source.width(), source.height());
+    if src_w == 0 || src_h == 0 {
+        return Err(AppError::BadRequest("Image has no pixels".into()));
+    }
+
+    let mut targets: Vec<u32> = widths.iter().copied().filter(|w| *w > 0).collect();
+    targets.sort_unstable();
+    targets.dedup();
+
+    let mut variants: Vec<ImageVariant> = Vec::new();
+    for target in targets {
+        let width = target.min(src_w);
+        // Several oversized targets collapse onto the source width; keep one.
+        if variants.last().is_some_and(|v| v.width == width) {
+            continue;
+        }
+        let height = scaled_height(src_w, src_h, width);
+        let resized = if width == src_w {
+            source.clone()
+        } else {
+            source.resize_exact(width, height, FilterType::Lanczos3)
+        };
+        variants.push(ImageVariant {
+            target_width: target,
+            width,
+            height,
+            bytes: encode_webp(resized)?,
+        });
+    }
+
+    if variants.is_empty() {
+        return Err(AppError::BadRequest("No image variants requested".into()));
+    }
+    Ok(variants)
+}
+
+/// Decode and resize off the async runtime, since both are CPU bound.
+pub async fn process_image_blocking(
+    bytes: bytes::Bytes,
+    kind: ImageKind,
+) -> AppResult<Vec<ImageVariant>> {
+    tokio::task::spawn_blocking(move || process_image(&bytes, kind.widths()))
+        .await
+        .map_err(|e| AppError::Internal(format!("Image worker failed: {}", e)))?
+}
+
+/// Persist `variants` under a shared random stem (`<stem>-<label>.webp`) so
+/// sibling sizes of one upload can be recognised on disk. Returned in the
+/// same order as `variants`, i.e. smallest first.
+pub async fn store_variants(
+    storage: &dyn Storage,
+    variants: &[ImageVariant],
+) -> AppResult<Vec<StoredImageVariant>> {
+    let stem = uuid::Uuid::new_v4();
+    let mut stored: Vec<StoredImageVariant> = Vec::with_capacity(variants.len());
+    for v in variants {
+        let label = v.label();
+        let file_name = format!("{}-{}.{}", stem, label, VARIANT_EXTENSION);
+        let path = storage.save_bytes_as(&v.bytes, &file_name).await?;
+        stored.push(StoredImageVariant {
+            label,
+            path,
+            file_name,
+            width: v.width,
+            height: v.height,
+            size: v.bytes.len(),
+        });
+    }
+    Ok(stored)
+}
+
+fn decode_oriented(bytes: &[u8]) -> AppResult<DynamicImage> {
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_SOURCE_DIMENSION);
+    limits.max_image_height = Some(MAX_SOURCE_DIMENSION);
+    limits.max_alloc = Some(MAX_DECODE_ALLOC);
+
+    let mut reader = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| AppError::BadRequest(format!("Unreadable image: {}", e)))?;
+    reader.limits(limits);
+
+    let mut decoder = reader
+        .into_decoder()
+        .map_err(|e| AppError::BadRequest(format!("Unsupported image: {}", e)))?;
+    let orientation = decoder
+        .orientation()
+        .map_err(|e| AppError::BadRequest(format!("Invalid image metadata: {}", e)))?;
+    let mut image = DynamicImage::from_decoder(decoder)
+        .map_err(|e| AppError::BadRequest(format!("Invalid image data: {}", e)))?;
+    image.apply_orientation(orientation);
+    Ok(image)
+}
+
+fn scaled_height(src_w: u32, src_h: u32, width: u32) -> u32 {
+    let h = (u64::from(src_h) * u64::from(width) + u64::from(src_w) / 2) / u64::from(src_w);
+    h.max(1) as u32
+}
+
+fn encode_webp(image: DynamicImage) -> AppResult<Vec<u8>> {
+    // The WebP encoder only accepts 8-bit RGB(A); drop alpha when unused.
+    let normalized = if image.color().has_alpha() {
+        DynamicImage::ImageRgba8(image.into_rgba8())
+    } else {
+        DynamicImage::ImageRgb8(image.into_rgb8())
+    };
+    let mut out = Cursor::new(Vec::new());
+    normalized
+        .write_to(&mut out, ImageFormat::WebP)
+        .map_err(|e| AppError::Internal(format!("WebP encode failed: {}", e)))?;
+    Ok(out.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageBuffer, Rgb};
+
+    fn png_bytes(width: u32, height: u32) -> Vec<u8> {
+        let img = ImageBuffer::from_pixel(width, height, Rgb([200u8, 40, 40]));
+        let mut out = Cursor::new(Vec::new());
+        DynamicImage::ImageRgb8(img)
+            .write_to(&mut out, ImageFormat::Png)
+            .unwrap();
+        out.into_inner()
+    }
+
+    #[test]
+    fn produces_webp_variants_preserving_aspect_ratio() {
+        let variants = process_image(&png_bytes(2000, 1000), &[1280, 320, 640]).unwrap();
+        let sizes: Vec<(u32, u32)> = variants.iter().map(|v| (v.width, v.height)).collect();
+        assert_eq!(sizes, vec![(320, 160), (640, 320), (1280, 640)]);
+        for v in &variants {
+            assert_eq!(
+                image::guess_format(&v.bytes).unwrap(),
+                ImageFormat::WebP,
+                "variant {} should be WebP",
+                v.label()
+            );
+        }
+    }
+
+    #[test]
+    fn never_upscales_small_sources() {
+        let variants = process_image(&png_bytes(500, 250), ImageKind::Thumbnail.widths()).unwrap();
+        let sizes: Vec<(u32, u32, u32)> = variants
+            .iter()
+            .map(|v| (v.target_width, v.width, v.height))
+            .collect();
+        assert_eq!(sizes, vec![(320, 320, 160), (640, 500, 250)]);
+    }
+
+    #[test]
+    fn rejects_non_images() {
+        assert!(process_image(b"definitely not an image", &[320]).is_err());
+    }
+}
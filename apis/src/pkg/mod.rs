@@ -1,14 +1,24 @@
 pub mod auth;
+pub mod client_ip;
+pub mod content_package;
+pub mod course_archive;
 pub mod email;
 pub mod error;
+pub mod image_processing;
 pub mod logger;
+pub mod money;
+pub mod payments;
+pub mod pdf;
+pub mod private_media;
 pub mod rate_limit;
 pub mod redis;
 pub mod response;
 pub mod security;
+pub mod subtitles;
 pub mod upload;
 pub mod utils;
 pub mod validators;
+pub mod video;
 
 // Back-compat: expose `pkg::security_services` pointing to `pkg::security::security_services`
 pub use self::security::security_services;
@@ -0,0 +1,226 @@
+//! Monetary amounts
+//!
+//! Amounts are integer counts of the currency's minor unit (cents for USD,
+//! yen for JPY, fils for KWD) paired with an upper-case ISO 4217 code, so
+//! prices add, compare and discount exactly. Decimal strings such as
+//! `"19.99"` are only accepted or produced at the edges, where they are
+//! converted using the currency's number of decimal places.
+//!
+//! ```rust
+//! use execute_academy::pkg::money::Money;
+//!
+//! let price = Money::parse_major("19.99", "usd").unwrap();
+//! assert_eq!(price.amount, 1999);
+//! assert_eq!(price.to_string(), "19.99 USD");
+//! ```
+
+use std::fmt;
+
+use async_graphql::{InputObject, SimpleObject};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+/// Currencies without a minor unit
+const ZERO_DECIMAL: &[&str] = &[
+    "BIF", "CLP", "DJF", "GNF", "JPY", "KMF", "KRW", "MGA", "PYG", "RWF", "UGX", "VND", "VUV",
+    "XAF", "XOF", "XPF",
+];
+/// Currencies with three decimal places
+const THREE_DECIMAL: &[&str] = &["BHD", "JOD", "KWD", "OMR", "TND"];
+
+/// An amount of money in a currency's minor unit
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    ToSchema,
+    SimpleObject,
+    InputObject,
+    Validate,
+)]
+#[graphql(input_name = "MoneyInput")]
+pub struct Money {
+    /// Amount in the currency's minor unit, e.g. 1999 for 19.99 USD
+    #[validate(range(min = 0))]
+    #[schema(example = 1999)]
+    pub amount: i64,
+    /// ISO 4217 currency code, upper case
+    #[validate(custom(function = "validate_currency_code"))]
+    #[schema(example = "USD")]
+    pub currency: String,
+}
+
+impl Money {
+    pub fn new(amount: i64, currency: impl Into<String>) -> Self {
+        Self {
+            amount,
+            currency: currency.into(),
+        }
+    }
+
+    pub fn zero(currency: impl Into<String>) -> Self {
+        Self::new(0, currency)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.amount == 0
+    }
+
+    /// Parse a non-negative decimal amount in major units, e.g. `"19.99"`.
+    ///
+    /// More decimal places than the currency has are rejected rather than
+    /// rounded.
+    pub fn parse_major(value: &str, currency: &str) -> Result<Self, String> {
+        let currency = normalize_currency(currency)
+            .ok_or_else(|| format!("'{}' is not an ISO 4217 currency code", currency))?;
+        let exponent = minor_unit_exponent(&currency);
+        let invalid = || format!("'{}' is not a valid amount", value.trim());
+
+        let (whole, fraction) = match value.trim().split_once('.') {
+            Some((whole, fraction)) => (whole, fraction),
+            None => (value.trim(), ""),
+        };
+        if whole.is_empty() || !whole.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        if !fraction.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        if fraction.len() > exponent as usize {
+            return Err(format!(
+                "{} amounts have at most {} decimal place(s)",
+                currency, exponent
+            ));
+        }
+
+        let scale = 10i64.pow(exponent);
+        let whole: i64 = whole.parse().map_err(|_| invalid())?;
+        let fraction: i64 = if fraction.is_empty() {
+            0
+        } else {
+            let padded = format!("{:0<width$}", fraction, width = exponent as usize);
+            padded.parse().map_err(|_| invalid())?
+        };
+        let amount = whole
+            .checked_mul(scale)
+            .and_then(|a| a.checked_add(fraction))
+            .ok_or_else(invalid)?;
+        Ok(Self { amount, currency })
+    }
+
+    /// Amount in major units with the currency's decimal places, e.g. `"19.99"`
+    pub fn to_major_string(&self) -> String {
+        let exponent = minor_unit_exponent(&self.currency);
+        if exponent == 0 {
+            return self.amount.to_string();
+        }
+        let scale = 10i64.pow(exponent);
+        let sign = if self.amount < 0 { "-" } else { "" };
+        let abs = self.amount.unsigned_abs();
+        format!(
+            "{}{}.{:0width$}",
+            sign,
+            abs / scale as u64,
+            abs % scale as u64,
+            width = exponent as usize
+        )
+    }
+
+    /// Sum of two amounts in the same currency; `None` on mismatch or overflow
+    pub fn checked_add(&self, other: &Money) -> Option<Money> {
+        if self.currency != other.currency {
+            return None;
+        }
+        Some(Self::new(
+            self.amount.checked_add(other.amount)?,
+            self.currency.clone(),
+        ))
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.to_major_string(), self.currency)
+    }
+}
+
+/// Upper-case a three-letter currency code; `None` if it is not one
+pub fn normalize_currency(code: &str) -> Option<String> {
+    let code = code.trim();
+    (code.len() == 3 && code.bytes().all(|b| b.is_ascii_alphabetic()))
+        .then(|| code.to_ascii_uppercase())
+}
+
+/// Number of decimal places of `currency`'s minor unit
+pub fn minor_unit_exponent(currency: &str) -> u32 {
+    if ZERO_DECIMAL.contains(&currency) {
+        0
+    } else if THREE_DECIMAL.contains(&currency) {
+        3
+    } else {
+        2
+    }
+}
+
+/// Validator for currency code fields: three upper-case ASCII letters
+pub fn validate_currency_code(code: &str) -> Result<(), ValidationError> {
+    if code.len() == 3 && code.bytes().all(|b| b.is_ascii_uppercase()) {
+        Ok(())
+    } else {
+        Err(ValidationError::new(
+            "Currency must be an upper-case ISO 4217 code",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_major_amounts_exactly() {
+        assert_eq!(
+            Money::parse_major("19.99", "usd").unwrap(),
+            Money::new(1999, "USD")
+        );
+        assert_eq!(Money::parse_major("0.1", "USD").unwrap().amount, 10);
+        assert_eq!(Money::parse_major("20", "USD").unwrap().amount, 2000);
+        assert_eq!(Money::parse_major("1500", "JPY").unwrap().amount, 1500);
+        assert_eq!(Money::parse_major("1.005", "KWD").unwrap().amount, 1005);
+
+        assert!(Money::parse_major("1.999", "USD").is_err());
+        assert!(Money::parse_major("15.5", "JPY").is_err());
+        assert!(Money::parse_major("-1", "USD").is_err());
+        assert!(Money::parse_major("1e3", "USD").is_err());
+        assert!(Money::parse_major(".5", "USD").is_err());
+        assert!(Money::parse_major("1", "dollars").is_err());
+        assert!(Money::parse_major("99999999999999999999", "USD").is_err());
+    }
+
+    #[test]
+    fn formats_with_currency_decimals() {
+        assert_eq!(Money::new(1999, "USD").to_major_string(), "19.99");
+        assert_eq!(Money::new(5, "EUR").to_major_string(), "0.05");
+        assert_eq!(Money::new(1500, "JPY").to_string(), "1500 JPY");
+        assert_eq!(Money::new(1005, "KWD").to_string(), "1.005 KWD");
+        assert_eq!(Money::new(-250, "USD").to_major_string(), "-2.50");
+    }
+
+    #[test]
+    fn validates_and_adds_within_one_currency() {
+        assert!(Money::new(100, "USD").validate().is_ok());
+        assert!(Money::new(-1, "USD").validate().is_err());
+        assert!(Money::new(100, "usd").validate().is_err());
+
+        let a = Money::new(150, "USD");
+        assert_eq!(
+            a.checked_add(&Money::new(50, "USD")),
+            Some(Money::new(200, "USD"))
+        );
+        assert_eq!(a.checked_add(&Money::new(50, "EUR")), None);
+    }
+}
@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use serde::Deserialize;
+
+use super::{
+    CreatePaymentIntent, CreateRefund, PaymentEvent, PaymentEventKind, PaymentIntent,
+    PaymentProvider, PaymentStatus, Refund, RefundStatus,
+};
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::money::Money;
+
+/// In-memory provider for tests and local development without a Stripe account.
+///
+/// Intents are created awaiting payment and report `succeeded` the first time
+/// they are retrieved, so checkout followed by confirmation completes without
+/// a card form. Tests can pin a state with [`FakePaymentProvider::set_status`].
+/// Canceling an intent that has not settled stops it from settling; refunds
+/// of succeeded intents succeed at once.
+#[derive(Default)]
+pub struct FakePaymentProvider {
+    intents: Mutex<HashMap<String, FakeIntent>>,
+}
+
+#[derive(Debug, Clone)]
+struct FakeIntent {
+    status: PaymentStatus,
+    pinned: bool,
+    amount: Money,
+    /// Minor units refunded, by refund request
+    refunds: HashMap<uuid::Uuid, i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FakeEvent {
+    id: String,
+    #[serde(rename = "type")]
+    event_type: String,
+    payment_id: Option<String>,
+}
+
+impl FakePaymentProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Force an intent into `status` and stop it from settling on its own.
+    pub fn set_status(&self, intent_id: &str, status: PaymentStatus) {
+        if let Some(i) = self.intents.lock().unwrap().get_mut(intent_id) {
+            i.status = status;
+            i.pinned = true;
+        }
+    }
+
+    /// Amount the intent was created for, if it exists.
+    pub fn amount(&self, intent_id: &str) -> Option<Money> {
+        self.intents
+            .lock()
+            .unwrap()
+            .get(intent_id)
+            .map(|i| i.amount.clone())
+    }
+}
+
+#[async_trait]
+impl PaymentProvider for FakePaymentProvider {
+    fn name(&self) -> &'static str {
+        "fake"
+    }
+
+    async fn create_intent(&self, input: &CreatePaymentIntent) -> AppResult<PaymentIntent> {
+        if input.amount.amount <= 0 {
+            return Err(AppError::BadRequest(
+                "Payment amount must be positive".into(),
+            ));
+        }
+        let id = format!("pi_fake_{}", input.order_id.simple());
+        let mut intents = self.intents.lock().unwrap();
+        let intent = intents.entry(id.clone()).or_insert(FakeIntent {
+            status: PaymentStatus::RequiresPayment,
+            pinned: false,
+            amount: input.amount.clone(),
+            refunds: HashMap::new(),
+        });
+        Ok(PaymentIntent {
+            client_secret: Some(format!("{}_secret_fake", id)),
+            status: intent.status,
+            id,
+        })
+    }
+
+    async fn retrieve_intent(&self, intent_id: &str) -> AppResult<PaymentIntent> {
+        let mut intents = self.intents.lock().unwrap();
+        let intent = intents
+            .get_mut(intent_id)
+            .ok_or_else(|| AppError::NotFound("Payment intent not found".into()))?;
+        if !intent.pinned && intent.status == PaymentStatus::RequiresPayment {
+            intent.status = PaymentStatus::Succeeded;
+        }
+        Ok(PaymentIntent {
+            id: intent_id.to_string(),
+            client_secret: None,
+            status: intent.status,
+        })
+    }
+
+    async fn cancel_intent(&self, intent_id: &str) -> AppResult<PaymentIntent> {
+        let mut intents = self.intents.lock().unwrap();
+        let intent = intents
+            .get_mut(intent_id)
+            .ok_or_else(|| AppError::NotFound("Payment intent not found".into()))?;
+        if intent.status == PaymentStatus::RequiresPayment {
+            intent.status = PaymentStatus::Canceled;
+            intent.pinned = true;
+        }
+        Ok(PaymentIntent {
+            id: intent_id.to_string(),
+            client_secret: None,
+            status: intent.status,
+        })
+    }
+
+    async fn refund(&self, input: &CreateRefund) -> AppResult<Refund> {
+        let mut intents = self.intents.lock().unwrap();
+        let intent = intents
+            .get_mut(&input.payment_id)
+            .ok_or_else(|| AppError::NotFound("Payment intent not found".into()))?;
+        if intent.status != PaymentStatus::Succeeded {
+            return Err(AppError::BadRequest(
+                "Only succeeded payments can be refunded".into(),
+            ));
+        }
+        let id = format!("re_fake_{}", input.refund_id.simple());
+        if intent.refunds.contains_key(&input.refund_id) {
+            return Ok(Refund {
+                id,
+                status: RefundStatus::Succeeded,
+            });
+        }
+        let refunded: i64 = intent.refunds.values().sum();
+        if input.amount.currency != intent.amount.currency
+            || refunded + input.amount.amount > intent.amount.amount
+        {
+            return Err(AppError::BadRequest(
+                "Refund exceeds the amount paid".into(),
+            ));
+        }
+        intent.refunds.insert(input.refund_id, input.amount.amount);
+        Ok(Refund {
+            id,
+            status: RefundStatus::Succeeded,
+        })
+    }
+
+    fn signature_header(&self) -> &'static str {
+        "Payment-Signature"
+    }
+
+    fn parse_event(&self, payload: &[u8]) -> AppResult<PaymentEvent> {
+        let event: FakeEvent = serde_json::from_slice(payload)
+            .map_err(|e| AppError::BadRequest(format!("Invalid payment event: {}", e)))?;
+        let kind = match event.event_type.as_str() {
+            "payment.succeeded" => PaymentEventKind::Succeeded,
+            "payment.failed" => PaymentEventKind::Failed,
+            "payment.canceled" => PaymentEventKind::Canceled,
+            "payment.refunded" => PaymentEventKind::Refunded,
+            _ => PaymentEventKind::Other,
+        };
+        Ok(PaymentEvent {
+            id: event.id,
+            event_type: event.event_type,
+            kind,
+            payment_id: event.payment_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intent_input(amount_cents: i64) -> CreatePaymentIntent {
+        CreatePaymentIntent {
+            order_id: uuid::Uuid::new_v4(),
+            amount: Money::new(amount_cents, "USD"),
+            description: "Order".into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn settles_on_first_retrieval() {
+        let provider = FakePaymentProvider::new();
+        let input = intent_input(1999);
+        let intent = provider.create_intent(&input).await.unwrap();
+        assert_eq!(intent.status, PaymentStatus::RequiresPayment);
+        assert!(intent.client_secret.is_some());
+
+        // Same order, same intent
+        let again = provider.create_intent(&input).await.unwrap();
+        assert_eq!(again.id, intent.id);
+
+        let fetched = provider.retrieve_intent(&intent.id).await.unwrap();
+        assert_eq!(fetched.status, PaymentStatus::Succeeded);
+        assert_eq!(provider.amount(&intent.id), Some(Money::new(1999, "USD")));
+    }
+
+    #[tokio::test]
+    async fn pinned_status_is_kept() {
+        let provider = FakePaymentProvider::new();
+        let intent = provider.create_intent(&intent_input(500)).await.unwrap();
+        provider.set_status(&intent.id, PaymentStatus::Failed);
+        assert_eq!(
+            provider.retrieve_intent(&intent.id).await.unwrap().status,
+            PaymentStatus::Failed
+        );
+        assert!(provider.create_intent(&intent_input(0)).await.is_err());
+        assert!(provider.retrieve_intent("missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn canceled_intents_never_settle() {
+        let provider = FakePaymentProvider::new();
+        let intent = provider.create_intent(&intent_input(800)).await.unwrap();
+        let canceled = provider.cancel_intent(&intent.id).await.unwrap();
+        assert_eq!(canceled.status, PaymentStatus::Canceled);
+        assert_eq!(
+            provider.retrieve_intent(&intent.id).await.unwrap().status,
+            PaymentStatus::Canceled
+        );
+
+        // A settled payment stays settled
+        let paid = provider.create_intent(&intent_input(800)).await.unwrap();
+        provider.retrieve_intent(&paid.id).await.unwrap();
+        assert_eq!(
+            provider.cancel_intent(&paid.id).await.unwrap().status,
+            PaymentStatus::Succeeded
+        );
+    }
+
+    #[tokio::test]
+    async fn refunds_are_idempotent_and_capped() {
+        let provider = FakePaymentProvider::new();
+        let intent = provider.create_intent(&intent_input(1000)).await.unwrap();
+        let refund = |refund_id, amount| CreateRefund {
+            refund_id,
+            payment_id: intent.id.clone(),
+            amount: Money::new(amount, "USD"),
+        };
+        let first = uuid::Uuid::new_v4();
+        // Not paid yet
+        assert!(provider.refund(&refund(first, 600)).await.is_err());
+
+        provider.retrieve_intent(&intent.id).await.unwrap();
+        let done = provider.refund(&refund(first, 600)).await.unwrap();
+        assert_eq!(done.status, RefundStatus::Succeeded);
+        let again = provider.refund(&refund(first, 600)).await.unwrap();
+        assert_eq!(again.id, done.id);
+        assert!(
+            provider
+                .refund(&refund(uuid::Uuid::new_v4(), 600))
+                .await
+                .is_err()
+        );
+    }
+}
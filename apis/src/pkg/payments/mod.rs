@@ -0,0 +1,171 @@
+//! Payment providers
+//!
+//! Checkout creates a payment intent with the provider for the order total
+//! and hands its client secret to the browser, which collects card details
+//! directly with the provider. The API never sees card data: it learns the
+//! outcome by asking the provider for the intent's status, and only then
+//! marks the order paid and enrolls the buyer. Providers also push the
+//! outcome to a signed webhook (see [`webhook`]), which covers payments that
+//! settle after the buyer has left the page. Refunds of a succeeded payment
+//! go through the same provider. Orders that are canceled or expire before
+//! paying cancel their intent, so it cannot settle afterwards.
+
+use async_trait::async_trait;
+
+use crate::pkg::error::AppResult;
+use crate::pkg::money::Money;
+
+pub mod fake;
+pub mod stripe;
+pub mod webhook;
+
+pub use fake::FakePaymentProvider;
+pub use stripe::StripeProvider;
+pub use webhook::WebhookVerifier;
+
+/// State of a payment intent, as stored in `payment_attempts.status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaymentStatus {
+    /// Waiting for the customer to provide or confirm a payment method
+    RequiresPayment,
+    /// Submitted; the provider has not settled it yet
+    Processing,
+    /// Funds captured
+    Succeeded,
+    /// Declined or errored; the order cannot be paid with this intent
+    Failed,
+    /// Abandoned before completion
+    Canceled,
+}
+
+impl PaymentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentStatus::RequiresPayment => "requires_payment",
+            PaymentStatus::Processing => "processing",
+            PaymentStatus::Succeeded => "succeeded",
+            PaymentStatus::Failed => "failed",
+            PaymentStatus::Canceled => "canceled",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "requires_payment" => Some(PaymentStatus::RequiresPayment),
+            "processing" => Some(PaymentStatus::Processing),
+            "succeeded" => Some(PaymentStatus::Succeeded),
+            "failed" => Some(PaymentStatus::Failed),
+            "canceled" => Some(PaymentStatus::Canceled),
+            _ => None,
+        }
+    }
+
+    /// Whether asking the provider again can still change the status.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            PaymentStatus::Succeeded | PaymentStatus::Failed | PaymentStatus::Canceled
+        )
+    }
+}
+
+/// Input for charging an order.
+#[derive(Debug, Clone)]
+pub struct CreatePaymentIntent {
+    /// Our order id; also the idempotency key, so retries reuse one intent
+    pub order_id: uuid::Uuid,
+    /// Amount to charge, in minor units of its currency
+    pub amount: Money,
+    /// Shown on the provider dashboard and the customer's statement details
+    pub description: String,
+}
+
+/// A payment intent as reported by the provider.
+#[derive(Debug, Clone)]
+pub struct PaymentIntent {
+    /// Provider-side intent identifier
+    pub id: String,
+    /// Secret the browser uses to confirm the payment with the provider
+    pub client_secret: Option<String>,
+    pub status: PaymentStatus,
+}
+
+/// Input for refunding a succeeded payment.
+#[derive(Debug, Clone)]
+pub struct CreateRefund {
+    /// Our refund request id; also the idempotency key, so a retried
+    /// approval refunds once
+    pub refund_id: uuid::Uuid,
+    /// Provider-side intent id of the payment to refund
+    pub payment_id: String,
+    /// Amount to give back, at most what was charged
+    pub amount: Money,
+}
+
+/// State of a refund with the provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefundStatus {
+    /// Accepted; the money is on its way back
+    Pending,
+    Succeeded,
+    /// Declined, e.g. because the payment is too old to refund
+    Failed,
+}
+
+/// A refund as reported by the provider.
+#[derive(Debug, Clone)]
+pub struct Refund {
+    /// Provider-side refund identifier
+    pub id: String,
+    pub status: RefundStatus,
+}
+
+/// What a provider callback reports about a payment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaymentEventKind {
+    Succeeded,
+    Failed,
+    Canceled,
+    Refunded,
+    /// Anything we do not act on
+    Other,
+}
+
+/// A provider callback, normalized.
+#[derive(Debug, Clone)]
+pub struct PaymentEvent {
+    /// Provider-side event id; deliveries of one event share it
+    pub id: String,
+    /// Provider's own event type, e.g. `payment_intent.succeeded`
+    pub event_type: String,
+    pub kind: PaymentEventKind,
+    /// Payment intent the event is about, when there is one
+    pub payment_id: Option<String>,
+}
+
+#[async_trait]
+pub trait PaymentProvider: Send + Sync {
+    /// Stable provider name stored next to intent ids, e.g. `"stripe"`.
+    fn name(&self) -> &'static str;
+
+    /// Create a payment intent for the order total.
+    async fn create_intent(&self, input: &CreatePaymentIntent) -> AppResult<PaymentIntent>;
+
+    /// Current state of a payment intent.
+    async fn retrieve_intent(&self, intent_id: &str) -> AppResult<PaymentIntent>;
+
+    /// Cancel an unsettled payment intent so it can no longer be paid.
+    ///
+    /// An intent that is already processing or succeeded cannot be canceled;
+    /// it is returned in that state instead of failing.
+    async fn cancel_intent(&self, intent_id: &str) -> AppResult<PaymentIntent>;
+
+    /// Give back (part of) a succeeded payment.
+    async fn refund(&self, input: &CreateRefund) -> AppResult<Refund>;
+
+    /// Request header carrying the webhook signature.
+    fn signature_header(&self) -> &'static str;
+
+    /// Decode a webhook body whose signature has already been verified.
+    fn parse_event(&self, payload: &[u8]) -> AppResult<PaymentEvent>;
+}
@@ -0,0 +1,253 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use super::{
+    CreatePaymentIntent, CreateRefund, PaymentEvent, PaymentEventKind, PaymentIntent,
+    PaymentProvider, PaymentStatus, Refund, RefundStatus,
+};
+use crate::pkg::error::{AppError, AppResult};
+
+/// Stripe PaymentIntents API client using a secret key.
+pub struct StripeProvider {
+    client: reqwest::Client,
+    secret_key: String,
+    api_base: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripePaymentIntent {
+    id: String,
+    status: String,
+    client_secret: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeRefund {
+    id: String,
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeEvent {
+    id: String,
+    #[serde(rename = "type")]
+    event_type: String,
+    data: StripeEventData,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeEventData {
+    object: StripeEventObject,
+}
+
+/// The subset of a payment intent or charge that events carry
+#[derive(Debug, Deserialize)]
+struct StripeEventObject {
+    id: Option<String>,
+    object: Option<String>,
+    /// Set on charges
+    payment_intent: Option<String>,
+}
+
+impl StripeProvider {
+    pub fn new(secret_key: impl Into<String>, api_base: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            secret_key: secret_key.into(),
+            api_base: api_base.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        self.client
+            .request(method, format!("{}{}", self.api_base, path))
+            .bearer_auth(&self.secret_key)
+    }
+
+    async fn send<T: serde::de::DeserializeOwned>(
+        &self,
+        req: reqwest::RequestBuilder,
+        action: &str,
+    ) -> AppResult<T> {
+        req.send()
+            .await
+            .map_err(|e| AppError::ServiceUnavailable(format!("Stripe {} failed: {}", action, e)))?
+            .error_for_status()
+            .map_err(|e| AppError::ServiceUnavailable(format!("Stripe {} failed: {}", action, e)))?
+            .json()
+            .await
+            .map_err(|e| AppError::ServiceUnavailable(e.to_string()))
+    }
+}
+
+/// Map Stripe's PaymentIntent lifecycle onto ours.
+fn map_status(status: &str) -> PaymentStatus {
+    match status {
+        "succeeded" => PaymentStatus::Succeeded,
+        "processing" | "requires_capture" => PaymentStatus::Processing,
+        "canceled" => PaymentStatus::Canceled,
+        // requires_payment_method, requires_confirmation, requires_action:
+        // the customer can still complete or retry the payment
+        _ => PaymentStatus::RequiresPayment,
+    }
+}
+
+/// Map Stripe's refund states onto ours.
+fn map_refund_status(status: &str) -> RefundStatus {
+    match status {
+        "succeeded" => RefundStatus::Succeeded,
+        "failed" | "canceled" => RefundStatus::Failed,
+        // pending, requires_action
+        _ => RefundStatus::Pending,
+    }
+}
+
+/// Normalize a Stripe event; refunds arrive on the charge, not the intent.
+fn map_event(event: StripeEvent) -> PaymentEvent {
+    let kind = match event.event_type.as_str() {
+        "payment_intent.succeeded" => PaymentEventKind::Succeeded,
+        "payment_intent.payment_failed" => PaymentEventKind::Failed,
+        "payment_intent.canceled" => PaymentEventKind::Canceled,
+        "charge.refunded" => PaymentEventKind::Refunded,
+        _ => PaymentEventKind::Other,
+    };
+    let object = event.data.object;
+    let payment_id = match object.object.as_deref() {
+        Some("payment_intent") => object.id,
+        _ => object.payment_intent,
+    };
+    PaymentEvent {
+        id: event.id,
+        event_type: event.event_type,
+        kind,
+        payment_id,
+    }
+}
+
+impl From<StripePaymentIntent> for PaymentIntent {
+    fn from(intent: StripePaymentIntent) -> Self {
+        PaymentIntent {
+            status: map_status(&intent.status),
+            id: intent.id,
+            client_secret: intent.client_secret,
+        }
+    }
+}
+
+#[async_trait]
+impl PaymentProvider for StripeProvider {
+    fn name(&self) -> &'static str {
+        "stripe"
+    }
+
+    async fn create_intent(&self, input: &CreatePaymentIntent) -> AppResult<PaymentIntent> {
+        let order_id = input.order_id.to_string();
+        let form = [
+            ("amount", input.amount.amount.to_string()),
+            ("currency", input.amount.currency.to_ascii_lowercase()),
+            ("description", input.description.clone()),
+            ("metadata[order_id]", order_id.clone()),
+            ("automatic_payment_methods[enabled]", "true".to_string()),
+        ];
+        let req = self
+            .request(reqwest::Method::POST, "/v1/payment_intents")
+            .header("Idempotency-Key", format!("order-{}", order_id))
+            .form(&form);
+        Ok(self
+            .send::<StripePaymentIntent>(req, "create payment intent")
+            .await?
+            .into())
+    }
+
+    async fn retrieve_intent(&self, intent_id: &str) -> AppResult<PaymentIntent> {
+        let req = self.request(
+            reqwest::Method::GET,
+            &format!("/v1/payment_intents/{}", urlencoding::encode(intent_id)),
+        );
+        Ok(self
+            .send::<StripePaymentIntent>(req, "get payment intent")
+            .await?
+            .into())
+    }
+
+    async fn cancel_intent(&self, intent_id: &str) -> AppResult<PaymentIntent> {
+        let req = self
+            .request(
+                reqwest::Method::POST,
+                &format!(
+                    "/v1/payment_intents/{}/cancel",
+                    urlencoding::encode(intent_id)
+                ),
+            )
+            .form(&[("cancellation_reason", "abandoned")]);
+        match self
+            .send::<StripePaymentIntent>(req, "cancel payment intent")
+            .await
+        {
+            Ok(intent) => Ok(intent.into()),
+            // Stripe refuses to cancel intents that are processing or have
+            // succeeded; report the state that stopped it
+            Err(e) => {
+                let intent = self.retrieve_intent(intent_id).await?;
+                match intent.status {
+                    PaymentStatus::Processing
+                    | PaymentStatus::Succeeded
+                    | PaymentStatus::Canceled => Ok(intent),
+                    _ => Err(e),
+                }
+            }
+        }
+    }
+
+    async fn refund(&self, input: &CreateRefund) -> AppResult<Refund> {
+        let refund_id = input.refund_id.to_string();
+        let form = [
+            ("payment_intent", input.payment_id.clone()),
+            ("amount", input.amount.amount.to_string()),
+            ("metadata[refund_request_id]", refund_id.clone()),
+        ];
+        let req = self
+            .request(reqwest::Method::POST, "/v1/refunds")
+            .header("Idempotency-Key", format!("refund-{}", refund_id))
+            .form(&form);
+        let refund: StripeRefund = self.send(req, "create refund").await?;
+        Ok(Refund {
+            status: map_refund_status(&refund.status),
+            id: refund.id,
+        })
+    }
+
+    fn signature_header(&self) -> &'static str {
+        "Stripe-Signature"
+    }
+
+    fn parse_event(&self, payload: &[u8]) -> AppResult<PaymentEvent> {
+        let event: StripeEvent = serde_json::from_slice(payload)
+            .map_err(|e| AppError::BadRequest(format!("Invalid Stripe event: {}", e)))?;
+        Ok(map_event(event))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_stripe_states() {
+        assert_eq!(map_status("succeeded"), PaymentStatus::Succeeded);
+        assert_eq!(map_status("processing"), PaymentStatus::Processing);
+        assert_eq!(map_status("requires_capture"), PaymentStatus::Processing);
+        assert_eq!(map_status("canceled"), PaymentStatus::Canceled);
+        assert_eq!(
+            map_status("requires_payment_method"),
+            PaymentStatus::RequiresPayment
+        );
+        assert_eq!(
+            map_status("requires_action"),
+            PaymentStatus::RequiresPayment
+        );
+        assert_eq!(map_refund_status("succeeded"), RefundStatus::Succeeded);
+        assert_eq!(map_refund_status("pending"), RefundStatus::Pending);
+        assert_eq!(map_refund_status("canceled"), RefundStatus::Failed);
+    }
+}
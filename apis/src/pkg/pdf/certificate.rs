@@ -0,0 +1,125 @@
+//! Completion certificate layout
+
+use super::{A4_LANDSCAPE, Font, Page, Rgb, UnsupportedText, fit_size, render};
+
+const ACCENT: Rgb = Rgb(0.16, 0.29, 0.52);
+const MUTED: Rgb = Rgb(0.35, 0.35, 0.35);
+
+/// Widest a line of text may be, leaving room for the border
+const MAX_TEXT_WIDTH: f32 = 680.0;
+
+/// What gets printed on a certificate
+#[derive(Debug, Clone)]
+pub struct CertificateDocument {
+    pub learner_name: String,
+    pub course_title: String,
+    pub instructor_name: String,
+    pub issued_on: chrono::NaiveDate,
+    pub verification_code: String,
+    /// Full URL where the certificate can be verified
+    pub verify_url: String,
+}
+
+/// Render a one-page A4 landscape certificate.
+///
+/// Fails when a name or title has characters the standard fonts cannot print.
+pub fn render_certificate(doc: &CertificateDocument) -> Result<Vec<u8>, UnsupportedText> {
+    let mut page = Page::new(A4_LANDSCAPE);
+    let (width, height) = A4_LANDSCAPE;
+    page.stroke_rect(24.0, 24.0, width - 48.0, height - 48.0, 3.0, ACCENT);
+    page.stroke_rect(34.0, 34.0, width - 68.0, height - 68.0, 0.75, ACCENT);
+
+    page.text_centered(
+        Font::HelveticaBold,
+        34.0,
+        460.0,
+        "Certificate of Completion",
+        ACCENT,
+    )?;
+    page.text_centered(Font::Helvetica, 14.0, 405.0, "This certifies that", MUTED)?;
+    let name_size = fit_size(Font::HelveticaBold, 30.0, MAX_TEXT_WIDTH, &doc.learner_name);
+    page.text_centered(
+        Font::HelveticaBold,
+        name_size,
+        358.0,
+        &doc.learner_name,
+        Rgb::BLACK,
+    )?;
+    page.line((251.0, 345.0), (591.0, 345.0), 0.75, MUTED);
+    page.text_centered(
+        Font::Helvetica,
+        14.0,
+        310.0,
+        "has successfully completed the course",
+        MUTED,
+    )?;
+    let title_size = fit_size(Font::HelveticaBold, 22.0, MAX_TEXT_WIDTH, &doc.course_title);
+    page.text_centered(
+        Font::HelveticaBold,
+        title_size,
+        272.0,
+        &doc.course_title,
+        Rgb::BLACK,
+    )?;
+    let instructor = format!("Instructor: {}", doc.instructor_name);
+    page.text_centered(
+        Font::Helvetica,
+        fit_size(Font::Helvetica, 13.0, MAX_TEXT_WIDTH, &instructor),
+        228.0,
+        &instructor,
+        MUTED,
+    )?;
+    page.text_centered(
+        Font::Helvetica,
+        13.0,
+        208.0,
+        &format!("Issued on {}", doc.issued_on.format("%B %-d, %Y")),
+        MUTED,
+    )?;
+
+    page.text_centered(
+        Font::HelveticaBold,
+        11.0,
+        96.0,
+        &format!("Verification code: {}", doc.verification_code),
+        Rgb::BLACK,
+    )?;
+    let verify = format!("Verify at {}", doc.verify_url);
+    page.text_centered(
+        Font::Helvetica,
+        fit_size(Font::Helvetica, 10.0, MAX_TEXT_WIDTH, &verify),
+        78.0,
+        &verify,
+        MUTED,
+    )?;
+
+    Ok(render(
+        &page,
+        &format!("Certificate - {} - {}", doc.course_title, doc.learner_name),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(learner_name: &str) -> CertificateDocument {
+        CertificateDocument {
+            learner_name: learner_name.into(),
+            course_title: "Rust for Everyone".into(),
+            instructor_name: "Zoë Martín".into(),
+            issued_on: chrono::NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            verification_code: "ABCD-EFGH-JKLM".into(),
+            verify_url: "https://example.com/verify/ABCD-EFGH-JKLM".into(),
+        }
+    }
+
+    #[test]
+    fn names_outside_the_fonts_are_not_printed_as_placeholders() {
+        let pdf = render_certificate(&document("José Núñez")).unwrap();
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+
+        let err = render_certificate(&document("অমিত রায়")).unwrap_err();
+        assert_eq!(err.text, "অমিত রায়");
+    }
+}
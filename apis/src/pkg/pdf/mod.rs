@@ -0,0 +1,382 @@
+//! Minimal single-page PDF writer
+//!
+//! Enough for generated documents such as certificates: text in the standard
+//! Helvetica fonts, lines and rectangles on one page. The standard fonts need
+//! no embedding, so the output stays a few kilobytes. Text is encoded as
+//! WinAnsi (Latin-1 plus typographic punctuation); drawing text with
+//! characters outside it fails with [`UnsupportedText`] rather than printing
+//! them as `?`.
+
+pub mod certificate;
+
+use std::fmt::{self, Write as _};
+
+/// A4 landscape, in points
+pub const A4_LANDSCAPE: (f32, f32) = (842.0, 595.0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Font {
+    Helvetica,
+    HelveticaBold,
+}
+
+impl Font {
+    fn resource_name(self) -> &'static str {
+        match self {
+            Font::Helvetica => "F1",
+            Font::HelveticaBold => "F2",
+        }
+    }
+}
+
+/// Text the standard fonts cannot print
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedText {
+    pub text: String,
+    /// Offending characters, each listed once
+    pub chars: Vec<char>,
+}
+
+impl fmt::Display for UnsupportedText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let chars: String = self.chars.iter().collect();
+        write!(
+            f,
+            "\"{}\" has characters the PDF fonts cannot print: {}",
+            self.text, chars
+        )
+    }
+}
+
+impl std::error::Error for UnsupportedText {}
+
+/// RGB colour with components in 0..=1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rgb(pub f32, pub f32, pub f32);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0.0, 0.0, 0.0);
+}
+
+/// One page of drawing operations; the origin is the bottom-left corner
+#[derive(Debug, Clone)]
+pub struct Page {
+    pub width: f32,
+    pub height: f32,
+    ops: String,
+}
+
+impl Page {
+    pub fn new((width, height): (f32, f32)) -> Self {
+        Self {
+            width,
+            height,
+            ops: String::new(),
+        }
+    }
+
+    pub fn stroke_rect(&mut self, x: f32, y: f32, w: f32, h: f32, line_width: f32, color: Rgb) {
+        let _ = writeln!(
+            self.ops,
+            "q {} {} {} RG {} w {} {} {} {} re S Q",
+            num(color.0),
+            num(color.1),
+            num(color.2),
+            num(line_width),
+            num(x),
+            num(y),
+            num(w),
+            num(h)
+        );
+    }
+
+    pub fn line(&mut self, from: (f32, f32), to: (f32, f32), line_width: f32, color: Rgb) {
+        let _ = writeln!(
+            self.ops,
+            "q {} {} {} RG {} w {} {} m {} {} l S Q",
+            num(color.0),
+            num(color.1),
+            num(color.2),
+            num(line_width),
+            num(from.0),
+            num(from.1),
+            num(to.0),
+            num(to.1)
+        );
+    }
+
+    /// Draw `text` with its baseline starting at (`x`, `y`)
+    pub fn text(
+        &mut self,
+        font: Font,
+        size: f32,
+        x: f32,
+        y: f32,
+        text: &str,
+        color: Rgb,
+    ) -> Result<(), UnsupportedText> {
+        let mut literal = String::with_capacity(text.len() + 2);
+        literal.push('(');
+        for byte in try_encode_win_ansi(text)? {
+            match byte {
+                b'(' | b')' | b'\\' => {
+                    literal.push('\\');
+                    literal.push(byte as char);
+                }
+                0x20..=0x7e => literal.push(byte as char),
+                _ => {
+                    let _ = write!(literal, "\\{:03o}", byte);
+                }
+            }
+        }
+        literal.push(')');
+        let _ = writeln!(
+            self.ops,
+            "BT {} {} {} rg /{} {} Tf {} {} Td {} Tj ET",
+            num(color.0),
+            num(color.1),
+            num(color.2),
+            font.resource_name(),
+            num(size),
+            num(x),
+            num(y),
+            literal
+        );
+        Ok(())
+    }
+
+    /// Draw `text` horizontally centred on the page
+    pub fn text_centered(
+        &mut self,
+        font: Font,
+        size: f32,
+        y: f32,
+        text: &str,
+        color: Rgb,
+    ) -> Result<(), UnsupportedText> {
+        let x = (self.width - text_width(font, size, text)) / 2.0;
+        self.text(font, size, x, y, text, color)
+    }
+}
+
+/// Serialize `page` as a complete PDF document
+pub fn render(page: &Page, title: &str) -> Vec<u8> {
+    let mut title_literal = String::from("<FEFF");
+    for unit in title.encode_utf16() {
+        let _ = write!(title_literal, "{:04X}", unit);
+    }
+    title_literal.push('>');
+
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /Font << /F1 5 0 R /F2 6 0 R >> >> /Contents 4 0 R >>",
+            num(page.width),
+            num(page.height)
+        ),
+        format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            page.ops.len(),
+            page.ops
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+        format!("<< /Title {} >>", title_literal),
+    ];
+
+    let mut out = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, body) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", i + 1, body).as_bytes());
+    }
+    let xref_offset = out.len();
+    let mut trailer = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(trailer, "{:010} 00000 n ", offset);
+    }
+    let _ = write!(
+        trailer,
+        "trailer\n<< /Size {} /Root 1 0 R /Info 7 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref_offset
+    );
+    out.extend_from_slice(trailer.as_bytes());
+    out
+}
+
+/// Width of `text` in points when set in `font` at `size`
+pub fn text_width(font: Font, size: f32, text: &str) -> f32 {
+    let units: u32 = encode_win_ansi(text)
+        .into_iter()
+        .map(|b| glyph_width(font, b) as u32)
+        .sum();
+    units as f32 * size / 1000.0
+}
+
+/// Largest size up to `max_size` at which `text` fits in `max_width`
+pub fn fit_size(font: Font, max_size: f32, max_width: f32, text: &str) -> f32 {
+    let width = text_width(font, max_size, text);
+    if width <= max_width {
+        max_size
+    } else {
+        max_size * max_width / width
+    }
+}
+
+/// Encode as WinAnsi (CP-1252); unmappable characters become `?`.
+/// Only for measuring; drawing goes through [`try_encode_win_ansi`].
+fn encode_win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|ch| win_ansi_byte(ch).unwrap_or(b'?'))
+        .collect()
+}
+
+/// Encode as WinAnsi (CP-1252), failing on characters it cannot represent
+fn try_encode_win_ansi(text: &str) -> Result<Vec<u8>, UnsupportedText> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut unsupported: Vec<char> = Vec::new();
+    for ch in text.chars() {
+        match win_ansi_byte(ch) {
+            Some(byte) => bytes.push(byte),
+            None if !unsupported.contains(&ch) => unsupported.push(ch),
+            None => {}
+        }
+    }
+    if unsupported.is_empty() {
+        Ok(bytes)
+    } else {
+        Err(UnsupportedText {
+            text: text.to_string(),
+            chars: unsupported,
+        })
+    }
+}
+
+fn win_ansi_byte(ch: char) -> Option<u8> {
+    Some(match ch {
+        '\u{20}'..='\u{7e}' | '\u{a0}'..='\u{ff}' => ch as u8,
+        '€' => 0x80,
+        '‚' => 0x82,
+        '„' => 0x84,
+        '…' => 0x85,
+        '‘' => 0x91,
+        '’' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '•' => 0x95,
+        '–' => 0x96,
+        '—' => 0x97,
+        '™' => 0x99,
+        _ => return None,
+    })
+}
+
+/// Advance width (1/1000 em) of a WinAnsi byte, from the Adobe font metrics.
+/// Bytes outside printable ASCII use the width of a typical lowercase letter.
+fn glyph_width(font: Font, byte: u8) -> u16 {
+    #[rustfmt::skip]
+    const HELVETICA: [u16; 95] = [
+        278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+        556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+        1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+        667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+        333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+        556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+    ];
+    #[rustfmt::skip]
+    const HELVETICA_BOLD: [u16; 95] = [
+        278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278,
+        556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611,
+        975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778,
+        667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556,
+        333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611,
+        611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+    ];
+    let table = match font {
+        Font::Helvetica => &HELVETICA,
+        Font::HelveticaBold => &HELVETICA_BOLD,
+    };
+    match byte {
+        0x20..=0x7e => table[(byte - 0x20) as usize],
+        _ => 556,
+    }
+}
+
+/// Format a coordinate without trailing zeros
+fn num(value: f32) -> String {
+    let s = format!("{:.2}", value);
+    s.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xref_points_at_each_object() {
+        let mut page = Page::new(A4_LANDSCAPE);
+        page.text(
+            Font::Helvetica,
+            12.0,
+            10.0,
+            10.0,
+            "Hello (world)",
+            Rgb::BLACK,
+        )
+        .unwrap();
+        let pdf = render(&page, "Test");
+        let text = String::from_utf8_lossy(&pdf);
+
+        let start: usize = text
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|s| s.lines().next())
+            .and_then(|s| s.parse().ok())
+            .unwrap();
+        let xref = std::str::from_utf8(&pdf[start..]).unwrap();
+        assert!(xref.starts_with("xref\n0 8\n"));
+        let entries: Vec<usize> = xref
+            .lines()
+            .skip(3)
+            .take(7)
+            .map(|l| l[..10].parse().unwrap())
+            .collect();
+        for (i, offset) in entries.into_iter().enumerate() {
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj", i + 1).as_bytes()));
+        }
+        assert!(text.contains("(Hello \\(world\\)) Tj"));
+    }
+
+    #[test]
+    fn text_is_win_ansi_encoded() {
+        assert_eq!(encode_win_ansi("Zoë – 日"), b"Zo\xeb \x96 ?".to_vec());
+        let mut page = Page::new(A4_LANDSCAPE);
+        page.text(Font::Helvetica, 12.0, 0.0, 0.0, "é", Rgb::BLACK)
+            .unwrap();
+        assert!(page.ops.contains("(\\351) Tj"));
+    }
+
+    #[test]
+    fn unprintable_text_is_refused() {
+        let mut page = Page::new(A4_LANDSCAPE);
+        let err = page
+            .text_centered(Font::Helvetica, 12.0, 0.0, "অমিত অমিত Roy", Rgb::BLACK)
+            .unwrap_err();
+        assert_eq!(err.chars, ['অ', 'ম', 'ি', 'ত']);
+        assert!(page.ops.is_empty());
+    }
+
+    #[test]
+    fn long_text_shrinks_to_fit() {
+        assert!((text_width(Font::Helvetica, 10.0, "Hi") - 9.44).abs() < 0.001);
+        let long = "A".repeat(100);
+        let size = fit_size(Font::HelveticaBold, 24.0, 500.0, &long);
+        assert!((text_width(Font::HelveticaBold, size, &long) - 500.0).abs() < 0.01);
+        assert_eq!(fit_size(Font::HelveticaBold, 24.0, 500.0, "Short"), 24.0);
+    }
+}
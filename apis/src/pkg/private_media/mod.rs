@@ -0,0 +1,203 @@
+//! Private media storage and signed download URLs
+//!
+//! Files stored here live outside the statically served `./uploads` tree and
+//! are addressed by a storage key such as `lessons/<uuid>.mp4`. A download URL
+//! carries the key, an expiry timestamp and an HMAC-SHA256 signature over both,
+//! so links cannot be forged, re-targeted at another file, or used after they
+//! expire.
+//!
+//! Typical usage:
+//! ```rust
+//! use execute_academy::pkg::private_media::UrlSigner;
+//!
+//! let signer = UrlSigner::new("secret");
+//! let link = signer.sign_download("lessons/intro.mp4", 900, 1_700_000_000);
+//! assert!(link.url.starts_with("/api/media/private/lessons/intro.mp4?expires="));
+//! ```
+
+use std::path::{Component, Path, PathBuf};
+
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::upload::{LocalFsStorage, Storage};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Route prefix under which signed private downloads are served.
+pub const DOWNLOAD_ROUTE_PREFIX: &str = "/api/media/private";
+
+/// Sub-directory (and key prefix) for private lesson content files.
+pub const LESSON_CONTENTS_DIR: &str = "lessons";
+
+/// A freshly issued download link.
+#[derive(Debug, Clone)]
+pub struct SignedDownload {
+    /// Relative URL, e.g. `/api/media/private/lessons/<file>?expires=..&signature=..`
+    pub url: String,
+    /// Unix timestamp (seconds) after which the link is rejected
+    pub expires_at: i64,
+}
+
+/// Signs and verifies private download URLs with HMAC-SHA256.
+#[derive(Clone)]
+pub struct UrlSigner {
+    secret: Vec<u8>,
+}
+
+impl UrlSigner {
+    pub fn new(secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            secret: secret.as_ref().to_vec(),
+        }
+    }
+
+    /// Issue a download URL for `key` valid for `ttl_seconds` from `now`.
+    pub fn sign_download(&self, key: &str, ttl_seconds: i64, now: i64) -> SignedDownload {
+        let expires_at = now.saturating_add(ttl_seconds);
+        let signature = URL_SAFE_NO_PAD.encode(self.mac(key, expires_at).finalize().into_bytes());
+        let encoded_key = key
+            .split('/')
+            .map(|seg| urlencoding::encode(seg).into_owned())
+            .collect::<Vec<_>>()
+            .join("/");
+        SignedDownload {
+            url: format!(
+                "{}/{}?expires={}&signature={}",
+                DOWNLOAD_ROUTE_PREFIX, encoded_key, expires_at, signature
+            ),
+            expires_at,
+        }
+    }
+
+    /// Check that `signature` was issued for `key` and `expires_at`, and that
+    /// the link has not expired at `now`. Comparison is constant time.
+    pub fn verify(&self, key: &str, expires_at: i64, signature: &str, now: i64) -> AppResult<()> {
+        let provided = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| AppError::Forbidden("Invalid download signature".into()))?;
+        self.mac(key, expires_at)
+            .verify_slice(&provided)
+            .map_err(|_| AppError::Forbidden("Invalid download signature".into()))?;
+        if now > expires_at {
+            return Err(AppError::Forbidden("Download link has expired".into()));
+        }
+        Ok(())
+    }
+
+    fn mac(&self, key: &str, expires_at: i64) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(key.as_bytes());
+        mac.update(b"\n");
+        mac.update(expires_at.to_string().as_bytes());
+        mac
+    }
+}
+
+/// Resolve a storage key to a path under `root`, rejecting anything that
+/// could escape it (absolute paths, `..`, empty segments).
+pub fn resolve_key(root: &Path, key: &str) -> AppResult<PathBuf> {
+    let relative = Path::new(key);
+    let mut depth = 0usize;
+    for comp in relative.components() {
+        match comp {
+            Component::Normal(_) => depth += 1,
+            _ => return Err(AppError::BadRequest("Invalid media key".into())),
+        }
+    }
+    if depth == 0 || key.contains('\\') {
+        return Err(AppError::BadRequest("Invalid media key".into()));
+    }
+    Ok(root.join(relative))
+}
+
+/// Stream a multipart file into private lesson storage and return its key.
+pub async fn save_lesson_file(
+    root: &Path,
+    field: axum_extra::extract::multipart::Field,
+    original_name: Option<&str>,
+    max_bytes: usize,
+) -> AppResult<String> {
+    let storage = LocalFsStorage::new(root.join(LESSON_CONTENTS_DIR));
+    let saved = storage
+        .save_multipart_field(field, original_name, max_bytes)
+        .await?;
+    lesson_key(&saved)
+}
+
+/// Store an in-memory file in private lesson storage and return its key.
+pub async fn save_lesson_bytes(
+    root: &Path,
+    bytes: &[u8],
+    original_name: Option<&str>,
+) -> AppResult<String> {
+    let storage = LocalFsStorage::new(root.join(LESSON_CONTENTS_DIR));
+    let saved = storage.save_bytes(bytes, original_name).await?;
+    lesson_key(&saved)
+}
+
+/// Turn the web-style path LocalFsStorage reports into a lesson content key;
+/// only the generated file name is meaningful since this tree is never served.
+fn lesson_key(saved: &str) -> AppResult<String> {
+    let file_name = saved
+        .rsplit('/')
+        .next()
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| AppError::Internal("Storage returned an empty path".into()))?;
+    Ok(format!("{}/{}", LESSON_CONTENTS_DIR, file_name))
+}
+
+/// Best-effort removal of a private file; missing files are ignored.
+pub async fn remove_key(root: &Path, key: &str) {
+    if let Ok(path) = resolve_key(root, key) {
+        let _ = tokio::fs::remove_file(path).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query_param<'a>(url: &'a str, name: &str) -> &'a str {
+        url.split(['?', '&'])
+            .find_map(|kv| kv.strip_prefix(&format!("{}=", name)))
+            .unwrap()
+    }
+
+    #[test]
+    fn issued_links_verify_until_expiry() {
+        let signer = UrlSigner::new("secret");
+        let link = signer.sign_download("lessons/a.mp4", 60, 1_000);
+        let sig = query_param(&link.url, "signature");
+        assert_eq!(link.expires_at, 1_060);
+        assert!(signer.verify("lessons/a.mp4", 1_060, sig, 1_060).is_ok());
+        assert!(signer.verify("lessons/a.mp4", 1_060, sig, 1_061).is_err());
+    }
+
+    #[test]
+    fn tampered_links_are_rejected() {
+        let signer = UrlSigner::new("secret");
+        let link = signer.sign_download("lessons/a.mp4", 60, 1_000);
+        let sig = query_param(&link.url, "signature");
+        assert!(signer.verify("lessons/b.mp4", 1_060, sig, 1_000).is_err());
+        assert!(signer.verify("lessons/a.mp4", 9_999, sig, 1_000).is_err());
+        assert!(UrlSigner::new("other").verify("lessons/a.mp4", 1_060, sig, 1_000).is_err());
+        assert!(signer.verify("lessons/a.mp4", 1_060, "not base64!", 1_000).is_err());
+    }
+
+    #[test]
+    fn keys_cannot_escape_root() {
+        let root = Path::new("/srv/private");
+        assert_eq!(
+            resolve_key(root, "lessons/a.mp4").unwrap(),
+            PathBuf::from("/srv/private/lessons/a.mp4")
+        );
+        for bad in ["", "../etc/passwd", "/etc/passwd", "lessons/../../x", "./a"] {
+            assert!(resolve_key(root, bad).is_err(), "{bad:?} should be rejected");
+        }
+    }
+}
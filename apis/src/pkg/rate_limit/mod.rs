@@ -1,9 +1,10 @@
 //! Rate limiting primitives
 //!
-//! This module provides a simple, production-ready sliding-window rate limiter
-//! with an in-memory backend. It is designed to be dependency-free and safe to
-//! use in async contexts because it uses short, non-blocking critical sections
-//! guarded by `std::sync::Mutex`.
+//! This module provides sliding-window rate limiters behind the [`RateLimiter`]
+//! trait. The in-memory backend is dependency-free and safe to use in async
+//! contexts because it uses short, non-blocking critical sections guarded by
+//! `std::sync::Mutex`. The Redis backend in [`redis_limiter`] shares counters
+//! across replicas and falls back to the in-memory limiter when Redis is down.
 //!
 //! Typical usage:
 //! ```rust
@@ -21,8 +22,28 @@
 
 use std::collections::{HashMap, VecDeque};
 use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
+pub mod policy;
+pub mod redis_limiter;
+
+pub use policy::{RateLimitKey, RateLimitPolicies, RateLimitPolicy};
+pub use redis_limiter::RedisSlidingWindowRateLimiter;
+
+/// How many checks happen between sweeps of idle keys in the in-memory limiter
+const SWEEP_EVERY: u64 = 1024;
+
+/// Backend-agnostic rate limiter used by the HTTP middleware.
+#[async_trait::async_trait]
+pub trait RateLimiter: Send + Sync {
+    /// Check if an event for `key` is allowed and consume a slot if yes.
+    async fn check_and_consume(&self, key: &str) -> RateLimitDecision;
+
+    /// Limits this limiter enforces.
+    fn config(&self) -> RateLimitConfig;
+}
+
 /// Immutable configuration for a sliding-window rate limiter
 #[derive(Debug, Clone, Copy)]
 pub struct RateLimitConfig {
@@ -55,7 +76,7 @@ pub struct RateLimitDecision {
 }
 
 impl RateLimitDecision {
-    fn allowed(limit: u32, remaining: u32, reset_after: Duration) -> Self {
+    pub(crate) fn allowed(limit: u32, remaining: u32, reset_after: Duration) -> Self {
         Self {
             allowed: true,
             limit,
@@ -65,7 +86,7 @@ impl RateLimitDecision {
         }
     }
 
-    fn limited(limit: u32, retry_after: Duration, reset_after: Duration) -> Self {
+    pub(crate) fn limited(limit: u32, retry_after: Duration, reset_after: Duration) -> Self {
         Self {
             allowed: false,
             limit,
@@ -83,11 +104,14 @@ impl RateLimitDecision {
 /// window are pruned. If the deque length is below capacity, the event is
 /// accepted and the current timestamp is recorded; otherwise the event is
 /// rejected and the earliest time when it would be accepted is derived from the
-/// oldest timestamp plus the window.
+/// oldest timestamp plus the window. Keys idle for a full window are evicted
+/// periodically so the map does not grow without bound.
 pub struct InMemorySlidingWindowRateLimiter {
     config: RateLimitConfig,
     // Key -> deque of accepted event instants within the current window
     state: Mutex<HashMap<String, VecDeque<Instant>>>,
+    // Checks since creation; drives periodic eviction of idle keys
+    checks: AtomicU64,
 }
 
 impl InMemorySlidingWindowRateLimiter {
@@ -96,6 +120,7 @@ impl InMemorySlidingWindowRateLimiter {
         Self {
             config,
             state: Mutex::new(HashMap::new()),
+            checks: AtomicU64::new(0),
         }
     }
 
@@ -110,6 +135,9 @@ impl InMemorySlidingWindowRateLimiter {
         let window = self.config.window;
 
         let mut guard = self.state.lock().expect("mutex poisoned");
+        if self.checks.fetch_add(1, Ordering::Relaxed) % SWEEP_EVERY == SWEEP_EVERY - 1 {
+            Self::evict_idle(&mut guard, now, window);
+        }
         let deque = guard.entry(key.to_string()).or_insert_with(VecDeque::new);
 
         // Prune timestamps outside the window
@@ -152,12 +180,42 @@ impl InMemorySlidingWindowRateLimiter {
         }
     }
 
+    /// Number of keys currently tracked (including idle ones not yet evicted).
+    pub fn tracked_keys(&self) -> usize {
+        self.state.lock().expect("mutex poisoned").len()
+    }
+
+    /// Drop every key whose newest event is older than the window.
+    pub fn evict_expired(&self) {
+        let mut guard = self.state.lock().expect("mutex poisoned");
+        Self::evict_idle(&mut guard, Instant::now(), self.config.window);
+    }
+
+    fn evict_idle(state: &mut HashMap<String, VecDeque<Instant>>, now: Instant, window: Duration) {
+        state.retain(|_, events| {
+            events
+                .back()
+                .is_some_and(|&last| now.duration_since(last) < window)
+        });
+    }
+
     /// Compose a stable key from parts, useful for namespacing (e.g., ip + route).
     pub fn compose_key(parts: &[&str]) -> String {
         parts.join(":")
     }
 }
 
+#[async_trait::async_trait]
+impl RateLimiter for InMemorySlidingWindowRateLimiter {
+    async fn check_and_consume(&self, key: &str) -> RateLimitDecision {
+        InMemorySlidingWindowRateLimiter::check_and_consume(self, key)
+    }
+
+    fn config(&self) -> RateLimitConfig {
+        self.config
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -212,4 +270,20 @@ mod tests {
         limiter.reset(key);
         assert!(limiter.check_and_consume(key).allowed);
     }
+
+    #[test]
+    fn evicts_idle_keys() {
+        let limiter = InMemorySlidingWindowRateLimiter::new(RateLimitConfig::new(
+            5,
+            Duration::from_millis(20),
+        ));
+        for i in 0..10 {
+            limiter.check_and_consume(&format!("ip:{}", i));
+        }
+        assert_eq!(limiter.tracked_keys(), 10);
+        thread::sleep(Duration::from_millis(30));
+        limiter.check_and_consume("ip:fresh");
+        limiter.evict_expired();
+        assert_eq!(limiter.tracked_keys(), 1);
+    }
 }
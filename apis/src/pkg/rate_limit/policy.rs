@@ -0,0 +1,330 @@
+//! Rate limit policy table
+//!
+//! A policy pairs a route pattern (and optional methods) with a limit, a window
+//! and how callers are identified. The first matching policy wins; requests
+//! that match none fall back to the default policy. Patterns are matched per
+//! path segment: `:name` matches any single segment and a trailing `*` matches
+//! the rest of the path, e.g. `/api/auth/*` or `/api/lessons/:id/video`.
+
+use std::net::IpAddr;
+use std::time::Duration;
+
+use ipnet::IpNet;
+use serde::Deserialize;
+
+use super::RateLimitConfig;
+
+/// How the caller of a request is identified for counting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitKey {
+    /// Always count by client IP (login/OTP endpoints where no user exists yet)
+    Ip,
+    /// Count by authenticated user id, falling back to client IP
+    #[default]
+    UserOrIp,
+}
+
+/// One row of the policy table
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RateLimitPolicy {
+    /// Unique name, also used to namespace limiter keys
+    pub name: String,
+    /// Upper-case HTTP methods this policy applies to; empty means any method
+    #[serde(default)]
+    pub methods: Vec<String>,
+    /// Route pattern, see module docs
+    pub path: String,
+    /// Events allowed per window; `0` disables limiting for matching routes
+    pub limit: u32,
+    /// Window length in seconds
+    pub window_secs: u64,
+    #[serde(default)]
+    pub key: RateLimitKey,
+}
+
+impl RateLimitPolicy {
+    pub fn new(
+        name: &str,
+        methods: &[&str],
+        path: &str,
+        limit: u32,
+        window_secs: u64,
+        key: RateLimitKey,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            methods: methods.iter().map(|m| m.to_ascii_uppercase()).collect(),
+            path: path.to_string(),
+            limit,
+            window_secs,
+            key,
+        }
+    }
+
+    /// Whether a request with `method` and `path` falls under this policy
+    pub fn matches(&self, method: &str, path: &str) -> bool {
+        let method_ok =
+            self.methods.is_empty() || self.methods.iter().any(|m| m.eq_ignore_ascii_case(method));
+        method_ok && path_matches(&self.path, path)
+    }
+
+    /// Whether matching requests bypass limiting entirely
+    pub fn is_unlimited(&self) -> bool {
+        self.limit == 0
+    }
+
+    /// Limiter configuration for this policy
+    pub fn config(&self) -> RateLimitConfig {
+        RateLimitConfig::new(self.limit, Duration::from_secs(self.window_secs))
+    }
+}
+
+/// Ordered policy table plus the IPs that are never limited
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitPolicies {
+    /// Applied when no policy matches
+    pub default: RateLimitPolicy,
+    /// Checked in order; first match wins
+    pub policies: Vec<RateLimitPolicy>,
+    /// Internal networks (probes, other services) exempt from limiting
+    pub allowlist: Vec<IpNet>,
+}
+
+impl RateLimitPolicies {
+    /// Only a default policy, keyed like the original ip+path limiter
+    pub fn single(limit: u32, window_secs: u64) -> Self {
+        Self {
+            default: RateLimitPolicy::new(
+                "default",
+                &[],
+                "/*",
+                limit,
+                window_secs,
+                RateLimitKey::Ip,
+            ),
+            policies: Vec::new(),
+            allowlist: Vec::new(),
+        }
+    }
+
+    /// Policy applying to a request
+    pub fn resolve(&self, method: &str, path: &str) -> &RateLimitPolicy {
+        self.policies
+            .iter()
+            .find(|p| p.matches(method, path))
+            .unwrap_or(&self.default)
+    }
+
+    /// Default policy followed by every table entry
+    pub fn all(&self) -> impl Iterator<Item = &RateLimitPolicy> {
+        std::iter::once(&self.default).chain(self.policies.iter())
+    }
+
+    /// Limiter key counting `caller`, a kind (`user` or `ip`) and its value,
+    /// under `policy`.
+    ///
+    /// A named policy gives the caller one budget across every path it
+    /// matches, so changing an id or sub-path does not reset it; the default
+    /// policy keeps a budget per path, like the original limiter.
+    pub fn counter_key(
+        &self,
+        policy: &RateLimitPolicy,
+        caller: (&str, &str),
+        path: &str,
+    ) -> String {
+        let (kind, id) = caller;
+        if policy.name == self.default.name {
+            [kind, id, "path", path].join(":")
+        } else {
+            [kind, id, "policy", policy.name.as_str()].join(":")
+        }
+    }
+
+    pub fn is_allowlisted(&self, ip: IpAddr) -> bool {
+        self.allowlist.iter().any(|net| net.contains(&ip))
+    }
+
+    /// Reject tables that would misbehave at runtime
+    pub fn validate(&self) -> Result<(), String> {
+        let mut seen = std::collections::HashSet::new();
+        for p in self.all() {
+            if p.name.trim().is_empty() {
+                return Err("rate limit policy name must not be empty".into());
+            }
+            if !seen.insert(p.name.as_str()) {
+                return Err(format!("duplicate rate limit policy '{}'", p.name));
+            }
+            if !p.path.starts_with('/') {
+                return Err(format!(
+                    "rate limit policy '{}' path must start with '/'",
+                    p.name
+                ));
+            }
+            if !p.is_unlimited() && p.window_secs == 0 {
+                return Err(format!(
+                    "rate limit policy '{}' window must be positive",
+                    p.name
+                ));
+            }
+            if let Some(m) = p
+                .methods
+                .iter()
+                .find(|m| http::Method::from_bytes(m.as_bytes()).is_err())
+            {
+                return Err(format!(
+                    "rate limit policy '{}' has invalid method '{}'",
+                    p.name, m
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Stricter defaults for credential and OTP endpoints; health probes are free
+    pub fn builtin() -> Vec<RateLimitPolicy> {
+        use RateLimitKey::Ip;
+        vec![
+            RateLimitPolicy::new("health", &["GET"], "/api/health", 0, 0, Ip),
+            RateLimitPolicy::new("auth-otp", &["POST"], "/api/auth/verify", 5, 300, Ip),
+            RateLimitPolicy::new(
+                "auth-otp-resend",
+                &["POST"],
+                "/api/auth/resend-otp",
+                3,
+                300,
+                Ip,
+            ),
+            RateLimitPolicy::new("auth-login", &["POST"], "/api/auth/login", 10, 300, Ip),
+            RateLimitPolicy::new(
+                "auth-password-reset",
+                &["POST"],
+                "/api/auth/forgot-password",
+                5,
+                900,
+                Ip,
+            ),
+            RateLimitPolicy::new(
+                "auth-password-reset-confirm",
+                &["POST"],
+                "/api/auth/reset-password",
+                5,
+                900,
+                Ip,
+            ),
+            RateLimitPolicy::new("auth", &[], "/api/auth/*", 20, 60, Ip),
+        ]
+    }
+}
+
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let mut pattern_segments = pattern.trim_end_matches('/').split('/');
+    let mut path_segments = path.trim_end_matches('/').split('/');
+    loop {
+        match (pattern_segments.next(), path_segments.next()) {
+            (Some("*"), _) if pattern_segments.clone().next().is_none() => return true,
+            (Some(p), Some(s)) => {
+                let wildcard = p == "*" || p.starts_with(':');
+                if (wildcard && s.is_empty()) || (!wildcard && p != s) {
+                    return false;
+                }
+            }
+            (None, None) => return true,
+            _ => return false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pkg::rate_limit::InMemorySlidingWindowRateLimiter;
+
+    #[test]
+    fn matches_patterns_by_segment() {
+        assert!(path_matches("/api/auth/*", "/api/auth/login"));
+        assert!(path_matches("/api/auth/*", "/api/auth"));
+        assert!(!path_matches("/api/auth/*", "/api/authors"));
+        assert!(path_matches(
+            "/api/lessons/:id/video",
+            "/api/lessons/42/video"
+        ));
+        assert!(!path_matches(
+            "/api/lessons/:id/video",
+            "/api/lessons/42/video/extra"
+        ));
+        assert!(path_matches("/api/health", "/api/health/"));
+        assert!(path_matches("/*", "/anything/at/all"));
+    }
+
+    #[test]
+    fn first_matching_policy_wins() {
+        let mut table = RateLimitPolicies::single(25, 60);
+        table.policies = RateLimitPolicies::builtin();
+        assert_eq!(table.resolve("POST", "/api/auth/login").name, "auth-login");
+        assert_eq!(table.resolve("GET", "/api/auth/login").name, "auth");
+        assert_eq!(table.resolve("POST", "/api/auth/refresh").name, "auth");
+        assert_eq!(table.resolve("GET", "/api/courses").name, "default");
+        assert!(table.resolve("GET", "/api/health").is_unlimited());
+        assert!(table.validate().is_ok());
+    }
+
+    #[test]
+    fn paths_under_one_policy_share_its_quota() {
+        let mut table = RateLimitPolicies::single(25, 60);
+        table.policies = vec![RateLimitPolicy::new(
+            "lesson-video",
+            &[],
+            "/api/lessons/:id/video",
+            1,
+            60,
+            RateLimitKey::Ip,
+        )];
+        let limiter = InMemorySlidingWindowRateLimiter::new(table.policies[0].config());
+        let caller = ("ip", "203.0.113.7");
+        let key = |path: &str| table.counter_key(table.resolve("GET", path), caller, path);
+
+        assert_eq!(key("/api/lessons/1/video"), key("/api/lessons/2/video"));
+        assert!(
+            limiter
+                .check_and_consume(&key("/api/lessons/1/video"))
+                .allowed
+        );
+        assert!(
+            !limiter
+                .check_and_consume(&key("/api/lessons/2/video"))
+                .allowed
+        );
+
+        // The default policy still counts each path on its own
+        assert_ne!(key("/api/courses/1"), key("/api/courses/2"));
+    }
+
+    #[test]
+    fn allowlist_and_validation() {
+        let mut table = RateLimitPolicies::single(25, 60);
+        table.allowlist = vec!["10.0.0.0/8".parse().unwrap(), "::1/128".parse().unwrap()];
+        assert!(table.is_allowlisted("10.1.2.3".parse().unwrap()));
+        assert!(table.is_allowlisted("::1".parse().unwrap()));
+        assert!(!table.is_allowlisted("192.168.0.1".parse().unwrap()));
+
+        table.policies = vec![RateLimitPolicy::new(
+            "default",
+            &[],
+            "/x",
+            1,
+            1,
+            RateLimitKey::Ip,
+        )];
+        assert!(table.validate().unwrap_err().contains("duplicate"));
+        table.policies = vec![RateLimitPolicy::new(
+            "bad",
+            &["G T"],
+            "/x",
+            1,
+            1,
+            RateLimitKey::Ip,
+        )];
+        assert!(table.validate().unwrap_err().contains("method"));
+    }
+}
@@ -0,0 +1,169 @@
+//! Redis-backed sliding-window rate limiter
+//!
+//! Each key is a sorted set of accepted event timestamps (a sliding-window
+//! log). A Lua script prunes, counts and records atomically using the Redis
+//! server clock, so every replica sees the same window regardless of local
+//! clock skew. When Redis errors, checks are served by an in-memory limiter
+//! for a short cooldown instead of failing requests.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use redis::aio::MultiplexedConnection;
+
+use super::{InMemorySlidingWindowRateLimiter, RateLimitConfig, RateLimitDecision, RateLimiter};
+use crate::pkg::logger::warn;
+use crate::pkg::redis::{RedisManager, RedisOps};
+
+/// How long to stay on the in-memory fallback after a Redis failure
+const REDIS_RETRY_COOLDOWN: Duration = Duration::from_secs(5);
+
+/// Upper bound on a Redis round trip before falling back; keeps a hung
+/// connection from stalling every request
+const REDIS_CHECK_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// KEYS[1] = window key; ARGV = window_ms, limit, unique member suffix.
+/// Returns {allowed (0/1), remaining, reset_ms, retry_ms}.
+const SLIDING_WINDOW_LUA: &str = r#"
+local t = redis.call('TIME')
+local now = tonumber(t[1]) * 1000 + math.floor(tonumber(t[2]) / 1000)
+local window = tonumber(ARGV[1])
+local limit = tonumber(ARGV[2])
+
+redis.call('ZREMRANGEBYSCORE', KEYS[1], '-inf', now - window)
+local count = redis.call('ZCARD', KEYS[1])
+
+if count < limit then
+  redis.call('ZADD', KEYS[1], now, now .. '-' .. ARGV[3])
+  redis.call('PEXPIRE', KEYS[1], window)
+  local oldest = redis.call('ZRANGE', KEYS[1], 0, 0, 'WITHSCORES')
+  local reset = tonumber(oldest[2]) + window - now
+  return {1, limit - count - 1, reset, 0}
+end
+
+local oldest = redis.call('ZRANGE', KEYS[1], 0, 0, 'WITHSCORES')
+local retry = window
+if oldest[2] then
+  retry = tonumber(oldest[2]) + window - now
+end
+return {0, 0, retry, retry}
+"#;
+
+/// Sliding-window limiter sharing its counters across replicas through Redis.
+pub struct RedisSlidingWindowRateLimiter {
+    redis: Arc<RedisManager>,
+    config: RateLimitConfig,
+    /// Namespace for keys, e.g. `rl:http`
+    prefix: String,
+    script: redis::Script,
+    conn: tokio::sync::Mutex<Option<MultiplexedConnection>>,
+    fallback: InMemorySlidingWindowRateLimiter,
+    /// While set and in the future, Redis is skipped in favour of `fallback`
+    degraded_until: Mutex<Option<Instant>>,
+}
+
+impl RedisSlidingWindowRateLimiter {
+    pub fn new(
+        redis: Arc<RedisManager>,
+        config: RateLimitConfig,
+        prefix: impl Into<String>,
+    ) -> Self {
+        Self {
+            redis,
+            config,
+            prefix: prefix.into(),
+            script: redis::Script::new(SLIDING_WINDOW_LUA),
+            conn: tokio::sync::Mutex::new(None),
+            fallback: InMemorySlidingWindowRateLimiter::new(config),
+            degraded_until: Mutex::new(None),
+        }
+    }
+
+    fn is_degraded(&self) -> bool {
+        let mut guard = self.degraded_until.lock().expect("mutex poisoned");
+        match *guard {
+            Some(until) if Instant::now() < until => true,
+            Some(_) => {
+                *guard = None;
+                false
+            }
+            None => false,
+        }
+    }
+
+    fn mark_degraded(&self, err: &dyn std::fmt::Display) {
+        let mut guard = self.degraded_until.lock().expect("mutex poisoned");
+        if guard.is_none() {
+            warn(format!(
+                "Redis rate limiter unavailable, using in-memory fallback: {}",
+                err
+            ));
+        }
+        *guard = Some(Instant::now() + REDIS_RETRY_COOLDOWN);
+    }
+
+    /// Reuse one multiplexed connection; reconnect lazily after failures.
+    async fn connection(&self) -> Result<MultiplexedConnection, redis::RedisError> {
+        let mut guard = self.conn.lock().await;
+        if let Some(conn) = guard.as_ref() {
+            return Ok(conn.clone());
+        }
+        let conn = self.redis.get_connection().await?;
+        *guard = Some(conn.clone());
+        Ok(conn)
+    }
+
+    async fn check_redis(&self, key: &str) -> Result<RateLimitDecision, redis::RedisError> {
+        let mut conn = self.connection().await?;
+        let window_ms = self.config.window.as_millis() as u64;
+        let result: Result<(i64, i64, i64, i64), _> = self
+            .script
+            .key(format!("{}:{}", self.prefix, key))
+            .arg(window_ms)
+            .arg(self.config.capacity)
+            .arg(uuid::Uuid::new_v4().simple().to_string())
+            .invoke_async(&mut conn)
+            .await;
+        let (allowed, remaining, reset_ms, retry_ms) = match result {
+            Ok(v) => v,
+            Err(e) => {
+                // Drop the cached connection so the next attempt reconnects
+                *self.conn.lock().await = None;
+                return Err(e);
+            }
+        };
+
+        let limit = self.config.capacity;
+        let reset_after = Duration::from_millis(reset_ms.max(0) as u64);
+        Ok(if allowed == 1 {
+            RateLimitDecision::allowed(limit, remaining.max(0) as u32, reset_after)
+        } else {
+            RateLimitDecision::limited(
+                limit,
+                Duration::from_millis(retry_ms.max(0) as u64),
+                reset_after,
+            )
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl RateLimiter for RedisSlidingWindowRateLimiter {
+    async fn check_and_consume(&self, key: &str) -> RateLimitDecision {
+        if !self.is_degraded() {
+            match tokio::time::timeout(REDIS_CHECK_TIMEOUT, self.check_redis(key)).await {
+                Ok(Ok(decision)) => return decision,
+                Ok(Err(e)) => self.mark_degraded(&e),
+                Err(elapsed) => {
+                    *self.conn.lock().await = None;
+                    self.mark_degraded(&elapsed);
+                }
+            }
+        }
+        self.fallback.check_and_consume(key)
+    }
+
+    fn config(&self) -> RateLimitConfig {
+        self.config
+    }
+}
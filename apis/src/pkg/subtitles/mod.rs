@@ -0,0 +1,352 @@
+//! WebVTT / SRT subtitle parsing and conversion
+//!
+//! Both formats are parsed into a list of [`Cue`]s, validated (well-formed
+//! timestamps, `start < end`, cues ordered by start time) and can be written
+//! back out in either format. A plain-text transcript is derived from the cue
+//! text with markup removed.
+//!
+//! Typical usage:
+//! ```rust
+//! use execute_academy::pkg::subtitles::{self, SubtitleFormat};
+//!
+//! let srt = "1\n00:00:01,000 --> 00:00:02,500\nHello <i>world</i>\n";
+//! let cues = subtitles::parse(srt, SubtitleFormat::Srt).unwrap();
+//! assert_eq!(subtitles::transcript(&cues), "Hello world");
+//! assert!(subtitles::to_vtt(&cues).starts_with("WEBVTT"));
+//! ```
+
+use crate::pkg::error::{AppError, AppResult};
+
+/// A single timed caption.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cue {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    /// Caption text, possibly multi-line and containing inline markup
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubtitleFormat {
+    Vtt,
+    Srt,
+}
+
+impl SubtitleFormat {
+    /// Parse a `format` query value or file extension (`vtt`, `srt`).
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().trim_start_matches('.').to_ascii_lowercase().as_str() {
+            "vtt" | "webvtt" => Some(SubtitleFormat::Vtt),
+            "srt" => Some(SubtitleFormat::Srt),
+            _ => None,
+        }
+    }
+
+    /// Detect the format of an upload from its file name, falling back to
+    /// sniffing the `WEBVTT` header.
+    pub fn detect(file_name: Option<&str>, body: &str) -> Self {
+        file_name
+            .and_then(|n| n.rsplit_once('.'))
+            .and_then(|(_, ext)| SubtitleFormat::parse(ext))
+            .unwrap_or_else(|| {
+                if body.trim_start_matches('\u{feff}').starts_with("WEBVTT") {
+                    SubtitleFormat::Vtt
+                } else {
+                    SubtitleFormat::Srt
+                }
+            })
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            SubtitleFormat::Vtt => "text/vtt; charset=utf-8",
+            SubtitleFormat::Srt => "application/x-subrip; charset=utf-8",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SubtitleFormat::Vtt => "vtt",
+            SubtitleFormat::Srt => "srt",
+        }
+    }
+}
+
+/// Parse and validate a subtitle document.
+pub fn parse(input: &str, format: SubtitleFormat) -> AppResult<Vec<Cue>> {
+    let normalized = input
+        .trim_start_matches('\u{feff}')
+        .replace("\r\n", "\n")
+        .replace('\r', "\n");
+    let mut blocks = split_blocks(&normalized);
+
+    if format == SubtitleFormat::Vtt {
+        let header = blocks.first().map(|(_, lines)| lines[0]).unwrap_or("");
+        if !(header == "WEBVTT" || header.starts_with("WEBVTT ") || header.starts_with("WEBVTT\t")) {
+            return Err(invalid(1, "WebVTT files must start with a WEBVTT header"));
+        }
+        blocks.remove(0);
+    }
+
+    let mut cues: Vec<Cue> = Vec::new();
+    for (line_no, lines) in blocks {
+        if format == SubtitleFormat::Vtt && is_vtt_metadata_block(lines[0]) {
+            continue;
+        }
+        // The timing line is the first line containing "-->"; anything before
+        // it is a cue identifier (SRT counter or optional VTT id)
+        let Some(timing_idx) = lines.iter().position(|l| l.contains("-->")) else {
+            return Err(invalid(line_no, "cue is missing a timing line"));
+        };
+        if timing_idx > 1 {
+            return Err(invalid(line_no, "unexpected text before the timing line"));
+        }
+        let timing_line_no = line_no + timing_idx;
+        let (start_ms, end_ms) = parse_timing(lines[timing_idx], format)
+            .ok_or_else(|| invalid(timing_line_no, "malformed cue timing"))?;
+        if end_ms <= start_ms {
+            return Err(invalid(timing_line_no, "cue must end after it starts"));
+        }
+        if cues.last().is_some_and(|prev| start_ms < prev.start_ms) {
+            return Err(invalid(timing_line_no, "cues must be ordered by start time"));
+        }
+        let text = lines[timing_idx + 1..].join("\n");
+        if text.trim().is_empty() {
+            return Err(invalid(timing_line_no, "cue has no text"));
+        }
+        cues.push(Cue {
+            start_ms,
+            end_ms,
+            text,
+        });
+    }
+
+    if cues.is_empty() {
+        return Err(AppError::BadRequest("Subtitle file contains no cues".into()));
+    }
+    Ok(cues)
+}
+
+/// Render cues as a WebVTT document.
+pub fn to_vtt(cues: &[Cue]) -> String {
+    let mut out = String::from("WEBVTT\n");
+    for cue in cues {
+        out.push('\n');
+        out.push_str(&format!(
+            "{} --> {}\n{}\n",
+            format_timestamp(cue.start_ms, '.'),
+            format_timestamp(cue.end_ms, '.'),
+            cue.text
+        ));
+    }
+    out
+}
+
+/// Render cues as an SRT document.
+pub fn to_srt(cues: &[Cue]) -> String {
+    let mut out = String::new();
+    for (i, cue) in cues.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        out.push_str(&format!(
+            "{}\n{} --> {}\n{}\n",
+            i + 1,
+            format_timestamp(cue.start_ms, ','),
+            format_timestamp(cue.end_ms, ','),
+            cue.text
+        ));
+    }
+    out
+}
+
+/// Render cues in `format`.
+pub fn render(cues: &[Cue], format: SubtitleFormat) -> String {
+    match format {
+        SubtitleFormat::Vtt => to_vtt(cues),
+        SubtitleFormat::Srt => to_srt(cues),
+    }
+}
+
+/// Plain-text transcript: markup stripped, whitespace collapsed, and
+/// consecutive repeated captions (roll-up captions) emitted once.
+pub fn transcript(cues: &[Cue]) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    for cue in cues {
+        let text = collapse_whitespace(&decode_entities(&strip_tags(&cue.text)));
+        if text.is_empty() || parts.last() == Some(&text) {
+            continue;
+        }
+        parts.push(text);
+    }
+    parts.join(" ")
+}
+
+/// Blocks of consecutive non-empty lines, each with its 1-based first line number.
+fn split_blocks(input: &str) -> Vec<(usize, Vec<&str>)> {
+    let mut blocks: Vec<(usize, Vec<&str>)> = Vec::new();
+    let mut current: Option<(usize, Vec<&str>)> = None;
+    for (idx, raw) in input.lines().enumerate() {
+        let line = raw.trim_end();
+        if line.trim().is_empty() {
+            if let Some(block) = current.take() {
+                blocks.push(block);
+            }
+            continue;
+        }
+        current.get_or_insert_with(|| (idx + 1, Vec::new())).1.push(line);
+    }
+    if let Some(block) = current {
+        blocks.push(block);
+    }
+    blocks
+}
+
+fn is_vtt_metadata_block(first_line: &str) -> bool {
+    ["NOTE", "STYLE", "REGION"]
+        .iter()
+        .any(|kw| first_line == *kw || first_line.starts_with(&format!("{} ", kw)))
+}
+
+fn parse_timing(line: &str, format: SubtitleFormat) -> Option<(u64, u64)> {
+    let (start, rest) = line.split_once("-->")?;
+    // WebVTT allows cue settings after the end timestamp
+    let end = rest.split_whitespace().next()?;
+    if format == SubtitleFormat::Srt && rest.split_whitespace().count() > 1 {
+        // SRT may carry legacy X1/Y1 coordinates; ignore them
+        if !rest.split_whitespace().skip(1).all(|s| s.contains(':')) {
+            return None;
+        }
+    }
+    Some((
+        parse_timestamp(start.trim(), format)?,
+        parse_timestamp(end, format)?,
+    ))
+}
+
+/// `hh:mm:ss.mmm` (VTT, hours optional) or `hh:mm:ss,mmm` (SRT).
+fn parse_timestamp(value: &str, format: SubtitleFormat) -> Option<u64> {
+    let sep = match format {
+        SubtitleFormat::Vtt => '.',
+        SubtitleFormat::Srt => ',',
+    };
+    let (hms, millis) = value.rsplit_once(sep)?;
+    if millis.len() != 3 || !millis.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let parts: Vec<&str> = hms.split(':').collect();
+    let (hours, minutes, seconds) = match (format, parts.as_slice()) {
+        (_, [h, m, s]) => (*h, *m, *s),
+        (SubtitleFormat::Vtt, [m, s]) => ("0", *m, *s),
+        _ => return None,
+    };
+    let digits = |s: &str, exact: Option<usize>| -> Option<u64> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        if exact.is_some_and(|n| s.len() != n) {
+            return None;
+        }
+        s.parse().ok()
+    };
+    let h = digits(hours, None)?;
+    let m = digits(minutes, Some(2))?;
+    let s = digits(seconds, Some(2))?;
+    if m >= 60 || s >= 60 {
+        return None;
+    }
+    let ms: u64 = millis.parse().ok()?;
+    Some(((h * 60 + m) * 60 + s) * 1000 + ms)
+}
+
+fn format_timestamp(total_ms: u64, sep: char) -> String {
+    let ms = total_ms % 1000;
+    let total_s = total_ms / 1000;
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        total_s / 3600,
+        (total_s / 60) % 60,
+        total_s % 60,
+        sep,
+        ms
+    )
+}
+
+fn strip_tags(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for ch in text.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            '{' if !in_tag => in_tag = true, // SRT styling like {\i1}
+            '}' if in_tag => in_tag = false,
+            c if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&lrm;", "")
+        .replace("&rlm;", "")
+        .replace("&amp;", "&")
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn invalid(line: usize, reason: &str) -> AppError {
+    AppError::BadRequest(format!("Invalid subtitles at line {}: {}", line, reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VTT: &str = "WEBVTT - lesson 1\n\nNOTE produced by hand\n\nintro\n00:01.000 --> 00:02.500 align:start\n<v Ann>Hello &amp; welcome</v>\n\n00:00:03.000 --> 00:00:04.000\nto the <b>course</b>\n";
+
+    #[test]
+    fn parses_vtt_with_ids_notes_and_settings() {
+        let cues = parse(VTT, SubtitleFormat::Vtt).unwrap();
+        assert_eq!(cues.len(), 2);
+        assert_eq!((cues[0].start_ms, cues[0].end_ms), (1_000, 2_500));
+        assert_eq!(transcript(&cues), "Hello & welcome to the course");
+    }
+
+    #[test]
+    fn round_trips_between_formats() {
+        let cues = parse(VTT, SubtitleFormat::Vtt).unwrap();
+        let srt = to_srt(&cues);
+        assert!(srt.starts_with("1\n00:00:01,000 --> 00:00:02,500\n"));
+        let back = parse(&srt, SubtitleFormat::Srt).unwrap();
+        assert_eq!(back, cues);
+        assert_eq!(parse(&to_vtt(&back), SubtitleFormat::Vtt).unwrap(), cues);
+    }
+
+    #[test]
+    fn rejects_bad_timing() {
+        let backwards = "1\n00:00:02,000 --> 00:00:01,000\nHi\n";
+        assert!(parse(backwards, SubtitleFormat::Srt).is_err());
+
+        let unordered = "1\n00:00:05,000 --> 00:00:06,000\nA\n\n2\n00:00:01,000 --> 00:00:02,000\nB\n";
+        assert!(parse(unordered, SubtitleFormat::Srt).is_err());
+
+        let malformed = "1\n00:00:61,000 --> 00:01:02,000\nA\n";
+        assert!(parse(malformed, SubtitleFormat::Srt).is_err());
+
+        assert!(parse("00:01.000 --> 00:02.000\nA\n", SubtitleFormat::Vtt).is_err());
+    }
+
+    #[test]
+    fn transcript_skips_repeated_rollup_lines() {
+        let srt = "1\n00:00:01,000 --> 00:00:02,000\n{\\i1}Same line{\\i0}\n\n2\n00:00:02,000 --> 00:00:03,000\nSame line\n\n3\n00:00:03,000 --> 00:00:04,000\nNext\n";
+        let cues = parse(srt, SubtitleFormat::Srt).unwrap();
+        assert_eq!(transcript(&cues), "Same line Next");
+    }
+}
@@ -6,6 +6,20 @@ use axum_extra::extract::multipart::Field;
 
 use crate::pkg::error::AppError;
 
+/// On-disk root of the publicly served uploads tree.
+pub const PUBLIC_UPLOADS_DIR: &str = "./uploads";
+
+/// URL path segment under which `PUBLIC_UPLOADS_DIR` is served.
+pub const PUBLIC_UPLOADS_SEGMENT: &str = "/uploads/";
+
+/// Split a URL pointing into the public uploads tree into its prefix (up to
+/// and including `/uploads/`) and the path relative to `PUBLIC_UPLOADS_DIR`.
+/// Returns `None` for URLs outside the tree.
+pub fn split_public_upload_url(url: &str) -> Option<(&str, &str)> {
+    let idx = url.find(PUBLIC_UPLOADS_SEGMENT)?;
+    Some(url.split_at(idx + PUBLIC_UPLOADS_SEGMENT.len()))
+}
+
 /// Storage abstraction for saving uploads. Currently only LocalFsStorage is implemented.
 #[async_trait::async_trait]
 pub trait Storage: Send + Sync {
@@ -16,6 +30,10 @@ pub trait Storage: Send + Sync {
         original_name: Option<&str>,
     ) -> Result<String, AppError>;
 
+    /// Save an in-memory buffer under an exact file name (e.g. "<stem>-640w.webp")
+    /// and return its relative path. Used when related files must share a stem.
+    async fn save_bytes_as(&self, bytes: &[u8], file_name: &str) -> Result<String, AppError>;
+
     /// Save a multipart field stream to disk with a size cap; returns a relative path.
     async fn save_multipart_field(
         &self,
@@ -102,6 +120,24 @@ impl Storage for LocalFsStorage {
         Ok(format!("/{}/{}", web_prefix, filename))
     }
 
+    async fn save_bytes_as(&self, bytes: &[u8], file_name: &str) -> Result<String, AppError> {
+        // Only plain file names are accepted; never let callers escape the root
+        if file_name.is_empty()
+            || file_name.contains(['/', '\\'])
+            || file_name == "."
+            || file_name == ".."
+        {
+            return Err(AppError::BadRequest("Invalid file name".into()));
+        }
+        self.ensure_dir()?;
+        let path = self.root.join(file_name);
+        fs::write(&path, bytes)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+        let web_prefix = self.web_prefix_under_uploads();
+        Ok(format!("/{}/{}", web_prefix, file_name))
+    }
+
     async fn save_multipart_field(
         &self,
         mut field: Field,
@@ -138,6 +174,6 @@ impl Storage for LocalFsStorage {
 
 /// Backwards-compatible helper: save bytes into ./uploads using LocalFsStorage.
 pub async fn save_bytes(bytes: &[u8], original_name: Option<&str>) -> Result<String, AppError> {
-    let storage = LocalFsStorage::new("./uploads");
+    let storage = LocalFsStorage::new(PUBLIC_UPLOADS_DIR);
     storage.save_bytes(bytes, original_name).await
 }
@@ -1,6 +1,7 @@
 use axum_extra::extract::Multipart;
 
 use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::money::Money;
 
 /// Universal multipart form-data parser with helpers for common field types.
 ///
@@ -111,6 +112,16 @@ impl MultipartForm {
         self.text(key).and_then(|v| v.parse::<f64>().ok())
     }
 
+    /// Decimal amount such as `19.99` in `currency`; malformed amounts are an error
+    pub fn money(&self, key: &str, currency: &str) -> AppResult<Option<Money>> {
+        self.text(key)
+            .map(|v| {
+                Money::parse_major(v, currency)
+                    .map_err(|e| AppError::BadRequest(format!("{}: {}", key, e)))
+            })
+            .transpose()
+    }
+
     pub fn json_vec_string(&self, key: &str) -> Option<Vec<String>> {
         self.text(key).and_then(|v| {
             serde_json::from_str::<Vec<String>>(v).ok().or_else(|| {
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use super::{CreateUploadRequest, UploadTicket, VideoProvider, VideoStatus};
+use crate::pkg::error::{AppError, AppResult};
+
+/// In-memory provider for tests and local development without a Vimeo account.
+///
+/// Every status poll advances a video one step (uploading → processing →
+/// ready), so a client polling loop completes without manual intervention.
+/// Tests can pin a state with [`FakeVideoProvider::set_status`].
+#[derive(Default)]
+pub struct FakeVideoProvider {
+    videos: Mutex<HashMap<String, FakeVideo>>,
+}
+
+#[derive(Debug, Clone)]
+struct FakeVideo {
+    status: VideoStatus,
+    pinned: bool,
+    domains: Vec<String>,
+}
+
+impl FakeVideoProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Force a video into `status` and stop it from advancing on its own.
+    pub fn set_status(&self, video_id: &str, status: VideoStatus) {
+        if let Some(v) = self.videos.lock().unwrap().get_mut(video_id) {
+            v.status = status;
+            v.pinned = true;
+        }
+    }
+
+    /// Domains the video was restricted to, if it exists.
+    pub fn embed_domains(&self, video_id: &str) -> Option<Vec<String>> {
+        self.videos
+            .lock()
+            .unwrap()
+            .get(video_id)
+            .map(|v| v.domains.clone())
+    }
+}
+
+#[async_trait]
+impl VideoProvider for FakeVideoProvider {
+    fn name(&self) -> &'static str {
+        "fake"
+    }
+
+    async fn create_upload(&self, input: &CreateUploadRequest) -> AppResult<UploadTicket> {
+        if input.size_bytes == 0 {
+            return Err(AppError::BadRequest("Video size must be positive".into()));
+        }
+        let video_id = uuid::Uuid::new_v4().simple().to_string();
+        self.videos.lock().unwrap().insert(
+            video_id.clone(),
+            FakeVideo {
+                status: VideoStatus::Uploading,
+                pinned: false,
+                domains: Vec::new(),
+            },
+        );
+        Ok(UploadTicket {
+            upload_link: format!("http://localhost/fake-video-uploads/{}", video_id),
+            video_id,
+        })
+    }
+
+    async fn status(&self, video_id: &str) -> AppResult<VideoStatus> {
+        let mut videos = self.videos.lock().unwrap();
+        let video = videos
+            .get_mut(video_id)
+            .ok_or_else(|| AppError::NotFound("Video not found".into()))?;
+        let current = video.status;
+        if !video.pinned {
+            video.status = match current {
+                VideoStatus::Uploading => VideoStatus::Processing,
+                VideoStatus::Processing => VideoStatus::Ready,
+                other => other,
+            };
+        }
+        Ok(current)
+    }
+
+    async fn restrict_embedding(&self, video_id: &str, domains: &[String]) -> AppResult<()> {
+        let mut videos = self.videos.lock().unwrap();
+        let video = videos
+            .get_mut(video_id)
+            .ok_or_else(|| AppError::NotFound("Video not found".into()))?;
+        video.domains = domains.to_vec();
+        Ok(())
+    }
+
+    async fn embed_url(&self, video_id: &str) -> AppResult<String> {
+        if !self.videos.lock().unwrap().contains_key(video_id) {
+            return Err(AppError::NotFound("Video not found".into()));
+        }
+        Ok(format!("http://localhost/fake-video-player/{}", video_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn advances_to_ready_on_polling() {
+        let provider = FakeVideoProvider::new();
+        let ticket = provider
+            .create_upload(&CreateUploadRequest {
+                name: "Intro".into(),
+                size_bytes: 1024,
+            })
+            .await
+            .unwrap();
+
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            seen.push(provider.status(&ticket.video_id).await.unwrap());
+        }
+        assert_eq!(
+            seen,
+            vec![
+                VideoStatus::Uploading,
+                VideoStatus::Processing,
+                VideoStatus::Ready,
+                VideoStatus::Ready
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn pinned_status_and_domains_are_kept() {
+        let provider = FakeVideoProvider::new();
+        let ticket = provider
+            .create_upload(&CreateUploadRequest {
+                name: "Intro".into(),
+                size_bytes: 1024,
+            })
+            .await
+            .unwrap();
+        provider.set_status(&ticket.video_id, VideoStatus::Failed);
+        assert_eq!(provider.status(&ticket.video_id).await.unwrap(), VideoStatus::Failed);
+
+        provider
+            .restrict_embedding(&ticket.video_id, &["academy.test".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(
+            provider.embed_domains(&ticket.video_id),
+            Some(vec!["academy.test".to_string()])
+        );
+        assert!(provider.status("missing").await.is_err());
+    }
+}
@@ -0,0 +1,92 @@
+//! Video hosting providers
+//!
+//! Lesson videos are uploaded straight from the browser to the hosting
+//! provider: the API asks the provider for an upload ticket, the client
+//! uploads to the returned link, and the API then polls the provider until the
+//! video is transcoded. Once ready, embedding is restricted to our domains and
+//! the player URL is stored on the lesson.
+
+use async_trait::async_trait;
+
+use crate::pkg::error::AppResult;
+
+pub mod fake;
+pub mod vimeo;
+
+pub use fake::FakeVideoProvider;
+pub use vimeo::VimeoProvider;
+
+/// Processing state of a hosted video, as stored in `lesson_videos.status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoStatus {
+    /// Ticket issued; the client has not finished uploading yet
+    Uploading,
+    /// Upload received; the provider is transcoding
+    Processing,
+    /// Playable
+    Ready,
+    /// Upload or transcoding failed on the provider side
+    Failed,
+}
+
+impl VideoStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VideoStatus::Uploading => "uploading",
+            VideoStatus::Processing => "processing",
+            VideoStatus::Ready => "ready",
+            VideoStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "uploading" => Some(VideoStatus::Uploading),
+            "processing" => Some(VideoStatus::Processing),
+            "ready" => Some(VideoStatus::Ready),
+            "failed" => Some(VideoStatus::Failed),
+            _ => None,
+        }
+    }
+
+    /// Whether polling the provider again can still change the status.
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, VideoStatus::Ready | VideoStatus::Failed)
+    }
+}
+
+/// Input for reserving an upload slot with the provider.
+#[derive(Debug, Clone)]
+pub struct CreateUploadRequest {
+    /// Display name on the provider dashboard
+    pub name: String,
+    /// Exact size of the file the client is about to upload
+    pub size_bytes: u64,
+}
+
+/// Where and how the client should upload the video file.
+#[derive(Debug, Clone)]
+pub struct UploadTicket {
+    /// Provider-side video identifier
+    pub video_id: String,
+    /// Resumable (tus) upload endpoint the client sends bytes to
+    pub upload_link: String,
+}
+
+#[async_trait]
+pub trait VideoProvider: Send + Sync {
+    /// Stable provider name stored next to video ids, e.g. `"vimeo"`.
+    fn name(&self) -> &'static str;
+
+    /// Reserve a video and return a resumable upload link for the client.
+    async fn create_upload(&self, input: &CreateUploadRequest) -> AppResult<UploadTicket>;
+
+    /// Current processing status of a video.
+    async fn status(&self, video_id: &str) -> AppResult<VideoStatus>;
+
+    /// Restrict embedding of the video to `domains` and hide it elsewhere.
+    async fn restrict_embedding(&self, video_id: &str, domains: &[String]) -> AppResult<()>;
+
+    /// Player URL suitable for an `<iframe src>`.
+    async fn embed_url(&self, video_id: &str) -> AppResult<String>;
+}
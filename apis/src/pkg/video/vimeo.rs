@@ -0,0 +1,202 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use super::{CreateUploadRequest, UploadTicket, VideoProvider, VideoStatus};
+use crate::pkg::error::{AppError, AppResult};
+
+const VIMEO_ACCEPT: &str = "application/vnd.vimeo.*+json;version=3.4";
+
+/// Vimeo API client using a personal/app access token with `upload`,
+/// `edit` and `private` scopes.
+pub struct VimeoProvider {
+    client: reqwest::Client,
+    access_token: String,
+    api_base: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct VimeoVideo {
+    uri: Option<String>,
+    upload: Option<VimeoUpload>,
+    transcode: Option<VimeoTranscode>,
+    player_embed_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VimeoUpload {
+    status: Option<String>,
+    upload_link: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VimeoTranscode {
+    status: Option<String>,
+}
+
+impl VimeoProvider {
+    pub fn new(access_token: impl Into<String>, api_base: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            access_token: access_token.into(),
+            api_base: api_base.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        self.client
+            .request(method, format!("{}{}", self.api_base, path))
+            .bearer_auth(&self.access_token)
+            .header("Accept", VIMEO_ACCEPT)
+    }
+
+    async fn send(&self, req: reqwest::RequestBuilder, action: &str) -> AppResult<reqwest::Response> {
+        req.send()
+            .await
+            .map_err(|e| AppError::ServiceUnavailable(format!("Vimeo {} failed: {}", action, e)))?
+            .error_for_status()
+            .map_err(|e| AppError::ServiceUnavailable(format!("Vimeo {} failed: {}", action, e)))
+    }
+
+    async fn fetch_video(&self, video_id: &str, fields: &str) -> AppResult<VimeoVideo> {
+        let req = self
+            .request(reqwest::Method::GET, &format!("/videos/{}", video_id))
+            .query(&[("fields", fields)]);
+        self.send(req, "get video")
+            .await?
+            .json()
+            .await
+            .map_err(|e| AppError::ServiceUnavailable(e.to_string()))
+    }
+}
+
+/// Map Vimeo's upload + transcode states onto our lifecycle.
+fn map_status(video: &VimeoVideo) -> VideoStatus {
+    let upload = video.upload.as_ref().and_then(|u| u.status.as_deref());
+    let transcode = video.transcode.as_ref().and_then(|t| t.status.as_deref());
+    match (upload, transcode) {
+        (Some("error"), _) | (_, Some("error")) => VideoStatus::Failed,
+        (_, Some("complete")) => VideoStatus::Ready,
+        (Some("complete"), _) => VideoStatus::Processing,
+        _ => VideoStatus::Uploading,
+    }
+}
+
+/// `/videos/12345` → `12345`
+fn video_id_from_uri(uri: &str) -> Option<&str> {
+    uri.strip_prefix("/videos/")
+        .map(|rest| rest.split(['/', ':']).next().unwrap_or(rest))
+        .filter(|id| !id.is_empty())
+}
+
+#[async_trait]
+impl VideoProvider for VimeoProvider {
+    fn name(&self) -> &'static str {
+        "vimeo"
+    }
+
+    async fn create_upload(&self, input: &CreateUploadRequest) -> AppResult<UploadTicket> {
+        let body = serde_json::json!({
+            "name": input.name,
+            "upload": { "approach": "tus", "size": input.size_bytes.to_string() },
+            // Hidden on vimeo.com and not embeddable until domains are whitelisted
+            "privacy": { "view": "disable", "embed": "whitelist" },
+        });
+        let req = self
+            .request(reqwest::Method::POST, "/me/videos")
+            .query(&[("fields", "uri,upload.upload_link")])
+            .json(&body);
+        let video: VimeoVideo = self
+            .send(req, "create upload")
+            .await?
+            .json()
+            .await
+            .map_err(|e| AppError::ServiceUnavailable(e.to_string()))?;
+
+        let video_id = video
+            .uri
+            .as_deref()
+            .and_then(video_id_from_uri)
+            .ok_or_else(|| AppError::ServiceUnavailable("Vimeo returned no video uri".into()))?
+            .to_string();
+        let upload_link = video
+            .upload
+            .and_then(|u| u.upload_link)
+            .ok_or_else(|| AppError::ServiceUnavailable("Vimeo returned no upload link".into()))?;
+        Ok(UploadTicket {
+            video_id,
+            upload_link,
+        })
+    }
+
+    async fn status(&self, video_id: &str) -> AppResult<VideoStatus> {
+        let video = self
+            .fetch_video(video_id, "upload.status,transcode.status")
+            .await?;
+        Ok(map_status(&video))
+    }
+
+    async fn restrict_embedding(&self, video_id: &str, domains: &[String]) -> AppResult<()> {
+        let req = self
+            .request(reqwest::Method::PATCH, &format!("/videos/{}", video_id))
+            .json(&serde_json::json!({ "privacy": { "view": "disable", "embed": "whitelist" } }));
+        self.send(req, "update privacy").await?;
+
+        for domain in domains {
+            let req = self.request(
+                reqwest::Method::PUT,
+                &format!(
+                    "/videos/{}/privacy/domains/{}",
+                    video_id,
+                    urlencoding::encode(domain)
+                ),
+            );
+            self.send(req, "whitelist domain").await?;
+        }
+        Ok(())
+    }
+
+    async fn embed_url(&self, video_id: &str) -> AppResult<String> {
+        let video = self.fetch_video(video_id, "player_embed_url").await?;
+        video
+            .player_embed_url
+            .ok_or_else(|| AppError::ServiceUnavailable("Vimeo returned no embed url".into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn video(upload: Option<&str>, transcode: Option<&str>) -> VimeoVideo {
+        VimeoVideo {
+            uri: None,
+            upload: Some(VimeoUpload {
+                status: upload.map(str::to_string),
+                upload_link: None,
+            }),
+            transcode: Some(VimeoTranscode {
+                status: transcode.map(str::to_string),
+            }),
+            player_embed_url: None,
+        }
+    }
+
+    #[test]
+    fn maps_vimeo_states() {
+        assert_eq!(map_status(&video(Some("in_progress"), None)), VideoStatus::Uploading);
+        assert_eq!(
+            map_status(&video(Some("complete"), Some("in_progress"))),
+            VideoStatus::Processing
+        );
+        assert_eq!(map_status(&video(Some("complete"), Some("complete"))), VideoStatus::Ready);
+        assert_eq!(map_status(&video(Some("error"), None)), VideoStatus::Failed);
+        assert_eq!(map_status(&video(Some("complete"), Some("error"))), VideoStatus::Failed);
+    }
+
+    #[test]
+    fn extracts_video_id_from_uri() {
+        assert_eq!(video_id_from_uri("/videos/12345"), Some("12345"));
+        assert_eq!(video_id_from_uri("/videos/12345:abcdef"), Some("12345"));
+        assert_eq!(video_id_from_uri("/users/1"), None);
+    }
+}
@@ -0,0 +1,129 @@
+use chrono::NaiveDate;
+
+use crate::pkg::error::AppResult;
+
+/// A course's rolled-up activity on one UTC day
+#[derive(Debug, Clone)]
+pub struct CourseDailyStatsRecord {
+    pub course_id: uuid::Uuid,
+    pub day: NaiveDate,
+    pub new_enrollments: i64,
+    pub revoked_enrollments: i64,
+    /// Active enrollments at the end of the day
+    pub active_enrollments: i64,
+    /// Certificates issued
+    pub completions: i64,
+    pub lessons_completed: i64,
+    pub quiz_attempts: i64,
+    /// Snapshot of the course's view counter; only known for days rolled up
+    /// while they were current
+    pub view_count: Option<i64>,
+}
+
+/// A course's revenue on one UTC day in one currency, in minor units
+#[derive(Debug, Clone)]
+pub struct CourseDailyRevenueRecord {
+    pub course_id: uuid::Uuid,
+    pub day: NaiveDate,
+    pub currency: String,
+    pub orders: i64,
+    pub gross_cents: i64,
+    pub refunds: i64,
+    pub refunded_cents: i64,
+}
+
+/// How far learners got through a module, as of the latest rollup
+#[derive(Debug, Clone)]
+pub struct ModuleFunnelRecord {
+    pub module_id: uuid::Uuid,
+    pub title: String,
+    pub position: i32,
+    pub learners_started: i64,
+    pub learners_completed: i64,
+}
+
+/// A published lesson's engagement over a date range
+#[derive(Debug, Clone)]
+pub struct LessonEngagementRecord {
+    pub lesson_id: uuid::Uuid,
+    pub module_id: uuid::Uuid,
+    pub title: String,
+    /// Learners who completed the lesson by the end of the range
+    pub learners_completed: i64,
+    pub completions: i64,
+    pub quiz_attempts: i64,
+    pub quiz_passes: i64,
+    pub quiz_score_sum: f64,
+}
+
+/// One of an instructor's courses summed over a date range
+#[derive(Debug, Clone)]
+pub struct CourseSummaryRecord {
+    pub course_id: uuid::Uuid,
+    pub title: String,
+    pub new_enrollments: i64,
+    pub completions: i64,
+    /// Active enrollments at the end of the range
+    pub active_enrollments: i64,
+    /// Growth of the view counter over the range, when snapshots exist
+    pub views: Option<i64>,
+}
+
+#[async_trait::async_trait]
+pub trait AnalyticsRepository: Send + Sync {
+    /// Most recent day that has been rolled up
+    async fn latest_rollup_day(&self) -> AppResult<Option<NaiveDate>>;
+
+    /// Recompute every rollup row of `day` from the activity tables in one
+    /// transaction; `snapshot_views` records the courses' current view
+    /// counters for it
+    async fn roll_up_day(&self, day: NaiveDate, snapshot_views: bool) -> AppResult<()>;
+
+    /// A course's daily rows between `from` and `to` inclusive, oldest first
+    async fn list_course_days(
+        &self,
+        course_id: uuid::Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<CourseDailyStatsRecord>>;
+
+    /// A course's daily revenue rows between `from` and `to` inclusive
+    async fn list_course_revenue(
+        &self,
+        course_id: uuid::Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<CourseDailyRevenueRecord>>;
+
+    /// Every module of a course in order with its latest snapshot up to `as_of`
+    async fn list_module_funnel(
+        &self,
+        course_id: uuid::Uuid,
+        as_of: NaiveDate,
+    ) -> AppResult<Vec<ModuleFunnelRecord>>;
+
+    /// The course's published lessons in curriculum order with their
+    /// engagement between `from` and `to` inclusive
+    async fn list_lesson_engagement(
+        &self,
+        course_id: uuid::Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<LessonEngagementRecord>>;
+
+    /// Every course of an instructor summed between `from` and `to` inclusive
+    async fn summarize_instructor_courses(
+        &self,
+        instructor_id: uuid::Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<CourseSummaryRecord>>;
+
+    /// Daily revenue rows of every course of an instructor
+    async fn list_instructor_revenue(
+        &self,
+        instructor_id: uuid::Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<CourseDailyRevenueRecord>>;
+}
@@ -0,0 +1,43 @@
+use crate::pkg::error::AppResult;
+
+/// Issued completion certificate
+#[derive(Debug, Clone)]
+pub struct CertificateRecord {
+    pub id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    /// `None` once the course has been deleted
+    pub course_id: Option<uuid::Uuid>,
+    pub verification_code: String,
+    pub learner_name: String,
+    pub course_title: String,
+    pub instructor_name: String,
+    pub issued_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Input for issuing a certificate
+#[derive(Debug, Clone)]
+pub struct CreateCertificateRecord {
+    pub user_id: uuid::Uuid,
+    pub course_id: uuid::Uuid,
+    pub verification_code: String,
+    pub learner_name: String,
+    pub course_title: String,
+    pub instructor_name: String,
+}
+
+#[async_trait::async_trait]
+pub trait CertificatesRepository: Send + Sync {
+    /// Issue a certificate, or return the one the user already holds for the course
+    async fn issue(&self, input: CreateCertificateRecord) -> AppResult<CertificateRecord>;
+
+    async fn find_by_code(&self, code: &str) -> AppResult<Option<CertificateRecord>>;
+
+    async fn find_by_user_and_course(
+        &self,
+        user_id: uuid::Uuid,
+        course_id: uuid::Uuid,
+    ) -> AppResult<Option<CertificateRecord>>;
+
+    /// Certificates held by a user, newest first
+    async fn list_by_user(&self, user_id: uuid::Uuid) -> AppResult<Vec<CertificateRecord>>;
+}
@@ -0,0 +1,111 @@
+use crate::pkg::error::AppResult;
+
+/// How a coupon discounts the courses it applies to (`coupons.discount_type`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiscountType {
+    /// `percent_off` percent of each eligible course's price
+    Percent,
+    /// `amount_off_cents` off the eligible courses, in the coupon's currency
+    Fixed,
+}
+
+impl DiscountType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Percent => "percent",
+            Self::Fixed => "fixed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "percent" => Some(Self::Percent),
+            "fixed" => Some(Self::Fixed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CouponRecord {
+    pub id: uuid::Uuid,
+    /// Upper-case redemption code
+    pub code: String,
+    pub description: Option<String>,
+    pub discount_type: String,
+    pub percent_off: Option<i32>,
+    pub amount_off_cents: Option<i64>,
+    pub currency: Option<String>,
+    /// Only this course is discounted
+    pub course_id: Option<uuid::Uuid>,
+    /// Only courses in this category are discounted
+    pub category_id: Option<i32>,
+    pub starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub max_redemptions: Option<i32>,
+    pub max_redemptions_per_user: Option<i32>,
+    pub active: bool,
+    /// Redemptions by paid, refunded and pending orders
+    pub times_redeemed: i64,
+    pub created_by: Option<uuid::Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateCouponRecord {
+    pub code: String,
+    pub description: Option<String>,
+    pub discount_type: DiscountType,
+    pub percent_off: Option<i32>,
+    pub amount_off_cents: Option<i64>,
+    pub currency: Option<String>,
+    pub course_id: Option<uuid::Uuid>,
+    pub category_id: Option<i32>,
+    pub starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub max_redemptions: Option<i32>,
+    pub max_redemptions_per_user: Option<i32>,
+    pub active: bool,
+    pub created_by: uuid::Uuid,
+}
+
+/// Changes to a coupon; `None` fields are left as they are
+#[derive(Debug, Clone, Default)]
+pub struct UpdateCouponRecord {
+    pub description: Option<String>,
+    pub starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub max_redemptions: Option<i32>,
+    pub max_redemptions_per_user: Option<i32>,
+    pub active: Option<bool>,
+}
+
+/// How often a coupon has been redeemed, overall and by one user
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CouponUsage {
+    pub total: i64,
+    pub by_user: i64,
+}
+
+#[async_trait::async_trait]
+pub trait CouponsRepository: Send + Sync {
+    async fn create(&self, input: CreateCouponRecord) -> AppResult<CouponRecord>;
+
+    async fn find_by_id(&self, id: uuid::Uuid) -> AppResult<Option<CouponRecord>>;
+
+    /// Look a coupon up by its upper-case code
+    async fn find_by_code(&self, code: &str) -> AppResult<Option<CouponRecord>>;
+
+    /// Coupons, newest first
+    async fn list(&self, limit: i64) -> AppResult<Vec<CouponRecord>>;
+
+    async fn update_partial(
+        &self,
+        id: uuid::Uuid,
+        input: UpdateCouponRecord,
+    ) -> AppResult<Option<CouponRecord>>;
+
+    /// Redemptions that count towards the coupon's caps
+    async fn usage(&self, coupon_id: uuid::Uuid, user_id: uuid::Uuid) -> AppResult<CouponUsage>;
+}
@@ -0,0 +1,118 @@
+use crate::pkg::error::AppResult;
+
+/// Course review joined with its author and helpful vote count
+#[derive(Debug, Clone)]
+pub struct CourseReviewRecord {
+    pub id: uuid::Uuid,
+    pub course_id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    pub rating: i16,
+    pub content: String,
+    pub approved: bool,
+    pub moderated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub instructor_reply: Option<String>,
+    pub replied_by: Option<uuid::Uuid>,
+    pub replied_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub helpful_count: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub username: String,
+    pub full_name: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
+/// Input for creating a course review
+#[derive(Debug, Clone)]
+pub struct CreateCourseReviewRecord {
+    pub course_id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    pub rating: i16,
+    pub content: String,
+}
+
+/// Author edits to a course review
+#[derive(Debug, Default, Clone)]
+pub struct UpdateCourseReviewRecord {
+    pub rating: Option<i16>,
+    pub content: Option<String>,
+}
+
+/// Order of public review listings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CourseReviewSort {
+    #[default]
+    Newest,
+    Helpful,
+    Highest,
+    Lowest,
+}
+
+impl CourseReviewSort {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "newest" => Some(Self::Newest),
+            "helpful" => Some(Self::Helpful),
+            "highest" => Some(Self::Highest),
+            "lowest" => Some(Self::Lowest),
+            _ => None,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+pub trait CourseReviewsRepository: Send + Sync {
+    /// Create a review awaiting moderation; Conflict if the user already
+    /// reviewed the course
+    async fn create(&self, input: CreateCourseReviewRecord) -> AppResult<uuid::Uuid>;
+
+    async fn find_by_id(&self, id: uuid::Uuid) -> AppResult<Option<CourseReviewRecord>>;
+
+    async fn find_by_user_and_course(
+        &self,
+        user_id: uuid::Uuid,
+        course_id: uuid::Uuid,
+    ) -> AppResult<Option<CourseReviewRecord>>;
+
+    /// Approved reviews of a course and their total count
+    async fn list_approved_by_course(
+        &self,
+        course_id: uuid::Uuid,
+        sort: CourseReviewSort,
+        offset: i64,
+        limit: i64,
+    ) -> AppResult<(Vec<CourseReviewRecord>, i64)>;
+
+    /// Number of approved reviews per star rating, highest rating first
+    async fn rating_distribution(&self, course_id: uuid::Uuid) -> AppResult<Vec<(i16, i64)>>;
+
+    /// Apply an author edit and send the review back to moderation
+    async fn update_by_author(
+        &self,
+        id: uuid::Uuid,
+        input: UpdateCourseReviewRecord,
+    ) -> AppResult<bool>;
+
+    async fn delete_by_id(&self, id: uuid::Uuid) -> AppResult<()>;
+
+    /// Set (`Some`) or clear (`None`) the instructor reply
+    async fn set_reply(
+        &self,
+        id: uuid::Uuid,
+        reply: Option<String>,
+        replied_by: Option<uuid::Uuid>,
+    ) -> AppResult<()>;
+
+    /// Mark a review helpful; voting twice is a no-op
+    async fn add_vote(&self, review_id: uuid::Uuid, user_id: uuid::Uuid) -> AppResult<()>;
+
+    async fn remove_vote(&self, review_id: uuid::Uuid, user_id: uuid::Uuid) -> AppResult<()>;
+
+    /// Approve a course review
+    async fn approve(&self, id: uuid::Uuid) -> AppResult<()>;
+
+    /// Reject a course review
+    async fn reject(&self, id: uuid::Uuid) -> AppResult<()>;
+
+    /// List reviews awaiting moderation, oldest first
+    async fn list_pending(&self, limit: i64) -> AppResult<Vec<CourseReviewRecord>>;
+}
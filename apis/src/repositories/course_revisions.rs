@@ -0,0 +1,117 @@
+use crate::pkg::error::AppResult;
+use crate::pkg::money::Money;
+use crate::repositories::modules::{CreateLessonContentData, CreateLessonDeepData};
+
+/// Stored revision; `content` is a serialized `CourseRevisionContent`
+#[derive(Debug, Clone)]
+pub struct CourseRevisionRecord {
+    pub id: uuid::Uuid,
+    pub course_id: uuid::Uuid,
+    pub status: String,
+    pub base_version: i32,
+    pub content: serde_json::Value,
+    pub created_by: uuid::Uuid,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Course columns overwritten when a revision is published
+#[derive(Debug, Clone)]
+pub struct PublishRevisionCourseData {
+    pub title: String,
+    pub description: String,
+    pub excerpt: Option<String>,
+    pub thumbnail: Option<String>,
+    /// Its currency becomes the course's base currency
+    pub price: Money,
+    pub original_price: Option<Money>,
+    pub duration: String,
+    pub outcomes: Vec<String>,
+    pub category: Option<String>,
+    pub tags: Vec<String>,
+}
+
+/// Content to keep (`id` of a live content of the lesson) or add (`id: None`)
+#[derive(Debug, Clone)]
+pub struct RevisionContentData {
+    pub id: Option<uuid::Uuid>,
+    pub content: CreateLessonContentData,
+}
+
+/// Lesson to keep (`id` of a live lesson) or add (`id: None`)
+#[derive(Debug, Clone)]
+pub struct RevisionLessonData {
+    pub id: Option<uuid::Uuid>,
+    /// `lesson.contents` is unused; contents are matched by id from `contents`
+    pub lesson: CreateLessonDeepData,
+    pub contents: Vec<RevisionContentData>,
+}
+
+/// Module to keep (`id` of a live module) or add (`id: None`)
+#[derive(Debug, Clone)]
+pub struct RevisionModuleData {
+    pub id: Option<uuid::Uuid>,
+    pub title: String,
+    pub description: Option<String>,
+    pub position: i32,
+    pub lessons: Vec<RevisionLessonData>,
+}
+
+/// Complete course content that replaces the live version
+#[derive(Debug, Clone)]
+pub struct PublishRevisionData {
+    pub course: PublishRevisionCourseData,
+    pub modules: Vec<RevisionModuleData>,
+}
+
+#[async_trait::async_trait]
+pub trait CourseRevisionsRepository: Send + Sync {
+    /// Current `courses.content_version`, `None` if the course does not exist.
+    ///
+    /// The database bumps it on every write to the course's revisioned fields
+    /// or curriculum, so drafts copied before any edit are detected as stale.
+    async fn content_version(&self, course_id: uuid::Uuid) -> AppResult<Option<i32>>;
+
+    async fn find_by_id(&self, id: uuid::Uuid) -> AppResult<Option<CourseRevisionRecord>>;
+
+    /// The course's open draft revision, if any
+    async fn find_open_draft(
+        &self,
+        course_id: uuid::Uuid,
+    ) -> AppResult<Option<CourseRevisionRecord>>;
+
+    /// Store a new draft; fails with Conflict if the course already has one
+    async fn create_draft(
+        &self,
+        course_id: uuid::Uuid,
+        created_by: uuid::Uuid,
+        base_version: i32,
+        content: serde_json::Value,
+    ) -> AppResult<CourseRevisionRecord>;
+
+    /// Replace a draft's content; `None` if the revision is not an open draft
+    async fn update_draft_content(
+        &self,
+        id: uuid::Uuid,
+        content: serde_json::Value,
+    ) -> AppResult<Option<CourseRevisionRecord>>;
+
+    /// Mark an open draft as discarded; `None` if it is not an open draft
+    async fn discard(&self, id: uuid::Uuid) -> AppResult<Option<CourseRevisionRecord>>;
+
+    /// Swap the live course content for `data` in one transaction and mark the
+    /// revision published.
+    ///
+    /// Live modules, lessons and contents referenced by id are updated in
+    /// place (keeping their videos, subtitles and private files), unreferenced
+    /// ones are deleted and the rest are inserted. Fails with Conflict when the
+    /// revision is no longer a draft or the live content moved past its base
+    /// version, and with BadRequest when a private content is not the live
+    /// private file of its lesson unchanged.
+    async fn publish(
+        &self,
+        id: uuid::Uuid,
+        data: PublishRevisionData,
+    ) -> AppResult<CourseRevisionRecord>;
+}
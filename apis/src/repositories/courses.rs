@@ -1,4 +1,6 @@
 use crate::pkg::error::AppResult;
+use crate::pkg::money::Money;
+use crate::repositories::modules::CreateLessonDeepData;
 
 /// Summary of an instructor joined from users table for embedding in course records
 #[derive(Debug, Clone)]
@@ -20,12 +22,18 @@ pub struct CourseRecord {
     pub description: String,
     pub excerpt: Option<String>,
     pub thumbnail: Option<String>,
-    pub price: f64,
-    pub original_price: Option<f64>,
+    /// Price in the course's base currency
+    pub price: Money,
+    /// Price before discount, in the base currency
+    pub original_price: Option<Money>,
     pub duration: String,
     pub lessons: i32,
     pub featured: bool,
     pub view_count: i32,
+    /// Average star rating of approved reviews (0 when there are none)
+    pub rating_average: f64,
+    /// Number of approved reviews
+    pub rating_count: i32,
     pub status: String,
     pub outcomes: Option<Vec<String>>,
     pub category: Option<String>,
@@ -34,6 +42,8 @@ pub struct CourseRecord {
     pub instructor_id: uuid::Uuid,
     pub instructor: Option<InstructorSummary>,
     pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// When a draft is due to be published automatically
+    pub scheduled_publish_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
@@ -45,8 +55,10 @@ pub struct CreateCourseRecord {
     pub description: String,
     pub excerpt: Option<String>,
     pub thumbnail: Option<String>,
-    pub price: f64,
-    pub original_price: Option<f64>,
+    /// Price; its currency becomes the course's base currency
+    pub price: Money,
+    /// Must be in the same currency as `price`
+    pub original_price: Option<Money>,
     pub duration: String,
     pub featured: bool,
     pub status: Option<String>,
@@ -62,8 +74,10 @@ pub struct UpdateCourseRecord {
     pub description: Option<String>,
     pub excerpt: Option<String>,
     pub thumbnail: Option<String>,
-    pub price: Option<f64>,
-    pub original_price: Option<f64>,
+    /// New price; a different currency changes the course's base currency
+    pub price: Option<Money>,
+    /// In the base currency the course has after the update
+    pub original_price: Option<Money>,
     pub duration: Option<String>,
     pub lessons: Option<i32>,
     pub status: Option<String>,
@@ -73,6 +87,158 @@ pub struct UpdateCourseRecord {
     pub tags: Option<Vec<String>>,
 }
 
+/// Filters for the public course catalogue; `None`/empty fields are ignored
+#[derive(Debug, Default, Clone)]
+pub struct CourseCatalogFilters {
+    /// Only courses linked to this category through `course_categories`
+    pub category_id: Option<i32>,
+    /// Courses carrying at least one of these tags
+    pub tags: Vec<String>,
+    /// Currency the price filters and price sort apply to; courses are
+    /// compared by their price in it, from the price list when it is not
+    /// their base currency
+    pub currency: String,
+    /// Minor units of `currency`
+    pub min_price: Option<i64>,
+    /// Minor units of `currency`
+    pub max_price: Option<i64>,
+    /// Only courses with a price of zero
+    pub free_only: bool,
+    pub featured: Option<bool>,
+    pub instructor_id: Option<uuid::Uuid>,
+    /// Only courses whose average rating is at least this
+    pub min_rating: Option<f64>,
+}
+
+/// Price of a course in a currency other than its base currency
+#[derive(Debug, Clone)]
+pub struct CoursePriceRecord {
+    pub course_id: uuid::Uuid,
+    pub price: Money,
+    /// In the same currency as `price`
+    pub original_price: Option<Money>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Course that must be completed before another course's lessons open
+#[derive(Debug, Clone)]
+pub struct CoursePrerequisiteRecord {
+    pub course_id: uuid::Uuid,
+    pub slug: String,
+    pub title: String,
+}
+
+/// Sort orders offered by the course catalogue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CourseCatalogSort {
+    #[default]
+    Newest,
+    Popular,
+    PriceAsc,
+    PriceDesc,
+    TopRated,
+}
+
+impl CourseCatalogSort {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "newest" => Some(Self::Newest),
+            "popular" => Some(Self::Popular),
+            "price_asc" => Some(Self::PriceAsc),
+            "price_desc" => Some(Self::PriceDesc),
+            "top_rated" => Some(Self::TopRated),
+            _ => None,
+        }
+    }
+}
+
+/// Lifecycle states of a course (`courses.status`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CourseStatus {
+    Draft,
+    Published,
+    Archived,
+}
+
+impl CourseStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Draft => "draft",
+            Self::Published => "published",
+            Self::Archived => "archived",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "draft" => Some(Self::Draft),
+            "published" => Some(Self::Published),
+            "archived" => Some(Self::Archived),
+            _ => None,
+        }
+    }
+}
+
+/// Content counts checked before a course may be published
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CoursePublishReadiness {
+    pub module_count: i64,
+    pub published_lesson_count: i64,
+}
+
+/// Target of a deep course copy
+#[derive(Debug, Clone)]
+pub struct DuplicateCourseRecord {
+    pub slug: String,
+    pub title: String,
+    /// Owner of the copy
+    pub instructor_id: uuid::Uuid,
+    /// Media references to replace in the copy (source URL or key -> copied one);
+    /// anything not listed keeps pointing at the source's media
+    pub media_rewrites: Vec<(String, String)>,
+}
+
+/// Subtitle track created with an imported lesson
+#[derive(Debug, Clone)]
+pub struct ImportSubtitleData {
+    pub language: String,
+    pub label: Option<String>,
+    pub vtt: String,
+    pub transcript: String,
+    pub cue_count: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportLessonData {
+    pub lesson: CreateLessonDeepData,
+    pub subtitles: Vec<ImportSubtitleData>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportModuleData {
+    pub title: String,
+    pub description: Option<String>,
+    pub position: i32,
+    pub lessons: Vec<ImportLessonData>,
+}
+
+/// Price-list entry created with an imported course
+#[derive(Debug, Clone)]
+pub struct ImportPriceData {
+    pub price: Money,
+    pub original_price: Option<Money>,
+}
+
+/// A complete course to create from an archive
+#[derive(Debug, Clone)]
+pub struct ImportCourseRecord {
+    pub course: CreateCourseRecord,
+    /// Prices in currencies other than the course's own
+    pub prices: Vec<ImportPriceData>,
+    pub modules: Vec<ImportModuleData>,
+}
+
 #[async_trait::async_trait]
 pub trait CoursesRepository: Send + Sync {
     // create course
@@ -86,6 +252,15 @@ pub trait CoursesRepository: Send + Sync {
         limit: i64,
     ) -> AppResult<(Vec<CourseRecord>, i64)>;
 
+    /// List published courses matching `filters` in `sort` order; returns (items, total)
+    async fn list_published_paginated(
+        &self,
+        filters: CourseCatalogFilters,
+        sort: CourseCatalogSort,
+        offset: i64,
+        limit: i64,
+    ) -> AppResult<(Vec<CourseRecord>, i64)>;
+
     /// Return a page of courses and the total count for pagination
     async fn find_by_id(&self, id: uuid::Uuid) -> AppResult<Option<CourseRecord>>;
 
@@ -101,4 +276,95 @@ pub trait CoursesRepository: Send + Sync {
 
     // delete course by id
     async fn delete_by_id(&self, id: uuid::Uuid) -> AppResult<()>;
+
+    /// Module and published lesson counts for the publish checks
+    async fn publish_readiness(&self, id: uuid::Uuid) -> AppResult<CoursePublishReadiness>;
+
+    /// Move the course to `to` only if its current status is one of `from`.
+    ///
+    /// Any pending schedule is cleared and `published_at` is stamped the first
+    /// time the course is published. Returns `None` when the course does not
+    /// exist or its status no longer matches `from`.
+    async fn transition_status(
+        &self,
+        id: uuid::Uuid,
+        from: &[CourseStatus],
+        to: CourseStatus,
+    ) -> AppResult<Option<CourseRecord>>;
+
+    /// Set or clear the scheduled publish time of a draft; `None` if it is not a draft
+    async fn set_scheduled_publish(
+        &self,
+        id: uuid::Uuid,
+        at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> AppResult<Option<CourseRecord>>;
+
+    /// Drafts whose scheduled publish time is at or before `now`, oldest first
+    async fn list_due_scheduled(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<uuid::Uuid>>;
+
+    /// Copy a course with its modules, lessons, lesson contents, questions,
+    /// options, assignments, subtitles, categories, price list and
+    /// prerequisites in one
+    /// transaction.
+    ///
+    /// The copy starts as an unfeatured draft. Hosted videos are not copied
+    /// since a provider video belongs to exactly one lesson. Returns the new
+    /// course id, or `None` if the source does not exist.
+    async fn duplicate_deep(
+        &self,
+        source_id: uuid::Uuid,
+        input: DuplicateCourseRecord,
+    ) -> AppResult<Option<uuid::Uuid>>;
+
+    /// Create a course with its whole module/lesson tree in one transaction
+    /// and return its id
+    async fn import_deep(&self, input: ImportCourseRecord) -> AppResult<uuid::Uuid>;
+
+    /// The course's prices in currencies other than its base currency, by currency
+    async fn list_prices(&self, course_id: uuid::Uuid) -> AppResult<Vec<CoursePriceRecord>>;
+
+    /// The course's price-list entry for `currency`, if any
+    async fn find_price(
+        &self,
+        course_id: uuid::Uuid,
+        currency: &str,
+    ) -> AppResult<Option<CoursePriceRecord>>;
+
+    /// Create or replace the price-list entry for `price.currency`
+    async fn upsert_price(
+        &self,
+        course_id: uuid::Uuid,
+        price: Money,
+        original_price: Option<Money>,
+    ) -> AppResult<CoursePriceRecord>;
+
+    /// Remove the price-list entry for `currency`; `false` if there was none
+    async fn delete_price(&self, course_id: uuid::Uuid, currency: &str) -> AppResult<bool>;
+
+    /// Courses to complete before this one, by title
+    async fn list_prerequisites(
+        &self,
+        course_id: uuid::Uuid,
+    ) -> AppResult<Vec<CoursePrerequisiteRecord>>;
+
+    /// Replace the course's prerequisites
+    async fn set_prerequisites(
+        &self,
+        course_id: uuid::Uuid,
+        required_course_ids: &[uuid::Uuid],
+    ) -> AppResult<()>;
+
+    /// Courses that require this one, directly or through other courses
+    async fn list_dependent_ids(&self, course_id: uuid::Uuid) -> AppResult<Vec<uuid::Uuid>>;
+
+    /// Prerequisites of the course the user holds no certificate for
+    async fn unmet_prerequisites(
+        &self,
+        course_id: uuid::Uuid,
+        user_id: uuid::Uuid,
+    ) -> AppResult<Vec<CoursePrerequisiteRecord>>;
 }
@@ -0,0 +1,77 @@
+use crate::pkg::error::AppResult;
+
+/// Persisted enrollment of a user in a course
+#[derive(Debug, Clone)]
+pub struct EnrollmentRecord {
+    pub id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    pub course_id: uuid::Uuid,
+    /// One of `active` or `revoked`
+    pub status: String,
+    pub enrolled_at: chrono::DateTime<chrono::Utc>,
+    /// When the enrollment was revoked, while it is
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Payload to enroll a user in a course
+#[derive(Debug, Clone)]
+pub struct CreateEnrollmentRecord {
+    pub user_id: uuid::Uuid,
+    pub course_id: uuid::Uuid,
+}
+
+/// Enrollment of a user in a learning path as a whole
+#[derive(Debug, Clone)]
+pub struct PathEnrollmentRecord {
+    pub id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    pub path_id: uuid::Uuid,
+    /// Order the path was bought with
+    pub order_id: Option<uuid::Uuid>,
+    /// One of `active` or `revoked`
+    pub status: String,
+    pub enrolled_at: chrono::DateTime<chrono::Utc>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[async_trait::async_trait]
+pub trait EnrollmentsRepository: Send + Sync {
+    /// Enroll a user, re-activating a previously revoked enrollment
+    async fn create(&self, input: CreateEnrollmentRecord) -> AppResult<EnrollmentRecord>;
+
+    /// Find the enrollment of a user in a course, whatever its status
+    async fn find_by_user_and_course(
+        &self,
+        user_id: uuid::Uuid,
+        course_id: uuid::Uuid,
+    ) -> AppResult<Option<EnrollmentRecord>>;
+
+    /// Whether the user currently holds an active enrollment in the course
+    async fn is_enrolled(&self, user_id: uuid::Uuid, course_id: uuid::Uuid) -> AppResult<bool>;
+
+    /// Revoke an active enrollment, keeping the learner's progress; returns
+    /// whether one was revoked
+    async fn revoke(&self, user_id: uuid::Uuid, course_id: uuid::Uuid) -> AppResult<bool>;
+
+    /// Enroll a user in a learning path, re-activating a revoked enrollment.
+    ///
+    /// Enrolling in the path's courses is up to the caller.
+    async fn enroll_in_path(
+        &self,
+        user_id: uuid::Uuid,
+        path_id: uuid::Uuid,
+        order_id: Option<uuid::Uuid>,
+    ) -> AppResult<PathEnrollmentRecord>;
+
+    /// The user's enrollment in a learning path, whatever its status
+    async fn find_path_enrollment(
+        &self,
+        user_id: uuid::Uuid,
+        path_id: uuid::Uuid,
+    ) -> AppResult<Option<PathEnrollmentRecord>>;
+
+    /// Revoke an active path enrollment; returns whether one was revoked
+    async fn revoke_path(&self, user_id: uuid::Uuid, path_id: uuid::Uuid) -> AppResult<bool>;
+}
@@ -0,0 +1,113 @@
+use crate::pkg::error::AppResult;
+use crate::pkg::money::Money;
+
+/// Lifecycle states of a learning path (`learning_paths.status`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LearningPathStatus {
+    Draft,
+    Published,
+    Archived,
+}
+
+impl LearningPathStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Draft => "draft",
+            Self::Published => "published",
+            Self::Archived => "archived",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "draft" => Some(Self::Draft),
+            "published" => Some(Self::Published),
+            "archived" => Some(Self::Archived),
+            _ => None,
+        }
+    }
+}
+
+/// Ordered track of courses sold together
+#[derive(Debug, Clone)]
+pub struct LearningPathRecord {
+    pub id: uuid::Uuid,
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    pub excerpt: Option<String>,
+    pub thumbnail: Option<String>,
+    pub status: String,
+    /// Price of the whole path
+    pub price: Money,
+    /// Number of courses in the path
+    pub course_count: i64,
+    pub created_by: Option<uuid::Uuid>,
+    pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateLearningPathRecord {
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    pub excerpt: Option<String>,
+    pub thumbnail: Option<String>,
+    pub price: Money,
+    pub created_by: uuid::Uuid,
+}
+
+/// Changes to a path; `None` fields are left as they are
+#[derive(Debug, Clone, Default)]
+pub struct UpdateLearningPathRecord {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub excerpt: Option<String>,
+    pub thumbnail: Option<String>,
+    pub price: Option<Money>,
+    /// `published_at` is stamped the first time the path is published
+    pub status: Option<LearningPathStatus>,
+}
+
+#[async_trait::async_trait]
+pub trait LearningPathsRepository: Send + Sync {
+    async fn create(&self, input: CreateLearningPathRecord) -> AppResult<LearningPathRecord>;
+
+    async fn find_by_id(&self, id: uuid::Uuid) -> AppResult<Option<LearningPathRecord>>;
+
+    async fn find_by_slug(&self, slug: &str) -> AppResult<Option<LearningPathRecord>>;
+
+    /// Paths in `status`, or all of them, newest first; returns (items, total)
+    async fn list_paginated(
+        &self,
+        status: Option<LearningPathStatus>,
+        offset: i64,
+        limit: i64,
+    ) -> AppResult<(Vec<LearningPathRecord>, i64)>;
+
+    async fn update_partial(
+        &self,
+        id: uuid::Uuid,
+        input: UpdateLearningPathRecord,
+    ) -> AppResult<Option<LearningPathRecord>>;
+
+    async fn delete_by_id(&self, id: uuid::Uuid) -> AppResult<bool>;
+
+    /// Ids of the path's courses in path order
+    async fn list_course_ids(&self, path_id: uuid::Uuid) -> AppResult<Vec<uuid::Uuid>>;
+
+    /// Replace the path's courses; their order in `course_ids` is the path order
+    async fn set_courses(&self, path_id: uuid::Uuid, course_ids: &[uuid::Uuid]) -> AppResult<()>;
+
+    /// Ids of the courses to complete before enrolling in the path
+    async fn list_prerequisite_ids(&self, path_id: uuid::Uuid) -> AppResult<Vec<uuid::Uuid>>;
+
+    /// Replace the path's prerequisite courses
+    async fn set_prerequisites(
+        &self,
+        path_id: uuid::Uuid,
+        course_ids: &[uuid::Uuid],
+    ) -> AppResult<()>;
+}
@@ -11,6 +11,8 @@ pub struct LessonContentRecord {
     pub file_size: Option<i64>,
     pub filename: Option<String>,
     pub position: i32,
+    /// When set, `url` is a key into private storage rather than a public URL
+    pub is_private: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
@@ -25,6 +27,7 @@ pub struct CreateLessonContentRecord {
     pub file_size: Option<i64>,
     pub filename: Option<String>,
     pub position: i32,
+    pub is_private: bool,
 }
 
 /// Partial update for a lesson content row
@@ -41,6 +44,7 @@ pub struct UpdateLessonContentRecord {
 #[async_trait::async_trait]
 pub trait LessonContentsRepository: Send + Sync {
     async fn create(&self, input: CreateLessonContentRecord) -> AppResult<uuid::Uuid>;
+    async fn find_by_id(&self, id: uuid::Uuid) -> AppResult<Option<LessonContentRecord>>;
     async fn list_by_lesson(&self, lesson_id: uuid::Uuid) -> AppResult<Vec<LessonContentRecord>>;
     async fn update_partial(
         &self,
@@ -0,0 +1,63 @@
+use crate::pkg::error::AppResult;
+
+/// Graded quiz attempt
+#[derive(Debug, Clone)]
+pub struct QuizAttemptRecord {
+    pub id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    pub lesson_id: uuid::Uuid,
+    pub course_id: uuid::Uuid,
+    pub correct: i32,
+    pub total: i32,
+    pub score: f64,
+    pub passed: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Input for recording a graded quiz attempt
+#[derive(Debug, Clone)]
+pub struct CreateQuizAttemptRecord {
+    pub user_id: uuid::Uuid,
+    pub lesson_id: uuid::Uuid,
+    pub course_id: uuid::Uuid,
+    pub correct: i32,
+    pub total: i32,
+    pub score: f64,
+    pub passed: bool,
+}
+
+/// A learner's progress through the published lessons of a course
+#[derive(Debug, Clone, Default)]
+pub struct CourseProgressRecord {
+    pub total_lessons: i64,
+    pub completed_lesson_ids: Vec<uuid::Uuid>,
+    /// Published lessons that have quiz questions
+    pub quiz_lessons: i64,
+    /// Of those, the ones with at least one passed attempt
+    pub passed_quiz_lessons: i64,
+}
+
+#[async_trait::async_trait]
+pub trait LessonProgressRepository: Send + Sync {
+    /// Record that the user completed a lesson; completing it again is a no-op
+    async fn mark_completed(
+        &self,
+        user_id: uuid::Uuid,
+        lesson_id: uuid::Uuid,
+        course_id: uuid::Uuid,
+    ) -> AppResult<()>;
+
+    async fn create_quiz_attempt(
+        &self,
+        input: CreateQuizAttemptRecord,
+    ) -> AppResult<QuizAttemptRecord>;
+
+    /// Whether the user has a passed attempt at the lesson's quiz
+    async fn has_passed_quiz(&self, user_id: uuid::Uuid, lesson_id: uuid::Uuid) -> AppResult<bool>;
+
+    async fn course_progress(
+        &self,
+        user_id: uuid::Uuid,
+        course_id: uuid::Uuid,
+    ) -> AppResult<CourseProgressRecord>;
+}
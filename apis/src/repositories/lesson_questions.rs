@@ -53,6 +53,7 @@ pub struct UpdateQuestionOptionRecord {
 pub trait LessonQuestionsRepository: Send + Sync {
     async fn create_question(&self, input: CreateLessonQuestionRecord) -> AppResult<uuid::Uuid>;
     async fn list_questions(&self, lesson_id: uuid::Uuid) -> AppResult<Vec<LessonQuestionRecord>>;
+    async fn find_question(&self, id: uuid::Uuid) -> AppResult<Option<LessonQuestionRecord>>;
     async fn update_question(
         &self,
         id: uuid::Uuid,
@@ -62,6 +63,7 @@ pub trait LessonQuestionsRepository: Send + Sync {
 
     async fn create_option(&self, input: CreateQuestionOptionRecord) -> AppResult<uuid::Uuid>;
     async fn list_options(&self, question_id: uuid::Uuid) -> AppResult<Vec<QuestionOptionRecord>>;
+    async fn find_option(&self, id: uuid::Uuid) -> AppResult<Option<QuestionOptionRecord>>;
     async fn update_option(
         &self,
         id: uuid::Uuid,
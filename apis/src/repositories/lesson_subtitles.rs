@@ -0,0 +1,57 @@
+use crate::pkg::error::AppResult;
+
+/// Caption track for a lesson in one language
+#[derive(Debug, Clone)]
+pub struct LessonSubtitleRecord {
+    pub id: uuid::Uuid,
+    pub lesson_id: uuid::Uuid,
+    pub language: String,
+    pub label: Option<String>,
+    /// Canonical WebVTT document
+    pub vtt: String,
+    /// Plain text derived from the cues
+    pub transcript: String,
+    pub cue_count: i32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Payload to create or replace a lesson's track for a language
+#[derive(Debug, Clone)]
+pub struct UpsertLessonSubtitleRecord {
+    pub lesson_id: uuid::Uuid,
+    pub language: String,
+    pub label: Option<String>,
+    pub vtt: String,
+    pub transcript: String,
+    pub cue_count: i32,
+}
+
+/// A lesson whose transcript matched a search query
+#[derive(Debug, Clone)]
+pub struct TranscriptMatchRecord {
+    pub course_id: uuid::Uuid,
+    pub lesson_id: uuid::Uuid,
+    pub language: String,
+    pub rank: f32,
+}
+
+#[async_trait::async_trait]
+pub trait LessonSubtitlesRepository: Send + Sync {
+    async fn upsert(&self, input: UpsertLessonSubtitleRecord) -> AppResult<LessonSubtitleRecord>;
+    async fn list_by_lesson(&self, lesson_id: uuid::Uuid) -> AppResult<Vec<LessonSubtitleRecord>>;
+    async fn find(
+        &self,
+        lesson_id: uuid::Uuid,
+        language: &str,
+    ) -> AppResult<Option<LessonSubtitleRecord>>;
+    /// Returns whether a track was deleted
+    async fn delete(&self, lesson_id: uuid::Uuid, language: &str) -> AppResult<bool>;
+
+    /// Full-text match over transcripts, best matches first; feeds course search
+    async fn search_transcripts(
+        &self,
+        query: &str,
+        limit: i64,
+    ) -> AppResult<Vec<TranscriptMatchRecord>>;
+}
@@ -0,0 +1,39 @@
+use crate::pkg::error::AppResult;
+
+/// Provider-hosted video attached to a lesson
+#[derive(Debug, Clone)]
+pub struct LessonVideoRecord {
+    pub lesson_id: uuid::Uuid,
+    pub provider: String,
+    pub provider_video_id: String,
+    /// One of `uploading`, `processing`, `ready`, `failed`
+    pub status: String,
+    pub embed_url: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Payload to attach (or replace) a lesson's hosted video
+#[derive(Debug, Clone)]
+pub struct UpsertLessonVideoRecord {
+    pub lesson_id: uuid::Uuid,
+    pub provider: String,
+    pub provider_video_id: String,
+    pub status: String,
+}
+
+#[async_trait::async_trait]
+pub trait LessonVideosRepository: Send + Sync {
+    /// Attach a video to a lesson, replacing any previous one
+    async fn upsert(&self, input: UpsertLessonVideoRecord) -> AppResult<LessonVideoRecord>;
+
+    async fn find_by_lesson(&self, lesson_id: uuid::Uuid) -> AppResult<Option<LessonVideoRecord>>;
+
+    /// Record a new processing status (and embed URL once ready)
+    async fn update_status(
+        &self,
+        lesson_id: uuid::Uuid,
+        status: &str,
+        embed_url: Option<String>,
+    ) -> AppResult<Option<LessonVideoRecord>>;
+}
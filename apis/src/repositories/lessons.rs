@@ -1,4 +1,5 @@
 use crate::pkg::error::AppResult;
+use crate::repositories::modules::DripSchedule;
 use crate::types::course_types::LessonPositionUpdate;
 
 #[derive(Debug, Clone)]
@@ -42,6 +43,31 @@ pub struct UpdateLessonRecord {
     pub published: Option<bool>,
 }
 
+/// Ownership and visibility facts needed to decide who may see a lesson
+#[derive(Debug, Clone)]
+pub struct LessonAccessRecord {
+    pub lesson_id: uuid::Uuid,
+    pub module_id: uuid::Uuid,
+    pub course_id: uuid::Uuid,
+    pub instructor_id: uuid::Uuid,
+    pub is_free: bool,
+    pub published: bool,
+    /// Release schedule of the lesson's module
+    pub drip: DripSchedule,
+    /// Status of the owning course
+    pub course_status: String,
+}
+
+/// Ownership and release facts of a module, shared by its lessons
+#[derive(Debug, Clone)]
+pub struct ModuleAccessRecord {
+    pub module_id: uuid::Uuid,
+    pub course_id: uuid::Uuid,
+    pub instructor_id: uuid::Uuid,
+    pub drip: DripSchedule,
+    pub course_status: String,
+}
+
 #[async_trait::async_trait]
 pub trait LessonsRepository: Send + Sync {
     async fn create(&self, input: CreateLessonRecord) -> AppResult<uuid::Uuid>;
@@ -54,6 +80,15 @@ pub trait LessonsRepository: Send + Sync {
     ) -> AppResult<Option<LessonRecord>>;
     async fn delete_by_id(&self, id: uuid::Uuid) -> AppResult<()>;
 
+    /// Resolve the owning course and instructor of a lesson
+    async fn find_access_context(&self, id: uuid::Uuid) -> AppResult<Option<LessonAccessRecord>>;
+
+    /// Resolve the owning course, instructor and drip schedule of a module
+    async fn find_module_access_context(
+        &self,
+        module_id: uuid::Uuid,
+    ) -> AppResult<Option<ModuleAccessRecord>>;
+
     /// Bulk update lesson positions for a module
     async fn bulk_update_positions(
         &self,
@@ -1,28 +1,56 @@
 use sqlx::{Pool, Postgres};
 use std::sync::Arc;
+pub mod analytics;
 pub mod categories;
+pub mod certificates;
+pub mod coupons;
 pub mod course_categories;
+pub mod course_reviews;
+pub mod course_revisions;
 pub mod courses;
+pub mod enrollments;
+pub mod learning_paths;
 pub mod lesson_assignments;
 pub mod lesson_contents;
+pub mod lesson_progress;
 pub mod lesson_questions;
+pub mod lesson_subtitles;
+pub mod lesson_videos;
 pub mod lessons;
 pub mod modules;
+pub mod orders;
+pub mod payment_events;
 pub mod postgresql;
+pub mod refund_requests;
+pub mod search;
 pub mod users;
 pub mod blog_posts;
 pub mod blog_tags;
 pub mod blog_likes;
 pub mod blog_comments;
 
+use analytics::AnalyticsRepository;
 use categories::CategoriesRepository;
+use certificates::CertificatesRepository;
+use coupons::CouponsRepository;
 use course_categories::CourseCategoriesRepository;
+use course_reviews::CourseReviewsRepository;
+use course_revisions::CourseRevisionsRepository;
 use courses::CoursesRepository;
+use enrollments::EnrollmentsRepository;
+use learning_paths::LearningPathsRepository;
 use lesson_assignments::LessonAssignmentsRepository;
 use lesson_contents::LessonContentsRepository;
+use lesson_progress::LessonProgressRepository;
 use lesson_questions::LessonQuestionsRepository;
+use lesson_subtitles::LessonSubtitlesRepository;
+use lesson_videos::LessonVideosRepository;
 use lessons::LessonsRepository;
 use modules::ModulesRepository;
+use orders::OrdersRepository;
+use payment_events::PaymentEventsRepository;
+use refund_requests::RefundRequestsRepository;
+use search::SearchRepository;
 use users::UsersRepository;
 use blog_posts::BlogPostsRepository;
 use blog_tags::BlogTagsRepository;
@@ -48,6 +76,20 @@ pub struct Repositories {
     pub blog_tags: Arc<dyn BlogTagsRepository>,
     pub blog_likes: Arc<dyn BlogLikesRepository>,
     pub blog_comments: Arc<dyn BlogCommentsRepository>,
+    pub enrollments: Arc<dyn EnrollmentsRepository>,
+    pub lesson_videos: Arc<dyn LessonVideosRepository>,
+    pub lesson_subtitles: Arc<dyn LessonSubtitlesRepository>,
+    pub search: Arc<dyn SearchRepository>,
+    pub course_revisions: Arc<dyn CourseRevisionsRepository>,
+    pub course_reviews: Arc<dyn CourseReviewsRepository>,
+    pub lesson_progress: Arc<dyn LessonProgressRepository>,
+    pub certificates: Arc<dyn CertificatesRepository>,
+    pub orders: Arc<dyn OrdersRepository>,
+    pub payment_events: Arc<dyn PaymentEventsRepository>,
+    pub coupons: Arc<dyn CouponsRepository>,
+    pub refund_requests: Arc<dyn RefundRequestsRepository>,
+    pub learning_paths: Arc<dyn LearningPathsRepository>,
+    pub analytics: Arc<dyn AnalyticsRepository>,
 }
 
 impl Repositories {
@@ -113,9 +155,77 @@ impl Repositories {
         );
         let blog_comments: Arc<dyn BlogCommentsRepository> = Arc::new(
             crate::repositories::postgresql::blog_comments::PostgresBlogCommentsRepository {
-                pool,
+                pool: pool.clone(),
+            },
+        );
+        let enrollments: Arc<dyn EnrollmentsRepository> = Arc::new(
+            crate::repositories::postgresql::enrollments::PostgresEnrollmentsRepository {
+                pool: pool.clone(),
             },
         );
+        let lesson_videos: Arc<dyn LessonVideosRepository> = Arc::new(
+            crate::repositories::postgresql::lesson_videos::PostgresLessonVideosRepository {
+                pool: pool.clone(),
+            },
+        );
+        let lesson_subtitles: Arc<dyn LessonSubtitlesRepository> = Arc::new(
+            crate::repositories::postgresql::lesson_subtitles::PostgresLessonSubtitlesRepository {
+                pool: pool.clone(),
+            },
+        );
+        let search: Arc<dyn SearchRepository> = Arc::new(
+            crate::repositories::postgresql::search::PostgresSearchRepository {
+                pool: pool.clone(),
+            },
+        );
+        let course_revisions: Arc<dyn CourseRevisionsRepository> = Arc::new(
+            crate::repositories::postgresql::course_revisions::PostgresCourseRevisionsRepository {
+                pool: pool.clone(),
+            },
+        );
+        let course_reviews: Arc<dyn CourseReviewsRepository> = Arc::new(
+            crate::repositories::postgresql::course_reviews::PostgresCourseReviewsRepository {
+                pool: pool.clone(),
+            },
+        );
+        let lesson_progress: Arc<dyn LessonProgressRepository> = Arc::new(
+            crate::repositories::postgresql::lesson_progress::PostgresLessonProgressRepository {
+                pool: pool.clone(),
+            },
+        );
+        let certificates: Arc<dyn CertificatesRepository> = Arc::new(
+            crate::repositories::postgresql::certificates::PostgresCertificatesRepository {
+                pool: pool.clone(),
+            },
+        );
+        let orders: Arc<dyn OrdersRepository> = Arc::new(
+            crate::repositories::postgresql::orders::PostgresOrdersRepository {
+                pool: pool.clone(),
+            },
+        );
+        let payment_events: Arc<dyn PaymentEventsRepository> = Arc::new(
+            crate::repositories::postgresql::payment_events::PostgresPaymentEventsRepository {
+                pool: pool.clone(),
+            },
+        );
+        let coupons: Arc<dyn CouponsRepository> = Arc::new(
+            crate::repositories::postgresql::coupons::PostgresCouponsRepository {
+                pool: pool.clone(),
+            },
+        );
+        let refund_requests: Arc<dyn RefundRequestsRepository> = Arc::new(
+            crate::repositories::postgresql::refund_requests::PostgresRefundRequestsRepository {
+                pool: pool.clone(),
+            },
+        );
+        let learning_paths: Arc<dyn LearningPathsRepository> = Arc::new(
+            crate::repositories::postgresql::learning_paths::PostgresLearningPathsRepository {
+                pool: pool.clone(),
+            },
+        );
+        let analytics: Arc<dyn AnalyticsRepository> = Arc::new(
+            crate::repositories::postgresql::analytics::PostgresAnalyticsRepository { pool },
+        );
 
         Self {
             users,
@@ -131,6 +241,20 @@ impl Repositories {
             blog_tags,
             blog_likes,
             blog_comments,
+            enrollments,
+            lesson_videos,
+            lesson_subtitles,
+            search,
+            course_revisions,
+            course_reviews,
+            lesson_progress,
+            certificates,
+            orders,
+            payment_events,
+            coupons,
+            refund_requests,
+            learning_paths,
+            analytics,
         }
     }
 }
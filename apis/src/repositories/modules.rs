@@ -1,6 +1,16 @@
 use crate::pkg::error::AppResult;
 use crate::types::course_types::ModulePositionUpdate;
 
+/// When a module's lessons are released to an enrolled learner; an empty
+/// schedule releases them at once
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DripSchedule {
+    /// Days after the learner enrolled
+    pub days: Option<i32>,
+    /// Fixed date, the same for every learner
+    pub at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 #[derive(Debug, Clone)]
 pub struct ModuleRecord {
     pub id: uuid::Uuid,
@@ -8,6 +18,7 @@ pub struct ModuleRecord {
     pub title: String,
     pub description: Option<String>,
     pub position: i32,
+    pub drip: DripSchedule,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
@@ -36,6 +47,8 @@ pub struct CreateLessonContentData {
     pub file_size: Option<i64>,
     pub filename: Option<String>,
     pub position: i32,
+    /// `url` is a private storage key; only applied when the row is created
+    pub is_private: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -113,6 +126,10 @@ pub trait ModulesRepository: Send + Sync {
     ) -> AppResult<Option<ModuleRecord>>;
     async fn delete_by_id(&self, id: uuid::Uuid) -> AppResult<()>;
 
+    /// Replace a module's drip schedule
+    async fn set_drip(&self, id: uuid::Uuid, drip: DripSchedule)
+    -> AppResult<Option<ModuleRecord>>;
+
     /// Bulk update module positions for a course
     async fn bulk_update_positions(
         &self,
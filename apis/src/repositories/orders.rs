@@ -0,0 +1,252 @@
+use crate::pkg::error::AppResult;
+use crate::pkg::payments::PaymentStatus;
+
+/// Lifecycle states of an order (`orders.status`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderStatus {
+    /// Awaiting payment
+    Pending,
+    /// Payment confirmed; the buyer is enrolled
+    Paid,
+    /// The payment was declined or errored
+    Failed,
+    /// Abandoned before payment
+    Canceled,
+    /// Paid, then refunded
+    Refunded,
+}
+
+impl OrderStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Paid => "paid",
+            Self::Failed => "failed",
+            Self::Canceled => "canceled",
+            Self::Refunded => "refunded",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(Self::Pending),
+            "paid" => Some(Self::Paid),
+            "failed" => Some(Self::Failed),
+            "canceled" => Some(Self::Canceled),
+            "refunded" => Some(Self::Refunded),
+            _ => None,
+        }
+    }
+}
+
+/// What happened to an order (`order_events.event`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderEventKind {
+    Created,
+    Paid,
+    Failed,
+    Canceled,
+    RefundRequested,
+    RefundRejected,
+    /// The provider declined or errored on a refund
+    RefundFailed,
+    Refunded,
+    /// The buyer lost access to the order's courses
+    EnrollmentsRevoked,
+}
+
+impl OrderEventKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Paid => "paid",
+            Self::Failed => "failed",
+            Self::Canceled => "canceled",
+            Self::RefundRequested => "refund_requested",
+            Self::RefundRejected => "refund_rejected",
+            Self::RefundFailed => "refund_failed",
+            Self::Refunded => "refunded",
+            Self::EnrollmentsRevoked => "enrollments_revoked",
+        }
+    }
+}
+
+/// Entry in an order's audit trail
+#[derive(Debug, Clone)]
+pub struct OrderEventRecord {
+    pub id: uuid::Uuid,
+    pub order_id: uuid::Uuid,
+    /// `None` for the system and the payment provider
+    pub actor_id: Option<uuid::Uuid>,
+    pub event: String,
+    pub note: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateOrderEventRecord {
+    pub order_id: uuid::Uuid,
+    pub actor_id: Option<uuid::Uuid>,
+    pub event: OrderEventKind,
+    pub note: Option<String>,
+}
+
+/// Course bought by an order, with title and price as charged
+#[derive(Debug, Clone)]
+pub struct OrderItemRecord {
+    pub id: uuid::Uuid,
+    /// `None` once the course has been deleted
+    pub course_id: Option<uuid::Uuid>,
+    pub course_title: String,
+    pub unit_price_cents: i64,
+    /// Coupon or bundle discount on this course; at most `unit_price_cents`
+    pub discount_cents: i64,
+}
+
+/// Persisted order with its items
+#[derive(Debug, Clone)]
+pub struct OrderRecord {
+    pub id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    pub status: String,
+    pub currency: String,
+    pub subtotal_cents: i64,
+    pub discount_cents: i64,
+    pub total_cents: i64,
+    /// Coupon redeemed by the order
+    pub coupon_code: Option<String>,
+    /// Learning path bought as a whole; paying enrolls the buyer in it
+    pub learning_path_id: Option<uuid::Uuid>,
+    pub items: Vec<OrderItemRecord>,
+    pub paid_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub refunded_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateOrderItemRecord {
+    pub course_id: uuid::Uuid,
+    pub course_title: String,
+    pub unit_price_cents: i64,
+    pub discount_cents: i64,
+}
+
+/// Coupon an order is placed with, and the caps to enforce when redeeming it
+#[derive(Debug, Clone)]
+pub struct OrderCouponRecord {
+    pub coupon_id: uuid::Uuid,
+    pub code: String,
+    pub max_redemptions: Option<i32>,
+    pub max_redemptions_per_user: Option<i32>,
+}
+
+/// Input for placing an order; totals are the sums of item prices and
+/// discounts
+#[derive(Debug, Clone)]
+pub struct CreateOrderRecord {
+    pub user_id: uuid::Uuid,
+    pub currency: String,
+    pub items: Vec<CreateOrderItemRecord>,
+    pub coupon: Option<OrderCouponRecord>,
+    pub learning_path_id: Option<uuid::Uuid>,
+}
+
+/// Attempt to collect an order's total through a payment provider
+#[derive(Debug, Clone)]
+pub struct PaymentAttemptRecord {
+    pub id: uuid::Uuid,
+    pub order_id: uuid::Uuid,
+    pub provider: String,
+    pub provider_payment_id: String,
+    pub status: String,
+    pub amount_cents: i64,
+    pub currency: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreatePaymentAttemptRecord {
+    pub order_id: uuid::Uuid,
+    pub provider: String,
+    pub provider_payment_id: String,
+    pub status: PaymentStatus,
+    pub amount_cents: i64,
+    pub currency: String,
+}
+
+#[async_trait::async_trait]
+pub trait OrdersRepository: Send + Sync {
+    /// Insert a pending order and its items in one transaction.
+    ///
+    /// With a coupon, its redemption is recorded in the same transaction;
+    /// fails with `Conflict` when that would exceed one of the coupon's caps.
+    async fn create(&self, input: CreateOrderRecord) -> AppResult<OrderRecord>;
+
+    async fn find_by_id(&self, id: uuid::Uuid) -> AppResult<Option<OrderRecord>>;
+
+    /// Orders placed by a user, newest first
+    async fn list_by_user(&self, user_id: uuid::Uuid) -> AppResult<Vec<OrderRecord>>;
+
+    /// Move the order to `to` only if its current status is one of `from`.
+    ///
+    /// Returns `None` when the order does not exist or is in another state,
+    /// which makes concurrent confirmations fulfil an order only once.
+    async fn transition_status(
+        &self,
+        id: uuid::Uuid,
+        from: &[OrderStatus],
+        to: OrderStatus,
+    ) -> AppResult<Option<OrderRecord>>;
+
+    /// Move the order to `paid` if its current status is one of `from`, and in
+    /// the same transaction record the `paid` event and enroll the buyer in
+    /// its courses and learning path.
+    ///
+    /// Returns `None`, changing nothing, when the order does not exist or is
+    /// in another state; a failure leaves the order unpaid so it can be
+    /// fulfilled again.
+    async fn mark_paid(
+        &self,
+        id: uuid::Uuid,
+        from: &[OrderStatus],
+    ) -> AppResult<Option<OrderRecord>>;
+
+    /// Pending orders created before `created_before`, oldest first
+    async fn list_stale_pending(
+        &self,
+        created_before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<uuid::Uuid>>;
+
+    async fn create_payment_attempt(
+        &self,
+        input: CreatePaymentAttemptRecord,
+    ) -> AppResult<PaymentAttemptRecord>;
+
+    /// Most recent payment attempt of an order
+    async fn latest_payment_attempt(
+        &self,
+        order_id: uuid::Uuid,
+    ) -> AppResult<Option<PaymentAttemptRecord>>;
+
+    /// Payment attempt by the provider's intent id
+    async fn find_payment_attempt(
+        &self,
+        provider: &str,
+        provider_payment_id: &str,
+    ) -> AppResult<Option<PaymentAttemptRecord>>;
+
+    async fn update_payment_attempt_status(
+        &self,
+        id: uuid::Uuid,
+        status: PaymentStatus,
+    ) -> AppResult<()>;
+
+    /// Append to the order's audit trail
+    async fn record_event(&self, input: CreateOrderEventRecord) -> AppResult<()>;
+
+    /// The order's audit trail, oldest first
+    async fn list_events(&self, order_id: uuid::Uuid) -> AppResult<Vec<OrderEventRecord>>;
+}
@@ -0,0 +1,32 @@
+use crate::pkg::error::AppResult;
+
+/// Webhook event received from a payment provider
+#[derive(Debug, Clone)]
+pub struct PaymentEventRecord {
+    pub id: uuid::Uuid,
+    pub provider: String,
+    pub event_id: String,
+    pub event_type: String,
+    pub payload: String,
+    pub order_id: Option<uuid::Uuid>,
+    pub received_at: chrono::DateTime<chrono::Utc>,
+    /// Set once the event has been applied; redeliveries are then ignored
+    pub processed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreatePaymentEventRecord {
+    pub provider: String,
+    pub event_id: String,
+    pub event_type: String,
+    pub payload: String,
+}
+
+#[async_trait::async_trait]
+pub trait PaymentEventsRepository: Send + Sync {
+    /// Store an event, or return the stored one if this event id was seen before
+    async fn record(&self, input: CreatePaymentEventRecord) -> AppResult<PaymentEventRecord>;
+
+    /// Mark an event applied, noting the order it affected
+    async fn mark_processed(&self, id: uuid::Uuid, order_id: Option<uuid::Uuid>) -> AppResult<()>;
+}
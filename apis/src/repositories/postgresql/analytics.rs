@@ -0,0 +1,401 @@
+use chrono::NaiveDate;
+use sqlx::Row;
+
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::analytics::{
+    AnalyticsRepository, CourseDailyRevenueRecord, CourseDailyStatsRecord, CourseSummaryRecord,
+    LessonEngagementRecord, ModuleFunnelRecord,
+};
+
+pub struct PostgresAnalyticsRepository {
+    pub pool: sqlx::Pool<sqlx::Postgres>,
+}
+
+#[async_trait::async_trait]
+impl AnalyticsRepository for PostgresAnalyticsRepository {
+    async fn latest_rollup_day(&self) -> AppResult<Option<NaiveDate>> {
+        sqlx::query_scalar("SELECT MAX(day) FROM course_daily_stats")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(AppError::from)
+    }
+
+    async fn roll_up_day(&self, day: NaiveDate, snapshot_views: bool) -> AppResult<()> {
+        let start = day.and_time(chrono::NaiveTime::MIN).and_utc();
+        let end = start + chrono::Duration::days(1);
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        sqlx::query(
+            r#"INSERT INTO course_daily_stats
+                   (course_id, day, new_enrollments, revoked_enrollments, active_enrollments,
+                    completions, lessons_completed, quiz_attempts, view_count, rolled_up_at)
+               SELECT c.id, $1,
+                   (SELECT COUNT(*) FROM enrollments e
+                    WHERE e.course_id = c.id AND e.enrolled_at >= $2 AND e.enrolled_at < $3),
+                   (SELECT COUNT(*) FROM enrollments e
+                    WHERE e.course_id = c.id AND e.revoked_at >= $2 AND e.revoked_at < $3),
+                   (SELECT COUNT(*) FROM enrollments e
+                    WHERE e.course_id = c.id AND e.enrolled_at < $3
+                      AND ((e.status = 'active' AND e.revoked_at IS NULL) OR e.revoked_at >= $3)),
+                   (SELECT COUNT(*) FROM certificates ce
+                    WHERE ce.course_id = c.id AND ce.issued_at >= $2 AND ce.issued_at < $3),
+                   (SELECT COUNT(*) FROM lesson_completions lc
+                    WHERE lc.course_id = c.id AND lc.completed_at >= $2 AND lc.completed_at < $3),
+                   (SELECT COUNT(*) FROM quiz_attempts qa
+                    WHERE qa.course_id = c.id AND qa.created_at >= $2 AND qa.created_at < $3),
+                   CASE WHEN $4 THEN c.view_count::bigint END,
+                   NOW()
+               FROM courses c
+               WHERE c.created_at < $3
+               ON CONFLICT (course_id, day) DO UPDATE SET
+                   new_enrollments = EXCLUDED.new_enrollments,
+                   revoked_enrollments = EXCLUDED.revoked_enrollments,
+                   active_enrollments = EXCLUDED.active_enrollments,
+                   completions = EXCLUDED.completions,
+                   lessons_completed = EXCLUDED.lessons_completed,
+                   quiz_attempts = EXCLUDED.quiz_attempts,
+                   view_count = COALESCE(EXCLUDED.view_count, course_daily_stats.view_count),
+                   rolled_up_at = NOW()"#,
+        )
+        .bind(day)
+        .bind(start)
+        .bind(end)
+        .bind(snapshot_views)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        // Orders paid or refunded on the day; a refund returns every item
+        sqlx::query("DELETE FROM course_daily_revenue WHERE day = $1")
+            .bind(day)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+        sqlx::query(
+            r#"INSERT INTO course_daily_revenue
+                   (course_id, day, currency, orders, gross_cents, refunds, refunded_cents)
+               SELECT oi.course_id, $1, o.currency,
+                   COUNT(*) FILTER (WHERE o.paid_at >= $2 AND o.paid_at < $3),
+                   COALESCE(SUM(oi.unit_price_cents - oi.discount_cents)
+                       FILTER (WHERE o.paid_at >= $2 AND o.paid_at < $3), 0)::bigint,
+                   COUNT(*) FILTER (WHERE o.refunded_at >= $2 AND o.refunded_at < $3),
+                   COALESCE(SUM(oi.unit_price_cents - oi.discount_cents)
+                       FILTER (WHERE o.refunded_at >= $2 AND o.refunded_at < $3), 0)::bigint
+               FROM order_items oi
+               JOIN orders o ON o.id = oi.order_id
+               WHERE oi.course_id IS NOT NULL
+                 AND ((o.paid_at >= $2 AND o.paid_at < $3)
+                   OR (o.refunded_at >= $2 AND o.refunded_at < $3))
+               GROUP BY oi.course_id, o.currency"#,
+        )
+        .bind(day)
+        .bind(start)
+        .bind(end)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        sqlx::query(
+            r#"WITH published AS (
+                   SELECT l.id, l.module_id, m.course_id
+                   FROM lessons l
+                   JOIN course_modules m ON m.id = l.module_id
+                   WHERE l.published
+               ), totals AS (
+                   SELECT module_id, course_id, COUNT(*) AS lessons
+                   FROM published
+                   GROUP BY module_id, course_id
+               ), progress AS (
+                   SELECT p.module_id, lc.user_id, COUNT(*) AS completed
+                   FROM lesson_completions lc
+                   JOIN published p ON p.id = lc.lesson_id
+                   WHERE lc.completed_at < $2
+                   GROUP BY p.module_id, lc.user_id
+               )
+               INSERT INTO module_daily_stats
+                   (module_id, course_id, day, learners_started, learners_completed)
+               SELECT t.module_id, t.course_id, $1,
+                   COUNT(p.user_id),
+                   COUNT(p.user_id) FILTER (WHERE p.completed >= t.lessons)
+               FROM totals t
+               LEFT JOIN progress p ON p.module_id = t.module_id
+               GROUP BY t.module_id, t.course_id
+               ON CONFLICT (module_id, day) DO UPDATE SET
+                   learners_started = EXCLUDED.learners_started,
+                   learners_completed = EXCLUDED.learners_completed"#,
+        )
+        .bind(day)
+        .bind(end)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        sqlx::query(
+            r#"INSERT INTO lesson_daily_stats
+                   (lesson_id, course_id, day, completions, learners_completed,
+                    quiz_attempts, quiz_passes, quiz_score_sum)
+               SELECT l.id, m.course_id, $1,
+                   (SELECT COUNT(*) FROM lesson_completions lc
+                    WHERE lc.lesson_id = l.id AND lc.completed_at >= $2 AND lc.completed_at < $3),
+                   (SELECT COUNT(*) FROM lesson_completions lc
+                    WHERE lc.lesson_id = l.id AND lc.completed_at < $3),
+                   q.attempts, q.passes, q.score_sum
+               FROM lessons l
+               JOIN course_modules m ON m.id = l.module_id
+               CROSS JOIN LATERAL (
+                   SELECT COUNT(*) AS attempts,
+                       COUNT(*) FILTER (WHERE qa.passed) AS passes,
+                       COALESCE(SUM(qa.score), 0) AS score_sum
+                   FROM quiz_attempts qa
+                   WHERE qa.lesson_id = l.id AND qa.created_at >= $2 AND qa.created_at < $3
+               ) q
+               WHERE l.created_at < $3
+               ON CONFLICT (lesson_id, day) DO UPDATE SET
+                   completions = EXCLUDED.completions,
+                   learners_completed = EXCLUDED.learners_completed,
+                   quiz_attempts = EXCLUDED.quiz_attempts,
+                   quiz_passes = EXCLUDED.quiz_passes,
+                   quiz_score_sum = EXCLUDED.quiz_score_sum"#,
+        )
+        .bind(day)
+        .bind(start)
+        .bind(end)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        tx.commit().await.map_err(AppError::from)?;
+        Ok(())
+    }
+
+    async fn list_course_days(
+        &self,
+        course_id: uuid::Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<CourseDailyStatsRecord>> {
+        let rows = sqlx::query(
+            r#"SELECT course_id, day, new_enrollments, revoked_enrollments, active_enrollments,
+                   completions, lessons_completed, quiz_attempts, view_count
+               FROM course_daily_stats
+               WHERE course_id = $1 AND day BETWEEN $2 AND $3
+               ORDER BY day"#,
+        )
+        .bind(course_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| CourseDailyStatsRecord {
+                course_id: row.get("course_id"),
+                day: row.get("day"),
+                new_enrollments: row.get("new_enrollments"),
+                revoked_enrollments: row.get("revoked_enrollments"),
+                active_enrollments: row.get("active_enrollments"),
+                completions: row.get("completions"),
+                lessons_completed: row.get("lessons_completed"),
+                quiz_attempts: row.get("quiz_attempts"),
+                view_count: row.get("view_count"),
+            })
+            .collect())
+    }
+
+    async fn list_course_revenue(
+        &self,
+        course_id: uuid::Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<CourseDailyRevenueRecord>> {
+        let rows = sqlx::query(
+            r#"SELECT course_id, day, currency, orders, gross_cents, refunds, refunded_cents
+               FROM course_daily_revenue
+               WHERE course_id = $1 AND day BETWEEN $2 AND $3
+               ORDER BY day, currency"#,
+        )
+        .bind(course_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(rows.into_iter().map(map_revenue_row).collect())
+    }
+
+    async fn list_module_funnel(
+        &self,
+        course_id: uuid::Uuid,
+        as_of: NaiveDate,
+    ) -> AppResult<Vec<ModuleFunnelRecord>> {
+        let rows = sqlx::query(
+            r#"SELECT m.id, m.title, m.position,
+                   COALESCE(s.learners_started, 0) AS learners_started,
+                   COALESCE(s.learners_completed, 0) AS learners_completed
+               FROM course_modules m
+               LEFT JOIN LATERAL (
+                   SELECT learners_started, learners_completed
+                   FROM module_daily_stats s
+                   WHERE s.module_id = m.id AND s.day <= $2
+                   ORDER BY s.day DESC
+                   LIMIT 1
+               ) s ON TRUE
+               WHERE m.course_id = $1
+               ORDER BY m.position"#,
+        )
+        .bind(course_id)
+        .bind(as_of)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| ModuleFunnelRecord {
+                module_id: row.get("id"),
+                title: row.get("title"),
+                position: row.get("position"),
+                learners_started: row.get("learners_started"),
+                learners_completed: row.get("learners_completed"),
+            })
+            .collect())
+    }
+
+    async fn list_lesson_engagement(
+        &self,
+        course_id: uuid::Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<LessonEngagementRecord>> {
+        let rows = sqlx::query(
+            r#"SELECT l.id, l.module_id, l.title,
+                   COALESCE(latest.learners_completed, 0) AS learners_completed,
+                   COALESCE(r.completions, 0) AS completions,
+                   COALESCE(r.quiz_attempts, 0) AS quiz_attempts,
+                   COALESCE(r.quiz_passes, 0) AS quiz_passes,
+                   COALESCE(r.quiz_score_sum, 0) AS quiz_score_sum
+               FROM lessons l
+               JOIN course_modules m ON m.id = l.module_id
+               LEFT JOIN LATERAL (
+                   SELECT SUM(s.completions)::bigint AS completions,
+                       SUM(s.quiz_attempts)::bigint AS quiz_attempts,
+                       SUM(s.quiz_passes)::bigint AS quiz_passes,
+                       SUM(s.quiz_score_sum) AS quiz_score_sum
+                   FROM lesson_daily_stats s
+                   WHERE s.lesson_id = l.id AND s.day BETWEEN $2 AND $3
+               ) r ON TRUE
+               LEFT JOIN LATERAL (
+                   SELECT s.learners_completed
+                   FROM lesson_daily_stats s
+                   WHERE s.lesson_id = l.id AND s.day <= $3
+                   ORDER BY s.day DESC
+                   LIMIT 1
+               ) latest ON TRUE
+               WHERE m.course_id = $1 AND l.published
+               ORDER BY m.position, l.position"#,
+        )
+        .bind(course_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| LessonEngagementRecord {
+                lesson_id: row.get("id"),
+                module_id: row.get("module_id"),
+                title: row.get("title"),
+                learners_completed: row.get("learners_completed"),
+                completions: row.get("completions"),
+                quiz_attempts: row.get("quiz_attempts"),
+                quiz_passes: row.get("quiz_passes"),
+                quiz_score_sum: row.get("quiz_score_sum"),
+            })
+            .collect())
+    }
+
+    async fn summarize_instructor_courses(
+        &self,
+        instructor_id: uuid::Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<CourseSummaryRecord>> {
+        let rows = sqlx::query(
+            r#"SELECT c.id, c.title,
+                   COALESCE(r.new_enrollments, 0) AS new_enrollments,
+                   COALESCE(r.completions, 0) AS completions,
+                   COALESCE(latest.active_enrollments, 0) AS active_enrollments,
+                   r.views
+               FROM courses c
+               LEFT JOIN LATERAL (
+                   SELECT SUM(s.new_enrollments)::bigint AS new_enrollments,
+                       SUM(s.completions)::bigint AS completions,
+                       MAX(s.view_count) - MIN(s.view_count) AS views
+                   FROM course_daily_stats s
+                   WHERE s.course_id = c.id AND s.day BETWEEN $2 AND $3
+               ) r ON TRUE
+               LEFT JOIN LATERAL (
+                   SELECT s.active_enrollments
+                   FROM course_daily_stats s
+                   WHERE s.course_id = c.id AND s.day <= $3
+                   ORDER BY s.day DESC
+                   LIMIT 1
+               ) latest ON TRUE
+               WHERE c.instructor_id = $1
+               ORDER BY c.created_at DESC, c.id"#,
+        )
+        .bind(instructor_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| CourseSummaryRecord {
+                course_id: row.get("id"),
+                title: row.get("title"),
+                new_enrollments: row.get("new_enrollments"),
+                completions: row.get("completions"),
+                active_enrollments: row.get("active_enrollments"),
+                views: row.get("views"),
+            })
+            .collect())
+    }
+
+    async fn list_instructor_revenue(
+        &self,
+        instructor_id: uuid::Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<CourseDailyRevenueRecord>> {
+        let rows = sqlx::query(
+            r#"SELECT r.course_id, r.day, r.currency, r.orders, r.gross_cents, r.refunds,
+                   r.refunded_cents
+               FROM course_daily_revenue r
+               JOIN courses c ON c.id = r.course_id
+               WHERE c.instructor_id = $1 AND r.day BETWEEN $2 AND $3
+               ORDER BY r.day, r.currency"#,
+        )
+        .bind(instructor_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(rows.into_iter().map(map_revenue_row).collect())
+    }
+}
+
+fn map_revenue_row(row: sqlx::postgres::PgRow) -> CourseDailyRevenueRecord {
+    CourseDailyRevenueRecord {
+        course_id: row.get("course_id"),
+        day: row.get("day"),
+        currency: row.get("currency"),
+        orders: row.get("orders"),
+        gross_cents: row.get("gross_cents"),
+        refunds: row.get("refunds"),
+        refunded_cents: row.get("refunded_cents"),
+    }
+}
@@ -0,0 +1,101 @@
+use sqlx::Row;
+
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::certificates::{
+    CertificateRecord, CertificatesRepository, CreateCertificateRecord,
+};
+
+const CERTIFICATE_COLUMNS: &str = "id, user_id, course_id, verification_code, learner_name, course_title, instructor_name, issued_at";
+
+pub struct PostgresCertificatesRepository {
+    pub pool: sqlx::Pool<sqlx::Postgres>,
+}
+
+#[async_trait::async_trait]
+impl CertificatesRepository for PostgresCertificatesRepository {
+    async fn issue(&self, input: CreateCertificateRecord) -> AppResult<CertificateRecord> {
+        let sql = format!(
+            r#"INSERT INTO certificates
+                   (user_id, course_id, verification_code, learner_name, course_title, instructor_name)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               ON CONFLICT (user_id, course_id) DO NOTHING
+               RETURNING {}"#,
+            CERTIFICATE_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(input.user_id)
+            .bind(input.course_id)
+            .bind(&input.verification_code)
+            .bind(&input.learner_name)
+            .bind(&input.course_title)
+            .bind(&input.instructor_name)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        match row {
+            Some(row) => Ok(map_certificate_row(row)),
+            // Issued concurrently by another request
+            None => self
+                .find_by_user_and_course(input.user_id, input.course_id)
+                .await?
+                .ok_or_else(|| AppError::Internal("Certificate disappeared".into())),
+        }
+    }
+
+    async fn find_by_code(&self, code: &str) -> AppResult<Option<CertificateRecord>> {
+        let sql = format!(
+            "SELECT {} FROM certificates WHERE verification_code = $1",
+            CERTIFICATE_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(code)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(row.map(map_certificate_row))
+    }
+
+    async fn find_by_user_and_course(
+        &self,
+        user_id: uuid::Uuid,
+        course_id: uuid::Uuid,
+    ) -> AppResult<Option<CertificateRecord>> {
+        let sql = format!(
+            "SELECT {} FROM certificates WHERE user_id = $1 AND course_id = $2",
+            CERTIFICATE_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(user_id)
+            .bind(course_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(row.map(map_certificate_row))
+    }
+
+    async fn list_by_user(&self, user_id: uuid::Uuid) -> AppResult<Vec<CertificateRecord>> {
+        let sql = format!(
+            "SELECT {} FROM certificates WHERE user_id = $1 ORDER BY issued_at DESC",
+            CERTIFICATE_COLUMNS
+        );
+        let rows = sqlx::query(&sql)
+            .bind(user_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(rows.into_iter().map(map_certificate_row).collect())
+    }
+}
+
+fn map_certificate_row(row: sqlx::postgres::PgRow) -> CertificateRecord {
+    CertificateRecord {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        course_id: row.get("course_id"),
+        verification_code: row.get("verification_code"),
+        learner_name: row.get("learner_name"),
+        course_title: row.get("course_title"),
+        instructor_name: row.get("instructor_name"),
+        issued_at: row.get("issued_at"),
+    }
+}
@@ -0,0 +1,163 @@
+use sqlx::Row;
+
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::coupons::{
+    CouponRecord, CouponUsage, CouponsRepository, CreateCouponRecord, UpdateCouponRecord,
+};
+
+const COUPON_COLUMNS: &str = r#"c.id, c.code, c.description, c.discount_type, c.percent_off,
+    c.amount_off_cents, c.currency, c.course_id, c.category_id, c.starts_at, c.expires_at,
+    c.max_redemptions, c.max_redemptions_per_user, c.active, c.created_by, c.created_at,
+    c.updated_at,
+    (SELECT COUNT(*) FROM coupon_redemptions r JOIN orders o ON o.id = r.order_id
+     WHERE r.coupon_id = c.id AND o.status IN ('pending', 'paid', 'refunded')) AS times_redeemed"#;
+
+/// Redemptions of coupon `$1` counting towards its caps, overall and by user `$2`.
+///
+/// Paid and refunded orders keep their redemption. A pending order only
+/// reserves it: the buyer may cancel the order, and unpaid orders expire
+/// after `PENDING_ORDER_TTL`, giving the redemption back like failed ones.
+pub(crate) const USAGE_SQL: &str = r#"SELECT
+        COUNT(*) AS total,
+        COUNT(*) FILTER (WHERE r.user_id = $2) AS by_user
+    FROM coupon_redemptions r
+    JOIN orders o ON o.id = r.order_id
+    WHERE r.coupon_id = $1 AND o.status IN ('pending', 'paid', 'refunded')"#;
+
+pub struct PostgresCouponsRepository {
+    pub pool: sqlx::Pool<sqlx::Postgres>,
+}
+
+#[async_trait::async_trait]
+impl CouponsRepository for PostgresCouponsRepository {
+    async fn create(&self, input: CreateCouponRecord) -> AppResult<CouponRecord> {
+        let id: uuid::Uuid = sqlx::query_scalar(
+            r#"INSERT INTO coupons
+                   (code, description, discount_type, percent_off, amount_off_cents, currency,
+                    course_id, category_id, starts_at, expires_at, max_redemptions,
+                    max_redemptions_per_user, active, created_by)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+               RETURNING id"#,
+        )
+        .bind(&input.code)
+        .bind(&input.description)
+        .bind(input.discount_type.as_str())
+        .bind(input.percent_off)
+        .bind(input.amount_off_cents)
+        .bind(&input.currency)
+        .bind(input.course_id)
+        .bind(input.category_id)
+        .bind(input.starts_at)
+        .bind(input.expires_at)
+        .bind(input.max_redemptions)
+        .bind(input.max_redemptions_per_user)
+        .bind(input.active)
+        .bind(input.created_by)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        self.find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::Internal("Created coupon not found".into()))
+    }
+
+    async fn find_by_id(&self, id: uuid::Uuid) -> AppResult<Option<CouponRecord>> {
+        let sql = format!("SELECT {} FROM coupons c WHERE c.id = $1", COUPON_COLUMNS);
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(row.map(map_coupon_row))
+    }
+
+    async fn find_by_code(&self, code: &str) -> AppResult<Option<CouponRecord>> {
+        let sql = format!("SELECT {} FROM coupons c WHERE c.code = $1", COUPON_COLUMNS);
+        let row = sqlx::query(&sql)
+            .bind(code)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(row.map(map_coupon_row))
+    }
+
+    async fn list(&self, limit: i64) -> AppResult<Vec<CouponRecord>> {
+        let sql = format!(
+            "SELECT {} FROM coupons c ORDER BY c.created_at DESC, c.id LIMIT $1",
+            COUPON_COLUMNS
+        );
+        let rows = sqlx::query(&sql)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(rows.into_iter().map(map_coupon_row).collect())
+    }
+
+    async fn update_partial(
+        &self,
+        id: uuid::Uuid,
+        input: UpdateCouponRecord,
+    ) -> AppResult<Option<CouponRecord>> {
+        let updated = sqlx::query(
+            r#"UPDATE coupons SET
+                    description = COALESCE($2, description),
+                    starts_at = COALESCE($3, starts_at),
+                    expires_at = COALESCE($4, expires_at),
+                    max_redemptions = COALESCE($5, max_redemptions),
+                    max_redemptions_per_user = COALESCE($6, max_redemptions_per_user),
+                    active = COALESCE($7, active)
+               WHERE id = $1"#,
+        )
+        .bind(id)
+        .bind(input.description)
+        .bind(input.starts_at)
+        .bind(input.expires_at)
+        .bind(input.max_redemptions)
+        .bind(input.max_redemptions_per_user)
+        .bind(input.active)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        if updated.rows_affected() == 0 {
+            return Ok(None);
+        }
+        self.find_by_id(id).await
+    }
+
+    async fn usage(&self, coupon_id: uuid::Uuid, user_id: uuid::Uuid) -> AppResult<CouponUsage> {
+        let row = sqlx::query(USAGE_SQL)
+            .bind(coupon_id)
+            .bind(user_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(CouponUsage {
+            total: row.get("total"),
+            by_user: row.get("by_user"),
+        })
+    }
+}
+
+fn map_coupon_row(row: sqlx::postgres::PgRow) -> CouponRecord {
+    CouponRecord {
+        id: row.get("id"),
+        code: row.get("code"),
+        description: row.get("description"),
+        discount_type: row.get("discount_type"),
+        percent_off: row.get("percent_off"),
+        amount_off_cents: row.get("amount_off_cents"),
+        currency: row.get("currency"),
+        course_id: row.get("course_id"),
+        category_id: row.get("category_id"),
+        starts_at: row.get("starts_at"),
+        expires_at: row.get("expires_at"),
+        max_redemptions: row.get("max_redemptions"),
+        max_redemptions_per_user: row.get("max_redemptions_per_user"),
+        active: row.get("active"),
+        times_redeemed: row.get("times_redeemed"),
+        created_by: row.get("created_by"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
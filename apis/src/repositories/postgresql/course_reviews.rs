@@ -0,0 +1,257 @@
+use sqlx::Row;
+
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::course_reviews::{
+    CourseReviewRecord, CourseReviewSort, CourseReviewsRepository, CreateCourseReviewRecord,
+    UpdateCourseReviewRecord,
+};
+
+const REVIEW_SELECT: &str = r#"SELECT r.id, r.course_id, r.user_id, r.rating, r.content, r.approved, r.moderated_at,
+           r.instructor_reply, r.replied_by, r.replied_at, r.created_at, r.updated_at,
+           u.username, u.full_name, u.avatar_url,
+           (SELECT COUNT(*) FROM course_review_votes v WHERE v.review_id = r.id) AS helpful_count
+    FROM course_reviews r
+    JOIN users u ON u.id = r.user_id"#;
+
+pub struct PostgresCourseReviewsRepository {
+    pub pool: sqlx::Pool<sqlx::Postgres>,
+}
+
+#[async_trait::async_trait]
+impl CourseReviewsRepository for PostgresCourseReviewsRepository {
+    async fn create(&self, input: CreateCourseReviewRecord) -> AppResult<uuid::Uuid> {
+        let row = sqlx::query(
+            r#"INSERT INTO course_reviews (course_id, user_id, rating, content)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id"#,
+        )
+        .bind(input.course_id)
+        .bind(input.user_id)
+        .bind(input.rating)
+        .bind(input.content)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(row.get("id"))
+    }
+
+    async fn find_by_id(&self, id: uuid::Uuid) -> AppResult<Option<CourseReviewRecord>> {
+        let sql = format!("{} WHERE r.id = $1", REVIEW_SELECT);
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(row.map(map_review_row))
+    }
+
+    async fn find_by_user_and_course(
+        &self,
+        user_id: uuid::Uuid,
+        course_id: uuid::Uuid,
+    ) -> AppResult<Option<CourseReviewRecord>> {
+        let sql = format!(
+            "{} WHERE r.user_id = $1 AND r.course_id = $2",
+            REVIEW_SELECT
+        );
+        let row = sqlx::query(&sql)
+            .bind(user_id)
+            .bind(course_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(row.map(map_review_row))
+    }
+
+    async fn list_approved_by_course(
+        &self,
+        course_id: uuid::Uuid,
+        sort: CourseReviewSort,
+        offset: i64,
+        limit: i64,
+    ) -> AppResult<(Vec<CourseReviewRecord>, i64)> {
+        let total: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM course_reviews WHERE course_id = $1 AND approved",
+        )
+        .bind(course_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        let order_by = match sort {
+            CourseReviewSort::Newest => "r.created_at DESC, r.id",
+            CourseReviewSort::Helpful => "helpful_count DESC, r.created_at DESC, r.id",
+            CourseReviewSort::Highest => "r.rating DESC, r.created_at DESC, r.id",
+            CourseReviewSort::Lowest => "r.rating ASC, r.created_at DESC, r.id",
+        };
+        let sql = format!(
+            r#"{}
+               WHERE r.course_id = $1 AND r.approved
+               ORDER BY {}
+               OFFSET $2 LIMIT $3"#,
+            REVIEW_SELECT, order_by
+        );
+        let rows = sqlx::query(&sql)
+            .bind(course_id)
+            .bind(offset)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok((rows.into_iter().map(map_review_row).collect(), total))
+    }
+
+    async fn rating_distribution(&self, course_id: uuid::Uuid) -> AppResult<Vec<(i16, i64)>> {
+        let rows = sqlx::query(
+            r#"SELECT rating, COUNT(*) AS total
+               FROM course_reviews
+               WHERE course_id = $1 AND approved
+               GROUP BY rating
+               ORDER BY rating DESC"#,
+        )
+        .bind(course_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(rows
+            .into_iter()
+            .map(|r| (r.get("rating"), r.get("total")))
+            .collect())
+    }
+
+    async fn update_by_author(
+        &self,
+        id: uuid::Uuid,
+        input: UpdateCourseReviewRecord,
+    ) -> AppResult<bool> {
+        let result = sqlx::query(
+            r#"UPDATE course_reviews
+               SET rating = COALESCE($2, rating),
+                   content = COALESCE($3, content),
+                   approved = false,
+                   moderated_at = NULL
+               WHERE id = $1"#,
+        )
+        .bind(id)
+        .bind(input.rating)
+        .bind(input.content)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete_by_id(&self, id: uuid::Uuid) -> AppResult<()> {
+        sqlx::query("DELETE FROM course_reviews WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(())
+    }
+
+    async fn set_reply(
+        &self,
+        id: uuid::Uuid,
+        reply: Option<String>,
+        replied_by: Option<uuid::Uuid>,
+    ) -> AppResult<()> {
+        sqlx::query(
+            r#"UPDATE course_reviews
+               SET instructor_reply = $2,
+                   replied_by = $3,
+                   replied_at = CASE WHEN $2::text IS NULL THEN NULL ELSE NOW() END
+               WHERE id = $1"#,
+        )
+        .bind(id)
+        .bind(reply)
+        .bind(replied_by)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(())
+    }
+
+    async fn add_vote(&self, review_id: uuid::Uuid, user_id: uuid::Uuid) -> AppResult<()> {
+        sqlx::query(
+            r#"INSERT INTO course_review_votes (review_id, user_id)
+               VALUES ($1, $2)
+               ON CONFLICT DO NOTHING"#,
+        )
+        .bind(review_id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(())
+    }
+
+    async fn remove_vote(&self, review_id: uuid::Uuid, user_id: uuid::Uuid) -> AppResult<()> {
+        sqlx::query("DELETE FROM course_review_votes WHERE review_id = $1 AND user_id = $2")
+            .bind(review_id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(())
+    }
+
+    async fn approve(&self, id: uuid::Uuid) -> AppResult<()> {
+        sqlx::query(
+            "UPDATE course_reviews SET approved = true, moderated_at = NOW() WHERE id = $1",
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(())
+    }
+
+    async fn reject(&self, id: uuid::Uuid) -> AppResult<()> {
+        sqlx::query(
+            "UPDATE course_reviews SET approved = false, moderated_at = NOW() WHERE id = $1",
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(())
+    }
+
+    async fn list_pending(&self, limit: i64) -> AppResult<Vec<CourseReviewRecord>> {
+        let sql = format!(
+            r#"{}
+               WHERE r.moderated_at IS NULL
+               ORDER BY r.created_at ASC
+               LIMIT $1"#,
+            REVIEW_SELECT
+        );
+        let rows = sqlx::query(&sql)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(rows.into_iter().map(map_review_row).collect())
+    }
+}
+
+fn map_review_row(row: sqlx::postgres::PgRow) -> CourseReviewRecord {
+    CourseReviewRecord {
+        id: row.get("id"),
+        course_id: row.get("course_id"),
+        user_id: row.get("user_id"),
+        rating: row.get("rating"),
+        content: row.get("content"),
+        approved: row.get("approved"),
+        moderated_at: row.get("moderated_at"),
+        instructor_reply: row.get("instructor_reply"),
+        replied_by: row.get("replied_by"),
+        replied_at: row.get("replied_at"),
+        helpful_count: row.get("helpful_count"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+        username: row.get("username"),
+        full_name: row.get("full_name"),
+        avatar_url: row.get("avatar_url"),
+    }
+}
@@ -0,0 +1,460 @@
+use std::collections::{HashMap, HashSet};
+
+use sqlx::Row;
+
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::course_revisions::{
+    CourseRevisionRecord, CourseRevisionsRepository, PublishRevisionData, RevisionContentData,
+};
+use crate::repositories::postgresql::modules::upsert_lesson_questions;
+
+/// Added to live positions while the tree is rearranged so the unique
+/// (parent, position) indexes never see two rows at the same final position
+const POSITION_SHIFT: i32 = 1_000_000;
+
+const REVISION_COLUMNS: &str = "id, course_id, status, base_version, content, created_by, created_at, updated_at, published_at";
+
+pub struct PostgresCourseRevisionsRepository {
+    pub pool: sqlx::Pool<sqlx::Postgres>,
+}
+
+#[async_trait::async_trait]
+impl CourseRevisionsRepository for PostgresCourseRevisionsRepository {
+    async fn content_version(&self, course_id: uuid::Uuid) -> AppResult<Option<i32>> {
+        let row = sqlx::query(r#"SELECT content_version FROM courses WHERE id = $1"#)
+            .bind(course_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(row.map(|r| r.get("content_version")))
+    }
+
+    async fn find_by_id(&self, id: uuid::Uuid) -> AppResult<Option<CourseRevisionRecord>> {
+        let sql = format!(
+            "SELECT {} FROM course_revisions WHERE id = $1",
+            REVISION_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(row.map(map_revision_row))
+    }
+
+    async fn find_open_draft(
+        &self,
+        course_id: uuid::Uuid,
+    ) -> AppResult<Option<CourseRevisionRecord>> {
+        let sql = format!(
+            "SELECT {} FROM course_revisions WHERE course_id = $1 AND status = 'draft'",
+            REVISION_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(course_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(row.map(map_revision_row))
+    }
+
+    async fn create_draft(
+        &self,
+        course_id: uuid::Uuid,
+        created_by: uuid::Uuid,
+        base_version: i32,
+        content: serde_json::Value,
+    ) -> AppResult<CourseRevisionRecord> {
+        let sql = format!(
+            r#"INSERT INTO course_revisions (course_id, created_by, base_version, content)
+               VALUES ($1, $2, $3, $4)
+               RETURNING {}"#,
+            REVISION_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(course_id)
+            .bind(created_by)
+            .bind(base_version)
+            .bind(content)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(map_revision_row(row))
+    }
+
+    async fn update_draft_content(
+        &self,
+        id: uuid::Uuid,
+        content: serde_json::Value,
+    ) -> AppResult<Option<CourseRevisionRecord>> {
+        let sql = format!(
+            r#"UPDATE course_revisions SET content = $2, updated_at = NOW()
+               WHERE id = $1 AND status = 'draft'
+               RETURNING {}"#,
+            REVISION_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .bind(content)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(row.map(map_revision_row))
+    }
+
+    async fn discard(&self, id: uuid::Uuid) -> AppResult<Option<CourseRevisionRecord>> {
+        let sql = format!(
+            r#"UPDATE course_revisions SET status = 'discarded', updated_at = NOW()
+               WHERE id = $1 AND status = 'draft'
+               RETURNING {}"#,
+            REVISION_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(row.map(map_revision_row))
+    }
+
+    async fn publish(
+        &self,
+        id: uuid::Uuid,
+        data: PublishRevisionData,
+    ) -> AppResult<CourseRevisionRecord> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        // Lock the revision, then the course, so concurrent publishes serialize
+        let revision = sqlx::query(
+            r#"SELECT course_id, status, base_version FROM course_revisions
+               WHERE id = $1 FOR UPDATE"#,
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::from)?
+        .ok_or_else(|| AppError::NotFound("Revision not found".into()))?;
+        let course_id: uuid::Uuid = revision.get("course_id");
+        let status: String = revision.get("status");
+        let base_version: i32 = revision.get("base_version");
+        if status != "draft" {
+            return Err(AppError::Conflict(format!(
+                "Revision is {} and can no longer be published",
+                status
+            )));
+        }
+
+        let live_version: i32 =
+            sqlx::query(r#"SELECT content_version FROM courses WHERE id = $1 FOR UPDATE"#)
+                .bind(course_id)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(AppError::from)?
+                .ok_or_else(|| AppError::NotFound("Course not found".into()))?
+                .get("content_version");
+        if live_version != base_version {
+            return Err(AppError::Conflict(
+                "The live course changed since this draft was created; start a new draft".into(),
+            ));
+        }
+
+        let course = &data.course;
+        sqlx::query(
+            r#"UPDATE courses SET
+                    title = $2, description = $3, excerpt = $4, thumbnail = $5,
+                    currency = $6, price_cents = $7, original_price_cents = $8, duration = $9,
+                    outcomes = $10, category = $11, tags = $12,
+                    content_version = content_version + 1
+               WHERE id = $1"#,
+        )
+        .bind(course_id)
+        .bind(&course.title)
+        .bind(&course.description)
+        .bind(&course.excerpt)
+        .bind(&course.thumbnail)
+        .bind(&course.price.currency)
+        .bind(course.price.amount)
+        .bind(course.original_price.as_ref().map(|p| p.amount))
+        .bind(&course.duration)
+        .bind(&course.outcomes)
+        .bind(&course.category)
+        .bind(&course.tags)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+        // The base currency needs no price-list entry of its own
+        sqlx::query(r#"DELETE FROM course_prices WHERE course_id = $1 AND currency = $2"#)
+            .bind(course_id)
+            .bind(&course.price.currency)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+
+        // Ids in the revision only count if they still belong to this course;
+        // anything else is inserted as new content
+        let live_modules: HashSet<uuid::Uuid> =
+            sqlx::query(r#"SELECT id FROM course_modules WHERE course_id = $1"#)
+                .bind(course_id)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(AppError::from)?
+                .into_iter()
+                .map(|r| r.get("id"))
+                .collect();
+        let live_lessons: HashSet<uuid::Uuid> = sqlx::query(
+            r#"SELECT l.id FROM lessons l
+               JOIN course_modules m ON m.id = l.module_id
+               WHERE m.course_id = $1"#,
+        )
+        .bind(course_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(AppError::from)?
+        .into_iter()
+        .map(|r| r.get("id"))
+        .collect();
+
+        let kept_lessons: Vec<uuid::Uuid> = data
+            .modules
+            .iter()
+            .flat_map(|m| m.lessons.iter())
+            .filter_map(|l| l.id.filter(|id| live_lessons.contains(id)))
+            .collect();
+        sqlx::query(
+            r#"DELETE FROM lessons l USING course_modules m
+               WHERE m.id = l.module_id AND m.course_id = $1 AND NOT (l.id = ANY($2))"#,
+        )
+        .bind(course_id)
+        .bind(&kept_lessons)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        // Park remaining rows out of the way before assigning final positions
+        sqlx::query(r#"UPDATE course_modules SET position = position + $2 WHERE course_id = $1"#)
+            .bind(course_id)
+            .bind(POSITION_SHIFT)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+        sqlx::query(
+            r#"UPDATE lessons l SET position = l.position + $2
+               FROM course_modules m
+               WHERE m.id = l.module_id AND m.course_id = $1"#,
+        )
+        .bind(course_id)
+        .bind(POSITION_SHIFT)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        let mut kept_modules: Vec<uuid::Uuid> = Vec::new();
+        for module in &data.modules {
+            let module_id: uuid::Uuid = match module.id.filter(|id| live_modules.contains(id)) {
+                Some(module_id) => {
+                    sqlx::query(
+                        r#"UPDATE course_modules
+                           SET title = $2, description = $3, position = $4
+                           WHERE id = $1"#,
+                    )
+                    .bind(module_id)
+                    .bind(&module.title)
+                    .bind(&module.description)
+                    .bind(module.position)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(AppError::from)?;
+                    module_id
+                }
+                None => sqlx::query(
+                    r#"INSERT INTO course_modules (course_id, title, description, position)
+                       VALUES ($1, $2, $3, $4)
+                       RETURNING id"#,
+                )
+                .bind(course_id)
+                .bind(&module.title)
+                .bind(&module.description)
+                .bind(module.position)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(AppError::from)?
+                .get("id"),
+            };
+            kept_modules.push(module_id);
+
+            for entry in &module.lessons {
+                let lesson = &entry.lesson;
+                let lesson_id: uuid::Uuid = match entry.id.filter(|id| live_lessons.contains(id)) {
+                    Some(lesson_id) => {
+                        sqlx::query(
+                            r#"UPDATE lessons SET
+                                    module_id = $2, title = $3, description = $4, content = $5,
+                                    video_url = $6, duration = $7, position = $8,
+                                    is_free = $9, published = $10, updated_at = NOW()
+                               WHERE id = $1"#,
+                        )
+                        .bind(lesson_id)
+                        .bind(module_id)
+                        .bind(&lesson.title)
+                        .bind(&lesson.description)
+                        .bind(&lesson.content)
+                        .bind(&lesson.video_url)
+                        .bind(&lesson.duration)
+                        .bind(lesson.position)
+                        .bind(lesson.is_free)
+                        .bind(lesson.published)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(AppError::from)?;
+                        lesson_id
+                    }
+                    None => sqlx::query(
+                        r#"INSERT INTO lessons (
+                                module_id, title, description, content, video_url,
+                                duration, position, is_free, published
+                            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                            RETURNING id"#,
+                    )
+                    .bind(module_id)
+                    .bind(&lesson.title)
+                    .bind(&lesson.description)
+                    .bind(&lesson.content)
+                    .bind(&lesson.video_url)
+                    .bind(&lesson.duration)
+                    .bind(lesson.position)
+                    .bind(lesson.is_free)
+                    .bind(lesson.published)
+                    .fetch_one(&mut *tx)
+                    .await
+                    .map_err(AppError::from)?
+                    .get("id"),
+                };
+                sync_lesson_contents(&mut tx, lesson_id, &entry.contents).await?;
+                upsert_lesson_questions(&mut tx, lesson_id, lesson).await?;
+            }
+        }
+
+        // Kept lessons have moved out by now, so this only drops dropped content
+        sqlx::query(r#"DELETE FROM course_modules WHERE course_id = $1 AND NOT (id = ANY($2))"#)
+            .bind(course_id)
+            .bind(&kept_modules)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+
+        let sql = format!(
+            r#"UPDATE course_revisions
+               SET status = 'published', published_at = NOW(), updated_at = NOW()
+               WHERE id = $1
+               RETURNING {}"#,
+            REVISION_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+
+        tx.commit().await.map_err(AppError::from)?;
+        Ok(map_revision_row(row))
+    }
+}
+
+/// Bring a lesson's contents in line with the revision, matching live rows by
+/// id and pruning the rest.
+///
+/// Private files are uploaded to the lesson directly, so a revision may only
+/// keep one as it is: with its live id and storage key, in the same lesson.
+async fn sync_lesson_contents(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    lesson_id: uuid::Uuid,
+    contents: &[RevisionContentData],
+) -> AppResult<()> {
+    let live: HashMap<uuid::Uuid, (bool, String)> =
+        sqlx::query(r#"SELECT id, is_private, url FROM lesson_contents WHERE lesson_id = $1"#)
+            .bind(lesson_id)
+            .fetch_all(&mut **tx)
+            .await
+            .map_err(AppError::from)?
+            .into_iter()
+            .map(|r| (r.get("id"), (r.get("is_private"), r.get("url"))))
+            .collect();
+    for entry in contents {
+        let c = &entry.content;
+        let live_private = entry
+            .id
+            .and_then(|id| live.get(&id))
+            .filter(|(is_private, _)| *is_private);
+        let unchanged = match live_private {
+            Some((_, url)) => c.is_private && c.url == *url,
+            None => !c.is_private,
+        };
+        if !unchanged {
+            return Err(AppError::BadRequest(format!(
+                "Private file '{}' cannot be added, moved or changed in a revision; upload private files to the lesson directly",
+                c.title
+            )));
+        }
+    }
+
+    let kept: Vec<uuid::Uuid> = contents
+        .iter()
+        .filter_map(|c| c.id.filter(|id| live.contains_key(id)))
+        .collect();
+    sqlx::query(r#"DELETE FROM lesson_contents WHERE lesson_id = $1 AND NOT (id = ANY($2))"#)
+        .bind(lesson_id)
+        .bind(&kept)
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::from)?;
+    sqlx::query(r#"UPDATE lesson_contents SET position = position + $2 WHERE lesson_id = $1"#)
+        .bind(lesson_id)
+        .bind(POSITION_SHIFT)
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::from)?;
+
+    for entry in contents {
+        let c = &entry.content;
+        match entry.id.filter(|id| live.contains_key(id)) {
+            Some(content_id) => sqlx::query(
+                r#"UPDATE lesson_contents SET
+                        title = $2, content_type = $3,
+                        url = CASE WHEN is_private THEN url ELSE $4 END,
+                        file_size = $5, filename = $6, position = $7, updated_at = NOW()
+                   WHERE id = $1"#,
+            )
+            .bind(content_id),
+            None => sqlx::query(
+                r#"INSERT INTO lesson_contents (
+                        lesson_id, title, content_type, url, file_size, filename, position
+                    ) VALUES ($1, $2, $3, $4, $5, $6, $7)"#,
+            )
+            .bind(lesson_id),
+        }
+        .bind(&c.title)
+        .bind(&c.content_type)
+        .bind(&c.url)
+        .bind(c.file_size)
+        .bind(&c.filename)
+        .bind(c.position)
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::from)?;
+    }
+    Ok(())
+}
+
+fn map_revision_row(row: sqlx::postgres::PgRow) -> CourseRevisionRecord {
+    CourseRevisionRecord {
+        id: row.get("id"),
+        course_id: row.get("course_id"),
+        status: row.get("status"),
+        base_version: row.get("base_version"),
+        content: row.get("content"),
+        created_by: row.get("created_by"),
+        created_at: row.get("created_at"),
+        updated_at: row.try_get("updated_at").ok(),
+        published_at: row.try_get("published_at").ok(),
+    }
+}
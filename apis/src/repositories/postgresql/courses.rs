@@ -1,9 +1,35 @@
 use sqlx::Row;
 
 use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::money::Money;
 use crate::repositories::courses::{
-    CourseRecord, CoursesRepository, CreateCourseRecord, UpdateCourseRecord,
+    CourseCatalogFilters, CourseCatalogSort, CoursePrerequisiteRecord, CoursePriceRecord, CoursePublishReadiness,
+    CourseRecord, CourseStatus, CoursesRepository, CreateCourseRecord, DuplicateCourseRecord, ImportCourseRecord,
+    UpdateCourseRecord,
 };
+use crate::repositories::postgresql::modules::upsert_lesson_children;
+
+/// Catalogue source rows with `p.price_in_currency`, the course's price in the
+/// requested currency (`$9`): its own price when that is its base currency,
+/// else its price-list entry, NULL when it has none
+const CATALOG_FROM: &str = r#"courses c
+               LEFT JOIN LATERAL (
+                   SELECT CASE WHEN c.currency = $9 THEN c.price_cents
+                               ELSE (SELECT cp.price_cents FROM course_prices cp
+                                      WHERE cp.course_id = c.id AND cp.currency = $9)
+                          END AS price_in_currency
+               ) p ON TRUE"#;
+
+/// Shared WHERE clause of the catalogue list and count queries
+const CATALOG_WHERE: &str = r#"c.status = 'published'
+                 AND ($1::int IS NULL OR EXISTS (SELECT 1 FROM course_categories cc WHERE cc.course_id = c.id AND cc.category_id = $1))
+                 AND (cardinality($2::text[]) = 0 OR c.tags && $2::text[])
+                 AND ($3::bigint IS NULL OR p.price_in_currency >= $3)
+                 AND ($4::bigint IS NULL OR p.price_in_currency <= $4)
+                 AND (NOT $5::boolean OR c.price_cents = 0)
+                 AND ($6::boolean IS NULL OR c.featured = $6)
+                 AND ($7::uuid IS NULL OR c.instructor_id = $7)
+                 AND ($8::float8 IS NULL OR c.rating_average >= $8)"#;
 
 pub struct PostgresCoursesRepository {
     pub pool: sqlx::Pool<sqlx::Postgres>,
@@ -15,9 +41,9 @@ impl CoursesRepository for PostgresCoursesRepository {
         let rec = sqlx::query(
             r#"INSERT INTO courses (
                     slug, title, description, excerpt, thumbnail,
-                    price, original_price, duration, featured, status, instructor_id, outcomes, category, tags
+                    currency, price_cents, original_price_cents, duration, featured, status, instructor_id, outcomes, category, tags
                 ) VALUES (
-                    $1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14
+                    $1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14,$15
                 ) RETURNING slug"#,
         )
         .bind(&input.slug)
@@ -25,8 +51,9 @@ impl CoursesRepository for PostgresCoursesRepository {
         .bind(&input.description)
         .bind(&input.excerpt)
         .bind(&input.thumbnail)
-        .bind(input.price)
-        .bind(&input.original_price)
+        .bind(&input.price.currency)
+        .bind(input.price.amount)
+        .bind(input.original_price.as_ref().map(|p| p.amount))
         .bind(&input.duration)
         .bind(input.featured)
         .bind(&input.status)
@@ -56,10 +83,10 @@ impl CoursesRepository for PostgresCoursesRepository {
 
         let rows = sqlx::query(
             r#"SELECT c.id, c.slug, c.title, c.description, c.excerpt, c.thumbnail,
-                       c.price, c.original_price, c.duration, c.lessons,
-                       c.status, c.featured, c.view_count, c.outcomes, c.category, c.tags,
+                       c.currency, c.price_cents, c.original_price_cents, c.duration, c.lessons,
+                       c.status, c.featured, c.view_count, c.rating_average, c.rating_count, c.outcomes, c.category, c.tags,
                        c.instructor_id,
-                       c.published_at, c.created_at, c.updated_at,
+                       c.published_at, c.scheduled_publish_at, c.created_at, c.updated_at,
                        u.id as instructor_id_join, u.username as instructor_username,
                        u.full_name as instructor_full_name, u.avatar_url as instructor_avatar_url,
                        u.email as instructor_email, u.role as instructor_role
@@ -93,13 +120,102 @@ impl CoursesRepository for PostgresCoursesRepository {
         Ok((items, count_row.0))
     }
 
+    async fn list_published_paginated(
+        &self,
+        filters: CourseCatalogFilters,
+        sort: CourseCatalogSort,
+        offset: i64,
+        limit: i64,
+    ) -> AppResult<(Vec<CourseRecord>, i64)> {
+        // Tie-break on id so pages stay stable when sort keys are equal
+        let order_by = match sort {
+            CourseCatalogSort::Newest => "c.published_at DESC NULLS LAST, c.created_at DESC, c.id",
+            CourseCatalogSort::Popular => "c.view_count DESC, c.published_at DESC NULLS LAST, c.id",
+            // Courses without a price in the currency go last either way
+            CourseCatalogSort::PriceAsc => {
+                "p.price_in_currency ASC NULLS LAST, c.published_at DESC NULLS LAST, c.id"
+            }
+            CourseCatalogSort::PriceDesc => {
+                "p.price_in_currency DESC NULLS LAST, c.published_at DESC NULLS LAST, c.id"
+            }
+            CourseCatalogSort::TopRated => {
+                "c.rating_average DESC, c.rating_count DESC, c.published_at DESC NULLS LAST, c.id"
+            }
+        };
+
+        // Same consistent snapshot approach as the instructor listing
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY")
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+
+        let list_sql = format!(
+            r#"SELECT c.id, c.slug, c.title, c.description, c.excerpt, c.thumbnail,
+                       c.currency, c.price_cents, c.original_price_cents, c.duration, c.lessons,
+                       c.status, c.featured, c.view_count, c.rating_average, c.rating_count, c.outcomes, c.category, c.tags,
+                       c.instructor_id,
+                       c.published_at, c.scheduled_publish_at, c.created_at, c.updated_at,
+                       u.id as instructor_id_join, u.username as instructor_username,
+                       u.full_name as instructor_full_name, u.avatar_url as instructor_avatar_url,
+                       u.email as instructor_email, u.role as instructor_role
+               FROM {}
+               LEFT JOIN users u ON u.id = c.instructor_id
+               WHERE {}
+               ORDER BY {}
+               OFFSET $10 LIMIT $11"#,
+            CATALOG_FROM, CATALOG_WHERE, order_by
+        );
+        let rows = sqlx::query(&list_sql)
+            .bind(filters.category_id)
+            .bind(&filters.tags)
+            .bind(filters.min_price)
+            .bind(filters.max_price)
+            .bind(filters.free_only)
+            .bind(filters.featured)
+            .bind(filters.instructor_id)
+            .bind(filters.min_rating)
+            .bind(&filters.currency)
+            .bind(offset)
+            .bind(limit)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+
+        let count_sql = format!(
+            "SELECT COUNT(*) FROM {} WHERE {}",
+            CATALOG_FROM, CATALOG_WHERE
+        );
+        let count_row: (i64,) = sqlx::query_as(&count_sql)
+            .bind(filters.category_id)
+            .bind(&filters.tags)
+            .bind(filters.min_price)
+            .bind(filters.max_price)
+            .bind(filters.free_only)
+            .bind(filters.featured)
+            .bind(filters.instructor_id)
+            .bind(filters.min_rating)
+            .bind(&filters.currency)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+
+        tx.commit().await.map_err(AppError::from)?;
+
+        let items = rows
+            .into_iter()
+            .map(map_course_row_with_instructor)
+            .collect();
+        Ok((items, count_row.0))
+    }
+
     async fn find_by_id(&self, id: uuid::Uuid) -> AppResult<Option<CourseRecord>> {
         let row = sqlx::query(
             r#"SELECT c.id, c.slug, c.title, c.description, c.excerpt, c.thumbnail,
-                       c.price, c.original_price, c.duration, c.lessons,
-                       c.status, c.featured, c.view_count, c.outcomes, c.category, c.tags,
+                       c.currency, c.price_cents, c.original_price_cents, c.duration, c.lessons,
+                       c.status, c.featured, c.view_count, c.rating_average, c.rating_count, c.outcomes, c.category, c.tags,
                        c.instructor_id,
-                       c.published_at, c.created_at, c.updated_at,
+                       c.published_at, c.scheduled_publish_at, c.created_at, c.updated_at,
                        u.id as instructor_id_join, u.username as instructor_username,
                        u.full_name as instructor_full_name, u.avatar_url as instructor_avatar_url,
                        u.email as instructor_email, u.role as instructor_role
@@ -117,10 +233,10 @@ impl CoursesRepository for PostgresCoursesRepository {
     async fn find_by_slug(&self, slug: &str) -> AppResult<Option<CourseRecord>> {
         let row = sqlx::query(
             r#"SELECT c.id, c.slug, c.title, c.description, c.excerpt, c.thumbnail,
-                       c.price, c.original_price, c.duration, c.lessons,
-                       c.status, c.featured, c.view_count, c.outcomes, c.category, c.tags,
+                       c.currency, c.price_cents, c.original_price_cents, c.duration, c.lessons,
+                       c.status, c.featured, c.view_count, c.rating_average, c.rating_count, c.outcomes, c.category, c.tags,
                        c.instructor_id,
-                       c.published_at, c.created_at, c.updated_at,
+                       c.published_at, c.scheduled_publish_at, c.created_at, c.updated_at,
                        u.id as instructor_id_join, u.username as instructor_username,
                        u.full_name as instructor_full_name, u.avatar_url as instructor_avatar_url,
                        u.email as instructor_email, u.role as instructor_role
@@ -140,14 +256,19 @@ impl CoursesRepository for PostgresCoursesRepository {
         id: uuid::Uuid,
         input: UpdateCourseRecord,
     ) -> AppResult<Option<CourseRecord>> {
+        // A new base currency no longer needs its own price-list entry
         let row = sqlx::query(
-            r#"UPDATE courses SET
+            r#"WITH rebased AS (
+                    DELETE FROM course_prices WHERE course_id = $14 AND currency = $15
+                )
+                UPDATE courses SET
                     title = COALESCE($1, title),
                     description = COALESCE($2, description),
                     excerpt = COALESCE($3, excerpt),
                     thumbnail = COALESCE($4, thumbnail),
-                    price = COALESCE($5, price),
-                    original_price = COALESCE($6, original_price),
+                    price_cents = COALESCE($5, price_cents),
+                    currency = COALESCE($15, currency),
+                    original_price_cents = COALESCE($6, original_price_cents),
                     duration = COALESCE($7, duration),
                     lessons = COALESCE($8, lessons),
                     status = COALESCE($9, status),
@@ -157,16 +278,16 @@ impl CoursesRepository for PostgresCoursesRepository {
                     tags = COALESCE($13, tags)
                 WHERE id = $14
                 RETURNING id, slug, title, description, excerpt, thumbnail,
-                          price, original_price, duration, lessons,
-                          status, featured, view_count, outcomes, category, tags,
-                          instructor_id, published_at, created_at, updated_at"#,
+                          currency, price_cents, original_price_cents, duration, lessons,
+                          status, featured, view_count, rating_average, rating_count, outcomes, category, tags,
+                          instructor_id, published_at, scheduled_publish_at, created_at, updated_at"#,
         )
         .bind(input.title)
         .bind(input.description)
         .bind(input.excerpt)
         .bind(input.thumbnail)
-        .bind(input.price)
-        .bind(input.original_price)
+        .bind(input.price.as_ref().map(|p| p.amount))
+        .bind(input.original_price.as_ref().map(|p| p.amount))
         .bind(input.duration)
         .bind(input.lessons)
         .bind(input.status)
@@ -175,6 +296,7 @@ impl CoursesRepository for PostgresCoursesRepository {
         .bind(input.category)
         .bind(input.tags)
         .bind(id)
+        .bind(input.price.map(|p| p.currency))
         .fetch_optional(&self.pool)
         .await
         .map_err(AppError::from)?;
@@ -186,16 +308,19 @@ impl CoursesRepository for PostgresCoursesRepository {
             description: row.get("description"),
             excerpt: row.try_get("excerpt").ok(),
             thumbnail: row.try_get("thumbnail").ok(),
-            price: row.get("price"),
-            original_price: row.try_get("original_price").ok(),
+            price: price_column(&row, "price_cents"),
+            original_price: optional_price_column(&row, "original_price_cents"),
             duration: row.get("duration"),
             lessons: row.get("lessons"),
             featured: row.get("featured"),
             view_count: row.get("view_count"),
+            rating_average: row.get("rating_average"),
+            rating_count: row.get("rating_count"),
             status: row.get("status"),
             instructor_id: row.get("instructor_id"),
             instructor: None,
             published_at: row.try_get("published_at").ok(),
+            scheduled_publish_at: row.try_get("scheduled_publish_at").ok(),
             outcomes: row.try_get("outcomes").ok(),
             category: row.try_get("category").ok(),
             tags: row.try_get("tags").ok(),
@@ -212,6 +337,587 @@ impl CoursesRepository for PostgresCoursesRepository {
             .map_err(AppError::from)?;
         Ok(())
     }
+
+    async fn publish_readiness(&self, id: uuid::Uuid) -> AppResult<CoursePublishReadiness> {
+        let row = sqlx::query(
+            r#"SELECT
+                    (SELECT COUNT(*) FROM course_modules m WHERE m.course_id = $1) AS module_count,
+                    (SELECT COUNT(*) FROM lessons l
+                       JOIN course_modules m ON m.id = l.module_id
+                      WHERE m.course_id = $1 AND l.published) AS published_lesson_count"#,
+        )
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(CoursePublishReadiness {
+            module_count: row.get("module_count"),
+            published_lesson_count: row.get("published_lesson_count"),
+        })
+    }
+
+    async fn transition_status(
+        &self,
+        id: uuid::Uuid,
+        from: &[CourseStatus],
+        to: CourseStatus,
+    ) -> AppResult<Option<CourseRecord>> {
+        let from: Vec<&str> = from.iter().map(|s| s.as_str()).collect();
+        let row = sqlx::query(
+            r#"UPDATE courses SET
+                    status = $3,
+                    scheduled_publish_at = NULL,
+                    published_at = CASE WHEN $3 = 'published'
+                                        THEN COALESCE(published_at, NOW())
+                                        ELSE published_at END
+                WHERE id = $1 AND status = ANY($2)
+                RETURNING id, slug, title, description, excerpt, thumbnail,
+                          currency, price_cents, original_price_cents, duration, lessons,
+                          status, featured, view_count, rating_average, rating_count, outcomes, category, tags,
+                          instructor_id, published_at, scheduled_publish_at, created_at, updated_at"#,
+        )
+        .bind(id)
+        .bind(&from)
+        .bind(to.as_str())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(row.map(map_course_row_with_instructor))
+    }
+
+    async fn set_scheduled_publish(
+        &self,
+        id: uuid::Uuid,
+        at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> AppResult<Option<CourseRecord>> {
+        let row = sqlx::query(
+            r#"UPDATE courses SET scheduled_publish_at = $2
+                WHERE id = $1 AND status = 'draft'
+                RETURNING id, slug, title, description, excerpt, thumbnail,
+                          currency, price_cents, original_price_cents, duration, lessons,
+                          status, featured, view_count, rating_average, rating_count, outcomes, category, tags,
+                          instructor_id, published_at, scheduled_publish_at, created_at, updated_at"#,
+        )
+        .bind(id)
+        .bind(at)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(row.map(map_course_row_with_instructor))
+    }
+
+    async fn list_due_scheduled(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<uuid::Uuid>> {
+        let rows = sqlx::query(
+            r#"SELECT id FROM courses
+                WHERE status = 'draft' AND scheduled_publish_at IS NOT NULL
+                  AND scheduled_publish_at <= $1
+                ORDER BY scheduled_publish_at
+                LIMIT $2"#,
+        )
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(rows.into_iter().map(|r| r.get("id")).collect())
+    }
+
+    async fn duplicate_deep(
+        &self,
+        source_id: uuid::Uuid,
+        input: DuplicateCourseRecord,
+    ) -> AppResult<Option<uuid::Uuid>> {
+        let (from_media, to_media): (Vec<String>, Vec<String>) =
+            input.media_rewrites.into_iter().unzip();
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        let course_id: uuid::Uuid = match sqlx::query(
+            r#"INSERT INTO courses (
+                    slug, title, description, excerpt, thumbnail,
+                    currency, price_cents, original_price_cents, duration, featured, status, instructor_id,
+                    outcomes, category, tags
+                )
+                SELECT $2, $3, c.description, c.excerpt,
+                       COALESCE((SELECT r.new_url FROM unnest($5::text[], $6::text[]) AS r(old_url, new_url)
+                                  WHERE r.old_url = c.thumbnail), c.thumbnail),
+                       c.currency, c.price_cents, c.original_price_cents, c.duration, FALSE, 'draft', $4,
+                       c.outcomes, c.category, c.tags
+                  FROM courses c
+                 WHERE c.id = $1
+                RETURNING id"#,
+        )
+        .bind(source_id)
+        .bind(&input.slug)
+        .bind(&input.title)
+        .bind(input.instructor_id)
+        .bind(&from_media)
+        .bind(&to_media)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::from)?
+        {
+            Some(row) => row.get("id"),
+            None => return Ok(None),
+        };
+
+        sqlx::query(
+            r#"INSERT INTO course_categories (course_id, category_id)
+                SELECT $2, category_id FROM course_categories WHERE course_id = $1"#,
+        )
+        .bind(source_id)
+        .bind(course_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        sqlx::query(
+            r#"INSERT INTO course_prices (course_id, currency, price_cents, original_price_cents)
+                SELECT $2, currency, price_cents, original_price_cents
+                  FROM course_prices WHERE course_id = $1"#,
+        )
+        .bind(source_id)
+        .bind(course_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        sqlx::query(
+            r#"INSERT INTO course_prerequisites (course_id, required_course_id)
+                SELECT $2, required_course_id
+                  FROM course_prerequisites WHERE course_id = $1"#,
+        )
+        .bind(source_id)
+        .bind(course_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        // Each level returns (source id, copy id) pairs that the next level joins on
+        let module_rows = sqlx::query(
+            r#"WITH src AS (
+                    SELECT id, gen_random_uuid() AS new_id, title, description, position,
+                           drip_days, drip_at
+                      FROM course_modules WHERE course_id = $1
+                ), ins AS (
+                    INSERT INTO course_modules (
+                        id, course_id, title, description, position, drip_days, drip_at
+                    )
+                    SELECT new_id, $2, title, description, position, drip_days, drip_at FROM src
+                )
+                SELECT id, new_id FROM src"#,
+        )
+        .bind(source_id)
+        .bind(course_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+        let (old_modules, new_modules) = id_pairs(module_rows);
+
+        let lesson_rows = sqlx::query(
+            r#"WITH src AS (
+                    SELECT l.id, gen_random_uuid() AS new_id, m.new_id AS new_module_id,
+                           l.title, l.description, l.content, l.video_url, l.duration,
+                           l.position, l.is_free, l.published
+                      FROM lessons l
+                      JOIN unnest($1::uuid[], $2::uuid[]) AS m(old_id, new_id) ON m.old_id = l.module_id
+                ), ins AS (
+                    INSERT INTO lessons (
+                        id, module_id, title, description, content, video_url,
+                        duration, position, is_free, published
+                    )
+                    SELECT new_id, new_module_id, title, description, content,
+                           COALESCE((SELECT r.new_url FROM unnest($3::text[], $4::text[]) AS r(old_url, new_url)
+                                      WHERE r.old_url = src.video_url), src.video_url),
+                           duration, position, is_free, published
+                      FROM src
+                )
+                SELECT id, new_id FROM src"#,
+        )
+        .bind(&old_modules)
+        .bind(&new_modules)
+        .bind(&from_media)
+        .bind(&to_media)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+        let (old_lessons, new_lessons) = id_pairs(lesson_rows);
+
+        sqlx::query(
+            r#"INSERT INTO lesson_contents (
+                    lesson_id, title, content_type, url, file_size, filename, position, is_private
+                )
+                SELECT m.new_id, c.title, c.content_type,
+                       COALESCE((SELECT r.new_url FROM unnest($3::text[], $4::text[]) AS r(old_url, new_url)
+                                  WHERE r.old_url = c.url), c.url),
+                       c.file_size, c.filename, c.position, c.is_private
+                  FROM lesson_contents c
+                  JOIN unnest($1::uuid[], $2::uuid[]) AS m(old_id, new_id) ON m.old_id = c.lesson_id"#,
+        )
+        .bind(&old_lessons)
+        .bind(&new_lessons)
+        .bind(&from_media)
+        .bind(&to_media)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        let question_rows = sqlx::query(
+            r#"WITH src AS (
+                    SELECT q.id, gen_random_uuid() AS new_id, m.new_id AS new_lesson_id,
+                           q.question_text, q.position
+                      FROM lesson_questions q
+                      JOIN unnest($1::uuid[], $2::uuid[]) AS m(old_id, new_id) ON m.old_id = q.lesson_id
+                ), ins AS (
+                    INSERT INTO lesson_questions (id, lesson_id, question_text, position)
+                    SELECT new_id, new_lesson_id, question_text, position FROM src
+                )
+                SELECT id, new_id FROM src"#,
+        )
+        .bind(&old_lessons)
+        .bind(&new_lessons)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+        let (old_questions, new_questions) = id_pairs(question_rows);
+
+        sqlx::query(
+            r#"INSERT INTO question_options (question_id, option_text, is_correct, position)
+                SELECT m.new_id, o.option_text, o.is_correct, o.position
+                  FROM question_options o
+                  JOIN unnest($1::uuid[], $2::uuid[]) AS m(old_id, new_id) ON m.old_id = o.question_id"#,
+        )
+        .bind(&old_questions)
+        .bind(&new_questions)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        sqlx::query(
+            r#"INSERT INTO lesson_assignments (lesson_id, title, description)
+                SELECT m.new_id, a.title, a.description
+                  FROM lesson_assignments a
+                  JOIN unnest($1::uuid[], $2::uuid[]) AS m(old_id, new_id) ON m.old_id = a.lesson_id"#,
+        )
+        .bind(&old_lessons)
+        .bind(&new_lessons)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        sqlx::query(
+            r#"INSERT INTO lesson_subtitles (lesson_id, language, label, vtt, transcript, cue_count)
+                SELECT m.new_id, s.language, s.label, s.vtt, s.transcript, s.cue_count
+                  FROM lesson_subtitles s
+                  JOIN unnest($1::uuid[], $2::uuid[]) AS m(old_id, new_id) ON m.old_id = s.lesson_id"#,
+        )
+        .bind(&old_lessons)
+        .bind(&new_lessons)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        tx.commit().await.map_err(AppError::from)?;
+        Ok(Some(course_id))
+    }
+
+    async fn import_deep(&self, input: ImportCourseRecord) -> AppResult<uuid::Uuid> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        let course = &input.course;
+        let course_id: uuid::Uuid = sqlx::query(
+            r#"INSERT INTO courses (
+                    slug, title, description, excerpt, thumbnail,
+                    currency, price_cents, original_price_cents, duration, featured, status, instructor_id, outcomes, category, tags
+                ) VALUES (
+                    $1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14,$15
+                ) RETURNING id"#,
+        )
+        .bind(&course.slug)
+        .bind(&course.title)
+        .bind(&course.description)
+        .bind(&course.excerpt)
+        .bind(&course.thumbnail)
+        .bind(&course.price.currency)
+        .bind(course.price.amount)
+        .bind(course.original_price.as_ref().map(|p| p.amount))
+        .bind(&course.duration)
+        .bind(course.featured)
+        .bind(&course.status)
+        .bind(course.instructor_id)
+        .bind(&course.outcomes)
+        .bind(&course.category)
+        .bind(&course.tags)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::from)?
+        .get("id");
+
+        for entry in &input.prices {
+            sqlx::query(
+                r#"INSERT INTO course_prices (course_id, currency, price_cents, original_price_cents)
+                   VALUES ($1, $2, $3, $4)"#,
+            )
+            .bind(course_id)
+            .bind(&entry.price.currency)
+            .bind(entry.price.amount)
+            .bind(entry.original_price.as_ref().map(|p| p.amount))
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+        }
+
+        for module in &input.modules {
+            let module_id: uuid::Uuid = sqlx::query(
+                r#"INSERT INTO course_modules (course_id, title, description, position)
+                   VALUES ($1, $2, $3, $4)
+                   RETURNING id"#,
+            )
+            .bind(course_id)
+            .bind(&module.title)
+            .bind(&module.description)
+            .bind(module.position)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(AppError::from)?
+            .get("id");
+
+            for entry in &module.lessons {
+                let lesson = &entry.lesson;
+                let lesson_id: uuid::Uuid = sqlx::query(
+                    r#"INSERT INTO lessons (
+                            module_id, title, description, content, video_url,
+                            duration, position, is_free, published
+                        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                        RETURNING id"#,
+                )
+                .bind(module_id)
+                .bind(&lesson.title)
+                .bind(&lesson.description)
+                .bind(&lesson.content)
+                .bind(&lesson.video_url)
+                .bind(&lesson.duration)
+                .bind(lesson.position)
+                .bind(lesson.is_free)
+                .bind(lesson.published)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(AppError::from)?
+                .get("id");
+                upsert_lesson_children(&mut tx, lesson_id, lesson).await?;
+
+                for subtitle in &entry.subtitles {
+                    sqlx::query(
+                        r#"INSERT INTO lesson_subtitles (lesson_id, language, label, vtt, transcript, cue_count)
+                           VALUES ($1, $2, $3, $4, $5, $6)"#,
+                    )
+                    .bind(lesson_id)
+                    .bind(&subtitle.language)
+                    .bind(&subtitle.label)
+                    .bind(&subtitle.vtt)
+                    .bind(&subtitle.transcript)
+                    .bind(subtitle.cue_count)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(AppError::from)?;
+                }
+            }
+        }
+
+        tx.commit().await.map_err(AppError::from)?;
+        Ok(course_id)
+    }
+
+    async fn list_prices(&self, course_id: uuid::Uuid) -> AppResult<Vec<CoursePriceRecord>> {
+        let rows = sqlx::query(
+            r#"SELECT course_id, currency, price_cents, original_price_cents, created_at, updated_at
+                 FROM course_prices
+                WHERE course_id = $1
+                ORDER BY currency"#,
+        )
+        .bind(course_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(rows.into_iter().map(map_course_price_row).collect())
+    }
+
+    async fn find_price(
+        &self,
+        course_id: uuid::Uuid,
+        currency: &str,
+    ) -> AppResult<Option<CoursePriceRecord>> {
+        let row = sqlx::query(
+            r#"SELECT course_id, currency, price_cents, original_price_cents, created_at, updated_at
+                 FROM course_prices
+                WHERE course_id = $1 AND currency = $2"#,
+        )
+        .bind(course_id)
+        .bind(currency)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(row.map(map_course_price_row))
+    }
+
+    async fn upsert_price(
+        &self,
+        course_id: uuid::Uuid,
+        price: Money,
+        original_price: Option<Money>,
+    ) -> AppResult<CoursePriceRecord> {
+        let row = sqlx::query(
+            r#"INSERT INTO course_prices (course_id, currency, price_cents, original_price_cents)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT (course_id, currency) DO UPDATE
+                  SET price_cents = EXCLUDED.price_cents,
+                      original_price_cents = EXCLUDED.original_price_cents,
+                      updated_at = NOW()
+               RETURNING course_id, currency, price_cents, original_price_cents, created_at, updated_at"#,
+        )
+        .bind(course_id)
+        .bind(&price.currency)
+        .bind(price.amount)
+        .bind(original_price.map(|p| p.amount))
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(map_course_price_row(row))
+    }
+
+    async fn delete_price(&self, course_id: uuid::Uuid, currency: &str) -> AppResult<bool> {
+        let result =
+            sqlx::query(r#"DELETE FROM course_prices WHERE course_id = $1 AND currency = $2"#)
+                .bind(course_id)
+                .bind(currency)
+                .execute(&self.pool)
+                .await
+                .map_err(AppError::from)?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_prerequisites(
+        &self,
+        course_id: uuid::Uuid,
+    ) -> AppResult<Vec<CoursePrerequisiteRecord>> {
+        let rows = sqlx::query(
+            r#"SELECT c.id, c.slug, c.title
+                 FROM course_prerequisites p
+                 JOIN courses c ON c.id = p.required_course_id
+                WHERE p.course_id = $1
+                ORDER BY c.title, c.id"#,
+        )
+        .bind(course_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(rows.into_iter().map(map_prerequisite_row).collect())
+    }
+
+    async fn set_prerequisites(
+        &self,
+        course_id: uuid::Uuid,
+        required_course_ids: &[uuid::Uuid],
+    ) -> AppResult<()> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+        sqlx::query("DELETE FROM course_prerequisites WHERE course_id = $1")
+            .bind(course_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+        sqlx::query(
+            r#"INSERT INTO course_prerequisites (course_id, required_course_id)
+               SELECT $1, UNNEST($2::uuid[])"#,
+        )
+        .bind(course_id)
+        .bind(required_course_ids)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+        tx.commit().await.map_err(AppError::from)?;
+        Ok(())
+    }
+
+    async fn list_dependent_ids(&self, course_id: uuid::Uuid) -> AppResult<Vec<uuid::Uuid>> {
+        let ids = sqlx::query_scalar(
+            r#"WITH RECURSIVE dependents(id) AS (
+                    SELECT course_id FROM course_prerequisites WHERE required_course_id = $1
+                    UNION
+                    SELECT p.course_id FROM course_prerequisites p
+                      JOIN dependents d ON p.required_course_id = d.id
+                )
+                SELECT id FROM dependents"#,
+        )
+        .bind(course_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(ids)
+    }
+
+    async fn unmet_prerequisites(
+        &self,
+        course_id: uuid::Uuid,
+        user_id: uuid::Uuid,
+    ) -> AppResult<Vec<CoursePrerequisiteRecord>> {
+        let rows = sqlx::query(
+            r#"SELECT c.id, c.slug, c.title
+                 FROM course_prerequisites p
+                 JOIN courses c ON c.id = p.required_course_id
+                WHERE p.course_id = $1
+                  AND NOT EXISTS (
+                      SELECT 1 FROM certificates ce
+                       WHERE ce.user_id = $2 AND ce.course_id = p.required_course_id
+                  )
+                ORDER BY c.title, c.id"#,
+        )
+        .bind(course_id)
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(rows.into_iter().map(map_prerequisite_row).collect())
+    }
+}
+
+fn map_prerequisite_row(row: sqlx::postgres::PgRow) -> CoursePrerequisiteRecord {
+    CoursePrerequisiteRecord {
+        course_id: row.get("id"),
+        slug: row.get("slug"),
+        title: row.get("title"),
+    }
+}
+
+
+
+/// Split `id, new_id` rows into parallel source and copy id vectors
+fn id_pairs(rows: Vec<sqlx::postgres::PgRow>) -> (Vec<uuid::Uuid>, Vec<uuid::Uuid>) {
+    rows.into_iter()
+        .map(|r| (r.get::<uuid::Uuid, _>("id"), r.get::<uuid::Uuid, _>("new_id")))
+        .unzip()
+}
+
+/// Amount column in the row's `currency`
+fn price_column(row: &sqlx::postgres::PgRow, column: &str) -> Money {
+    Money::new(row.get(column), row.get::<String, _>("currency"))
+}
+
+fn optional_price_column(row: &sqlx::postgres::PgRow, column: &str) -> Option<Money> {
+    row.get::<Option<i64>, _>(column)
+        .map(|amount| Money::new(amount, row.get::<String, _>("currency")))
+}
+
+fn map_course_price_row(row: sqlx::postgres::PgRow) -> CoursePriceRecord {
+    CoursePriceRecord {
+        course_id: row.get("course_id"),
+        price: price_column(&row, "price_cents"),
+        original_price: optional_price_column(&row, "original_price_cents"),
+        created_at: row.get("created_at"),
+        updated_at: row.try_get("updated_at").ok(),
+    }
 }
 
 fn map_course_row_with_instructor(row: sqlx::postgres::PgRow) -> CourseRecord {
@@ -251,12 +957,14 @@ fn map_course_row_with_instructor(row: sqlx::postgres::PgRow) -> CourseRecord {
         description: row.get("description"),
         excerpt: row.try_get("excerpt").ok(),
         thumbnail: row.try_get("thumbnail").ok(),
-        price: row.get("price"),
-        original_price: row.try_get("original_price").ok(),
+        price: price_column(&row, "price_cents"),
+        original_price: optional_price_column(&row, "original_price_cents"),
         duration: row.get("duration"),
         lessons: row.get("lessons"),
         featured: row.get("featured"),
         view_count: row.get("view_count"),
+        rating_average: row.get("rating_average"),
+        rating_count: row.get("rating_count"),
         status: row.get("status"),
         outcomes: row.try_get("outcomes").ok(),
         category: row.try_get("category").ok(),
@@ -264,6 +972,7 @@ fn map_course_row_with_instructor(row: sqlx::postgres::PgRow) -> CourseRecord {
         instructor_id: row.get("instructor_id"),
         instructor,
         published_at: row.try_get("published_at").ok(),
+        scheduled_publish_at: row.try_get("scheduled_publish_at").ok(),
         created_at: row.get("created_at"),
         updated_at: row.try_get("updated_at").ok(),
     }
@@ -0,0 +1,158 @@
+use sqlx::Row;
+
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::enrollments::{
+    CreateEnrollmentRecord, EnrollmentRecord, EnrollmentsRepository, PathEnrollmentRecord,
+};
+
+const PATH_ENROLLMENT_COLUMNS: &str =
+    "id, user_id, path_id, order_id, status, enrolled_at, revoked_at";
+
+pub struct PostgresEnrollmentsRepository {
+    pub pool: sqlx::Pool<sqlx::Postgres>,
+}
+
+#[async_trait::async_trait]
+impl EnrollmentsRepository for PostgresEnrollmentsRepository {
+    async fn create(&self, input: CreateEnrollmentRecord) -> AppResult<EnrollmentRecord> {
+        let row = sqlx::query(
+            r#"INSERT INTO enrollments (user_id, course_id)
+               VALUES ($1, $2)
+               ON CONFLICT (user_id, course_id)
+               DO UPDATE SET status = 'active', enrolled_at = NOW(), revoked_at = NULL
+               RETURNING id, user_id, course_id, status, enrolled_at, revoked_at, created_at, updated_at"#,
+        )
+        .bind(input.user_id)
+        .bind(input.course_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(map_row(row))
+    }
+
+    async fn find_by_user_and_course(
+        &self,
+        user_id: uuid::Uuid,
+        course_id: uuid::Uuid,
+    ) -> AppResult<Option<EnrollmentRecord>> {
+        let row = sqlx::query(
+            r#"SELECT id, user_id, course_id, status, enrolled_at, revoked_at, created_at, updated_at
+               FROM enrollments WHERE user_id = $1 AND course_id = $2"#,
+        )
+        .bind(user_id)
+        .bind(course_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(row.map(map_row))
+    }
+
+    async fn is_enrolled(&self, user_id: uuid::Uuid, course_id: uuid::Uuid) -> AppResult<bool> {
+        let exists = sqlx::query_scalar::<_, bool>(
+            r#"SELECT EXISTS(
+                   SELECT 1 FROM enrollments
+                   WHERE user_id = $1 AND course_id = $2 AND status = 'active'
+               )"#,
+        )
+        .bind(user_id)
+        .bind(course_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(exists)
+    }
+
+    async fn revoke(&self, user_id: uuid::Uuid, course_id: uuid::Uuid) -> AppResult<bool> {
+        let result = sqlx::query(
+            r#"UPDATE enrollments SET status = 'revoked', revoked_at = NOW()
+               WHERE user_id = $1 AND course_id = $2 AND status = 'active'"#,
+        )
+        .bind(user_id)
+        .bind(course_id)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn enroll_in_path(
+        &self,
+        user_id: uuid::Uuid,
+        path_id: uuid::Uuid,
+        order_id: Option<uuid::Uuid>,
+    ) -> AppResult<PathEnrollmentRecord> {
+        let sql = format!(
+            r#"INSERT INTO learning_path_enrollments (user_id, path_id, order_id)
+               VALUES ($1, $2, $3)
+               ON CONFLICT (user_id, path_id)
+               DO UPDATE SET status = 'active', enrolled_at = NOW(), revoked_at = NULL,
+                             order_id = COALESCE(EXCLUDED.order_id, learning_path_enrollments.order_id)
+               RETURNING {}"#,
+            PATH_ENROLLMENT_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(user_id)
+            .bind(path_id)
+            .bind(order_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(map_path_row(row))
+    }
+
+    async fn find_path_enrollment(
+        &self,
+        user_id: uuid::Uuid,
+        path_id: uuid::Uuid,
+    ) -> AppResult<Option<PathEnrollmentRecord>> {
+        let sql = format!(
+            "SELECT {} FROM learning_path_enrollments WHERE user_id = $1 AND path_id = $2",
+            PATH_ENROLLMENT_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(user_id)
+            .bind(path_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(row.map(map_path_row))
+    }
+
+    async fn revoke_path(&self, user_id: uuid::Uuid, path_id: uuid::Uuid) -> AppResult<bool> {
+        let result = sqlx::query(
+            r#"UPDATE learning_path_enrollments SET status = 'revoked', revoked_at = NOW()
+               WHERE user_id = $1 AND path_id = $2 AND status = 'active'"#,
+        )
+        .bind(user_id)
+        .bind(path_id)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+fn map_row(row: sqlx::postgres::PgRow) -> EnrollmentRecord {
+    EnrollmentRecord {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        course_id: row.get("course_id"),
+        status: row.get("status"),
+        enrolled_at: row.get("enrolled_at"),
+        revoked_at: row.get("revoked_at"),
+        created_at: row.get("created_at"),
+        updated_at: row.try_get("updated_at").ok(),
+    }
+}
+
+fn map_path_row(row: sqlx::postgres::PgRow) -> PathEnrollmentRecord {
+    PathEnrollmentRecord {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        path_id: row.get("path_id"),
+        order_id: row.get("order_id"),
+        status: row.get("status"),
+        enrolled_at: row.get("enrolled_at"),
+        revoked_at: row.get("revoked_at"),
+    }
+}
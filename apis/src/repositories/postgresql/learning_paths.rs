@@ -0,0 +1,234 @@
+use sqlx::Row;
+
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::money::Money;
+use crate::repositories::learning_paths::{
+    CreateLearningPathRecord, LearningPathRecord, LearningPathStatus, LearningPathsRepository,
+    UpdateLearningPathRecord,
+};
+
+const PATH_COLUMNS: &str = r#"p.id, p.slug, p.title, p.description, p.excerpt, p.thumbnail,
+    p.status, p.price_cents, p.currency, p.created_by, p.published_at, p.created_at,
+    p.updated_at,
+    (SELECT COUNT(*) FROM learning_path_courses lc WHERE lc.path_id = p.id) AS course_count"#;
+
+pub struct PostgresLearningPathsRepository {
+    pub pool: sqlx::Pool<sqlx::Postgres>,
+}
+
+#[async_trait::async_trait]
+impl LearningPathsRepository for PostgresLearningPathsRepository {
+    async fn create(&self, input: CreateLearningPathRecord) -> AppResult<LearningPathRecord> {
+        let id: uuid::Uuid = sqlx::query_scalar(
+            r#"INSERT INTO learning_paths
+                   (slug, title, description, excerpt, thumbnail, price_cents, currency, created_by)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+               RETURNING id"#,
+        )
+        .bind(&input.slug)
+        .bind(&input.title)
+        .bind(&input.description)
+        .bind(&input.excerpt)
+        .bind(&input.thumbnail)
+        .bind(input.price.amount)
+        .bind(&input.price.currency)
+        .bind(input.created_by)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        self.find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::Internal("Created learning path not found".into()))
+    }
+
+    async fn find_by_id(&self, id: uuid::Uuid) -> AppResult<Option<LearningPathRecord>> {
+        let sql = format!(
+            "SELECT {} FROM learning_paths p WHERE p.id = $1",
+            PATH_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(row.map(map_path_row))
+    }
+
+    async fn find_by_slug(&self, slug: &str) -> AppResult<Option<LearningPathRecord>> {
+        let sql = format!(
+            "SELECT {} FROM learning_paths p WHERE p.slug = $1",
+            PATH_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(slug)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(row.map(map_path_row))
+    }
+
+    async fn list_paginated(
+        &self,
+        status: Option<LearningPathStatus>,
+        offset: i64,
+        limit: i64,
+    ) -> AppResult<(Vec<LearningPathRecord>, i64)> {
+        let status = status.map(LearningPathStatus::as_str);
+        let sql = format!(
+            r#"SELECT {} FROM learning_paths p
+               WHERE ($1::text IS NULL OR p.status = $1)
+               ORDER BY COALESCE(p.published_at, p.created_at) DESC, p.id
+               LIMIT $2 OFFSET $3"#,
+            PATH_COLUMNS
+        );
+        let rows = sqlx::query(&sql)
+            .bind(status)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        let total: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM learning_paths WHERE ($1::text IS NULL OR status = $1)",
+        )
+        .bind(status)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok((rows.into_iter().map(map_path_row).collect(), total))
+    }
+
+    async fn update_partial(
+        &self,
+        id: uuid::Uuid,
+        input: UpdateLearningPathRecord,
+    ) -> AppResult<Option<LearningPathRecord>> {
+        let status = input.status.map(LearningPathStatus::as_str);
+        let updated = sqlx::query(
+            r#"UPDATE learning_paths SET
+                    title = COALESCE($2, title),
+                    description = COALESCE($3, description),
+                    excerpt = COALESCE($4, excerpt),
+                    thumbnail = COALESCE($5, thumbnail),
+                    price_cents = COALESCE($6, price_cents),
+                    currency = COALESCE($7, currency),
+                    status = COALESCE($8, status),
+                    published_at = CASE
+                        WHEN $8 = 'published' THEN COALESCE(published_at, NOW())
+                        ELSE published_at END
+               WHERE id = $1"#,
+        )
+        .bind(id)
+        .bind(input.title)
+        .bind(input.description)
+        .bind(input.excerpt)
+        .bind(input.thumbnail)
+        .bind(input.price.as_ref().map(|p| p.amount))
+        .bind(input.price.as_ref().map(|p| p.currency.as_str()))
+        .bind(status)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        if updated.rows_affected() == 0 {
+            return Ok(None);
+        }
+        self.find_by_id(id).await
+    }
+
+    async fn delete_by_id(&self, id: uuid::Uuid) -> AppResult<bool> {
+        let deleted = sqlx::query("DELETE FROM learning_paths WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(deleted.rows_affected() > 0)
+    }
+
+    async fn list_course_ids(&self, path_id: uuid::Uuid) -> AppResult<Vec<uuid::Uuid>> {
+        let ids = sqlx::query_scalar(
+            r#"SELECT course_id FROM learning_path_courses
+               WHERE path_id = $1 ORDER BY position"#,
+        )
+        .bind(path_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(ids)
+    }
+
+    async fn set_courses(&self, path_id: uuid::Uuid, course_ids: &[uuid::Uuid]) -> AppResult<()> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+        sqlx::query("DELETE FROM learning_path_courses WHERE path_id = $1")
+            .bind(path_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+        sqlx::query(
+            r#"INSERT INTO learning_path_courses (path_id, course_id, position)
+               SELECT $1, c.id, c.position - 1
+               FROM UNNEST($2::uuid[]) WITH ORDINALITY AS c(id, position)"#,
+        )
+        .bind(path_id)
+        .bind(course_ids)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+        tx.commit().await.map_err(AppError::from)?;
+        Ok(())
+    }
+
+    async fn list_prerequisite_ids(&self, path_id: uuid::Uuid) -> AppResult<Vec<uuid::Uuid>> {
+        let ids = sqlx::query_scalar(
+            r#"SELECT p.course_id FROM learning_path_prerequisites p
+               JOIN courses c ON c.id = p.course_id
+               WHERE p.path_id = $1 ORDER BY c.title, c.id"#,
+        )
+        .bind(path_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(ids)
+    }
+
+    async fn set_prerequisites(
+        &self,
+        path_id: uuid::Uuid,
+        course_ids: &[uuid::Uuid],
+    ) -> AppResult<()> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+        sqlx::query("DELETE FROM learning_path_prerequisites WHERE path_id = $1")
+            .bind(path_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+        sqlx::query(
+            r#"INSERT INTO learning_path_prerequisites (path_id, course_id)
+               SELECT $1, UNNEST($2::uuid[])"#,
+        )
+        .bind(path_id)
+        .bind(course_ids)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+        tx.commit().await.map_err(AppError::from)?;
+        Ok(())
+    }
+}
+
+fn map_path_row(row: sqlx::postgres::PgRow) -> LearningPathRecord {
+    LearningPathRecord {
+        id: row.get("id"),
+        slug: row.get("slug"),
+        title: row.get("title"),
+        description: row.get("description"),
+        excerpt: row.get("excerpt"),
+        thumbnail: row.get("thumbnail"),
+        status: row.get("status"),
+        price: Money::new(row.get("price_cents"), row.get::<String, _>("currency")),
+        course_count: row.get("course_count"),
+        created_by: row.get("created_by"),
+        published_at: row.get("published_at"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
@@ -15,9 +15,9 @@ impl LessonContentsRepository for PostgresLessonContentsRepository {
     async fn create(&self, input: CreateLessonContentRecord) -> AppResult<uuid::Uuid> {
         let rec = sqlx::query(
             r#"INSERT INTO lesson_contents (
-                    lesson_id, title, content_type, url, file_size, filename, position
+                    lesson_id, title, content_type, url, file_size, filename, position, is_private
                 ) VALUES (
-                    $1,$2,$3,$4,$5,$6,$7
+                    $1,$2,$3,$4,$5,$6,$7,$8
                 ) RETURNING id"#,
         )
         .bind(input.lesson_id)
@@ -27,15 +27,28 @@ impl LessonContentsRepository for PostgresLessonContentsRepository {
         .bind(input.file_size)
         .bind(&input.filename)
         .bind(input.position)
+        .bind(input.is_private)
         .fetch_one(&self.pool)
         .await
         .map_err(AppError::from)?;
         Ok(rec.get("id"))
     }
 
+    async fn find_by_id(&self, id: uuid::Uuid) -> AppResult<Option<LessonContentRecord>> {
+        let row = sqlx::query(
+            r#"SELECT id, lesson_id, title, content_type, url, file_size, filename, position, is_private, created_at, updated_at
+               FROM lesson_contents WHERE id = $1"#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(row.map(map_row))
+    }
+
     async fn list_by_lesson(&self, lesson_id: uuid::Uuid) -> AppResult<Vec<LessonContentRecord>> {
         let rows = sqlx::query(
-            r#"SELECT id, lesson_id, title, content_type, url, file_size, filename, position, created_at, updated_at
+            r#"SELECT id, lesson_id, title, content_type, url, file_size, filename, position, is_private, created_at, updated_at
                FROM lesson_contents WHERE lesson_id = $1 ORDER BY position ASC, created_at ASC"#,
         )
         .bind(lesson_id)
@@ -54,12 +67,12 @@ impl LessonContentsRepository for PostgresLessonContentsRepository {
             r#"UPDATE lesson_contents SET
                     title = COALESCE($1, title),
                     content_type = COALESCE($2, content_type),
-                    url = COALESCE($3, url),
+                    url = CASE WHEN is_private THEN url ELSE COALESCE($3, url) END,
                     file_size = COALESCE($4, file_size),
                     filename = COALESCE($5, filename),
                     position = COALESCE($6, position)
                WHERE id = $7
-               RETURNING id, lesson_id, title, content_type, url, file_size, filename, position, created_at, updated_at"#,
+               RETURNING id, lesson_id, title, content_type, url, file_size, filename, position, is_private, created_at, updated_at"#,
         )
         .bind(input.title)
         .bind(input.content_type)
@@ -95,6 +108,7 @@ fn map_row(row: sqlx::postgres::PgRow) -> LessonContentRecord {
         file_size: row.try_get("file_size").ok(),
         filename: row.try_get("filename").ok(),
         position: row.get("position"),
+        is_private: row.get("is_private"),
         created_at: row.get("created_at"),
         updated_at: row.try_get("updated_at").ok(),
     }
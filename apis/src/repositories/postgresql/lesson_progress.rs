@@ -0,0 +1,118 @@
+use sqlx::Row;
+
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::lesson_progress::{
+    CourseProgressRecord, CreateQuizAttemptRecord, LessonProgressRepository, QuizAttemptRecord,
+};
+
+pub struct PostgresLessonProgressRepository {
+    pub pool: sqlx::Pool<sqlx::Postgres>,
+}
+
+#[async_trait::async_trait]
+impl LessonProgressRepository for PostgresLessonProgressRepository {
+    async fn mark_completed(
+        &self,
+        user_id: uuid::Uuid,
+        lesson_id: uuid::Uuid,
+        course_id: uuid::Uuid,
+    ) -> AppResult<()> {
+        sqlx::query(
+            r#"INSERT INTO lesson_completions (user_id, lesson_id, course_id)
+               VALUES ($1, $2, $3)
+               ON CONFLICT DO NOTHING"#,
+        )
+        .bind(user_id)
+        .bind(lesson_id)
+        .bind(course_id)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(())
+    }
+
+    async fn create_quiz_attempt(
+        &self,
+        input: CreateQuizAttemptRecord,
+    ) -> AppResult<QuizAttemptRecord> {
+        let row = sqlx::query(
+            r#"INSERT INTO quiz_attempts (user_id, lesson_id, course_id, correct, total, score, passed)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id, user_id, lesson_id, course_id, correct, total, score, passed, created_at"#,
+        )
+        .bind(input.user_id)
+        .bind(input.lesson_id)
+        .bind(input.course_id)
+        .bind(input.correct)
+        .bind(input.total)
+        .bind(input.score)
+        .bind(input.passed)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(QuizAttemptRecord {
+            id: row.get("id"),
+            user_id: row.get("user_id"),
+            lesson_id: row.get("lesson_id"),
+            course_id: row.get("course_id"),
+            correct: row.get("correct"),
+            total: row.get("total"),
+            score: row.get("score"),
+            passed: row.get("passed"),
+            created_at: row.get("created_at"),
+        })
+    }
+
+    async fn has_passed_quiz(&self, user_id: uuid::Uuid, lesson_id: uuid::Uuid) -> AppResult<bool> {
+        sqlx::query_scalar::<_, bool>(
+            r#"SELECT EXISTS(
+                   SELECT 1 FROM quiz_attempts WHERE user_id = $1 AND lesson_id = $2 AND passed
+               )"#,
+        )
+        .bind(user_id)
+        .bind(lesson_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)
+    }
+
+    async fn course_progress(
+        &self,
+        user_id: uuid::Uuid,
+        course_id: uuid::Uuid,
+    ) -> AppResult<CourseProgressRecord> {
+        let rows = sqlx::query(
+            r#"SELECT l.id,
+                      EXISTS(SELECT 1 FROM lesson_completions lc
+                             WHERE lc.user_id = $1 AND lc.lesson_id = l.id) AS completed,
+                      EXISTS(SELECT 1 FROM lesson_questions q WHERE q.lesson_id = l.id) AS has_quiz,
+                      EXISTS(SELECT 1 FROM quiz_attempts a
+                             WHERE a.user_id = $1 AND a.lesson_id = l.id AND a.passed) AS quiz_passed
+               FROM lessons l
+               JOIN course_modules m ON m.id = l.module_id
+               WHERE m.course_id = $2 AND l.published"#,
+        )
+        .bind(user_id)
+        .bind(course_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        let mut progress = CourseProgressRecord {
+            total_lessons: rows.len() as i64,
+            ..Default::default()
+        };
+        for row in rows {
+            if row.get::<bool, _>("completed") {
+                progress.completed_lesson_ids.push(row.get("id"));
+            }
+            if row.get::<bool, _>("has_quiz") {
+                progress.quiz_lessons += 1;
+                if row.get::<bool, _>("quiz_passed") {
+                    progress.passed_quiz_lessons += 1;
+                }
+            }
+        }
+        Ok(progress)
+    }
+}
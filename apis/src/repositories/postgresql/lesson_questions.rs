@@ -42,6 +42,18 @@ impl LessonQuestionsRepository for PostgresLessonQuestionsRepository {
         Ok(rows.into_iter().map(map_question_row).collect())
     }
 
+    async fn find_question(&self, id: uuid::Uuid) -> AppResult<Option<LessonQuestionRecord>> {
+        let row = sqlx::query(
+            r#"SELECT id, lesson_id, question_text, position, created_at, updated_at
+               FROM lesson_questions WHERE id = $1"#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(row.map(map_question_row))
+    }
+
     async fn update_question(
         &self,
         id: uuid::Uuid,
@@ -102,6 +114,18 @@ impl LessonQuestionsRepository for PostgresLessonQuestionsRepository {
         Ok(rows.into_iter().map(map_option_row).collect())
     }
 
+    async fn find_option(&self, id: uuid::Uuid) -> AppResult<Option<QuestionOptionRecord>> {
+        let row = sqlx::query(
+            r#"SELECT id, question_id, option_text, is_correct, position, created_at, updated_at
+               FROM question_options WHERE id = $1"#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(row.map(map_option_row))
+    }
+
     async fn update_option(
         &self,
         id: uuid::Uuid,
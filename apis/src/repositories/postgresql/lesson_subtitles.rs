@@ -0,0 +1,124 @@
+use sqlx::Row;
+
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::lesson_subtitles::{
+    LessonSubtitleRecord, LessonSubtitlesRepository, TranscriptMatchRecord,
+    UpsertLessonSubtitleRecord,
+};
+
+pub struct PostgresLessonSubtitlesRepository {
+    pub pool: sqlx::Pool<sqlx::Postgres>,
+}
+
+#[async_trait::async_trait]
+impl LessonSubtitlesRepository for PostgresLessonSubtitlesRepository {
+    async fn upsert(&self, input: UpsertLessonSubtitleRecord) -> AppResult<LessonSubtitleRecord> {
+        let row = sqlx::query(
+            r#"INSERT INTO lesson_subtitles (lesson_id, language, label, vtt, transcript, cue_count)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               ON CONFLICT (lesson_id, language)
+               DO UPDATE SET
+                   label = EXCLUDED.label,
+                   vtt = EXCLUDED.vtt,
+                   transcript = EXCLUDED.transcript,
+                   cue_count = EXCLUDED.cue_count
+               RETURNING id, lesson_id, language, label, vtt, transcript, cue_count, created_at, updated_at"#,
+        )
+        .bind(input.lesson_id)
+        .bind(&input.language)
+        .bind(&input.label)
+        .bind(&input.vtt)
+        .bind(&input.transcript)
+        .bind(input.cue_count)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(map_row(row))
+    }
+
+    async fn list_by_lesson(&self, lesson_id: uuid::Uuid) -> AppResult<Vec<LessonSubtitleRecord>> {
+        let rows = sqlx::query(
+            r#"SELECT id, lesson_id, language, label, vtt, transcript, cue_count, created_at, updated_at
+               FROM lesson_subtitles WHERE lesson_id = $1 ORDER BY language ASC"#,
+        )
+        .bind(lesson_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(rows.into_iter().map(map_row).collect())
+    }
+
+    async fn find(
+        &self,
+        lesson_id: uuid::Uuid,
+        language: &str,
+    ) -> AppResult<Option<LessonSubtitleRecord>> {
+        let row = sqlx::query(
+            r#"SELECT id, lesson_id, language, label, vtt, transcript, cue_count, created_at, updated_at
+               FROM lesson_subtitles WHERE lesson_id = $1 AND language = $2"#,
+        )
+        .bind(lesson_id)
+        .bind(language)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(row.map(map_row))
+    }
+
+    async fn delete(&self, lesson_id: uuid::Uuid, language: &str) -> AppResult<bool> {
+        let result =
+            sqlx::query(r#"DELETE FROM lesson_subtitles WHERE lesson_id = $1 AND language = $2"#)
+                .bind(lesson_id)
+                .bind(language)
+                .execute(&self.pool)
+                .await
+                .map_err(AppError::from)?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn search_transcripts(
+        &self,
+        query: &str,
+        limit: i64,
+    ) -> AppResult<Vec<TranscriptMatchRecord>> {
+        let rows = sqlx::query(
+            r#"SELECT m.course_id, s.lesson_id, s.language,
+                      ts_rank(s.transcript_tsv, q) AS rank
+               FROM lesson_subtitles s
+               JOIN lessons l ON l.id = s.lesson_id
+               JOIN course_modules m ON m.id = l.module_id,
+                    plainto_tsquery('simple', $1) q
+               WHERE s.transcript_tsv @@ q
+               ORDER BY rank DESC
+               LIMIT $2"#,
+        )
+        .bind(query)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| TranscriptMatchRecord {
+                course_id: row.get("course_id"),
+                lesson_id: row.get("lesson_id"),
+                language: row.get("language"),
+                rank: row.get("rank"),
+            })
+            .collect())
+    }
+}
+
+fn map_row(row: sqlx::postgres::PgRow) -> LessonSubtitleRecord {
+    LessonSubtitleRecord {
+        id: row.get("id"),
+        lesson_id: row.get("lesson_id"),
+        language: row.get("language"),
+        label: row.try_get("label").ok(),
+        vtt: row.get("vtt"),
+        transcript: row.get("transcript"),
+        cue_count: row.get("cue_count"),
+        created_at: row.get("created_at"),
+        updated_at: row.try_get("updated_at").ok(),
+    }
+}
@@ -0,0 +1,81 @@
+use sqlx::Row;
+
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::lesson_videos::{
+    LessonVideoRecord, LessonVideosRepository, UpsertLessonVideoRecord,
+};
+
+pub struct PostgresLessonVideosRepository {
+    pub pool: sqlx::Pool<sqlx::Postgres>,
+}
+
+#[async_trait::async_trait]
+impl LessonVideosRepository for PostgresLessonVideosRepository {
+    async fn upsert(&self, input: UpsertLessonVideoRecord) -> AppResult<LessonVideoRecord> {
+        let row = sqlx::query(
+            r#"INSERT INTO lesson_videos (lesson_id, provider, provider_video_id, status)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT (lesson_id)
+               DO UPDATE SET
+                   provider = EXCLUDED.provider,
+                   provider_video_id = EXCLUDED.provider_video_id,
+                   status = EXCLUDED.status,
+                   embed_url = NULL
+               RETURNING lesson_id, provider, provider_video_id, status, embed_url, created_at, updated_at"#,
+        )
+        .bind(input.lesson_id)
+        .bind(&input.provider)
+        .bind(&input.provider_video_id)
+        .bind(&input.status)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(map_row(row))
+    }
+
+    async fn find_by_lesson(&self, lesson_id: uuid::Uuid) -> AppResult<Option<LessonVideoRecord>> {
+        let row = sqlx::query(
+            r#"SELECT lesson_id, provider, provider_video_id, status, embed_url, created_at, updated_at
+               FROM lesson_videos WHERE lesson_id = $1"#,
+        )
+        .bind(lesson_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(row.map(map_row))
+    }
+
+    async fn update_status(
+        &self,
+        lesson_id: uuid::Uuid,
+        status: &str,
+        embed_url: Option<String>,
+    ) -> AppResult<Option<LessonVideoRecord>> {
+        let row = sqlx::query(
+            r#"UPDATE lesson_videos SET
+                    status = $1,
+                    embed_url = COALESCE($2, embed_url)
+               WHERE lesson_id = $3
+               RETURNING lesson_id, provider, provider_video_id, status, embed_url, created_at, updated_at"#,
+        )
+        .bind(status)
+        .bind(embed_url)
+        .bind(lesson_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(row.map(map_row))
+    }
+}
+
+fn map_row(row: sqlx::postgres::PgRow) -> LessonVideoRecord {
+    LessonVideoRecord {
+        lesson_id: row.get("lesson_id"),
+        provider: row.get("provider"),
+        provider_video_id: row.get("provider_video_id"),
+        status: row.get("status"),
+        embed_url: row.try_get("embed_url").ok(),
+        created_at: row.get("created_at"),
+        updated_at: row.try_get("updated_at").ok(),
+    }
+}
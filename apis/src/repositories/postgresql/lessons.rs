@@ -2,8 +2,10 @@ use sqlx::Row;
 
 use crate::pkg::error::{AppError, AppResult};
 use crate::repositories::lessons::{
-    CreateLessonRecord, LessonRecord, LessonsRepository, UpdateLessonRecord,
+    CreateLessonRecord, LessonAccessRecord, LessonRecord, LessonsRepository, ModuleAccessRecord,
+    UpdateLessonRecord,
 };
+use crate::repositories::modules::DripSchedule;
 
 pub struct PostgresLessonsRepository {
     pub pool: sqlx::Pool<sqlx::Postgres>,
@@ -108,6 +110,62 @@ impl LessonsRepository for PostgresLessonsRepository {
         Ok(())
     }
 
+    async fn find_access_context(&self, id: uuid::Uuid) -> AppResult<Option<LessonAccessRecord>> {
+        let row = sqlx::query(
+            r#"SELECT l.id AS lesson_id, l.module_id, m.course_id, c.instructor_id,
+                       l.is_free, l.published, m.drip_days, m.drip_at,
+                       c.status AS course_status
+               FROM lessons l
+               JOIN course_modules m ON m.id = l.module_id
+               JOIN courses c ON c.id = m.course_id
+               WHERE l.id = $1"#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(row.map(|row| LessonAccessRecord {
+            lesson_id: row.get("lesson_id"),
+            module_id: row.get("module_id"),
+            course_id: row.get("course_id"),
+            instructor_id: row.get("instructor_id"),
+            is_free: row.get("is_free"),
+            published: row.get("published"),
+            drip: DripSchedule {
+                days: row.get("drip_days"),
+                at: row.get("drip_at"),
+            },
+            course_status: row.get("course_status"),
+        }))
+    }
+
+    async fn find_module_access_context(
+        &self,
+        module_id: uuid::Uuid,
+    ) -> AppResult<Option<ModuleAccessRecord>> {
+        let row = sqlx::query(
+            r#"SELECT m.id, m.course_id, c.instructor_id, m.drip_days, m.drip_at,
+                      c.status AS course_status
+               FROM course_modules m
+               JOIN courses c ON c.id = m.course_id
+               WHERE m.id = $1"#,
+        )
+        .bind(module_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(row.map(|row| ModuleAccessRecord {
+            module_id: row.get("id"),
+            course_id: row.get("course_id"),
+            instructor_id: row.get("instructor_id"),
+            drip: DripSchedule {
+                days: row.get("drip_days"),
+                at: row.get("drip_at"),
+            },
+            course_status: row.get("course_status"),
+        }))
+    }
+
     async fn bulk_update_positions(
         &self,
         module_id: uuid::Uuid,
@@ -11,3 +11,17 @@ pub mod blog_posts;
 pub mod blog_tags;
 pub mod blog_likes;
 pub mod blog_comments;
+pub mod enrollments;
+pub mod lesson_videos;
+pub mod lesson_subtitles;
+pub mod search;
+pub mod course_revisions;
+pub mod course_reviews;
+pub mod lesson_progress;
+pub mod certificates;
+pub mod orders;
+pub mod payment_events;
+pub mod coupons;
+pub mod refund_requests;
+pub mod learning_paths;
+pub mod analytics;
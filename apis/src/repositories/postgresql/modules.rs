@@ -6,8 +6,8 @@ use crate::repositories::lesson_contents::LessonContentRecord;
 use crate::repositories::lesson_questions::{LessonQuestionRecord, QuestionOptionRecord};
 use crate::repositories::lessons::LessonRecord;
 use crate::repositories::modules::{
-    CreateLessonDeepData, CreateModuleDeepRecord, CreateModuleRecord, LessonDeepRecord,
-    ModuleDeepRecord, ModuleRecord, ModulesRepository, UpdateModuleRecord,
+    CreateLessonDeepData, CreateModuleDeepRecord, CreateModuleRecord, DripSchedule,
+    LessonDeepRecord, ModuleDeepRecord, ModuleRecord, ModulesRepository, UpdateModuleRecord,
 };
 
 pub struct PostgresModulesRepository {
@@ -34,7 +34,7 @@ impl ModulesRepository for PostgresModulesRepository {
 
     async fn find_by_id(&self, id: uuid::Uuid) -> AppResult<Option<ModuleRecord>> {
         let row = sqlx::query(
-            r#"SELECT id, course_id, title, description, position, created_at, updated_at
+            r#"SELECT id, course_id, title, description, position, drip_days, drip_at, created_at, updated_at
                FROM course_modules WHERE id = $1"#,
         )
         .bind(id)
@@ -46,7 +46,7 @@ impl ModulesRepository for PostgresModulesRepository {
 
     async fn list_by_course(&self, course_id: uuid::Uuid) -> AppResult<Vec<ModuleRecord>> {
         let rows = sqlx::query(
-            r#"SELECT id, course_id, title, description, position, created_at, updated_at
+            r#"SELECT id, course_id, title, description, position, drip_days, drip_at, created_at, updated_at
                FROM course_modules WHERE course_id = $1 ORDER BY position ASC, created_at ASC"#,
         )
         .bind(course_id)
@@ -67,7 +67,7 @@ impl ModulesRepository for PostgresModulesRepository {
                     description = COALESCE($2, description),
                     position = COALESCE($3, position)
                WHERE id = $4
-               RETURNING id, course_id, title, description, position, created_at, updated_at"#,
+               RETURNING id, course_id, title, description, position, drip_days, drip_at, created_at, updated_at"#,
         )
         .bind(input.title)
         .bind(input.description)
@@ -89,6 +89,25 @@ impl ModulesRepository for PostgresModulesRepository {
         Ok(())
     }
 
+    async fn set_drip(
+        &self,
+        id: uuid::Uuid,
+        drip: DripSchedule,
+    ) -> AppResult<Option<ModuleRecord>> {
+        let row = sqlx::query(
+            r#"UPDATE course_modules SET drip_days = $1, drip_at = $2, updated_at = NOW()
+               WHERE id = $3
+               RETURNING id, course_id, title, description, position, drip_days, drip_at, created_at, updated_at"#,
+        )
+        .bind(drip.days)
+        .bind(drip.at)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(row.map(map_module_row))
+    }
+
     async fn create_deep(&self, input: CreateModuleDeepRecord) -> AppResult<ModuleDeepRecord> {
         // Start a transaction so we can guarantee atomicity of the whole module graph
         let mut tx = self.pool.begin().await.map_err(AppError::from)?;
@@ -102,7 +121,7 @@ impl ModulesRepository for PostgresModulesRepository {
                     title = EXCLUDED.title,
                     description = EXCLUDED.description,
                     updated_at = NOW()
-                RETURNING id, created_at, updated_at"#,
+                RETURNING id, drip_days, drip_at, created_at, updated_at"#,
         )
         .bind(input.course_id)
         .bind(&input.title)
@@ -122,6 +141,10 @@ impl ModulesRepository for PostgresModulesRepository {
             title: input.title.clone(),
             description: input.description.clone(),
             position: input.position,
+            drip: DripSchedule {
+                days: module_row.get("drip_days"),
+                at: module_row.get("drip_at"),
+            },
             created_at: module_created_at,
             updated_at: module_updated_at,
         };
@@ -191,183 +214,9 @@ impl ModulesRepository for PostgresModulesRepository {
                 updated_at: lesson_updated_at,
             };
 
-            // contents
-            // Prune contents that are no longer present by position
-            let desired_content_positions: Vec<i32> =
-                lesson.contents.iter().map(|c| c.position).collect();
-            sqlx::query(
-                r#"DELETE FROM lesson_contents
-                   WHERE lesson_id = $1 AND NOT (position = ANY($2))"#,
-            )
-            .bind(lesson_id)
-            .bind(&desired_content_positions)
-            .execute(&mut **tx)
-            .await
-            .map_err(AppError::from)?;
-
-            let mut contents_created: Vec<LessonContentRecord> = Vec::new();
-            for c in &lesson.contents {
-                let row = sqlx::query(
-                    r#"INSERT INTO lesson_contents (lesson_id, title, content_type, url, file_size, filename, position)
-                       VALUES ($1,$2,$3,$4,$5,$6,$7) 
-                       ON CONFLICT (lesson_id, position) 
-                       DO UPDATE SET 
-                           title = EXCLUDED.title,
-                           content_type = EXCLUDED.content_type,
-                           url = EXCLUDED.url,
-                           file_size = EXCLUDED.file_size,
-                           filename = EXCLUDED.filename,
-                           updated_at = NOW()
-                       RETURNING id, created_at, updated_at"#,
-                )
-                .bind(lesson_id)
-                .bind(&c.title)
-                .bind(&c.content_type)
-                .bind(&c.url)
-                .bind(c.file_size)
-                .bind(&c.filename)
-                .bind(c.position)
-                .fetch_one(&mut **tx)
-                .await
-                .map_err(AppError::from)?;
-                contents_created.push(LessonContentRecord {
-                    id: row.get("id"),
-                    lesson_id,
-                    title: c.title.clone(),
-                    content_type: c.content_type.clone(),
-                    url: c.url.clone(),
-                    file_size: c.file_size,
-                    filename: c.filename.clone(),
-                    position: c.position,
-                    created_at: row.get("created_at"),
-                    updated_at: row.try_get("updated_at").ok(),
-                });
-            }
-
-            // questions and options
-            // Prune questions not present by position (options cascade)
-            let desired_question_positions: Vec<i32> =
-                lesson.questions.iter().map(|q| q.position).collect();
-            sqlx::query(
-                r#"DELETE FROM lesson_questions
-                   WHERE lesson_id = $1 AND NOT (position = ANY($2))"#,
-            )
-            .bind(lesson_id)
-            .bind(&desired_question_positions)
-            .execute(&mut **tx)
-            .await
-            .map_err(AppError::from)?;
-
-            let mut questions_created: Vec<(LessonQuestionRecord, Vec<QuestionOptionRecord>)> =
-                Vec::new();
-            for q in &lesson.questions {
-                let q_row = sqlx::query(
-                    r#"INSERT INTO lesson_questions (lesson_id, question_text, position)
-                       VALUES ($1,$2,$3) 
-                       ON CONFLICT (lesson_id, position) 
-                       DO UPDATE SET 
-                           question_text = EXCLUDED.question_text,
-                           updated_at = NOW()
-                       RETURNING id, created_at, updated_at"#,
-                )
-                .bind(lesson_id)
-                .bind(&q.question_text)
-                .bind(q.position)
-                .fetch_one(&mut **tx)
-                .await
-                .map_err(AppError::from)?;
-                let question_id: uuid::Uuid = q_row.get("id");
-                let question_rec = LessonQuestionRecord {
-                    id: question_id,
-                    lesson_id,
-                    question_text: q.question_text.clone(),
-                    position: q.position,
-                    created_at: q_row.get("created_at"),
-                    updated_at: q_row.try_get("updated_at").ok(),
-                };
-
-                // Prune options not present by position for this question
-                let desired_option_positions: Vec<i32> =
-                    q.options.iter().map(|o| o.position).collect();
-                sqlx::query(
-                    r#"DELETE FROM question_options
-                       WHERE question_id = $1 AND NOT (position = ANY($2))"#,
-                )
-                .bind(question_id)
-                .bind(&desired_option_positions)
-                .execute(&mut **tx)
-                .await
-                .map_err(AppError::from)?;
-
-                let mut option_records: Vec<QuestionOptionRecord> = Vec::new();
-                for o in &q.options {
-                    let row = sqlx::query(
-                        r#"INSERT INTO question_options (question_id, option_text, is_correct, position)
-                           VALUES ($1,$2,$3,$4) 
-                           ON CONFLICT (question_id, position) 
-                           DO UPDATE SET 
-                               option_text = EXCLUDED.option_text,
-                               is_correct = EXCLUDED.is_correct,
-                               updated_at = NOW()
-                           RETURNING id, created_at, updated_at"#,
-                    )
-                    .bind(question_id)
-                    .bind(&o.option_text)
-                    .bind(o.is_correct)
-                    .bind(o.position)
-                    .fetch_one(&mut **tx)
-                    .await
-                    .map_err(AppError::from)?;
-                    option_records.push(QuestionOptionRecord {
-                        id: row.get("id"),
-                        question_id,
-                        option_text: o.option_text.clone(),
-                        is_correct: o.is_correct,
-                        position: o.position,
-                        created_at: row.get("created_at"),
-                        updated_at: row.try_get("updated_at").ok(),
-                    });
-                }
-                questions_created.push((question_rec, option_records));
-            }
-
-            // optional assignment
-            let mut assignment_record: Option<LessonAssignmentRecord> = None;
-            if let Some(a) = &lesson.assignment {
-                let row = sqlx::query(
-                    r#"INSERT INTO lesson_assignments (lesson_id, title, description)
-                       VALUES ($1,$2,$3)
-                       ON CONFLICT (lesson_id) DO UPDATE SET title = EXCLUDED.title, description = EXCLUDED.description
-                       RETURNING title, description, created_at, updated_at"#,
-                )
-                .bind(lesson_id)
-                .bind(&a.title)
-                .bind(&a.description)
-                .fetch_one(&mut **tx)
-                .await
-                .map_err(AppError::from)?;
-                assignment_record = Some(LessonAssignmentRecord {
-                    lesson_id,
-                    title: row.get("title"),
-                    description: row.try_get("description").ok(),
-                    created_at: row.get("created_at"),
-                    updated_at: row.try_get("updated_at").ok(),
-                });
-            } else {
-                // Prune assignment if it exists but not provided in payload
-                sqlx::query(r#"DELETE FROM lesson_assignments WHERE lesson_id = $1"#)
-                    .bind(lesson_id)
-                    .execute(&mut **tx)
-                    .await
-                    .map_err(AppError::from)?;
-            }
-
-            Ok((
-                lesson_record,
-                contents_created,
-                questions_created,
-                assignment_record,
-            ))
+            let (contents, questions, assignment) =
+                upsert_lesson_children(tx, lesson_id, lesson).await?;
+            Ok((lesson_record, contents, questions, assignment))
         }
 
         let mut lessons_created: Vec<LessonDeepRecord> = Vec::new();
@@ -405,7 +254,7 @@ impl ModulesRepository for PostgresModulesRepository {
     async fn list_by_course_deep(&self, course_id: uuid::Uuid) -> AppResult<Vec<ModuleDeepRecord>> {
         // Get all modules for the course
         let module_rows = sqlx::query(
-            r#"SELECT id, course_id, title, description, position, created_at, updated_at
+            r#"SELECT id, course_id, title, description, position, drip_days, drip_at, created_at, updated_at
                FROM course_modules 
                WHERE course_id = $1 
                ORDER BY position, created_at"#,
@@ -454,7 +303,7 @@ impl ModulesRepository for PostgresModulesRepository {
 
                 // Get lesson contents
                 let content_rows = sqlx::query(
-                    r#"SELECT id, lesson_id, title, content_type, url, file_size, filename, position, created_at, updated_at
+                    r#"SELECT id, lesson_id, title, content_type, url, file_size, filename, position, is_private, created_at, updated_at
                        FROM lesson_contents 
                        WHERE lesson_id = $1 
                        ORDER BY position, created_at"#,
@@ -475,6 +324,7 @@ impl ModulesRepository for PostgresModulesRepository {
                         file_size: row.try_get("file_size").ok(),
                         filename: row.try_get("filename").ok(),
                         position: row.get("position"),
+                        is_private: row.get("is_private"),
                         created_at: row.get("created_at"),
                         updated_at: row.try_get("updated_at").ok(),
                     })
@@ -638,7 +488,7 @@ impl ModulesRepository for PostgresModulesRepository {
 
         // Fetch all updated modules
         let rows = sqlx::query(
-            r#"SELECT id, course_id, title, description, position, created_at, updated_at
+            r#"SELECT id, course_id, title, description, position, drip_days, drip_at, created_at, updated_at
                FROM course_modules 
                WHERE course_id = $1 
                ORDER BY position ASC, created_at ASC"#,
@@ -657,6 +507,210 @@ impl ModulesRepository for PostgresModulesRepository {
     }
 }
 
+/// Bring a lesson's contents, questions (with options) and assignment in line
+/// with `lesson`, matching existing rows by position and pruning the rest.
+///
+/// Private contents keep their stored object key; only their metadata changes.
+pub(crate) async fn upsert_lesson_children(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    lesson_id: uuid::Uuid,
+    lesson: &CreateLessonDeepData,
+) -> AppResult<(
+    Vec<LessonContentRecord>,
+    Vec<(LessonQuestionRecord, Vec<QuestionOptionRecord>)>,
+    Option<LessonAssignmentRecord>,
+)> {
+    // contents
+    // Prune contents that are no longer present by position
+    let desired_content_positions: Vec<i32> =
+        lesson.contents.iter().map(|c| c.position).collect();
+    sqlx::query(
+        r#"DELETE FROM lesson_contents
+           WHERE lesson_id = $1 AND NOT (position = ANY($2))"#,
+    )
+    .bind(lesson_id)
+    .bind(&desired_content_positions)
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::from)?;
+
+    let mut contents_created: Vec<LessonContentRecord> = Vec::new();
+    for c in &lesson.contents {
+        let row = sqlx::query(
+            r#"INSERT INTO lesson_contents (lesson_id, title, content_type, url, file_size, filename, position, is_private)
+               VALUES ($1,$2,$3,$4,$5,$6,$7,$8) 
+               ON CONFLICT (lesson_id, position) 
+               DO UPDATE SET 
+                   title = EXCLUDED.title,
+                   content_type = EXCLUDED.content_type,
+                   url = CASE WHEN lesson_contents.is_private THEN lesson_contents.url ELSE EXCLUDED.url END,
+                   file_size = EXCLUDED.file_size,
+                   filename = EXCLUDED.filename,
+                   updated_at = NOW()
+               RETURNING id, url, is_private, created_at, updated_at"#,
+        )
+        .bind(lesson_id)
+        .bind(&c.title)
+        .bind(&c.content_type)
+        .bind(&c.url)
+        .bind(c.file_size)
+        .bind(&c.filename)
+        .bind(c.position)
+        .bind(c.is_private)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(AppError::from)?;
+        contents_created.push(LessonContentRecord {
+            id: row.get("id"),
+            lesson_id,
+            title: c.title.clone(),
+            content_type: c.content_type.clone(),
+            url: row.get("url"),
+            file_size: c.file_size,
+            filename: c.filename.clone(),
+            position: c.position,
+            is_private: row.get("is_private"),
+            created_at: row.get("created_at"),
+            updated_at: row.try_get("updated_at").ok(),
+        });
+    }
+
+    let (questions_created, assignment_record) =
+        upsert_lesson_questions(tx, lesson_id, lesson).await?;
+    Ok((contents_created, questions_created, assignment_record))
+}
+
+/// Bring a lesson's questions (with options) and assignment in line with
+/// `lesson`, matching existing rows by position and pruning the rest.
+pub(crate) async fn upsert_lesson_questions(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    lesson_id: uuid::Uuid,
+    lesson: &CreateLessonDeepData,
+) -> AppResult<(
+    Vec<(LessonQuestionRecord, Vec<QuestionOptionRecord>)>,
+    Option<LessonAssignmentRecord>,
+)> {
+    // questions and options
+    // Prune questions not present by position (options cascade)
+    let desired_question_positions: Vec<i32> =
+        lesson.questions.iter().map(|q| q.position).collect();
+    sqlx::query(
+        r#"DELETE FROM lesson_questions
+           WHERE lesson_id = $1 AND NOT (position = ANY($2))"#,
+    )
+    .bind(lesson_id)
+    .bind(&desired_question_positions)
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::from)?;
+
+    let mut questions_created: Vec<(LessonQuestionRecord, Vec<QuestionOptionRecord>)> =
+        Vec::new();
+    for q in &lesson.questions {
+        let q_row = sqlx::query(
+            r#"INSERT INTO lesson_questions (lesson_id, question_text, position)
+               VALUES ($1,$2,$3) 
+               ON CONFLICT (lesson_id, position) 
+               DO UPDATE SET 
+                   question_text = EXCLUDED.question_text,
+                   updated_at = NOW()
+               RETURNING id, created_at, updated_at"#,
+        )
+        .bind(lesson_id)
+        .bind(&q.question_text)
+        .bind(q.position)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(AppError::from)?;
+        let question_id: uuid::Uuid = q_row.get("id");
+        let question_rec = LessonQuestionRecord {
+            id: question_id,
+            lesson_id,
+            question_text: q.question_text.clone(),
+            position: q.position,
+            created_at: q_row.get("created_at"),
+            updated_at: q_row.try_get("updated_at").ok(),
+        };
+
+        // Prune options not present by position for this question
+        let desired_option_positions: Vec<i32> =
+            q.options.iter().map(|o| o.position).collect();
+        sqlx::query(
+            r#"DELETE FROM question_options
+               WHERE question_id = $1 AND NOT (position = ANY($2))"#,
+        )
+        .bind(question_id)
+        .bind(&desired_option_positions)
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::from)?;
+
+        let mut option_records: Vec<QuestionOptionRecord> = Vec::new();
+        for o in &q.options {
+            let row = sqlx::query(
+                r#"INSERT INTO question_options (question_id, option_text, is_correct, position)
+                   VALUES ($1,$2,$3,$4) 
+                   ON CONFLICT (question_id, position) 
+                   DO UPDATE SET 
+                       option_text = EXCLUDED.option_text,
+                       is_correct = EXCLUDED.is_correct,
+                       updated_at = NOW()
+                   RETURNING id, created_at, updated_at"#,
+            )
+            .bind(question_id)
+            .bind(&o.option_text)
+            .bind(o.is_correct)
+            .bind(o.position)
+            .fetch_one(&mut **tx)
+            .await
+            .map_err(AppError::from)?;
+            option_records.push(QuestionOptionRecord {
+                id: row.get("id"),
+                question_id,
+                option_text: o.option_text.clone(),
+                is_correct: o.is_correct,
+                position: o.position,
+                created_at: row.get("created_at"),
+                updated_at: row.try_get("updated_at").ok(),
+            });
+        }
+        questions_created.push((question_rec, option_records));
+    }
+
+    // optional assignment
+    let mut assignment_record: Option<LessonAssignmentRecord> = None;
+    if let Some(a) = &lesson.assignment {
+        let row = sqlx::query(
+            r#"INSERT INTO lesson_assignments (lesson_id, title, description)
+               VALUES ($1,$2,$3)
+               ON CONFLICT (lesson_id) DO UPDATE SET title = EXCLUDED.title, description = EXCLUDED.description
+               RETURNING title, description, created_at, updated_at"#,
+        )
+        .bind(lesson_id)
+        .bind(&a.title)
+        .bind(&a.description)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(AppError::from)?;
+        assignment_record = Some(LessonAssignmentRecord {
+            lesson_id,
+            title: row.get("title"),
+            description: row.try_get("description").ok(),
+            created_at: row.get("created_at"),
+            updated_at: row.try_get("updated_at").ok(),
+        });
+    } else {
+        // Prune assignment if it exists but not provided in payload
+        sqlx::query(r#"DELETE FROM lesson_assignments WHERE lesson_id = $1"#)
+            .bind(lesson_id)
+            .execute(&mut **tx)
+            .await
+            .map_err(AppError::from)?;
+    }
+
+    Ok((questions_created, assignment_record))
+}
+
 fn map_module_row(row: sqlx::postgres::PgRow) -> ModuleRecord {
     ModuleRecord {
         id: row.get("id"),
@@ -664,6 +718,10 @@ fn map_module_row(row: sqlx::postgres::PgRow) -> ModuleRecord {
         title: row.get("title"),
         description: row.try_get("description").ok(),
         position: row.get("position"),
+        drip: DripSchedule {
+            days: row.get("drip_days"),
+            at: row.get("drip_at"),
+        },
         created_at: row.get("created_at"),
         updated_at: row.try_get("updated_at").ok(),
     }
@@ -0,0 +1,451 @@
+use std::collections::HashMap;
+
+use sqlx::Row;
+
+use crate::pkg::error::{AppError, AppResult};
+use crate::pkg::payments::PaymentStatus;
+use crate::repositories::postgresql::coupons::USAGE_SQL;
+use crate::repositories::orders::{
+    CreateOrderEventRecord, CreateOrderRecord, CreatePaymentAttemptRecord, OrderEventKind,
+    OrderEventRecord, OrderItemRecord, OrderRecord, OrderStatus, OrdersRepository,
+    PaymentAttemptRecord,
+};
+
+const ORDER_COLUMNS: &str = "id, user_id, status, currency, subtotal_cents, discount_cents, total_cents, coupon_code, learning_path_id, paid_at, refunded_at, created_at, updated_at";
+const ATTEMPT_COLUMNS: &str = "id, order_id, provider, provider_payment_id, status, amount_cents, currency, created_at, updated_at";
+
+pub struct PostgresOrdersRepository {
+    pub pool: sqlx::Pool<sqlx::Postgres>,
+}
+
+impl PostgresOrdersRepository {
+    /// Attach items to each order, keeping the orders' order
+    async fn with_items(&self, rows: Vec<sqlx::postgres::PgRow>) -> AppResult<Vec<OrderRecord>> {
+        let mut orders: Vec<OrderRecord> = rows.into_iter().map(map_order_row).collect();
+        if orders.is_empty() {
+            return Ok(orders);
+        }
+        let ids: Vec<uuid::Uuid> = orders.iter().map(|o| o.id).collect();
+        let rows = sqlx::query(
+            r#"SELECT id, order_id, course_id, course_title, unit_price_cents, discount_cents
+               FROM order_items WHERE order_id = ANY($1)
+               ORDER BY course_title, id"#,
+        )
+        .bind(&ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        let mut items: HashMap<uuid::Uuid, Vec<OrderItemRecord>> = HashMap::new();
+        for row in rows {
+            items
+                .entry(row.get("order_id"))
+                .or_default()
+                .push(OrderItemRecord {
+                    id: row.get("id"),
+                    course_id: row.get("course_id"),
+                    course_title: row.get("course_title"),
+                    unit_price_cents: row.get("unit_price_cents"),
+                    discount_cents: row.get("discount_cents"),
+                });
+        }
+        for order in &mut orders {
+            order.items = items.remove(&order.id).unwrap_or_default();
+        }
+        Ok(orders)
+    }
+
+    async fn with_items_one(
+        &self,
+        row: Option<sqlx::postgres::PgRow>,
+    ) -> AppResult<Option<OrderRecord>> {
+        match row {
+            Some(row) => Ok(self.with_items(vec![row]).await?.pop()),
+            None => Ok(None),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl OrdersRepository for PostgresOrdersRepository {
+    async fn create(&self, input: CreateOrderRecord) -> AppResult<OrderRecord> {
+        let subtotal: i64 = input.items.iter().map(|i| i.unit_price_cents).sum();
+        let discount: i64 = input.items.iter().map(|i| i.discount_cents).sum();
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        if let Some(coupon) = &input.coupon {
+            // Serialize redemptions of the coupon so concurrent checkouts
+            // cannot both take its last use
+            sqlx::query("SELECT id FROM coupons WHERE id = $1 FOR UPDATE")
+                .bind(coupon.coupon_id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(AppError::from)?;
+            let usage = sqlx::query(USAGE_SQL)
+                .bind(coupon.coupon_id)
+                .bind(input.user_id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(AppError::from)?;
+            let total: i64 = usage.get("total");
+            let by_user: i64 = usage.get("by_user");
+            if coupon.max_redemptions.is_some_and(|max| total >= max as i64) {
+                return Err(AppError::Conflict(format!(
+                    "Coupon {} has been used up",
+                    coupon.code
+                )));
+            }
+            if coupon
+                .max_redemptions_per_user
+                .is_some_and(|max| by_user >= max as i64)
+            {
+                return Err(AppError::Conflict(format!(
+                    "You have already used coupon {}",
+                    coupon.code
+                )));
+            }
+        }
+
+        let sql = format!(
+            r#"INSERT INTO orders
+                   (user_id, currency, subtotal_cents, discount_cents, total_cents, coupon_code,
+                    learning_path_id)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING {}"#,
+            ORDER_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(input.user_id)
+            .bind(&input.currency)
+            .bind(subtotal)
+            .bind(discount)
+            .bind(subtotal - discount)
+            .bind(input.coupon.as_ref().map(|c| c.code.as_str()))
+            .bind(input.learning_path_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+        let mut order = map_order_row(row);
+
+        for item in input.items {
+            let id: uuid::Uuid = sqlx::query_scalar(
+                r#"INSERT INTO order_items
+                       (order_id, course_id, course_title, unit_price_cents, discount_cents)
+                   VALUES ($1, $2, $3, $4, $5)
+                   RETURNING id"#,
+            )
+            .bind(order.id)
+            .bind(item.course_id)
+            .bind(&item.course_title)
+            .bind(item.unit_price_cents)
+            .bind(item.discount_cents)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+            order.items.push(OrderItemRecord {
+                id,
+                course_id: Some(item.course_id),
+                course_title: item.course_title,
+                unit_price_cents: item.unit_price_cents,
+                discount_cents: item.discount_cents,
+            });
+        }
+
+        if let Some(coupon) = &input.coupon {
+            sqlx::query(
+                r#"INSERT INTO coupon_redemptions (coupon_id, user_id, order_id, discount_cents)
+                   VALUES ($1, $2, $3, $4)"#,
+            )
+            .bind(coupon.coupon_id)
+            .bind(input.user_id)
+            .bind(order.id)
+            .bind(discount)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+        }
+
+        tx.commit().await.map_err(AppError::from)?;
+        Ok(order)
+    }
+
+    async fn find_by_id(&self, id: uuid::Uuid) -> AppResult<Option<OrderRecord>> {
+        let sql = format!("SELECT {} FROM orders WHERE id = $1", ORDER_COLUMNS);
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        self.with_items_one(row).await
+    }
+
+    async fn list_by_user(&self, user_id: uuid::Uuid) -> AppResult<Vec<OrderRecord>> {
+        let sql = format!(
+            "SELECT {} FROM orders WHERE user_id = $1 ORDER BY created_at DESC",
+            ORDER_COLUMNS
+        );
+        let rows = sqlx::query(&sql)
+            .bind(user_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        self.with_items(rows).await
+    }
+
+    async fn transition_status(
+        &self,
+        id: uuid::Uuid,
+        from: &[OrderStatus],
+        to: OrderStatus,
+    ) -> AppResult<Option<OrderRecord>> {
+        let from: Vec<&str> = from.iter().map(|s| s.as_str()).collect();
+        let sql = format!(
+            r#"UPDATE orders SET
+                    status = $3,
+                    paid_at = CASE WHEN $3 = 'paid' THEN NOW() ELSE paid_at END,
+                    refunded_at = CASE WHEN $3 = 'refunded' THEN NOW() ELSE refunded_at END
+                WHERE id = $1 AND status = ANY($2)
+                RETURNING {}"#,
+            ORDER_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .bind(&from)
+            .bind(to.as_str())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        self.with_items_one(row).await
+    }
+
+    async fn mark_paid(
+        &self,
+        id: uuid::Uuid,
+        from: &[OrderStatus],
+    ) -> AppResult<Option<OrderRecord>> {
+        let from: Vec<&str> = from.iter().map(|s| s.as_str()).collect();
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+        let row = sqlx::query(
+            r#"UPDATE orders SET status = 'paid', paid_at = NOW()
+               WHERE id = $1 AND status = ANY($2)
+               RETURNING user_id, learning_path_id"#,
+        )
+        .bind(id)
+        .bind(&from)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let user_id: uuid::Uuid = row.get("user_id");
+        let learning_path_id: Option<uuid::Uuid> = row.get("learning_path_id");
+
+        sqlx::query(
+            r#"INSERT INTO order_events (order_id, event)
+               VALUES ($1, $2)"#,
+        )
+        .bind(id)
+        .bind(OrderEventKind::Paid.as_str())
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+        sqlx::query(
+            r#"INSERT INTO enrollments (user_id, course_id)
+               SELECT $1, course_id FROM order_items
+               WHERE order_id = $2 AND course_id IS NOT NULL
+               ON CONFLICT (user_id, course_id)
+               DO UPDATE SET status = 'active', enrolled_at = NOW(), revoked_at = NULL"#,
+        )
+        .bind(user_id)
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+        if let Some(path_id) = learning_path_id {
+            sqlx::query(
+                r#"INSERT INTO learning_path_enrollments (user_id, path_id, order_id)
+                   VALUES ($1, $2, $3)
+                   ON CONFLICT (user_id, path_id)
+                   DO UPDATE SET status = 'active', enrolled_at = NOW(), revoked_at = NULL,
+                                 order_id = EXCLUDED.order_id"#,
+            )
+            .bind(user_id)
+            .bind(path_id)
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+        }
+        tx.commit().await.map_err(AppError::from)?;
+        self.find_by_id(id).await
+    }
+
+    async fn list_stale_pending(
+        &self,
+        created_before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<uuid::Uuid>> {
+        sqlx::query_scalar(
+            r#"SELECT id FROM orders
+               WHERE status = 'pending' AND created_at < $1
+               ORDER BY created_at
+               LIMIT $2"#,
+        )
+        .bind(created_before)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)
+    }
+
+    async fn create_payment_attempt(
+        &self,
+        input: CreatePaymentAttemptRecord,
+    ) -> AppResult<PaymentAttemptRecord> {
+        // Providers return the same intent for a retried order; keep one row
+        let sql = format!(
+            r#"INSERT INTO payment_attempts
+                   (order_id, provider, provider_payment_id, status, amount_cents, currency)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               ON CONFLICT (provider, provider_payment_id)
+               DO UPDATE SET status = EXCLUDED.status
+               RETURNING {}"#,
+            ATTEMPT_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(input.order_id)
+            .bind(&input.provider)
+            .bind(&input.provider_payment_id)
+            .bind(input.status.as_str())
+            .bind(input.amount_cents)
+            .bind(&input.currency)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(map_attempt_row(row))
+    }
+
+    async fn latest_payment_attempt(
+        &self,
+        order_id: uuid::Uuid,
+    ) -> AppResult<Option<PaymentAttemptRecord>> {
+        let sql = format!(
+            r#"SELECT {} FROM payment_attempts WHERE order_id = $1
+               ORDER BY created_at DESC LIMIT 1"#,
+            ATTEMPT_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(order_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(row.map(map_attempt_row))
+    }
+
+    async fn find_payment_attempt(
+        &self,
+        provider: &str,
+        provider_payment_id: &str,
+    ) -> AppResult<Option<PaymentAttemptRecord>> {
+        let sql = format!(
+            "SELECT {} FROM payment_attempts WHERE provider = $1 AND provider_payment_id = $2",
+            ATTEMPT_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(provider)
+            .bind(provider_payment_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(row.map(map_attempt_row))
+    }
+
+    async fn update_payment_attempt_status(
+        &self,
+        id: uuid::Uuid,
+        status: PaymentStatus,
+    ) -> AppResult<()> {
+        sqlx::query(
+            r#"UPDATE payment_attempts SET status = $2, updated_at = NOW()
+               WHERE id = $1 AND status <> $2"#,
+        )
+        .bind(id)
+        .bind(status.as_str())
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(())
+    }
+
+    async fn record_event(&self, input: CreateOrderEventRecord) -> AppResult<()> {
+        sqlx::query(
+            r#"INSERT INTO order_events (order_id, actor_id, event, note)
+               VALUES ($1, $2, $3, $4)"#,
+        )
+        .bind(input.order_id)
+        .bind(input.actor_id)
+        .bind(input.event.as_str())
+        .bind(&input.note)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(())
+    }
+
+    async fn list_events(&self, order_id: uuid::Uuid) -> AppResult<Vec<OrderEventRecord>> {
+        let rows = sqlx::query(
+            r#"SELECT id, order_id, actor_id, event, note, created_at
+               FROM order_events WHERE order_id = $1
+               ORDER BY created_at, id"#,
+        )
+        .bind(order_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| OrderEventRecord {
+                id: row.get("id"),
+                order_id: row.get("order_id"),
+                actor_id: row.get("actor_id"),
+                event: row.get("event"),
+                note: row.get("note"),
+                created_at: row.get("created_at"),
+            })
+            .collect())
+    }
+}
+
+fn map_order_row(row: sqlx::postgres::PgRow) -> OrderRecord {
+    OrderRecord {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        status: row.get("status"),
+        currency: row.get("currency"),
+        subtotal_cents: row.get("subtotal_cents"),
+        discount_cents: row.get("discount_cents"),
+        total_cents: row.get("total_cents"),
+        coupon_code: row.get("coupon_code"),
+        learning_path_id: row.get("learning_path_id"),
+        items: Vec::new(),
+        paid_at: row.get("paid_at"),
+        refunded_at: row.get("refunded_at"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+fn map_attempt_row(row: sqlx::postgres::PgRow) -> PaymentAttemptRecord {
+    PaymentAttemptRecord {
+        id: row.get("id"),
+        order_id: row.get("order_id"),
+        provider: row.get("provider"),
+        provider_payment_id: row.get("provider_payment_id"),
+        status: row.get("status"),
+        amount_cents: row.get("amount_cents"),
+        currency: row.get("currency"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
@@ -0,0 +1,55 @@
+use sqlx::Row;
+
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::payment_events::{
+    CreatePaymentEventRecord, PaymentEventRecord, PaymentEventsRepository,
+};
+
+pub struct PostgresPaymentEventsRepository {
+    pub pool: sqlx::Pool<sqlx::Postgres>,
+}
+
+#[async_trait::async_trait]
+impl PaymentEventsRepository for PostgresPaymentEventsRepository {
+    async fn record(&self, input: CreatePaymentEventRecord) -> AppResult<PaymentEventRecord> {
+        // The no-op update makes RETURNING yield the existing row on replays
+        let row = sqlx::query(
+            r#"INSERT INTO payment_events (provider, event_id, event_type, payload)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT (provider, event_id)
+               DO UPDATE SET provider = EXCLUDED.provider
+               RETURNING id, provider, event_id, event_type, payload, order_id,
+                         received_at, processed_at"#,
+        )
+        .bind(&input.provider)
+        .bind(&input.event_id)
+        .bind(&input.event_type)
+        .bind(&input.payload)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(PaymentEventRecord {
+            id: row.get("id"),
+            provider: row.get("provider"),
+            event_id: row.get("event_id"),
+            event_type: row.get("event_type"),
+            payload: row.get("payload"),
+            order_id: row.get("order_id"),
+            received_at: row.get("received_at"),
+            processed_at: row.get("processed_at"),
+        })
+    }
+
+    async fn mark_processed(&self, id: uuid::Uuid, order_id: Option<uuid::Uuid>) -> AppResult<()> {
+        sqlx::query(
+            r#"UPDATE payment_events SET processed_at = NOW(), order_id = $2
+               WHERE id = $1 AND processed_at IS NULL"#,
+        )
+        .bind(id)
+        .bind(order_id)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+use sqlx::Row;
+
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::refund_requests::{
+    CreateRefundRequestRecord, RefundRequestRecord, RefundRequestStatus, RefundRequestsRepository,
+    ReviewRefundRequestRecord,
+};
+
+const REFUND_COLUMNS: &str = "id, order_id, user_id, status, reason, amount_cents, currency, provider_refund_id, reviewed_by, reviewed_at, review_note, created_at, updated_at";
+
+pub struct PostgresRefundRequestsRepository {
+    pub pool: sqlx::Pool<sqlx::Postgres>,
+}
+
+#[async_trait::async_trait]
+impl RefundRequestsRepository for PostgresRefundRequestsRepository {
+    async fn create(&self, input: CreateRefundRequestRecord) -> AppResult<RefundRequestRecord> {
+        let sql = format!(
+            r#"INSERT INTO refund_requests (order_id, user_id, reason, amount_cents, currency)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING {}"#,
+            REFUND_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(input.order_id)
+            .bind(input.user_id)
+            .bind(&input.reason)
+            .bind(input.amount_cents)
+            .bind(&input.currency)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| match AppError::from(e) {
+                AppError::Conflict(_) => {
+                    AppError::Conflict("A refund for this order is already requested".into())
+                }
+                other => other,
+            })?;
+        Ok(map_refund_row(row))
+    }
+
+    async fn find_by_id(&self, id: uuid::Uuid) -> AppResult<Option<RefundRequestRecord>> {
+        let sql = format!(
+            "SELECT {} FROM refund_requests WHERE id = $1",
+            REFUND_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(row.map(map_refund_row))
+    }
+
+    async fn find_open_by_order(
+        &self,
+        order_id: uuid::Uuid,
+    ) -> AppResult<Option<RefundRequestRecord>> {
+        let sql = format!(
+            r#"SELECT {} FROM refund_requests
+               WHERE order_id = $1 AND status IN ('pending', 'failed')"#,
+            REFUND_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(order_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(row.map(map_refund_row))
+    }
+
+    async fn list(
+        &self,
+        status: Option<RefundRequestStatus>,
+        limit: i64,
+    ) -> AppResult<Vec<RefundRequestRecord>> {
+        let sql = format!(
+            r#"SELECT {} FROM refund_requests
+               WHERE ($1::text IS NULL OR status = $1)
+               ORDER BY created_at, id
+               LIMIT $2"#,
+            REFUND_COLUMNS
+        );
+        let rows = sqlx::query(&sql)
+            .bind(status.map(|s| s.as_str()))
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(rows.into_iter().map(map_refund_row).collect())
+    }
+
+    async fn transition_status(
+        &self,
+        id: uuid::Uuid,
+        from: &[RefundRequestStatus],
+        to: RefundRequestStatus,
+        review: ReviewRefundRequestRecord,
+    ) -> AppResult<Option<RefundRequestRecord>> {
+        let from: Vec<&str> = from.iter().map(|s| s.as_str()).collect();
+        let sql = format!(
+            r#"UPDATE refund_requests SET
+                    status = $3,
+                    reviewed_by = $4,
+                    reviewed_at = NOW(),
+                    review_note = $5,
+                    provider_refund_id = COALESCE($6, provider_refund_id)
+                WHERE id = $1 AND status = ANY($2)
+                RETURNING {}"#,
+            REFUND_COLUMNS
+        );
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .bind(&from)
+            .bind(to.as_str())
+            .bind(review.reviewed_by)
+            .bind(&review.note)
+            .bind(&review.provider_refund_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(row.map(map_refund_row))
+    }
+}
+
+fn map_refund_row(row: sqlx::postgres::PgRow) -> RefundRequestRecord {
+    RefundRequestRecord {
+        id: row.get("id"),
+        order_id: row.get("order_id"),
+        user_id: row.get("user_id"),
+        status: row.get("status"),
+        reason: row.get("reason"),
+        amount_cents: row.get("amount_cents"),
+        currency: row.get("currency"),
+        provider_refund_id: row.get("provider_refund_id"),
+        reviewed_by: row.get("reviewed_by"),
+        reviewed_at: row.get("reviewed_at"),
+        review_note: row.get("review_note"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
@@ -0,0 +1,159 @@
+use sqlx::Row;
+
+use crate::pkg::error::{AppError, AppResult};
+use crate::repositories::search::{
+    HIGHLIGHT_START, HIGHLIGHT_STOP, SearchHitRecord, SearchKind, SearchRepository,
+};
+
+pub struct PostgresSearchRepository {
+    pub pool: sqlx::Pool<sqlx::Postgres>,
+}
+
+/// Minimum `word_similarity` for a fuzzy title match
+const FUZZY_THRESHOLD: f32 = 0.4;
+
+/// Paid lesson bodies and transcripts never appear in snippets, only descriptions
+const COURSE_SEARCH_SQL: &str = r#"
+    SELECT c.id, c.title, c.slug, NULL::uuid AS course_id, NULL::text AS course_slug,
+           ts_headline('english', concat_ws(' ', c.excerpt, c.description), q, $3) AS snippet,
+           ts_rank_cd(c.search_tsv, q) AS rank, FALSE AS transcript_match
+    FROM courses c, websearch_to_tsquery('english', $1) q
+    WHERE c.status = 'published' AND c.search_tsv @@ q
+    ORDER BY rank DESC, c.published_at DESC NULLS LAST
+    LIMIT $2"#;
+
+const LESSON_SEARCH_SQL: &str = r#"
+    SELECT l.id, l.title, NULL::text AS slug, c.id AS course_id, c.slug AS course_slug,
+           CASE
+               WHEN l.search_tsv @@ q THEN ts_headline('english',
+                   CASE WHEN l.is_free THEN concat_ws(' ', l.description, l.content)
+                        ELSE coalesce(l.description, '') END, q, $3)
+               WHEN l.is_free THEN ts_headline('simple', t.transcript, tq, $3)
+               ELSE coalesce(l.description, '')
+           END AS snippet,
+           GREATEST(
+               CASE WHEN l.search_tsv @@ q THEN ts_rank_cd(l.search_tsv, q) ELSE 0 END,
+               coalesce(t.rank, 0) * 0.5
+           )::real AS rank,
+           NOT coalesce(l.search_tsv @@ q, FALSE) AS transcript_match
+    FROM lessons l
+    JOIN course_modules m ON m.id = l.module_id
+    JOIN courses c ON c.id = m.course_id
+    CROSS JOIN websearch_to_tsquery('english', $1) q
+    CROSS JOIN websearch_to_tsquery('simple', $1) tq
+    LEFT JOIN LATERAL (
+        SELECT s.transcript, ts_rank_cd(s.transcript_tsv, tq) AS rank
+        FROM lesson_subtitles s
+        WHERE s.lesson_id = l.id AND s.transcript_tsv @@ tq
+        ORDER BY rank DESC
+        LIMIT 1
+    ) t ON TRUE
+    WHERE l.published AND c.status = 'published'
+      AND (l.search_tsv @@ q OR t.rank IS NOT NULL)
+    ORDER BY rank DESC
+    LIMIT $2"#;
+
+const POST_SEARCH_SQL: &str = r#"
+    SELECT bp.id, bp.title, bp.slug, NULL::uuid AS course_id, NULL::text AS course_slug,
+           ts_headline('english', concat_ws(' ', bp.excerpt, bp.content), q, $3) AS snippet,
+           ts_rank_cd(bp.search_tsv, q) AS rank, FALSE AS transcript_match
+    FROM blog_posts bp, websearch_to_tsquery('english', $1) q
+    WHERE bp.published AND bp.search_tsv @@ q
+    ORDER BY rank DESC, bp.published_at DESC NULLS LAST
+    LIMIT $2"#;
+
+const COURSE_FUZZY_SQL: &str = r#"
+    SELECT c.id, c.title, c.slug, NULL::uuid AS course_id, NULL::text AS course_slug,
+           left(coalesce(c.excerpt, c.description), 200) AS snippet,
+           word_similarity($1, c.title) AS rank, FALSE AS transcript_match
+    FROM courses c
+    WHERE c.status = 'published' AND word_similarity($1, c.title) >= $3
+    ORDER BY rank DESC
+    LIMIT $2"#;
+
+const LESSON_FUZZY_SQL: &str = r#"
+    SELECT l.id, l.title, NULL::text AS slug, c.id AS course_id, c.slug AS course_slug,
+           left(coalesce(l.description, ''), 200) AS snippet,
+           word_similarity($1, l.title) AS rank, FALSE AS transcript_match
+    FROM lessons l
+    JOIN course_modules m ON m.id = l.module_id
+    JOIN courses c ON c.id = m.course_id
+    WHERE l.published AND c.status = 'published' AND word_similarity($1, l.title) >= $3
+    ORDER BY rank DESC
+    LIMIT $2"#;
+
+const POST_FUZZY_SQL: &str = r#"
+    SELECT bp.id, bp.title, bp.slug, NULL::uuid AS course_id, NULL::text AS course_slug,
+           left(coalesce(bp.excerpt, bp.content), 200) AS snippet,
+           word_similarity($1, bp.title) AS rank, FALSE AS transcript_match
+    FROM blog_posts bp
+    WHERE bp.published AND word_similarity($1, bp.title) >= $3
+    ORDER BY rank DESC
+    LIMIT $2"#;
+
+#[async_trait::async_trait]
+impl SearchRepository for PostgresSearchRepository {
+    async fn search(
+        &self,
+        kind: SearchKind,
+        query: &str,
+        limit: i64,
+    ) -> AppResult<Vec<SearchHitRecord>> {
+        let sql = match kind {
+            SearchKind::Course => COURSE_SEARCH_SQL,
+            SearchKind::Lesson => LESSON_SEARCH_SQL,
+            SearchKind::Post => POST_SEARCH_SQL,
+        };
+        let headline_options = format!(
+            "StartSel={}, StopSel={}, MaxWords=35, MinWords=12, MaxFragments=2, FragmentDelimiter=\" ... \"",
+            HIGHLIGHT_START, HIGHLIGHT_STOP
+        );
+        let rows = sqlx::query(sql)
+            .bind(query)
+            .bind(limit)
+            .bind(headline_options)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(rows.into_iter().map(|row| map_row(kind, row)).collect())
+    }
+
+    async fn search_fuzzy(
+        &self,
+        kind: SearchKind,
+        query: &str,
+        limit: i64,
+    ) -> AppResult<Vec<SearchHitRecord>> {
+        let sql = match kind {
+            SearchKind::Course => COURSE_FUZZY_SQL,
+            SearchKind::Lesson => LESSON_FUZZY_SQL,
+            SearchKind::Post => POST_FUZZY_SQL,
+        };
+        let rows = sqlx::query(sql)
+            .bind(query)
+            .bind(limit)
+            .bind(FUZZY_THRESHOLD)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(rows.into_iter().map(|row| map_row(kind, row)).collect())
+    }
+}
+
+fn map_row(kind: SearchKind, row: sqlx::postgres::PgRow) -> SearchHitRecord {
+    SearchHitRecord {
+        kind,
+        id: row.get("id"),
+        title: row.get("title"),
+        slug: row.try_get("slug").ok().flatten(),
+        course_id: row.try_get("course_id").ok().flatten(),
+        course_slug: row.try_get("course_slug").ok().flatten(),
+        snippet: row
+            .try_get::<Option<String>, _>("snippet")
+            .ok()
+            .flatten()
+            .unwrap_or_default(),
+        rank: row.get("rank"),
+        transcript_match: row.get("transcript_match"),
+    }
+}
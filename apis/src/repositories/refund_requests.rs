@@ -0,0 +1,100 @@
+use crate::pkg::error::AppResult;
+
+/// Review states of a refund request (`refund_requests.status`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefundRequestStatus {
+    /// Awaiting an admin's decision
+    Pending,
+    Rejected,
+    /// Money returned and access revoked
+    Refunded,
+    /// Approved, but the provider declined; can be approved again
+    Failed,
+}
+
+impl RefundRequestStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Rejected => "rejected",
+            Self::Refunded => "refunded",
+            Self::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(Self::Pending),
+            "rejected" => Some(Self::Rejected),
+            "refunded" => Some(Self::Refunded),
+            "failed" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RefundRequestRecord {
+    pub id: uuid::Uuid,
+    pub order_id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    pub status: String,
+    pub reason: String,
+    pub amount_cents: i64,
+    pub currency: String,
+    pub provider_refund_id: Option<String>,
+    pub reviewed_by: Option<uuid::Uuid>,
+    pub reviewed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub review_note: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateRefundRequestRecord {
+    pub order_id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    pub reason: String,
+    pub amount_cents: i64,
+    pub currency: String,
+}
+
+/// Outcome of reviewing a request
+#[derive(Debug, Clone)]
+pub struct ReviewRefundRequestRecord {
+    pub reviewed_by: uuid::Uuid,
+    pub note: Option<String>,
+    /// Kept when `None`
+    pub provider_refund_id: Option<String>,
+}
+
+#[async_trait::async_trait]
+pub trait RefundRequestsRepository: Send + Sync {
+    /// Fails with `Conflict` while the order has another open request
+    async fn create(&self, input: CreateRefundRequestRecord) -> AppResult<RefundRequestRecord>;
+
+    async fn find_by_id(&self, id: uuid::Uuid) -> AppResult<Option<RefundRequestRecord>>;
+
+    /// The order's pending or failed request, if any
+    async fn find_open_by_order(
+        &self,
+        order_id: uuid::Uuid,
+    ) -> AppResult<Option<RefundRequestRecord>>;
+
+    /// Requests, oldest first, optionally only those in `status`
+    async fn list(
+        &self,
+        status: Option<RefundRequestStatus>,
+        limit: i64,
+    ) -> AppResult<Vec<RefundRequestRecord>>;
+
+    /// Move the request to `to` only if its current status is one of `from`,
+    /// recording the review; `None` when it is in another state
+    async fn transition_status(
+        &self,
+        id: uuid::Uuid,
+        from: &[RefundRequestStatus],
+        to: RefundRequestStatus,
+        review: ReviewRefundRequestRecord,
+    ) -> AppResult<Option<RefundRequestRecord>>;
+}
@@ -0,0 +1,72 @@
+use crate::pkg::error::AppResult;
+
+/// Kind of document a search hit points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchKind {
+    Course,
+    Lesson,
+    Post,
+}
+
+impl SearchKind {
+    pub const ALL: [SearchKind; 3] = [SearchKind::Course, SearchKind::Lesson, SearchKind::Post];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchKind::Course => "course",
+            SearchKind::Lesson => "lesson",
+            SearchKind::Post => "post",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "course" | "courses" => Some(SearchKind::Course),
+            "lesson" | "lessons" => Some(SearchKind::Lesson),
+            "post" | "posts" => Some(SearchKind::Post),
+            _ => None,
+        }
+    }
+}
+
+/// One ranked match from the search index
+#[derive(Debug, Clone)]
+pub struct SearchHitRecord {
+    pub kind: SearchKind,
+    pub id: uuid::Uuid,
+    pub title: String,
+    /// Course or blog post slug
+    pub slug: Option<String>,
+    /// Owning course of a lesson hit
+    pub course_id: Option<uuid::Uuid>,
+    pub course_slug: Option<String>,
+    /// Excerpt with matches wrapped in [`HIGHLIGHT_START`]/[`HIGHLIGHT_STOP`]
+    pub snippet: String,
+    pub rank: f32,
+    /// The lesson matched through its subtitle transcript only
+    pub transcript_match: bool,
+}
+
+/// Control characters marking highlighted terms in `SearchHitRecord::snippet`;
+/// they cannot appear in stored text, so callers can escape the snippet safely
+pub const HIGHLIGHT_START: char = '\u{2}';
+pub const HIGHLIGHT_STOP: char = '\u{3}';
+
+#[async_trait::async_trait]
+pub trait SearchRepository: Send + Sync {
+    /// Full-text search over published documents of `kind`, best matches first
+    async fn search(
+        &self,
+        kind: SearchKind,
+        query: &str,
+        limit: i64,
+    ) -> AppResult<Vec<SearchHitRecord>>;
+
+    /// Trigram title similarity for queries with typos; no highlighting
+    async fn search_fuzzy(
+        &self,
+        kind: SearchKind,
+        query: &str,
+        limit: i64,
+    ) -> AppResult<Vec<SearchHitRecord>>;
+}
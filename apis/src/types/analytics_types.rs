@@ -0,0 +1,152 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::pkg::money::Money;
+
+/// Date range of an analytics report, in UTC days
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct AnalyticsQuery {
+    /// First day (default 29 days before `to`)
+    pub from: Option<NaiveDate>,
+    /// Last day (default today)
+    pub to: Option<NaiveDate>,
+}
+
+/// Totals of a report's date range; `views` is absent when the range holds no
+/// view snapshots
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct CourseAnalyticsTotals {
+    pub new_enrollments: i64,
+    pub revoked_enrollments: i64,
+    /// Active enrollments at the end of the range
+    pub active_enrollments: i64,
+    /// Certificates issued
+    pub completions: i64,
+    pub lessons_completed: i64,
+    pub quiz_attempts: i64,
+    pub views: Option<i64>,
+}
+
+/// A course's activity on one day
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CourseAnalyticsDay {
+    pub day: NaiveDate,
+    pub new_enrollments: i64,
+    pub revoked_enrollments: i64,
+    pub active_enrollments: i64,
+    pub completions: i64,
+    pub lessons_completed: i64,
+    pub quiz_attempts: i64,
+    /// Course views counted since the previous day's snapshot
+    pub views: Option<i64>,
+    /// The course's total view counter at the end of the day
+    pub view_count: Option<i64>,
+}
+
+/// Revenue in one currency over a report's range
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RevenueSummary {
+    pub orders: i64,
+    pub gross: Money,
+    pub refunds: i64,
+    pub refunded: Money,
+    /// Gross minus refunded
+    pub net: Money,
+}
+
+/// Revenue in one currency on one day
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RevenueDay {
+    pub day: NaiveDate,
+    pub orders: i64,
+    pub gross: Money,
+    pub refunded: Money,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CourseAnalytics {
+    pub course_id: Uuid,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// Last day rolled up; activity after it is not counted yet
+    pub rolled_up_through: Option<NaiveDate>,
+    pub totals: CourseAnalyticsTotals,
+    /// One entry per rolled-up day, oldest first
+    pub daily: Vec<CourseAnalyticsDay>,
+    /// One summary per currency the course sold in
+    pub revenue: Vec<RevenueSummary>,
+    pub revenue_daily: Vec<RevenueDay>,
+}
+
+/// How many learners reached a module, as of the end of a report's range
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ModuleFunnelStep {
+    pub module_id: Uuid,
+    pub title: String,
+    pub position: i32,
+    /// Learners who completed at least one of its lessons
+    pub learners_started: i64,
+    /// Learners who completed every published lesson
+    pub learners_completed: i64,
+    /// Share of the course's active learners who completed it, 0 to 1
+    pub completion_rate: f64,
+}
+
+/// A published lesson's engagement over a report's range
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LessonEngagement {
+    pub lesson_id: Uuid,
+    pub module_id: Uuid,
+    pub title: String,
+    /// Learners who completed it by the end of the range
+    pub learners_completed: i64,
+    /// Completions within the range
+    pub completions: i64,
+    /// Share of the previous lesson's learners who did not complete this one,
+    /// 0 to 1; absent for the first lesson
+    pub drop_off: Option<f64>,
+    pub quiz_attempts: i64,
+    /// Average quiz score in the range; absent without attempts
+    pub quiz_average_score: Option<f64>,
+    /// Share of quiz attempts that passed, 0 to 1
+    pub quiz_pass_rate: Option<f64>,
+}
+
+/// Module completion funnel and lesson drop-off of a course
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CurriculumAnalytics {
+    pub course_id: Uuid,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub rolled_up_through: Option<NaiveDate>,
+    /// Active enrollments at the end of the range
+    pub active_enrollments: i64,
+    /// Modules in course order
+    pub modules: Vec<ModuleFunnelStep>,
+    /// Published lessons in curriculum order
+    pub lessons: Vec<LessonEngagement>,
+}
+
+/// One of the instructor's courses over a report's range
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct InstructorCourseSummary {
+    pub course_id: Uuid,
+    pub title: String,
+    pub new_enrollments: i64,
+    pub completions: i64,
+    pub active_enrollments: i64,
+    pub views: Option<i64>,
+}
+
+/// Every course of the signed-in instructor
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct InstructorAnalytics {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub rolled_up_through: Option<NaiveDate>,
+    pub courses: Vec<InstructorCourseSummary>,
+    /// One summary per currency, across all courses
+    pub revenue: Vec<RevenueSummary>,
+}
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Completion certificate held by a learner
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct Certificate {
+    pub id: Uuid,
+    pub course_id: Option<Uuid>,
+    pub verification_code: String,
+    pub learner_name: String,
+    pub course_title: String,
+    pub instructor_name: String,
+    pub issued_at: chrono::DateTime<chrono::Utc>,
+    /// Public page confirming the certificate
+    pub verify_url: String,
+    pub pdf_url: String,
+}
+
+/// Public answer to "is this certificate genuine?"
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CertificateVerification {
+    pub valid: bool,
+    pub verification_code: String,
+    pub learner_name: String,
+    pub course_title: String,
+    pub instructor_name: String,
+    pub course_id: Option<Uuid>,
+    pub issued_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A learner's progress through a course's published lessons
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CourseProgress {
+    pub course_id: Uuid,
+    pub total_lessons: i64,
+    pub completed_lessons: i64,
+    /// 0-100
+    pub percent: f64,
+    pub completed_lesson_ids: Vec<Uuid>,
+    /// Lessons with a quiz, and how many of those quizzes are passed
+    pub quiz_lessons: i64,
+    pub passed_quiz_lessons: i64,
+    pub completed: bool,
+    pub certificate: Option<Certificate>,
+    /// Set while the enrollment is revoked, e.g. after a refund: progress is
+    /// kept as of this moment and cannot change
+    pub frozen_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct QuizAnswerInput {
+    pub question_id: Uuid,
+    /// Every option the learner selected
+    #[serde(default)]
+    pub option_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct SubmitQuizAttemptRequest {
+    #[validate(nested)]
+    pub answers: Vec<QuizAnswerInput>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct QuizAttemptResult {
+    pub id: Uuid,
+    pub lesson_id: Uuid,
+    pub correct: i32,
+    pub total: i32,
+    /// Percentage of questions answered correctly
+    pub score: f64,
+    pub pass_mark: f64,
+    pub passed: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub progress: CourseProgress,
+}
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::pkg::money::Money;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct Coupon {
+    pub id: Uuid,
+    pub code: String,
+    pub description: Option<String>,
+    /// `percent` or `fixed`
+    pub discount_type: String,
+    pub percent_off: Option<i32>,
+    /// Set for `fixed` coupons, which only apply to orders in its currency
+    pub amount_off: Option<Money>,
+    /// Only this course is discounted
+    pub course_id: Option<Uuid>,
+    /// Only courses in this category are discounted
+    pub category_id: Option<i32>,
+    pub starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Total uses allowed; unlimited when absent
+    pub max_redemptions: Option<i32>,
+    /// Uses allowed per learner; unlimited when absent
+    pub max_redemptions_per_user: Option<i32>,
+    pub active: bool,
+    /// Orders placed with the coupon that are paid, refunded or still awaiting
+    /// payment; canceled and expired orders give their use back
+    pub times_redeemed: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// New coupon; exactly one of `percent_off` and `amount_off` must match
+/// `discount_type`, and at most one of `course_id` and `category_id` is set
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct CreateCouponRequest {
+    /// Letters, digits, `-` and `_`; stored upper-case
+    #[validate(length(min = 3, max = 40))]
+    pub code: String,
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+    /// `percent` or `fixed`
+    pub discount_type: String,
+    #[validate(range(min = 1, max = 100))]
+    pub percent_off: Option<i32>,
+    #[validate(nested)]
+    pub amount_off: Option<Money>,
+    pub course_id: Option<Uuid>,
+    pub category_id: Option<i32>,
+    pub starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[validate(range(min = 1))]
+    pub max_redemptions: Option<i32>,
+    #[validate(range(min = 1))]
+    pub max_redemptions_per_user: Option<i32>,
+    /// Defaults to `true`
+    pub active: Option<bool>,
+}
+
+/// Changes to a coupon; the discount and its scope cannot be changed once
+/// created
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct UpdateCouponRequest {
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+    pub starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[validate(range(min = 1))]
+    pub max_redemptions: Option<i32>,
+    #[validate(range(min = 1))]
+    pub max_redemptions_per_user: Option<i32>,
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct CouponsQuery {
+    /// Maximum number of coupons (default 50, max 200)
+    pub limit: Option<i64>,
+}
+
+/// Courses the signed-in learner is about to buy, and the coupon to try
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct ValidateCouponRequest {
+    #[validate(length(min = 1, max = 40))]
+    pub code: String,
+    #[validate(length(min = 1, max = 20))]
+    pub course_ids: Vec<Uuid>,
+    /// ISO 4217 currency to pay in (default: store currency)
+    pub currency: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CouponPreviewItem {
+    pub course_id: Uuid,
+    pub course_title: String,
+    pub unit_price: Money,
+    /// Zero for courses the coupon does not apply to
+    pub discount: Money,
+}
+
+/// What checkout would charge with the coupon applied
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CouponPreview {
+    pub code: String,
+    pub description: Option<String>,
+    pub subtotal: Money,
+    pub discount: Money,
+    pub total: Money,
+    pub items: Vec<CouponPreviewItem>,
+}
@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use super::course_types::PageMeta;
+
+/// Learner who wrote a review
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CourseReviewAuthor {
+    pub id: Uuid,
+    pub username: String,
+    pub full_name: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
+/// Instructor reply to a review
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CourseReviewReply {
+    pub content: String,
+    pub replied_by: Option<Uuid>,
+    pub replied_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CourseReview {
+    pub id: Uuid,
+    pub course_id: Uuid,
+    /// 1-5 stars
+    pub rating: i16,
+    pub content: String,
+    /// `pending`, `approved` or `rejected`
+    pub status: String,
+    pub helpful_count: i64,
+    pub author: CourseReviewAuthor,
+    pub reply: Option<CourseReviewReply>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct CreateCourseReviewRequest {
+    #[validate(range(min = 1, max = 5))]
+    pub rating: i16,
+    #[serde(default)]
+    #[validate(length(max = 5000))]
+    pub content: String,
+}
+
+/// Edits send the review back to moderation
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct UpdateCourseReviewRequest {
+    #[validate(range(min = 1, max = 5))]
+    pub rating: Option<i16>,
+    #[validate(length(max = 5000))]
+    pub content: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct CourseReviewReplyRequest {
+    #[validate(length(min = 1, max = 5000))]
+    pub content: String,
+}
+
+/// Query for a course's public reviews
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct CourseReviewsQuery {
+    /// 1-based page index
+    #[serde(default)]
+    pub page: Option<i64>,
+    /// Items per page
+    #[serde(default)]
+    pub per_page: Option<i64>,
+    /// `newest` (default), `helpful`, `highest` or `lowest`
+    pub sort: Option<String>,
+}
+
+/// Number of approved reviews with a given star rating
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RatingBucket {
+    pub rating: i16,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CourseRatingSummary {
+    pub average: f64,
+    pub count: i32,
+    /// One bucket per star rating, 5 down to 1
+    pub distribution: Vec<RatingBucket>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CourseReviewPage {
+    pub summary: CourseRatingSummary,
+    pub items: Vec<CourseReview>,
+    pub meta: PageMeta,
+}
+
+/// Query for the moderation queue
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct PendingCourseReviewsQuery {
+    /// Maximum number of reviews (default 50, max 200)
+    pub limit: Option<i64>,
+}
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::pkg::money::Money;
+
+use super::course_types::{CreateLessonAssignmentInput, CreateLessonQuestionInput};
+
+/// Course fields staged in a revision
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct RevisionCourseFields {
+    #[validate(length(min = 1))]
+    pub title: String,
+    #[validate(length(min = 1))]
+    pub description: String,
+    pub excerpt: Option<String>,
+    #[validate(url)]
+    pub thumbnail: Option<String>,
+    /// Publishing makes its currency the course's base currency
+    #[validate(nested)]
+    pub price: Money,
+    /// In the same currency as `price`
+    #[validate(nested)]
+    pub original_price: Option<Money>,
+    #[validate(length(min = 1))]
+    pub duration: String,
+    #[serde(default)]
+    pub outcomes: Vec<String>,
+    pub category: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Lesson content in a revision tree; `id` links it to the live content it
+/// edits, `None` adds a new one.
+///
+/// Private files keep their live storage key: a private item must keep the
+/// `id`, `url` and lesson of the live content, and new items cannot be private.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct RevisionLessonContent {
+    pub id: Option<Uuid>,
+    #[validate(length(min = 1))]
+    pub title: String,
+    #[validate(length(min = 1))]
+    pub content_type: String,
+    #[validate(length(min = 1))]
+    pub url: String,
+    pub file_size: Option<i64>,
+    pub filename: Option<String>,
+    pub position: i32,
+    #[serde(default)]
+    pub is_private: bool,
+}
+
+/// Lesson in a revision tree; `id` links it to the live lesson it edits, `None` adds a new one
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct RevisionLesson {
+    pub id: Option<Uuid>,
+    #[validate(length(min = 1))]
+    pub title: String,
+    pub description: Option<String>,
+    pub content: Option<String>,
+    #[validate(url)]
+    pub video_url: Option<String>,
+    #[validate(length(min = 1))]
+    pub duration: String,
+    #[validate(range(min = 0))]
+    pub position: i32,
+    pub is_free: bool,
+    pub published: bool,
+    #[serde(default)]
+    #[validate(nested)]
+    pub contents: Vec<RevisionLessonContent>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub questions: Vec<CreateLessonQuestionInput>,
+    #[validate(nested)]
+    pub assignment: Option<CreateLessonAssignmentInput>,
+}
+
+/// Module in a revision tree; `id` links it to the live module it edits, `None` adds a new one
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct RevisionModule {
+    pub id: Option<Uuid>,
+    #[validate(length(min = 1))]
+    pub title: String,
+    pub description: Option<String>,
+    #[validate(range(min = 0))]
+    pub position: i32,
+    #[serde(default)]
+    #[validate(nested)]
+    pub lessons: Vec<RevisionLesson>,
+}
+
+/// Full editable copy of a course: its fields plus the module/lesson tree.
+/// Also the body of `PUT /api/courses/:id/revisions/:revision_id`.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CourseRevisionContent {
+    #[validate(nested)]
+    pub course: RevisionCourseFields,
+    #[validate(nested)]
+    pub modules: Vec<RevisionModule>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CourseRevision {
+    pub id: Uuid,
+    pub course_id: Uuid,
+    /// `draft`, `published` or `discarded`
+    pub status: String,
+    /// Live content version the draft was copied from
+    pub base_version: i32,
+    pub content: CourseRevisionContent,
+    pub created_by: Uuid,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// A course field whose draft value differs from the live one
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RevisionFieldChange {
+    pub field: String,
+    pub live: serde_json::Value,
+    pub draft: serde_json::Value,
+}
+
+/// A module or lesson added, removed or changed by the draft
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RevisionItemChange {
+    /// `added`, `removed` or `changed`
+    pub change: String,
+    /// Live id; `None` for items the draft adds
+    pub id: Option<Uuid>,
+    pub title: String,
+    /// Changed fields (only for `changed`)
+    pub fields: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CourseRevisionDiff {
+    pub revision_id: Uuid,
+    /// The live course changed since the draft was taken; publishing will be refused
+    pub stale: bool,
+    pub course: Vec<RevisionFieldChange>,
+    pub modules: Vec<RevisionItemChange>,
+    pub lessons: Vec<RevisionItemChange>,
+}
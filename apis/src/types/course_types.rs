@@ -3,6 +3,8 @@ use utoipa::ToSchema;
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::pkg::money::Money;
+
 #[derive(Debug, Deserialize, ToSchema, Validate)]
 pub struct CreateCourseRequest {
     #[validate(length(min = 1))]
@@ -14,12 +16,14 @@ pub struct CreateCourseRequest {
     pub excerpt: Option<String>,
     #[validate(url)]
     pub thumbnail: Option<String>,
-    /// Price must be non-negative.
-    #[validate(range(min = 0.0))]
-    pub price: f64,
-    /// Original price must be non-negative.
-    #[validate(range(min = 0.0))]
-    pub original_price: Option<f64>,
+    /// Price in minor units; its currency becomes the course's base currency.
+    /// Multipart forms send it as a decimal string with a separate `currency`
+    /// field, e.g. `price=19.99`, `currency=EUR`.
+    #[validate(nested)]
+    pub price: Money,
+    /// Price before discount, in the same currency as `price`.
+    #[validate(nested)]
+    pub original_price: Option<Money>,
 
     #[validate(length(min = 1))]
     pub duration: String,
@@ -40,13 +44,17 @@ pub struct Course {
     pub description: String,
     pub excerpt: Option<String>,
     pub thumbnail: Option<String>,
-    pub price: f64,
-    pub original_price: Option<f64>,
+    /// Price in the course's base currency
+    pub price: Money,
+    pub original_price: Option<Money>,
     pub duration: String,
     pub lessons: i32,
     pub students: i32,
     pub featured: bool,
     pub view_count: i32,
+    /// Average star rating of approved reviews (0 when there are none)
+    pub rating_average: f64,
+    pub rating_count: i32,
     pub status: String,
     pub outcomes: Option<Vec<String>>,
     pub category: Option<String>,
@@ -54,6 +62,8 @@ pub struct Course {
     pub instructor_id: Uuid,
     pub instructor: Option<Instructor>,
     pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Set while a draft waits for its scheduled publish time
+    pub scheduled_publish_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
@@ -79,6 +89,36 @@ pub struct PaginationQuery {
     pub per_page: Option<i64>,
 }
 
+/// Query for the public course catalogue
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct CourseCatalogQuery {
+    /// 1-based page index
+    #[serde(default)]
+    pub page: Option<i64>,
+    /// Items per page
+    #[serde(default)]
+    pub per_page: Option<i64>,
+    /// Category id (see `/api/categories`)
+    pub category_id: Option<i32>,
+    /// Comma-separated tags; a course matches if it has any of them
+    pub tags: Option<String>,
+    /// Currency for the price filters and price sort (defaults to the store
+    /// currency); courses are compared by their price in it
+    pub currency: Option<String>,
+    /// Minimum price in minor units of `currency`
+    pub min_price: Option<i64>,
+    /// Maximum price in minor units of `currency`
+    pub max_price: Option<i64>,
+    /// Only free courses
+    pub free: Option<bool>,
+    pub featured: Option<bool>,
+    pub instructor_id: Option<Uuid>,
+    /// Minimum average rating (0-5)
+    pub min_rating: Option<f64>,
+    /// `newest` (default), `popular`, `price_asc`, `price_desc` or `top_rated`
+    pub sort: Option<String>,
+}
+
 /// Pagination metadata
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct PageMeta {
@@ -100,6 +140,37 @@ where
     pub meta: PageMeta,
 }
 
+// ----- Price lists -----
+
+/// One price of a course
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, async_graphql::SimpleObject)]
+pub struct CoursePrice {
+    pub price: Money,
+    /// Price before discount, in the same currency
+    pub original_price: Option<Money>,
+    /// Whether this is the course's own price rather than a price-list entry
+    pub is_base: bool,
+}
+
+/// All currencies a course is sold in; the base price comes first
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, async_graphql::SimpleObject)]
+pub struct CoursePriceList {
+    pub course_id: Uuid,
+    pub base_currency: String,
+    pub prices: Vec<CoursePrice>,
+}
+
+/// Price of a course in the currency named in the path
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct SetCoursePriceRequest {
+    /// Minor units of the currency
+    #[validate(range(min = 0))]
+    pub amount: i64,
+    /// Price before discount, in minor units of the currency
+    #[validate(range(min = 0))]
+    pub original_amount: Option<i64>,
+}
+
 // ----- Categories -----
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -129,6 +200,10 @@ pub struct CourseModule {
     pub title: String,
     pub description: Option<String>,
     pub position: i32,
+    /// Lessons open this many days after the learner enrolled
+    pub drip_days: Option<i32>,
+    /// Lessons open on this date
+    pub drip_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
@@ -145,6 +220,12 @@ pub struct Lesson {
     pub position: i32,
     pub is_free: bool,
     pub published: bool,
+    /// Not released to the viewer yet; `content` and `video_url` are withheld
+    #[serde(default)]
+    pub locked: bool,
+    /// When a locked lesson opens, if it is known
+    #[serde(default)]
+    pub unlocks_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
@@ -156,8 +237,12 @@ pub struct UpdateCourseRequest {
     pub excerpt: Option<String>,
     #[validate(url)]
     pub thumbnail: Option<String>,
-    pub price: Option<f64>,
-    pub original_price: Option<f64>,
+    /// New price; a different currency changes the course's base currency
+    #[validate(nested)]
+    pub price: Option<Money>,
+    /// In the course's base currency after the update
+    #[validate(nested)]
+    pub original_price: Option<Money>,
     pub duration: Option<String>,
     pub lessons: Option<i32>,
     pub students: Option<i32>,
@@ -168,6 +253,54 @@ pub struct UpdateCourseRequest {
     pub tags: Option<Vec<String>>,
 }
 
+/// Body of `POST /api/courses/:id/schedule`
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct ScheduleCoursePublishRequest {
+    /// Future time at which the draft is published automatically
+    pub publish_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// How `POST /api/courses/:id/duplicate` treats uploaded files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateMediaMode {
+    /// The copy points at the source course's files
+    #[default]
+    Reference,
+    /// Uploaded files are copied so both courses can change them independently
+    Copy,
+}
+
+/// Body of `POST /api/courses/:id/duplicate`
+#[derive(Debug, Default, Deserialize, ToSchema, Validate)]
+pub struct DuplicateCourseRequest {
+    /// Title of the copy; defaults to the source title with a " (copy)" suffix
+    #[validate(length(min = 1))]
+    pub title: Option<String>,
+    /// Slug of the copy; a free `<source-slug>-copy[-n]` slug is picked when omitted
+    #[validate(length(min = 1))]
+    pub slug: Option<String>,
+    #[serde(default)]
+    pub media: DuplicateMediaMode,
+}
+
+/// Something a SCORM / Common Cartridge import skipped
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PackageImportNotice {
+    /// Identifier of the manifest item or resource concerned
+    pub item: Option<String>,
+    pub message: String,
+}
+
+/// Result of `POST /api/courses/import/package`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PackageImportResult {
+    pub course: Course,
+    /// `scorm_1.2`, `scorm_2004`, `common_cartridge` or `ims_content_package`
+    pub package_type: String,
+    pub log: Vec<PackageImportNotice>,
+}
+
 #[derive(Debug, Deserialize, ToSchema, Validate)]
 pub struct CreateModuleRequest {
     pub course_id: Uuid,
@@ -184,6 +317,30 @@ pub struct UpdateModuleRequest {
     pub position: Option<i32>,
 }
 
+/// Drip schedule of a module; sending neither field releases it at once
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct SetModuleDripRequest {
+    /// Open the lessons this many days after the learner enrolled
+    #[validate(range(min = 0, max = 3650))]
+    pub drip_days: Option<i32>,
+    /// Open the lessons on a fixed date
+    pub drip_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Course a learner must complete before another course's lessons open
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CoursePrerequisite {
+    pub course_id: Uuid,
+    pub slug: String,
+    pub title: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct SetCoursePrerequisitesRequest {
+    #[validate(length(max = 20))]
+    pub course_ids: Vec<Uuid>,
+}
+
 #[derive(Debug, Deserialize, ToSchema, Validate)]
 pub struct BulkUpdateModulePositionsRequest {
     pub course_id: Uuid,
@@ -237,6 +394,66 @@ pub struct UpdateLessonRequest {
     pub published: Option<bool>,
 }
 
+// ----- Lesson Videos -----
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct StartLessonVideoUploadRequest {
+    /// Display name on the hosting provider; defaults to the lesson title
+    pub name: Option<String>,
+    /// Exact size of the file that will be uploaded
+    #[validate(range(min = 1))]
+    pub size_bytes: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LessonVideoUpload {
+    pub provider: String,
+    pub video_id: String,
+    /// Resumable (tus) endpoint the client uploads the file to
+    pub upload_link: String,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LessonVideo {
+    pub lesson_id: Uuid,
+    pub provider: String,
+    pub video_id: String,
+    /// One of `uploading`, `processing`, `ready`, `failed`
+    pub status: String,
+    pub embed_url: Option<String>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+// ----- Lesson Subtitles -----
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LessonSubtitle {
+    pub id: Uuid,
+    pub lesson_id: Uuid,
+    /// BCP 47 language tag, e.g. `en` or `pt-BR`
+    pub language: String,
+    pub label: Option<String>,
+    pub cue_count: i32,
+    /// Where to fetch the track; append `?format=srt` for SubRip
+    pub url: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LessonTranscript {
+    pub lesson_id: Uuid,
+    pub language: String,
+    pub text: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SubtitleFormatQuery {
+    /// `vtt` (default) or `srt`
+    pub format: Option<String>,
+}
+
 // ----- Lesson Contents -----
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -245,10 +462,13 @@ pub struct LessonContent {
     pub lesson_id: Uuid,
     pub title: String,
     pub content_type: String,
+    /// Public URL, or for private contents the endpoint issuing a signed link
     pub url: String,
     pub file_size: Option<i64>,
     pub filename: Option<String>,
     pub position: i32,
+    /// Stored outside the public uploads tree; fetch via a signed download URL
+    pub is_private: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
@@ -277,6 +497,14 @@ pub struct UpdateLessonContentRequest {
     pub position: Option<i32>,
 }
 
+/// Short-lived link to a lesson content file
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LessonContentDownload {
+    pub url: String,
+    /// When the link stops working; `None` for public contents
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 // ----- Lesson Questions / Options -----
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -308,7 +536,10 @@ pub struct QuestionOption {
     pub id: Uuid,
     pub question_id: Uuid,
     pub option_text: String,
-    pub is_correct: bool,
+    /// Whether this option is a right answer; only shown to the course's
+    /// instructor and admins
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_correct: Option<bool>,
     pub position: i32,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::pkg::money::Money;
+use crate::types::course_types::Course;
+use crate::types::order_types::CheckoutResponse;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LearningPath {
+    pub id: Uuid,
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    pub excerpt: Option<String>,
+    pub thumbnail: Option<String>,
+    /// One of `draft`, `published` or `archived`
+    pub status: String,
+    /// Price of the whole path
+    pub price: Money,
+    pub course_count: i64,
+    pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Course to complete before enrolling in a path
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PathPrerequisite {
+    pub course_id: Uuid,
+    pub slug: String,
+    pub title: String,
+}
+
+/// A path with its courses in path order
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LearningPathDetail {
+    pub path: LearningPath,
+    pub courses: Vec<Course>,
+    pub prerequisites: Vec<PathPrerequisite>,
+    /// What the courses cost bought one by one, in the path's currency;
+    /// absent when one of them is not sold in it
+    pub separate_price: Option<Money>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct CreateLearningPathRequest {
+    #[validate(length(min = 1))]
+    pub slug: String,
+    #[validate(length(min = 1))]
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    pub excerpt: Option<String>,
+    #[validate(url)]
+    pub thumbnail: Option<String>,
+    /// Price of the whole path; the courses must be sold in its currency
+    #[validate(nested)]
+    pub price: Money,
+}
+
+/// Changes to a path; omitted fields are left as they are
+#[derive(Debug, Default, Deserialize, ToSchema, Validate)]
+pub struct UpdateLearningPathRequest {
+    #[validate(length(min = 1))]
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub excerpt: Option<String>,
+    #[validate(url)]
+    pub thumbnail: Option<String>,
+    #[validate(nested)]
+    pub price: Option<Money>,
+    /// `draft`, `published` or `archived`; a path is published once it has
+    /// courses and all of them are published
+    pub status: Option<String>,
+}
+
+/// Ordered course list, for a path's courses or its prerequisites
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct SetPathCoursesRequest {
+    #[validate(length(max = 50))]
+    pub course_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct LearningPathsQuery {
+    /// 1-based page index
+    pub page: Option<i64>,
+    /// Items per page
+    pub per_page: Option<i64>,
+    /// Admins only: `draft`, `published` (default), `archived` or `all`
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PathEnrollment {
+    pub path_id: Uuid,
+    /// One of `active` or `revoked`
+    pub status: String,
+    /// Order the path was bought with
+    pub order_id: Option<Uuid>,
+    pub enrolled_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PathEnrollmentResponse {
+    /// Present once the learner is enrolled; a paid order enrolls them when
+    /// the payment succeeds
+    pub enrollment: Option<PathEnrollment>,
+    /// Order for the courses the learner did not own yet, at the path's price
+    pub checkout: Option<CheckoutResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PathCourseProgress {
+    pub course_id: Uuid,
+    pub title: String,
+    pub enrolled: bool,
+    /// The learner holds the course's certificate
+    pub completed: bool,
+    pub total_lessons: i64,
+    pub completed_lessons: i64,
+    pub percent: f64,
+}
+
+/// A learner's progress through a path, derived from their course progress
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PathProgress {
+    pub path_id: Uuid,
+    pub enrolled: bool,
+    pub total_courses: i64,
+    pub completed_courses: i64,
+    pub total_lessons: i64,
+    pub completed_lessons: i64,
+    /// Share of the path's lessons completed, 0-100
+    pub percent: f64,
+    /// First course in path order that is not completed
+    pub next_course_id: Option<Uuid>,
+    /// In path order
+    pub courses: Vec<PathCourseProgress>,
+}
@@ -1,2 +1,10 @@
 pub mod course_types;
 pub mod users;
+pub mod search_types;
+pub mod course_revision_types;
+pub mod course_review_types;
+pub mod certificate_types;
+pub mod order_types;
+pub mod coupon_types;
+pub mod learning_path_types;
+pub mod analytics_types;
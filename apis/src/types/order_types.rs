@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::pkg::money::Money;
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct CheckoutRequest {
+    /// Courses to buy in one order
+    #[validate(length(min = 1, max = 20))]
+    pub course_ids: Vec<Uuid>,
+    /// ISO 4217 currency to pay in (default: store currency); every course
+    /// must be sold in it
+    pub currency: Option<String>,
+    /// Coupon to redeem; preview it with `POST /api/coupons/validate`
+    #[validate(length(min = 1, max = 40))]
+    pub coupon_code: Option<String>,
+}
+
+/// Course bought by an order, with its title and price as charged
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OrderItem {
+    pub course_id: Option<Uuid>,
+    pub course_title: String,
+    pub unit_price: Money,
+    /// Coupon or bundle discount on this course
+    pub discount: Money,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct Order {
+    pub id: Uuid,
+    /// One of `pending`, `paid`, `failed`, `canceled` or `refunded`
+    pub status: String,
+    pub subtotal: Money,
+    pub discount: Money,
+    pub total: Money,
+    /// Coupon redeemed by the order
+    pub coupon_code: Option<String>,
+    /// Learning path bought as a whole
+    pub learning_path_id: Option<Uuid>,
+    pub items: Vec<OrderItem>,
+    pub paid_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub refunded_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// What the browser needs to collect the payment with the provider
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PaymentDetails {
+    pub provider: String,
+    /// Provider-side payment intent id
+    pub payment_id: String,
+    /// Secret for confirming the payment client-side, e.g. with Stripe.js
+    pub client_secret: Option<String>,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CheckoutResponse {
+    pub order: Order,
+    /// Absent when the order is free and was fulfilled immediately
+    pub payment: Option<PaymentDetails>,
+}
+
+/// Acknowledgement returned to the payment provider
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WebhookReceipt {
+    pub event_id: String,
+    /// The event was processed before; nothing changed
+    pub duplicate: bool,
+    pub order_id: Option<Uuid>,
+    pub order_status: Option<String>,
+}
+
+/// Entry in an order's audit trail
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OrderEvent {
+    /// E.g. `created`, `paid`, `refund_requested`, `refunded` or
+    /// `enrollments_revoked`
+    pub event: String,
+    /// User who caused it; absent for the system and the payment provider
+    pub actor_id: Option<Uuid>,
+    pub note: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct CreateRefundRequest {
+    #[validate(length(min = 1, max = 2000))]
+    pub reason: String,
+}
+
+/// Admin decision on a refund request; rejections need a note
+#[derive(Debug, Default, Deserialize, ToSchema, Validate)]
+pub struct ReviewRefundRequest {
+    #[validate(length(min = 1, max = 2000))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct RefundRequestsQuery {
+    /// `pending` (default), `rejected`, `refunded`, `failed` or `all`
+    pub status: Option<String>,
+    /// Maximum number of requests (default 50, max 200)
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RefundRequest {
+    pub id: Uuid,
+    pub order_id: Uuid,
+    pub user_id: Uuid,
+    /// `pending`, `rejected`, `refunded` or `failed` (the provider declined;
+    /// it can be approved again)
+    pub status: String,
+    pub reason: String,
+    pub amount: Money,
+    pub provider_refund_id: Option<String>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub review_note: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
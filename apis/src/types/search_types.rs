@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Query for the unified search endpoint
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct SearchQuery {
+    /// Search text; supports quoted phrases, `or` and `-exclusions`
+    pub q: String,
+    /// Comma-separated result types: `course`, `lesson`, `post` (default: all)
+    pub types: Option<String>,
+    /// Maximum results (default 20, max 50)
+    pub limit: Option<i64>,
+}
+
+/// One ranked search result
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SearchResult {
+    /// `course`, `lesson` or `post`
+    pub kind: String,
+    pub id: Uuid,
+    pub title: String,
+    /// Course or blog post slug
+    pub slug: Option<String>,
+    /// Course a lesson belongs to
+    pub course_id: Option<Uuid>,
+    pub course_slug: Option<String>,
+    /// HTML-escaped excerpt with matched terms wrapped in `<mark>`
+    pub snippet: String,
+    pub rank: f32,
+    /// True when a lesson matched through its subtitle transcript only
+    pub transcript_match: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SearchResponse {
+    pub query: String,
+    /// True when no exact matches were found and results come from
+    /// typo-tolerant title matching
+    pub fuzzy: bool,
+    pub items: Vec<SearchResult>,
+}